@@ -0,0 +1,53 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use ruvector_gnn::{CompressionLevel, TensorCompress};
+
+fn bench_compression(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compression");
+    let compressor = TensorCompress::new();
+    let embedding: Vec<f32> = (0..768).map(|i| (i as f32 * 0.02).sin()).collect();
+
+    for (name, level) in [
+        ("half", CompressionLevel::Half { scale: 1.0 }),
+        (
+            "pq8",
+            CompressionLevel::PQ8 {
+                subvectors: 8,
+                centroids: 16,
+            },
+        ),
+        (
+            "pq4",
+            CompressionLevel::PQ4 {
+                subvectors: 8,
+                outlier_threshold: 3.0,
+            },
+        ),
+        ("binary", CompressionLevel::Binary { threshold: 0.0 }),
+    ] {
+        group.bench_with_input(
+            BenchmarkId::new("compress", name),
+            &level,
+            |bench, level| {
+                bench.iter(|| {
+                    compressor
+                        .compress_with_level(black_box(&embedding), level)
+                        .unwrap()
+                });
+            },
+        );
+
+        let compressed = compressor.compress_with_level(&embedding, &level).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("decompress", name),
+            &compressed,
+            |bench, compressed| {
+                bench.iter(|| compressor.decompress(black_box(compressed)).unwrap());
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_compression);
+criterion_main!(benches);
@@ -0,0 +1,29 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ruvector_core::distance::distance;
+use ruvector_core::types::DistanceMetric;
+
+fn bench_distance(c: &mut Criterion) {
+    let mut group = c.benchmark_group("distance");
+
+    for dim in [128, 384, 768, 1536] {
+        let a: Vec<f32> = (0..dim).map(|i| (i as f32 * 0.01).sin()).collect();
+        let b: Vec<f32> = (0..dim).map(|i| (i as f32 * 0.01).cos()).collect();
+        group.throughput(Throughput::Elements(dim as u64));
+
+        for (name, metric) in [
+            ("euclidean", DistanceMetric::Euclidean),
+            ("cosine", DistanceMetric::Cosine),
+            ("dot_product", DistanceMetric::DotProduct),
+            ("manhattan", DistanceMetric::Manhattan),
+        ] {
+            group.bench_with_input(BenchmarkId::new(name, dim), &dim, |bench, _| {
+                bench.iter(|| distance(black_box(&a), black_box(&b), metric));
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_distance);
+criterion_main!(benches);
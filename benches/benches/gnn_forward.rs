@@ -0,0 +1,35 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use ruvector_gnn::RuvectorLayer;
+
+fn bench_gnn_forward(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gnn_forward");
+
+    for neighbors in [8, 32] {
+        let dim = 128;
+        let layer = RuvectorLayer::new(dim, dim, 4, 0.0);
+        let node: Vec<f32> = (0..dim).map(|i| (i as f32 * 0.05).sin()).collect();
+        let neighbor_embeddings: Vec<Vec<f32>> = (0..neighbors)
+            .map(|n| (0..dim).map(|i| ((n + i) as f32 * 0.05).cos()).collect())
+            .collect();
+        let weights: Vec<f32> = (0..neighbors).map(|n| 1.0 / (n + 1) as f32).collect();
+
+        group.bench_with_input(
+            BenchmarkId::new("layer", neighbors),
+            &neighbors,
+            |bench, _| {
+                bench.iter(|| {
+                    layer.forward(
+                        black_box(&node),
+                        black_box(&neighbor_embeddings),
+                        black_box(&weights),
+                    )
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_gnn_forward);
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use ruvector_core::types::{DbOptions, DistanceMetric, HnswConfig, SearchQuery};
+use ruvector_core::{VectorDB, VectorEntry};
+
+const DIMENSIONS: usize = 128;
+
+fn bench_hnsw_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hnsw_search");
+
+    for size in [1_000, 10_000] {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = VectorDB::new(DbOptions {
+            dimensions: DIMENSIONS,
+            distance_metric: DistanceMetric::Cosine,
+            storage_path: temp_dir
+                .path()
+                .join("bench.db")
+                .to_string_lossy()
+                .to_string(),
+            hnsw_config: Some(HnswConfig::default()),
+            quantization: None,
+        })
+        .unwrap();
+
+        let entries: Vec<VectorEntry> = (0..size)
+            .map(|i| VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: (0..DIMENSIONS)
+                    .map(|j| ((i * 31 + j * 17) % 97) as f32 / 97.0)
+                    .collect(),
+                metadata: None,
+            })
+            .collect();
+        db.insert_batch(entries).unwrap();
+
+        let query: Vec<f32> = (0..DIMENSIONS).map(|j| (j % 13) as f32 / 13.0).collect();
+        for k in [10, 100] {
+            group.bench_with_input(
+                BenchmarkId::new(format!("k{}", k), size),
+                &k,
+                |bench, &k| {
+                    bench.iter(|| {
+                        db.search(SearchQuery {
+                            vector: black_box(query.clone()),
+                            k,
+                            filter: None,
+                            ef_search: None,
+                        })
+                        .unwrap()
+                    });
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_hnsw_search);
+criterion_main!(benches);
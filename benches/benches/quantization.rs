@@ -0,0 +1,56 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use ruvector_core::quantization::{
+    BinaryQuantized, ProductQuantized, QuantizedVector, ScalarQuantized,
+};
+
+fn bench_quantization(c: &mut Criterion) {
+    let mut group = c.benchmark_group("quantization");
+
+    for dim in [128, 768] {
+        let vector: Vec<f32> = (0..dim).map(|i| (i as f32 * 0.1).sin()).collect();
+        let other: Vec<f32> = (0..dim).map(|i| (i as f32 * 0.1).cos()).collect();
+
+        group.bench_with_input(BenchmarkId::new("scalar_encode", dim), &dim, |bench, _| {
+            bench.iter(|| ScalarQuantized::quantize(black_box(&vector)));
+        });
+        let (sa, sb) = (
+            ScalarQuantized::quantize(&vector),
+            ScalarQuantized::quantize(&other),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("scalar_distance", dim),
+            &dim,
+            |bench, _| {
+                bench.iter(|| sa.distance(black_box(&sb)));
+            },
+        );
+
+        group.bench_with_input(BenchmarkId::new("binary_encode", dim), &dim, |bench, _| {
+            bench.iter(|| BinaryQuantized::quantize(black_box(&vector)));
+        });
+        let (ba, bb) = (
+            BinaryQuantized::quantize(&vector),
+            BinaryQuantized::quantize(&other),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("binary_distance", dim),
+            &dim,
+            |bench, _| {
+                bench.iter(|| ba.distance(black_box(&bb)));
+            },
+        );
+
+        let training: Vec<Vec<f32>> = (0..256)
+            .map(|n| (0..dim).map(|i| ((n * i) as f32 * 0.01).sin()).collect())
+            .collect();
+        let pq = ProductQuantized::train(&training, 8, 16, 5).unwrap();
+        group.bench_with_input(BenchmarkId::new("pq_encode", dim), &dim, |bench, _| {
+            bench.iter(|| pq.encode(black_box(&vector)));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_quantization);
+criterion_main!(benches);
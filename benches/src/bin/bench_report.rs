@@ -0,0 +1,53 @@
+//! Run the criterion suite and write a JSON benchmark report
+//!
+//! ```text
+//! bench-report [--out FILE] [--no-run] [BENCH_FILTER]
+//! ```
+//!
+//! Without `--no-run`, `cargo bench -p ruvector-benches` runs first; the
+//! latest criterion results are then written as a `BenchReport` to FILE
+//! (stdout by default).
+
+use anyhow::{bail, Context, Result};
+use ruvector_benches::{criterion_dir, criterion_report};
+use std::process::Command;
+
+fn main() -> Result<()> {
+    let mut out = None;
+    let mut run = true;
+    let mut filter = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => out = Some(args.next().context("--out needs a file path")?),
+            "--no-run" => run = false,
+            _ if arg.starts_with("--") => bail!("Unknown option: {}", arg),
+            _ => filter = Some(arg),
+        }
+    }
+
+    if run {
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+        let mut command = Command::new(cargo);
+        command.args(["bench", "-p", "ruvector-benches", "--"]);
+        if let Some(filter) = &filter {
+            command.arg(filter);
+        }
+        let status = command.status().context("Failed to run cargo bench")?;
+        if !status.success() {
+            bail!("cargo bench failed: {}", status);
+        }
+    }
+
+    let report = criterion_report(&criterion_dir())?;
+    let json = serde_json::to_string_pretty(&report)?;
+    match out {
+        Some(path) => {
+            std::fs::write(&path, json).with_context(|| format!("Cannot write {}", path))?;
+            eprintln!("Wrote {} results to {}", report.records.len(), path);
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
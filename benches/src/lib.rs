@@ -0,0 +1,146 @@
+//! Cross-crate benchmark suite for Ruvector
+//!
+//! The criterion benches in `benches/` cover distance kernels, HNSW search,
+//! quantization, GNN forward passes and tensor compression. Each criterion
+//! group is named after its suite, so a run can be turned into a
+//! [`BenchReport`] — the same JSON `ruvector benchmark --json` writes:
+//!
+//! ```bash
+//! cargo run --release -p ruvector-benches --bin bench-report -- --out bench.json
+//! ```
+
+use anyhow::{Context, Result};
+use ruvector_core::{BenchRecord, BenchReport};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Benchmark identity criterion writes to `new/benchmark.json`
+#[derive(Debug, Deserialize)]
+struct CriterionBenchmark {
+    group_id: String,
+    function_id: Option<String>,
+    value_str: Option<String>,
+    throughput: Option<CriterionThroughput>,
+}
+
+#[derive(Debug, Deserialize)]
+enum CriterionThroughput {
+    Bytes(u64),
+    BytesDecimal(u64),
+    Elements(u64),
+}
+
+#[derive(Debug, Deserialize)]
+struct Estimate {
+    point_estimate: f64,
+}
+
+/// Timing estimates criterion writes to `new/estimates.json`
+#[derive(Debug, Deserialize)]
+struct CriterionEstimates {
+    mean: Estimate,
+    median: Estimate,
+    std_dev: Estimate,
+}
+
+/// Default criterion output directory for this workspace
+pub fn criterion_dir() -> PathBuf {
+    let target = std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("../target"));
+    target.join("criterion")
+}
+
+/// Read the latest result of every benchmark under a criterion directory
+pub fn collect_criterion(dir: &Path) -> Result<Vec<BenchRecord>> {
+    let mut records = Vec::new();
+    visit(dir, &mut records)?;
+    records.sort_by(|a, b| (&a.suite, &a.name).cmp(&(&b.suite, &b.name)));
+    Ok(records)
+}
+
+fn visit(dir: &Path, records: &mut Vec<BenchRecord>) -> Result<()> {
+    let latest = dir.join("new");
+    if latest.join("estimates.json").is_file() {
+        records.push(read_record(&latest)?);
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir).with_context(|| format!("Cannot read {}", dir.display()))? {
+        let path = entry?.path();
+        // "report" holds criterion's HTML, "base" and "change" older runs
+        if path.is_dir() && path.file_name().map_or(true, |n| n != "report") {
+            visit(&path, records)?;
+        }
+    }
+    Ok(())
+}
+
+fn read_record(dir: &Path) -> Result<BenchRecord> {
+    let read = |name: &str| -> Result<String> {
+        let path = dir.join(name);
+        fs::read_to_string(&path).with_context(|| format!("Cannot read {}", path.display()))
+    };
+    let benchmark: CriterionBenchmark = serde_json::from_str(&read("benchmark.json")?)?;
+    let estimates: CriterionEstimates = serde_json::from_str(&read("estimates.json")?)?;
+
+    let name = [benchmark.function_id, benchmark.value_str]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("/");
+    let elements = match benchmark.throughput {
+        Some(CriterionThroughput::Elements(n))
+        | Some(CriterionThroughput::Bytes(n))
+        | Some(CriterionThroughput::BytesDecimal(n)) => n as f64,
+        None => 1.0,
+    };
+    let mean = estimates.mean.point_estimate;
+
+    Ok(BenchRecord {
+        suite: benchmark.group_id,
+        name,
+        mean_ns: mean,
+        median_ns: estimates.median.point_estimate,
+        std_dev_ns: estimates.std_dev.point_estimate,
+        throughput_per_sec: (mean > 0.0).then(|| elements * 1e9 / mean),
+    })
+}
+
+/// Build a report from the latest criterion results
+pub fn criterion_report(dir: &Path) -> Result<BenchReport> {
+    Ok(BenchReport::new("criterion", collect_criterion(dir)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_criterion() {
+        let dir = tempfile::tempdir().unwrap();
+        let latest = dir.path().join("distance/cosine/384/new");
+        fs::create_dir_all(&latest).unwrap();
+        fs::create_dir_all(dir.path().join("distance/report")).unwrap();
+        fs::write(
+            latest.join("benchmark.json"),
+            r#"{"group_id":"distance","function_id":"cosine","value_str":"384",
+                "throughput":{"Elements":1000},"full_id":"distance/cosine/384",
+                "directory_name":"distance/cosine/384"}"#,
+        )
+        .unwrap();
+        fs::write(
+            latest.join("estimates.json"),
+            r#"{"mean":{"point_estimate":500.0},"median":{"point_estimate":480.0},
+                "std_dev":{"point_estimate":12.0}}"#,
+        )
+        .unwrap();
+
+        let records = collect_criterion(dir.path()).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].suite, "distance");
+        assert_eq!(records[0].name, "cosine/384");
+        assert_eq!(records[0].throughput_per_sec, Some(2e9));
+    }
+}
@@ -4,6 +4,7 @@
 //! - Async attention computation with tokio
 //! - Batch processing utilities
 //! - Parallel attention computation
+//! - Attention mode calibration
 
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
@@ -11,9 +12,13 @@ use ruvector_attention::{
     attention::ScaledDotProductAttention,
     sparse::{FlashAttention, LinearAttention, LocalGlobalAttention},
     hyperbolic::{HyperbolicAttention, HyperbolicAttentionConfig},
+    sdk::{
+        calibrate, AttentionDefaults, AttentionType as ModeType, CalibrationConfig,
+        CalibrationReport as RustCalibrationReport,
+    },
     traits::Attention,
 };
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 // ============================================================================
 // Batch Processing Configuration
@@ -528,3 +533,189 @@ pub async fn benchmark_attention(
 
     Ok(result)
 }
+
+// ============================================================================
+// Attention Mode Calibration
+// ============================================================================
+
+/// Options for attention mode calibration
+#[napi(object)]
+pub struct CalibrationOptions {
+    pub dim: u32,
+    /// Candidate counts to time each mode at (default [64, 1024])
+    pub candidate_counts: Option<Vec<u32>>,
+    /// Modes to compare: standard, flash, hyperbolic, moe, multi_head,
+    /// linear, local_global (default: the first four)
+    pub modes: Option<Vec<String>>,
+    /// Timed calls per mode and candidate count (default 10)
+    pub iterations: Option<u32>,
+}
+
+/// Timing of one mode at one candidate count
+#[napi(object)]
+pub struct ModeTiming {
+    pub mode: String,
+    pub num_candidates: u32,
+    pub mean_ns: f64,
+}
+
+/// Fastest mode at one candidate count
+#[napi(object)]
+pub struct BestMode {
+    pub num_candidates: u32,
+    pub mode: String,
+}
+
+/// Result of attention mode calibration
+#[napi(object)]
+pub struct CalibrationReport {
+    pub dim: u32,
+    pub measurements: Vec<ModeTiming>,
+    pub best_per_count: Vec<BestMode>,
+    /// Fastest mode overall, if any mode could be measured
+    pub selected: Option<String>,
+}
+
+impl From<&RustCalibrationReport> for CalibrationReport {
+    fn from(report: &RustCalibrationReport) -> Self {
+        Self {
+            dim: report.dim as u32,
+            measurements: report
+                .measurements
+                .iter()
+                .map(|m| ModeTiming {
+                    mode: m.mode.name().to_string(),
+                    num_candidates: m.num_candidates as u32,
+                    mean_ns: m.mean_ns,
+                })
+                .collect(),
+            best_per_count: report
+                .best_per_count
+                .iter()
+                .map(|(n, mode)| BestMode {
+                    num_candidates: *n as u32,
+                    mode: mode.name().to_string(),
+                })
+                .collect(),
+            selected: report.selected.as_ref().map(|m| m.name().to_string()),
+        }
+    }
+}
+
+fn parse_mode(name: &str) -> Result<ModeType> {
+    ModeType::from_name(name)
+        .ok_or_else(|| Error::from_reason(format!("Unknown attention mode: {}", name)))
+}
+
+fn calibration_config(options: CalibrationOptions) -> Result<CalibrationConfig> {
+    let mut config = CalibrationConfig {
+        dim: options.dim as usize,
+        ..Default::default()
+    };
+    if let Some(counts) = options.candidate_counts {
+        config.candidate_counts = counts.iter().map(|&c| c as usize).collect();
+    }
+    if let Some(modes) = options.modes {
+        config.modes = modes.iter().map(|m| parse_mode(m)).collect::<Result<_>>()?;
+    }
+    if let Some(iterations) = options.iterations {
+        config.iterations = iterations as usize;
+    }
+    Ok(config)
+}
+
+/// Time each attention mode on this machine and report the fastest
+#[napi]
+pub async fn calibrate_attention(options: CalibrationOptions) -> Result<CalibrationReport> {
+    let config = calibration_config(options)?;
+    let report = tokio::task::spawn_blocking(move || calibrate(&config))
+        .await
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok((&report).into())
+}
+
+/// Default attention mode per collection, chosen by calibration
+#[napi]
+pub struct AttentionSelector {
+    inner: Arc<Mutex<AttentionDefaults>>,
+}
+
+impl Default for AttentionSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[napi]
+impl AttentionSelector {
+    /// Create a selector with no collections
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(AttentionDefaults::new())),
+        }
+    }
+
+    /// Restore a selector saved with `toJson`
+    #[napi(factory)]
+    pub fn from_json(json: String) -> Result<Self> {
+        let defaults: AttentionDefaults = serde_json::from_str(&json)
+            .map_err(|e| Error::from_reason(format!("Invalid selector JSON: {}", e)))?;
+        Ok(Self {
+            inner: Arc::new(Mutex::new(defaults)),
+        })
+    }
+
+    /// Calibrate for `collection` and store the selected mode
+    #[napi]
+    pub async fn calibrate(
+        &self,
+        collection: String,
+        options: CalibrationOptions,
+    ) -> Result<CalibrationReport> {
+        let config = calibration_config(options)?;
+        let defaults = self.inner.clone();
+        let report = tokio::task::spawn_blocking(move || {
+            let report = calibrate(&config);
+            defaults
+                .lock()
+                .expect("Mutex poisoned")
+                .record(&collection, &report);
+            report
+        })
+        .await
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+        Ok((&report).into())
+    }
+
+    /// Set the mode for `collection`
+    #[napi]
+    pub fn set(&self, collection: String, mode: String) -> Result<()> {
+        let mode = parse_mode(&mode)?;
+        self.inner.lock().expect("Mutex poisoned").set(&collection, mode);
+        Ok(())
+    }
+
+    /// Mode for `collection`, if one was chosen
+    #[napi]
+    pub fn get(&self, collection: String) -> Option<String> {
+        self.inner
+            .lock()
+            .expect("Mutex poisoned")
+            .get(&collection)
+            .map(|m| m.name().to_string())
+    }
+
+    /// Forget the mode for `collection`
+    #[napi]
+    pub fn remove(&self, collection: String) -> bool {
+        self.inner.lock().expect("Mutex poisoned").remove(&collection).is_some()
+    }
+
+    /// Serialize the choices for persisting
+    #[napi]
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(&*self.inner.lock().expect("Mutex poisoned"))
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+}
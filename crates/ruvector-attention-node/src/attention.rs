@@ -290,6 +290,26 @@ impl FlashAttention {
         }
     }
 
+    /// Create a flash attention instance that picks its block size per call
+    /// from candidate count, dimension and the detected cache size
+    ///
+    /// # Arguments
+    /// * `dim` - Embedding dimension
+    #[napi(factory)]
+    pub fn auto(dim: u32) -> Self {
+        Self {
+            inner: RustFlash::auto(dim as usize),
+            dim_value: dim as usize,
+            block_size_value: 0,
+        }
+    }
+
+    /// Block size that will be used for a given number of candidates
+    #[napi]
+    pub fn block_size_for(&self, num_candidates: u32) -> u32 {
+        self.inner.block_size_for(num_candidates as usize) as u32
+    }
+
     /// Compute flash attention
     #[napi]
     pub fn compute(
@@ -316,13 +336,53 @@ impl FlashAttention {
         self.dim_value as u32
     }
 
-    /// Get the block size
+    /// Get the block size (0 when the block size is chosen automatically)
     #[napi(getter)]
     pub fn block_size(&self) -> u32 {
         self.block_size_value as u32
     }
 }
 
+/// Best measured block size for a candidate count
+#[napi(object)]
+pub struct FlashBlockSizeEntry {
+    pub num_candidates: u32,
+    pub block_size: u32,
+    pub mean_ns: f64,
+}
+
+/// Measure flash attention over a grid of candidate counts and block sizes,
+/// returning the fastest block size per candidate count on this machine
+#[napi]
+pub fn calibrate_flash_block_size(
+    dim: u32,
+    candidate_counts: Vec<u32>,
+    block_sizes: Vec<u32>,
+    iterations: u32,
+) -> Vec<FlashBlockSizeEntry> {
+    let counts: Vec<usize> = candidate_counts.iter().map(|&c| c as usize).collect();
+    let blocks: Vec<usize> = block_sizes.iter().map(|&b| b as usize).collect();
+    let (measurements, table) =
+        ruvector_attention::sparse::flash_tune::calibrate(dim as usize, &counts, &blocks, iterations as usize);
+
+    table
+        .entries
+        .iter()
+        .map(|&(n, b)| {
+            let mean_ns = measurements
+                .iter()
+                .find(|m| m.num_candidates == n && m.block_size == b)
+                .map(|m| m.mean_ns)
+                .unwrap_or(0.0);
+            FlashBlockSizeEntry {
+                num_candidates: n as u32,
+                block_size: b as u32,
+                mean_ns,
+            }
+        })
+        .collect()
+}
+
 /// Linear attention (Performer-style) with O(n) complexity
 #[napi]
 pub struct LinearAttention {
@@ -455,6 +515,28 @@ pub struct MoEConfig {
     pub expert_capacity: Option<f64>,
 }
 
+/// Per-expert routing statistics for a batch
+#[napi(object)]
+pub struct MoERoutingStats {
+    /// Number of times each expert was selected
+    pub expert_counts: Vec<u32>,
+    /// Fraction of selections routed to each expert
+    pub expert_load: Vec<f64>,
+    /// Auxiliary load-balancing loss (0 when perfectly balanced)
+    pub load_balance_loss: f64,
+    /// Number of experts that received no traffic
+    pub idle_experts: u32,
+    /// Busiest expert's load relative to a uniform split
+    pub max_load_ratio: f64,
+}
+
+/// Batch MoE result with routing statistics
+#[napi(object)]
+pub struct MoEBatchResult {
+    pub outputs: Vec<Float32Array>,
+    pub routing: MoERoutingStats,
+}
+
 /// Mixture of Experts attention
 #[napi]
 pub struct MoEAttention {
@@ -526,6 +608,37 @@ impl MoEAttention {
         self.config.dim
     }
 
+    /// Compute MoE attention for a batch of queries, returning per-expert
+    /// routing counts and the auxiliary load-balancing loss
+    #[napi]
+    pub fn compute_with_stats(
+        &self,
+        queries: Vec<Float32Array>,
+        keys: Vec<Float32Array>,
+        values: Vec<Float32Array>,
+    ) -> Result<MoEBatchResult> {
+        let queries_vec: Vec<Vec<f32>> = queries.into_iter().map(|q| q.to_vec()).collect();
+        let keys_vec: Vec<Vec<f32>> = keys.into_iter().map(|k| k.to_vec()).collect();
+        let values_vec: Vec<Vec<f32>> = values.into_iter().map(|v| v.to_vec()).collect();
+        let queries_refs: Vec<&[f32]> = queries_vec.iter().map(|q| q.as_slice()).collect();
+        let keys_refs: Vec<&[f32]> = keys_vec.iter().map(|k| k.as_slice()).collect();
+        let values_refs: Vec<&[f32]> = values_vec.iter().map(|v| v.as_slice()).collect();
+
+        let (outputs, stats) = self.inner.compute_with_stats(&queries_refs, &keys_refs, &values_refs)
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+
+        Ok(MoEBatchResult {
+            outputs: outputs.into_iter().map(Float32Array::new).collect(),
+            routing: MoERoutingStats {
+                idle_experts: stats.idle_experts() as u32,
+                max_load_ratio: stats.max_load_ratio() as f64,
+                expert_counts: stats.expert_counts.iter().map(|&c| c as u32).collect(),
+                expert_load: stats.expert_load.iter().map(|&l| l as f64).collect(),
+                load_balance_loss: stats.load_balance_loss as f64,
+            },
+        })
+    }
+
     /// Get the number of experts
     #[napi(getter)]
     pub fn num_experts(&self) -> u32 {
@@ -28,6 +28,8 @@ pub use attention::{
     LocalGlobalAttention,
     MoEAttention,
     MoEConfig,
+    MoERoutingStats,
+    MoEBatchResult,
     AttentionConfig,
 };
 
@@ -58,6 +60,9 @@ pub use async_ops::{
     AttentionType,
     StreamProcessor,
     BenchmarkResult,
+    CalibrationOptions,
+    CalibrationReport,
+    AttentionSelector,
 };
 
 // Re-export graph attention types
@@ -139,6 +139,33 @@ impl Attention for ScaledDotProductAttention {
     }
 }
 
+#[cfg(feature = "f16")]
+impl crate::precision::HalfAttention for ScaledDotProductAttention {
+    fn compute_f16(
+        &self,
+        query: &[f32],
+        keys: &crate::precision::HalfMatrix,
+        values: &crate::precision::HalfMatrix,
+    ) -> AttentionResult<Vec<f32>> {
+        use crate::precision::{add_scaled_f16, check_inputs, dot_f16};
+
+        check_inputs(self.dim, query, keys, values)?;
+
+        let scale = (self.dim as f32).sqrt();
+        let scores: Vec<f32> = (0..keys.len())
+            .map(|i| dot_f16(query, keys.row(i)) / scale)
+            .collect();
+        let weights = self.softmax(&scores);
+
+        let mut output = vec![0.0; values.dim()];
+        for (i, &weight) in weights.iter().enumerate() {
+            add_scaled_f16(&mut output, weight, values.row(i));
+        }
+
+        Ok(output)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
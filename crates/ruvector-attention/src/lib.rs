@@ -8,6 +8,7 @@
 //! - Graph attention for GNN applications
 //! - Geometric attention in hyperbolic spaces
 //! - Sparse attention patterns
+//! - Half-precision keys and values (`f16` feature)
 //!
 //! ## Features
 //!
@@ -51,6 +52,8 @@ pub mod moe;
 pub mod graph;
 pub mod training;
 pub mod sdk;
+#[cfg(feature = "f16")]
+pub mod precision;
 
 // Re-export main types
 pub use attention::{MultiHeadAttention, ScaledDotProductAttention};
@@ -70,13 +73,14 @@ pub use hyperbolic::{
 pub use sparse::{
     SparseMaskBuilder, AttentionMask,
     LocalGlobalAttention, LinearAttention, FlashAttention,
+    BlockSize, BlockSizeTable,
 };
 
 // MoE exports
 pub use moe::{
     MoEAttention, MoEConfig,
     Expert, ExpertType, StandardExpert, HyperbolicExpert, LinearExpert,
-    Router, LearnedRouter, TopKRouting,
+    Router, LearnedRouter, RoutingStats, TopKRouting,
 };
 
 // Graph attention exports
@@ -94,8 +98,12 @@ pub use training::{
     NegativeMiner, HardNegativeMiner, MiningStrategy,
 };
 
+// Half-precision exports
+#[cfg(feature = "f16")]
+pub use precision::{HalfAttention, HalfMatrix};
+
 // SDK exports
-pub use sdk::{AttentionBuilder, AttentionPipeline, presets};
+pub use sdk::{AttentionBuilder, AttentionPipeline, AttentionDefaults, CalibrationReport, presets};
 
 /// Library version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -7,5 +7,5 @@ pub mod router;
 pub mod moe_attention;
 
 pub use expert::{Expert, ExpertType, StandardExpert, HyperbolicExpert, LinearExpert};
-pub use router::{Router, LearnedRouter, TopKRouting};
+pub use router::{Router, LearnedRouter, RoutingStats, TopKRouting};
 pub use moe_attention::{MoEAttention, MoEConfig};
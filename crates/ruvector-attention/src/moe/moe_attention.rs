@@ -3,7 +3,7 @@
 use crate::error::{AttentionError, AttentionResult};
 use crate::traits::Attention;
 use super::expert::{Expert, StandardExpert, HyperbolicExpert, LinearExpert};
-use super::router::{Router, LearnedRouter, TopKRouting};
+use super::router::{Router, LearnedRouter, RoutingStats, TopKRouting};
 
 /// MoE configuration
 #[derive(Clone, Debug)]
@@ -113,6 +113,21 @@ impl MoEAttention {
         keys: &[&[f32]],
         values: &[&[f32]],
     ) -> AttentionResult<(Vec<Vec<f32>>, f32)> {
+        let (outputs, stats) = self.compute_with_stats(queries, keys, values)?;
+        Ok((outputs, stats.load_balance_loss))
+    }
+
+    /// Compute a batch and return per-expert routing statistics
+    ///
+    /// The returned [`RoutingStats`] include the auxiliary load-balancing loss,
+    /// so callers can both add it to the training objective and monitor for
+    /// expert collapse.
+    pub fn compute_with_stats(
+        &self,
+        queries: &[&[f32]],
+        keys: &[&[f32]],
+        values: &[&[f32]],
+    ) -> AttentionResult<(Vec<Vec<f32>>, RoutingStats)> {
         let mut outputs = Vec::with_capacity(queries.len());
         let mut routing_decisions = Vec::with_capacity(queries.len());
 
@@ -132,8 +147,7 @@ impl MoEAttention {
             outputs.push(output);
         }
 
-        let loss = self.router.load_balance_loss(&routing_decisions);
-        Ok((outputs, loss))
+        Ok((outputs, self.router.routing_stats(&routing_decisions)))
     }
 
     /// Get expert usage statistics
@@ -248,6 +262,36 @@ mod tests {
         assert!(loss >= 0.0);
     }
 
+    #[test]
+    fn test_moe_with_stats() {
+        let config = MoEConfig::builder()
+            .dim(32)
+            .num_experts(4)
+            .top_k(2)
+            .build();
+
+        let moe = MoEAttention::new(config);
+
+        let queries: Vec<Vec<f32>> = (0..6).map(|i| vec![0.1 * i as f32; 32]).collect();
+        let keys: Vec<Vec<f32>> = vec![vec![0.3; 32]; 5];
+        let values: Vec<Vec<f32>> = vec![vec![1.0; 32]; 5];
+
+        let query_refs: Vec<&[f32]> = queries.iter().map(|q| q.as_slice()).collect();
+        let keys_refs: Vec<&[f32]> = keys.iter().map(|k| k.as_slice()).collect();
+        let values_refs: Vec<&[f32]> = values.iter().map(|v| v.as_slice()).collect();
+
+        let (outputs, stats) = moe
+            .compute_with_stats(&query_refs, &keys_refs, &values_refs)
+            .unwrap();
+
+        assert_eq!(outputs.len(), 6);
+        assert_eq!(stats.num_tokens, 6);
+        assert_eq!(stats.expert_counts.len(), 4);
+        // top_k = 2 selections per query
+        assert_eq!(stats.expert_counts.iter().sum::<usize>(), 12);
+        assert!(stats.load_balance_loss >= 0.0);
+    }
+
     #[test]
     fn test_config_builder() {
         let config = MoEConfig::builder()
@@ -18,6 +18,41 @@ pub struct TopKRouting {
     pub selections: Vec<(usize, f32)>,
 }
 
+/// Per-expert routing statistics for a batch of routing decisions
+///
+/// Used to detect expert collapse during training: a healthy router spreads
+/// load across experts, while a collapsed one sends most tokens to one or two.
+#[derive(Clone, Debug, Default)]
+pub struct RoutingStats {
+    /// Number of times each expert was selected
+    pub expert_counts: Vec<usize>,
+    /// Fraction of all selections routed to each expert (sums to 1)
+    pub expert_load: Vec<f32>,
+    /// Sum of gate weights assigned to each expert
+    pub expert_weight: Vec<f32>,
+    /// Auxiliary load-balancing loss (CV-squared, 0 when perfectly balanced)
+    pub load_balance_loss: f32,
+    /// Number of routing decisions (queries) in the batch
+    pub num_tokens: usize,
+}
+
+impl RoutingStats {
+    /// Number of experts that received no traffic
+    pub fn idle_experts(&self) -> usize {
+        self.expert_counts.iter().filter(|&&c| c == 0).count()
+    }
+
+    /// Load of the busiest expert relative to a uniform split (1.0 = balanced)
+    pub fn max_load_ratio(&self) -> f32 {
+        let n = self.expert_load.len();
+        if n == 0 {
+            return 0.0;
+        }
+        let max = self.expert_load.iter().cloned().fold(0.0f32, f32::max);
+        max * n as f32
+    }
+}
+
 /// Learned router with softmax gating
 pub struct LearnedRouter {
     num_experts: usize,
@@ -100,6 +135,27 @@ impl LearnedRouter {
         self.num_experts as f32 * count_var
     }
 
+    /// Collect per-expert counts, load fractions and the auxiliary loss
+    pub fn routing_stats(&self, routing_decisions: &[TopKRouting]) -> RoutingStats {
+        let mut expert_counts = vec![0usize; self.num_experts];
+        let mut expert_weight = vec![0.0f32; self.num_experts];
+
+        for decision in routing_decisions {
+            for &(expert_idx, weight) in &decision.selections {
+                expert_counts[expert_idx] += 1;
+                expert_weight[expert_idx] += weight;
+            }
+        }
+
+        RoutingStats {
+            expert_counts,
+            expert_load: self.expert_statistics(routing_decisions),
+            expert_weight,
+            load_balance_loss: self.load_balance_loss(routing_decisions),
+            num_tokens: routing_decisions.len(),
+        }
+    }
+
     /// Update gate weights (for training)
     pub fn update_weights(&mut self, gradients: &[f32], learning_rate: f32) {
         for (w, g) in self.gate_weights.iter_mut().zip(gradients.iter()) {
@@ -207,4 +263,31 @@ mod tests {
         let sum: f32 = stats.iter().sum();
         assert!((sum - 1.0).abs() < 1e-5);
     }
+
+    #[test]
+    fn test_routing_stats_detects_collapse() {
+        let router = LearnedRouter::new(4, 32, 1);
+
+        let balanced: Vec<TopKRouting> = (0..8)
+            .map(|i| TopKRouting {
+                selections: vec![(i % 4, 1.0)],
+            })
+            .collect();
+        let collapsed: Vec<TopKRouting> = (0..8)
+            .map(|_| TopKRouting {
+                selections: vec![(0, 1.0)],
+            })
+            .collect();
+
+        let b = router.routing_stats(&balanced);
+        assert_eq!(b.expert_counts, vec![2, 2, 2, 2]);
+        assert_eq!(b.idle_experts(), 0);
+        assert!(b.load_balance_loss < 1e-6);
+
+        let c = router.routing_stats(&collapsed);
+        assert_eq!(c.expert_counts, vec![8, 0, 0, 0]);
+        assert_eq!(c.idle_experts(), 3);
+        assert!((c.max_load_ratio() - 4.0).abs() < 1e-5);
+        assert!(c.load_balance_loss > b.load_balance_loss);
+    }
 }
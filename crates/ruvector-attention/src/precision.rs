@@ -0,0 +1,200 @@
+//! Half-precision keys and values
+//!
+//! Attention over large candidate sets is bound by memory bandwidth, since
+//! every key and value is read once per query. [`HalfMatrix`] stores rows as
+//! IEEE f16, halving the bytes read. The [`HalfAttention`] backends widen
+//! each element to f32 as it is read, so dot products, softmax and the
+//! weighted sum of values are still accumulated in f32.
+
+use crate::error::{AttentionError, AttentionResult};
+
+pub use half::f16;
+
+/// Row-major matrix of f16 rows, e.g. the keys or values of a candidate set
+#[derive(Clone, Debug, Default)]
+pub struct HalfMatrix {
+    dim: usize,
+    data: Vec<f16>,
+}
+
+impl HalfMatrix {
+    /// Create an empty matrix of `dim`-wide rows
+    pub fn new(dim: usize) -> Self {
+        Self {
+            dim,
+            data: Vec::new(),
+        }
+    }
+
+    /// Convert f32 rows, which must all have the same length
+    pub fn from_rows(rows: &[&[f32]]) -> AttentionResult<Self> {
+        let mut matrix = Self::new(rows.first().map_or(0, |r| r.len()));
+        matrix.data.reserve(rows.len() * matrix.dim);
+        for row in rows {
+            matrix.push(row)?;
+        }
+        Ok(matrix)
+    }
+
+    /// Append a row, rounding it to f16
+    pub fn push(&mut self, row: &[f32]) -> AttentionResult<()> {
+        if row.len() != self.dim {
+            return Err(AttentionError::DimensionMismatch {
+                expected: self.dim,
+                actual: row.len(),
+            });
+        }
+        self.data.extend(row.iter().map(|&x| f16::from_f32(x)));
+        Ok(())
+    }
+
+    /// Row width
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        if self.dim == 0 {
+            0
+        } else {
+            self.data.len() / self.dim
+        }
+    }
+
+    /// Check if the matrix has no rows
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Row `i`
+    pub fn row(&self, i: usize) -> &[f16] {
+        &self.data[i * self.dim..(i + 1) * self.dim]
+    }
+
+    /// Bytes used by the rows
+    pub fn size_bytes(&self) -> usize {
+        self.data.len() * std::mem::size_of::<f16>()
+    }
+}
+
+/// Dot product of an f32 query with an f16 row, accumulated in f32
+pub fn dot_f16(query: &[f32], row: &[f16]) -> f32 {
+    query.iter().zip(row).map(|(&q, &k)| q * k.to_f32()).sum()
+}
+
+/// Add `weight` times an f16 row to an f32 accumulator
+pub fn add_scaled_f16(output: &mut [f32], weight: f32, row: &[f16]) {
+    for (o, &v) in output.iter_mut().zip(row) {
+        *o += weight * v.to_f32();
+    }
+}
+
+/// Attention backends that read f16 keys and values
+pub trait HalfAttention {
+    /// Compute attention over f16 keys and values with f32 accumulation
+    ///
+    /// Matches [`crate::traits::Attention::compute`] on the same inputs up
+    /// to f16 rounding of the keys and values.
+    fn compute_f16(
+        &self,
+        query: &[f32],
+        keys: &HalfMatrix,
+        values: &HalfMatrix,
+    ) -> AttentionResult<Vec<f32>>;
+}
+
+/// Check the shapes shared by every [`HalfAttention`] backend
+pub(crate) fn check_inputs(
+    dim: usize,
+    query: &[f32],
+    keys: &HalfMatrix,
+    values: &HalfMatrix,
+) -> AttentionResult<()> {
+    if query.len() != dim {
+        return Err(AttentionError::DimensionMismatch {
+            expected: dim,
+            actual: query.len(),
+        });
+    }
+    if keys.dim() != dim {
+        return Err(AttentionError::DimensionMismatch {
+            expected: dim,
+            actual: keys.dim(),
+        });
+    }
+    if keys.is_empty() || values.is_empty() {
+        return Err(AttentionError::EmptyInput("keys or values".to_string()));
+    }
+    if keys.len() != values.len() {
+        return Err(AttentionError::DimensionMismatch {
+            expected: keys.len(),
+            actual: values.len(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attention::ScaledDotProductAttention;
+    use crate::sparse::FlashAttention;
+    use crate::traits::Attention;
+
+    fn rows(n: usize, dim: usize, seed: usize) -> Vec<Vec<f32>> {
+        (0..n)
+            .map(|i| {
+                (0..dim)
+                    .map(|j| (((i * 31 + j * 7 + seed) % 23) as f32 - 11.0) * 0.07)
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn assert_close(half: &[f32], full: &[f32], tolerance: f32) {
+        assert_eq!(half.len(), full.len());
+        for (h, f) in half.iter().zip(full) {
+            assert!((h - f).abs() < tolerance, "f16: {}, f32: {}", h, f);
+        }
+    }
+
+    #[test]
+    fn test_half_matches_full_precision() {
+        let dim = 64;
+        let query = rows(1, dim, 3).remove(0);
+        let keys = rows(1000, dim, 0);
+        let values = rows(1000, dim, 5);
+        let keys_refs: Vec<&[f32]> = keys.iter().map(|k| k.as_slice()).collect();
+        let values_refs: Vec<&[f32]> = values.iter().map(|v| v.as_slice()).collect();
+        let half_keys = HalfMatrix::from_rows(&keys_refs).unwrap();
+        let half_values = HalfMatrix::from_rows(&values_refs).unwrap();
+        assert_eq!(half_keys.len(), 1000);
+        assert_eq!(half_keys.size_bytes(), 1000 * dim * 2);
+
+        let standard = ScaledDotProductAttention::new(dim);
+        let full = standard.compute(&query, &keys_refs, &values_refs).unwrap();
+        let half = standard
+            .compute_f16(&query, &half_keys, &half_values)
+            .unwrap();
+        assert_close(&half, &full, 1e-2);
+
+        let flash = FlashAttention::new(dim, 64);
+        let full = flash.compute(&query, &keys_refs, &values_refs).unwrap();
+        let half = flash.compute_f16(&query, &half_keys, &half_values).unwrap();
+        assert_close(&half, &full, 1e-2);
+    }
+
+    #[test]
+    fn test_half_rejects_bad_shapes() {
+        let mut keys = HalfMatrix::new(4);
+        assert!(keys.push(&[1.0; 3]).is_err());
+        keys.push(&[1.0; 4]).unwrap();
+        let values = HalfMatrix::new(4);
+
+        let attention = ScaledDotProductAttention::new(4);
+        assert!(attention.compute_f16(&[1.0; 4], &keys, &values).is_err());
+        assert!(attention.compute_f16(&[1.0; 3], &keys, &keys).is_err());
+        assert!(attention.compute_f16(&[1.0; 4], &keys, &keys).is_ok());
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::{traits::Attention, error::AttentionResult};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum AttentionType {
     ScaledDot,
     MultiHead,
@@ -13,6 +13,37 @@ pub enum AttentionType {
     MoE,
 }
 
+impl AttentionType {
+    /// Every attention type
+    pub const ALL: [AttentionType; 7] = [
+        AttentionType::ScaledDot,
+        AttentionType::MultiHead,
+        AttentionType::Flash,
+        AttentionType::Linear,
+        AttentionType::LocalGlobal,
+        AttentionType::Hyperbolic,
+        AttentionType::MoE,
+    ];
+
+    /// Lowercase name, as accepted by [`AttentionType::from_name`]
+    pub fn name(&self) -> &'static str {
+        match self {
+            AttentionType::ScaledDot => "standard",
+            AttentionType::MultiHead => "multi_head",
+            AttentionType::Flash => "flash",
+            AttentionType::Linear => "linear",
+            AttentionType::LocalGlobal => "local_global",
+            AttentionType::Hyperbolic => "hyperbolic",
+            AttentionType::MoE => "moe",
+        }
+    }
+
+    /// Parse a name returned by [`AttentionType::name`]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.name() == name)
+    }
+}
+
 pub struct AttentionBuilder {
     dim: usize,
     attention_type: AttentionType,
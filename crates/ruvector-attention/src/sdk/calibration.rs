@@ -0,0 +1,311 @@
+//! Attention backend calibration
+//!
+//! Which attention mode is fastest depends on the machine, the vector
+//! dimension and how many candidates are scored. [`calibrate`] times each
+//! enabled mode over a set of candidate counts and selects the one that is
+//! fastest overall; [`AttentionDefaults`] keeps the selection per collection
+//! so it can be persisted and reused instead of re-measured.
+
+use super::builder::AttentionType;
+use crate::attention::{MultiHeadAttention, ScaledDotProductAttention};
+use crate::hyperbolic::{HyperbolicAttention, HyperbolicAttentionConfig};
+use crate::moe::{MoEAttention, MoEConfig};
+use crate::sparse::{FlashAttention, LinearAttention, LocalGlobalAttention};
+use crate::traits::Attention;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Modes calibrated by default
+pub const DEFAULT_CALIBRATION_MODES: [AttentionType; 4] = [
+    AttentionType::ScaledDot,
+    AttentionType::Flash,
+    AttentionType::Hyperbolic,
+    AttentionType::MoE,
+];
+
+/// Create the attention backend for `mode` with default settings
+pub fn build_attention(mode: &AttentionType, dim: usize) -> Box<dyn Attention> {
+    match mode {
+        AttentionType::ScaledDot => Box::new(ScaledDotProductAttention::new(dim)),
+        AttentionType::MultiHead => {
+            let heads = (1..=8).rev().find(|h| dim % h == 0).unwrap_or(1);
+            Box::new(MultiHeadAttention::new(dim, heads))
+        }
+        AttentionType::Flash => Box::new(FlashAttention::auto(dim)),
+        AttentionType::Linear => Box::new(LinearAttention::new(dim, dim.max(1))),
+        AttentionType::LocalGlobal => Box::new(LocalGlobalAttention::new(dim, 128, 8)),
+        AttentionType::Hyperbolic => {
+            Box::new(HyperbolicAttention::new(HyperbolicAttentionConfig {
+                dim,
+                ..Default::default()
+            }))
+        }
+        AttentionType::MoE => Box::new(MoEAttention::new(MoEConfig::builder().dim(dim).build())),
+    }
+}
+
+/// What to measure in [`calibrate`]
+#[derive(Clone, Debug)]
+pub struct CalibrationConfig {
+    /// Vector dimension
+    pub dim: usize,
+    /// Candidate counts to time each mode at
+    pub candidate_counts: Vec<usize>,
+    /// Modes to compare
+    pub modes: Vec<AttentionType>,
+    /// Timed calls per mode and candidate count
+    pub iterations: usize,
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            dim: 128,
+            candidate_counts: vec![64, 1024],
+            modes: DEFAULT_CALIBRATION_MODES.to_vec(),
+            iterations: 10,
+        }
+    }
+}
+
+/// Timing for one (mode, candidate count) pair
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModeMeasurement {
+    pub mode: AttentionType,
+    pub num_candidates: usize,
+    /// Mean time per attention call in nanoseconds
+    pub mean_ns: f64,
+}
+
+/// Result of [`calibrate`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CalibrationReport {
+    pub dim: usize,
+    /// Every successful measurement; modes that failed are left out
+    pub measurements: Vec<ModeMeasurement>,
+    /// Fastest mode per candidate count, sorted by candidate count
+    pub best_per_count: Vec<(usize, AttentionType)>,
+    /// Mode with the lowest mean slowdown relative to the fastest mode
+    /// across all candidate counts
+    pub selected: Option<AttentionType>,
+}
+
+impl CalibrationReport {
+    /// Fastest measured mode for `num_candidates`
+    ///
+    /// Uses the entry with the largest candidate count not exceeding
+    /// `num_candidates`, or the first entry for smaller inputs.
+    pub fn best_for(&self, num_candidates: usize) -> Option<&AttentionType> {
+        self.best_per_count
+            .iter()
+            .rev()
+            .find(|(n, _)| *n <= num_candidates)
+            .or_else(|| self.best_per_count.first())
+            .map(|(_, mode)| mode)
+    }
+}
+
+/// Micro-benchmark each configured mode over each candidate count
+///
+/// Intended to be run once per machine and collection shape, with the
+/// selection kept in [`AttentionDefaults`].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn calibrate(config: &CalibrationConfig) -> CalibrationReport {
+    use std::time::Instant;
+
+    let dim = config.dim.max(1);
+    let iterations = config.iterations.max(1);
+    // Small coordinates keep every vector inside the Poincaré ball
+    let scale = 0.5 / (dim as f32).sqrt();
+    let query: Vec<f32> = (0..dim)
+        .map(|i| ((i % 7) as f32 / 6.0 - 0.5) * scale)
+        .collect();
+    let backends: Vec<(AttentionType, Box<dyn Attention>)> = config
+        .modes
+        .iter()
+        .map(|mode| (mode.clone(), build_attention(mode, dim)))
+        .collect();
+
+    let mut measurements = Vec::new();
+    let mut best_per_count = Vec::with_capacity(config.candidate_counts.len());
+    for &n in &config.candidate_counts {
+        let keys: Vec<Vec<f32>> = (0..n)
+            .map(|i| {
+                (0..dim)
+                    .map(|j| (((i * 31 + j) % 17) as f32 / 16.0 - 0.5) * scale)
+                    .collect()
+            })
+            .collect();
+        let keys_refs: Vec<&[f32]> = keys.iter().map(|k| k.as_slice()).collect();
+
+        let mut best: Option<(AttentionType, f64)> = None;
+        for (mode, attention) in &backends {
+            // Warm-up pass, which also skips modes that reject the input
+            if attention.compute(&query, &keys_refs, &keys_refs).is_err() {
+                continue;
+            }
+
+            let start = Instant::now();
+            for _ in 0..iterations {
+                let _ = attention.compute(&query, &keys_refs, &keys_refs);
+            }
+            let mean_ns = start.elapsed().as_nanos() as f64 / iterations as f64;
+
+            measurements.push(ModeMeasurement {
+                mode: mode.clone(),
+                num_candidates: n,
+                mean_ns,
+            });
+            if best.as_ref().map_or(true, |(_, t)| mean_ns < *t) {
+                best = Some((mode.clone(), mean_ns));
+            }
+        }
+
+        if let Some((mode, _)) = best {
+            best_per_count.push((n, mode));
+        }
+    }
+    best_per_count.sort_by_key(|(n, _)| *n);
+
+    let selected = select(&measurements, &config.modes);
+    CalibrationReport {
+        dim,
+        measurements,
+        best_per_count,
+        selected,
+    }
+}
+
+/// Mode with the lowest mean ratio to the fastest time per candidate count,
+/// among modes measured at every count
+fn select(measurements: &[ModeMeasurement], modes: &[AttentionType]) -> Option<AttentionType> {
+    let mut fastest: HashMap<usize, f64> = HashMap::new();
+    for m in measurements {
+        let t = fastest.entry(m.num_candidates).or_insert(f64::INFINITY);
+        *t = t.min(m.mean_ns);
+    }
+
+    modes
+        .iter()
+        .filter_map(|mode| {
+            let ratios: Vec<f64> = measurements
+                .iter()
+                .filter(|m| &m.mode == mode)
+                .map(|m| m.mean_ns / fastest[&m.num_candidates].max(1.0))
+                .collect();
+            if ratios.len() < fastest.len() || ratios.is_empty() {
+                return None;
+            }
+            Some((mode, ratios.iter().sum::<f64>() / ratios.len() as f64))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(mode, _)| mode.clone())
+}
+
+/// Default attention mode per collection
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AttentionDefaults {
+    modes: HashMap<String, AttentionType>,
+}
+
+impl AttentionDefaults {
+    /// Create an empty set of defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the mode for `collection`
+    pub fn set(&mut self, collection: &str, mode: AttentionType) {
+        self.modes.insert(collection.to_string(), mode);
+    }
+
+    /// Store the mode selected by `report` for `collection`
+    ///
+    /// Returns the stored mode, or `None` (leaving any previous choice) when
+    /// the report selected nothing.
+    pub fn record(
+        &mut self,
+        collection: &str,
+        report: &CalibrationReport,
+    ) -> Option<&AttentionType> {
+        let mode = report.selected.clone()?;
+        self.set(collection, mode);
+        self.modes.get(collection)
+    }
+
+    /// Mode for `collection`
+    pub fn get(&self, collection: &str) -> Option<&AttentionType> {
+        self.modes.get(collection)
+    }
+
+    /// Forget the mode for `collection`
+    pub fn remove(&mut self, collection: &str) -> Option<AttentionType> {
+        self.modes.remove(collection)
+    }
+
+    /// Backend for `collection`, or `None` if no mode was chosen for it
+    pub fn build(&self, collection: &str, dim: usize) -> Option<Box<dyn Attention>> {
+        self.get(collection).map(|mode| build_attention(mode, dim))
+    }
+
+    /// Iterate over collections and their modes
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &AttentionType)> {
+        self.modes.iter().map(|(c, m)| (c.as_str(), m))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibrate_selects_mode() {
+        let config = CalibrationConfig {
+            dim: 16,
+            candidate_counts: vec![32, 8],
+            iterations: 2,
+            ..Default::default()
+        };
+        let report = calibrate(&config);
+        assert_eq!(report.measurements.len(), 8);
+        assert_eq!(report.best_per_count.len(), 2);
+        assert_eq!(report.best_per_count[0].0, 8);
+
+        let selected = report.selected.clone().unwrap();
+        assert!(DEFAULT_CALIBRATION_MODES.contains(&selected));
+        assert!(report.best_for(1000).is_some());
+
+        let mut defaults = AttentionDefaults::new();
+        assert_eq!(defaults.record("docs", &report), Some(&selected));
+        assert!(defaults.build("docs", 16).is_some());
+        assert!(defaults.build("other", 16).is_none());
+    }
+
+    #[test]
+    fn test_select_prefers_lowest_relative_time() {
+        let m = |mode, num_candidates, mean_ns| ModeMeasurement {
+            mode,
+            num_candidates,
+            mean_ns,
+        };
+        let measurements = [
+            m(AttentionType::ScaledDot, 10, 100.0),
+            m(AttentionType::Flash, 10, 150.0),
+            m(AttentionType::ScaledDot, 1000, 10_000.0),
+            m(AttentionType::Flash, 1000, 4_000.0),
+            // Only measured at one size
+            m(AttentionType::MoE, 10, 200.0),
+        ];
+        let modes = DEFAULT_CALIBRATION_MODES;
+        assert_eq!(select(&measurements, &modes), Some(AttentionType::Flash));
+        assert_eq!(select(&[], &modes), None);
+    }
+
+    #[test]
+    fn test_mode_names_round_trip() {
+        for mode in AttentionType::ALL {
+            assert_eq!(AttentionType::from_name(mode.name()), Some(mode));
+        }
+        assert_eq!(AttentionType::from_name("unknown"), None);
+    }
+}
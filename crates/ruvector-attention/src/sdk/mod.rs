@@ -3,9 +3,15 @@
 //! High-level, ergonomic APIs for building attention mechanisms.
 
 pub mod builder;
+pub mod calibration;
 pub mod pipeline;
 pub mod presets;
 
 pub use builder::{AttentionBuilder, AttentionType, scaled_dot, multi_head, flash};
+pub use calibration::{
+    build_attention, AttentionDefaults, CalibrationConfig, CalibrationReport, ModeMeasurement,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use calibration::calibrate;
 pub use pipeline::{AttentionPipeline, PipelineStage, NormType};
 pub use presets::{AttentionPreset, for_sequences, for_graphs, for_large_scale};
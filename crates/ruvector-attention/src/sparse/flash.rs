@@ -4,13 +4,26 @@
 
 use crate::error::{AttentionError, AttentionResult};
 use crate::traits::Attention;
+use super::flash_tune::{auto_block_size, detect_cache_bytes, BlockSizeTable};
+
+/// How the tile size is chosen for each call
+#[derive(Clone, Debug)]
+pub enum BlockSize {
+    /// Always use this many candidates per tile
+    Fixed(usize),
+    /// Pick per call from candidate count, dimension and detected cache size
+    Auto,
+    /// Use measured crossover points, falling back to `Auto` when empty
+    Tuned(BlockSizeTable),
+}
 
 /// Flash attention with block-wise computation
 ///
 /// Computes attention in tiles to minimize memory usage while maintaining numerical stability.
 pub struct FlashAttention {
     dim: usize,
-    block_size: usize,
+    block_size: BlockSize,
+    cache_bytes: usize,
     scale: f32,
     causal: bool,
 }
@@ -18,9 +31,26 @@ pub struct FlashAttention {
 impl FlashAttention {
     /// Create new flash attention
     pub fn new(dim: usize, block_size: usize) -> Self {
+        Self::with_block_size(dim, BlockSize::Fixed(block_size.max(1)))
+    }
+
+    /// Create with automatic block-size selection
+    pub fn auto(dim: usize) -> Self {
+        Self::with_block_size(dim, BlockSize::Auto)
+    }
+
+    /// Create with an explicit block-size policy
+    ///
+    /// A fixed block size of 0 is raised to 1.
+    pub fn with_block_size(dim: usize, block_size: BlockSize) -> Self {
+        let (block_size, cache_bytes) = match block_size {
+            BlockSize::Fixed(b) => (BlockSize::Fixed(b.max(1)), 0),
+            other => (other, detect_cache_bytes()),
+        };
         Self {
             dim,
             block_size,
+            cache_bytes,
             scale: 1.0 / (dim as f32).sqrt(),
             causal: false,
         }
@@ -29,72 +59,57 @@ impl FlashAttention {
     /// Create with causal masking
     pub fn causal(dim: usize, block_size: usize) -> Self {
         Self {
-            dim,
-            block_size,
-            scale: 1.0 / (dim as f32).sqrt(),
             causal: true,
+            ..Self::new(dim, block_size)
         }
     }
 
-    /// Compute attention scores for a block
-    fn compute_block_scores(&self, query: &[f32], keys: &[&[f32]], start_idx: usize) -> Vec<f32> {
-        keys.iter()
-            .enumerate()
-            .map(|(j, key)| {
-                if self.causal && start_idx + j > 0 {
-                    // Simplified causal: assuming query is at position 0
-                    f32::NEG_INFINITY
-                } else {
-                    query
-                        .iter()
-                        .zip(key.iter())
-                        .map(|(q, k)| q * k)
-                        .sum::<f32>()
-                        * self.scale
-                }
-            })
-            .collect()
+    /// Block size that will be used for `num_candidates` keys
+    pub fn block_size_for(&self, num_candidates: usize) -> usize {
+        match &self.block_size {
+            BlockSize::Fixed(b) => *b,
+            BlockSize::Auto => auto_block_size(num_candidates, self.dim, self.cache_bytes),
+            BlockSize::Tuned(table) => table
+                .best_for(num_candidates)
+                .unwrap_or_else(|| auto_block_size(num_candidates, self.dim, self.cache_bytes)),
+        }
     }
-}
 
-impl Attention for FlashAttention {
-    fn compute(
-        &self,
-        query: &[f32],
-        keys: &[&[f32]],
-        values: &[&[f32]],
-    ) -> AttentionResult<Vec<f32>> {
-        if keys.is_empty() {
-            return Err(AttentionError::InvalidConfig("Empty keys".to_string()));
-        }
-        if keys.len() != values.len() {
-            return Err(AttentionError::DimensionMismatch {
-                expected: keys.len(),
-                actual: values.len(),
-            });
-        }
-        if query.len() != self.dim {
-            return Err(AttentionError::DimensionMismatch {
-                expected: self.dim,
-                actual: query.len(),
-            });
+    /// Score of key `idx` given its raw dot product with the query
+    fn score(&self, idx: usize, dot: f32) -> f32 {
+        if self.causal && idx > 0 {
+            // Simplified causal: assuming query is at position 0
+            f32::NEG_INFINITY
+        } else {
+            dot * self.scale
         }
+    }
 
-        let n = keys.len();
-        let value_dim = values[0].len();
-
-        // Online softmax with tiled computation
+    /// Online softmax over `n` keys in tiles
+    ///
+    /// `dot(i)` is the dot product of the query with key `i`, and
+    /// `add_value(output, weight, i)` adds `weight` times value `i` to the
+    /// output, so the same tiling serves every key and value storage.
+    fn compute_tiled(
+        &self,
+        n: usize,
+        value_dim: usize,
+        dot: impl Fn(usize) -> f32,
+        add_value: impl Fn(&mut [f32], f32, usize),
+    ) -> Vec<f32> {
         let mut output = vec![0.0f32; value_dim];
         let mut max_so_far = f32::NEG_INFINITY;
         let mut sum_exp = 0.0f32;
 
         // Process in blocks
-        for block_start in (0..n).step_by(self.block_size) {
-            let block_end = (block_start + self.block_size).min(n);
-            let block_keys: Vec<&[f32]> = keys[block_start..block_end].to_vec();
+        let block_size = self.block_size_for(n);
+        for block_start in (0..n).step_by(block_size) {
+            let block_end = (block_start + block_size).min(n);
 
             // Compute attention scores for this block
-            let block_scores = self.compute_block_scores(query, &block_keys, block_start);
+            let block_scores: Vec<f32> = (block_start..block_end)
+                .map(|idx| self.score(idx, dot(idx)))
+                .collect();
 
             // Find block maximum
             let block_max = block_scores
@@ -122,11 +137,7 @@ impl Attention for FlashAttention {
                 if score.is_finite() {
                     let exp_score = (score - new_max).exp();
                     sum_exp += exp_score;
-
-                    let global_idx = block_start + local_idx;
-                    for (j, &vj) in values[global_idx].iter().enumerate() {
-                        output[j] += exp_score * vj;
-                    }
+                    add_value(&mut output, exp_score, block_start + local_idx);
                 }
             }
 
@@ -138,6 +149,44 @@ impl Attention for FlashAttention {
             output.iter_mut().for_each(|o| *o /= sum_exp);
         }
 
+        output
+    }
+}
+
+impl Attention for FlashAttention {
+    fn compute(
+        &self,
+        query: &[f32],
+        keys: &[&[f32]],
+        values: &[&[f32]],
+    ) -> AttentionResult<Vec<f32>> {
+        if keys.is_empty() {
+            return Err(AttentionError::InvalidConfig("Empty keys".to_string()));
+        }
+        if keys.len() != values.len() {
+            return Err(AttentionError::DimensionMismatch {
+                expected: keys.len(),
+                actual: values.len(),
+            });
+        }
+        if query.len() != self.dim {
+            return Err(AttentionError::DimensionMismatch {
+                expected: self.dim,
+                actual: query.len(),
+            });
+        }
+
+        let output = self.compute_tiled(
+            keys.len(),
+            values[0].len(),
+            |i| query.iter().zip(keys[i].iter()).map(|(q, k)| q * k).sum::<f32>(),
+            |output, weight, i| {
+                for (o, &v) in output.iter_mut().zip(values[i].iter()) {
+                    *o += weight * v;
+                }
+            },
+        );
+
         Ok(output)
     }
 
@@ -163,6 +212,27 @@ impl Attention for FlashAttention {
     }
 }
 
+#[cfg(feature = "f16")]
+impl crate::precision::HalfAttention for FlashAttention {
+    fn compute_f16(
+        &self,
+        query: &[f32],
+        keys: &crate::precision::HalfMatrix,
+        values: &crate::precision::HalfMatrix,
+    ) -> AttentionResult<Vec<f32>> {
+        use crate::precision::{add_scaled_f16, check_inputs, dot_f16};
+
+        check_inputs(self.dim, query, keys, values)?;
+
+        Ok(self.compute_tiled(
+            keys.len(),
+            values.dim(),
+            |i| dot_f16(query, keys.row(i)),
+            |output, weight, i| add_scaled_f16(output, weight, values.row(i)),
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -205,6 +275,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_auto_block_size_matches_fixed() {
+        let dim = 32;
+        let auto = FlashAttention::auto(dim);
+        let fixed = FlashAttention::new(dim, 8);
+
+        let query = vec![0.5; dim];
+        let keys: Vec<Vec<f32>> = (0..100).map(|i| vec![(i as f32) * 0.01; dim]).collect();
+        let keys_refs: Vec<&[f32]> = keys.iter().map(|k| k.as_slice()).collect();
+
+        let a = auto.compute(&query, &keys_refs, &keys_refs).unwrap();
+        let f = fixed.compute(&query, &keys_refs, &keys_refs).unwrap();
+        for (x, y) in a.iter().zip(f.iter()) {
+            assert!((x - y).abs() < 1e-4);
+        }
+
+        assert_eq!(fixed.block_size_for(100), 8);
+        assert!(auto.block_size_for(100) <= 128);
+    }
+
+    #[test]
+    fn test_tuned_block_size() {
+        let table = BlockSizeTable {
+            dim: 16,
+            entries: vec![(0, 8), (1000, 64)],
+        };
+        let attention = FlashAttention::with_block_size(16, BlockSize::Tuned(table));
+        assert_eq!(attention.block_size_for(10), 8);
+        assert_eq!(attention.block_size_for(5000), 64);
+
+        // Zero-sized tiles would never advance
+        let table = BlockSizeTable {
+            dim: 16,
+            entries: vec![(0, 0)],
+        };
+        let keys = vec![vec![0.5; 16]; 4];
+        let keys_refs: Vec<&[f32]> = keys.iter().map(|k| k.as_slice()).collect();
+        for block_size in [BlockSize::Fixed(0), BlockSize::Tuned(table)] {
+            let attention = FlashAttention::with_block_size(16, block_size);
+            assert_eq!(attention.block_size_for(4), 1);
+            let result = attention
+                .compute(&[1.0; 16], &keys_refs, &keys_refs)
+                .unwrap();
+            assert_eq!(result.len(), 16);
+        }
+    }
+
     #[test]
     fn test_causal_flash() {
         let attention = FlashAttention::causal(32, 8);
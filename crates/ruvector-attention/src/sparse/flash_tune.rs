@@ -0,0 +1,231 @@
+//! Block-size selection for flash attention
+//!
+//! The best tile size depends on how many candidates are scored, the vector
+//! dimension and how much cache the machine has. [`auto_block_size`] picks a
+//! size from a cache-fit heuristic, and [`calibrate`] measures the actual
+//! crossover points on the current machine so the heuristic can be replaced by
+//! a [`BlockSizeTable`].
+
+use super::flash::FlashAttention;
+use crate::traits::Attention;
+
+/// Smallest block size considered by the tuner
+pub const MIN_BLOCK_SIZE: usize = 8;
+
+/// Largest block size considered by the tuner
+pub const MAX_BLOCK_SIZE: usize = 1024;
+
+/// Fallback cache size when it cannot be detected (256 KiB, a typical L2)
+pub const DEFAULT_CACHE_BYTES: usize = 256 * 1024;
+
+/// Detect the per-core L2 cache size in bytes
+///
+/// Reads sysfs on Linux and falls back to [`DEFAULT_CACHE_BYTES`] elsewhere.
+pub fn detect_cache_bytes() -> usize {
+    #[cfg(target_os = "linux")]
+    {
+        for index in 0..4 {
+            let base = format!("/sys/devices/system/cpu/cpu0/cache/index{}", index);
+            let level = std::fs::read_to_string(format!("{}/level", base)).ok();
+            if level.as_deref().map(str::trim) != Some("2") {
+                continue;
+            }
+            if let Some(bytes) = std::fs::read_to_string(format!("{}/size", base))
+                .ok()
+                .and_then(|s| parse_cache_size(s.trim()))
+            {
+                return bytes;
+            }
+        }
+    }
+    DEFAULT_CACHE_BYTES
+}
+
+/// Parse sysfs cache sizes such as `"512K"` or `"2M"`
+fn parse_cache_size(s: &str) -> Option<usize> {
+    let (digits, mult) = match s.chars().last()? {
+        'K' | 'k' => (&s[..s.len() - 1], 1024),
+        'M' | 'm' => (&s[..s.len() - 1], 1024 * 1024),
+        _ => (s, 1),
+    };
+    digits.parse::<usize>().ok().map(|n| n * mult)
+}
+
+/// Pick a block size for `num_candidates` vectors of `dim` floats
+///
+/// A block holds one key and one value row per candidate; the block is sized so
+/// that both fit in half of the cache, leaving room for the query and output
+/// accumulators. The result is a power of two clamped to
+/// `[MIN_BLOCK_SIZE, MAX_BLOCK_SIZE]` and never larger than needed to cover
+/// all candidates in one tile.
+pub fn auto_block_size(num_candidates: usize, dim: usize, cache_bytes: usize) -> usize {
+    let row_bytes = 2 * dim.max(1) * std::mem::size_of::<f32>();
+    let fit = (cache_bytes / 2) / row_bytes;
+    let mut block = MIN_BLOCK_SIZE;
+    while block * 2 <= fit && block * 2 <= MAX_BLOCK_SIZE {
+        block *= 2;
+    }
+    let cover = num_candidates.max(1).next_power_of_two().max(MIN_BLOCK_SIZE);
+    block.min(cover)
+}
+
+/// Measured block-size crossover points for one dimension
+///
+/// Entries are `(num_candidates, best_block_size)` sorted by candidate count.
+#[derive(Clone, Debug, Default)]
+pub struct BlockSizeTable {
+    pub dim: usize,
+    pub entries: Vec<(usize, usize)>,
+}
+
+impl BlockSizeTable {
+    /// Create a table, sorting entries and raising block sizes of 0 to 1
+    pub fn new(dim: usize, entries: Vec<(usize, usize)>) -> Self {
+        let mut entries: Vec<(usize, usize)> =
+            entries.into_iter().map(|(n, b)| (n, b.max(1))).collect();
+        entries.sort_by_key(|(n, _)| *n);
+        Self { dim, entries }
+    }
+
+    /// Best measured block size for `num_candidates`
+    ///
+    /// Uses the entry with the largest candidate count not exceeding
+    /// `num_candidates`, or the first entry for smaller inputs. A block size
+    /// of 0 is returned as 1.
+    pub fn best_for(&self, num_candidates: usize) -> Option<usize> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(n, _)| *n <= num_candidates)
+            .or_else(|| self.entries.first())
+            .map(|(_, b)| (*b).max(1))
+    }
+}
+
+/// Timing for one (candidate count, block size) pair
+#[derive(Clone, Debug)]
+pub struct BlockSizeMeasurement {
+    pub num_candidates: usize,
+    pub block_size: usize,
+    /// Mean time per attention call in nanoseconds
+    pub mean_ns: f64,
+}
+
+/// Micro-benchmark flash attention over a grid of candidate counts and block sizes
+///
+/// Returns every measurement plus a [`BlockSizeTable`] holding the fastest block
+/// size per candidate count. Intended to be run once per machine, e.g. at
+/// install time, with the table persisted by the caller. A block size of 0 is
+/// measured as 1.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn calibrate(
+    dim: usize,
+    candidate_counts: &[usize],
+    block_sizes: &[usize],
+    iterations: usize,
+) -> (Vec<BlockSizeMeasurement>, BlockSizeTable) {
+    use std::time::Instant;
+
+    let iterations = iterations.max(1);
+    let query: Vec<f32> = (0..dim).map(|i| ((i % 7) as f32 - 3.0) * 0.1).collect();
+    let mut measurements = Vec::new();
+    let mut entries = Vec::with_capacity(candidate_counts.len());
+
+    for &n in candidate_counts {
+        let keys: Vec<Vec<f32>> = (0..n)
+            .map(|i| (0..dim).map(|j| ((i * 31 + j) % 17) as f32 * 0.05).collect())
+            .collect();
+        let keys_refs: Vec<&[f32]> = keys.iter().map(|k| k.as_slice()).collect();
+
+        let mut best: Option<(usize, f64)> = None;
+        for &block_size in block_sizes {
+            let block_size = block_size.max(1);
+            let attention = FlashAttention::new(dim, block_size);
+            // Warm-up pass so the first timed iteration is not penalised
+            let _ = attention.compute(&query, &keys_refs, &keys_refs);
+
+            let start = Instant::now();
+            for _ in 0..iterations {
+                let _ = attention.compute(&query, &keys_refs, &keys_refs);
+            }
+            let mean_ns = start.elapsed().as_nanos() as f64 / iterations as f64;
+
+            measurements.push(BlockSizeMeasurement {
+                num_candidates: n,
+                block_size,
+                mean_ns,
+            });
+            let faster = match best {
+                Some((_, t)) => mean_ns < t,
+                None => true,
+            };
+            if faster {
+                best = Some((block_size, mean_ns));
+            }
+        }
+
+        if let Some((block_size, _)) = best {
+            entries.push((n, block_size));
+        }
+    }
+
+    (measurements, BlockSizeTable::new(dim, entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cache_size() {
+        assert_eq!(parse_cache_size("512K"), Some(512 * 1024));
+        assert_eq!(parse_cache_size("2M"), Some(2 * 1024 * 1024));
+        assert_eq!(parse_cache_size("4096"), Some(4096));
+        assert_eq!(parse_cache_size("abc"), None);
+    }
+
+    #[test]
+    fn test_auto_block_size_bounds() {
+        // Few candidates: never larger than needed to cover them
+        assert_eq!(auto_block_size(10, 64, DEFAULT_CACHE_BYTES), 16);
+        // Large dimension shrinks the block to fit cache
+        let small = auto_block_size(100_000, 4096, DEFAULT_CACHE_BYTES);
+        let large = auto_block_size(100_000, 32, DEFAULT_CACHE_BYTES);
+        assert!(small < large);
+        assert!(small >= MIN_BLOCK_SIZE);
+        assert!(large <= MAX_BLOCK_SIZE);
+        assert!(large.is_power_of_two());
+    }
+
+    #[test]
+    fn test_table_lookup() {
+        let table = BlockSizeTable {
+            dim: 64,
+            entries: vec![(64, 16), (1024, 64), (16384, 256)],
+        };
+        assert_eq!(table.best_for(10), Some(16));
+        assert_eq!(table.best_for(2000), Some(64));
+        assert_eq!(table.best_for(1_000_000), Some(256));
+        assert_eq!(BlockSizeTable::default().best_for(10), None);
+
+        let table = BlockSizeTable::new(64, vec![(1024, 0), (64, 16)]);
+        assert_eq!(table.entries, vec![(64, 16), (1024, 1)]);
+        let table = BlockSizeTable {
+            dim: 64,
+            entries: vec![(0, 0)],
+        };
+        assert_eq!(table.best_for(10), Some(1));
+    }
+
+    #[test]
+    fn test_calibrate_produces_table() {
+        let (measurements, table) = calibrate(16, &[32, 128], &[8, 32], 2);
+        assert_eq!(measurements.len(), 4);
+        assert_eq!(table.entries.len(), 2);
+        assert!(table.entries.iter().all(|(_, b)| *b == 8 || *b == 32));
+
+        let (measurements, table) = calibrate(16, &[32], &[0], 1);
+        assert_eq!(measurements[0].block_size, 1);
+        assert_eq!(table.entries, vec![(32, 1)]);
+    }
+}
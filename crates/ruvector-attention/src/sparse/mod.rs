@@ -6,8 +6,10 @@ pub mod mask;
 pub mod local_global;
 pub mod linear;
 pub mod flash;
+pub mod flash_tune;
 
 pub use mask::{SparseMaskBuilder, AttentionMask};
 pub use local_global::LocalGlobalAttention;
 pub use linear::LinearAttention;
-pub use flash::FlashAttention;
+pub use flash::{BlockSize, FlashAttention};
+pub use flash_tune::{auto_block_size, BlockSizeMeasurement, BlockSizeTable};
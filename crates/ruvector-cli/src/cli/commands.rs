@@ -2,14 +2,14 @@
 
 use crate::cli::{
     export_csv, export_json, format_error, format_search_results, format_stats, format_success,
-    ProgressTracker,
+    format_warning, ProgressTracker,
 };
 use crate::config::Config;
 use anyhow::{Context, Result};
 use colored::*;
 use ruvector_core::{
     types::{DbOptions, SearchQuery, VectorEntry},
-    VectorDB,
+    AnomalyConfig, AnomalyMethod, BenchRecord, BenchReport, VectorDB,
 };
 use std::path::{Path, PathBuf};
 use std::time::Instant;
@@ -140,7 +140,7 @@ pub fn search_vectors(
 }
 
 /// Show database information
-pub fn show_info(db_path: &str, config: &Config) -> Result<()> {
+pub fn show_info(db_path: &str, vectors: bool, config: &Config) -> Result<()> {
     let mut db_options = config.to_db_options();
     db_options.storage_path = db_path.to_string();
 
@@ -162,11 +162,52 @@ pub fn show_info(db_path: &str, config: &Config) -> Result<()> {
         println!("  ef_search: {}", hnsw_config.ef_search.to_string().cyan());
     }
 
+    if vectors {
+        let stats = db
+            .collection_stats()
+            .context("Failed to compute vector statistics")?;
+        let norms = &stats.norms;
+        println!("{}", "Vector Statistics:".bold().green());
+        println!(
+            "  Norms: min {:.4}, p5 {:.4}, median {:.4}, p95 {:.4}, max {:.4}",
+            norms.min, norms.p5, norms.p50, norms.p95, norms.max
+        );
+        println!("  Mean vector norm: {:.4}", stats.mean_vector_norm);
+        match stats.intrinsic_dimension {
+            Some(d) => println!(
+                "  Intrinsic dimensionality: {} (from {} samples)",
+                format!("{:.1}", d).cyan(),
+                stats.intrinsic_dim_sample
+            ),
+            None => println!("  Intrinsic dimensionality: n/a"),
+        }
+        if stats.zero_vectors > 0 {
+            println!(
+                "{}",
+                format_warning(&format!("{} all-zero vectors", stats.zero_vectors))
+            );
+        }
+        if stats.non_finite > 0 {
+            println!(
+                "{}",
+                format_warning(&format!(
+                    "{} vectors with NaN or infinite components",
+                    stats.non_finite
+                ))
+            );
+        }
+    }
+
     Ok(())
 }
 
 /// Run a quick benchmark
-pub fn run_benchmark(db_path: &str, config: &Config, num_queries: usize) -> Result<()> {
+pub fn run_benchmark(
+    db_path: &str,
+    config: &Config,
+    num_queries: usize,
+    json_output: Option<&str>,
+) -> Result<()> {
     let mut db_options = config.to_db_options();
     db_options.storage_path = db_path.to_string();
 
@@ -196,8 +237,10 @@ pub fn run_benchmark(db_path: &str, config: &Config, num_queries: usize) -> Resu
     }
 
     // Benchmark
+    let mut latencies_ns = Vec::with_capacity(num_queries);
     let start = Instant::now();
     for query in &queries {
+        let query_start = Instant::now();
         db.search(SearchQuery {
             vector: query.clone(),
             k: 10,
@@ -205,6 +248,7 @@ pub fn run_benchmark(db_path: &str, config: &Config, num_queries: usize) -> Resu
             ef_search: None,
         })
         .context("Search failed")?;
+        latencies_ns.push(query_start.elapsed().as_nanos() as f64);
     }
     let elapsed = start.elapsed();
 
@@ -216,6 +260,15 @@ pub fn run_benchmark(db_path: &str, config: &Config, num_queries: usize) -> Resu
     println!("  Queries per second: {:.0}", qps.to_string().cyan());
     println!("  Average latency: {:.2}ms", avg_latency.to_string().cyan());
 
+    if let Some(path) = json_output {
+        // Same schema as the criterion suite's bench-report output
+        let record = BenchRecord::from_samples("hnsw_search", "cli/k10", &latencies_ns);
+        let report = BenchReport::new("cli", vec![record]);
+        std::fs::write(path, serde_json::to_string_pretty(&report)?)
+            .with_context(|| format!("Failed to write {}", path))?;
+        println!("  Report: {}", path.cyan());
+    }
+
     Ok(())
 }
 
@@ -249,6 +302,238 @@ pub fn export_database(
     // ...
 }
 
+/// Analyze the k-NN graph of a database
+pub fn analyze_graph(
+    db_path: &str,
+    k: usize,
+    hub_count: usize,
+    output_file: Option<&str>,
+    config: &Config,
+) -> Result<()> {
+    let mut db_options = config.to_db_options();
+    db_options.storage_path = db_path.to_string();
+
+    let db = VectorDB::new(db_options).context("Failed to open database")?;
+
+    let start = Instant::now();
+    let graph = db
+        .export_knn_graph(k)
+        .context("Failed to build k-NN graph")?;
+    let analytics = graph.analyze(hub_count);
+
+    println!("{}", "Graph Analysis:".bold().green());
+    println!("  Nodes: {}", analytics.node_count.to_string().cyan());
+    println!("  Edges: {}", analytics.edge_count.to_string().cyan());
+    println!(
+        "  Components: {} (largest covers {:.1}%)",
+        analytics.component_count().to_string().cyan(),
+        analytics.largest_component_fraction * 100.0
+    );
+    println!(
+        "  In-degree: mean {:.2}, skewness {:.2}",
+        analytics.mean_in_degree, analytics.in_degree_skewness
+    );
+    println!(
+        "  Unreachable nodes: {}",
+        analytics.unreachable.len().to_string().cyan()
+    );
+
+    if !analytics.hubs.is_empty() {
+        println!("\n{}", "Top hubs:".bold().green());
+        for hub in &analytics.hubs {
+            println!("  {} (in-degree {})", hub.id.cyan(), hub.in_degree);
+        }
+    }
+
+    if let Some(path) = output_file {
+        graph
+            .write_to_file(path)
+            .with_context(|| format!("Failed to write edge list to {}", path))?;
+        println!(
+            "\n{}",
+            format_success(&format!("Wrote {} edges to: {}", graph.edge_count(), path))
+        );
+    }
+
+    println!("\n  Completed in {:.2}s", start.elapsed().as_secs_f64());
+    Ok(())
+}
+
+/// Show HNSW graph statistics and optionally one node's neighbors
+pub fn inspect_graph(
+    db_path: &str,
+    node: Option<&str>,
+    level: usize,
+    config: &Config,
+) -> Result<()> {
+    let mut db_options = config.to_db_options();
+    db_options.storage_path = db_path.to_string();
+
+    let db = VectorDB::new(db_options).context("Failed to open database")?;
+    let stats = db.graph_stats().context("Failed to inspect graph")?;
+
+    println!("{}", "HNSW Graph:".bold().green());
+    println!("  Levels: {}", stats.levels.to_string().cyan());
+    for (level, (nodes, degree)) in stats
+        .nodes_per_level
+        .iter()
+        .zip(&stats.avg_out_degree)
+        .enumerate()
+    {
+        println!(
+            "  Level {}: {} nodes, mean out-degree {:.2}",
+            level,
+            nodes.to_string().cyan(),
+            degree
+        );
+    }
+    println!(
+        "  Entry point: {}",
+        stats.entry_point.as_deref().unwrap_or("-").cyan()
+    );
+    println!(
+        "  Reachable from entry: {} ({} unreachable)",
+        stats.reachable_from_entry.to_string().cyan(),
+        stats.unreachable
+    );
+    println!(
+        "  Isolated nodes: {}",
+        stats.isolated_nodes.to_string().cyan()
+    );
+    println!(
+        "  Removed nodes: {}",
+        stats.removed_nodes.to_string().cyan()
+    );
+
+    if let Some(id) = node {
+        let neighbors = db
+            .neighbors(id, level)
+            .with_context(|| format!("Failed to read neighbors of {}", id))?;
+        println!(
+            "\n{}",
+            format!("Neighbors of {} on level {}:", id, level)
+                .bold()
+                .green()
+        );
+        for neighbor in &neighbors {
+            println!(
+                "  {} (distance {:.4})",
+                neighbor.id.as_deref().unwrap_or("<removed>").cyan(),
+                neighbor.distance
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Salvage a damaged database into a new one
+pub fn recover_database(db_path: &str, output: &str, config: &Config) -> Result<()> {
+    let mut db_options = config.to_db_options();
+    db_options.storage_path = output.to_string();
+
+    let (db, report) =
+        VectorDB::recover(db_path, db_options).context("Failed to recover database")?;
+
+    println!("{}", "Recovery:".bold().green());
+    println!("  Recovered: {}", report.recovered.to_string().cyan());
+    println!(
+        "  Irrecoverable: {}",
+        report.irrecoverable.to_string().cyan()
+    );
+    for id in &report.irrecoverable_ids {
+        println!("    {}", id);
+    }
+    println!(
+        "  Metadata lost: {}",
+        report.metadata_lost.to_string().cyan()
+    );
+    println!(
+        "  Trashed entries: {}",
+        report.trash_recovered.to_string().cyan()
+    );
+    if !report.config_recovered {
+        println!(
+            "{}",
+            format_warning("Stored configuration unreadable; used CLI configuration")
+        );
+    }
+    if !report.complete {
+        println!(
+            "{}",
+            format_warning("A damaged region stopped a scan; uncounted entries may be missing")
+        );
+    }
+    println!(
+        "{}",
+        format_success(&format!("Wrote {} vectors to: {}", db.len()?, output))
+    );
+
+    Ok(())
+}
+
+/// Score vectors by outlierness, optionally writing scores to metadata
+pub fn score_anomalies(
+    db_path: &str,
+    k: usize,
+    method: &str,
+    threshold: Option<f32>,
+    dry_run: bool,
+    config: &Config,
+) -> Result<()> {
+    let method = match method {
+        "lof" => AnomalyMethod::Lof,
+        "kth" => AnomalyMethod::KthDistance,
+        other => anyhow::bail!("Unknown anomaly method '{}' (expected lof or kth)", other),
+    };
+    let mut db_options = config.to_db_options();
+    db_options.storage_path = db_path.to_string();
+
+    let db = VectorDB::new(db_options).context("Failed to open database")?;
+    let anomaly_config = AnomalyConfig {
+        k,
+        method,
+        // LOF has a scale-free default; k-th distances depend on the model
+        threshold: threshold.or(match method {
+            AnomalyMethod::Lof => AnomalyConfig::default().threshold,
+            AnomalyMethod::KthDistance => None,
+        }),
+        write_metadata: !dry_run,
+        ..Default::default()
+    };
+    let report = db
+        .score_anomalies(&anomaly_config)
+        .context("Failed to score anomalies")?;
+
+    println!("{}", "Anomaly Scores:".bold().green());
+    println!("  Scored: {}", report.scored.to_string().cyan());
+    println!(
+        "  Mean: {:.4}, p99: {:.4}",
+        report.mean_score, report.p99_score
+    );
+    if let Some(threshold) = anomaly_config.threshold {
+        println!(
+            "  Above {}: {}",
+            threshold,
+            report.flagged.to_string().cyan()
+        );
+    }
+    if !report.top.is_empty() {
+        println!("\n{}", "Top outliers:".bold().green());
+        for entry in &report.top {
+            println!("  {} ({:.4})", entry.id.cyan(), entry.score);
+        }
+    }
+    if report.written > 0 {
+        println!(
+            "\n{}",
+            format_success(&format!("Wrote scores to {} vectors", report.written))
+        );
+    }
+
+    Ok(())
+}
+
 /// Import from other vector databases
 pub fn import_from_external(
     db_path: &str,
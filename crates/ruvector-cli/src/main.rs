@@ -88,6 +88,10 @@ enum Commands {
         /// Database file path
         #[arg(short = 'b', long, default_value = "./ruvector.db")]
         db: String,
+
+        /// Also report vector statistics (scans every vector)
+        #[arg(long)]
+        vectors: bool,
     },
 
     /// Run a quick performance benchmark
@@ -99,6 +103,10 @@ enum Commands {
         /// Number of queries to run
         #[arg(short = 'n', long, default_value = "1000")]
         queries: usize,
+
+        /// Also write results as a JSON benchmark report to this file
+        #[arg(long)]
+        json: Option<String>,
     },
 
     /// Export database to file
@@ -116,6 +124,74 @@ enum Commands {
         format: String,
     },
 
+    /// Analyze index connectivity (components, degrees, hubs)
+    Analyze {
+        /// Database file path
+        #[arg(short = 'b', long, default_value = "./ruvector.db")]
+        db: String,
+
+        /// Neighbors per node in the k-NN graph
+        #[arg(short = 'k', long, default_value = "10")]
+        k: usize,
+
+        /// Number of hubs to report
+        #[arg(long, default_value = "10")]
+        hubs: usize,
+
+        /// Also write the k-NN edge list to this file (.csv or .json)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Inspect the HNSW graph (levels, degrees, entry point connectivity)
+    Inspect {
+        /// Database file path
+        #[arg(short = 'b', long, default_value = "./ruvector.db")]
+        db: String,
+
+        /// Also list the graph neighbors of this vector id
+        #[arg(short, long)]
+        neighbors: Option<String>,
+
+        /// Graph level for --neighbors
+        #[arg(short, long, default_value = "0")]
+        level: usize,
+    },
+
+    /// Score vectors by outlierness and tag anomalies in metadata
+    Anomalies {
+        /// Database file path
+        #[arg(short = 'b', long, default_value = "./ruvector.db")]
+        db: String,
+
+        /// Neighbors per vector
+        #[arg(short = 'k', long, default_value = "10")]
+        k: usize,
+
+        /// Scoring method (lof, kth)
+        #[arg(short, long, default_value = "lof")]
+        method: String,
+
+        /// Score above which vectors are flagged
+        #[arg(short, long)]
+        threshold: Option<f32>,
+
+        /// Report scores without writing them to metadata
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Salvage readable entries of a damaged database into a new one
+    Recover {
+        /// Damaged database file path
+        #[arg(short = 'b', long, default_value = "./ruvector.db")]
+        db: String,
+
+        /// Path of the new database (must not exist)
+        #[arg(short, long)]
+        output: String,
+    },
+
     /// Import from other vector databases
     Import {
         /// Database file path
@@ -175,9 +251,30 @@ async fn main() -> Result<()> {
             let query_vec = parse_query_vector(&query)?;
             search_vectors(&db, query_vec, top_k, &config, show_vectors)
         }
-        Commands::Info { db } => show_info(&db, &config),
-        Commands::Benchmark { db, queries } => run_benchmark(&db, &config, queries),
+        Commands::Info { db, vectors } => show_info(&db, vectors, &config),
+        Commands::Benchmark { db, queries, json } => {
+            run_benchmark(&db, &config, queries, json.as_deref())
+        }
         Commands::Export { db, output, format } => export_database(&db, &output, &format, &config),
+        Commands::Analyze {
+            db,
+            k,
+            hubs,
+            output,
+        } => analyze_graph(&db, k, hubs, output.as_deref(), &config),
+        Commands::Inspect {
+            db,
+            neighbors,
+            level,
+        } => inspect_graph(&db, neighbors.as_deref(), level, &config),
+        Commands::Anomalies {
+            db,
+            k,
+            method,
+            threshold,
+            dry_run,
+        } => score_anomalies(&db, k, &method, threshold, dry_run, &config),
+        Commands::Recover { db, output } => recover_database(&db, &output, &config),
         Commands::Import {
             db,
             source,
@@ -1,5 +1,8 @@
 //! Collection types and operations
 
+use ruvector_core::embedding_model::EmbeddingModel;
+use ruvector_core::multi_metric::check_secondary_metric;
+use ruvector_core::normalization::NormalizationPolicy;
 use ruvector_core::types::{DistanceMetric, HnswConfig, QuantizationConfig};
 use ruvector_core::vector_db::VectorDB;
 use serde::{Deserialize, Serialize};
@@ -23,6 +26,22 @@ pub struct CollectionConfig {
 
     /// Whether to store payload data on disk
     pub on_disk_payload: bool,
+
+    /// Embedding model the collection's vectors come from
+    #[serde(default)]
+    pub embedding_model: Option<EmbeddingModel>,
+
+    /// Reject writes and searches that declare a different model
+    #[serde(default)]
+    pub strict_model: bool,
+
+    /// When vectors are L2-normalized
+    #[serde(default)]
+    pub normalization: NormalizationPolicy,
+
+    /// Metrics searches may select besides `distance_metric`
+    #[serde(default)]
+    pub secondary_metrics: Vec<DistanceMetric>,
 }
 
 impl CollectionConfig {
@@ -40,6 +59,27 @@ impl CollectionConfig {
             });
         }
 
+        if let Some(model) = &self.embedding_model {
+            if model.dimensions != self.dimensions {
+                return Err(CollectionError::InvalidConfiguration {
+                    message: format!(
+                        "Embedding model '{}' produces {} dimensions, collection has {}",
+                        model.reference(),
+                        model.dimensions,
+                        self.dimensions
+                    ),
+                });
+            }
+        }
+
+        for &metric in &self.secondary_metrics {
+            check_secondary_metric(self.distance_metric, metric, self.normalization).map_err(
+                |e| CollectionError::InvalidConfiguration {
+                    message: e.to_string(),
+                },
+            )?;
+        }
+
         // Validate HNSW config if present
         if let Some(ref hnsw_config) = self.hnsw_config {
             if hnsw_config.m == 0 {
@@ -72,6 +112,10 @@ impl CollectionConfig {
             hnsw_config: Some(HnswConfig::default()),
             quantization: Some(QuantizationConfig::Scalar),
             on_disk_payload: true,
+            embedding_model: None,
+            strict_model: false,
+            normalization: NormalizationPolicy::None,
+            secondary_metrics: Vec::new(),
         }
     }
 }
@@ -122,6 +166,11 @@ impl Collection {
         };
 
         let db = VectorDB::new(db_options)?;
+        if let Some(model) = &config.embedding_model {
+            db.set_embedding_model(model.clone(), config.strict_model)?;
+        }
+        db.set_normalization(config.normalization)?;
+        db.set_secondary_metrics(config.secondary_metrics.clone())?;
 
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -227,6 +276,10 @@ mod tests {
             hnsw_config: None,
             quantization: None,
             on_disk_payload: true,
+            embedding_model: None,
+            strict_model: false,
+            normalization: NormalizationPolicy::None,
+            secondary_metrics: Vec::new(),
         };
         assert!(config.validate().is_err());
 
@@ -237,8 +290,24 @@ mod tests {
             hnsw_config: None,
             quantization: None,
             on_disk_payload: true,
+            embedding_model: None,
+            strict_model: false,
+            normalization: NormalizationPolicy::None,
+            secondary_metrics: Vec::new(),
         };
         assert!(config.validate().is_err());
+
+        // Invalid: model dimensions differ
+        let mut config = CollectionConfig::with_dimensions(384);
+        config.embedding_model = Some(EmbeddingModel::new("mpnet", 768));
+        assert!(config.validate().is_err());
+
+        // Secondary metrics need normalized inserts
+        let mut config = CollectionConfig::with_dimensions(384);
+        config.secondary_metrics = vec![DistanceMetric::DotProduct];
+        assert!(config.validate().is_err());
+        config.normalization = NormalizationPolicy::Insert;
+        assert!(config.validate().is_ok());
     }
 
     #[test]
@@ -70,6 +70,13 @@ pub enum CollectionError {
         reason: String,
     },
 
+    /// Entry cannot be stored, e.g. it lacks its segment timestamp
+    #[error("Invalid entry: {message}")]
+    InvalidEntry {
+        /// Error message
+        message: String,
+    },
+
     /// Core database error
     #[error("Database error: {0}")]
     DatabaseError(#[from] ruvector_core::error::RuvectorError),
@@ -0,0 +1,77 @@
+//! Copy-on-write collection forks
+//!
+//! [`CollectionManager::fork_collection`](crate::CollectionManager::fork_collection)
+//! creates a new collection holding the same entries as an existing one,
+//! for experiments such as trying a different quantization against
+//! production data. Where the filesystem supports it (btrfs, XFS, and other
+//! Linux filesystems with `FICLONE`), the fork's storage file shares its
+//! blocks with the source; a block is only copied once either side writes
+//! to it, so forking a multi-gigabyte collection is nearly instant and
+//! costs no extra disk up front. Elsewhere the file is copied.
+//!
+//! Fork and source are independent collections afterwards: writes to one
+//! are never visible in the other.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// How a fork's storage file was created
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForkMethod {
+    /// Blocks shared with the source until written (reflink)
+    Reflink,
+    /// Full copy; some platforms, such as APFS on macOS, still share blocks
+    Copy,
+}
+
+/// Outcome of a fork
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkReport {
+    /// Collection that was forked
+    pub source: String,
+    /// Name of the new collection
+    pub name: String,
+    /// Entries in the fork
+    pub vectors: usize,
+    /// Size of the storage file
+    pub bytes: u64,
+    /// How the storage file was created
+    pub method: ForkMethod,
+    /// Time spent, including opening the fork
+    pub elapsed_ms: u64,
+}
+
+/// Clone `source` to the new file `target`, sharing blocks when possible
+pub(crate) fn clone_file(source: &Path, target: &Path) -> std::io::Result<ForkMethod> {
+    #[cfg(target_os = "linux")]
+    if reflink(source, target).is_ok() {
+        return Ok(ForkMethod::Reflink);
+    }
+    std::fs::copy(source, target)?;
+    Ok(ForkMethod::Copy)
+}
+
+#[cfg(target_os = "linux")]
+fn reflink(source: &Path, target: &Path) -> std::io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    /// `_IOW(0x94, 9, int)` from `linux/fs.h`
+    const FICLONE: u32 = 0x4004_9409;
+
+    let src = std::fs::File::open(source)?;
+    let dst = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(target)?;
+    // SAFETY: both descriptors are open for the duration of the call, and
+    // FICLONE takes the source descriptor by value
+    let rc = unsafe { libc::ioctl(dst.as_raw_fd(), FICLONE as _, src.as_raw_fd()) };
+    if rc == 0 {
+        return Ok(());
+    }
+    let err = std::io::Error::last_os_error();
+    drop(dst);
+    let _ = std::fs::remove_file(target);
+    Err(err)
+}
@@ -7,6 +7,10 @@
 //! - **Multiple Collections**: Organize vectors into separate collections
 //! - **Alias Management**: Create aliases for collection names
 //! - **Collection Statistics**: Track collection metrics
+//! - **Re-embedding**: Rebuild a collection with a new embedding model and swap it in
+//! - **Forks**: Copy-on-write clones of a collection for experiments
+//! - **Time segments**: Partition a collection by day or week, prune searches by
+//!   time range and drop old segments for retention
 //! - **Thread-safe**: Concurrent access using DashMap
 //! - **Persistence**: Store collections on disk
 //!
@@ -28,6 +32,10 @@
 //!     hnsw_config: Some(HnswConfig::default()),
 //!     quantization: None,
 //!     on_disk_payload: true,
+//!     embedding_model: None,
+//!     strict_model: false,
+//!     normalization: Default::default(),
+//!     secondary_metrics: Vec::new(),
 //! };
 //!
 //! manager.create_collection("documents", config)?;
@@ -46,8 +54,14 @@
 
 pub mod collection;
 pub mod error;
+pub mod fork;
 pub mod manager;
+pub mod reembed;
+pub mod segment;
 
 pub use collection::{Collection, CollectionConfig, CollectionStats};
 pub use error::{CollectionError, Result};
+pub use fork::{ForkMethod, ForkReport};
 pub use manager::CollectionManager;
+pub use reembed::{ReembedConfig, ReembedProgress, ReembedReport};
+pub use segment::{SegmentConfig, SegmentGranularity, SegmentInfo, SegmentedCollection, TimeRange};
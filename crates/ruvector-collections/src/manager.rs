@@ -2,12 +2,21 @@
 
 use dashmap::DashMap;
 use parking_lot::RwLock;
+use ruvector_core::drift::{compare_embeddings, DriftConfig, DriftReport};
+use ruvector_core::{EmbeddingModel, EmbeddingProvider, EmbeddingStats};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::collection::{Collection, CollectionConfig, CollectionStats};
 use crate::error::{CollectionError, Result};
+use crate::fork::{clone_file, ForkReport};
+use crate::reembed::{
+    matches_filter, ReembedConfig, ReembedProgress, ReembedReport, REEMBED_SUFFIX,
+};
+use crate::segment::{
+    SegmentConfig, SegmentedCollection, SegmentedMetadata, SEGMENTED_METADATA_FILE,
+};
 
 /// Metadata for persisting collections
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -24,6 +33,9 @@ pub struct CollectionManager {
     /// Active collections
     collections: DashMap<String, Arc<RwLock<Collection>>>,
 
+    /// Time-partitioned collections
+    segmented: DashMap<String, Arc<SegmentedCollection>>,
+
     /// Alias mappings (alias -> collection_name)
     aliases: DashMap<String, String>,
 
@@ -52,6 +64,7 @@ impl CollectionManager {
 
         let manager = Self {
             collections: DashMap::new(),
+            segmented: DashMap::new(),
             aliases: DashMap::new(),
             base_path,
         };
@@ -76,20 +89,7 @@ impl CollectionManager {
         // Validate collection name
         Self::validate_name(name)?;
 
-        // Check if collection already exists
-        if self.collections.contains_key(name) {
-            return Err(CollectionError::CollectionAlreadyExists {
-                name: name.to_string(),
-            });
-        }
-
-        // Check if an alias with this name exists
-        if self.aliases.contains_key(name) {
-            return Err(CollectionError::InvalidName {
-                name: name.to_string(),
-                reason: "An alias with this name already exists".to_string(),
-            });
-        }
+        self.check_name_free(name)?;
 
         // Create storage path for this collection
         let storage_path = self.base_path.join(name);
@@ -113,6 +113,72 @@ impl CollectionManager {
         Ok(())
     }
 
+    /// Create a collection partitioned into time segments
+    ///
+    /// See [`crate::segment`]. Segments share `config` and are created as
+    /// entries arrive. The name is shared with regular collections and
+    /// aliases.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CollectionAlreadyExists` if a collection with the same name exists
+    pub fn create_segmented_collection(
+        &self,
+        name: &str,
+        config: CollectionConfig,
+        segments: SegmentConfig,
+    ) -> Result<Arc<SegmentedCollection>> {
+        Self::validate_name(name)?;
+        self.check_name_free(name)?;
+
+        let path = self.base_path.join(name);
+        let metadata = SegmentedMetadata {
+            name: name.to_string(),
+            config,
+            segments,
+            created_at: chrono::Utc::now().timestamp(),
+        };
+        let collection = Arc::new(SegmentedCollection::open(metadata.clone(), path.clone())?);
+        let json = serde_json::to_string_pretty(&metadata)?;
+        std::fs::write(path.join(SEGMENTED_METADATA_FILE), json)?;
+
+        self.segmented.insert(name.to_string(), collection.clone());
+        Ok(collection)
+    }
+
+    /// Get a segmented collection by name
+    pub fn get_segmented_collection(&self, name: &str) -> Option<Arc<SegmentedCollection>> {
+        self.segmented.get(name).map(|entry| entry.value().clone())
+    }
+
+    /// List all segmented collection names
+    pub fn list_segmented_collections(&self) -> Vec<String> {
+        self.segmented
+            .iter()
+            .map(|entry| entry.key().clone())
+            .collect()
+    }
+
+    /// Delete a segmented collection and all of its segments
+    ///
+    /// # Errors
+    ///
+    /// Returns `CollectionNotFound` if no such segmented collection exists
+    pub fn delete_segmented_collection(&self, name: &str) -> Result<()> {
+        if self.segmented.remove(name).is_none() {
+            return Err(CollectionError::CollectionNotFound {
+                name: name.to_string(),
+            });
+        }
+
+        let collection_path = self.base_path.join(name);
+        if collection_path.exists() {
+            std::fs::remove_dir_all(&collection_path)?;
+        }
+
+        Ok(())
+    }
+
     /// Delete a collection
     ///
     /// # Arguments
@@ -158,6 +224,68 @@ impl CollectionManager {
         Ok(())
     }
 
+    /// Create `name` as a copy-on-write fork of the collection `source`
+    ///
+    /// See [`crate::fork`]. `source` may be an alias. Writes to `source`
+    /// wait while its storage file is cloned.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CollectionNotFound` if `source` doesn't exist and
+    /// `CollectionAlreadyExists` if `name` is taken
+    pub fn fork_collection(&self, source: &str, name: &str) -> Result<ForkReport> {
+        Self::validate_name(name)?;
+        self.check_name_free(name)?;
+        let source_name = self
+            .resolve_alias(source)
+            .unwrap_or_else(|| source.to_string());
+        let collection = self.get_collection(&source_name).ok_or_else(|| {
+            CollectionError::CollectionNotFound {
+                name: source.to_string(),
+            }
+        })?;
+
+        let started = std::time::Instant::now();
+        let target_dir = self.base_path.join(name);
+        std::fs::create_dir_all(&target_dir)?;
+        let target_db = target_dir.join("vectors.db");
+
+        // Hold the write lock so the clone sees only committed transactions
+        let guard = collection.write();
+        let cloned = clone_file(
+            &self.base_path.join(&source_name).join("vectors.db"),
+            &target_db,
+        );
+        let config = guard.config.clone();
+        drop(guard);
+        let method = match cloned {
+            Ok(method) => method,
+            Err(e) => {
+                let _ = std::fs::remove_dir_all(&target_dir);
+                return Err(e.into());
+            }
+        };
+
+        let fork = Collection::new(
+            name.to_string(),
+            config,
+            target_db.to_string_lossy().to_string(),
+        )?;
+        self.save_collection_metadata(&fork)?;
+        let report = ForkReport {
+            source: source_name,
+            name: name.to_string(),
+            vectors: fork.db.len()?,
+            bytes: std::fs::metadata(&target_db)?.len(),
+            method,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        };
+
+        self.collections
+            .insert(name.to_string(), Arc::new(RwLock::new(fork)));
+        Ok(report)
+    }
+
     /// Get a collection by name or alias
     ///
     /// # Arguments
@@ -201,6 +329,217 @@ impl CollectionManager {
         guard.stats()
     }
 
+    /// Summarize the vectors of a collection: norms, mean and intrinsic
+    /// dimensionality
+    ///
+    /// Unlike [`CollectionManager::collection_stats`] this scans every
+    /// vector; see [`ruvector_core::embedding_stats`].
+    pub fn vector_stats(&self, name: &str) -> Result<EmbeddingStats> {
+        let collection =
+            self.get_collection(name)
+                .ok_or_else(|| CollectionError::CollectionNotFound {
+                    name: name.to_string(),
+                })?;
+
+        let guard = collection.read();
+        Ok(guard.db.collection_stats()?)
+    }
+
+    /// Measure embedding drift between two collections holding the same ids
+    ///
+    /// Typically `baseline` is the collection an alias currently points to and
+    /// `candidate` its re-embedded replacement; inspect the report before
+    /// calling [`switch_alias`](Self::switch_alias).
+    pub fn compare_collections(
+        &self,
+        baseline: &str,
+        candidate: &str,
+        config: &DriftConfig,
+    ) -> Result<DriftReport> {
+        let lookup = |name: &str| {
+            self.get_collection(name)
+                .ok_or_else(|| CollectionError::CollectionNotFound {
+                    name: name.to_string(),
+                })
+        };
+        let baseline = lookup(baseline)?;
+        let candidate = lookup(candidate)?;
+
+        let baseline = baseline.read();
+        let candidate = candidate.read();
+        Ok(compare_embeddings(&baseline.db, &candidate.db, config)?)
+    }
+
+    /// Re-embed a collection with `provider` using the default settings
+    ///
+    /// See [`reembed_with`](Self::reembed_with).
+    pub fn reembed(
+        &self,
+        collection: &str,
+        provider: &dyn EmbeddingProvider,
+        filter: Option<HashMap<String, serde_json::Value>>,
+    ) -> Result<ReembedReport> {
+        let config = ReembedConfig {
+            filter,
+            ..Default::default()
+        };
+        self.reembed_with(collection, provider, &config, |_| {})
+    }
+
+    /// Re-embed a collection into a shadow collection and swap it in
+    ///
+    /// `collection` may be a name or an alias. The source is read one batch
+    /// at a time, so writers are only blocked briefly. Running the job again
+    /// with the same target resumes it. When started through an alias and
+    /// `config.switch_alias` is set, the alias points at the shadow
+    /// collection once every entry has been written.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CollectionNotFound` if the source doesn't exist, and
+    /// `InvalidConfiguration` if an existing target has different dimensions
+    /// than `provider`. Embedding failures abort the job; completed batches
+    /// are kept for the next run.
+    pub fn reembed_with<F>(
+        &self,
+        collection: &str,
+        provider: &dyn EmbeddingProvider,
+        config: &ReembedConfig,
+        mut on_progress: F,
+    ) -> Result<ReembedReport>
+    where
+        F: FnMut(ReembedProgress),
+    {
+        let start = std::time::Instant::now();
+        let resolved = self.resolve_alias(collection);
+        let alias = resolved.is_some().then(|| collection.to_string());
+        let source_name = resolved.unwrap_or_else(|| collection.to_string());
+        let source = self
+            .collections
+            .get(&source_name)
+            .map(|e| e.value().clone())
+            .ok_or_else(|| CollectionError::CollectionNotFound {
+                name: source_name.clone(),
+            })?;
+
+        if config.batch_size == 0 {
+            return Err(CollectionError::InvalidConfiguration {
+                message: "batch_size must be greater than zero".to_string(),
+            });
+        }
+
+        let target_name = config
+            .target
+            .clone()
+            .unwrap_or_else(|| format!("{}{}", source_name, REEMBED_SUFFIX));
+        if target_name == source_name {
+            return Err(CollectionError::InvalidConfiguration {
+                message: "re-embed target must differ from the source".to_string(),
+            });
+        }
+
+        let dimensions = provider.dimensions();
+        if !self.collections.contains_key(&target_name) {
+            let mut target_config = source.read().config.clone();
+            target_config.dimensions = dimensions;
+            target_config.embedding_model = Some(EmbeddingModel::new(provider.name(), dimensions));
+            self.create_collection(&target_name, target_config)?;
+        }
+        let target = self
+            .collections
+            .get(&target_name)
+            .map(|e| e.value().clone())
+            .ok_or_else(|| CollectionError::CollectionNotFound {
+                name: target_name.clone(),
+            })?;
+        if target.read().config.dimensions != dimensions {
+            return Err(CollectionError::InvalidConfiguration {
+                message: format!(
+                    "target '{}' has {} dimensions but provider '{}' produces {}",
+                    target_name,
+                    target.read().config.dimensions,
+                    provider.name(),
+                    dimensions
+                ),
+            });
+        }
+
+        let ids = source.read().db.keys()?;
+        let mut progress = ReembedProgress {
+            processed: 0,
+            total: ids.len(),
+            reembedded: 0,
+            copied: 0,
+            skipped: 0,
+            resumed: 0,
+        };
+        let mut skipped_ids = Vec::new();
+
+        for batch in ids.chunks(config.batch_size) {
+            let mut entries = Vec::with_capacity(batch.len());
+            {
+                let source = source.read();
+                let target = target.read();
+                for id in batch {
+                    progress.processed += 1;
+                    if target.db.get(id)?.is_some() {
+                        progress.resumed += 1;
+                        continue;
+                    }
+                    // Deleted since the id list was taken
+                    let Some(mut entry) = source.db.get(id)? else {
+                        continue;
+                    };
+
+                    let text = matches_filter(config.filter.as_ref(), entry.metadata.as_ref())
+                        .then(|| {
+                            entry
+                                .metadata
+                                .as_ref()
+                                .and_then(|m| m.get(&config.text_field))
+                                .and_then(|v| v.as_str())
+                        })
+                        .flatten();
+                    if let Some(text) = text {
+                        entry.vector = provider.embed(text)?;
+                        progress.reembedded += 1;
+                    } else if entry.vector.len() == dimensions {
+                        progress.copied += 1;
+                    } else {
+                        progress.skipped += 1;
+                        skipped_ids.push(id.clone());
+                        continue;
+                    }
+                    entries.push(entry);
+                }
+            }
+
+            if !entries.is_empty() {
+                let mut target = target.write();
+                target.db.insert_batch(entries)?;
+                target.touch();
+            }
+            on_progress(progress);
+        }
+
+        let switched_alias = match alias {
+            Some(alias) if config.switch_alias => {
+                self.switch_alias(&alias, &target_name)?;
+                Some(alias)
+            }
+            _ => None,
+        };
+
+        Ok(ReembedReport {
+            source: source_name,
+            target: target_name,
+            progress,
+            skipped_ids,
+            switched_alias,
+            elapsed_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+
     // ===== Alias Management =====
 
     /// Create an alias for a collection
@@ -226,7 +565,7 @@ impl CollectionManager {
         }
 
         // Check if a collection with this name exists
-        if self.collections.contains_key(alias) {
+        if self.collections.contains_key(alias) || self.segmented.contains_key(alias) {
             return Err(CollectionError::InvalidName {
                 name: alias.to_string(),
                 reason: "A collection with this name already exists".to_string(),
@@ -338,6 +677,24 @@ impl CollectionManager {
 
     // ===== Internal Methods =====
 
+    /// Check that no collection, segmented collection or alias uses `name`
+    fn check_name_free(&self, name: &str) -> Result<()> {
+        if self.collections.contains_key(name) || self.segmented.contains_key(name) {
+            return Err(CollectionError::CollectionAlreadyExists {
+                name: name.to_string(),
+            });
+        }
+
+        if self.aliases.contains_key(name) {
+            return Err(CollectionError::InvalidName {
+                name: name.to_string(),
+                reason: "An alias with this name already exists".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
     /// Validate a collection or alias name
     fn validate_name(name: &str) -> Result<()> {
         if name.is_empty() {
@@ -395,6 +752,16 @@ impl CollectionManager {
                     continue;
                 }
 
+                let segmented_path = path.join(SEGMENTED_METADATA_FILE);
+                if segmented_path.exists() {
+                    let json = std::fs::read_to_string(segmented_path)?;
+                    let metadata: SegmentedMetadata = serde_json::from_str(&json)?;
+                    if let Ok(collection) = SegmentedCollection::open(metadata, path.clone()) {
+                        self.segmented.insert(name.clone(), Arc::new(collection));
+                    }
+                    continue;
+                }
+
                 // Try to load collection metadata
                 if let Ok(metadata) = self.load_collection_metadata(&name) {
                     let db_path = path.join("vectors.db").to_string_lossy().to_string();
@@ -519,4 +886,199 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_reembed_switches_alias_and_resumes() -> Result<()> {
+        use ruvector_core::{HashEmbedding, VectorEntry};
+
+        let temp_dir = std::env::temp_dir().join("ruvector_test_reembed");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let manager = CollectionManager::new(temp_dir.clone())?;
+
+        let mut config = CollectionConfig::with_dimensions(4);
+        config.hnsw_config = None;
+        manager.create_collection("docs", config)?;
+        manager.create_alias("current", "docs")?;
+
+        let entries: Vec<VectorEntry> = (0..10)
+            .map(|i| {
+                let mut metadata = HashMap::new();
+                if i < 8 {
+                    metadata.insert("text".to_string(), serde_json::json!(format!("doc {}", i)));
+                }
+                VectorEntry {
+                    id: Some(format!("doc_{}", i)),
+                    vector: vec![i as f32; 4],
+                    metadata: Some(metadata),
+                }
+            })
+            .collect();
+        manager
+            .get_collection("docs")
+            .unwrap()
+            .read()
+            .db
+            .insert_batch(entries)?;
+
+        let provider = HashEmbedding::new(8);
+        let mut batches = 0;
+        let report = manager.reembed_with(
+            "current",
+            &provider,
+            &ReembedConfig {
+                batch_size: 3,
+                ..Default::default()
+            },
+            |_| batches += 1,
+        )?;
+
+        assert_eq!(batches, 4);
+        assert_eq!(report.target, "docs-reembed");
+        assert_eq!(report.progress.reembedded, 8);
+        // Entries without text can't keep a 4-d vector in an 8-d collection
+        assert_eq!(report.progress.skipped, 2);
+        assert_eq!(report.switched_alias.as_deref(), Some("current"));
+        assert_eq!(
+            manager.resolve_alias("current").as_deref(),
+            Some("docs-reembed")
+        );
+
+        let target = manager.get_collection("current").unwrap();
+        let entry = target.read().db.get("doc_3")?.unwrap();
+        assert_eq!(entry.vector, provider.embed("doc 3")?);
+
+        // A second run against the same target only picks up what's missing
+        let report = manager.reembed("docs", &provider, None)?;
+        assert_eq!(report.progress.resumed, 8);
+        assert_eq!(report.progress.reembedded, 0);
+        assert!(report.switched_alias.is_none());
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        Ok(())
+    }
+
+    #[test]
+    fn test_segmented_collection() -> Result<()> {
+        use crate::segment::{SegmentGranularity, TimeRange};
+        use ruvector_core::types::SearchQuery;
+        use ruvector_core::VectorEntry;
+
+        const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+        let temp_dir = std::env::temp_dir().join("ruvector_test_segmented");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let manager = CollectionManager::new(temp_dir.clone())?;
+
+        let mut config = CollectionConfig::with_dimensions(2);
+        config.hnsw_config = None;
+        let segments = SegmentConfig {
+            time_field: "ts".to_string(),
+            granularity: SegmentGranularity::Daily,
+            unit: Default::default(),
+            retention_secs: Some(2 * 24 * 60 * 60),
+        };
+        let events = manager.create_segmented_collection("events", config, segments)?;
+        assert!(manager.create_alias("events", "events").is_err());
+
+        let day0 = 1_714_521_600_000;
+        let entries = (0..4)
+            .map(|day| VectorEntry {
+                id: Some(format!("e{}", day)),
+                vector: vec![1.0, day as f32],
+                metadata: Some(HashMap::from([(
+                    "ts".to_string(),
+                    serde_json::json!(day0 + day * DAY_MS + 1000),
+                )])),
+            })
+            .collect();
+        events.insert_batch(entries)?;
+        assert_eq!(events.list_segments()?.len(), 4);
+        assert!(events
+            .insert(VectorEntry {
+                id: None,
+                vector: vec![1.0, 0.0],
+                metadata: None,
+            })
+            .is_err());
+
+        // Only days 1 and 2 are searched
+        let query = SearchQuery {
+            vector: vec![1.0, 0.0],
+            k: 10,
+            filter: None,
+            ef_search: None,
+        };
+        let range = TimeRange {
+            start_ms: Some(day0 + DAY_MS),
+            end_ms: Some(day0 + 3 * DAY_MS),
+        };
+        let ids: Vec<String> = events
+            .search(query.clone(), range)?
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(ids, vec!["e1".to_string(), "e2".to_string()]);
+
+        // Segments survive a restart; retention drops whole days
+        drop(events);
+        let manager = CollectionManager::new(temp_dir.clone())?;
+        let events = manager.get_segmented_collection("events").unwrap();
+        assert_eq!(events.len()?, 4);
+        let dropped = events.apply_retention(day0 + 4 * DAY_MS)?;
+        assert_eq!(
+            dropped,
+            vec!["2024-05-01".to_string(), "2024-05-02".to_string()]
+        );
+        assert_eq!(events.search(query, TimeRange::default())?.len(), 2);
+        assert!(!temp_dir.join("events/segments/2024-05-01").exists());
+
+        manager.delete_segmented_collection("events")?;
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fork_collection() -> Result<()> {
+        use ruvector_core::VectorEntry;
+
+        let temp_dir = std::env::temp_dir().join("ruvector_test_fork");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let manager = CollectionManager::new(temp_dir.clone())?;
+
+        let mut config = CollectionConfig::with_dimensions(2);
+        config.hnsw_config = None;
+        manager.create_collection("prod", config)?;
+        manager.create_alias("live", "prod")?;
+        let entry = |id: &str| VectorEntry {
+            id: Some(id.to_string()),
+            vector: vec![1.0, 0.0],
+            metadata: None,
+        };
+        manager
+            .get_collection("prod")
+            .unwrap()
+            .read()
+            .db
+            .insert(entry("a"))?;
+
+        let report = manager.fork_collection("live", "experiment")?;
+        assert_eq!(report.source, "prod");
+        assert_eq!(report.vectors, 1);
+        assert!(manager.fork_collection("prod", "experiment").is_err());
+        assert!(manager.fork_collection("missing", "other").is_err());
+
+        // Fork and source diverge independently
+        let fork = manager.get_collection("experiment").unwrap();
+        fork.read().db.insert(entry("b"))?;
+        assert_eq!(fork.read().db.len()?, 2);
+        assert_eq!(manager.get_collection("prod").unwrap().read().db.len()?, 1);
+
+        // The fork is a regular collection after a restart
+        drop(fork);
+        drop(manager);
+        let manager = CollectionManager::new(temp_dir.clone())?;
+        assert_eq!(manager.collection_stats("experiment")?.vectors_count, 2);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        Ok(())
+    }
 }
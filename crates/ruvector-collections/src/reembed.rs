@@ -0,0 +1,121 @@
+//! Re-embedding collections with a new embedding provider
+//!
+//! [`CollectionManager::reembed`](crate::CollectionManager::reembed) replaces
+//! the hand-run "new model" procedure:
+//!
+//! 1. Create a shadow collection (`{source}-reembed` by default) with the
+//!    provider's dimensions.
+//! 2. Stream the source in batches. Entries matching the filter get a new
+//!    vector from `metadata[text_field]`. Other entries keep their old
+//!    vector, provided the dimensions still match.
+//! 3. If the job was started through an alias, switch the alias to the
+//!    shadow collection in one step.
+//!
+//! The job can be resumed: ids already in the shadow collection are skipped,
+//! so an interrupted run continues where it stopped. The source collection
+//! is left in place for rollback or [`compare_collections`].
+//!
+//! [`compare_collections`]: crate::CollectionManager::compare_collections
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Suffix of the default shadow collection name
+pub const REEMBED_SUFFIX: &str = "-reembed";
+
+/// Re-embedding settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReembedConfig {
+    /// Metadata field holding the text to embed
+    pub text_field: String,
+    /// Only entries whose metadata matches every key are re-embedded
+    pub filter: Option<HashMap<String, serde_json::Value>>,
+    /// Entries read and written per batch
+    pub batch_size: usize,
+    /// Shadow collection name; defaults to `{source}-reembed`
+    pub target: Option<String>,
+    /// Switch the alias to the shadow collection when the job was started
+    /// through an alias
+    pub switch_alias: bool,
+}
+
+impl Default for ReembedConfig {
+    fn default() -> Self {
+        Self {
+            text_field: "text".to_string(),
+            filter: None,
+            batch_size: 256,
+            target: None,
+            switch_alias: true,
+        }
+    }
+}
+
+/// Progress after each batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReembedProgress {
+    /// Source entries handled so far
+    pub processed: usize,
+    /// Source entries when the job started
+    pub total: usize,
+    /// Entries given a new vector
+    pub reembedded: usize,
+    /// Entries copied with their old vector
+    pub copied: usize,
+    /// Entries left out of the shadow collection
+    pub skipped: usize,
+    /// Entries already present from an earlier run
+    pub resumed: usize,
+}
+
+/// Outcome of a re-embedding job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReembedReport {
+    /// Collection that was read
+    pub source: String,
+    /// Shadow collection that was written
+    pub target: String,
+    /// Final counts
+    pub progress: ReembedProgress,
+    /// Ids left out: no text to embed and old vector of the wrong size
+    pub skipped_ids: Vec<String>,
+    /// Alias switched to `target`, if any
+    pub switched_alias: Option<String>,
+    /// Total time in milliseconds
+    pub elapsed_ms: u64,
+}
+
+/// Whether `metadata` satisfies `filter`, with the same semantics as search
+pub(crate) fn matches_filter(
+    filter: Option<&HashMap<String, serde_json::Value>>,
+    metadata: Option<&HashMap<String, serde_json::Value>>,
+) -> bool {
+    match (filter, metadata) {
+        (None, _) => true,
+        (Some(filter), Some(metadata)) => filter
+            .iter()
+            .all(|(key, value)| metadata.get(key) == Some(value)),
+        (Some(_), None) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_matches_filter() {
+        let mut metadata = HashMap::new();
+        metadata.insert("lang".to_string(), json!("en"));
+        let mut filter = HashMap::new();
+        filter.insert("lang".to_string(), json!("en"));
+
+        assert!(matches_filter(None, None));
+        assert!(matches_filter(Some(&filter), Some(&metadata)));
+        assert!(!matches_filter(Some(&filter), None));
+
+        filter.insert("lang".to_string(), json!("de"));
+        assert!(!matches_filter(Some(&filter), Some(&metadata)));
+    }
+}
@@ -0,0 +1,431 @@
+//! Time-partitioned collections
+//!
+//! A [`SegmentedCollection`] splits its vectors into daily or weekly
+//! segments by a timestamp metadata field. Each segment is an ordinary
+//! [`Collection`] in its own directory, so:
+//!
+//! - searches restricted to a [`TimeRange`] only visit segments that overlap
+//!   it, and
+//! - retention drops whole segments by deleting their directory instead of
+//!   deleting vectors one by one.
+//!
+//! Segments are created on the first insert that falls into them. Timestamps
+//! are read like [`ruvector_core::recency`] reads them: numbers in the
+//! configured [`TimeUnit`] or RFC 3339 strings. Segment boundaries are UTC;
+//! weeks start on Monday.
+
+use parking_lot::RwLock;
+use ruvector_core::recency::{timestamp_ms, TimeUnit};
+use ruvector_core::types::{SearchQuery, SearchResult, VectorEntry, VectorId};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::collection::{Collection, CollectionConfig};
+use crate::error::{CollectionError, Result};
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Candidates fetched per result from segments that only partly overlap the
+/// query's time range
+const PARTIAL_OVERSAMPLE: usize = 4;
+
+/// Length of a segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentGranularity {
+    /// One segment per UTC day
+    Daily,
+    /// One segment per ISO week (Monday to Sunday, UTC)
+    Weekly,
+}
+
+impl SegmentGranularity {
+    /// Start of the segment containing `ms`, in milliseconds since the epoch
+    pub fn segment_start(self, ms: i64) -> i64 {
+        let day = ms.div_euclid(DAY_MS);
+        match self {
+            SegmentGranularity::Daily => day * DAY_MS,
+            // 1970-01-01 was a Thursday, three days after a Monday
+            SegmentGranularity::Weekly => ((day + 3).div_euclid(7) * 7 - 3) * DAY_MS,
+        }
+    }
+
+    /// Length of a segment in milliseconds
+    pub fn length_ms(self) -> i64 {
+        match self {
+            SegmentGranularity::Daily => DAY_MS,
+            SegmentGranularity::Weekly => 7 * DAY_MS,
+        }
+    }
+}
+
+/// How a collection is split into segments
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SegmentConfig {
+    /// Metadata field holding each entry's timestamp
+    pub time_field: String,
+    /// Length of a segment
+    pub granularity: SegmentGranularity,
+    /// Unit of numeric timestamps
+    #[serde(default)]
+    pub unit: TimeUnit,
+    /// Segments that ended longer ago than this many seconds are dropped by
+    /// [`SegmentedCollection::apply_retention`]
+    #[serde(default)]
+    pub retention_secs: Option<u64>,
+}
+
+/// Half-open time range `[start_ms, end_ms)`; an unset bound is unbounded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeRange {
+    /// Inclusive lower bound in milliseconds since the epoch
+    pub start_ms: Option<i64>,
+    /// Exclusive upper bound in milliseconds since the epoch
+    pub end_ms: Option<i64>,
+}
+
+impl TimeRange {
+    /// Whether `ms` lies in the range
+    pub fn contains(&self, ms: i64) -> bool {
+        self.start_ms.map_or(true, |start| ms >= start) && self.end_ms.map_or(true, |end| ms < end)
+    }
+
+    /// Whether `[start, end)` overlaps the range
+    fn overlaps(&self, start: i64, end: i64) -> bool {
+        self.start_ms.map_or(true, |s| end > s) && self.end_ms.map_or(true, |e| start < e)
+    }
+
+    /// Whether `[start, end)` lies entirely inside the range
+    fn covers(&self, start: i64, end: i64) -> bool {
+        self.start_ms.map_or(true, |s| start >= s) && self.end_ms.map_or(true, |e| end <= e)
+    }
+}
+
+/// Summary of one segment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SegmentInfo {
+    /// Segment name, the UTC date it starts on (`YYYY-MM-DD`)
+    pub key: String,
+    /// Start in milliseconds since the epoch (inclusive)
+    pub start_ms: i64,
+    /// End in milliseconds since the epoch (exclusive)
+    pub end_ms: i64,
+    /// Number of vectors in the segment
+    pub vectors: usize,
+}
+
+/// Persisted description of a segmented collection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SegmentedMetadata {
+    pub(crate) name: String,
+    pub(crate) config: CollectionConfig,
+    pub(crate) segments: SegmentConfig,
+    pub(crate) created_at: i64,
+}
+
+/// File describing a segmented collection inside its directory
+pub(crate) const SEGMENTED_METADATA_FILE: &str = "segmented.json";
+
+/// A collection partitioned into time segments
+pub struct SegmentedCollection {
+    /// Collection name
+    pub name: String,
+    /// Configuration shared by every segment
+    pub config: CollectionConfig,
+    /// How entries are assigned to segments
+    pub segments: SegmentConfig,
+    /// When the collection was created (Unix timestamp in seconds)
+    pub created_at: i64,
+    path: PathBuf,
+    open: RwLock<BTreeMap<i64, Collection>>,
+}
+
+impl std::fmt::Debug for SegmentedCollection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SegmentedCollection")
+            .field("name", &self.name)
+            .field("config", &self.config)
+            .field("segments", &self.segments)
+            .field("open", &self.open.read().len())
+            .finish()
+    }
+}
+
+impl SegmentedCollection {
+    /// Open (or create) a segmented collection stored under `path`
+    ///
+    /// Existing segment directories are opened.
+    pub(crate) fn open(metadata: SegmentedMetadata, path: PathBuf) -> Result<Self> {
+        metadata.config.validate()?;
+        if metadata.segments.time_field.is_empty() {
+            return Err(CollectionError::InvalidConfiguration {
+                message: "Segment time field must not be empty".to_string(),
+            });
+        }
+
+        let collection = Self {
+            name: metadata.name,
+            config: metadata.config,
+            segments: metadata.segments,
+            created_at: metadata.created_at,
+            path,
+            open: RwLock::new(BTreeMap::new()),
+        };
+
+        let segments_dir = collection.path.join("segments");
+        std::fs::create_dir_all(&segments_dir)?;
+        let mut open = collection.open.write();
+        for entry in std::fs::read_dir(&segments_dir)? {
+            let key = entry?.file_name().to_string_lossy().to_string();
+            let Ok(date) = chrono::NaiveDate::parse_from_str(&key, "%Y-%m-%d") else {
+                continue;
+            };
+            let start = date
+                .and_hms_opt(0, 0, 0)
+                .expect("midnight is valid")
+                .and_utc()
+                .timestamp_millis();
+            open.insert(start, collection.open_segment(start)?);
+        }
+        drop(open);
+
+        Ok(collection)
+    }
+
+    /// Store `entry` in the segment of its timestamp, creating the segment if needed
+    ///
+    /// Ids are only unique within a segment: re-inserting an id with a
+    /// timestamp in another segment stores a second copy there.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidEntry` if the entry has no readable timestamp
+    pub fn insert(&self, entry: VectorEntry) -> Result<VectorId> {
+        let start = self.segment_of(&entry)?;
+        self.with_segment(start, |segment| Ok(segment.db.insert(entry)?))
+    }
+
+    /// Store entries, grouped by segment
+    ///
+    /// Nothing is stored if any entry lacks a readable timestamp. Ids are
+    /// returned in segment order.
+    pub fn insert_batch(&self, entries: Vec<VectorEntry>) -> Result<Vec<VectorId>> {
+        let mut by_segment: BTreeMap<i64, Vec<VectorEntry>> = BTreeMap::new();
+        for entry in entries {
+            by_segment
+                .entry(self.segment_of(&entry)?)
+                .or_default()
+                .push(entry);
+        }
+
+        let mut ids = Vec::new();
+        for (start, entries) in by_segment {
+            ids.extend(self.with_segment(start, |segment| Ok(segment.db.insert_batch(entries)?))?);
+        }
+        Ok(ids)
+    }
+
+    /// Search the segments overlapping `range`
+    ///
+    /// Segments entirely outside the range are skipped. Results from
+    /// segments that only partly overlap it are checked against each
+    /// entry's timestamp. Results are merged by score, best first.
+    pub fn search(&self, query: SearchQuery, range: TimeRange) -> Result<Vec<SearchResult>> {
+        let k = query.k;
+        let length = self.segments.granularity.length_ms();
+        let mut results = Vec::new();
+
+        for (&start, segment) in self.open.read().iter() {
+            let end = start + length;
+            if !range.overlaps(start, end) {
+                continue;
+            }
+            if range.covers(start, end) {
+                results.extend(segment.db.search(query.clone())?);
+                continue;
+            }
+            let candidates = segment.db.search(SearchQuery {
+                k: k.saturating_mul(PARTIAL_OVERSAMPLE),
+                ..query.clone()
+            })?;
+            results.extend(candidates.into_iter().filter(|result| {
+                result
+                    .metadata
+                    .as_ref()
+                    .and_then(|m| m.get(&self.segments.time_field))
+                    .and_then(|v| timestamp_ms(v, self.segments.unit))
+                    .is_some_and(|ms| range.contains(ms))
+            }));
+        }
+
+        results.sort_by(|a, b| a.score.total_cmp(&b.score));
+        results.truncate(k);
+        Ok(results)
+    }
+
+    /// Get an entry by id from whichever segment holds it
+    pub fn get(&self, id: &str) -> Result<Option<VectorEntry>> {
+        for segment in self.open.read().values() {
+            if let Some(entry) = segment.db.get(id)? {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Delete an entry by id from every segment holding it
+    pub fn delete(&self, id: &str) -> Result<bool> {
+        let mut deleted = false;
+        for segment in self.open.read().values() {
+            deleted |= segment.db.delete(id)?;
+        }
+        Ok(deleted)
+    }
+
+    /// Segments in time order
+    pub fn list_segments(&self) -> Result<Vec<SegmentInfo>> {
+        let length = self.segments.granularity.length_ms();
+        self.open
+            .read()
+            .iter()
+            .map(|(&start, segment)| {
+                Ok(SegmentInfo {
+                    key: segment_key(start),
+                    start_ms: start,
+                    end_ms: start + length,
+                    vectors: segment.db.len()?,
+                })
+            })
+            .collect()
+    }
+
+    /// Total vectors across segments
+    pub fn len(&self) -> Result<usize> {
+        let mut total = 0;
+        for segment in self.open.read().values() {
+            total += segment.db.len()?;
+        }
+        Ok(total)
+    }
+
+    /// Whether no segment holds any vector
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Drop every segment that ends at or before `cutoff_ms`
+    ///
+    /// Each segment is removed by deleting its directory. Returns the keys
+    /// of the dropped segments.
+    pub fn drop_segments_before(&self, cutoff_ms: i64) -> Result<Vec<String>> {
+        let length = self.segments.granularity.length_ms();
+        let mut open = self.open.write();
+        let expired: Vec<i64> = open
+            .keys()
+            .copied()
+            .filter(|&start| start + length <= cutoff_ms)
+            .collect();
+
+        let mut dropped = Vec::with_capacity(expired.len());
+        for start in expired {
+            open.remove(&start);
+            let key = segment_key(start);
+            let dir = self.path.join("segments").join(&key);
+            if dir.exists() {
+                std::fs::remove_dir_all(&dir)?;
+            }
+            dropped.push(key);
+        }
+        Ok(dropped)
+    }
+
+    /// Drop segments older than the configured retention period
+    ///
+    /// Does nothing without `retention_secs`. Returns the dropped keys.
+    pub fn apply_retention(&self, now_ms: i64) -> Result<Vec<String>> {
+        match self.segments.retention_secs {
+            Some(secs) => {
+                let retention_ms = i64::try_from(secs.saturating_mul(1000)).unwrap_or(i64::MAX);
+                self.drop_segments_before(now_ms.saturating_sub(retention_ms))
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn segment_of(&self, entry: &VectorEntry) -> Result<i64> {
+        let ms = entry
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get(&self.segments.time_field))
+            .and_then(|v| timestamp_ms(v, self.segments.unit))
+            .ok_or_else(|| CollectionError::InvalidEntry {
+                message: format!(
+                    "Entry {} has no readable '{}' timestamp",
+                    entry.id.as_deref().unwrap_or("<new>"),
+                    self.segments.time_field
+                ),
+            })?;
+        Ok(self.segments.granularity.segment_start(ms))
+    }
+
+    fn with_segment<T>(
+        &self,
+        start: i64,
+        write: impl FnOnce(&Collection) -> Result<T>,
+    ) -> Result<T> {
+        if let Some(segment) = self.open.read().get(&start) {
+            return write(segment);
+        }
+        let mut open = self.open.write();
+        if !open.contains_key(&start) {
+            let segment = self.open_segment(start)?;
+            open.insert(start, segment);
+        }
+        write(&open[&start])
+    }
+
+    fn open_segment(&self, start: i64) -> Result<Collection> {
+        let key = segment_key(start);
+        let dir = self.path.join("segments").join(&key);
+        std::fs::create_dir_all(&dir)?;
+        Collection::new(
+            format!("{}/{}", self.name, key),
+            self.config.clone(),
+            dir.join("vectors.db").to_string_lossy().to_string(),
+        )
+    }
+}
+
+/// Directory name of the segment starting at `start_ms`
+fn segment_key(start_ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(start_ms)
+        .map(|t| t.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| start_ms.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_boundaries() {
+        // 2024-05-01 was a Wednesday
+        let may_first = 1_714_521_600_000;
+        let noon = may_first + DAY_MS / 2;
+        assert_eq!(SegmentGranularity::Daily.segment_start(noon), may_first);
+        let monday = SegmentGranularity::Weekly.segment_start(noon);
+        assert_eq!(segment_key(monday), "2024-04-29");
+        assert_eq!(SegmentGranularity::Weekly.segment_start(-1), -3 * DAY_MS);
+
+        let range = TimeRange {
+            start_ms: Some(noon),
+            end_ms: None,
+        };
+        assert!(range.overlaps(may_first, may_first + DAY_MS));
+        assert!(!range.covers(may_first, may_first + DAY_MS));
+        assert!(!range.overlaps(may_first - DAY_MS, may_first));
+        assert!(range.contains(noon));
+        assert!(!range.contains(noon - 1));
+    }
+}
@@ -135,7 +135,7 @@ fn bench_arena_allocation(c: &mut Criterion) {
 
     group.bench_function("arena_allocation", |bench| {
         bench.iter(|| {
-            let arena = Arena::new(1024 * 1024);
+            let arena = Arena::with_tag(1024 * 1024, "bench_batch");
             let mut vecs = Vec::new();
             for _ in 0..num_allocations {
                 let mut v = arena.alloc_vec::<f32>(vec_size);
@@ -0,0 +1,175 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use ruvector_core::lockfree::{
+    contention_stats, set_contention_tracking, LockFreeStats, LockFreeWorkQueue, ObjectPool,
+};
+use ruvector_core::types::{DbOptions, SearchQuery};
+use ruvector_core::{VectorDB, VectorEntry};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use tempfile::tempdir;
+
+const THREADS: [usize; 4] = [1, 2, 4, 8];
+const OPS_PER_THREAD: usize = 1000;
+
+fn bench_stats_contention(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lockfree_stats_contention");
+
+    for &threads in THREADS.iter() {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(threads),
+            &threads,
+            |bench, &threads| {
+                let stats = Arc::new(LockFreeStats::new());
+                bench.iter(|| {
+                    thread::scope(|s| {
+                        for _ in 0..threads {
+                            let stats = Arc::clone(&stats);
+                            s.spawn(move || {
+                                for i in 0..OPS_PER_THREAD {
+                                    stats.record_query(black_box(i as u64));
+                                }
+                            });
+                        }
+                    });
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn bench_pool_and_queue_contention(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lockfree_pool_queue_contention");
+
+    for &threads in THREADS.iter() {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(threads),
+            &threads,
+            |bench, &threads| {
+                // Fewer pooled objects than threads forces waiting on returns
+                let pool = Arc::new(ObjectPool::new(threads.max(2) / 2, || {
+                    Vec::<f32>::with_capacity(128)
+                }));
+                let queue = Arc::new(LockFreeWorkQueue::new(64));
+                set_contention_tracking(true);
+                let before = contention_stats();
+
+                bench.iter(|| {
+                    thread::scope(|s| {
+                        for t in 0..threads {
+                            let pool = Arc::clone(&pool);
+                            let queue = Arc::clone(&queue);
+                            s.spawn(move || {
+                                for i in 0..OPS_PER_THREAD {
+                                    let mut buffer = pool.acquire();
+                                    buffer.push(i as f32);
+                                    buffer.clear();
+                                    if t % 2 == 0 {
+                                        let _ = queue.try_push(i);
+                                    } else {
+                                        black_box(queue.try_pop());
+                                    }
+                                }
+                            });
+                        }
+                    });
+                });
+
+                let delta = contention_stats().since(&before);
+                eprintln!("threads={} contention: {:?}", threads, delta);
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Readers search while writers insert into the same database
+fn bench_contended_read_write(c: &mut Criterion) {
+    let mut group = c.benchmark_group("contended_read_write");
+    group.sample_size(10);
+
+    let dimensions = 64;
+    for &writers in [0usize, 1, 2].iter() {
+        let readers = 4;
+        group.bench_with_input(
+            BenchmarkId::new(format!("{}_readers", readers), writers),
+            &writers,
+            |bench, &writers| {
+                let dir = tempdir().unwrap();
+                let mut options = DbOptions::default();
+                options.storage_path = dir.path().join("bench.db").to_string_lossy().to_string();
+                options.dimensions = dimensions;
+
+                let db = Arc::new(VectorDB::new(options).unwrap());
+                let seed: Vec<VectorEntry> = (0..2000)
+                    .map(|i| VectorEntry {
+                        id: Some(format!("seed_{}", i)),
+                        vector: (0..dimensions)
+                            .map(|j| ((i * 31 + j) % 97) as f32 / 97.0)
+                            .collect(),
+                        metadata: None,
+                    })
+                    .collect();
+                db.insert_batch(seed).unwrap();
+                let next_id = Arc::new(AtomicUsize::new(0));
+
+                bench.iter(|| {
+                    let done = AtomicBool::new(false);
+                    thread::scope(|s| {
+                        for _ in 0..writers {
+                            let db = Arc::clone(&db);
+                            let next_id = Arc::clone(&next_id);
+                            let done = &done;
+                            s.spawn(move || {
+                                while !done.load(Ordering::Relaxed) {
+                                    let i = next_id.fetch_add(1, Ordering::Relaxed);
+                                    db.insert(VectorEntry {
+                                        id: Some(format!("w_{}", i)),
+                                        vector: vec![(i % 100) as f32 / 100.0; dimensions],
+                                        metadata: None,
+                                    })
+                                    .unwrap();
+                                }
+                            });
+                        }
+
+                        let reader_handles: Vec<_> = (0..readers)
+                            .map(|r| {
+                                let db = Arc::clone(&db);
+                                s.spawn(move || {
+                                    for i in 0..50 {
+                                        let query = SearchQuery {
+                                            vector: vec![((r + i) % 10) as f32 / 10.0; dimensions],
+                                            k: 10,
+                                            filter: None,
+                                            ef_search: None,
+                                        };
+                                        black_box(db.search(query).unwrap());
+                                    }
+                                })
+                            })
+                            .collect();
+
+                        for handle in reader_handles {
+                            handle.join().unwrap();
+                        }
+                        done.store(true, Ordering::Relaxed);
+                    });
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_stats_contention,
+    bench_pool_and_queue_contention,
+    bench_contended_read_write
+);
+criterion_main!(benches);
@@ -0,0 +1,315 @@
+//! Adaptive efSearch from predicted query difficulty
+//!
+//! A fixed `ef_search` is tuned for the hardest queries and wastes work on
+//! easy ones. [`VectorDB::enable_adaptive_ef`](crate::VectorDB::enable_adaptive_ef)
+//! instead picks `ef_search` per query from a small logistic model over
+//! three cheap features:
+//!
+//! - **norm deviation**: how far the query norm is from the typical stored
+//!   norm,
+//! - **centroid distance**: distance to the data centroid relative to the
+//!   typical stored vector (outliers have sparse neighborhoods),
+//! - **crowding**: how little the distances of a low-ef probe search spread
+//!   out (many near-equidistant candidates need a wider beam).
+//!
+//! The shipped weights are a reasonable default;
+//! [`VectorDB::train_adaptive_ef`](crate::VectorDB::train_adaptive_ef) refits
+//! them on the database's own data for the configured recall target.
+//! Queries with an explicit `ef_search` bypass the predictor.
+
+use crate::types::SearchResult;
+use serde::{Deserialize, Serialize};
+
+/// Adaptive efSearch settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdaptiveEfConfig {
+    /// Recall@k the predictor is trained to reach
+    pub recall_target: f32,
+    /// efSearch for the easiest queries
+    pub min_ef: usize,
+    /// efSearch for the hardest queries
+    pub max_ef: usize,
+    /// efSearch of the probe search used for the crowding feature
+    pub probe_ef: usize,
+    /// Results of the probe search
+    pub probe_k: usize,
+    /// Stored vectors sampled for data statistics
+    pub sample_size: usize,
+}
+
+impl Default for AdaptiveEfConfig {
+    fn default() -> Self {
+        Self {
+            recall_target: 0.95,
+            min_ef: 16,
+            max_ef: 512,
+            probe_ef: 16,
+            probe_k: 8,
+            sample_size: 1000,
+        }
+    }
+}
+
+/// Inputs to the difficulty model
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct QueryFeatures {
+    /// `|‖q‖ / mean‖x‖ - 1|`
+    pub norm_deviation: f32,
+    /// `‖q - centroid‖ / mean‖x - centroid‖`
+    pub centroid_distance: f32,
+    /// 1 minus the relative spread of probe distances, in `[0, 1]`
+    pub crowding: f32,
+}
+
+impl QueryFeatures {
+    fn as_array(&self) -> [f32; 3] {
+        [self.norm_deviation, self.centroid_distance, self.crowding]
+    }
+}
+
+/// Logistic model mapping [`QueryFeatures`] to a difficulty in `[0, 1]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DifficultyModel {
+    /// Feature weights, in [`QueryFeatures`] field order
+    pub weights: [f32; 3],
+    /// Intercept
+    pub bias: f32,
+}
+
+impl Default for DifficultyModel {
+    fn default() -> Self {
+        Self {
+            weights: [0.8, 1.5, 3.0],
+            bias: -4.0,
+        }
+    }
+}
+
+impl DifficultyModel {
+    /// Predicted difficulty in `[0, 1]`
+    pub fn predict(&self, features: &QueryFeatures) -> f32 {
+        let z = self.bias
+            + self
+                .weights
+                .iter()
+                .zip(features.as_array())
+                .map(|(w, x)| w * x)
+                .sum::<f32>();
+        sigmoid(z)
+    }
+
+    /// Fit by gradient descent on cross-entropy against soft labels in `[0, 1]`
+    pub fn fit(samples: &[(QueryFeatures, f32)], epochs: usize, learning_rate: f32) -> Self {
+        let mut model = Self::default();
+        if samples.is_empty() {
+            return model;
+        }
+
+        let n = samples.len() as f32;
+        for _ in 0..epochs {
+            let mut grad_w = [0.0f32; 3];
+            let mut grad_b = 0.0f32;
+            for (features, label) in samples {
+                let error = model.predict(features) - label.clamp(0.0, 1.0);
+                for (g, x) in grad_w.iter_mut().zip(features.as_array()) {
+                    *g += error * x;
+                }
+                grad_b += error;
+            }
+            for (w, g) in model.weights.iter_mut().zip(grad_w) {
+                *w -= learning_rate * g / n;
+            }
+            model.bias -= learning_rate * grad_b / n;
+        }
+        model
+    }
+}
+
+/// Difficulty assigned to one query
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct QueryDifficulty {
+    /// Predicted difficulty in `[0, 1]`
+    pub difficulty: f32,
+    /// efSearch used
+    pub ef_search: usize,
+    /// Features the prediction was made from
+    pub features: QueryFeatures,
+}
+
+/// Statistics of the stored vectors the features are normalized by
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataStats {
+    /// Mean vector
+    pub centroid: Vec<f32>,
+    /// Mean vector norm
+    pub mean_norm: f32,
+    /// Mean distance to the centroid
+    pub mean_centroid_distance: f32,
+}
+
+impl DataStats {
+    /// Compute statistics over `vectors`; `None` if there are none
+    pub fn from_vectors(vectors: &[Vec<f32>]) -> Option<Self> {
+        let first = vectors.first()?;
+        let n = vectors.len() as f32;
+
+        let mut centroid = vec![0.0f32; first.len()];
+        for vector in vectors {
+            for (c, x) in centroid.iter_mut().zip(vector) {
+                *c += x;
+            }
+        }
+        centroid.iter_mut().for_each(|c| *c /= n);
+
+        let mean_norm = vectors.iter().map(|v| norm(v)).sum::<f32>() / n;
+        let mean_centroid_distance = vectors.iter().map(|v| l2(v, &centroid)).sum::<f32>() / n;
+
+        Some(Self {
+            centroid,
+            mean_norm,
+            mean_centroid_distance,
+        })
+    }
+}
+
+/// Installed predictor: configuration, data statistics and model
+#[derive(Debug, Clone)]
+pub struct AdaptiveEf {
+    /// Settings
+    pub config: AdaptiveEfConfig,
+    /// Normalization statistics
+    pub stats: DataStats,
+    /// Difficulty model
+    pub model: DifficultyModel,
+}
+
+impl AdaptiveEf {
+    /// Features of `query` given the results of its probe search
+    pub fn features(&self, query: &[f32], probe: &[SearchResult]) -> QueryFeatures {
+        let norm_deviation = if self.stats.mean_norm > f32::EPSILON {
+            (norm(query) / self.stats.mean_norm - 1.0).abs()
+        } else {
+            0.0
+        };
+        let centroid_distance = if self.stats.mean_centroid_distance > f32::EPSILON {
+            l2(query, &self.stats.centroid) / self.stats.mean_centroid_distance
+        } else {
+            0.0
+        };
+        let crowding = match (probe.first(), probe.last()) {
+            (Some(first), Some(last)) if probe.len() > 1 => {
+                let spread = (last.score - first.score)
+                    / (last.score.abs() + first.score.abs() + f32::EPSILON);
+                1.0 - spread.clamp(0.0, 1.0)
+            }
+            _ => 1.0,
+        };
+
+        QueryFeatures {
+            norm_deviation,
+            centroid_distance,
+            crowding,
+        }
+    }
+
+    /// efSearch for a predicted difficulty, never below `k`
+    pub fn ef_for(&self, difficulty: f32, k: usize) -> usize {
+        let span = self.config.max_ef.saturating_sub(self.config.min_ef) as f32;
+        let ef = self.config.min_ef + (difficulty.clamp(0.0, 1.0) * span).round() as usize;
+        ef.max(k)
+    }
+
+    /// Training label: where `ef_needed` falls between `min_ef` and `max_ef`
+    pub fn label_for(&self, ef_needed: usize) -> f32 {
+        let span = self.config.max_ef.saturating_sub(self.config.min_ef).max(1) as f32;
+        (ef_needed.saturating_sub(self.config.min_ef) as f32 / span).clamp(0.0, 1.0)
+    }
+
+    /// Predict difficulty and efSearch for `query`
+    pub fn assess(&self, query: &[f32], probe: &[SearchResult], k: usize) -> QueryDifficulty {
+        let features = self.features(query, probe);
+        let difficulty = self.model.predict(&features);
+        QueryDifficulty {
+            difficulty,
+            ef_search: self.ef_for(difficulty, k),
+            features,
+        }
+    }
+}
+
+fn sigmoid(z: f32) -> f32 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+fn norm(v: &[f32]) -> f32 {
+    v.iter().map(|x| x * x).sum::<f32>().sqrt()
+}
+
+fn l2(a: &[f32], b: &[f32]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f32>()
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(score: f32) -> SearchResult {
+        SearchResult {
+            id: String::new(),
+            score,
+            vector: None,
+            metadata: None,
+        }
+    }
+
+    fn predictor() -> AdaptiveEf {
+        AdaptiveEf {
+            config: AdaptiveEfConfig::default(),
+            stats: DataStats::from_vectors(&[vec![1.0, 0.0], vec![-1.0, 0.0]]).unwrap(),
+            model: DifficultyModel::default(),
+        }
+    }
+
+    #[test]
+    fn test_crowded_outlier_is_harder() {
+        let adaptive = predictor();
+        let easy = adaptive.assess(&[1.0, 0.0], &[result(0.0), result(2.0)], 10);
+        let hard = adaptive.assess(&[5.0, 5.0], &[result(4.0), result(4.1)], 10);
+
+        assert!(hard.difficulty > easy.difficulty);
+        assert!(hard.ef_search > easy.ef_search);
+        assert!(easy.ef_search >= 16);
+        assert!(hard.ef_search <= 512);
+    }
+
+    #[test]
+    fn test_ef_label_roundtrip() {
+        let adaptive = predictor();
+        assert_eq!(adaptive.ef_for(adaptive.label_for(16), 1), 16);
+        assert_eq!(adaptive.ef_for(adaptive.label_for(264), 1), 264);
+        assert_eq!(adaptive.ef_for(0.0, 40), 40);
+    }
+
+    #[test]
+    fn test_fit_separates_labels() {
+        let easy = QueryFeatures {
+            norm_deviation: 0.0,
+            centroid_distance: 0.5,
+            crowding: 0.1,
+        };
+        let hard = QueryFeatures {
+            norm_deviation: 0.5,
+            centroid_distance: 2.0,
+            crowding: 0.9,
+        };
+        let samples: Vec<_> = (0..20).flat_map(|_| [(easy, 0.0), (hard, 1.0)]).collect();
+
+        let model = DifficultyModel::fit(&samples, 500, 0.5);
+        assert!(model.predict(&easy) < 0.3);
+        assert!(model.predict(&hard) > 0.7);
+    }
+}
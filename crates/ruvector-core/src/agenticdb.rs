@@ -762,6 +762,327 @@ impl AgenticDB {
     }
 }
 
+// ============ AgenticDB Migration ============
+
+/// One entry of an AgenticDB operation log (JSON lines, one object per line)
+///
+/// `id` fields carry the identifier used by the source AgenticDB; ids that
+/// ruvector generates itself (episodes, skills, edges, sessions) are mapped so
+/// later operations can keep referring to the original value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum AgenticOperation {
+    /// `insert` - add a raw vector
+    Insert {
+        /// Vector id; generated when absent
+        id: Option<String>,
+        /// Vector data
+        vector: Vec<f32>,
+        /// Optional metadata
+        #[serde(default)]
+        metadata: Option<HashMap<String, serde_json::Value>>,
+    },
+    /// `delete` - remove a vector
+    Delete {
+        /// Vector id
+        id: String,
+    },
+    /// `search` - k-NN query, optionally checked against recorded results
+    Search {
+        /// Query vector
+        vector: Vec<f32>,
+        /// Number of results
+        k: usize,
+        /// Ids the source database returned, in rank order
+        #[serde(default)]
+        expected: Option<Vec<String>>,
+    },
+    /// `store_episode` - see [`AgenticDB::store_episode`]
+    StoreEpisode {
+        /// Source episode id
+        id: Option<String>,
+        /// Task description
+        task: String,
+        /// Actions taken
+        #[serde(default)]
+        actions: Vec<String>,
+        /// Observations made
+        #[serde(default)]
+        observations: Vec<String>,
+        /// Self-critique
+        critique: String,
+    },
+    /// `create_skill` - see [`AgenticDB::create_skill`]
+    CreateSkill {
+        /// Source skill id
+        id: Option<String>,
+        /// Skill name
+        name: String,
+        /// Skill description
+        description: String,
+        /// Parameter name -> type
+        #[serde(default)]
+        parameters: HashMap<String, String>,
+        /// Usage examples
+        #[serde(default)]
+        examples: Vec<String>,
+    },
+    /// `add_causal_edge` - see [`AgenticDB::add_causal_edge`]
+    AddCausalEdge {
+        /// Source edge id
+        id: Option<String>,
+        /// Cause nodes
+        causes: Vec<String>,
+        /// Effect nodes
+        effects: Vec<String>,
+        /// Edge confidence
+        confidence: f64,
+        /// Context text
+        context: String,
+    },
+    /// `start_session` - see [`AgenticDB::start_session`]
+    StartSession {
+        /// Source session id
+        id: Option<String>,
+        /// RL algorithm name
+        algorithm: String,
+        /// State dimensions
+        state_dim: usize,
+        /// Action dimensions
+        action_dim: usize,
+    },
+    /// `add_experience` - see [`AgenticDB::add_experience`]
+    AddExperience {
+        /// Source session id
+        session_id: String,
+        /// State before the action
+        state: Vec<f32>,
+        /// Action taken
+        action: Vec<f32>,
+        /// Observed reward
+        reward: f64,
+        /// State after the action
+        next_state: Vec<f32>,
+        /// Whether the episode ended
+        done: bool,
+    },
+}
+
+/// Known behavioural difference found while replaying an operation log
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CompatibilityGap {
+    /// The line could not be parsed as an AgenticDB operation
+    #[error("line {line}: unsupported or malformed operation: {message}")]
+    UnsupportedOperation {
+        /// 1-based line number in the log
+        line: usize,
+        /// Parser message
+        message: String,
+    },
+
+    /// ruvector rejected an operation the source database accepted
+    #[error("line {line}: operation failed: {message}")]
+    OperationFailed {
+        /// 1-based line number in the log
+        line: usize,
+        /// Error reported by ruvector
+        message: String,
+    },
+
+    /// A search returned different ids than the source database recorded
+    #[error("line {line}: search results differ (expected {expected:?}, got {actual:?})")]
+    ResultMismatch {
+        /// 1-based line number in the log
+        line: usize,
+        /// Ids recorded by the source database
+        expected: Vec<String>,
+        /// Ids returned by ruvector
+        actual: Vec<String>,
+    },
+}
+
+/// Outcome of replaying an AgenticDB operation log
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    /// Operations applied successfully
+    pub applied: usize,
+    /// Searches whose results matched the recorded ids
+    pub verified_searches: usize,
+    /// Source id -> ruvector id for generated identifiers
+    pub id_map: HashMap<String, String>,
+    /// Every difference encountered; empty means the log replayed cleanly
+    pub gaps: Vec<CompatibilityGap>,
+}
+
+impl ImportReport {
+    /// True when the replay found no compatibility gaps
+    pub fn is_compatible(&self) -> bool {
+        self.gaps.is_empty()
+    }
+}
+
+impl AgenticDB {
+    /// Import an AgenticDB operation log (JSON lines) into this database
+    ///
+    /// Blank lines and lines starting with `#` are skipped. Failures are
+    /// recorded in the report rather than aborting the import; only an
+    /// unreadable file is returned as an error.
+    pub fn agenticdb_import<P: AsRef<Path>>(&self, path: P) -> Result<ImportReport> {
+        let contents = std::fs::read_to_string(path)?;
+        let mut report = ImportReport::default();
+
+        for (idx, raw) in contents.lines().enumerate() {
+            let line = idx + 1;
+            let raw = raw.trim();
+            if raw.is_empty() || raw.starts_with('#') {
+                continue;
+            }
+            match serde_json::from_str::<AgenticOperation>(raw) {
+                Ok(op) => self.replay_operation(line, op, &mut report),
+                Err(e) => report.gaps.push(CompatibilityGap::UnsupportedOperation {
+                    line,
+                    message: e.to_string(),
+                }),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Replay already-parsed operations, numbering them from 1
+    pub fn replay_operations(
+        &self,
+        operations: impl IntoIterator<Item = AgenticOperation>,
+    ) -> ImportReport {
+        let mut report = ImportReport::default();
+        for (idx, op) in operations.into_iter().enumerate() {
+            self.replay_operation(idx + 1, op, &mut report);
+        }
+        report
+    }
+
+    fn replay_operation(&self, line: usize, op: AgenticOperation, report: &mut ImportReport) {
+        let resolve = |report: &ImportReport, id: &str| {
+            report.id_map.get(id).cloned().unwrap_or_else(|| id.to_string())
+        };
+
+        let outcome: Result<()> = match op {
+            AgenticOperation::Insert {
+                id,
+                vector,
+                metadata,
+            } => self
+                .insert(VectorEntry {
+                    id,
+                    vector,
+                    metadata,
+                })
+                .map(|_| ()),
+            AgenticOperation::Delete { id } => {
+                let id = resolve(report, &id);
+                self.delete(&id).map(|_| ())
+            }
+            AgenticOperation::Search {
+                vector,
+                k,
+                expected,
+            } => self
+                .search(SearchQuery {
+                    vector,
+                    k,
+                    filter: None,
+                    ef_search: None,
+                })
+                .map(|results| {
+                    if let Some(expected) = expected {
+                        let expected: Vec<String> =
+                            expected.iter().map(|id| resolve(report, id)).collect();
+                        let actual: Vec<String> = results.into_iter().map(|r| r.id).collect();
+                        if actual == expected {
+                            report.verified_searches += 1;
+                        } else {
+                            report.gaps.push(CompatibilityGap::ResultMismatch {
+                                line,
+                                expected,
+                                actual,
+                            });
+                        }
+                    }
+                }),
+            AgenticOperation::StoreEpisode {
+                id,
+                task,
+                actions,
+                observations,
+                critique,
+            } => self
+                .store_episode(task, actions, observations, critique)
+                .map(|new_id| record_id(report, id, new_id)),
+            AgenticOperation::CreateSkill {
+                id,
+                name,
+                description,
+                parameters,
+                examples,
+            } => self
+                .create_skill(name, description, parameters, examples)
+                .map(|new_id| record_id(report, id, new_id)),
+            AgenticOperation::AddCausalEdge {
+                id,
+                causes,
+                effects,
+                confidence,
+                context,
+            } => self
+                .add_causal_edge(causes, effects, confidence, context)
+                .map(|new_id| record_id(report, id, new_id)),
+            AgenticOperation::StartSession {
+                id,
+                algorithm,
+                state_dim,
+                action_dim,
+            } => self
+                .start_session(algorithm, state_dim, action_dim)
+                .map(|new_id| record_id(report, id, new_id)),
+            AgenticOperation::AddExperience {
+                session_id,
+                state,
+                action,
+                reward,
+                next_state,
+                done,
+            } => {
+                let session_id = resolve(report, &session_id);
+                self.add_experience(&session_id, state, action, reward, next_state, done)
+            }
+        };
+
+        match outcome {
+            Ok(()) => report.applied += 1,
+            Err(e) => report.gaps.push(CompatibilityGap::OperationFailed {
+                line,
+                message: e.to_string(),
+            }),
+        }
+    }
+}
+
+fn record_id(report: &mut ImportReport, source: Option<String>, new_id: String) {
+    if let Some(source) = source {
+        report.id_map.insert(source, new_id);
+    }
+}
+
+/// Create a database at `options.storage_path` and import an AgenticDB operation log
+pub fn agenticdb_import<P: AsRef<Path>>(
+    path: P,
+    options: DbOptions,
+) -> Result<(AgenticDB, ImportReport)> {
+    let db = AgenticDB::new(options)?;
+    let report = db.agenticdb_import(path)?;
+    Ok((db, report))
+}
+
 // Helper functions
 fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
     a.iter()
@@ -865,6 +1186,51 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_agenticdb_import_replays_log() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("import.db").to_string_lossy().to_string();
+        options.dimensions = 4;
+
+        let log = [
+            "# exported from agenticdb",
+            r#"{"op":"insert","id":"a","vector":[1.0,0.0,0.0,0.0]}"#,
+            r#"{"op":"insert","id":"b","vector":[0.0,1.0,0.0,0.0],"metadata":{"kind":"doc"}}"#,
+            r#"{"op":"insert","id":"c","vector":[0.9,0.1,0.0,0.0]}"#,
+            r#"{"op":"search","vector":[1.0,0.0,0.0,0.0],"k":2,"expected":["a","c"]}"#,
+            r#"{"op":"delete","id":"c"}"#,
+            r#"{"op":"search","vector":[1.0,0.0,0.0,0.0],"k":2,"expected":["a","c"]}"#,
+            r#"{"op":"start_session","id":"s1","algorithm":"Q-Learning","state_dim":2,"action_dim":1}"#,
+            r#"{"op":"add_experience","session_id":"s1","state":[0.0,1.0],"action":[1.0],"reward":1.0,"next_state":[1.0,0.0],"done":true}"#,
+            r#"{"op":"vacuum"}"#,
+        ]
+        .join("\n");
+        let log_path = dir.path().join("ops.jsonl");
+        std::fs::write(&log_path, log)?;
+
+        let (db, report) = agenticdb_import(&log_path, options)?;
+
+        assert_eq!(report.applied, 8);
+        assert_eq!(report.verified_searches, 1);
+        assert!(!report.is_compatible());
+        assert_eq!(report.gaps.len(), 2);
+        assert!(matches!(
+            report.gaps[0],
+            CompatibilityGap::ResultMismatch { line: 7, .. }
+        ));
+        assert!(matches!(
+            report.gaps[1],
+            CompatibilityGap::UnsupportedOperation { line: 10, .. }
+        ));
+
+        let session_id = &report.id_map["s1"];
+        assert_eq!(db.get_session(session_id)?.unwrap().experiences.len(), 1);
+        assert!(db.get("b")?.is_some());
+
+        Ok(())
+    }
+
     #[test]
     fn test_auto_consolidate() -> Result<()> {
         let db = create_test_db()?;
@@ -0,0 +1,202 @@
+//! Outlier scoring over stored vectors
+//!
+//! [`VectorDB::score_anomalies`](crate::VectorDB::score_anomalies) scores
+//! every entry by how far it sits from its neighbors, so monitoring can
+//! alert when an ingestion source starts producing vectors unlike the rest
+//! of the collection. Two scores are available:
+//!
+//! - [`AnomalyMethod::KthDistance`]: distance to the k-th nearest neighbor,
+//!   in the units of the database's metric. Cheap and easy to threshold
+//!   for a fixed model.
+//! - [`AnomalyMethod::Lof`]: the Local Outlier Factor, comparing an
+//!   entry's local density with that of its neighbors. Around 1 for
+//!   inliers and well above 1 for outliers, whatever the density of the
+//!   region. It is approximate because neighbors come from the index.
+//!
+//! Scores are written to [`ANOMALY_SCORE_KEY`] in each entry's metadata,
+//! and entries above [`AnomalyConfig::threshold`] are also marked with
+//! [`ANOMALY_FLAG_KEY`], so filtered searches can find or exclude them.
+
+use crate::types::VectorId;
+use serde::{Deserialize, Serialize};
+
+/// Metadata key holding an entry's anomaly score
+pub const ANOMALY_SCORE_KEY: &str = "anomaly_score";
+
+/// Metadata key set to `true` on entries scoring above the threshold
+pub const ANOMALY_FLAG_KEY: &str = "anomaly";
+
+/// Entries whose metadata is rewritten per storage transaction
+pub const ANOMALY_WRITE_BATCH: usize = 1000;
+
+/// How entries are scored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyMethod {
+    /// Distance to the k-th nearest neighbor
+    KthDistance,
+    /// Local Outlier Factor over the k nearest neighbors
+    Lof,
+}
+
+/// Anomaly scoring settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnomalyConfig {
+    /// Neighbors per entry
+    pub k: usize,
+    /// Score to compute
+    pub method: AnomalyMethod,
+    /// Score above which entries are flagged
+    pub threshold: Option<f32>,
+    /// Write scores and flags to metadata; otherwise only report them
+    pub write_metadata: bool,
+    /// Highest-scoring entries listed in the report
+    pub top: usize,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            k: 10,
+            method: AnomalyMethod::Lof,
+            threshold: Some(1.5),
+            write_metadata: true,
+            top: 20,
+        }
+    }
+}
+
+/// The score of one entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnomalyScore {
+    /// Entry id
+    pub id: VectorId,
+    /// Its score
+    pub score: f32,
+}
+
+/// Outcome of a scoring run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnomalyReport {
+    /// Entries scored
+    pub scored: usize,
+    /// Mean score
+    pub mean_score: f32,
+    /// 99th percentile score
+    pub p99_score: f32,
+    /// Entries above the threshold
+    pub flagged: usize,
+    /// Entries whose metadata was rewritten
+    pub written: usize,
+    /// Highest-scoring entries, highest first
+    pub top: Vec<AnomalyScore>,
+}
+
+/// Score entries given each one's nearest neighbors
+///
+/// `neighbors[p]` lists `(position, distance)` pairs of entry `p`'s
+/// neighbors, nearest first.
+pub(crate) fn scores(neighbors: &[Vec<(usize, f32)>], method: AnomalyMethod) -> Vec<f32> {
+    let k_distance: Vec<f32> = neighbors
+        .iter()
+        .map(|n| n.last().map_or(0.0, |&(_, d)| d))
+        .collect();
+    if method == AnomalyMethod::KthDistance {
+        return k_distance;
+    }
+
+    // Local reachability density; duplicates would make it infinite
+    let lrd: Vec<f32> = neighbors
+        .iter()
+        .map(|n| {
+            let reach: f32 = n.iter().map(|&(o, d)| d.max(k_distance[o])).sum();
+            n.len() as f32 / reach.max(f32::EPSILON)
+        })
+        .collect();
+    neighbors
+        .iter()
+        .enumerate()
+        .map(|(p, n)| {
+            if n.is_empty() {
+                return 1.0;
+            }
+            let mean = n.iter().map(|&(o, _)| lrd[o]).sum::<f32>() / n.len() as f32;
+            mean / lrd[p]
+        })
+        .collect()
+}
+
+/// Summarize `scores` of the entries `ids`
+pub(crate) fn report(ids: &[VectorId], scores: &[f32], config: &AnomalyConfig) -> AnomalyReport {
+    let mut ranked: Vec<(usize, f32)> = scores.iter().copied().enumerate().collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let p99 = match ranked.len() {
+        0 => 0.0,
+        n => ranked[(n as f32 * 0.01).floor() as usize].1,
+    };
+    AnomalyReport {
+        scored: scores.len(),
+        mean_score: scores.iter().sum::<f32>() / scores.len().max(1) as f32,
+        p99_score: p99,
+        flagged: config
+            .threshold
+            .map_or(0, |t| scores.iter().filter(|&&s| s > t).count()),
+        written: 0,
+        top: ranked
+            .into_iter()
+            .take(config.top)
+            .map(|(p, score)| AnomalyScore {
+                id: ids[p].clone(),
+                score,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Exact neighbors of points on a line
+    fn line_neighbors(points: &[f32], k: usize) -> Vec<Vec<(usize, f32)>> {
+        points
+            .iter()
+            .enumerate()
+            .map(|(p, &x)| {
+                let mut n: Vec<(usize, f32)> = points
+                    .iter()
+                    .enumerate()
+                    .filter(|&(o, _)| o != p)
+                    .map(|(o, &y)| (o, (x - y).abs()))
+                    .collect();
+                n.sort_by(|a, b| a.1.total_cmp(&b.1));
+                n.truncate(k);
+                n
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_outlier_scores_highest() {
+        // A dense cluster, a sparse cluster, and one point far from both
+        let mut points: Vec<f32> = (0..20).map(|i| i as f32 * 0.1).collect();
+        points.extend((0..20).map(|i| 100.0 + i as f32 * 2.0));
+        points.push(60.0);
+        let neighbors = line_neighbors(&points, 5);
+
+        let lof = scores(&neighbors, AnomalyMethod::Lof);
+        let outlier = points.len() - 1;
+        assert!(lof[outlier] > 3.0, "LOF {}", lof[outlier]);
+        // Both clusters look normal to LOF despite different densities
+        assert!(lof[..outlier].iter().all(|&s| s < 1.6), "{:?}", lof);
+
+        let kth = scores(&neighbors, AnomalyMethod::KthDistance);
+        assert!((kth[0] - 0.5).abs() < 1e-5);
+
+        let ids: Vec<VectorId> = (0..points.len()).map(|i| i.to_string()).collect();
+        let config = AnomalyConfig::default();
+        let report = report(&ids, &lof, &config);
+        assert_eq!(report.top[0].id, outlier.to_string());
+        assert_eq!(report.flagged, 1);
+    }
+}
@@ -2,10 +2,123 @@
 //!
 //! This module provides arena-based memory allocation to reduce allocation
 //! overhead in hot paths and improve memory locality.
+//!
+//! Every arena reports its usage to process-wide counters, readable with
+//! [`global_stats`]. With [`set_debug_tracking`] enabled, arenas created
+//! afterwards also attribute their memory to a subsystem tag (see
+//! [`Arena::with_tag`]), which makes slow growth traceable to its owner.
 
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
 use std::alloc::{alloc, dealloc, Layout};
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap};
 use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+/// Tag of arenas created without [`Arena::with_tag`]
+pub const UNTAGGED: &str = "untagged";
+
+static DEBUG_TRACKING: AtomicBool = AtomicBool::new(false);
+static LIVE_ARENAS: AtomicUsize = AtomicUsize::new(0);
+static LIVE_CHUNKS: AtomicUsize = AtomicUsize::new(0);
+static RESERVED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static USED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static HIGH_WATER_BYTES: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+fn tag_registry() -> &'static Mutex<HashMap<&'static str, TagStats>> {
+    static REGISTRY: once_cell::sync::Lazy<Mutex<HashMap<&'static str, TagStats>>> =
+        once_cell::sync::Lazy::new(|| Mutex::new(HashMap::new()));
+    &REGISTRY
+}
+
+/// Enable or disable per-tag accounting for arenas created from now on
+pub fn set_debug_tracking(enabled: bool) {
+    DEBUG_TRACKING.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether per-tag accounting is enabled
+pub fn debug_tracking_enabled() -> bool {
+    DEBUG_TRACKING.load(Ordering::Relaxed)
+}
+
+/// Usage of a single arena
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArenaStats {
+    /// Chunks currently held
+    pub chunks: usize,
+    /// Bytes reserved from the system allocator
+    pub reserved_bytes: usize,
+    /// Bytes handed out since the last reset, including alignment padding
+    pub used_bytes: usize,
+    /// Peak of `used_bytes`
+    pub high_water_bytes: usize,
+    /// Allocations served since creation
+    pub allocations: u64,
+    /// Share of reserved bytes not in use, in `[0, 1]`
+    pub fragmentation: f64,
+}
+
+/// Memory attributed to one subsystem tag (debug tracking only)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TagStats {
+    /// Live arenas with this tag
+    pub live_arenas: usize,
+    /// Bytes reserved by those arenas
+    pub reserved_bytes: usize,
+    /// Allocations served by arenas with this tag, including dropped ones
+    pub allocations: u64,
+}
+
+/// Process-wide arena usage
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GlobalArenaStats {
+    /// Arenas not yet dropped
+    pub live_arenas: usize,
+    /// Chunks held by live arenas
+    pub live_chunks: usize,
+    /// Bytes reserved by live arenas
+    pub reserved_bytes: usize,
+    /// Bytes in use across live arenas
+    pub used_bytes: usize,
+    /// Peak of `used_bytes` since process start
+    pub high_water_bytes: usize,
+    /// Allocations served since process start
+    pub allocations: u64,
+    /// Share of reserved bytes not in use, in `[0, 1]`
+    pub fragmentation: f64,
+    /// Per-tag breakdown; empty unless debug tracking was enabled
+    pub by_tag: BTreeMap<String, TagStats>,
+}
+
+/// Snapshot of process-wide arena usage
+pub fn global_stats() -> GlobalArenaStats {
+    let reserved_bytes = RESERVED_BYTES.load(Ordering::Relaxed);
+    let used_bytes = USED_BYTES.load(Ordering::Relaxed);
+    GlobalArenaStats {
+        live_arenas: LIVE_ARENAS.load(Ordering::Relaxed),
+        live_chunks: LIVE_CHUNKS.load(Ordering::Relaxed),
+        reserved_bytes,
+        used_bytes,
+        high_water_bytes: HIGH_WATER_BYTES.load(Ordering::Relaxed),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        fragmentation: fragmentation(reserved_bytes, used_bytes),
+        by_tag: tag_registry()
+            .lock()
+            .iter()
+            .map(|(tag, stats)| (tag.to_string(), *stats))
+            .collect(),
+    }
+}
+
+fn fragmentation(reserved: usize, used: usize) -> f64 {
+    if reserved == 0 {
+        0.0
+    } else {
+        1.0 - used.min(reserved) as f64 / reserved as f64
+    }
+}
 
 /// Arena allocator for temporary allocations
 ///
@@ -14,6 +127,10 @@ use std::ptr;
 pub struct Arena {
     chunks: RefCell<Vec<Chunk>>,
     chunk_size: usize,
+    tag: &'static str,
+    tracked: bool,
+    high_water: Cell<usize>,
+    allocations: Cell<u64>,
 }
 
 struct Chunk {
@@ -25,9 +142,24 @@ struct Chunk {
 impl Arena {
     /// Create a new arena with the specified chunk size
     pub fn new(chunk_size: usize) -> Self {
+        Self::with_tag(chunk_size, UNTAGGED)
+    }
+
+    /// Create an arena whose memory is attributed to `tag` (e.g. "hnsw",
+    /// "batch_insert") when debug tracking is enabled
+    pub fn with_tag(chunk_size: usize, tag: &'static str) -> Self {
+        let tracked = debug_tracking_enabled();
+        LIVE_ARENAS.fetch_add(1, Ordering::Relaxed);
+        if tracked {
+            tag_registry().lock().entry(tag).or_default().live_arenas += 1;
+        }
         Self {
             chunks: RefCell::new(Vec::new()),
             chunk_size,
+            tag,
+            tracked,
+            high_water: Cell::new(0),
+            allocations: Cell::new(0),
         }
     }
 
@@ -75,13 +207,17 @@ impl Arena {
                 .expect("Arena allocation size overflow");
 
             if needed <= chunk.capacity {
+                let grown = needed - chunk.used;
                 chunk.used = needed;
-                return unsafe {
+                let ptr = unsafe {
                     // SECURITY: Verify pointer arithmetic doesn't overflow
                     let ptr = chunk.data.add(aligned);
                     debug_assert!(ptr as usize >= chunk.data as usize, "Pointer underflow");
                     ptr
                 };
+                drop(chunks);
+                self.record_alloc(grown, 0);
+                return ptr;
             }
         }
 
@@ -98,17 +234,63 @@ impl Arena {
         };
 
         let ptr = unsafe { data.add(aligned) };
+        let used = chunk.used;
         chunks.push(chunk);
+        drop(chunks);
+        self.record_alloc(used, chunk_size);
 
         ptr
     }
 
+    /// Update counters after handing out `used` bytes and reserving `reserved`
+    fn record_alloc(&self, used: usize, reserved: usize) {
+        self.allocations.set(self.allocations.get() + 1);
+        self.high_water.set(self.high_water.get().max(self.used_bytes()));
+
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        let total_used = USED_BYTES.fetch_add(used, Ordering::Relaxed) + used;
+        HIGH_WATER_BYTES.fetch_max(total_used, Ordering::Relaxed);
+        if reserved > 0 {
+            LIVE_CHUNKS.fetch_add(1, Ordering::Relaxed);
+            RESERVED_BYTES.fetch_add(reserved, Ordering::Relaxed);
+        }
+
+        if self.tracked {
+            let mut registry = tag_registry().lock();
+            let stats = registry.entry(self.tag).or_default();
+            stats.allocations += 1;
+            stats.reserved_bytes += reserved;
+        }
+    }
+
+    /// Subsystem tag of this arena
+    pub fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    /// Usage of this arena
+    pub fn stats(&self) -> ArenaStats {
+        let reserved_bytes = self.allocated_bytes();
+        let used_bytes = self.used_bytes();
+        ArenaStats {
+            chunks: self.chunks.borrow().len(),
+            reserved_bytes,
+            used_bytes,
+            high_water_bytes: self.high_water.get(),
+            allocations: self.allocations.get(),
+            fragmentation: fragmentation(reserved_bytes, used_bytes),
+        }
+    }
+
     /// Reset the arena, allowing reuse of allocated memory
     pub fn reset(&self) {
         let mut chunks = self.chunks.borrow_mut();
+        let mut released = 0;
         for chunk in chunks.iter_mut() {
+            released += chunk.used;
             chunk.used = 0;
         }
+        USED_BYTES.fetch_sub(released, Ordering::Relaxed);
     }
 
     /// Get total allocated bytes
@@ -126,6 +308,18 @@ impl Arena {
 
 impl Drop for Arena {
     fn drop(&mut self) {
+        let stats = self.stats();
+        LIVE_ARENAS.fetch_sub(1, Ordering::Relaxed);
+        LIVE_CHUNKS.fetch_sub(stats.chunks, Ordering::Relaxed);
+        RESERVED_BYTES.fetch_sub(stats.reserved_bytes, Ordering::Relaxed);
+        USED_BYTES.fetch_sub(stats.used_bytes, Ordering::Relaxed);
+        if self.tracked {
+            if let Some(tag) = tag_registry().lock().get_mut(self.tag) {
+                tag.live_arenas -= 1;
+                tag.reserved_bytes -= stats.reserved_bytes;
+            }
+        }
+
         let chunks = self.chunks.borrow();
         for chunk in chunks.iter() {
             let layout = Layout::from_size_align(chunk.capacity, 64).unwrap();
@@ -213,7 +407,8 @@ impl<T> std::ops::DerefMut for ArenaVec<T> {
 
 /// Thread-local arena for per-thread allocations
 thread_local! {
-    static THREAD_ARENA: RefCell<Arena> = RefCell::new(Arena::with_default_chunk_size());
+    static THREAD_ARENA: RefCell<Arena> =
+        RefCell::new(Arena::with_tag(1024 * 1024, "thread_local"));
 }
 
 /// Get the thread-local arena
@@ -274,4 +469,43 @@ mod tests {
 
         assert!(used_after < used_before);
     }
+
+    #[test]
+    fn test_arena_stats() {
+        let arena = Arena::new(1024);
+        let _a = arena.alloc_vec::<u64>(16);
+        let _b = arena.alloc_vec::<u8>(3);
+
+        let stats = arena.stats();
+        assert_eq!(stats.chunks, 1);
+        assert_eq!(stats.reserved_bytes, 1024);
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.used_bytes, arena.used_bytes());
+        assert!(stats.fragmentation > 0.0 && stats.fragmentation < 1.0);
+
+        arena.reset();
+        let stats = arena.stats();
+        assert_eq!(stats.used_bytes, 0);
+        assert!(stats.high_water_bytes >= 8 * 16 + 3);
+    }
+
+    #[test]
+    fn test_tagged_arena_is_tracked() {
+        set_debug_tracking(true);
+        let arena = Arena::with_tag(4096, "arena_test_tag");
+        set_debug_tracking(false);
+        let _v = arena.alloc_vec::<f32>(8);
+
+        let global = global_stats();
+        let tag = global.by_tag["arena_test_tag"];
+        assert_eq!(tag.live_arenas, 1);
+        assert_eq!(tag.reserved_bytes, 4096);
+        assert!(global.live_arenas >= 1);
+
+        drop(arena);
+        let tag = global_stats().by_tag["arena_test_tag"];
+        assert_eq!(tag.live_arenas, 0);
+        assert_eq!(tag.reserved_bytes, 0);
+        assert_eq!(tag.allocations, 1);
+    }
 }
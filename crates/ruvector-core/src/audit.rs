@@ -0,0 +1,350 @@
+//! Search audit log
+//!
+//! Records each audited search (query hash, returned ids and scores) together
+//! with later accept/reject feedback, so an agent decision can be traced back
+//! to the memories that informed it. Feedback is also published as
+//! [`FeedbackEvent`]s to registered sinks, which is how learning components
+//! such as SONA receive reward signals without this crate depending on them.
+
+use crate::error::{Result, RuvectorError};
+use crate::types::SearchResult;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Identifier assigned to an audited search
+pub type QueryId = u64;
+
+/// Downstream judgement of a returned result
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Feedback {
+    /// The result was used
+    Accepted,
+    /// The result was discarded
+    Rejected,
+    /// Graded usefulness in `[0, 1]`
+    Reward(f32),
+}
+
+impl Feedback {
+    /// Reward in `[0, 1]`
+    pub fn reward(&self) -> f32 {
+        match self {
+            Feedback::Accepted => 1.0,
+            Feedback::Rejected => 0.0,
+            Feedback::Reward(r) => r.clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// One returned result as recorded in the log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditedResult {
+    /// Vector id
+    pub id: String,
+    /// Score reported by the search
+    pub score: f32,
+}
+
+/// Feedback attached to an audited search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeedbackRecord {
+    /// Result the feedback refers to
+    pub result_id: String,
+    /// The judgement
+    pub feedback: Feedback,
+    /// Milliseconds since the Unix epoch
+    pub timestamp_ms: i64,
+}
+
+/// A single audited search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Query identifier
+    pub query_id: QueryId,
+    /// Stable hash of the query vector
+    pub query_hash: u64,
+    /// Query vector, if [`AuditConfig::store_query_vectors`] is set
+    pub query_vector: Option<Vec<f32>>,
+    /// Requested number of results
+    pub k: usize,
+    /// Results in rank order
+    pub results: Vec<AuditedResult>,
+    /// Feedback received so far
+    pub feedback: Vec<FeedbackRecord>,
+    /// Milliseconds since the Unix epoch
+    pub timestamp_ms: i64,
+}
+
+/// Feedback published to sinks
+#[derive(Debug, Clone)]
+pub struct FeedbackEvent {
+    /// Query the feedback refers to
+    pub query_id: QueryId,
+    /// Stable hash of the query vector
+    pub query_hash: u64,
+    /// Query vector, if stored
+    pub query_vector: Option<Vec<f32>>,
+    /// Result the feedback refers to
+    pub result_id: String,
+    /// Zero-based rank of the result in the search
+    pub rank: usize,
+    /// Score the search reported for the result
+    pub score: f32,
+    /// Reward in `[0, 1]`
+    pub reward: f32,
+}
+
+/// Callback receiving feedback events
+pub type FeedbackSink = Arc<dyn Fn(&FeedbackEvent) + Send + Sync>;
+
+/// Audit log configuration
+#[derive(Debug, Clone)]
+pub struct AuditConfig {
+    /// Maximum retained searches; the oldest are evicted first
+    pub capacity: usize,
+    /// Keep query vectors (needed to turn feedback into learning signals)
+    pub store_query_vectors: bool,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            store_query_vectors: true,
+        }
+    }
+}
+
+/// Bounded log of audited searches
+pub struct AuditLog {
+    config: AuditConfig,
+    next_id: AtomicU64,
+    entries: RwLock<VecDeque<AuditEntry>>,
+    sinks: RwLock<Vec<FeedbackSink>>,
+}
+
+impl AuditLog {
+    /// Create an empty log
+    pub fn new(config: AuditConfig) -> Self {
+        Self {
+            config,
+            next_id: AtomicU64::new(1),
+            entries: RwLock::new(VecDeque::new()),
+            sinks: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Number of retained searches
+    pub fn len(&self) -> usize {
+        self.entries.read().len()
+    }
+
+    /// Whether the log is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Record a search and return its query id
+    pub fn record_search(&self, query: &[f32], k: usize, results: &[SearchResult]) -> QueryId {
+        let query_id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let entry = AuditEntry {
+            query_id,
+            query_hash: hash_vector(query),
+            query_vector: self.config.store_query_vectors.then(|| query.to_vec()),
+            k,
+            results: results
+                .iter()
+                .map(|r| AuditedResult {
+                    id: r.id.clone(),
+                    score: r.score,
+                })
+                .collect(),
+            feedback: Vec::new(),
+            timestamp_ms: now_ms(),
+        };
+
+        let mut entries = self.entries.write();
+        entries.push_back(entry);
+        while entries.len() > self.config.capacity.max(1) {
+            entries.pop_front();
+        }
+
+        query_id
+    }
+
+    /// Attach feedback for one result of an audited search
+    ///
+    /// Fails if the query was never recorded (or has been evicted) or if the
+    /// result was not part of its response.
+    pub fn record_feedback(
+        &self,
+        query_id: QueryId,
+        result_id: &str,
+        feedback: Feedback,
+    ) -> Result<FeedbackEvent> {
+        let event = {
+            let mut entries = self.entries.write();
+            let entry = entries
+                .iter_mut()
+                .find(|e| e.query_id == query_id)
+                .ok_or_else(|| {
+                    RuvectorError::InvalidInput(format!("Unknown query id: {}", query_id))
+                })?;
+            let (rank, result) = entry
+                .results
+                .iter()
+                .enumerate()
+                .find(|(_, r)| r.id == result_id)
+                .ok_or_else(|| {
+                    RuvectorError::InvalidInput(format!(
+                        "Result {} was not returned by query {}",
+                        result_id, query_id
+                    ))
+                })?;
+
+            let event = FeedbackEvent {
+                query_id,
+                query_hash: entry.query_hash,
+                query_vector: entry.query_vector.clone(),
+                result_id: result_id.to_string(),
+                rank,
+                score: result.score,
+                reward: feedback.reward(),
+            };
+            entry.feedback.push(FeedbackRecord {
+                result_id: result_id.to_string(),
+                feedback,
+                timestamp_ms: now_ms(),
+            });
+            event
+        };
+
+        for sink in self.sinks.read().iter() {
+            sink(&event);
+        }
+
+        Ok(event)
+    }
+
+    /// Register a sink that receives every feedback event
+    pub fn subscribe(&self, sink: FeedbackSink) {
+        self.sinks.write().push(sink);
+    }
+
+    /// Look up an audited search
+    pub fn get(&self, query_id: QueryId) -> Option<AuditEntry> {
+        self.entries
+            .read()
+            .iter()
+            .find(|e| e.query_id == query_id)
+            .cloned()
+    }
+
+    /// All searches that returned `result_id`, oldest first
+    pub fn trace(&self, result_id: &str) -> Vec<AuditEntry> {
+        self.entries
+            .read()
+            .iter()
+            .filter(|e| e.results.iter().any(|r| r.id == result_id))
+            .cloned()
+            .collect()
+    }
+
+    /// Snapshot of all retained searches, oldest first
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.entries.read().iter().cloned().collect()
+    }
+
+    /// Drop all retained searches
+    pub fn clear(&self) {
+        self.entries.write().clear();
+    }
+}
+
+/// FNV-1a hash over the bit patterns of a vector
+pub fn hash_vector(vector: &[f32]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    vector
+        .iter()
+        .flat_map(|x| x.to_bits().to_le_bytes())
+        .fold(OFFSET, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(PRIME)
+        })
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn result(id: &str, score: f32) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            score,
+            vector: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_record_and_trace() {
+        let log = AuditLog::new(AuditConfig::default());
+        let q1 = log.record_search(&[1.0, 0.0], 2, &[result("a", 0.1), result("b", 0.2)]);
+        let q2 = log.record_search(&[0.0, 1.0], 1, &[result("b", 0.05)]);
+
+        assert_ne!(q1, q2);
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.get(q1).unwrap().query_hash, hash_vector(&[1.0, 0.0]));
+        assert_eq!(log.trace("b").len(), 2);
+        assert_eq!(log.trace("a").len(), 1);
+    }
+
+    #[test]
+    fn test_feedback_reaches_sinks() {
+        let log = AuditLog::new(AuditConfig::default());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink_seen = seen.clone();
+        log.subscribe(Arc::new(move |e: &FeedbackEvent| {
+            sink_seen
+                .lock()
+                .unwrap()
+                .push((e.result_id.clone(), e.rank, e.reward));
+        }));
+
+        let q = log.record_search(&[1.0], 2, &[result("a", 0.1), result("b", 0.3)]);
+        log.record_feedback(q, "b", Feedback::Accepted).unwrap();
+        log.record_feedback(q, "a", Feedback::Reward(3.0)).unwrap();
+
+        assert!(log.record_feedback(q, "zzz", Feedback::Rejected).is_err());
+        assert!(log.record_feedback(999, "a", Feedback::Rejected).is_err());
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![("b".to_string(), 1, 1.0), ("a".to_string(), 0, 1.0)]
+        );
+        assert_eq!(log.get(q).unwrap().feedback.len(), 2);
+    }
+
+    #[test]
+    fn test_capacity_evicts_oldest() {
+        let log = AuditLog::new(AuditConfig {
+            capacity: 2,
+            store_query_vectors: false,
+        });
+        let first = log.record_search(&[1.0], 1, &[]);
+        log.record_search(&[2.0], 1, &[]);
+        log.record_search(&[3.0], 1, &[]);
+
+        assert_eq!(log.len(), 2);
+        assert!(log.get(first).is_none());
+        assert!(log.entries().iter().all(|e| e.query_vector.is_none()));
+    }
+}
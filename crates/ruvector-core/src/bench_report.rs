@@ -0,0 +1,103 @@
+//! Machine-readable benchmark results
+//!
+//! The criterion suite in `benches/` and `ruvector benchmark --json` both
+//! write a [`BenchReport`], so numbers quoted in an issue can be compared
+//! with a local run regardless of which tool produced them. The report
+//! embeds the [`Capabilities`] of the machine that ran it.
+
+use crate::capabilities::{capabilities, Capabilities};
+use serde::{Deserialize, Serialize};
+
+/// Current report schema version
+pub const BENCH_REPORT_VERSION: u32 = 1;
+
+/// Timing of one benchmark
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchRecord {
+    /// Area being measured, e.g. `"distance"` or `"hnsw_search"`
+    pub suite: String,
+    /// Benchmark name within the suite, including parameters
+    pub name: String,
+    /// Mean time per iteration in nanoseconds
+    pub mean_ns: f64,
+    /// Median time per iteration in nanoseconds
+    pub median_ns: f64,
+    /// Standard deviation of the time per iteration in nanoseconds
+    pub std_dev_ns: f64,
+    /// Elements processed per second (iterations when no throughput is declared)
+    pub throughput_per_sec: Option<f64>,
+}
+
+impl BenchRecord {
+    /// Summarize per-iteration timings in nanoseconds
+    pub fn from_samples(
+        suite: impl Into<String>,
+        name: impl Into<String>,
+        samples_ns: &[f64],
+    ) -> Self {
+        let n = samples_ns.len().max(1) as f64;
+        let mean = samples_ns.iter().sum::<f64>() / n;
+        let variance = samples_ns.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n;
+
+        let mut sorted = samples_ns.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let median = sorted.get(sorted.len() / 2).copied().unwrap_or(0.0);
+
+        Self {
+            suite: suite.into(),
+            name: name.into(),
+            mean_ns: mean,
+            median_ns: median,
+            std_dev_ns: variance.sqrt(),
+            throughput_per_sec: (mean > 0.0).then(|| 1e9 / mean),
+        }
+    }
+}
+
+/// A set of benchmark results with the environment that produced them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchReport {
+    /// Report schema version
+    pub version: u32,
+    /// Tool that produced the report, e.g. `"criterion"` or `"cli"`
+    pub source: String,
+    /// RFC 3339 timestamp of the run
+    pub timestamp: String,
+    /// Build and machine capabilities
+    pub capabilities: Capabilities,
+    /// Benchmark results
+    pub records: Vec<BenchRecord>,
+}
+
+impl BenchReport {
+    /// Create a report for the current machine
+    pub fn new(source: impl Into<String>, records: Vec<BenchRecord>) -> Self {
+        Self {
+            version: BENCH_REPORT_VERSION,
+            source: source.into(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            capabilities: capabilities(),
+            records,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_from_samples() {
+        let record = BenchRecord::from_samples("distance", "cosine/128", &[100.0, 200.0, 300.0]);
+        assert_eq!(record.mean_ns, 200.0);
+        assert_eq!(record.median_ns, 200.0);
+        assert!(record.std_dev_ns > 0.0);
+        assert_eq!(record.throughput_per_sec, Some(5e6));
+
+        let report = BenchReport::new("cli", vec![record]);
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed: BenchReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.version, BENCH_REPORT_VERSION);
+        assert_eq!(parsed.records[0].name, "cosine/128");
+    }
+}
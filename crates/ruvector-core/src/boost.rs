@@ -0,0 +1,281 @@
+//! Query-time metadata boosting
+//!
+//! A [`BoostSpec`] adjusts each candidate's similarity by its metadata while
+//! results are ranked, so applications do not have to over-fetch and
+//! re-rank on the client. Boosts are applied in order to the similarity of
+//! the candidate (see [`similarity`]); each one either multiplies or adds a
+//! value taken from the metadata:
+//!
+//! ```json
+//! {"boosts": [
+//!   {"type": "lookup", "field": "source", "values": {"docs": 1.5, "forum": 0.8}},
+//!   {"type": "numeric", "field": "votes", "op": "add", "scale": 0.01}
+//! ]}
+//! ```
+
+use crate::error::{Result, RuvectorError};
+use crate::types::{DistanceMetric, SearchResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Candidates fetched per requested result unless configured otherwise
+pub const DEFAULT_OVERSAMPLE: usize = 4;
+
+/// How a boost value is combined with the running score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoostOp {
+    /// `score * value`
+    #[default]
+    Multiply,
+    /// `score + value`
+    Add,
+}
+
+impl BoostOp {
+    fn apply(self, score: f32, value: f32) -> f32 {
+        match self {
+            BoostOp::Multiply => score * value,
+            BoostOp::Add => score + value,
+        }
+    }
+}
+
+/// One score adjustment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Boost {
+    /// Value chosen by the metadata value of `field`, e.g. a weight per source
+    Lookup {
+        /// Metadata field
+        field: String,
+        /// Value per field value; non-string field values match by JSON text
+        values: HashMap<String, f32>,
+        /// Value when the field is missing or not listed
+        #[serde(default = "neutral_multiplier")]
+        default: f32,
+        /// How the value is combined with the score
+        #[serde(default)]
+        op: BoostOp,
+    },
+    /// `scale * field` for a numeric metadata field
+    Numeric {
+        /// Metadata field
+        field: String,
+        /// Factor applied to the field value
+        scale: f32,
+        /// Value when the field is missing or not a number
+        #[serde(default)]
+        missing: f32,
+        /// How the value is combined with the score
+        #[serde(default)]
+        op: BoostOp,
+    },
+}
+
+fn neutral_multiplier() -> f32 {
+    1.0
+}
+
+impl Boost {
+    /// Metadata field the boost reads
+    pub fn field(&self) -> &str {
+        match self {
+            Boost::Lookup { field, .. } | Boost::Numeric { field, .. } => field,
+        }
+    }
+
+    fn apply(&self, score: f32, metadata: Option<&HashMap<String, Value>>) -> f32 {
+        let field = metadata.and_then(|m| m.get(self.field()));
+        match self {
+            Boost::Lookup {
+                values,
+                default,
+                op,
+                ..
+            } => {
+                let value = field
+                    .and_then(|v| match v {
+                        Value::String(s) => values.get(s),
+                        other => values.get(&other.to_string()),
+                    })
+                    .copied()
+                    .unwrap_or(*default);
+                op.apply(score, value)
+            }
+            Boost::Numeric {
+                scale, missing, op, ..
+            } => {
+                let value = field
+                    .and_then(Value::as_f64)
+                    .map_or(*missing, |x| scale * x as f32);
+                op.apply(score, value)
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        let finite = match self {
+            Boost::Lookup {
+                values, default, ..
+            } => default.is_finite() && values.values().all(|v| v.is_finite()),
+            Boost::Numeric { scale, missing, .. } => scale.is_finite() && missing.is_finite(),
+        };
+        if self.field().is_empty() {
+            return Err(RuvectorError::InvalidParameter(
+                "Boost field must not be empty".to_string(),
+            ));
+        }
+        if !finite {
+            return Err(RuvectorError::InvalidParameter(format!(
+                "Boost on {} has a non-finite value",
+                self.field()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Ordered list of boosts for one query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoostSpec {
+    /// Adjustments, applied in order
+    pub boosts: Vec<Boost>,
+    /// Candidates fetched per requested result before boosting
+    #[serde(default = "default_oversample")]
+    pub oversample: usize,
+}
+
+fn default_oversample() -> usize {
+    DEFAULT_OVERSAMPLE
+}
+
+impl Default for BoostSpec {
+    fn default() -> Self {
+        Self {
+            boosts: Vec::new(),
+            oversample: DEFAULT_OVERSAMPLE,
+        }
+    }
+}
+
+impl BoostSpec {
+    /// Check that every boost names a field and uses finite values
+    pub fn validate(&self) -> Result<()> {
+        if self.oversample == 0 {
+            return Err(RuvectorError::InvalidParameter(
+                "Boost oversample must be at least 1".to_string(),
+            ));
+        }
+        self.boosts.iter().try_for_each(Boost::validate)
+    }
+
+    /// Boosted score of a candidate with the given similarity
+    pub fn score(&self, similarity: f32, metadata: Option<&HashMap<String, Value>>) -> f32 {
+        self.boosts
+            .iter()
+            .fold(similarity, |score, boost| boost.apply(score, metadata))
+    }
+
+    /// Boost `candidates` scored with `metric` and keep the best `k`
+    pub fn rank(
+        &self,
+        candidates: Vec<SearchResult>,
+        metric: DistanceMetric,
+        k: usize,
+    ) -> Vec<BoostedResult> {
+        let mut ranked: Vec<BoostedResult> = candidates
+            .into_iter()
+            .map(|result| {
+                let similarity = similarity(metric, result.score);
+                BoostedResult {
+                    score: self.score(similarity, result.metadata.as_ref()),
+                    similarity,
+                    result,
+                }
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+        ranked.truncate(k);
+        ranked
+    }
+}
+
+/// A search result with its boosted score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoostedResult {
+    /// The underlying result; `result.score` is still the raw distance
+    pub result: SearchResult,
+    /// Similarity before boosting (higher is better)
+    pub similarity: f32,
+    /// Score after boosting (higher is better)
+    pub score: f32,
+}
+
+/// Similarity for a distance under `metric`, higher meaning more similar
+///
+/// Cosine distance maps to cosine similarity and dot-product distance to the
+/// dot product; Euclidean and Manhattan distances map to `1 / (1 + d)`.
+pub fn similarity(metric: DistanceMetric, distance: f32) -> f32 {
+    match metric {
+        DistanceMetric::Cosine => 1.0 - distance,
+        DistanceMetric::DotProduct => -distance,
+        DistanceMetric::Euclidean | DistanceMetric::Manhattan => 1.0 / (1.0 + distance),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(id: &str, distance: f32, metadata: Value) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            score: distance,
+            vector: None,
+            metadata: serde_json::from_value(metadata).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_boost_reranks() {
+        let spec: BoostSpec = serde_json::from_value(serde_json::json!({
+            "boosts": [
+                {"type": "lookup", "field": "source", "values": {"docs": 2.0}, "default": 0.5},
+                {"type": "numeric", "field": "votes", "op": "add", "scale": 0.01}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(spec.oversample, DEFAULT_OVERSAMPLE);
+        spec.validate().unwrap();
+
+        let ranked = spec.rank(
+            vec![
+                candidate("close", 0.1, serde_json::json!({"source": "forum"})),
+                candidate(
+                    "far",
+                    0.5,
+                    serde_json::json!({"source": "docs", "votes": 10}),
+                ),
+            ],
+            DistanceMetric::Cosine,
+            1,
+        );
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].result.id, "far");
+        assert!((ranked[0].similarity - 0.5).abs() < 1e-6);
+        assert!((ranked[0].score - 1.1).abs() < 1e-6);
+
+        let bad = BoostSpec {
+            boosts: vec![Boost::Numeric {
+                field: "votes".to_string(),
+                scale: f32::NAN,
+                missing: 0.0,
+                op: BoostOp::Add,
+            }],
+            ..Default::default()
+        };
+        assert!(bad.validate().is_err());
+    }
+}
@@ -0,0 +1,111 @@
+//! Bulk loading
+//!
+//! [`VectorDB::bulk_load`](crate::VectorDB::bulk_load) is the fast path for
+//! initial index construction. Instead of taking the index lock and linking
+//! the graph once per vector, it:
+//!
+//! - writes entries to storage in large transactions of `chunk_size`,
+//! - stages vectors and links them into the index in a few large parallel
+//!   passes (normally one, at the end),
+//! - links early and pauses when the staging buffer exceeds
+//!   `max_staged_bytes` or the system runs low on memory.
+//!
+//! Loaded entries stay hidden from searches until the load finishes.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Bulk load settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkLoadConfig {
+    /// Entries per storage transaction
+    pub chunk_size: usize,
+    /// Staged vector bytes that trigger an early index pass
+    pub max_staged_bytes: usize,
+    /// Pause while the system has less available memory than this; 0 disables
+    pub min_available_bytes: u64,
+    /// Pause between memory checks while under pressure
+    pub throttle_delay: Duration,
+    /// Maximum pauses per memory-pressure episode before continuing anyway
+    pub max_throttle_waits: usize,
+}
+
+impl Default for BulkLoadConfig {
+    fn default() -> Self {
+        Self {
+            chunk_size: 10_000,
+            max_staged_bytes: 1 << 30,
+            min_available_bytes: 512 << 20,
+            throttle_delay: Duration::from_millis(50),
+            max_throttle_waits: 100,
+        }
+    }
+}
+
+/// Outcome of a bulk load
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BulkLoadReport {
+    /// Entries stored and indexed
+    pub inserted: usize,
+    /// Entries skipped because their content id was already stored
+    #[serde(default)]
+    pub skipped: usize,
+    /// Storage transactions committed
+    pub chunks: usize,
+    /// Index linking passes
+    pub index_passes: usize,
+    /// Pauses taken because of memory pressure
+    pub throttle_waits: usize,
+    /// Total time in milliseconds
+    pub elapsed_ms: u64,
+}
+
+/// Memory the kernel reports as available, where known
+pub fn available_memory_bytes() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        parse_mem_available(&meminfo)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Whether available memory is below the configured floor
+pub(crate) fn under_memory_pressure(config: &BulkLoadConfig) -> bool {
+    config.min_available_bytes > 0
+        && available_memory_bytes().is_some_and(|available| available < config.min_available_bytes)
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_mem_available(meminfo: &str) -> Option<u64> {
+    meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemAvailable:"))
+        .and_then(|rest| rest.trim().strip_suffix("kB"))
+        .and_then(|kb| kb.trim().parse::<u64>().ok())
+        .map(|kb| kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mem_available() {
+        let meminfo = "MemTotal:       16318412 kB\nMemFree:         1234567 kB\nMemAvailable:    8000000 kB\n";
+        assert_eq!(parse_mem_available(meminfo), Some(8_000_000 * 1024));
+        assert_eq!(parse_mem_available("MemTotal: 1 kB\n"), None);
+    }
+
+    #[test]
+    fn test_pressure_disabled() {
+        let config = BulkLoadConfig {
+            min_available_bytes: 0,
+            ..Default::default()
+        };
+        assert!(!under_memory_pressure(&config));
+    }
+}
@@ -0,0 +1,129 @@
+//! Build and runtime capability reporting
+//!
+//! [`capabilities`] describes what this build of Ruvector can do on the
+//! current machine: compiled-in features, the SIMD level the CPU supports,
+//! the storage backend and the thread pool size. The Node.js and WASM
+//! bindings expose the same report so installation problems ("why is search
+//! slow?", "why can't I persist?") can be diagnosed from application code.
+
+use serde::{Deserialize, Serialize};
+
+/// Features available to this build on the current machine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// Crate version
+    pub version: String,
+    /// Best SIMD instruction set detected: "avx512", "avx2", "sse4.1", "neon", "simd128" or "scalar"
+    pub simd_level: String,
+    /// Whether the SimSIMD distance kernels are compiled in
+    pub simd_kernels: bool,
+    /// Whether a GPU backend is available
+    pub gpu: bool,
+    /// Storage backend: "redb" (file-backed) or "memory"
+    pub storage_backend: String,
+    /// Whether HNSW indexing is compiled in
+    pub hnsw: bool,
+    /// Whether operations run on a parallel thread pool
+    pub parallel: bool,
+    /// Worker threads available for parallel operations
+    pub threads: usize,
+    /// Quantization codecs: "scalar", "product", "binary"
+    pub quantization: Vec<String>,
+    /// Attention mechanisms; empty unless a binding links an attention crate
+    pub attention: Vec<String>,
+    /// Whether API-based embeddings are compiled in
+    pub api_embeddings: bool,
+}
+
+/// Report the capabilities of this build on the current machine
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        simd_level: simd_level().to_string(),
+        simd_kernels: cfg!(feature = "simd"),
+        gpu: false,
+        storage_backend: if cfg!(feature = "storage") {
+            "redb"
+        } else {
+            "memory"
+        }
+        .to_string(),
+        hnsw: cfg!(feature = "hnsw"),
+        parallel: cfg!(feature = "parallel"),
+        threads: threads(),
+        quantization: ["scalar", "product", "binary"]
+            .iter()
+            .map(|codec| codec.to_string())
+            .collect(),
+        attention: Vec::new(),
+        api_embeddings: cfg!(feature = "api-embeddings"),
+    }
+}
+
+/// Best SIMD instruction set usable on this CPU
+pub fn simd_level() -> &'static str {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx512f") {
+            return "avx512";
+        }
+        if is_x86_feature_detected!("avx2") {
+            return "avx2";
+        }
+        if is_x86_feature_detected!("sse4.1") {
+            return "sse4.1";
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            return "neon";
+        }
+    }
+
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    {
+        return "simd128";
+    }
+
+    #[allow(unreachable_code)]
+    "scalar"
+}
+
+fn threads() -> usize {
+    #[cfg(feature = "parallel")]
+    {
+        rayon::current_num_threads()
+    }
+
+    #[cfg(all(not(feature = "parallel"), not(target_arch = "wasm32")))]
+    {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    }
+
+    #[cfg(all(not(feature = "parallel"), target_arch = "wasm32"))]
+    {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_report() {
+        let caps = capabilities();
+        assert_eq!(caps.version, env!("CARGO_PKG_VERSION"));
+        assert!(caps.threads >= 1);
+        assert_eq!(caps.storage_backend == "redb", cfg!(feature = "storage"));
+        assert!(caps.quantization.contains(&"product".to_string()));
+
+        let json = serde_json::to_string(&caps).unwrap();
+        let parsed: Capabilities = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, caps);
+    }
+}
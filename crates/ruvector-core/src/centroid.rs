@@ -0,0 +1,283 @@
+//! Server-maintained centroid groups
+//!
+//! A centroid group is declared on a metadata key, e.g. `"user"`. For every
+//! distinct value of that key the database keeps the mean of all vectors
+//! carrying it, and stores that mean as an ordinary entry with id
+//! [`centroid_id`]`(key, value)`. Centroids are indexed and searchable like
+//! any other vector and are updated as members are inserted, overwritten,
+//! updated, deleted or restored.
+//!
+//! Centroid entries carry [`CENTROID_KEY`] (the group key),
+//! [`CENTROID_VALUE_KEY`] and [`CENTROID_MEMBERS_KEY`] in their metadata;
+//! filter on [`CENTROID_KEY`] to search centroids only. They are derived
+//! data and are not recorded in the op log.
+
+use crate::types::{VectorEntry, VectorId};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Metadata key holding the group key on centroid entries
+pub const CENTROID_KEY: &str = "__centroid__";
+
+/// Metadata key holding the member value on centroid entries
+pub const CENTROID_VALUE_KEY: &str = "__centroid_value__";
+
+/// Metadata key holding the member count on centroid entries
+pub const CENTROID_MEMBERS_KEY: &str = "__centroid_members__";
+
+/// Whether `entry` is a centroid entry rather than a member
+pub fn is_centroid(entry: &VectorEntry) -> bool {
+    entry
+        .metadata
+        .as_ref()
+        .is_some_and(|metadata| metadata.contains_key(CENTROID_KEY))
+}
+
+/// Id of the centroid entry for `value` in the group on `key`
+pub fn centroid_id(key: &str, value: &str) -> VectorId {
+    format!("{}:{}:{}", CENTROID_KEY, key, value)
+}
+
+/// The group value a metadata `value` stands for: strings as-is, numbers
+/// and booleans by their JSON text
+pub(crate) fn member_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        v @ (Value::Number(_) | Value::Bool(_)) => Some(v.to_string()),
+        _ => None,
+    }
+}
+
+/// Running sum of a centroid's members
+#[derive(Debug, Clone, Default)]
+struct RunningMean {
+    sum: Vec<f64>,
+    count: u64,
+}
+
+/// Change to a centroid entry after members were added or removed
+#[derive(Debug, Clone)]
+pub(crate) enum CentroidChange {
+    /// Store this entry (new or updated centroid)
+    Upsert(VectorEntry),
+    /// The last member left; delete the centroid entry
+    Remove(VectorId),
+}
+
+/// Running means of every centroid in every declared group
+#[derive(Debug, Default)]
+pub(crate) struct CentroidTracker {
+    groups: HashMap<String, HashMap<String, RunningMean>>,
+}
+
+impl CentroidTracker {
+    /// Whether any group is declared
+    pub(crate) fn is_active(&self) -> bool {
+        !self.groups.is_empty()
+    }
+
+    /// Declared group keys, sorted
+    pub(crate) fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.groups.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    /// Declare a group; returns false if it already existed
+    pub(crate) fn define(&mut self, key: &str) -> bool {
+        if self.groups.contains_key(key) {
+            return false;
+        }
+        self.groups.insert(key.to_string(), HashMap::new());
+        true
+    }
+
+    /// Forget a group, returning the ids of its centroid entries
+    pub(crate) fn drop_group(&mut self, key: &str) -> Option<Vec<VectorId>> {
+        self.groups.remove(key).map(|centroids| {
+            centroids
+                .keys()
+                .map(|value| centroid_id(key, value))
+                .collect()
+        })
+    }
+
+    /// Apply member removals then additions, returning centroid entries to write
+    pub(crate) fn update(
+        &mut self,
+        removed: &[VectorEntry],
+        added: &[VectorEntry],
+    ) -> Vec<CentroidChange> {
+        let mut touched = Vec::new();
+        for entry in removed {
+            touched.extend(self.accumulate(entry, -1.0));
+        }
+        for entry in added {
+            touched.extend(self.accumulate(entry, 1.0));
+        }
+        touched.sort();
+        touched.dedup();
+
+        touched
+            .into_iter()
+            .map(|(key, value)| {
+                let group = self.groups.get_mut(&key).expect("touched group exists");
+                let id = centroid_id(&key, &value);
+                match group.get(&value) {
+                    Some(mean) if mean.count > 0 => {
+                        CentroidChange::Upsert(centroid_entry(id, &key, &value, mean))
+                    }
+                    _ => {
+                        group.remove(&value);
+                        CentroidChange::Remove(id)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Forget all running means, keeping the declared groups
+    pub(crate) fn reset(&mut self) {
+        for group in self.groups.values_mut() {
+            group.clear();
+        }
+    }
+
+    /// Count `entry` as a member without producing changes
+    pub(crate) fn add_member(&mut self, entry: &VectorEntry) {
+        self.accumulate(entry, 1.0);
+    }
+
+    /// Current entries of every centroid
+    pub(crate) fn entries(&self) -> Vec<VectorEntry> {
+        self.groups
+            .iter()
+            .flat_map(|(key, group)| {
+                group
+                    .iter()
+                    .filter(|(_, mean)| mean.count > 0)
+                    .map(move |(value, mean)| {
+                        centroid_entry(centroid_id(key, value), key, value, mean)
+                    })
+            })
+            .collect()
+    }
+
+    /// Current entries of the centroids in the group on `key`, or `None`
+    /// if no such group is declared
+    pub(crate) fn group_entries(&self, key: &str) -> Option<Vec<VectorEntry>> {
+        let group = self.groups.get(key)?;
+        Some(
+            group
+                .iter()
+                .filter(|(_, mean)| mean.count > 0)
+                .map(|(value, mean)| centroid_entry(centroid_id(key, value), key, value, mean))
+                .collect(),
+        )
+    }
+
+    /// Number of centroid entries across all groups
+    pub(crate) fn len(&self) -> usize {
+        self.groups.values().map(HashMap::len).sum()
+    }
+
+    /// Add (`sign` 1) or remove (`sign` -1) `entry`, returning the centroids touched
+    fn accumulate(&mut self, entry: &VectorEntry, sign: f64) -> Vec<(String, String)> {
+        let memberships = self.memberships(entry);
+        for (key, value) in &memberships {
+            let Some(group) = self.groups.get_mut(key) else {
+                continue;
+            };
+            let mean = group.entry(value.clone()).or_default();
+            if mean.sum.len() != entry.vector.len() {
+                mean.sum = vec![0.0; entry.vector.len()];
+            }
+            for (s, &x) in mean.sum.iter_mut().zip(&entry.vector) {
+                *s += sign * x as f64;
+            }
+            if sign > 0.0 {
+                mean.count += 1;
+            } else {
+                mean.count = mean.count.saturating_sub(1);
+            }
+        }
+        memberships
+    }
+
+    /// `(group key, value)` pairs `entry` is a member of
+    fn memberships(&self, entry: &VectorEntry) -> Vec<(String, String)> {
+        let Some(metadata) = entry.metadata.as_ref().filter(|_| !is_centroid(entry)) else {
+            return Vec::new();
+        };
+        self.groups
+            .keys()
+            .filter_map(|key| Some((key.clone(), member_value(metadata.get(key)?)?)))
+            .collect()
+    }
+}
+
+fn centroid_entry(id: VectorId, key: &str, value: &str, mean: &RunningMean) -> VectorEntry {
+    let n = mean.count as f64;
+    VectorEntry {
+        id: Some(id),
+        vector: mean.sum.iter().map(|s| (s / n) as f32).collect(),
+        metadata: Some(HashMap::from([
+            (CENTROID_KEY.to_string(), Value::from(key)),
+            (CENTROID_VALUE_KEY.to_string(), Value::from(value)),
+            (CENTROID_MEMBERS_KEY.to_string(), Value::from(mean.count)),
+        ])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(id: &str, user: &str, vector: Vec<f32>) -> VectorEntry {
+        VectorEntry {
+            id: Some(id.to_string()),
+            vector,
+            metadata: Some(HashMap::from([("user".to_string(), Value::from(user))])),
+        }
+    }
+
+    #[test]
+    fn test_running_mean() {
+        let mut tracker = CentroidTracker::default();
+        assert!(tracker.define("user"));
+        assert!(!tracker.define("user"));
+
+        let a = member("a", "u1", vec![1.0, 0.0]);
+        let b = member("b", "u1", vec![3.0, 2.0]);
+        let changes = tracker.update(&[], &[a.clone(), b]);
+        match &changes[..] {
+            [CentroidChange::Upsert(entry)] => {
+                assert_eq!(entry.id.as_deref(), Some("__centroid__:user:u1"));
+                assert_eq!(entry.vector, vec![2.0, 1.0]);
+                assert_eq!(entry.metadata.as_ref().unwrap()[CENTROID_MEMBERS_KEY], 2);
+            }
+            other => panic!("unexpected changes {:?}", other),
+        }
+
+        // Centroid entries never count as members
+        let changes = tracker.update(&[], &[centroid_entry_for_test()]);
+        assert!(changes.is_empty());
+
+        let changes = tracker.update(&[a], &[]);
+        assert!(matches!(&changes[..], [CentroidChange::Upsert(e)] if e.vector == vec![3.0, 2.0]));
+        let changes = tracker.update(&[member("b", "u1", vec![3.0, 2.0])], &[]);
+        assert!(
+            matches!(&changes[..], [CentroidChange::Remove(id)] if id == "__centroid__:user:u1")
+        );
+    }
+
+    fn centroid_entry_for_test() -> VectorEntry {
+        let mut entry = member("c", "u1", vec![9.0, 9.0]);
+        entry
+            .metadata
+            .as_mut()
+            .unwrap()
+            .insert(CENTROID_KEY.to_string(), Value::from("user"));
+        entry
+    }
+}
@@ -0,0 +1,165 @@
+//! Classification against labelled entries
+//!
+//! [`VectorDB::classify`](crate::VectorDB::classify) predicts a label for
+//! a vector from the stored entries labelled in a metadata field, e.g.
+//! tagging incoming documents by `"topic"` during ingestion:
+//!
+//! - [`ClassifyMethod::Centroid`] picks the class whose mean vector is
+//!   nearest. Means come from the centroid group on the field (see
+//!   [`crate::centroid`]), which must be declared first; they are updated
+//!   as labelled entries are inserted, updated or deleted, so the classifier
+//!   learns online with no retraining step.
+//! - [`ClassifyMethod::Knn`] searches the `k` nearest entries and lets
+//!   those carrying the field vote, breaking ties by total distance. It
+//!   needs no setup and follows inserts immediately, at the cost of a
+//!   search.
+//!
+//! Values are compared as in centroid groups: strings as-is, numbers and
+//! booleans by their JSON text.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How a label is predicted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "method")]
+pub enum ClassifyMethod {
+    /// Nearest class mean
+    Centroid,
+    /// Majority vote of the labelled entries among the `k` nearest
+    Knn {
+        /// Neighbors that vote
+        k: usize,
+    },
+}
+
+/// Classification settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassifyConfig {
+    /// Metadata field holding the label
+    pub field: String,
+    /// How the label is predicted
+    pub method: ClassifyMethod,
+    /// Softmax temperature over class distances for centroid confidence
+    pub temperature: f32,
+}
+
+impl ClassifyConfig {
+    /// Nearest-centroid classification on `field`
+    pub fn centroid(field: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            method: ClassifyMethod::Centroid,
+            temperature: 0.05,
+        }
+    }
+
+    /// k-NN vote on `field`
+    pub fn knn(field: &str, k: usize) -> Self {
+        Self {
+            method: ClassifyMethod::Knn { k },
+            ..Self::centroid(field)
+        }
+    }
+}
+
+/// A predicted label
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Classification {
+    /// Predicted label
+    pub label: String,
+    /// Distance to the class mean, or mean distance of the winning votes
+    pub distance: f32,
+    /// Lead over the runner-up: distance gap for centroids, vote share
+    /// gap for k-NN; 1 when there is no runner-up
+    pub margin: f32,
+    /// Softmax probability over class distances for centroids, vote share
+    /// for k-NN
+    pub confidence: f32,
+    /// Members of the class for centroids, votes for k-NN
+    pub support: usize,
+}
+
+/// Nearest class from `(label, distance, members)` candidates
+pub(crate) fn nearest_class(
+    classes: &[(String, f32, usize)],
+    temperature: f32,
+) -> Option<Classification> {
+    let mut ranked: Vec<&(String, f32, usize)> = classes.iter().collect();
+    ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let &(ref label, distance, members) = *ranked.first()?;
+
+    let temperature = temperature.max(f32::EPSILON);
+    let total: f32 = classes
+        .iter()
+        .map(|c| ((distance - c.1) / temperature).exp())
+        .sum();
+    Some(Classification {
+        label: label.clone(),
+        distance,
+        margin: ranked.get(1).map_or(1.0, |r| r.1 - distance),
+        confidence: 1.0 / total,
+        support: members,
+    })
+}
+
+/// Majority vote over `(label, distance)` neighbors
+pub(crate) fn vote(neighbors: &[(String, f32)]) -> Option<Classification> {
+    let mut tally: HashMap<&str, (usize, f32)> = HashMap::new();
+    for (label, distance) in neighbors {
+        let entry = tally.entry(label.as_str()).or_default();
+        entry.0 += 1;
+        entry.1 += distance;
+    }
+    let mut ranked: Vec<(&str, (usize, f32))> = tally.into_iter().collect();
+    ranked.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then(a.1 .1.total_cmp(&b.1 .1)));
+
+    let (label, (votes, total)) = *ranked.first()?;
+    let voters = neighbors.len() as f32;
+    let runner_up = ranked.get(1).map_or(0, |r| r.1 .0);
+    Some(Classification {
+        label: label.to_string(),
+        distance: total / votes as f32,
+        margin: if ranked.len() > 1 {
+            (votes - runner_up) as f32 / voters
+        } else {
+            1.0
+        },
+        confidence: votes as f32 / voters,
+        support: votes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_class() {
+        let classes = vec![
+            ("sports".to_string(), 0.4, 10),
+            ("politics".to_string(), 0.1, 3),
+            ("tech".to_string(), 0.3, 7),
+        ];
+        let result = nearest_class(&classes, 0.05).unwrap();
+        assert_eq!(result.label, "politics");
+        assert_eq!(result.support, 3);
+        assert!((result.margin - 0.2).abs() < 1e-6);
+        assert!(result.confidence > 0.9);
+        assert!(nearest_class(&[], 0.05).is_none());
+    }
+
+    #[test]
+    fn test_vote() {
+        let neighbors: Vec<(String, f32)> = [("a", 0.1), ("b", 0.2), ("b", 0.3), ("a", 0.2)]
+            .iter()
+            .map(|&(l, d)| (l.to_string(), d))
+            .collect();
+        // Two votes each; "a" is closer in total
+        let result = vote(&neighbors).unwrap();
+        assert_eq!(result.label, "a");
+        assert_eq!(result.margin, 0.0);
+        assert_eq!(result.confidence, 0.5);
+        assert!((result.distance - 0.15).abs() < 1e-6);
+    }
+}
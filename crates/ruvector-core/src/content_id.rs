@@ -0,0 +1,125 @@
+//! Content-addressable vector ids
+//!
+//! Under [`IdStrategy::Content`], set with
+//! [`VectorDB::set_id_strategy`](crate::VectorDB::set_id_strategy), entries
+//! inserted without an id are stored under a hash of their vector and
+//! metadata. Inserting the same content twice then yields the same id, and
+//! the second insert is skipped instead of writing a duplicate, so an
+//! ingestion job can simply be retried from the start.
+//!
+//! The hash covers the vector as stored (after normalization) and the
+//! metadata with object keys sorted, so key order does not matter but
+//! `1` and `1.0` do. Entries inserted with an explicit id keep it, and
+//! updates do not change an entry's id.
+
+use crate::types::VectorId;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Bytes of the SHA-256 digest kept in a content id
+const CONTENT_ID_BYTES: usize = 16;
+
+/// How ids are chosen for entries inserted without one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdStrategy {
+    /// A random UUID
+    #[default]
+    Random,
+    /// A hash of the vector and metadata; repeated inserts are skipped
+    Content,
+}
+
+/// The content id of a vector and its metadata
+///
+/// 32 hex characters of the SHA-256 of the components' little-endian bits
+/// followed by the canonical JSON of the metadata.
+pub fn content_id(
+    vector: &[f32],
+    metadata: Option<&HashMap<String, serde_json::Value>>,
+) -> VectorId {
+    let mut hasher = Sha256::new();
+    hasher.update((vector.len() as u64).to_le_bytes());
+    for &x in vector {
+        // -0.0 and 0.0 compare equal, so they hash equal too
+        let x = if x == 0.0 { 0.0f32 } else { x };
+        hasher.update(x.to_le_bytes());
+    }
+    if let Some(metadata) = metadata.filter(|m| !m.is_empty()) {
+        let mut json = String::new();
+        write_object(metadata.iter(), &mut json);
+        hasher.update(json.as_bytes());
+    }
+
+    hasher.finalize()[..CONTENT_ID_BYTES]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// JSON text of `value` with object keys sorted at every level
+fn write_canonical(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        serde_json::Value::Object(map) => write_object(map.iter(), out),
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+fn write_object<'a>(
+    fields: impl Iterator<Item = (&'a String, &'a serde_json::Value)>,
+    out: &mut String,
+) {
+    let mut fields: Vec<_> = fields.collect();
+    fields.sort_by(|a, b| a.0.cmp(b.0));
+    out.push('{');
+    for (i, (key, value)) in fields.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(&serde_json::Value::String(key.clone()).to_string());
+        out.push(':');
+        write_canonical(value, out);
+    }
+    out.push('}');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_content_id_is_canonical() {
+        let a: HashMap<String, serde_json::Value> = serde_json::from_value(json!({
+            "source": "doc.md",
+            "tags": {"lang": "en", "kind": "chunk"},
+        }))
+        .unwrap();
+        let b: HashMap<String, serde_json::Value> = serde_json::from_value(json!({
+            "tags": {"kind": "chunk", "lang": "en"},
+            "source": "doc.md",
+        }))
+        .unwrap();
+
+        let id = content_id(&[1.0, -0.0], Some(&a));
+        assert_eq!(id.len(), 2 * CONTENT_ID_BYTES);
+        assert_eq!(id, content_id(&[1.0, 0.0], Some(&b)));
+        assert_ne!(id, content_id(&[1.0, 0.0], None));
+        assert_ne!(id, content_id(&[1.0, 0.5], Some(&a)));
+        assert_eq!(
+            content_id(&[1.0], None),
+            content_id(&[1.0], Some(&HashMap::new()))
+        );
+    }
+}
@@ -0,0 +1,263 @@
+//! Context packs for retrieval-augmented generation
+//!
+//! [`VectorDB::build_context`](crate::VectorDB::build_context) turns a query
+//! into prompt-ready chunks in one call:
+//!
+//! 1. fetch `candidates` results,
+//! 2. drop near-duplicates (cosine similarity at or above
+//!    `dedupe_threshold`), keeping the more relevant copy,
+//! 3. pick chunks greedily by maximal marginal relevance until the token
+//!    budget is spent; chunks that would overflow it are skipped,
+//! 4. return the picked chunks ordered by relevance.
+//!
+//! Token counts are estimated from character counts; they are meant for
+//! budgeting, not billing.
+
+use crate::dedupe::cosine_similarity;
+use crate::types::{SearchResult, VectorId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Context pack settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextPackConfig {
+    /// Maximum estimated tokens across all chunks
+    pub token_budget: usize,
+    /// Results fetched before deduplication and selection
+    pub candidates: usize,
+    /// Cosine similarity at which two chunks count as duplicates; `None`
+    /// keeps duplicates
+    pub dedupe_threshold: Option<f32>,
+    /// MMR trade-off between relevance (1.0) and diversity (0.0); `None`
+    /// selects purely by relevance
+    pub mmr_lambda: Option<f32>,
+    /// Metadata field holding the chunk text
+    pub text_field: String,
+    /// Characters per token for the estimate
+    pub chars_per_token: f32,
+}
+
+impl Default for ContextPackConfig {
+    fn default() -> Self {
+        Self {
+            token_budget: 2000,
+            candidates: 50,
+            dedupe_threshold: Some(0.95),
+            mmr_lambda: Some(0.7),
+            text_field: "text".to_string(),
+            chars_per_token: 4.0,
+        }
+    }
+}
+
+/// A chunk selected for the context
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextChunk {
+    /// Entry id
+    pub id: VectorId,
+    /// Chunk text
+    pub text: String,
+    /// Search score (distance; lower is more relevant)
+    pub score: f32,
+    /// Cosine similarity to the query
+    pub relevance: f32,
+    /// Estimated tokens
+    pub tokens: usize,
+    /// Entry metadata
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+}
+
+/// Chunks ready to place in a prompt
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContextPack {
+    /// Selected chunks, most relevant first
+    pub chunks: Vec<ContextChunk>,
+    /// Estimated tokens across `chunks`
+    pub total_tokens: usize,
+    /// Candidates fetched
+    pub candidates: usize,
+    /// Candidates dropped as near-duplicates
+    pub duplicates_removed: usize,
+    /// Candidates without text in `text_field`
+    pub missing_text: usize,
+    /// Candidates left out because they did not fit the budget
+    pub over_budget: usize,
+}
+
+impl ContextPack {
+    /// Chunk texts joined with `separator`, in pack order
+    pub fn join(&self, separator: &str) -> String {
+        self.chunks
+            .iter()
+            .map(|chunk| chunk.text.as_str())
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+}
+
+/// Estimated token count of `text`
+pub fn estimate_tokens(text: &str, chars_per_token: f32) -> usize {
+    (text.chars().count() as f32 / chars_per_token.max(f32::EPSILON)).ceil() as usize
+}
+
+struct Candidate {
+    result: SearchResult,
+    text: String,
+    vector: Vec<f32>,
+    relevance: f32,
+    tokens: usize,
+}
+
+/// Build a pack from search results sorted by relevance
+///
+/// Results must carry their vectors; results without one are treated as
+/// missing text.
+pub fn assemble(
+    query: &[f32],
+    results: Vec<SearchResult>,
+    config: &ContextPackConfig,
+) -> ContextPack {
+    let mut pack = ContextPack {
+        candidates: results.len(),
+        ..Default::default()
+    };
+
+    let mut candidates: Vec<Candidate> = Vec::with_capacity(results.len());
+    for mut result in results {
+        let text = result
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get(&config.text_field))
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        let (Some(text), Some(vector)) = (text, result.vector.take()) else {
+            pack.missing_text += 1;
+            continue;
+        };
+
+        if let Some(threshold) = config.dedupe_threshold {
+            if candidates
+                .iter()
+                .any(|kept| cosine_similarity(&kept.vector, &vector) >= threshold)
+            {
+                pack.duplicates_removed += 1;
+                continue;
+            }
+        }
+
+        candidates.push(Candidate {
+            relevance: cosine_similarity(query, &vector),
+            tokens: estimate_tokens(&text, config.chars_per_token),
+            result,
+            text,
+            vector,
+        });
+    }
+
+    let lambda = config.mmr_lambda.unwrap_or(1.0).clamp(0.0, 1.0);
+    let mut selected: Vec<Candidate> = Vec::new();
+    let mut remaining_budget = config.token_budget;
+    while !candidates.is_empty() {
+        // Chunks that can no longer fit are out for good
+        let before = candidates.len();
+        candidates.retain(|c| c.tokens <= remaining_budget);
+        pack.over_budget += before - candidates.len();
+
+        let best = candidates
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let redundancy = selected
+                    .iter()
+                    .map(|s| cosine_similarity(&s.vector, &c.vector))
+                    .fold(0.0f32, f32::max);
+                (i, lambda * c.relevance - (1.0 - lambda) * redundancy)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i);
+        let Some(best) = best else { break };
+
+        let chosen = candidates.swap_remove(best);
+        remaining_budget -= chosen.tokens;
+        selected.push(chosen);
+    }
+
+    selected.sort_by(|a, b| a.result.score.total_cmp(&b.result.score));
+    pack.total_tokens = selected.iter().map(|c| c.tokens).sum();
+    pack.chunks = selected
+        .into_iter()
+        .map(|c| ContextChunk {
+            id: c.result.id,
+            text: c.text,
+            score: c.result.score,
+            relevance: c.relevance,
+            tokens: c.tokens,
+            metadata: c.result.metadata,
+        })
+        .collect();
+    pack
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(id: &str, score: f32, vector: Vec<f32>, text: &str) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("text".to_string(), serde_json::json!(text));
+        SearchResult {
+            id: id.to_string(),
+            score,
+            vector: Some(vector),
+            metadata: Some(metadata),
+        }
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens("", 4.0), 0);
+        assert_eq!(estimate_tokens("abcde", 4.0), 2);
+    }
+
+    #[test]
+    fn test_assemble_dedupes_and_respects_budget() {
+        let results = vec![
+            result("a", 0.1, vec![1.0, 0.0], "aaaa aaaa"),
+            result("a_copy", 0.11, vec![1.0, 0.001], "aaaa aaaa"),
+            result("b", 0.3, vec![0.7, 0.7], "bbbb bbbb"),
+            result("long", 0.4, vec![0.0, 1.0], &"x".repeat(400)),
+        ];
+        let config = ContextPackConfig {
+            token_budget: 10,
+            ..Default::default()
+        };
+
+        let pack = assemble(&[1.0, 0.0], results, &config);
+        let ids: Vec<_> = pack.chunks.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+        assert_eq!(pack.duplicates_removed, 1);
+        assert_eq!(pack.over_budget, 1);
+        assert_eq!(pack.total_tokens, 6);
+        assert!(pack.total_tokens <= config.token_budget);
+        assert_eq!(pack.join("\n"), "aaaa aaaa\nbbbb bbbb");
+    }
+
+    #[test]
+    fn test_mmr_prefers_diverse_chunk() {
+        let results = vec![
+            result("a", 0.1, vec![1.0, 0.0], "aaaa"),
+            result("a2", 0.12, vec![0.95, 0.3], "cccc"),
+            result("b", 0.5, vec![0.6, -0.8], "bbbb"),
+        ];
+        let config = ContextPackConfig {
+            token_budget: 2,
+            dedupe_threshold: None,
+            mmr_lambda: Some(0.3),
+            ..Default::default()
+        };
+
+        let pack = assemble(&[1.0, 0.0], results, &config);
+        let ids: Vec<_> = pack.chunks.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+    }
+}
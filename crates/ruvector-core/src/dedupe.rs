@@ -0,0 +1,160 @@
+//! Near-duplicate detection
+//!
+//! Finds groups of entries whose cosine similarity exceeds a threshold, using
+//! the index to limit comparisons to each vector's nearest candidates. See
+//! [`VectorDB::dedupe`](crate::VectorDB::dedupe).
+
+use crate::distance::cosine_distance;
+use crate::types::VectorId;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Metadata key set on tagged duplicates, pointing at the kept entry
+pub const DUPLICATE_OF_KEY: &str = "duplicate_of";
+
+/// What to do with the duplicates that are found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DuplicateAction {
+    /// Only report groups
+    Report,
+    /// Set [`DUPLICATE_OF_KEY`] in each duplicate's metadata
+    Tag,
+    /// Delete duplicates, keeping one entry per group
+    Delete,
+}
+
+/// Deduplication settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DedupeConfig {
+    /// Minimum cosine similarity for two entries to count as duplicates
+    pub threshold: f32,
+    /// Nearest neighbors examined per entry
+    pub candidates: usize,
+    /// Action applied to duplicates
+    pub action: DuplicateAction,
+}
+
+impl Default for DedupeConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.98,
+            candidates: 16,
+            action: DuplicateAction::Report,
+        }
+    }
+}
+
+/// A set of near-identical entries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    /// Entry that is kept (smallest id in the group)
+    pub keep: VectorId,
+    /// The other entries of the group
+    pub duplicates: Vec<VectorId>,
+}
+
+/// Outcome of a deduplication pass
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DedupeReport {
+    /// Entries examined
+    pub scanned: usize,
+    /// Duplicate groups, ordered by kept id
+    pub groups: Vec<DuplicateGroup>,
+    /// Entries tagged with [`DUPLICATE_OF_KEY`]
+    pub tagged: usize,
+    /// Entries deleted
+    pub deleted: usize,
+}
+
+impl DedupeReport {
+    /// Total number of duplicates across all groups
+    pub fn duplicate_count(&self) -> usize {
+        self.groups.iter().map(|g| g.duplicates.len()).sum()
+    }
+}
+
+/// Cosine similarity of two vectors
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    1.0 - cosine_distance(a, b)
+}
+
+/// Merge similar pairs into groups (transitively)
+pub fn group_pairs(pairs: &[(VectorId, VectorId)]) -> Vec<DuplicateGroup> {
+    let mut parent: HashMap<&VectorId, &VectorId> = HashMap::new();
+
+    fn root<'a>(
+        parent: &mut HashMap<&'a VectorId, &'a VectorId>,
+        id: &'a VectorId,
+    ) -> &'a VectorId {
+        let mut current = id;
+        while let Some(&next) = parent.get(current) {
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+        parent.insert(id, current);
+        current
+    }
+
+    for (a, b) in pairs {
+        parent.entry(a).or_insert(a);
+        parent.entry(b).or_insert(b);
+        let (ra, rb) = (root(&mut parent, a), root(&mut parent, b));
+        if ra != rb {
+            // The smaller id becomes the root, so it ends up as the kept entry
+            let (low, high) = if ra < rb { (ra, rb) } else { (rb, ra) };
+            parent.insert(high, low);
+        }
+    }
+
+    let ids: Vec<&VectorId> = parent.keys().copied().collect();
+    let mut groups: BTreeMap<VectorId, Vec<VectorId>> = BTreeMap::new();
+    for id in ids {
+        let r = root(&mut parent, id);
+        if r != id {
+            groups.entry(r.clone()).or_default().push(id.clone());
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(keep, mut duplicates)| {
+            duplicates.sort();
+            DuplicateGroup { keep, duplicates }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(a: &str, b: &str) -> (VectorId, VectorId) {
+        (a.to_string(), b.to_string())
+    }
+
+    #[test]
+    fn test_group_pairs_transitive() {
+        let groups = group_pairs(&[pair("c", "b"), pair("b", "a"), pair("x", "y")]);
+        assert_eq!(
+            groups,
+            vec![
+                DuplicateGroup {
+                    keep: "a".to_string(),
+                    duplicates: vec!["b".to_string(), "c".to_string()],
+                },
+                DuplicateGroup {
+                    keep: "x".to_string(),
+                    duplicates: vec!["y".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-5);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,109 @@
+//! Partial vector updates
+//!
+//! Online-learned embeddings, such as a user profile nudged on every event,
+//! change by small steps. [`VectorDB::update_delta`](crate::VectorDB::update_delta)
+//! applies a [`DeltaUpdate`] to the stored vector server-side, so clients do
+//! not have to read the vector, modify it and write it back while racing
+//! other writers. Delta updates to the same database are serialized; the
+//! stored metadata is kept.
+
+use crate::error::{Result, RuvectorError};
+use serde::{Deserialize, Serialize};
+
+/// How a delta is combined with the stored vector
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DeltaUpdate {
+    /// `stored + alpha * delta`
+    Add {
+        /// Step size
+        alpha: f32,
+    },
+    /// `(1 - alpha) * stored + alpha * delta`, moving toward `delta`
+    Interpolate {
+        /// Fraction of the way to move, in `[0, 1]`
+        alpha: f32,
+    },
+}
+
+impl DeltaUpdate {
+    /// Combine `stored` with `delta`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuvectorError::DimensionMismatch`] if the lengths differ and
+    /// [`RuvectorError::InvalidParameter`] for a non-finite or out-of-range
+    /// `alpha` or a non-finite result.
+    pub fn apply(&self, stored: &[f32], delta: &[f32]) -> Result<Vec<f32>> {
+        if stored.len() != delta.len() {
+            return Err(RuvectorError::DimensionMismatch {
+                expected: stored.len(),
+                actual: delta.len(),
+            });
+        }
+
+        let updated: Vec<f32> = match *self {
+            DeltaUpdate::Add { alpha } => {
+                check_alpha(alpha, f32::NEG_INFINITY..=f32::INFINITY)?;
+                stored
+                    .iter()
+                    .zip(delta)
+                    .map(|(s, d)| s + alpha * d)
+                    .collect()
+            }
+            DeltaUpdate::Interpolate { alpha } => {
+                check_alpha(alpha, 0.0..=1.0)?;
+                stored
+                    .iter()
+                    .zip(delta)
+                    .map(|(s, d)| (1.0 - alpha) * s + alpha * d)
+                    .collect()
+            }
+        };
+
+        if updated.iter().any(|x| !x.is_finite()) {
+            return Err(RuvectorError::InvalidParameter(
+                "Delta update produced a non-finite component".to_string(),
+            ));
+        }
+        Ok(updated)
+    }
+}
+
+fn check_alpha(alpha: f32, range: std::ops::RangeInclusive<f32>) -> Result<()> {
+    if !alpha.is_finite() || !range.contains(&alpha) {
+        return Err(RuvectorError::InvalidParameter(format!(
+            "Delta alpha {} is out of range",
+            alpha
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_delta() {
+        let stored = [1.0, 2.0];
+        let added = DeltaUpdate::Add { alpha: 0.5 }
+            .apply(&stored, &[2.0, -2.0])
+            .unwrap();
+        assert_eq!(added, vec![2.0, 1.0]);
+
+        let moved = DeltaUpdate::Interpolate { alpha: 0.25 }
+            .apply(&stored, &[5.0, 2.0])
+            .unwrap();
+        assert_eq!(moved, vec![2.0, 2.0]);
+
+        assert!(DeltaUpdate::Interpolate { alpha: 1.5 }
+            .apply(&stored, &[0.0, 0.0])
+            .is_err());
+        assert!(DeltaUpdate::Add { alpha: f32::NAN }
+            .apply(&stored, &[0.0, 0.0])
+            .is_err());
+        assert!(DeltaUpdate::Add { alpha: 1.0 }
+            .apply(&stored, &[0.0])
+            .is_err());
+    }
+}
@@ -56,7 +56,8 @@ pub fn cosine_distance(a: &[f32], b: &[f32]) -> f32 {
         let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
         let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
         if norm_a > 1e-8 && norm_b > 1e-8 {
-            1.0 - (dot / (norm_a * norm_b))
+            // Rounding can push the similarity of parallel vectors past 1
+            (1.0 - (dot / (norm_a * norm_b))).max(0.0)
         } else {
             1.0
         }
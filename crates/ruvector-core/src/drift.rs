@@ -0,0 +1,254 @@
+//! Embedding drift detection
+//!
+//! Compares two databases holding the same ids embedded by different model
+//! versions, to validate a re-embedding migration before cutting over.
+//! Two signals are reported:
+//!
+//! - **Cosine displacement**: `1 - cos(old, new)` per id. Only meaningful when
+//!   both models share a vector space, so it is skipped when dimensions differ.
+//! - **Neighborhood overlap@k**: the share of each id's k nearest neighbors
+//!   that survive the migration. This works across dimensions and is usually
+//!   the better predictor of changed search behavior.
+
+use crate::distance::cosine_distance;
+use crate::error::{Result, RuvectorError};
+use crate::types::{SearchQuery, VectorId};
+use crate::vector_db::VectorDB;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Drift comparison settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftConfig {
+    /// Ids sampled from the baseline; 0 compares every id
+    pub sample_size: usize,
+    /// Neighborhood size for overlap@k
+    pub k: usize,
+    /// Number of lowest-overlap ids to list in the report
+    pub worst_count: usize,
+    /// Seed for sampling
+    pub seed: u64,
+}
+
+impl Default for DriftConfig {
+    fn default() -> Self {
+        Self {
+            sample_size: 1000,
+            k: 10,
+            worst_count: 10,
+            seed: 42,
+        }
+    }
+}
+
+/// Drift of a single id
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdDrift {
+    /// Vector id
+    pub id: VectorId,
+    /// `1 - cos(old, new)`, when dimensions match
+    pub cosine_displacement: Option<f32>,
+    /// Share of the baseline neighbors still among the candidate neighbors
+    pub neighborhood_overlap: f32,
+}
+
+/// Distribution shift between two embedding versions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftReport {
+    /// Ids compared
+    pub compared: usize,
+    /// Sampled ids missing from the candidate database
+    pub missing: Vec<VectorId>,
+    /// Mean cosine displacement, when dimensions match
+    pub mean_cosine_displacement: Option<f32>,
+    /// Largest cosine displacement, when dimensions match
+    pub max_cosine_displacement: Option<f32>,
+    /// Mean neighborhood overlap@k in `[0, 1]`
+    pub mean_neighborhood_overlap: f32,
+    /// Ids with the lowest overlap, ascending
+    pub worst: Vec<IdDrift>,
+}
+
+/// Compare `baseline` (current embeddings) with `candidate` (re-embedded)
+pub fn compare_embeddings(
+    baseline: &VectorDB,
+    candidate: &VectorDB,
+    config: &DriftConfig,
+) -> Result<DriftReport> {
+    if config.k == 0 {
+        return Err(RuvectorError::InvalidParameter(
+            "k must be greater than zero".to_string(),
+        ));
+    }
+
+    let mut ids = baseline.keys()?;
+    ids.sort();
+    if config.sample_size > 0 && config.sample_size < ids.len() {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        ids = ids
+            .choose_multiple(&mut rng, config.sample_size)
+            .cloned()
+            .collect();
+    }
+
+    let mut missing = Vec::new();
+    let mut drifts = Vec::with_capacity(ids.len());
+
+    for id in ids {
+        let (Some(old), Some(new)) = (baseline.get(&id)?, candidate.get(&id)?) else {
+            missing.push(id);
+            continue;
+        };
+
+        let cosine_displacement = (old.vector.len() == new.vector.len())
+            .then(|| cosine_distance(&old.vector, &new.vector));
+
+        let before = neighbor_ids(baseline, &id, old.vector, config.k)?;
+        let after = neighbor_ids(candidate, &id, new.vector, config.k)?;
+        let neighborhood_overlap = if before.is_empty() {
+            1.0
+        } else {
+            before.intersection(&after).count() as f32 / before.len() as f32
+        };
+
+        drifts.push(IdDrift {
+            id,
+            cosine_displacement,
+            neighborhood_overlap,
+        });
+    }
+
+    let compared = drifts.len();
+    let displacements: Vec<f32> = drifts
+        .iter()
+        .filter_map(|d| d.cosine_displacement)
+        .collect();
+    let (mean_cosine_displacement, max_cosine_displacement) = if displacements.is_empty() {
+        (None, None)
+    } else {
+        (
+            Some(displacements.iter().sum::<f32>() / displacements.len() as f32),
+            displacements.iter().copied().reduce(f32::max),
+        )
+    };
+    let mean_neighborhood_overlap = if compared == 0 {
+        1.0
+    } else {
+        drifts.iter().map(|d| d.neighborhood_overlap).sum::<f32>() / compared as f32
+    };
+
+    drifts.sort_by(|a, b| {
+        a.neighborhood_overlap
+            .total_cmp(&b.neighborhood_overlap)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    drifts.truncate(config.worst_count);
+
+    Ok(DriftReport {
+        compared,
+        missing,
+        mean_cosine_displacement,
+        max_cosine_displacement,
+        mean_neighborhood_overlap,
+        worst: drifts,
+    })
+}
+
+fn neighbor_ids(db: &VectorDB, id: &str, vector: Vec<f32>, k: usize) -> Result<HashSet<VectorId>> {
+    Ok(db
+        .search(SearchQuery {
+            vector,
+            k: k + 1,
+            filter: None,
+            ef_search: None,
+        })?
+        .into_iter()
+        .map(|r| r.id)
+        .filter(|n| n != id)
+        .take(k)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DbOptions, DistanceMetric, VectorEntry};
+    use tempfile::tempdir;
+
+    fn db_with(path: &std::path::Path, entries: &[(&str, Vec<f32>)]) -> Result<VectorDB> {
+        let mut options = DbOptions::default();
+        options.storage_path = path.to_string_lossy().to_string();
+        options.dimensions = entries[0].1.len();
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        for (id, vector) in entries {
+            db.insert(VectorEntry {
+                id: Some(id.to_string()),
+                vector: vector.clone(),
+                metadata: None,
+            })?;
+        }
+        Ok(db)
+    }
+
+    #[test]
+    fn test_identical_embeddings_do_not_drift() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let entries = [
+            ("a", vec![1.0, 0.0]),
+            ("b", vec![0.9, 0.1]),
+            ("c", vec![0.0, 1.0]),
+        ];
+        let old = db_with(&dir.path().join("old.db"), &entries)?;
+        let new = db_with(&dir.path().join("new.db"), &entries[..2])?;
+
+        let config = DriftConfig {
+            k: 1,
+            ..Default::default()
+        };
+        let report = compare_embeddings(&old, &new, &config)?;
+        assert_eq!(report.compared, 2);
+        assert_eq!(report.missing, vec!["c".to_string()]);
+        assert!(report.mean_cosine_displacement.unwrap() < 1e-5);
+        assert_eq!(report.mean_neighborhood_overlap, 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reshuffled_neighborhoods() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let old = db_with(
+            &dir.path().join("old.db"),
+            &[
+                ("a", vec![0.0, 0.0, 0.0]),
+                ("b", vec![0.1, 0.0, 0.0]),
+                ("c", vec![5.0, 0.0, 0.0]),
+            ],
+        )?;
+        // New model (different dimensionality) pairs a with c instead of b
+        let new = db_with(
+            &dir.path().join("new.db"),
+            &[
+                ("a", vec![0.0, 0.0]),
+                ("b", vec![5.0, 5.0]),
+                ("c", vec![0.1, 0.0]),
+            ],
+        )?;
+
+        let config = DriftConfig {
+            k: 1,
+            ..Default::default()
+        };
+        let report = compare_embeddings(&old, &new, &config)?;
+        assert!(report.mean_cosine_displacement.is_none());
+        assert!(report.mean_neighborhood_overlap < 1.0);
+        assert_eq!(report.worst[0].id, "a");
+        assert_eq!(report.worst[0].neighborhood_overlap, 0.0);
+        Ok(())
+    }
+}
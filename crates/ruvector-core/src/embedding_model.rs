@@ -0,0 +1,136 @@
+//! Embedding model metadata and compatibility guard
+//!
+//! Vectors from different embedding models live in unrelated spaces, so a
+//! query embedded with the wrong model returns plausible-looking but
+//! meaningless neighbors. A database can record the model its vectors come
+//! from with [`VectorDB::set_embedding_model`](crate::VectorDB::set_embedding_model);
+//! the record is persisted with the database.
+//!
+//! Writes and searches may then declare the model they used, as `"name"` or
+//! `"name@version"`, through
+//! [`VectorDB::search_with_model`](crate::VectorDB::search_with_model) and
+//! [`VectorDB::insert_batch_with_model`](crate::VectorDB::insert_batch_with_model).
+//! A mismatching declaration is rejected with
+//! [`RuvectorError::ModelMismatch`] in strict mode and logged otherwise.
+//! Operations that declare nothing are not checked.
+
+use crate::error::{Result, RuvectorError};
+use serde::{Deserialize, Serialize};
+
+/// The embedding model a database's vectors come from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddingModel {
+    /// Model name, e.g. `"all-MiniLM-L6-v2"`
+    pub name: String,
+    /// Model version or revision, if tracked
+    pub version: Option<String>,
+    /// Dimensions the model produces
+    pub dimensions: usize,
+}
+
+impl EmbeddingModel {
+    /// Model `name` producing `dimensions`-dimensional vectors
+    pub fn new(name: impl Into<String>, dimensions: usize) -> Self {
+        Self {
+            name: name.into(),
+            version: None,
+            dimensions,
+        }
+    }
+
+    /// Set the version
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// `name@version`, or `name` when unversioned
+    pub fn reference(&self) -> String {
+        match &self.version {
+            Some(version) => format!("{}@{}", self.name, version),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Whether a declared `"name"` or `"name@version"` refers to this model
+    ///
+    /// A declaration without a version matches any version of the model; a
+    /// versioned declaration only matches that version.
+    pub fn matches(&self, declared: &str) -> bool {
+        let (name, version) = match declared.split_once('@') {
+            Some((name, version)) => (name, Some(version)),
+            None => (declared, None),
+        };
+        name == self.name
+            && version.map_or(true, |version| self.version.as_deref() == Some(version))
+    }
+}
+
+/// Model recorded for a database and how declarations are enforced
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelGuard {
+    /// Recorded model
+    pub model: EmbeddingModel,
+    /// Reject mismatching declarations instead of logging them
+    pub strict: bool,
+}
+
+impl ModelGuard {
+    /// Check a declared model against the recorded one
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuvectorError::ModelMismatch`] if `declared` does not match
+    /// and the guard is strict
+    pub fn check(&self, declared: &str) -> Result<()> {
+        if self.model.matches(declared) {
+            return Ok(());
+        }
+        if self.strict {
+            return Err(RuvectorError::ModelMismatch {
+                expected: self.model.reference(),
+                actual: declared.to_string(),
+            });
+        }
+        tracing::warn!(
+            "Embedding model '{}' does not match the database's '{}'",
+            declared,
+            self.model.reference()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declarations_match_name_and_version() {
+        let model = EmbeddingModel::new("minilm", 384).with_version("v2");
+        assert_eq!(model.reference(), "minilm@v2");
+        assert!(model.matches("minilm"));
+        assert!(model.matches("minilm@v2"));
+        assert!(!model.matches("minilm@v1"));
+        assert!(!model.matches("mpnet"));
+
+        let unversioned = EmbeddingModel::new("minilm", 384);
+        assert!(!unversioned.matches("minilm@v2"));
+    }
+
+    #[test]
+    fn test_only_strict_guard_rejects() {
+        let mut guard = ModelGuard {
+            model: EmbeddingModel::new("minilm", 384),
+            strict: false,
+        };
+        assert!(guard.check("mpnet").is_ok());
+
+        guard.strict = true;
+        assert!(guard.check("minilm").is_ok());
+        assert!(matches!(
+            guard.check("mpnet"),
+            Err(RuvectorError::ModelMismatch { .. })
+        ));
+    }
+}
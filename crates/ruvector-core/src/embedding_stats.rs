@@ -0,0 +1,234 @@
+//! Distribution statistics over stored vectors
+//!
+//! [`VectorDB::collection_stats`](crate::VectorDB::collection_stats) scans
+//! every vector and summarizes what an embedding pipeline has been writing.
+//! Broken pipelines tend to show up here before they show up in recall:
+//! all-zero or non-finite vectors, norms collapsing to one value, or an
+//! intrinsic dimensionality far below what the model normally produces.
+//!
+//! Intrinsic dimensionality is estimated with TwoNN (Facco et al., 2017)
+//! on a sample: for each sampled vector, `mu = r2 / r1` is the ratio of the
+//! Euclidean distances to its second and first nearest neighbors, and the
+//! maximum-likelihood estimate is `n / sum(ln mu)`. Neighbors come from the
+//! index, so the estimate is approximate under HNSW.
+
+use serde::{Deserialize, Serialize};
+
+/// Vectors sampled for the intrinsic dimensionality estimate by default
+pub const DEFAULT_INTRINSIC_DIM_SAMPLE: usize = 1000;
+
+/// Neighbor candidates fetched per sampled vector
+pub(crate) const INTRINSIC_DIM_CANDIDATES: usize = 10;
+
+/// Settings for [`VectorDB::collection_stats_with`](crate::VectorDB::collection_stats_with)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatsConfig {
+    /// Vectors sampled to estimate intrinsic dimensionality; 0 skips it
+    pub intrinsic_dim_sample: usize,
+    /// Seed for the sample
+    pub seed: u64,
+}
+
+impl Default for StatsConfig {
+    fn default() -> Self {
+        Self {
+            intrinsic_dim_sample: DEFAULT_INTRINSIC_DIM_SAMPLE,
+            seed: 42,
+        }
+    }
+}
+
+/// Percentiles of the L2 norms of stored vectors
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NormPercentiles {
+    /// Smallest norm
+    pub min: f32,
+    /// 1st percentile
+    pub p1: f32,
+    /// 5th percentile
+    pub p5: f32,
+    /// 25th percentile
+    pub p25: f32,
+    /// Median
+    pub p50: f32,
+    /// 75th percentile
+    pub p75: f32,
+    /// 95th percentile
+    pub p95: f32,
+    /// 99th percentile
+    pub p99: f32,
+    /// Largest norm
+    pub max: f32,
+}
+
+impl NormPercentiles {
+    /// Nearest-rank percentiles of `norms`, which must be sorted
+    pub(crate) fn of_sorted(norms: &[f32]) -> Self {
+        if norms.is_empty() {
+            return Self::default();
+        }
+        let at = |p: f32| {
+            let rank = (p / 100.0 * norms.len() as f32).ceil() as usize;
+            norms[rank.clamp(1, norms.len()) - 1]
+        };
+        Self {
+            min: norms[0],
+            p1: at(1.0),
+            p5: at(5.0),
+            p25: at(25.0),
+            p50: at(50.0),
+            p75: at(75.0),
+            p95: at(95.0),
+            p99: at(99.0),
+            max: norms[norms.len() - 1],
+        }
+    }
+}
+
+/// Summary of the vectors in a database
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddingStats {
+    /// Configured dimensions
+    pub dimensions: usize,
+    /// Vectors summarized; centroid entries are not counted
+    pub count: usize,
+    /// Vectors whose components are all zero
+    pub zero_vectors: usize,
+    /// Vectors with a NaN or infinite component; left out of the norms
+    /// and the mean
+    pub non_finite: usize,
+    /// Distribution of L2 norms
+    pub norms: NormPercentiles,
+    /// Component-wise mean
+    pub mean_vector: Vec<f32>,
+    /// L2 norm of the mean; close to the typical norm when vectors all
+    /// point the same way
+    pub mean_vector_norm: f32,
+    /// TwoNN estimate; `None` when skipped or fewer than three distinct
+    /// vectors are stored
+    pub intrinsic_dimension: Option<f32>,
+    /// Sampled vectors the estimate used
+    pub intrinsic_dim_sample: usize,
+}
+
+/// Accumulates [`EmbeddingStats`] over one pass
+pub(crate) struct StatsAccumulator {
+    dimensions: usize,
+    count: usize,
+    zero_vectors: usize,
+    non_finite: usize,
+    norms: Vec<f32>,
+    sum: Vec<f64>,
+}
+
+impl StatsAccumulator {
+    pub(crate) fn new(dimensions: usize) -> Self {
+        Self {
+            dimensions,
+            count: 0,
+            zero_vectors: 0,
+            non_finite: 0,
+            norms: Vec::new(),
+            sum: vec![0.0; dimensions],
+        }
+    }
+
+    pub(crate) fn add(&mut self, vector: &[f32]) {
+        self.count += 1;
+        if vector.iter().any(|x| !x.is_finite()) {
+            self.non_finite += 1;
+            return;
+        }
+        if vector.iter().all(|&x| x == 0.0) {
+            self.zero_vectors += 1;
+        }
+        self.norms
+            .push(vector.iter().map(|x| x * x).sum::<f32>().sqrt());
+        for (sum, &x) in self.sum.iter_mut().zip(vector) {
+            *sum += x as f64;
+        }
+    }
+
+    pub(crate) fn finish(mut self, intrinsic: Option<(f32, usize)>) -> EmbeddingStats {
+        self.norms.sort_by(f32::total_cmp);
+        let finite = self.norms.len().max(1) as f64;
+        let mean_vector: Vec<f32> = self.sum.iter().map(|&s| (s / finite) as f32).collect();
+        EmbeddingStats {
+            dimensions: self.dimensions,
+            count: self.count,
+            zero_vectors: self.zero_vectors,
+            non_finite: self.non_finite,
+            norms: NormPercentiles::of_sorted(&self.norms),
+            mean_vector_norm: mean_vector.iter().map(|x| x * x).sum::<f32>().sqrt(),
+            mean_vector,
+            intrinsic_dimension: intrinsic.map(|(d, _)| d),
+            intrinsic_dim_sample: intrinsic.map_or(0, |(_, n)| n),
+        }
+    }
+}
+
+/// TwoNN estimate from `(r1, r2)` nearest-neighbor distance pairs
+///
+/// Pairs with `r1 == 0` (exact duplicates) are skipped. Returns the
+/// estimate and the number of pairs used, or `None` if no pair was usable.
+pub(crate) fn two_nn(pairs: &[(f32, f32)]) -> Option<(f32, usize)> {
+    let log_ratios: Vec<f64> = pairs
+        .iter()
+        .filter(|(r1, r2)| *r1 > 0.0 && r2.is_finite())
+        .map(|&(r1, r2)| (r2 as f64 / r1 as f64).ln())
+        .collect();
+    let total: f64 = log_ratios.iter().sum();
+    if log_ratios.is_empty() || total <= 0.0 {
+        return None;
+    }
+    Some(((log_ratios.len() as f64 / total) as f32, log_ratios.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_accumulator() {
+        let mut stats = StatsAccumulator::new(2);
+        stats.add(&[3.0, 4.0]);
+        stats.add(&[0.0, 0.0]);
+        stats.add(&[f32::NAN, 1.0]);
+        stats.add(&[1.0, 0.0]);
+        let stats = stats.finish(None);
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.zero_vectors, 1);
+        assert_eq!(stats.non_finite, 1);
+        assert_eq!(stats.norms.min, 0.0);
+        assert_eq!(stats.norms.p50, 1.0);
+        assert_eq!(stats.norms.max, 5.0);
+        assert!((stats.mean_vector[0] - 4.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_two_nn_on_a_plane() {
+        // Points on a 2-d plane embedded in 3-d, neighbors found exactly
+        let mut rng = StdRng::seed_from_u64(3);
+        let points: Vec<[f32; 3]> = (0..2000)
+            .map(|_| [rng.gen::<f32>(), rng.gen::<f32>(), 0.0])
+            .collect();
+        let pairs: Vec<(f32, f32)> = points
+            .iter()
+            .take(300)
+            .map(|p| {
+                let mut d: Vec<f32> = points
+                    .iter()
+                    .map(|q| ((p[0] - q[0]).powi(2) + (p[1] - q[1]).powi(2)).sqrt())
+                    .filter(|&d| d > 0.0)
+                    .collect();
+                d.sort_by(f32::total_cmp);
+                (d[0], d[1])
+            })
+            .collect();
+        let (dimension, used) = two_nn(&pairs).unwrap();
+        assert_eq!(used, 300);
+        assert!((dimension - 2.0).abs() < 0.4, "estimated {}", dimension);
+    }
+}
@@ -65,6 +65,46 @@ pub enum RuvectorError {
     #[error("Invalid path: {0}")]
     InvalidPath(String),
 
+    /// Declared embedding model differs from the database's
+    #[error("Embedding model mismatch: database uses {expected}, got {actual}")]
+    ModelMismatch {
+        /// Model recorded for the database
+        expected: String,
+        /// Model declared by the caller
+        actual: String,
+    },
+
+    /// The database has been shut down
+    #[error("Database is shut down")]
+    ShutDown,
+
+    /// Writes refused because the index is past a health threshold
+    #[error("Write backpressure: {signal} is {value}, threshold {threshold}; compact the index")]
+    Backpressure {
+        /// Signal past its threshold, e.g. `"tombstone_ratio"`
+        signal: String,
+        /// Current value of the signal
+        value: f32,
+        /// Configured threshold
+        threshold: f32,
+    },
+
+    /// A private export would exceed the privacy budget
+    #[error(
+        "Privacy budget exceeded: export needs epsilon {epsilon}, delta {delta}; \
+         remaining epsilon {remaining_epsilon}, delta {remaining_delta}"
+    )]
+    PrivacyBudgetExceeded {
+        /// Epsilon the export would spend
+        epsilon: f64,
+        /// Delta the export would spend
+        delta: f64,
+        /// Epsilon left in the budget
+        remaining_epsilon: f64,
+        /// Delta left in the budget
+        remaining_delta: f64,
+    },
+
     /// Other errors
     #[error("Internal error: {0}")]
     Internal(String),
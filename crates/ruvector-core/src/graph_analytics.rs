@@ -0,0 +1,215 @@
+//! Graph analytics over the index topology
+//!
+//! Connected components, degree distribution and hub detection computed on a
+//! materialized [`KnnGraph`]. Useful when debugging recall (fragmented
+//! components and unreachable nodes hurt it) and as structural features for
+//! GNN training.
+
+use crate::knn_graph::KnnGraph;
+use crate::types::VectorId;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// A node with its in-degree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HubNode {
+    /// Vector id
+    pub id: VectorId,
+    /// Number of nodes that list this one as a neighbor
+    pub in_degree: usize,
+}
+
+/// Summary of the graph structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphAnalytics {
+    /// Number of nodes
+    pub node_count: usize,
+    /// Number of edges
+    pub edge_count: usize,
+    /// Weakly connected component sizes, largest first
+    pub component_sizes: Vec<usize>,
+    /// Share of nodes in the largest component
+    pub largest_component_fraction: f64,
+    /// Histogram of in-degree to node count
+    pub in_degree_histogram: BTreeMap<usize, usize>,
+    /// Mean in-degree
+    pub mean_in_degree: f64,
+    /// Skewness of the in-degree distribution (high values indicate hubness)
+    pub in_degree_skewness: f64,
+    /// Nodes no other node points to; they are hard to reach during search
+    pub unreachable: Vec<VectorId>,
+    /// Highest in-degree nodes, descending
+    pub hubs: Vec<HubNode>,
+}
+
+impl GraphAnalytics {
+    /// Number of weakly connected components
+    pub fn component_count(&self) -> usize {
+        self.component_sizes.len()
+    }
+}
+
+impl KnnGraph {
+    /// Weakly connected components, each a list of node ids
+    ///
+    /// Components are ordered largest first.
+    pub fn connected_components(&self) -> Vec<Vec<VectorId>> {
+        let positions = self.node_positions();
+        let mut parent: Vec<usize> = (0..self.nodes.len()).collect();
+
+        for edge in &self.edges {
+            if let (Some(&a), Some(&b)) = (positions.get(&edge.source), positions.get(&edge.target))
+            {
+                let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+                if ra != rb {
+                    parent[ra.max(rb)] = ra.min(rb);
+                }
+            }
+        }
+
+        let mut groups: BTreeMap<usize, Vec<VectorId>> = BTreeMap::new();
+        for (i, id) in self.nodes.iter().enumerate() {
+            let root = find(&mut parent, i);
+            groups.entry(root).or_default().push(id.clone());
+        }
+
+        let mut components: Vec<Vec<VectorId>> = groups.into_values().collect();
+        components.sort_by(|a, b| b.len().cmp(&a.len()));
+        components
+    }
+
+    /// In-degree of every node
+    pub fn in_degrees(&self) -> HashMap<VectorId, usize> {
+        let mut degrees: HashMap<VectorId, usize> =
+            self.nodes.iter().map(|id| (id.clone(), 0)).collect();
+        for edge in &self.edges {
+            *degrees.entry(edge.target.clone()).or_insert(0) += 1;
+        }
+        degrees
+    }
+
+    /// The `n` nodes with the highest in-degree, ties broken by id
+    pub fn hubs(&self, n: usize) -> Vec<HubNode> {
+        let mut hubs: Vec<HubNode> = self
+            .in_degrees()
+            .into_iter()
+            .map(|(id, in_degree)| HubNode { id, in_degree })
+            .collect();
+        hubs.sort_by(|a, b| b.in_degree.cmp(&a.in_degree).then_with(|| a.id.cmp(&b.id)));
+        hubs.truncate(n);
+        hubs
+    }
+
+    /// Compute components, degree statistics and the top `hub_count` hubs
+    pub fn analyze(&self, hub_count: usize) -> GraphAnalytics {
+        let component_sizes: Vec<usize> =
+            self.connected_components().iter().map(Vec::len).collect();
+        let degrees = self.in_degrees();
+
+        let mut in_degree_histogram = BTreeMap::new();
+        for &d in degrees.values() {
+            *in_degree_histogram.entry(d).or_insert(0) += 1;
+        }
+
+        let n = degrees.len().max(1) as f64;
+        let mean = degrees.values().sum::<usize>() as f64 / n;
+        let (m2, m3) = degrees.values().fold((0.0, 0.0), |(m2, m3), &d| {
+            let diff = d as f64 - mean;
+            (m2 + diff * diff / n, m3 + diff * diff * diff / n)
+        });
+        let skewness = if m2 > 0.0 { m3 / m2.powf(1.5) } else { 0.0 };
+
+        let mut unreachable: Vec<VectorId> = degrees
+            .iter()
+            .filter(|(_, &d)| d == 0)
+            .map(|(id, _)| id.clone())
+            .collect();
+        unreachable.sort();
+
+        GraphAnalytics {
+            node_count: self.node_count(),
+            edge_count: self.edge_count(),
+            largest_component_fraction: component_sizes.first().map_or(0.0, |&s| s as f64 / n),
+            component_sizes,
+            in_degree_histogram,
+            mean_in_degree: mean,
+            in_degree_skewness: skewness,
+            unreachable,
+            hubs: self.hubs(hub_count),
+        }
+    }
+
+    fn node_positions(&self) -> HashMap<&VectorId, usize> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (id, i))
+            .collect()
+    }
+}
+
+fn find(parent: &mut [usize], mut x: usize) -> usize {
+    while parent[x] != x {
+        parent[x] = parent[parent[x]];
+        x = parent[x];
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knn_graph::KnnEdge;
+    use crate::types::DistanceMetric;
+
+    fn graph(nodes: &[&str], edges: &[(&str, &str)]) -> KnnGraph {
+        KnnGraph {
+            k: 1,
+            metric: DistanceMetric::Euclidean,
+            nodes: nodes.iter().map(|s| s.to_string()).collect(),
+            edges: edges
+                .iter()
+                .map(|(s, t)| KnnEdge {
+                    source: s.to_string(),
+                    target: t.to_string(),
+                    distance: 1.0,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_components() {
+        let g = graph(
+            &["a", "b", "c", "d", "e"],
+            &[("a", "b"), ("c", "b"), ("d", "e")],
+        );
+        let components = g.connected_components();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].len(), 3);
+        assert_eq!(components[1], vec!["d".to_string(), "e".to_string()]);
+    }
+
+    #[test]
+    fn test_analyze_hubs() {
+        let g = graph(
+            &["hub", "a", "b", "c"],
+            &[("a", "hub"), ("b", "hub"), ("c", "hub"), ("hub", "a")],
+        );
+        let stats = g.analyze(1);
+
+        assert_eq!(stats.component_count(), 1);
+        assert_eq!(stats.largest_component_fraction, 1.0);
+        assert_eq!(
+            stats.hubs,
+            vec![HubNode {
+                id: "hub".to_string(),
+                in_degree: 3
+            }]
+        );
+        assert_eq!(stats.unreachable, vec!["b".to_string(), "c".to_string()]);
+        assert_eq!(stats.in_degree_histogram[&0], 2);
+        assert!((stats.mean_in_degree - 1.0).abs() < 1e-9);
+        assert!(stats.in_degree_skewness > 0.0);
+    }
+}
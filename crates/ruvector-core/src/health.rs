@@ -0,0 +1,167 @@
+//! Database health checks
+//!
+//! [`VectorDB::health_check`](crate::VectorDB::health_check) is cheap enough
+//! for a liveness probe: it compares the stored and indexed counts.
+//! [`VectorDB::health_check_deep`](crate::VectorDB::health_check_deep) also
+//!
+//! - reads a sample of stored entries and verifies that each decodes, has
+//!   the configured dimensions and contains only finite values,
+//! - runs canary searches from sampled vectors, measuring latency and
+//!   whether each vector finds itself.
+//!
+//! Writes go straight to storage and the index, so there is no write-ahead
+//! log to fall behind; the lag reported is the number of stored entries
+//! missing from the index.
+//!
+//! Every report also carries the process-wide [`crate::arena`] usage, so
+//! slow memory growth shows up next to the database it accompanies.
+
+use crate::arena::{self, GlobalArenaStats};
+use crate::types::VectorId;
+use serde::{Deserialize, Serialize};
+
+/// Overall or per-check health
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// Everything checked is fine
+    Healthy,
+    /// Serving, but slower or less complete than it should be
+    Degraded,
+    /// Not fit to serve traffic
+    Unhealthy,
+}
+
+/// Deep health check settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCheckConfig {
+    /// Stored entries read and verified
+    pub sample_size: usize,
+    /// Canary searches run from sampled vectors
+    pub canary_queries: usize,
+    /// Results requested per canary search
+    pub canary_k: usize,
+    /// Median canary latency above which the database counts as degraded
+    pub latency_budget_ms: u64,
+    /// Stored entries missing from the index before the database counts as
+    /// degraded
+    pub max_index_lag: usize,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            sample_size: 64,
+            canary_queries: 8,
+            canary_k: 10,
+            latency_budget_ms: 50,
+            max_index_lag: 0,
+        }
+    }
+}
+
+/// Outcome of one check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckResult {
+    /// Check name, e.g. `"storage"` or `"canary_latency"`
+    pub name: String,
+    /// Outcome
+    pub status: HealthStatus,
+    /// Human-readable detail
+    pub detail: String,
+}
+
+/// Structured health report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// Worst status across `checks`
+    pub status: HealthStatus,
+    /// Whether the deep checks ran
+    pub deep: bool,
+    /// Stored entries
+    pub vectors: usize,
+    /// Indexed entries
+    pub indexed: usize,
+    /// Stored entries missing from the index
+    pub index_lag: usize,
+    /// Entries of uncommitted ingest batches
+    pub pending: usize,
+    /// Stored entries read and verified
+    pub sampled: usize,
+    /// Sampled entries that failed verification
+    pub corrupt: Vec<VectorId>,
+    /// Median canary latency in microseconds
+    pub canary_p50_us: Option<u64>,
+    /// Slowest canary in microseconds
+    pub canary_max_us: Option<u64>,
+    /// Share of canaries that found their own vector
+    pub canary_self_recall: Option<f32>,
+    /// Individual checks in the order they ran
+    pub checks: Vec<CheckResult>,
+    /// Process-wide arena allocator usage when the check started
+    #[serde(default)]
+    pub arena: GlobalArenaStats,
+    /// Time spent on the check
+    pub elapsed_ms: u64,
+}
+
+impl HealthReport {
+    pub(crate) fn new(deep: bool) -> Self {
+        Self {
+            status: HealthStatus::Healthy,
+            deep,
+            vectors: 0,
+            indexed: 0,
+            index_lag: 0,
+            pending: 0,
+            sampled: 0,
+            corrupt: Vec::new(),
+            canary_p50_us: None,
+            canary_max_us: None,
+            canary_self_recall: None,
+            checks: Vec::new(),
+            arena: arena::global_stats(),
+            elapsed_ms: 0,
+        }
+    }
+
+    /// Record a check, lowering the overall status if needed
+    pub fn push(&mut self, name: &str, status: HealthStatus, detail: impl Into<String>) {
+        self.status = self.status.max(status);
+        self.checks.push(CheckResult {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+        });
+    }
+
+    /// Whether the database can serve traffic (healthy or degraded)
+    pub fn is_ready(&self) -> bool {
+        self.status != HealthStatus::Unhealthy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_is_worst_check() {
+        let mut report = HealthReport::new(true);
+        report.push("storage", HealthStatus::Healthy, "ok");
+        assert_eq!(report.status, HealthStatus::Healthy);
+
+        report.push("canary_latency", HealthStatus::Degraded, "slow");
+        report.push("index_lag", HealthStatus::Healthy, "ok");
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert!(report.is_ready());
+
+        report.push("storage_sample", HealthStatus::Unhealthy, "corrupt");
+        assert!(!report.is_ready());
+        assert_eq!(report.checks.len(), 4);
+        assert_eq!(
+            serde_json::to_value(report.status).unwrap(),
+            serde_json::json!("unhealthy")
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Bloom filter over stored ids
+//!
+//! [`VectorDB::maybe_contains`](crate::VectorDB::maybe_contains) answers
+//! "was this id ever stored?" from memory, so an ingestion pipeline can skip
+//! the storage read for the common case of a chunk it has not seen. A false
+//! answer is definite; a true answer may be a false positive, at roughly
+//! [`ID_FILTER_FALSE_POSITIVE_RATE`], and should be confirmed with
+//! [`VectorDB::get`](crate::VectorDB::get).
+//!
+//! Deleted ids stay in the filter and only add false positives. When more
+//! ids have been added than the filter was sized for, it is rebuilt from
+//! storage at twice the size, which also drops deleted ids.
+//!
+//! The filter is kept per [`VectorDB`](crate::VectorDB) handle: writes made
+//! through another handle on the same file are not seen.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// False-positive rate the filter is sized for
+pub const ID_FILTER_FALSE_POSITIVE_RATE: f64 = 0.01;
+
+/// Smallest number of ids a filter is sized for
+const MIN_CAPACITY: usize = 1024;
+
+/// Bloom filter over vector ids
+#[derive(Debug, Clone)]
+pub(crate) struct IdFilter {
+    bits: Vec<u64>,
+    num_bits: u64,
+    num_hashes: u32,
+    capacity: usize,
+    added: usize,
+}
+
+impl IdFilter {
+    /// An empty filter sized for `expected` ids
+    pub(crate) fn with_capacity(expected: usize) -> Self {
+        let capacity = expected.max(MIN_CAPACITY);
+        let ln2 = std::f64::consts::LN_2;
+        let num_bits =
+            (-(capacity as f64) * ID_FILTER_FALSE_POSITIVE_RATE.ln() / (ln2 * ln2)).ceil() as u64;
+        let num_hashes = ((num_bits as f64 / capacity as f64) * ln2).round().max(1.0) as u32;
+        Self {
+            bits: vec![0; num_bits.div_ceil(64) as usize],
+            num_bits,
+            num_hashes,
+            capacity,
+            added: 0,
+        }
+    }
+
+    /// Add `id`
+    pub(crate) fn insert(&mut self, id: &str) {
+        for bit in positions(self.num_bits, self.num_hashes, id) {
+            self.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+        }
+        self.added += 1;
+    }
+
+    /// False if `id` was definitely never added
+    pub(crate) fn maybe_contains(&self, id: &str) -> bool {
+        positions(self.num_bits, self.num_hashes, id)
+            .all(|bit| self.bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Whether more ids were added than the filter was sized for
+    pub(crate) fn is_saturated(&self) -> bool {
+        self.added > self.capacity
+    }
+}
+
+/// Bit positions of `id` in a filter of `num_bits` bits, by double hashing
+fn positions(num_bits: u64, num_hashes: u32, id: &str) -> impl Iterator<Item = u64> {
+    let mut hasher = DefaultHasher::new();
+    id.hash(&mut hasher);
+    let h1 = hasher.finish();
+    0u8.hash(&mut hasher);
+    let h2 = hasher.finish() | 1;
+    (0..num_hashes as u64).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % num_bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_false_negatives() {
+        let mut filter = IdFilter::with_capacity(10_000);
+        for i in 0..10_000 {
+            filter.insert(&format!("chunk-{}", i));
+        }
+        assert!((0..10_000).all(|i| filter.maybe_contains(&format!("chunk-{}", i))));
+        assert!(!filter.is_saturated());
+
+        let false_positives = (0..10_000)
+            .filter(|i| filter.maybe_contains(&format!("other-{}", i)))
+            .count();
+        assert!(false_positives < 300, "{} false positives", false_positives);
+
+        filter.insert("one-more");
+        assert!(filter.is_saturated());
+    }
+}
@@ -1,11 +1,17 @@
 //! Index structures for efficient vector search
 
 pub mod flat;
+pub mod graph;
 #[cfg(feature = "hnsw")]
 pub mod hnsw;
+pub mod quantized;
+pub mod tiered;
 
-use crate::error::Result;
+use crate::error::{Result, RuvectorError};
+use crate::index::tiered::MemoryUsage;
 use crate::types::{DistanceMetric, SearchResult, VectorId};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
 /// Trait for vector index implementations
 pub trait VectorIndex: Send + Sync {
@@ -23,6 +29,19 @@ pub trait VectorIndex: Send + Sync {
     /// Search for k nearest neighbors
     fn search(&self, query: &[f32], k: usize) -> Result<Vec<SearchResult>>;
 
+    /// Search with an explicit candidate list size (efSearch)
+    ///
+    /// Indexes without a tunable search width ignore `ef_search`.
+    fn search_with_ef(
+        &self,
+        query: &[f32],
+        k: usize,
+        ef_search: usize,
+    ) -> Result<Vec<SearchResult>> {
+        let _ = ef_search;
+        self.search(query, k)
+    }
+
     /// Remove a vector from the index
     fn remove(&mut self, id: &VectorId) -> Result<bool>;
 
@@ -33,4 +52,137 @@ pub trait VectorIndex: Send + Sync {
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Resident memory, for indexes that keep within a budget
+    fn memory_usage(&self) -> Option<MemoryUsage> {
+        None
+    }
+
+    /// Whether the index is graph-based and answers [`VectorIndex::neighbors`]
+    fn has_graph(&self) -> bool {
+        false
+    }
+
+    /// Shape of the proximity graph, for graph-based indexes
+    fn graph_stats(&self) -> Option<GraphStats> {
+        None
+    }
+
+    /// Out-edges of `id` on graph level `level`
+    ///
+    /// Levels above the node's own top level have no edges.
+    fn neighbors(&self, id: &VectorId, level: usize) -> Result<Vec<GraphNeighbor>> {
+        let _ = (id, level);
+        Err(RuvectorError::InvalidParameter(
+            "Index does not expose a graph".to_string(),
+        ))
+    }
+}
+
+/// Summary of a proximity graph, for debugging recall problems
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphStats {
+    /// Number of levels; level 0 holds every node
+    pub levels: usize,
+    /// Live nodes present on each level
+    pub nodes_per_level: Vec<usize>,
+    /// Mean out-degree of live nodes on each level
+    pub avg_out_degree: Vec<f32>,
+    /// Node searches start from; `None` if the graph is empty or it was deleted
+    pub entry_point: Option<VectorId>,
+    /// Live nodes reachable from the entry point on level 0
+    pub reachable_from_entry: usize,
+    /// Live nodes a search from the entry point can never visit
+    pub unreachable: usize,
+    /// Live nodes without in- or out-edges on level 0
+    pub isolated_nodes: usize,
+    /// Deleted nodes still linked into the graph as waypoints
+    pub removed_nodes: usize,
+}
+
+/// One out-edge of a graph node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphNeighbor {
+    /// Neighbor id; `None` for a deleted node still linked in the graph
+    pub id: Option<VectorId>,
+    /// Distance between the two nodes
+    pub distance: f32,
+}
+
+/// One node of a graph as seen by [`graph_stats`]
+pub(crate) struct GraphNode {
+    /// Vector id, or `None` if the node was deleted
+    pub(crate) id: Option<VectorId>,
+    /// Positions of out-neighbors, one list per level the node is on
+    pub(crate) neighbors: Vec<Vec<usize>>,
+}
+
+/// Compute [`GraphStats`] over `nodes`, searching from position `entry`
+pub(crate) fn graph_stats(nodes: &[GraphNode], entry: Option<usize>) -> GraphStats {
+    let levels = nodes.iter().map(|n| n.neighbors.len()).max().unwrap_or(0);
+    let live = || nodes.iter().filter(|n| n.id.is_some());
+
+    let mut nodes_per_level = vec![0; levels];
+    let mut edges_per_level = vec![0usize; levels];
+    for node in live() {
+        for (level, edges) in node.neighbors.iter().enumerate() {
+            nodes_per_level[level] += 1;
+            edges_per_level[level] += edges.len();
+        }
+    }
+    let avg_out_degree = nodes_per_level
+        .iter()
+        .zip(&edges_per_level)
+        .map(|(&n, &e)| if n == 0 { 0.0 } else { e as f32 / n as f32 })
+        .collect();
+
+    /// Level-0 out-neighbors of `node`
+    fn base(node: &GraphNode) -> &[usize] {
+        node.neighbors.first().map_or(&[], |e| e)
+    }
+    let mut has_in_edge = vec![false; nodes.len()];
+    for node in nodes {
+        for &target in base(node) {
+            if let Some(flag) = has_in_edge.get_mut(target) {
+                *flag = true;
+            }
+        }
+    }
+    let isolated_nodes = nodes
+        .iter()
+        .zip(&has_in_edge)
+        .filter(|(node, &has_in)| node.id.is_some() && !has_in && base(node).is_empty())
+        .count();
+
+    // Deleted nodes are still traversed, so they count as waypoints here
+    let mut visited = vec![false; nodes.len()];
+    let mut queue: VecDeque<usize> = entry.filter(|&e| e < nodes.len()).into_iter().collect();
+    for &start in &queue {
+        visited[start] = true;
+    }
+    while let Some(current) = queue.pop_front() {
+        for &next in base(&nodes[current]) {
+            if next < nodes.len() && !visited[next] {
+                visited[next] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    let reachable_from_entry = nodes
+        .iter()
+        .zip(&visited)
+        .filter(|(node, &seen)| seen && node.id.is_some())
+        .count();
+    let live_count = live().count();
+
+    GraphStats {
+        levels,
+        nodes_per_level,
+        avg_out_degree,
+        entry_point: entry.and_then(|e| nodes.get(e)).and_then(|n| n.id.clone()),
+        reachable_from_entry,
+        unreachable: live_count - reachable_from_entry,
+        isolated_nodes,
+        removed_nodes: nodes.len() - live_count,
+    }
 }
@@ -0,0 +1,461 @@
+//! Single-layer proximity graph built from pre-computed neighbor lists
+//!
+//! Users who already have an exact k-NN graph (for example from a GPU job)
+//! can skip the HNSW construction heuristic entirely: the supplied lists are
+//! used as the graph's edges, reverse edges are added for navigability, and
+//! queries run a Vamana-style greedy beam search from the medoid.
+//!
+//! Vectors added afterwards are linked by searching the graph for their
+//! nearest nodes, so the index stays usable for incremental inserts.
+
+use crate::distance::distance;
+use crate::error::{Result, RuvectorError};
+use crate::index::{graph_stats, GraphNeighbor, GraphNode, GraphStats, VectorIndex};
+use crate::types::{DistanceMetric, SearchResult, VectorId};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Graph construction and search settings
+#[derive(Debug, Clone)]
+pub struct NeighborGraphConfig {
+    /// Out-edges kept per node from the supplied lists
+    pub max_degree: usize,
+    /// Add reverse edges (up to twice `max_degree` per node)
+    pub add_reverse_edges: bool,
+    /// Candidate list size during search
+    pub beam_width: usize,
+}
+
+impl Default for NeighborGraphConfig {
+    fn default() -> Self {
+        Self {
+            max_degree: 32,
+            add_reverse_edges: true,
+            beam_width: 64,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct Scored {
+    distance: f32,
+    node: usize,
+}
+
+impl Eq for Scored {}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then_with(|| self.node.cmp(&other.node))
+    }
+}
+
+/// Graph index over supplied neighbor lists
+pub struct NeighborGraphIndex {
+    config: NeighborGraphConfig,
+    metric: DistanceMetric,
+    dimensions: usize,
+    ids: Vec<VectorId>,
+    vectors: Vec<Vec<f32>>,
+    neighbors: Vec<Vec<usize>>,
+    positions: HashMap<VectorId, usize>,
+    deleted: HashSet<usize>,
+    entry: usize,
+}
+
+impl NeighborGraphIndex {
+    /// Build the graph from `vectors` and, for each of them, the positions
+    /// of its nearest neighbors in `vectors`
+    pub fn from_neighbor_lists(
+        dimensions: usize,
+        metric: DistanceMetric,
+        vectors: Vec<(VectorId, Vec<f32>)>,
+        neighbor_lists: Vec<Vec<usize>>,
+        config: NeighborGraphConfig,
+    ) -> Result<Self> {
+        if vectors.len() != neighbor_lists.len() {
+            return Err(RuvectorError::InvalidInput(format!(
+                "{} vectors but {} neighbor lists",
+                vectors.len(),
+                neighbor_lists.len()
+            )));
+        }
+        if config.max_degree == 0 {
+            return Err(RuvectorError::InvalidParameter(
+                "max_degree must be greater than zero".to_string(),
+            ));
+        }
+
+        let n = vectors.len();
+        let mut ids = Vec::with_capacity(n);
+        let mut data = Vec::with_capacity(n);
+        let mut positions = HashMap::with_capacity(n);
+        for (position, (id, vector)) in vectors.into_iter().enumerate() {
+            if vector.len() != dimensions {
+                return Err(RuvectorError::DimensionMismatch {
+                    expected: dimensions,
+                    actual: vector.len(),
+                });
+            }
+            positions.insert(id.clone(), position);
+            ids.push(id);
+            data.push(vector);
+        }
+
+        let mut index = Self {
+            config,
+            metric,
+            dimensions,
+            ids,
+            vectors: data,
+            neighbors: vec![Vec::new(); n],
+            positions,
+            deleted: HashSet::new(),
+            entry: 0,
+        };
+
+        for (node, list) in neighbor_lists.into_iter().enumerate() {
+            let mut scored = Vec::with_capacity(list.len());
+            for neighbor in list {
+                if neighbor >= n {
+                    return Err(RuvectorError::InvalidInput(format!(
+                        "neighbor {} of node {} is out of range (n = {})",
+                        neighbor, node, n
+                    )));
+                }
+                if neighbor != node {
+                    scored.push(Scored {
+                        distance: index.distance_between(node, neighbor)?,
+                        node: neighbor,
+                    });
+                }
+            }
+            scored.sort();
+            scored.dedup_by_key(|s| s.node);
+            scored.truncate(index.config.max_degree);
+            index.neighbors[node] = scored.into_iter().map(|s| s.node).collect();
+        }
+
+        if index.config.add_reverse_edges {
+            let forward = index.neighbors.clone();
+            for (node, list) in forward.iter().enumerate() {
+                for &neighbor in list {
+                    index.link(neighbor, node, 2 * index.config.max_degree)?;
+                }
+            }
+        }
+
+        index.entry = index.medoid()?;
+        Ok(index)
+    }
+
+    /// Search with an explicit beam width
+    pub fn search_with_beam(
+        &self,
+        query: &[f32],
+        k: usize,
+        beam_width: usize,
+    ) -> Result<Vec<SearchResult>> {
+        if query.len() != self.dimensions {
+            return Err(RuvectorError::DimensionMismatch {
+                expected: self.dimensions,
+                actual: query.len(),
+            });
+        }
+
+        let found = self.beam_search(query, beam_width.max(k))?;
+        Ok(found
+            .into_iter()
+            .filter(|s| !self.deleted.contains(&s.node))
+            .take(k)
+            .map(|s| SearchResult {
+                id: self.ids[s.node].clone(),
+                score: s.distance,
+                vector: None,
+                metadata: None,
+            })
+            .collect())
+    }
+
+    /// Out-degree of each live node
+    pub fn degrees(&self) -> Vec<usize> {
+        (0..self.ids.len())
+            .filter(|node| !self.deleted.contains(node))
+            .map(|node| self.neighbors[node].len())
+            .collect()
+    }
+
+    fn distance_between(&self, a: usize, b: usize) -> Result<f32> {
+        distance(&self.vectors[a], &self.vectors[b], self.metric)
+    }
+
+    /// Node closest to the centroid, used as the search entry point
+    fn medoid(&self) -> Result<usize> {
+        if self.vectors.is_empty() {
+            return Ok(0);
+        }
+        let mut centroid = vec![0.0f32; self.dimensions];
+        for vector in &self.vectors {
+            for (c, x) in centroid.iter_mut().zip(vector) {
+                *c += x / self.vectors.len() as f32;
+            }
+        }
+        let mut best = Scored {
+            distance: f32::INFINITY,
+            node: 0,
+        };
+        for (node, vector) in self.vectors.iter().enumerate() {
+            let d = distance(&centroid, vector, self.metric)?;
+            if d < best.distance {
+                best = Scored { distance: d, node };
+            }
+        }
+        Ok(best.node)
+    }
+
+    /// Add edge `from -> to`, keeping the `cap` closest neighbors of `from`
+    fn link(&mut self, from: usize, to: usize, cap: usize) -> Result<()> {
+        if from == to || self.neighbors[from].contains(&to) {
+            return Ok(());
+        }
+        self.neighbors[from].push(to);
+        if self.neighbors[from].len() > cap {
+            let mut scored = self.neighbors[from]
+                .iter()
+                .map(|&n| {
+                    Ok(Scored {
+                        distance: self.distance_between(from, n)?,
+                        node: n,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            scored.sort();
+            scored.truncate(cap);
+            self.neighbors[from] = scored.into_iter().map(|s| s.node).collect();
+        }
+        Ok(())
+    }
+
+    /// Greedy best-first search returning up to `beam` nodes, nearest first
+    fn beam_search(&self, query: &[f32], beam: usize) -> Result<Vec<Scored>> {
+        if self.vectors.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let start = Scored {
+            distance: distance(query, &self.vectors[self.entry], self.metric)?,
+            node: self.entry,
+        };
+        let mut visited = HashSet::from([self.entry]);
+        let mut candidates = BinaryHeap::from([Reverse(start)]);
+        let mut best = BinaryHeap::from([start]);
+
+        while let Some(Reverse(current)) = candidates.pop() {
+            if best.len() >= beam && best.peek().is_some_and(|w| current.distance > w.distance) {
+                break;
+            }
+            for &neighbor in &self.neighbors[current.node] {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let d = distance(query, &self.vectors[neighbor], self.metric)?;
+                if best.len() < beam || best.peek().is_some_and(|w| d < w.distance) {
+                    let scored = Scored {
+                        distance: d,
+                        node: neighbor,
+                    };
+                    candidates.push(Reverse(scored));
+                    best.push(scored);
+                    if best.len() > beam {
+                        best.pop();
+                    }
+                }
+            }
+        }
+
+        Ok(best.into_sorted_vec())
+    }
+}
+
+impl VectorIndex for NeighborGraphIndex {
+    fn add(&mut self, id: VectorId, vector: Vec<f32>) -> Result<()> {
+        if vector.len() != self.dimensions {
+            return Err(RuvectorError::DimensionMismatch {
+                expected: self.dimensions,
+                actual: vector.len(),
+            });
+        }
+        if let Some(&old) = self.positions.get(&id) {
+            self.deleted.insert(old);
+        }
+
+        let beam = self.config.beam_width.max(self.config.max_degree);
+        let nearest: Vec<usize> = self
+            .beam_search(&vector, beam)?
+            .into_iter()
+            .filter(|s| !self.deleted.contains(&s.node))
+            .take(self.config.max_degree)
+            .map(|s| s.node)
+            .collect();
+
+        let node = self.ids.len();
+        self.positions.insert(id.clone(), node);
+        self.ids.push(id);
+        self.vectors.push(vector);
+        self.neighbors.push(nearest.clone());
+        for neighbor in nearest {
+            self.link(neighbor, node, 2 * self.config.max_degree)?;
+        }
+        Ok(())
+    }
+
+    fn search(&self, query: &[f32], k: usize) -> Result<Vec<SearchResult>> {
+        self.search_with_beam(query, k, self.config.beam_width)
+    }
+
+    fn search_with_ef(
+        &self,
+        query: &[f32],
+        k: usize,
+        ef_search: usize,
+    ) -> Result<Vec<SearchResult>> {
+        self.search_with_beam(query, k, ef_search)
+    }
+
+    fn remove(&mut self, id: &VectorId) -> Result<bool> {
+        // Removed nodes stay in the graph as waypoints but are never returned
+        Ok(match self.positions.remove(id) {
+            Some(node) => self.deleted.insert(node),
+            None => false,
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    fn has_graph(&self) -> bool {
+        true
+    }
+
+    fn graph_stats(&self) -> Option<GraphStats> {
+        let nodes: Vec<GraphNode> = self
+            .neighbors
+            .iter()
+            .enumerate()
+            .map(|(node, edges)| GraphNode {
+                id: (!self.deleted.contains(&node)).then(|| self.ids[node].clone()),
+                neighbors: vec![edges.clone()],
+            })
+            .collect();
+        Some(graph_stats(&nodes, Some(self.entry)))
+    }
+
+    fn neighbors(&self, id: &VectorId, level: usize) -> Result<Vec<GraphNeighbor>> {
+        let node = *self
+            .positions
+            .get(id)
+            .ok_or_else(|| RuvectorError::VectorNotFound(id.clone()))?;
+        if level > 0 {
+            return Ok(Vec::new());
+        }
+        self.neighbors[node]
+            .iter()
+            .map(|&neighbor| {
+                Ok(GraphNeighbor {
+                    id: (!self.deleted.contains(&neighbor)).then(|| self.ids[neighbor].clone()),
+                    distance: distance(&self.vectors[node], &self.vectors[neighbor], self.metric)?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Vec<(VectorId, Vec<f32>)> {
+        (0..100)
+            .map(|i| (format!("p{}", i), vec![(i % 10) as f32, (i / 10) as f32]))
+            .collect()
+    }
+
+    fn exact_knn(vectors: &[(VectorId, Vec<f32>)], k: usize) -> Vec<Vec<usize>> {
+        vectors
+            .iter()
+            .enumerate()
+            .map(|(i, (_, v))| {
+                let mut others: Vec<(f32, usize)> = vectors
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(j, (_, w))| (distance(v, w, DistanceMetric::Euclidean).unwrap(), j))
+                    .collect();
+                others.sort_by(|a, b| a.0.total_cmp(&b.0));
+                others.into_iter().take(k).map(|(_, j)| j).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_search_prebuilt_graph() -> Result<()> {
+        let vectors = grid();
+        let lists = exact_knn(&vectors, 6);
+        let mut index = NeighborGraphIndex::from_neighbor_lists(
+            2,
+            DistanceMetric::Euclidean,
+            vectors,
+            lists,
+            NeighborGraphConfig::default(),
+        )?;
+        assert_eq!(index.len(), 100);
+        assert!(index.degrees().iter().all(|&d| (6..=64).contains(&d)));
+
+        let results = index.search(&[7.0, 3.0], 1)?;
+        assert_eq!(results[0].id, "p37");
+
+        index.add("new".to_string(), vec![7.1, 3.0])?;
+        let results = index.search(&[7.2, 3.0], 2)?;
+        assert_eq!(results[0].id, "new");
+        assert_eq!(results[1].id, "p37");
+
+        assert!(index.remove(&"new".to_string())?);
+        assert_eq!(index.search(&[7.2, 3.0], 1)?[0].id, "p37");
+
+        let stats = index.graph_stats().unwrap();
+        assert_eq!(stats.levels, 1);
+        assert_eq!(stats.nodes_per_level, vec![100]);
+        assert_eq!(stats.removed_nodes, 1);
+        assert_eq!(stats.reachable_from_entry, 100);
+        assert_eq!(stats.isolated_nodes, 0);
+        let neighbors = index.neighbors(&"p37".to_string(), 0)?;
+        assert!(neighbors.iter().any(|n| n.id.is_none()));
+        assert!(index.neighbors(&"p37".to_string(), 1)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_bad_lists() {
+        let vectors = grid();
+        let build = |lists| {
+            NeighborGraphIndex::from_neighbor_lists(
+                2,
+                DistanceMetric::Euclidean,
+                grid(),
+                lists,
+                NeighborGraphConfig::default(),
+            )
+        };
+        assert!(build(vec![vec![0]; 99]).is_err());
+        assert!(build(vec![vec![100]; vectors.len()]).is_err());
+    }
+}
@@ -2,12 +2,13 @@
 
 use crate::distance::distance;
 use crate::error::{Result, RuvectorError};
-use crate::index::VectorIndex;
+use crate::index::{graph_stats, GraphNeighbor, GraphNode, GraphStats, VectorIndex};
 use crate::types::{DistanceMetric, HnswConfig, SearchResult, VectorId};
 use bincode::{Decode, Encode};
 use dashmap::DashMap;
 use hnsw_rs::prelude::*;
 use parking_lot::RwLock;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Distance function wrapper for hnsw_rs
@@ -297,9 +298,6 @@ impl VectorIndex for HnswIndex {
 
         let mut inner = self.inner.write();
 
-        // Prepare batch data for parallel insertion
-        use rayon::prelude::*;
-
         // First, assign indices and collect vector data
         let data_with_ids: Vec<_> = entries
             .iter()
@@ -313,12 +311,13 @@ impl VectorIndex for HnswIndex {
         // Update next_idx
         inner.next_idx += entries.len();
 
-        // Insert into HNSW sequentially
-        // Note: Using sequential insertion to avoid Send requirements with RwLock guard
-        // For large batches, consider restructuring to use hnsw_rs parallel_insert
-        for (_id, idx, vector) in &data_with_ids {
-            inner.hnsw.insert_data(vector, *idx);
-        }
+        // Link the batch into the graph in parallel; the write guard stays on
+        // this thread and rayon workers only borrow the (Sync) graph
+        let refs: Vec<(&Vec<f32>, usize)> = data_with_ids
+            .iter()
+            .map(|(_id, idx, vector)| (vector, *idx))
+            .collect();
+        inner.hnsw.parallel_insert(&refs);
 
         // Store mappings
         for (id, idx, vector) in data_with_ids {
@@ -335,6 +334,15 @@ impl VectorIndex for HnswIndex {
         self.search_with_ef(query, k, self.config.ef_search)
     }
 
+    fn search_with_ef(
+        &self,
+        query: &[f32],
+        k: usize,
+        ef_search: usize,
+    ) -> Result<Vec<SearchResult>> {
+        HnswIndex::search_with_ef(self, query, k, ef_search)
+    }
+
     fn remove(&mut self, id: &VectorId) -> Result<bool> {
         let mut inner = self.inner.write();
 
@@ -355,6 +363,97 @@ impl VectorIndex for HnswIndex {
     fn len(&self) -> usize {
         self.inner.read().vectors.len()
     }
+
+    fn has_graph(&self) -> bool {
+        true
+    }
+
+    fn graph_stats(&self) -> Option<GraphStats> {
+        let inner = self.inner.read();
+        let points = inner.points();
+        let positions: HashMap<usize, usize> = points
+            .iter()
+            .enumerate()
+            .map(|(position, point)| (point.get_origin_id(), position))
+            .collect();
+        let nodes: Vec<GraphNode> = points
+            .iter()
+            .map(|point| GraphNode {
+                id: inner.live_id(point.get_origin_id()),
+                neighbors: point_levels(point)
+                    .into_iter()
+                    .map(|level| {
+                        level
+                            .iter()
+                            .filter_map(|n| positions.get(&n.d_id).copied())
+                            .collect()
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        // Points are listed by level, and the first point to reach the top
+        // level is the entry point
+        let entry = (!points.is_empty()).then(|| {
+            let top = points[points.len() - 1].get_point_id().0;
+            points
+                .iter()
+                .position(|p| p.get_point_id().0 == top)
+                .unwrap_or(0)
+        });
+        Some(graph_stats(&nodes, entry))
+    }
+
+    fn neighbors(&self, id: &VectorId, level: usize) -> Result<Vec<GraphNeighbor>> {
+        let inner = self.inner.read();
+        let idx = *inner
+            .id_to_idx
+            .get(id)
+            .ok_or_else(|| RuvectorError::VectorNotFound(id.clone()))?;
+        let point = inner
+            .points()
+            .into_iter()
+            .find(|p| p.get_origin_id() == idx)
+            .ok_or_else(|| RuvectorError::VectorNotFound(id.clone()))?;
+        Ok(point_levels(&point)
+            .into_iter()
+            .nth(level)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|n| GraphNeighbor {
+                id: inner.live_id(n.d_id),
+                distance: n.distance,
+            })
+            .collect())
+    }
+}
+
+impl HnswInner {
+    /// Every point in the graph, level 0 points first
+    fn points(&self) -> Vec<Arc<Point<'static, f32>>> {
+        if self.hnsw.get_nb_point() == 0 {
+            return Vec::new();
+        }
+        let indexation = self.hnsw.get_point_indexation();
+        let top = self.hnsw.get_max_level_observed() as usize;
+        (0..=top)
+            .flat_map(|layer| indexation.get_layer_iterator(layer))
+            .collect()
+    }
+
+    /// Id of the point with data index `idx`, unless it was removed or overwritten
+    fn live_id(&self, idx: usize) -> Option<VectorId> {
+        let id = self.idx_to_id.get(&idx)?.clone();
+        let current = self.id_to_idx.get(&id).map(|i| *i);
+        (current == Some(idx)).then_some(id)
+    }
+}
+
+/// Out-edges of `point` on each level it is on
+fn point_levels(point: &Point<'static, f32>) -> Vec<Vec<Neighbour>> {
+    let mut levels = point.get_neighborhood_id();
+    levels.truncate(point.get_point_id().0 as usize + 1);
+    levels
 }
 
 #[cfg(test)]
@@ -480,4 +579,32 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_graph_introspection() -> Result<()> {
+        let mut index = HnswIndex::new(16, DistanceMetric::Euclidean, HnswConfig::default())?;
+        assert_eq!(index.graph_stats().unwrap().levels, 0);
+
+        let entries: Vec<_> = generate_random_vectors(200, 16)
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| (format!("vec_{}", i), v))
+            .collect();
+        index.add_batch(entries)?;
+        index.remove(&"vec_0".to_string())?;
+
+        let stats = index.graph_stats().unwrap();
+        assert!(stats.levels >= 1);
+        assert_eq!(stats.nodes_per_level[0], 199);
+        assert_eq!(stats.removed_nodes, 1);
+        assert!(stats.avg_out_degree[0] > 0.0);
+        assert_eq!(stats.reachable_from_entry + stats.unreachable, 199);
+
+        let neighbors = index.neighbors(&"vec_1".to_string(), 0)?;
+        assert!(!neighbors.is_empty());
+        assert!(index.neighbors(&"vec_1".to_string(), 64)?.is_empty());
+        assert!(index.neighbors(&"vec_0".to_string(), 0).is_err());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,233 @@
+//! Brute-force index over quantized codes with asymmetric distances
+//!
+//! Only the codes are kept in memory. Queries stay in full precision and are
+//! scored directly against the codes: scalar codes are dequantized one
+//! component at a time, product codes through a per-query
+//! [`DistanceTable`](crate::quantization::DistanceTable). No candidate is
+//! ever decompressed into a full vector.
+//!
+//! Scores are approximate. [`SearchPrecision`] lets each query choose between
+//! the raw code distances and reranking an oversampled candidate set with
+//! the stored full-precision vectors.
+
+use crate::error::{Result, RuvectorError};
+use crate::index::VectorIndex;
+use crate::quantization::{ProductQuantized, QuantizedVector, ScalarQuantized};
+use crate::types::{DistanceMetric, SearchResult, VectorId};
+use dashmap::DashMap;
+
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+use rayon::prelude::*;
+
+/// Accuracy/speed trade-off for a single search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchPrecision {
+    /// Return index distances as-is (asymmetric code distances on a
+    /// quantized index)
+    Approximate,
+    /// Fetch `k * oversample` candidates, then rescore them with the stored
+    /// full-precision vectors
+    Rerank {
+        /// Candidate multiplier; 1 only re-sorts the top k
+        oversample: usize,
+    },
+}
+
+impl Default for SearchPrecision {
+    fn default() -> Self {
+        SearchPrecision::Rerank { oversample: 4 }
+    }
+}
+
+enum Codec {
+    Scalar,
+    Product(ProductQuantized),
+}
+
+enum Codes {
+    Scalar(ScalarQuantized),
+    Product { codes: Vec<u8>, norm: f32 },
+}
+
+/// Flat index storing quantized codes instead of vectors
+pub struct QuantizedIndex {
+    codes: DashMap<VectorId, Codes>,
+    codec: Codec,
+    metric: DistanceMetric,
+    dimensions: usize,
+}
+
+impl QuantizedIndex {
+    /// Index with per-vector int8 scalar quantization
+    pub fn scalar(dimensions: usize, metric: DistanceMetric) -> Self {
+        Self {
+            codes: DashMap::new(),
+            codec: Codec::Scalar,
+            metric,
+            dimensions,
+        }
+    }
+
+    /// Index with product quantization using trained codebooks
+    pub fn product(
+        dimensions: usize,
+        metric: DistanceMetric,
+        pq: ProductQuantized,
+    ) -> Result<Self> {
+        if pq.codebooks.is_empty() || dimensions % pq.codebooks.len() != 0 {
+            return Err(RuvectorError::InvalidParameter(format!(
+                "{} subspaces do not evenly divide {} dimensions",
+                pq.codebooks.len(),
+                dimensions
+            )));
+        }
+        Ok(Self {
+            codes: DashMap::new(),
+            codec: Codec::Product(pq),
+            metric,
+            dimensions,
+        })
+    }
+
+    /// Bytes held by codes, excluding ids and map overhead
+    pub fn code_bytes(&self) -> usize {
+        self.codes
+            .iter()
+            .map(|entry| match entry.value() {
+                Codes::Scalar(q) => q.data.len() + 8,
+                Codes::Product { codes, .. } => codes.len() + 4,
+            })
+            .sum()
+    }
+
+    fn encode(&self, vector: &[f32]) -> Codes {
+        match &self.codec {
+            Codec::Scalar => Codes::Scalar(ScalarQuantized::quantize(vector)),
+            Codec::Product(pq) => {
+                let codes = pq.encode(vector);
+                let norm = pq.code_norm(&codes);
+                Codes::Product { codes, norm }
+            }
+        }
+    }
+}
+
+impl VectorIndex for QuantizedIndex {
+    fn add(&mut self, id: VectorId, vector: Vec<f32>) -> Result<()> {
+        if vector.len() != self.dimensions {
+            return Err(RuvectorError::DimensionMismatch {
+                expected: self.dimensions,
+                actual: vector.len(),
+            });
+        }
+        let codes = self.encode(&vector);
+        self.codes.insert(id, codes);
+        Ok(())
+    }
+
+    fn search(&self, query: &[f32], k: usize) -> Result<Vec<SearchResult>> {
+        if query.len() != self.dimensions {
+            return Err(RuvectorError::DimensionMismatch {
+                expected: self.dimensions,
+                actual: query.len(),
+            });
+        }
+
+        let table = match &self.codec {
+            Codec::Product(pq) => Some(pq.distance_table(query, self.metric)),
+            Codec::Scalar => None,
+        };
+        let score = |codes: &Codes| match (codes, &table) {
+            (Codes::Scalar(q), _) => q.asymmetric_distance(query, self.metric),
+            (Codes::Product { codes, norm }, Some(table)) => table.distance(codes, *norm),
+            (Codes::Product { .. }, None) => f32::INFINITY,
+        };
+
+        #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+        let mut results: Vec<(VectorId, f32)> = self
+            .codes
+            .iter()
+            .par_bridge()
+            .map(|entry| (entry.key().clone(), score(entry.value())))
+            .collect();
+
+        #[cfg(any(not(feature = "parallel"), target_arch = "wasm32"))]
+        let mut results: Vec<(VectorId, f32)> = self
+            .codes
+            .iter()
+            .map(|entry| (entry.key().clone(), score(entry.value())))
+            .collect();
+
+        results.sort_by(|a, b| a.1.total_cmp(&b.1));
+        results.truncate(k);
+
+        Ok(results
+            .into_iter()
+            .map(|(id, score)| SearchResult {
+                id,
+                score,
+                vector: None,
+                metadata: None,
+            })
+            .collect())
+    }
+
+    fn remove(&mut self, id: &VectorId) -> Result<bool> {
+        Ok(self.codes.remove(id).is_some())
+    }
+
+    fn len(&self) -> usize {
+        self.codes.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dataset() -> Vec<Vec<f32>> {
+        (0..200)
+            .map(|i| {
+                (0..8)
+                    .map(|j| ((i * 31 + j * 17) as f32 * 0.618).fract())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_scalar_index_finds_exact_match() -> Result<()> {
+        let mut index = QuantizedIndex::scalar(8, DistanceMetric::Euclidean);
+        for (i, v) in dataset().into_iter().enumerate() {
+            index.add(format!("v{}", i), v)?;
+        }
+        let results = index.search(&dataset()[42], 3)?;
+        assert_eq!(results[0].id, "v42");
+        assert!(results[0].score < 0.05);
+        Ok(())
+    }
+
+    #[test]
+    fn test_product_index() -> Result<()> {
+        let vectors = dataset();
+        let pq = ProductQuantized::train(&vectors, 4, 32, 10)?;
+        let mut index = QuantizedIndex::product(8, DistanceMetric::Euclidean, pq)?;
+        for (i, v) in vectors.iter().enumerate() {
+            index.add(format!("v{}", i), v.clone())?;
+        }
+        assert_eq!(index.len(), 200);
+        assert_eq!(index.code_bytes(), 200 * (4 + 4));
+
+        let results = index.search(&vectors[7], 10)?;
+        assert_eq!(results.len(), 10);
+        assert!(results.iter().any(|r| r.id == "v7"));
+        assert!(index.remove(&"v7".to_string())?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_product_rejects_uneven_subspaces() {
+        let pq = ProductQuantized::train(&dataset(), 3, 8, 2).unwrap();
+        assert!(QuantizedIndex::product(8, DistanceMetric::Euclidean, pq).is_err());
+    }
+}
@@ -0,0 +1,505 @@
+//! Flat index that keeps within a memory budget by tiering vectors
+//!
+//! Each vector lives in one of three tiers, chosen by how often it shows up
+//! in search results:
+//!
+//! - [`Tier::Hot`]: full precision, `4 * d` bytes,
+//! - [`Tier::Warm`]: int8 scalar codes, `d + 8` bytes,
+//! - [`Tier::Cold`]: one sign bit per component plus a scale, `d / 8 + 4`
+//!   bytes; the full vector is only on disk.
+//!
+//! Searches score every vector in its tier, take `k * oversample`
+//! candidates and rescore the cold ones with their stored vectors, read
+//! from storage one by one or, with a [`ColdFile`], from a spill file in one
+//! batched [`VectorFile::read_many`]. Every
+//! `rebalance_every` searches the access counts decay and vectors are
+//! reassigned: the most frequently returned go hot, as many as the budget
+//! allows, then warm, and the rest cold. If even an all-cold index exceeds
+//! the budget, [`MemoryUsage::used_bytes`] reports the overrun.
+//!
+//! The budget counts vector data only, not ids or map overhead.
+
+use crate::distance::distance;
+use crate::error::{Result, RuvectorError};
+use crate::index::VectorIndex;
+use crate::quantization::{BinaryQuantized, QuantizedVector, ScalarQuantized};
+use crate::types::{DistanceMetric, SearchResult, VectorId};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[cfg(feature = "storage")]
+use crate::storage::VectorStorage;
+#[cfg(feature = "storage")]
+use crate::vector_file::{ReadBackend, VectorFile};
+#[cfg(feature = "storage")]
+use parking_lot::RwLock;
+#[cfg(feature = "storage")]
+use std::path::PathBuf;
+
+#[cfg(not(feature = "storage"))]
+use crate::storage_memory::MemoryStorage as VectorStorage;
+
+/// Memory budget for vector data held by the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryBudget {
+    /// Upper bound on resident vector bytes
+    pub max_bytes: usize,
+    /// Searches between tier reassignments
+    pub rebalance_every: usize,
+    /// Candidate multiplier for rescoring cold vectors
+    pub oversample: usize,
+    /// Factor applied to access counts at each reassignment
+    pub decay: f32,
+    /// Spill file for cold vectors; without one they are read back from
+    /// storage one at a time
+    #[cfg(feature = "storage")]
+    #[serde(default)]
+    pub cold_file: Option<ColdFile>,
+}
+
+/// File that cold vectors are spilled to, for batched candidate reads
+#[cfg(feature = "storage")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColdFile {
+    /// Path of the file; it is recreated when the budget is enabled
+    pub path: PathBuf,
+    /// How candidate vectors are read
+    #[serde(default)]
+    pub backend: ReadBackend,
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        Self {
+            max_bytes: 1 << 30,
+            rebalance_every: 1000,
+            oversample: 4,
+            decay: 0.5,
+            #[cfg(feature = "storage")]
+            cold_file: None,
+        }
+    }
+}
+
+/// Storage tier of a vector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tier {
+    /// Full precision in memory
+    Hot,
+    /// Scalar-quantized in memory
+    Warm,
+    /// Binary codes in memory, full precision on disk
+    Cold,
+}
+
+impl Tier {
+    /// Resident bytes of one vector with `dimensions` components
+    pub fn bytes(self, dimensions: usize) -> usize {
+        match self {
+            Tier::Hot => dimensions * 4,
+            Tier::Warm => dimensions + 8,
+            Tier::Cold => (dimensions + 7) / 8 + 4,
+        }
+    }
+}
+
+/// Resident memory of a tiered index
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryUsage {
+    /// Configured budget
+    pub budget_bytes: usize,
+    /// Bytes of vector data currently resident
+    pub used_bytes: usize,
+    /// Vectors in the hot tier
+    pub hot: usize,
+    /// Vectors in the warm tier
+    pub warm: usize,
+    /// Vectors in the cold tier
+    pub cold: usize,
+    /// Tier reassignments since the index was built
+    pub rebalances: u64,
+}
+
+enum Resident {
+    Hot(Vec<f32>),
+    Warm(ScalarQuantized),
+    Cold { code: BinaryQuantized, scale: f32 },
+}
+
+impl Resident {
+    fn encode(vector: Vec<f32>, tier: Tier) -> Self {
+        match tier {
+            Tier::Hot => Resident::Hot(vector),
+            Tier::Warm => Resident::Warm(ScalarQuantized::quantize(&vector)),
+            Tier::Cold => {
+                let scale =
+                    vector.iter().map(|x| x.abs()).sum::<f32>() / vector.len().max(1) as f32;
+                Resident::Cold {
+                    code: BinaryQuantized::quantize(&vector),
+                    scale,
+                }
+            }
+        }
+    }
+
+    fn tier(&self) -> Tier {
+        match self {
+            Resident::Hot(_) => Tier::Hot,
+            Resident::Warm(_) => Tier::Warm,
+            Resident::Cold { .. } => Tier::Cold,
+        }
+    }
+
+    fn score(&self, query: &[f32], metric: DistanceMetric) -> Result<f32> {
+        match self {
+            Resident::Hot(vector) => distance(query, vector, metric),
+            Resident::Warm(codes) => Ok(codes.asymmetric_distance(query, metric)),
+            Resident::Cold { code, scale } => {
+                let approx: Vec<f32> = code.reconstruct().iter().map(|x| x * scale).collect();
+                distance(query, &approx, metric)
+            }
+        }
+    }
+}
+
+struct Slot {
+    resident: Resident,
+    hits: f32,
+    /// Position in the spill file while the vector is cold
+    spilled: Option<u64>,
+}
+
+/// A search candidate before rescoring
+struct Candidate {
+    id: VectorId,
+    score: f32,
+    tier: Tier,
+    spilled: Option<u64>,
+}
+
+/// Number of hot and warm vectors, hottest first, that fit in `max_bytes`
+/// with every remaining vector cold
+pub(crate) fn plan_tiers(count: usize, dimensions: usize, max_bytes: usize) -> (usize, usize) {
+    let cold = Tier::Cold.bytes(dimensions);
+    let mut left = max_bytes.saturating_sub(count * cold);
+    let hot_extra = Tier::Hot.bytes(dimensions) - cold;
+    let warm_extra = Tier::Warm.bytes(dimensions).saturating_sub(cold);
+
+    let hot = (left / hot_extra.max(1)).min(count);
+    left -= hot * hot_extra;
+    let warm = if warm_extra == 0 {
+        count - hot
+    } else {
+        (left / warm_extra).min(count - hot)
+    };
+    (hot, warm)
+}
+
+/// Brute-force index holding vectors in hot, warm and cold tiers
+pub struct TieredIndex {
+    slots: DashMap<VectorId, Slot>,
+    storage: Arc<VectorStorage>,
+    budget: MemoryBudget,
+    metric: DistanceMetric,
+    dimensions: usize,
+    used: AtomicUsize,
+    searches: AtomicUsize,
+    rebalancing: AtomicBool,
+    rebalances: AtomicU64,
+    /// Spill file of cold vectors; demotions append, so it only grows
+    /// until the index is rebuilt
+    #[cfg(feature = "storage")]
+    cold_file: Option<RwLock<VectorFile>>,
+}
+
+impl TieredIndex {
+    /// Empty index reading cold vectors back from `storage`, or from the
+    /// budget's spill file if it has one
+    pub(crate) fn new(
+        dimensions: usize,
+        metric: DistanceMetric,
+        budget: MemoryBudget,
+        storage: Arc<VectorStorage>,
+    ) -> Result<Self> {
+        #[cfg(feature = "storage")]
+        let cold_file = match &budget.cold_file {
+            Some(config) => Some(RwLock::new(
+                VectorFile::create(&config.path, dimensions)?.with_backend(config.backend)?,
+            )),
+            None => None,
+        };
+        Ok(Self {
+            slots: DashMap::new(),
+            storage,
+            budget,
+            metric,
+            dimensions,
+            used: AtomicUsize::new(0),
+            searches: AtomicUsize::new(0),
+            rebalancing: AtomicBool::new(false),
+            rebalances: AtomicU64::new(0),
+            #[cfg(feature = "storage")]
+            cold_file,
+        })
+    }
+
+    /// Encode `vector` for `tier`, spilling it if it goes cold
+    fn encode(&self, vector: Vec<f32>, tier: Tier) -> Result<(Resident, Option<u64>)> {
+        #[cfg(feature = "storage")]
+        if let (Tier::Cold, Some(file)) = (tier, &self.cold_file) {
+            let position = file.write().append(&vector)?;
+            return Ok((Resident::encode(vector, tier), Some(position)));
+        }
+        Ok((Resident::encode(vector, tier), None))
+    }
+
+    /// Replace the approximate scores of cold candidates with exact ones
+    ///
+    /// Spilled vectors are read in one batch; the rest come from storage.
+    fn rescore_cold(&self, query: &[f32], candidates: &mut [Candidate]) -> Result<()> {
+        let mut spilled = Vec::new();
+        for (i, candidate) in candidates.iter_mut().enumerate() {
+            if candidate.tier != Tier::Cold {
+                continue;
+            }
+            match candidate.spilled {
+                Some(position) => spilled.push((i, position)),
+                None => {
+                    if let Some(entry) = self.storage.get(&candidate.id)? {
+                        candidate.score = distance(query, &entry.vector, self.metric)?;
+                    }
+                }
+            }
+        }
+
+        #[cfg(feature = "storage")]
+        if let (Some(file), false) = (&self.cold_file, spilled.is_empty()) {
+            let positions: Vec<u64> = spilled.iter().map(|&(_, p)| p).collect();
+            let vectors = file.read().read_many(&positions)?;
+            for ((i, _), vector) in spilled.iter().zip(vectors) {
+                candidates[*i].score = distance(query, &vector, self.metric)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Decay access counts and reassign tiers to fit the budget
+    ///
+    /// Vectors promoted to the hot tier are read back from storage.
+    pub fn rebalance(&self) -> Result<()> {
+        if self.rebalancing.swap(true, Ordering::Acquire) {
+            return Ok(());
+        }
+        let result = self.reassign();
+        self.rebalancing.store(false, Ordering::Release);
+        self.rebalances.fetch_add(1, Ordering::Relaxed);
+        result
+    }
+
+    fn reassign(&self) -> Result<()> {
+        let mut ranked: Vec<(VectorId, f32)> = self
+            .slots
+            .iter_mut()
+            .map(|mut slot| {
+                slot.hits *= self.budget.decay;
+                (slot.key().clone(), slot.hits)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let (hot, warm) = plan_tiers(ranked.len(), self.dimensions, self.budget.max_bytes);
+        for (rank, (id, _)) in ranked.iter().enumerate() {
+            let tier = if rank < hot {
+                Tier::Hot
+            } else if rank < hot + warm {
+                Tier::Warm
+            } else {
+                Tier::Cold
+            };
+            let Some(current) = self.slots.get(id).map(|slot| slot.resident.tier()) else {
+                continue;
+            };
+            if current == tier {
+                continue;
+            }
+            // Promotions need the full vector; demotions can use it too
+            let Some(entry) = self.storage.get(id)? else {
+                continue;
+            };
+            let (resident, spilled) = self.encode(entry.vector, tier)?;
+            if let Some(mut slot) = self.slots.get_mut(id) {
+                slot.resident = resident;
+                slot.spilled = spilled;
+            }
+        }
+
+        let used = self
+            .slots
+            .iter()
+            .map(|slot| slot.resident.tier().bytes(self.dimensions))
+            .sum();
+        self.used.store(used, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Resident memory by tier
+    pub fn usage(&self) -> MemoryUsage {
+        let mut usage = MemoryUsage {
+            budget_bytes: self.budget.max_bytes,
+            used_bytes: self.used.load(Ordering::Relaxed),
+            hot: 0,
+            warm: 0,
+            cold: 0,
+            rebalances: self.rebalances.load(Ordering::Relaxed),
+        };
+        for slot in self.slots.iter() {
+            match slot.resident.tier() {
+                Tier::Hot => usage.hot += 1,
+                Tier::Warm => usage.warm += 1,
+                Tier::Cold => usage.cold += 1,
+            }
+        }
+        usage
+    }
+
+    /// The tier `id` is held in
+    pub fn tier(&self, id: &str) -> Option<Tier> {
+        self.slots.get(id).map(|slot| slot.resident.tier())
+    }
+
+    fn check_dimensions(&self, len: usize) -> Result<()> {
+        if len != self.dimensions {
+            return Err(RuvectorError::DimensionMismatch {
+                expected: self.dimensions,
+                actual: len,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl VectorIndex for TieredIndex {
+    fn add(&mut self, id: VectorId, vector: Vec<f32>) -> Result<()> {
+        self.check_dimensions(vector.len())?;
+        let used = self.used.load(Ordering::Relaxed);
+        let tier = [Tier::Hot, Tier::Warm]
+            .into_iter()
+            .find(|tier| used + tier.bytes(self.dimensions) <= self.budget.max_bytes)
+            .unwrap_or(Tier::Cold);
+
+        let (resident, spilled) = self.encode(vector, tier)?;
+        let slot = Slot {
+            resident,
+            hits: 0.0,
+            spilled,
+        };
+        if let Some(previous) = self.slots.insert(id, slot) {
+            self.used.fetch_sub(
+                previous.resident.tier().bytes(self.dimensions),
+                Ordering::Relaxed,
+            );
+        }
+        self.used
+            .fetch_add(tier.bytes(self.dimensions), Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn add_batch(&mut self, entries: Vec<(VectorId, Vec<f32>)>) -> Result<()> {
+        for (id, vector) in entries {
+            self.add(id, vector)?;
+        }
+        if self.used.load(Ordering::Relaxed) > self.budget.max_bytes {
+            self.rebalance()?;
+        }
+        Ok(())
+    }
+
+    fn search(&self, query: &[f32], k: usize) -> Result<Vec<SearchResult>> {
+        self.check_dimensions(query.len())?;
+
+        let mut scored = Vec::with_capacity(self.slots.len());
+        for slot in self.slots.iter() {
+            scored.push(Candidate {
+                id: slot.key().clone(),
+                score: slot.resident.score(query, self.metric)?,
+                tier: slot.resident.tier(),
+                spilled: slot.spilled,
+            });
+        }
+        scored.sort_by(|a, b| a.score.total_cmp(&b.score));
+        scored.truncate(k * self.budget.oversample.max(1));
+
+        self.rescore_cold(query, &mut scored)?;
+        scored.sort_by(|a, b| a.score.total_cmp(&b.score));
+        scored.truncate(k);
+
+        for Candidate { id, .. } in &scored {
+            if let Some(mut slot) = self.slots.get_mut(id) {
+                slot.hits += 1.0;
+            }
+        }
+        let searches = self.searches.fetch_add(1, Ordering::Relaxed) + 1;
+        if searches % self.budget.rebalance_every.max(1) == 0 {
+            self.rebalance()?;
+        }
+
+        Ok(scored
+            .into_iter()
+            .map(|Candidate { id, score, .. }| SearchResult {
+                id,
+                score,
+                vector: None,
+                metadata: None,
+            })
+            .collect())
+    }
+
+    fn remove(&mut self, id: &VectorId) -> Result<bool> {
+        let Some((_, slot)) = self.slots.remove(id) else {
+            return Ok(false);
+        };
+        self.used.fetch_sub(
+            slot.resident.tier().bytes(self.dimensions),
+            Ordering::Relaxed,
+        );
+        Ok(true)
+    }
+
+    fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn memory_usage(&self) -> Option<MemoryUsage> {
+        Some(self.usage())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_tiers_fills_budget_hottest_first() {
+        // 16 dims: hot 64 bytes, warm 24, cold 6
+        assert_eq!(plan_tiers(10, 16, usize::MAX), (10, 0));
+        assert_eq!(plan_tiers(10, 16, 60), (0, 0));
+        // 60 for all cold, 58 more buys one hot upgrade (58) ...
+        assert_eq!(plan_tiers(10, 16, 118), (1, 0));
+        // ... and 18 per warm upgrade with what is left
+        assert_eq!(plan_tiers(10, 16, 118 + 36), (1, 2));
+        assert_eq!(plan_tiers(0, 16, 0), (0, 0));
+    }
+
+    #[test]
+    fn test_cold_codes_keep_scale() {
+        let cold = Resident::encode(vec![2.0, -2.0, 2.0, -2.0], Tier::Cold);
+        assert_eq!(cold.tier(), Tier::Cold);
+        let score = cold
+            .score(&[2.0, -2.0, 2.0, -2.0], DistanceMetric::Euclidean)
+            .unwrap();
+        assert!(score < 1e-6);
+    }
+}
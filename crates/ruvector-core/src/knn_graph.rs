@@ -0,0 +1,139 @@
+//! k-NN graph export
+//!
+//! Materializes the k-nearest-neighbor graph of a database as an edge list
+//! that can be loaded into NetworkX, Gephi or similar tools, e.g. to audit
+//! index connectivity before and after compaction. [`Subgraph`] holds the
+//! local neighborhood around a few vectors instead of the whole graph.
+
+use crate::error::{Result, RuvectorError};
+use crate::types::{DistanceMetric, VectorId};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// A directed edge from a vector to one of its nearest neighbors
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KnnEdge {
+    /// Source vector id
+    pub source: VectorId,
+    /// Neighbor vector id
+    pub target: VectorId,
+    /// Distance between the two vectors
+    pub distance: f32,
+}
+
+/// A materialized k-NN graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnnGraph {
+    /// Neighbors requested per node
+    pub k: usize,
+    /// Distance metric used for the edges
+    pub metric: DistanceMetric,
+    /// All node ids, including nodes without outgoing edges
+    pub nodes: Vec<VectorId>,
+    /// Edges, grouped by source in rank order
+    pub edges: Vec<KnnEdge>,
+}
+
+impl KnnGraph {
+    /// Number of nodes
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of edges
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Write the edges as CSV with a `source,target,distance` header
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> Result<()> {
+        writeln!(writer, "source,target,distance")?;
+        for edge in &self.edges {
+            writeln!(
+                writer,
+                "{},{},{}",
+                csv_field(&edge.source),
+                csv_field(&edge.target),
+                edge.distance
+            )?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Write the graph to a file
+    ///
+    /// Files ending in `.json` get the full graph as JSON; anything else gets
+    /// the CSV edge list (readable with `networkx.read_edgelist(path,
+    /// delimiter=",", data=[("distance", float)])` after skipping the header).
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let writer = BufWriter::new(File::create(path)?);
+        if path.extension().map_or(false, |ext| ext == "json") {
+            serde_json::to_writer(writer, self)
+                .map_err(|e| RuvectorError::SerializationError(e.to_string()))
+        } else {
+            self.write_csv(writer)
+        }
+    }
+}
+
+/// Local neighborhood around a set of vectors, see
+/// [`crate::VectorDB::subgraph`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Subgraph {
+    /// Node ids, seeds first, then in order of discovery
+    pub nodes: Vec<VectorId>,
+    /// Hops from the nearest seed for each node, parallel to `nodes`
+    pub hops: Vec<usize>,
+    /// Directed edges between nodes, grouped by source
+    pub edges: Vec<KnnEdge>,
+}
+
+impl Subgraph {
+    /// Number of nodes
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of edges
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_csv_escapes_ids() {
+        let graph = KnnGraph {
+            k: 1,
+            metric: DistanceMetric::Euclidean,
+            nodes: vec!["a".to_string(), "b,c".to_string()],
+            edges: vec![KnnEdge {
+                source: "a".to_string(),
+                target: "b,c".to_string(),
+                distance: 0.5,
+            }],
+        };
+
+        let mut out = Vec::new();
+        graph.write_csv(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "source,target,distance\na,\"b,c\",0.5\n"
+        );
+    }
+}
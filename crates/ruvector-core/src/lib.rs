@@ -28,17 +28,52 @@
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 
+pub mod adaptive_ef;
 pub mod advanced_features;
 
 // AgenticDB requires storage feature
 #[cfg(feature = "storage")]
 pub mod agenticdb;
 
+pub mod anomaly;
+pub mod audit;
+pub mod bench_report;
+pub mod boost;
+pub mod bulk_load;
+pub mod capabilities;
+pub mod centroid;
+pub mod classifier;
+pub mod content_id;
+pub mod context_pack;
+pub mod dedupe;
+pub mod delta;
 pub mod distance;
+pub mod drift;
+pub mod embedding_model;
+pub mod embedding_stats;
 pub mod embeddings;
 pub mod error;
+pub mod graph_analytics;
+pub mod health;
+pub mod id_filter;
 pub mod index;
+pub mod knn_graph;
+pub mod maintenance;
+pub mod multi_metric;
+pub mod normalization;
+pub mod op_log;
+pub mod privacy;
+pub mod projection;
 pub mod quantization;
+pub mod query_template;
+pub mod query_vector;
+pub mod recency;
+#[cfg(feature = "storage")]
+pub mod recovery;
+pub mod result_cache;
+pub mod router;
+pub mod shutdown;
+pub mod slow_query;
 
 // Storage backends - conditional compilation based on features
 #[cfg(feature = "storage")]
@@ -50,8 +85,13 @@ pub mod storage_memory;
 #[cfg(not(feature = "storage"))]
 pub use storage_memory as storage;
 
+pub mod throttle;
+pub mod trash;
 pub mod types;
 pub mod vector_db;
+#[cfg(feature = "storage")]
+pub mod vector_file;
+pub mod warmup;
 
 // Performance optimization modules
 pub mod arena;
@@ -64,6 +104,7 @@ pub mod simd_intrinsics;
 pub mod advanced;
 
 // Re-exports
+pub use adaptive_ef::{AdaptiveEfConfig, DifficultyModel, QueryDifficulty, QueryFeatures};
 pub use advanced_features::{
     ConformalConfig, ConformalPredictor, EnhancedPQ, FilterExpression, FilterStrategy,
     FilteredSearch, HybridConfig, HybridSearch, MMRConfig, MMRSearch, PQConfig, PredictionSet,
@@ -71,8 +112,12 @@ pub use advanced_features::{
 };
 
 #[cfg(feature = "storage")]
-pub use agenticdb::AgenticDB;
+pub use agenticdb::{
+    agenticdb_import, AgenticDB, AgenticOperation, CompatibilityGap, ImportReport,
+};
 
+pub use embedding_model::{EmbeddingModel, ModelGuard};
+pub use embedding_stats::{EmbeddingStats, NormPercentiles, StatsConfig};
 pub use embeddings::{EmbeddingProvider, HashEmbedding, BoxedEmbeddingProvider};
 #[cfg(feature = "api-embeddings")]
 pub use embeddings::ApiEmbedding;
@@ -92,9 +137,47 @@ const _: () = {
     let _ = AGENTICDB_EMBEDDING_WARNING;
 };
 
+pub use anomaly::{AnomalyConfig, AnomalyMethod, AnomalyReport, AnomalyScore};
+pub use audit::{AuditConfig, AuditLog, Feedback, FeedbackEvent, QueryId};
+pub use bench_report::{BenchRecord, BenchReport};
+pub use boost::{Boost, BoostOp, BoostSpec, BoostedResult};
+pub use bulk_load::{BulkLoadConfig, BulkLoadReport};
+pub use capabilities::{capabilities, Capabilities};
+pub use classifier::{Classification, ClassifyConfig, ClassifyMethod};
+pub use content_id::{content_id, IdStrategy};
+pub use context_pack::{ContextChunk, ContextPack, ContextPackConfig};
+pub use dedupe::{DedupeConfig, DedupeReport, DuplicateAction, DuplicateGroup};
+pub use delta::DeltaUpdate;
+pub use drift::{compare_embeddings, DriftConfig, DriftReport};
 pub use error::{Result, RuvectorError};
+pub use graph_analytics::{GraphAnalytics, HubNode};
+pub use health::{CheckResult, HealthCheckConfig, HealthReport, HealthStatus};
+pub use index::quantized::{QuantizedIndex, SearchPrecision};
+pub use index::{GraphNeighbor, GraphStats};
+pub use index::tiered::{MemoryBudget, MemoryUsage, Tier, TieredIndex};
+pub use knn_graph::{KnnEdge, KnnGraph, Subgraph};
+pub use maintenance::{
+    MaintenanceConfig, MaintenanceOutcome, MaintenanceRun, MaintenanceScheduler, MaintenanceTask,
+    MaintenanceWindow,
+};
+pub use normalization::NormalizationPolicy;
+pub use op_log::{OpLog, OpLogConfig, OpRecord, Operation, ReplayReport, VectorCapture};
+pub use privacy::{DpExport, DpExportConfig, PrivacyBudget, PrivacyCharge, PrivacyLedger};
+pub use projection::{ProjectedPoint, ProjectionConfig, ProjectionMethod};
+pub use query_template::{FusionSettings, QueryTemplate};
+pub use query_vector::{QueryVector, VectorSource, WeightedTerm};
+pub use recency::{TimeDecay, TimeUnit};
+#[cfg(feature = "storage")]
+pub use recovery::RecoveryReport;
+pub use result_cache::{ResultCache, ResultCacheConfig, ResultCacheStats};
+pub use router::{RouteMatch, Router, RouterConfig};
+pub use shutdown::ShutdownReport;
+pub use slow_query::{SlowQueryConfig, SlowQueryEntry, SlowQueryLog, VectorStats};
+pub use throttle::PerformanceConfig;
+pub use trash::TrashedEntry;
 pub use types::{DistanceMetric, SearchQuery, SearchResult, VectorEntry, VectorId};
-pub use vector_db::VectorDB;
+pub use vector_db::{IngestBatch, VectorDB};
+pub use warmup::{WarmupConfig, WarmupPhase, WarmupProgress, WarmupReport};
 
 #[cfg(test)]
 mod tests {
@@ -4,14 +4,91 @@
 //! to minimize contention and improve scalability.
 //!
 //! Note: This module requires the `parallel` feature and is not available on WASM.
+//!
+//! With [`set_contention_tracking`] enabled, contention is counted
+//! process-wide and read with [`contention_stats`]. Tracking is off by
+//! default so the hot paths do not all write to the same shared counters.
+//! Memory is reclaimed by the crossbeam queues themselves rather than by an
+//! epoch collector, so there is no reclamation lag to report.
 
 #![cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
 
 use crossbeam::queue::{ArrayQueue, SegQueue};
 use crossbeam::utils::CachePadded;
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 
+static TRACKING: AtomicBool = AtomicBool::new(false);
+static CAS_RETRIES: CachePadded<AtomicU64> = CachePadded::new(AtomicU64::new(0));
+static POOL_HITS: CachePadded<AtomicU64> = CachePadded::new(AtomicU64::new(0));
+static POOL_MISSES: CachePadded<AtomicU64> = CachePadded::new(AtomicU64::new(0));
+static POOL_SPIN_WAITS: CachePadded<AtomicU64> = CachePadded::new(AtomicU64::new(0));
+static QUEUE_PUSH_FULL: CachePadded<AtomicU64> = CachePadded::new(AtomicU64::new(0));
+static QUEUE_POP_EMPTY: CachePadded<AtomicU64> = CachePadded::new(AtomicU64::new(0));
+
+/// Enable or disable contention counting
+pub fn set_contention_tracking(enabled: bool) {
+    TRACKING.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether contention counting is enabled
+pub fn contention_tracking_enabled() -> bool {
+    TRACKING.load(Ordering::Relaxed)
+}
+
+/// Bump a contention counter if tracking is enabled
+#[inline]
+fn record(counter: &AtomicU64) {
+    if TRACKING.load(Ordering::Relaxed) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Process-wide contention counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContentionSnapshot {
+    /// Failed compare-and-swap attempts that had to be retried
+    pub cas_retries: u64,
+    /// Pool acquisitions served from returned objects
+    pub pool_hits: u64,
+    /// Pool acquisitions that created a new object
+    pub pool_misses: u64,
+    /// Spin iterations spent waiting on an exhausted pool
+    pub pool_spin_waits: u64,
+    /// Pushes rejected by a full work queue
+    pub queue_push_full: u64,
+    /// Pops that found the work queue empty
+    pub queue_pop_empty: u64,
+}
+
+impl ContentionSnapshot {
+    /// Counters accumulated since `earlier`
+    pub fn since(&self, earlier: &ContentionSnapshot) -> ContentionSnapshot {
+        ContentionSnapshot {
+            cas_retries: self.cas_retries - earlier.cas_retries,
+            pool_hits: self.pool_hits - earlier.pool_hits,
+            pool_misses: self.pool_misses - earlier.pool_misses,
+            pool_spin_waits: self.pool_spin_waits - earlier.pool_spin_waits,
+            queue_push_full: self.queue_push_full - earlier.queue_push_full,
+            queue_pop_empty: self.queue_pop_empty - earlier.queue_pop_empty,
+        }
+    }
+}
+
+/// Read the process-wide contention counters
+///
+/// Counters only advance while tracking is enabled.
+pub fn contention_stats() -> ContentionSnapshot {
+    ContentionSnapshot {
+        cas_retries: CAS_RETRIES.load(Ordering::Relaxed),
+        pool_hits: POOL_HITS.load(Ordering::Relaxed),
+        pool_misses: POOL_MISSES.load(Ordering::Relaxed),
+        pool_spin_waits: POOL_SPIN_WAITS.load(Ordering::Relaxed),
+        queue_push_full: QUEUE_PUSH_FULL.load(Ordering::Relaxed),
+        queue_pop_empty: QUEUE_POP_EMPTY.load(Ordering::Relaxed),
+    }
+}
+
 /// Lock-free counter with cache padding to prevent false sharing
 #[repr(align(64))]
 pub struct LockFreeCounter {
@@ -39,6 +116,26 @@ impl LockFreeCounter {
     pub fn add(&self, delta: u64) -> u64 {
         self.value.fetch_add(delta, Ordering::Relaxed)
     }
+
+    /// Raise the value to at least `value`, returning the previous value
+    pub fn update_max(&self, value: u64) -> u64 {
+        let mut current = self.value.load(Ordering::Relaxed);
+        while current < value {
+            match self.value.compare_exchange_weak(
+                current,
+                value,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(previous) => return previous,
+                Err(actual) => {
+                    record(&CAS_RETRIES);
+                    current = actual;
+                }
+            }
+        }
+        current
+    }
 }
 
 /// Lock-free statistics collector
@@ -130,21 +227,27 @@ impl<T> ObjectPool<T> {
 
     /// Get an object from the pool or create a new one
     pub fn acquire(&self) -> PooledObject<T> {
-        let object = self.queue.pop().unwrap_or_else(|| {
-            let current = self.allocated.fetch_add(1, Ordering::Relaxed);
-            if current < self.capacity {
+        let object = match self.queue.pop() {
+            Some(object) => {
+                record(&POOL_HITS);
+                object
+            }
+            None if self.try_reserve() => {
+                record(&POOL_MISSES);
                 (self.factory)()
-            } else {
-                self.allocated.fetch_sub(1, Ordering::Relaxed);
+            }
+            None => {
                 // Wait for an object to be returned
                 loop {
                     if let Some(obj) = self.queue.pop() {
+                        record(&POOL_HITS);
                         break obj;
                     }
+                    record(&POOL_SPIN_WAITS);
                     std::hint::spin_loop();
                 }
             }
-        });
+        };
 
         PooledObject {
             object: Some(object),
@@ -153,6 +256,28 @@ impl<T> ObjectPool<T> {
     }
 }
 
+impl<T> ObjectPool<T> {
+    /// Claim room for one more object if under capacity
+    fn try_reserve(&self) -> bool {
+        let mut current = self.allocated.load(Ordering::Relaxed);
+        while current < self.capacity {
+            match self.allocated.compare_exchange_weak(
+                current,
+                current + 1,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return true,
+                Err(actual) => {
+                    record(&CAS_RETRIES);
+                    current = actual;
+                }
+            }
+        }
+        false
+    }
+}
+
 /// RAII wrapper for pooled objects
 pub struct PooledObject<T> {
     object: Option<T>,
@@ -205,12 +330,20 @@ impl<T> LockFreeWorkQueue<T> {
 
     #[inline]
     pub fn try_push(&self, item: T) -> Result<(), T> {
-        self.queue.push(item)
+        let result = self.queue.push(item);
+        if result.is_err() {
+            record(&QUEUE_PUSH_FULL);
+        }
+        result
     }
 
     #[inline]
     pub fn try_pop(&self) -> Option<T> {
-        self.queue.pop()
+        let item = self.queue.pop();
+        if item.is_none() {
+            record(&QUEUE_POP_EMPTY);
+        }
+        item
     }
 
     #[inline]
@@ -278,4 +411,45 @@ mod tests {
         assert_eq!(snapshot.inserts, 1);
         assert_eq!(snapshot.avg_latency_ns, 1500);
     }
+
+    #[test]
+    fn test_contention_counters() {
+        set_contention_tracking(true);
+        let before = contention_stats();
+
+        let pool = ObjectPool::new(1, Vec::<u8>::new);
+        drop(pool.acquire());
+        drop(pool.acquire());
+
+        let queue = LockFreeWorkQueue::new(1);
+        queue.try_push(1).unwrap();
+        assert!(queue.try_push(2).is_err());
+        queue.try_pop();
+        assert!(queue.try_pop().is_none());
+
+        let delta = contention_stats().since(&before);
+        assert!(delta.pool_misses >= 1);
+        assert!(delta.pool_hits >= 1);
+        assert!(delta.queue_push_full >= 1);
+        assert!(delta.queue_pop_empty >= 1);
+    }
+
+    #[test]
+    fn test_update_max() {
+        let counter = Arc::new(LockFreeCounter::new(0));
+        let handles: Vec<_> = (0..8)
+            .map(|t| {
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || {
+                    for i in 0..1000 {
+                        counter.update_max(t * 1000 + i);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(counter.get(), 7999);
+    }
 }
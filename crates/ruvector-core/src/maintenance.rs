@@ -0,0 +1,363 @@
+//! Background maintenance scheduler
+//!
+//! Deleting from the HNSW index only unlinks the id; the graph node stays
+//! behind and keeps costing memory and search time until the index is
+//! rebuilt. Product-quantization codebooks trained on an early snapshot of
+//! the data fit later vectors less and less well. Both are fixed by
+//! rebuilding, which operators previously had to trigger by hand.
+//!
+//! [`VectorDB::start_maintenance`](crate::VectorDB::start_maintenance)
+//! starts a thread that periodically checks which tasks are due and runs
+//! them when traffic is low:
+//!
+//! - only inside the configured UTC hour windows, if any,
+//! - only while the database admits fewer operations per second than
+//!   [`MaintenanceConfig::max_ops_per_sec`],
+//! - reading storage in batches with pauses that cap both the read rate and
+//!   the share of wall time spent working.
+//!
+//! Rebuilt indexes are built on the side and swapped in at the end. If a
+//! write lands while a task runs, the result is discarded and the task runs
+//! again on a later check. Storage needs no vacuuming of its own: the
+//! storage engine reuses pages freed by deletes on commit.
+
+use crate::types::VectorId;
+use crate::vector_db::VectorDB;
+use chrono::Timelike;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Longest uninterrupted sleep, so stopping the scheduler is prompt
+const SLEEP_SLICE: Duration = Duration::from_millis(10);
+
+/// Maintenance runs kept in the scheduler history
+const HISTORY: usize = 64;
+
+/// A maintenance task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaintenanceTask {
+    /// Rebuild the HNSW index from storage, dropping deleted nodes
+    CompactIndex,
+    /// Retrain product-quantization codebooks and re-encode every vector
+    RetrainCodebooks,
+}
+
+/// Hours of the day (UTC) in which maintenance may run
+///
+/// `start_hour` is inclusive and `end_hour` exclusive; a window that ends
+/// before it starts wraps past midnight, e.g. 22 to 4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceWindow {
+    /// First hour of the window (0-23)
+    pub start_hour: u32,
+    /// Hour the window ends (0-23)
+    pub end_hour: u32,
+}
+
+impl MaintenanceWindow {
+    /// Whether `hour` falls inside the window
+    pub fn contains(&self, hour: u32) -> bool {
+        if self.start_hour <= self.end_hour {
+            (self.start_hour..self.end_hour).contains(&hour)
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+}
+
+/// Maintenance scheduling and throttling settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceConfig {
+    /// How often the scheduler checks for due tasks
+    pub interval: Duration,
+    /// Windows in which tasks may run; empty means any time
+    pub windows: Vec<MaintenanceWindow>,
+    /// Tasks are postponed while the database admits more writes and
+    /// searches per second than this
+    pub max_ops_per_sec: f64,
+    /// Deleted share of HNSW nodes at which the index is compacted
+    pub tombstone_ratio: f32,
+    /// Relative change in vector count since codebooks were trained at which
+    /// they are retrained
+    pub codebook_drift: f32,
+    /// Stored vectors read per batch
+    pub batch_size: usize,
+    /// Cap on stored vectors read per second; `None` is unlimited
+    pub max_reads_per_sec: Option<u64>,
+    /// Share of wall time spent working, in (0, 1]; the rest is slept
+    pub cpu_duty_cycle: f32,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60),
+            windows: Vec::new(),
+            max_ops_per_sec: 10.0,
+            tombstone_ratio: 0.2,
+            codebook_drift: 0.5,
+            batch_size: 1024,
+            max_reads_per_sec: Some(50_000),
+            cpu_duty_cycle: 0.5,
+        }
+    }
+}
+
+impl MaintenanceConfig {
+    /// Whether `hour` (UTC) is inside a maintenance window
+    pub fn in_window(&self, hour: u32) -> bool {
+        self.windows.is_empty() || self.windows.iter().any(|w| w.contains(hour))
+    }
+
+    /// How long to pause after a batch of `reads` that took `busy`
+    pub fn pause_after(&self, busy: Duration, reads: usize) -> Duration {
+        let duty = self.cpu_duty_cycle.clamp(0.01, 1.0);
+        let cpu = busy.mul_f32((1.0 - duty) / duty);
+        let io = match self.max_reads_per_sec {
+            Some(rate) if rate > 0 => {
+                Duration::from_secs_f64(reads as f64 / rate as f64).saturating_sub(busy)
+            }
+            _ => Duration::ZERO,
+        };
+        cpu.max(io)
+    }
+}
+
+/// How a maintenance run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaintenanceOutcome {
+    /// The rebuilt index is in use
+    Completed,
+    /// A write landed during the run; the result was discarded
+    Superseded,
+    /// The scheduler was stopped or the database shut down
+    Cancelled,
+}
+
+/// One maintenance run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceRun {
+    /// Task that ran
+    pub task: MaintenanceTask,
+    /// How it ended
+    pub outcome: MaintenanceOutcome,
+    /// Stored vectors read
+    pub vectors: usize,
+    /// Deleted nodes dropped from the index
+    pub tombstones_removed: usize,
+    /// Milliseconds since the Unix epoch when the run started
+    pub started_ms: i64,
+    /// Wall time, including throttling pauses
+    pub elapsed_ms: u64,
+    /// Time spent paused by throttling
+    pub throttled_ms: u64,
+}
+
+/// Stored vectors with their ids, in read order
+pub(crate) type IdVectors = Vec<(VectorId, Vec<f32>)>;
+
+/// Reads stored vectors in throttled batches
+pub(crate) struct ThrottledReader<'a> {
+    pub(crate) config: &'a MaintenanceConfig,
+    pub(crate) cancel: &'a AtomicBool,
+    pub(crate) throttled: Duration,
+}
+
+impl ThrottledReader<'_> {
+    /// Read `ids` through `get`, or `None` if cancelled part way
+    pub(crate) fn read<F>(
+        &mut self,
+        ids: Vec<VectorId>,
+        mut get: F,
+    ) -> crate::Result<Option<IdVectors>>
+    where
+        F: FnMut(&VectorId) -> crate::Result<Option<Vec<f32>>>,
+    {
+        let mut vectors = Vec::with_capacity(ids.len());
+        for chunk in ids.chunks(self.config.batch_size.max(1)) {
+            if self.cancelled() {
+                return Ok(None);
+            }
+            let started = Instant::now();
+            for id in chunk {
+                if let Some(vector) = get(id)? {
+                    vectors.push((id.clone(), vector));
+                }
+            }
+            let pause = self.config.pause_after(started.elapsed(), chunk.len());
+            if !sleep_unless(self.cancel, pause) {
+                return Ok(None);
+            }
+            self.throttled += pause;
+        }
+        Ok(Some(vectors))
+    }
+
+    pub(crate) fn cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+}
+
+/// Sleep for `duration` in slices; false if `cancel` was set meanwhile
+fn sleep_unless(cancel: &AtomicBool, duration: Duration) -> bool {
+    let deadline = Instant::now() + duration;
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return false;
+        }
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return true;
+        }
+        std::thread::sleep(left.min(SLEEP_SLICE));
+    }
+}
+
+/// Handle to a running maintenance thread
+pub struct MaintenanceScheduler {
+    config: MaintenanceConfig,
+    stop: AtomicBool,
+    history: RwLock<VecDeque<MaintenanceRun>>,
+    thread: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl MaintenanceScheduler {
+    pub(crate) fn start(db: &Arc<VectorDB>, config: MaintenanceConfig) -> Arc<Self> {
+        let scheduler = Arc::new(Self {
+            config,
+            stop: AtomicBool::new(false),
+            history: RwLock::new(VecDeque::new()),
+            thread: Mutex::new(None),
+        });
+        let handle = {
+            let scheduler = Arc::clone(&scheduler);
+            let db = Arc::downgrade(db);
+            std::thread::Builder::new()
+                .name("ruvector-maintenance".to_string())
+                .spawn(move || scheduler.run(db))
+                .expect("failed to spawn maintenance thread")
+        };
+        *scheduler.thread.lock() = Some(handle);
+        scheduler
+    }
+
+    /// Settings the scheduler runs with
+    pub fn config(&self) -> &MaintenanceConfig {
+        &self.config
+    }
+
+    /// Recent runs, oldest first
+    pub fn history(&self) -> Vec<MaintenanceRun> {
+        self.history.read().iter().cloned().collect()
+    }
+
+    /// Whether the thread has been asked to stop
+    pub fn is_stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    /// Stop the thread, cancelling a running task, and wait for it to exit
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let handle = self.thread.lock().take();
+        if let Some(handle) = handle {
+            if handle.thread().id() != std::thread::current().id() {
+                let _ = handle.join();
+            }
+        }
+    }
+
+    fn run(&self, db: Weak<VectorDB>) {
+        let mut last_ops = None;
+        let mut last_check = Instant::now();
+        while sleep_unless(&self.stop, self.config.interval) {
+            let Some(db) = db.upgrade() else { break };
+            if db.is_shut_down() {
+                break;
+            }
+
+            let ops = db.ops_started();
+            let elapsed = last_check.elapsed().as_secs_f64().max(1e-3);
+            let rate = last_ops.map_or(0.0, |last| (ops - last) as f64 / elapsed);
+            last_ops = Some(ops);
+            last_check = Instant::now();
+
+            if !self.config.in_window(chrono::Utc::now().hour()) {
+                continue;
+            }
+            if rate > self.config.max_ops_per_sec {
+                tracing::debug!("Postponing maintenance: {:.1} ops/s", rate);
+                continue;
+            }
+
+            for task in db.maintenance_due(&self.config) {
+                match db.run_maintenance_with(task, &self.config, &self.stop) {
+                    Ok(run) => self.record(run),
+                    Err(e) => tracing::warn!("Maintenance task {:?} failed: {}", task, e),
+                }
+            }
+        }
+    }
+
+    fn record(&self, run: MaintenanceRun) {
+        let mut history = self.history.write();
+        history.push_back(run);
+        while history.len() > HISTORY {
+            history.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows_wrap_midnight() {
+        let night = MaintenanceWindow {
+            start_hour: 22,
+            end_hour: 4,
+        };
+        assert!(night.contains(23));
+        assert!(night.contains(0));
+        assert!(!night.contains(4));
+        assert!(!night.contains(12));
+
+        let mut config = MaintenanceConfig::default();
+        assert!(config.in_window(12));
+        config.windows.push(night);
+        assert!(!config.in_window(12));
+    }
+
+    #[test]
+    fn test_pause_caps_cpu_and_reads() {
+        let config = MaintenanceConfig {
+            cpu_duty_cycle: 0.25,
+            max_reads_per_sec: Some(1000),
+            ..Default::default()
+        };
+        // CPU: 10ms of work at a 25% duty cycle sleeps 30ms
+        let pause = config.pause_after(Duration::from_millis(10), 1);
+        assert!((pause.as_secs_f64() - 0.030).abs() < 1e-6);
+        // IO: 500 reads at 1000/s must take 500ms in total
+        let pause = config.pause_after(Duration::from_millis(10), 500);
+        assert_eq!(pause, Duration::from_millis(490));
+
+        let unthrottled = MaintenanceConfig {
+            cpu_duty_cycle: 1.0,
+            max_reads_per_sec: None,
+            ..Default::default()
+        };
+        assert_eq!(
+            unthrottled.pause_after(Duration::from_millis(10), 500),
+            Duration::ZERO
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! Secondary distance metrics over the same vectors
+//!
+//! The index is built for one metric, so another metric can only be served
+//! from it when both rank every candidate the same way. For unit-length
+//! stored vectors and any query `q`:
+//!
+//! - dot product distance is `-q·x`,
+//! - cosine distance is `1 - q·x / |q|`,
+//! - squared euclidean distance is `|q|² + 1 - 2 q·x`,
+//!
+//! all monotone in `q·x`, so cosine, dot product and euclidean are
+//! interchangeable once inserts are normalized (see
+//! [`crate::normalization`]). Manhattan distance has no such relation and
+//! cannot be combined with anything else.
+//!
+//! [`VectorDB::search_with_metric`](crate::VectorDB::search_with_metric)
+//! searches the index with its own metric, then rescores the results with
+//! the requested one.
+
+use crate::error::{Result, RuvectorError};
+use crate::normalization::NormalizationPolicy;
+use crate::types::DistanceMetric;
+
+/// Whether `metric` ranks like the other inner-product family metrics on
+/// unit vectors
+fn inner_product_family(metric: DistanceMetric) -> bool {
+    matches!(
+        metric,
+        DistanceMetric::Cosine | DistanceMetric::DotProduct | DistanceMetric::Euclidean
+    )
+}
+
+/// Check that `secondary` can be served by an index built for `primary`
+///
+/// # Errors
+///
+/// Returns [`RuvectorError::InvalidParameter`] if the metrics do not rank
+/// alike, or only rank alike for normalized vectors and `policy` does not
+/// normalize inserts
+pub fn check_secondary_metric(
+    primary: DistanceMetric,
+    secondary: DistanceMetric,
+    policy: NormalizationPolicy,
+) -> Result<()> {
+    if primary == secondary {
+        return Ok(());
+    }
+    if !inner_product_family(primary) || !inner_product_family(secondary) {
+        return Err(RuvectorError::InvalidParameter(format!(
+            "{:?} cannot be served by a {:?} index",
+            secondary, primary
+        )));
+    }
+    if !policy.normalizes_inserts() {
+        return Err(RuvectorError::InvalidParameter(format!(
+            "{:?} over a {:?} index needs inserts to be normalized",
+            secondary, primary
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secondary_metric_rules() {
+        use DistanceMetric::*;
+        let normalized = NormalizationPolicy::Insert;
+
+        assert!(check_secondary_metric(Cosine, Cosine, NormalizationPolicy::None).is_ok());
+        assert!(check_secondary_metric(Cosine, DotProduct, normalized).is_ok());
+        assert!(check_secondary_metric(Euclidean, Cosine, normalized).is_ok());
+        assert!(check_secondary_metric(Cosine, DotProduct, NormalizationPolicy::None).is_err());
+        assert!(check_secondary_metric(Cosine, Manhattan, normalized).is_err());
+        assert!(check_secondary_metric(Manhattan, Euclidean, normalized).is_err());
+    }
+}
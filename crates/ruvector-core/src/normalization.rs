@@ -0,0 +1,72 @@
+//! Per-database vector normalization policy
+//!
+//! Cosine similarity is only meaningful between vectors on the same footing;
+//! a collection that holds some unit-length and some raw vectors ranks them
+//! inconsistently under dot-product and euclidean comparisons, and every
+//! quantizer sees two different value ranges. A
+//! [`NormalizationPolicy`] set with
+//! [`VectorDB::set_normalization`](crate::VectorDB::set_normalization) is
+//! persisted and enforced by the engine on every write path, so callers
+//! cannot forget it.
+//!
+//! Zero vectors have no direction and are stored unchanged.
+
+use serde::{Deserialize, Serialize};
+
+/// When vectors are L2-normalized
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NormalizationPolicy {
+    /// Vectors are stored and searched as given
+    #[default]
+    None,
+    /// Inserted vectors are normalized; queries are used as given
+    Insert,
+    /// Inserted vectors and query vectors are normalized
+    InsertAndQuery,
+}
+
+impl NormalizationPolicy {
+    /// Whether inserted vectors are normalized
+    pub fn normalizes_inserts(self) -> bool {
+        matches!(
+            self,
+            NormalizationPolicy::Insert | NormalizationPolicy::InsertAndQuery
+        )
+    }
+
+    /// Whether query vectors are normalized
+    pub fn normalizes_queries(self) -> bool {
+        self == NormalizationPolicy::InsertAndQuery
+    }
+}
+
+/// Scale `vector` to unit L2 norm; returns false for zero or non-finite
+/// norms, leaving it unchanged
+pub fn l2_normalize(vector: &mut [f32]) -> bool {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm == 0.0 || !norm.is_finite() {
+        return false;
+    }
+    vector.iter_mut().for_each(|x| *x /= norm);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_l2_normalize() {
+        let mut v = vec![3.0, 4.0];
+        assert!(l2_normalize(&mut v));
+        assert_eq!(v, vec![0.6, 0.8]);
+
+        let mut zero = vec![0.0, 0.0];
+        assert!(!l2_normalize(&mut zero));
+        assert_eq!(zero, vec![0.0, 0.0]);
+
+        assert!(NormalizationPolicy::InsertAndQuery.normalizes_inserts());
+        assert!(!NormalizationPolicy::Insert.normalizes_queries());
+    }
+}
@@ -0,0 +1,344 @@
+//! Operation log capture and deterministic replay
+//!
+//! With an [`OpLog`] enabled, every mutating call on a
+//! [`VectorDB`](crate::VectorDB) is recorded in order: inserts (including
+//! batches and bulk loads) and deletes (including dedupe deletes and the
+//! rollback of an aborted ingest batch). Metadata-only rewrites, such as
+//! dedupe tagging and anomaly scores, are recorded as inserts of the
+//! rewritten entry. The log can be exported as JSON lines and replayed into
+//! an empty database with [`replay`] to reproduce a reported index problem
+//! locally.
+//!
+//! Vectors may be sensitive, so [`VectorCapture`] controls how much of each
+//! one is kept. Every record carries the dimensions and an FNV-1a hash of the
+//! vector; [`VectorCapture::Full`] also keeps the values and
+//! [`VectorCapture::Sampled`] keeps them for every n-th vector. On replay a
+//! vector without values is replaced by a deterministic pseudo-random vector
+//! seeded by its hash, so the index sees the same sequence of operations and
+//! the same ids even when the data itself was not shipped.
+
+use crate::audit::hash_vector;
+use crate::error::{Result, RuvectorError};
+use crate::types::{VectorEntry, VectorId};
+use crate::vector_db::VectorDB;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// How much of each vector an op log keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VectorCapture {
+    /// Keep every vector
+    Full,
+    /// Keep only dimensions and hash
+    Hashed,
+    /// Keep the values of every `every`-th vector, hashes for the rest
+    Sampled {
+        /// Sampling interval
+        every: usize,
+    },
+}
+
+/// Op log settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpLogConfig {
+    /// How much of each vector is kept
+    pub capture: VectorCapture,
+    /// Whether metadata is recorded with inserts
+    pub capture_metadata: bool,
+}
+
+impl Default for OpLogConfig {
+    fn default() -> Self {
+        Self {
+            capture: VectorCapture::Hashed,
+            capture_metadata: false,
+        }
+    }
+}
+
+/// A vector as recorded in the log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedVector {
+    /// Number of components
+    pub dimensions: usize,
+    /// FNV-1a hash of the component bit patterns
+    pub hash: u64,
+    /// Component values, when captured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<f32>>,
+}
+
+impl RecordedVector {
+    /// The captured values, or a deterministic stand-in seeded by the hash
+    pub fn reconstruct(&self) -> Vec<f32> {
+        if let Some(values) = &self.values {
+            return values.clone();
+        }
+        // SplitMix64 so the stand-in only depends on the hash
+        let mut state = self.hash;
+        (0..self.dimensions)
+            .map(|_| {
+                state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+                let mut z = state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+                z ^= z >> 31;
+                (z >> 40) as f32 / (1u64 << 24) as f32 * 2.0 - 1.0
+            })
+            .collect()
+    }
+}
+
+/// A mutating operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Operation {
+    /// A vector was stored under `id`, replacing any previous entry
+    Insert {
+        /// Id the vector was stored under
+        id: VectorId,
+        /// The vector as stored (after normalization)
+        vector: RecordedVector,
+        /// Metadata, when captured
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        metadata: Option<HashMap<String, serde_json::Value>>,
+    },
+    /// `id` was deleted
+    Delete {
+        /// Deleted id
+        id: VectorId,
+    },
+}
+
+/// One logged operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpRecord {
+    /// Sequence number, increasing across the log's lifetime
+    pub seq: u64,
+    /// Milliseconds since the Unix epoch when the operation was applied
+    pub timestamp_ms: i64,
+    /// The operation
+    #[serde(flatten)]
+    pub op: Operation,
+}
+
+/// Ordered log of mutating operations
+pub struct OpLog {
+    config: OpLogConfig,
+    next_seq: AtomicU64,
+    vectors_seen: AtomicU64,
+    records: RwLock<Vec<OpRecord>>,
+}
+
+impl OpLog {
+    /// Create an empty log
+    pub fn new(config: OpLogConfig) -> Self {
+        Self {
+            config,
+            next_seq: AtomicU64::new(1),
+            vectors_seen: AtomicU64::new(0),
+            records: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Settings this log was created with
+    pub fn config(&self) -> &OpLogConfig {
+        &self.config
+    }
+
+    /// Record that `entry` was stored under `id`
+    pub fn record_insert(&self, id: &str, entry: &VectorEntry) {
+        let n = self.vectors_seen.fetch_add(1, Ordering::Relaxed);
+        let keep = match self.config.capture {
+            VectorCapture::Full => true,
+            VectorCapture::Hashed => false,
+            VectorCapture::Sampled { every } => n % every.max(1) as u64 == 0,
+        };
+        self.push(Operation::Insert {
+            id: id.to_string(),
+            vector: RecordedVector {
+                dimensions: entry.vector.len(),
+                hash: hash_vector(&entry.vector),
+                values: keep.then(|| entry.vector.clone()),
+            },
+            metadata: if self.config.capture_metadata {
+                entry.metadata.clone()
+            } else {
+                None
+            },
+        });
+    }
+
+    /// Record that `id` was deleted
+    pub fn record_delete(&self, id: &str) {
+        self.push(Operation::Delete { id: id.to_string() });
+    }
+
+    fn push(&self, op: Operation) {
+        // Sequence numbers are assigned under the lock so they match log order
+        let mut records = self.records.write();
+        records.push(OpRecord {
+            seq: self.next_seq.fetch_add(1, Ordering::Relaxed),
+            timestamp_ms: chrono::Utc::now().timestamp_millis(),
+            op,
+        });
+    }
+
+    /// Number of recorded operations
+    pub fn len(&self) -> usize {
+        self.records.read().len()
+    }
+
+    /// Whether nothing has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Snapshot of recorded operations, oldest first
+    pub fn records(&self) -> Vec<OpRecord> {
+        self.records.read().clone()
+    }
+
+    /// Drop all recorded operations
+    pub fn clear(&self) {
+        self.records.write().clear();
+    }
+
+    /// Write the log as JSON lines, one operation per line
+    pub fn write_jsonl<W: Write>(&self, mut writer: W) -> Result<()> {
+        for record in self.records.read().iter() {
+            serde_json::to_writer(&mut writer, record)
+                .map_err(|e| RuvectorError::SerializationError(e.to_string()))?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Read an op log written by [`OpLog::write_jsonl`]
+pub fn read_jsonl<R: BufRead>(reader: R) -> Result<Vec<OpRecord>> {
+    let mut records = Vec::new();
+    for (n, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(&line).map_err(|e| {
+            RuvectorError::SerializationError(format!("Op log line {}: {}", n + 1, e))
+        })?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Outcome of replaying an op log
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplayReport {
+    /// Operations applied
+    pub applied: usize,
+    /// Inserts whose vector was not captured and was synthesized from its hash
+    pub synthesized: usize,
+    /// Captured vectors whose values do not match their recorded hash
+    pub hash_mismatches: Vec<u64>,
+    /// Operations that failed, by sequence number, with the error
+    pub failed: Vec<(u64, String)>,
+}
+
+/// Apply recorded operations to `db` in sequence order
+///
+/// Operations are applied one at a time on the calling thread, so replaying
+/// the same log into a fresh database always performs the same index
+/// mutations. Failures are collected rather than stopping the replay.
+pub fn replay(db: &VectorDB, records: &[OpRecord]) -> ReplayReport {
+    let mut ordered: Vec<&OpRecord> = records.iter().collect();
+    ordered.sort_by_key(|record| record.seq);
+
+    let mut report = ReplayReport::default();
+    for record in ordered {
+        let result = match &record.op {
+            Operation::Insert {
+                id,
+                vector,
+                metadata,
+            } => {
+                match &vector.values {
+                    None => report.synthesized += 1,
+                    Some(values) if hash_vector(values) != vector.hash => {
+                        report.hash_mismatches.push(record.seq)
+                    }
+                    Some(_) => {}
+                }
+                db.insert(VectorEntry {
+                    id: Some(id.clone()),
+                    vector: vector.reconstruct(),
+                    metadata: metadata.clone(),
+                })
+                .map(|_| ())
+            }
+            Operation::Delete { id } => db.delete(id).map(|_| ()),
+        };
+        match result {
+            Ok(()) => report.applied += 1,
+            Err(e) => report.failed.push((record.seq, e.to_string())),
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(vector: Vec<f32>) -> VectorEntry {
+        VectorEntry {
+            id: None,
+            vector,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_capture_modes_and_jsonl() {
+        let log = OpLog::new(OpLogConfig {
+            capture: VectorCapture::Sampled { every: 2 },
+            capture_metadata: false,
+        });
+        log.record_insert("a", &entry(vec![1.0, 2.0]));
+        log.record_insert("b", &entry(vec![3.0, 4.0]));
+        log.record_delete("a");
+
+        let records = log.records();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[2].seq, 3);
+        match (&records[0].op, &records[1].op) {
+            (Operation::Insert { vector: a, .. }, Operation::Insert { vector: b, .. }) => {
+                assert_eq!(a.values, Some(vec![1.0, 2.0]));
+                assert_eq!(b.values, None);
+                assert_eq!(b.hash, hash_vector(&[3.0, 4.0]));
+            }
+            _ => panic!("expected inserts"),
+        }
+
+        let mut buffer = Vec::new();
+        log.write_jsonl(&mut buffer).unwrap();
+        let parsed = read_jsonl(buffer.as_slice()).unwrap();
+        assert_eq!(parsed, records);
+    }
+
+    #[test]
+    fn test_reconstruct_is_deterministic() {
+        let recorded = RecordedVector {
+            dimensions: 8,
+            hash: 42,
+            values: None,
+        };
+        let v = recorded.reconstruct();
+        assert_eq!(v.len(), 8);
+        assert_eq!(v, recorded.reconstruct());
+        assert!(v.iter().all(|x| (-1.0..1.0).contains(x)));
+    }
+}
@@ -0,0 +1,337 @@
+//! Differentially private exports
+//!
+//! [`VectorDB::export_private`](crate::VectorDB::export_private) releases a
+//! copy of the stored entries with calibrated Gaussian noise, for sharing a
+//! dataset outside the environment it was built in. Each exported record is
+//! `(epsilon, delta)`-differentially private with respect to replacing the
+//! source entry it came from:
+//!
+//! - the vector is clipped to L2 norm [`DpExportConfig::clip_norm`],
+//! - each field in [`DpExportConfig::numeric_fields`] is clipped to
+//!   `[-field_bound, field_bound]`; missing and non-numeric values count
+//!   as 0, so the presence of a field is not revealed,
+//! - Gaussian noise with standard deviation
+//!   `2 * sqrt(clip_norm² + fields * field_bound²) * sqrt(2 ln(1.25 / delta)) / epsilon`
+//!   is added to every component and field.
+//!
+//! Fields in [`DpExportConfig::keep_fields`] are copied unchanged and are
+//! **not** protected; all other metadata is dropped. Ids are replaced with
+//! positions in a shuffled order unless `keep_ids` is set, and the number
+//! of records is released exactly.
+//!
+//! Every export is charged to a persisted [`PrivacyLedger`]. Charges add up
+//! under basic composition, and once a [`PrivacyBudget`] is set exports
+//! that would exceed it fail with
+//! [`RuvectorError::PrivacyBudgetExceeded`].
+
+use crate::error::{Result, RuvectorError};
+use crate::types::VectorEntry;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_distr::{Distribution, Normal};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Noise and release settings for a private export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DpExportConfig {
+    /// Privacy loss per record, in `(0, 1)`
+    pub epsilon: f64,
+    /// Probability of exceeding `epsilon`, in `(0, 1)`
+    pub delta: f64,
+    /// L2 norm vectors are clipped to before noise is added
+    pub clip_norm: f32,
+    /// Numeric metadata fields released with noise
+    #[serde(default)]
+    pub numeric_fields: Vec<String>,
+    /// Magnitude numeric fields are clipped to
+    #[serde(default = "default_field_bound")]
+    pub field_bound: f64,
+    /// Metadata fields copied without protection
+    #[serde(default)]
+    pub keep_fields: Vec<String>,
+    /// Release the original ids instead of positions
+    #[serde(default)]
+    pub keep_ids: bool,
+    /// Seed for reproducible noise in tests; exports meant for release
+    /// must leave it unset, since a known seed lets the noise be removed
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+fn default_field_bound() -> f64 {
+    1.0
+}
+
+impl DpExportConfig {
+    /// Settings with the given privacy parameters and clip norm
+    pub fn new(epsilon: f64, delta: f64, clip_norm: f32) -> Self {
+        Self {
+            epsilon,
+            delta,
+            clip_norm,
+            numeric_fields: Vec::new(),
+            field_bound: default_field_bound(),
+            keep_fields: Vec::new(),
+            keep_ids: false,
+            seed: None,
+        }
+    }
+
+    /// Check the privacy parameters and bounds
+    pub fn validate(&self) -> Result<()> {
+        // The classic Gaussian mechanism bound only holds for epsilon < 1
+        if !(self.epsilon > 0.0 && self.epsilon < 1.0) {
+            return Err(RuvectorError::InvalidParameter(format!(
+                "epsilon must be in (0, 1), got {}",
+                self.epsilon
+            )));
+        }
+        if !(self.delta > 0.0 && self.delta < 1.0) {
+            return Err(RuvectorError::InvalidParameter(format!(
+                "delta must be in (0, 1), got {}",
+                self.delta
+            )));
+        }
+        if !(self.clip_norm.is_finite() && self.clip_norm > 0.0) {
+            return Err(RuvectorError::InvalidParameter(format!(
+                "clip_norm must be positive, got {}",
+                self.clip_norm
+            )));
+        }
+        let field_bound_valid = self.field_bound.is_finite() && self.field_bound > 0.0;
+        if !self.numeric_fields.is_empty() && !field_bound_valid {
+            return Err(RuvectorError::InvalidParameter(format!(
+                "field_bound must be positive, got {}",
+                self.field_bound
+            )));
+        }
+        if let Some(field) = self
+            .numeric_fields
+            .iter()
+            .find(|f| self.keep_fields.contains(f))
+        {
+            return Err(RuvectorError::InvalidParameter(format!(
+                "Field '{}' is both noised and kept",
+                field
+            )));
+        }
+        Ok(())
+    }
+
+    /// Standard deviation of the noise added to each component
+    pub fn noise_scale(&self) -> f64 {
+        let clip = self.clip_norm as f64;
+        let fields = self.numeric_fields.len() as f64;
+        let sensitivity = 2.0 * (clip * clip + fields * self.field_bound * self.field_bound).sqrt();
+        sensitivity * (2.0 * (1.25 / self.delta).ln()).sqrt() / self.epsilon
+    }
+}
+
+/// Privacy loss, or a limit on it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrivacyBudget {
+    /// Total epsilon
+    pub epsilon: f64,
+    /// Total delta
+    pub delta: f64,
+}
+
+/// One export recorded in the ledger
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrivacyCharge {
+    /// Epsilon spent
+    pub epsilon: f64,
+    /// Delta spent
+    pub delta: f64,
+    /// Records released
+    pub records: usize,
+    /// Standard deviation of the noise used
+    pub noise_scale: f64,
+    /// When the export ran, in Unix seconds
+    pub exported_at: i64,
+}
+
+/// Privacy loss charged against a database
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrivacyLedger {
+    /// Limit on the total loss; unlimited when `None`
+    pub budget: Option<PrivacyBudget>,
+    /// Every export, oldest first
+    pub charges: Vec<PrivacyCharge>,
+}
+
+impl PrivacyLedger {
+    /// Total loss under basic composition
+    pub fn spent(&self) -> PrivacyBudget {
+        self.charges
+            .iter()
+            .fold(PrivacyBudget::default(), |total, charge| PrivacyBudget {
+                epsilon: total.epsilon + charge.epsilon,
+                delta: total.delta + charge.delta,
+            })
+    }
+
+    /// Loss left before the budget is exhausted; `None` when unlimited
+    pub fn remaining(&self) -> Option<PrivacyBudget> {
+        let spent = self.spent();
+        self.budget.map(|budget| PrivacyBudget {
+            epsilon: (budget.epsilon - spent.epsilon).max(0.0),
+            delta: (budget.delta - spent.delta).max(0.0),
+        })
+    }
+
+    /// Refuse a charge the budget can't cover
+    pub(crate) fn check(&self, epsilon: f64, delta: f64) -> Result<()> {
+        let (Some(budget), Some(remaining)) = (self.budget, self.remaining()) else {
+            return Ok(());
+        };
+        // Tolerate rounding when a budget is spent in equal parts
+        let slack = 1e-9;
+        if epsilon > remaining.epsilon + slack * budget.epsilon
+            || delta > remaining.delta + slack * budget.delta
+        {
+            return Err(RuvectorError::PrivacyBudgetExceeded {
+                epsilon,
+                delta,
+                remaining_epsilon: remaining.epsilon,
+                remaining_delta: remaining.delta,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Records released by a private export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DpExport {
+    /// Noised records
+    pub entries: Vec<VectorEntry>,
+    /// What the export cost, as recorded in the ledger
+    pub charge: PrivacyCharge,
+}
+
+/// Noise `entries` according to `config`, which must be valid
+pub(crate) fn privatize(entries: Vec<VectorEntry>, config: &DpExportConfig) -> Vec<VectorEntry> {
+    let mut rng = match config.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let noise = Normal::new(0.0, config.noise_scale()).expect("noise scale is finite");
+
+    let mut released: Vec<VectorEntry> = entries
+        .into_iter()
+        .map(|entry| {
+            let norm = entry.vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+            let clip = if norm > config.clip_norm {
+                config.clip_norm / norm
+            } else {
+                1.0
+            };
+            let vector = entry
+                .vector
+                .iter()
+                .map(|&x| x * clip + noise.sample(&mut rng) as f32)
+                .collect();
+
+            let source = entry.metadata.unwrap_or_default();
+            let mut metadata = HashMap::new();
+            for field in &config.keep_fields {
+                if let Some(value) = source.get(field) {
+                    metadata.insert(field.clone(), value.clone());
+                }
+            }
+            for field in &config.numeric_fields {
+                let value = source
+                    .get(field)
+                    .and_then(serde_json::Value::as_f64)
+                    .unwrap_or(0.0)
+                    .clamp(-config.field_bound, config.field_bound);
+                let noised = value + noise.sample(&mut rng);
+                metadata.insert(field.clone(), serde_json::json!(noised));
+            }
+
+            VectorEntry {
+                id: entry.id.filter(|_| config.keep_ids),
+                vector,
+                metadata: (!metadata.is_empty()).then_some(metadata),
+            }
+        })
+        .collect();
+
+    if !config.keep_ids {
+        released.shuffle(&mut rng);
+        for (position, entry) in released.iter_mut().enumerate() {
+            entry.id = Some(position.to_string());
+        }
+    }
+    released
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_privatize_clips_and_drops() {
+        let mut metadata = HashMap::new();
+        metadata.insert("age".to_string(), serde_json::json!(1000));
+        metadata.insert("lang".to_string(), serde_json::json!("en"));
+        metadata.insert("email".to_string(), serde_json::json!("a@b.c"));
+        let entries = vec![VectorEntry {
+            id: Some("user-1".to_string()),
+            vector: vec![300.0, 400.0],
+            metadata: Some(metadata),
+        }];
+
+        let mut config = DpExportConfig::new(0.5, 1e-5, 1.0);
+        config.numeric_fields = vec!["age".to_string()];
+        config.field_bound = 100.0;
+        config.keep_fields = vec!["lang".to_string()];
+        config.seed = Some(7);
+        config.validate().unwrap();
+
+        let released = privatize(entries, &config);
+        let entry = &released[0];
+        assert_eq!(entry.id.as_deref(), Some("0"));
+        let metadata = entry.metadata.as_ref().unwrap();
+        assert_eq!(metadata["lang"], "en");
+        assert!(!metadata.contains_key("email"));
+        // Clipped to 100 before noise, so far below the raw 1000
+        let age = metadata["age"].as_f64().unwrap();
+        assert!((age - 100.0).abs() < 10.0 * config.noise_scale());
+        // Clipped to unit norm before noise
+        let scale = config.noise_scale() as f32;
+        assert!((entry.vector[0] - 0.6).abs() < 10.0 * scale);
+    }
+
+    #[test]
+    fn test_ledger_budget() {
+        let mut ledger = PrivacyLedger {
+            budget: Some(PrivacyBudget {
+                epsilon: 1.0,
+                delta: 1e-5,
+            }),
+            charges: Vec::new(),
+        };
+        for _ in 0..2 {
+            ledger.check(0.5, 5e-6).unwrap();
+            ledger.charges.push(PrivacyCharge {
+                epsilon: 0.5,
+                delta: 5e-6,
+                records: 10,
+                noise_scale: 1.0,
+                exported_at: 0,
+            });
+        }
+        assert!((ledger.spent().epsilon - 1.0).abs() < 1e-12);
+        assert!(matches!(
+            ledger.check(0.1, 1e-7),
+            Err(RuvectorError::PrivacyBudgetExceeded { .. })
+        ));
+
+        let bad = DpExportConfig::new(1.5, 1e-5, 1.0);
+        assert!(bad.validate().is_err());
+    }
+}
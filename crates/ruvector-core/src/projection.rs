@@ -0,0 +1,331 @@
+//! Dimensionality reduction for visualization
+//!
+//! Projects a sample of a collection to 2D or 3D so front-ends can render
+//! embedding maps without exporting every vector.
+//!
+//! - [`ProjectionMethod::Pca`]: principal components via power iteration
+//!   with deflation; deterministic and exact up to convergence.
+//! - [`ProjectionMethod::Umap`]: a lightweight UMAP approximation. It builds
+//!   an exact k-NN graph over the sample (quadratic, so keep samples in the
+//!   low thousands), initializes from PCA and runs the standard attractive /
+//!   negative-sampling SGD. Good enough to reveal clusters, not a faithful
+//!   reimplementation.
+
+use crate::distance::euclidean_distance;
+use crate::types::VectorId;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Projection algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProjectionMethod {
+    /// Principal component analysis
+    Pca,
+    /// Lightweight UMAP approximation
+    Umap,
+}
+
+/// Projection settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectionConfig {
+    /// Algorithm to use
+    pub method: ProjectionMethod,
+    /// Output dimensions (2 or 3)
+    pub output_dims: usize,
+    /// Maximum number of vectors projected; 0 projects all
+    pub sample_size: usize,
+    /// Neighbors per point in the UMAP graph
+    pub n_neighbors: usize,
+    /// UMAP optimization epochs
+    pub epochs: usize,
+    /// Seed for sampling and UMAP optimization
+    pub seed: u64,
+}
+
+impl Default for ProjectionConfig {
+    fn default() -> Self {
+        Self {
+            method: ProjectionMethod::Pca,
+            output_dims: 2,
+            sample_size: 2000,
+            n_neighbors: 15,
+            epochs: 200,
+            seed: 42,
+        }
+    }
+}
+
+/// A projected vector
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectedPoint {
+    /// Vector id
+    pub id: VectorId,
+    /// Low-dimensional coordinates
+    pub coords: Vec<f32>,
+}
+
+/// Project `data` with the configured method
+pub fn project(data: &[Vec<f32>], config: &ProjectionConfig) -> Vec<Vec<f32>> {
+    match config.method {
+        ProjectionMethod::Pca => pca(data, config.output_dims),
+        ProjectionMethod::Umap => umap_lite(data, config),
+    }
+}
+
+/// Project onto the top `output_dims` principal components
+pub fn pca(data: &[Vec<f32>], output_dims: usize) -> Vec<Vec<f32>> {
+    const ITERATIONS: usize = 100;
+
+    let n = data.len();
+    let d = data.first().map_or(0, Vec::len);
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut mean = vec![0.0f64; d];
+    for row in data {
+        for (m, &x) in mean.iter_mut().zip(row) {
+            *m += x as f64 / n as f64;
+        }
+    }
+    let centered: Vec<Vec<f64>> = data
+        .iter()
+        .map(|row| row.iter().zip(&mean).map(|(&x, m)| x as f64 - m).collect())
+        .collect();
+
+    let mut components: Vec<Vec<f64>> = Vec::with_capacity(output_dims);
+    for c in 0..output_dims.min(d) {
+        // Deterministic start that is unlikely to be orthogonal to any component
+        let mut v: Vec<f64> = (0..d).map(|i| 1.0 / (1.0 + ((i + c) % d) as f64)).collect();
+        orthonormalize(&mut v, &components);
+
+        for _ in 0..ITERATIONS {
+            // w = Xᵀ(Xv)
+            let mut w = vec![0.0f64; d];
+            for row in &centered {
+                let proj: f64 = row.iter().zip(&v).map(|(a, b)| a * b).sum();
+                for (wi, &ri) in w.iter_mut().zip(row) {
+                    *wi += proj * ri;
+                }
+            }
+            if !orthonormalize(&mut w, &components) {
+                break;
+            }
+            let delta: f64 = w.iter().zip(&v).map(|(a, b)| (a - b).abs()).sum();
+            v = w;
+            if delta < 1e-9 {
+                break;
+            }
+        }
+        components.push(v);
+    }
+
+    centered
+        .iter()
+        .map(|row| {
+            let mut coords: Vec<f32> = components
+                .iter()
+                .map(|u| row.iter().zip(u).map(|(a, b)| a * b).sum::<f64>() as f32)
+                .collect();
+            coords.resize(output_dims, 0.0);
+            coords
+        })
+        .collect()
+}
+
+/// Remove projections onto `basis` and normalize; false if nothing is left
+fn orthonormalize(v: &mut [f64], basis: &[Vec<f64>]) -> bool {
+    for u in basis {
+        let dot: f64 = v.iter().zip(u).map(|(a, b)| a * b).sum();
+        for (vi, ui) in v.iter_mut().zip(u) {
+            *vi -= dot * ui;
+        }
+    }
+    let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+    if norm < 1e-12 {
+        v.iter_mut().for_each(|x| *x = 0.0);
+        return false;
+    }
+    v.iter_mut().for_each(|x| *x /= norm);
+    true
+}
+
+/// Lightweight UMAP approximation
+pub fn umap_lite(data: &[Vec<f32>], config: &ProjectionConfig) -> Vec<Vec<f32>> {
+    // Curve parameters for min_dist = 0.1, spread = 1.0
+    const A: f32 = 1.577;
+    const B: f32 = 0.895;
+    const NEGATIVE_SAMPLES: usize = 5;
+    const CLIP: f32 = 4.0;
+
+    let n = data.len();
+    let k = config.n_neighbors.min(n.saturating_sub(1));
+    let dims = config.output_dims;
+    let mut embedding = pca(data, dims);
+    if n < 3 || k == 0 {
+        return embedding;
+    }
+
+    // Scale the PCA layout into roughly [-10, 10]
+    let extent = embedding
+        .iter()
+        .flatten()
+        .fold(0.0f32, |m, &x| m.max(x.abs()))
+        .max(1e-6);
+    embedding
+        .iter_mut()
+        .flatten()
+        .for_each(|x| *x *= 10.0 / extent);
+
+    let edges = fuzzy_graph(data, k);
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let epochs = config.epochs.max(1);
+
+    for epoch in 0..epochs {
+        let lr = 1.0 - epoch as f32 / epochs as f32;
+        for &(i, j, weight) in &edges {
+            if rng.gen::<f32>() > weight {
+                continue;
+            }
+
+            let d2 = squared_distance(&embedding[i], &embedding[j]);
+            if d2 > 0.0 {
+                let coef = -2.0 * A * B * d2.powf(B - 1.0) / (1.0 + A * d2.powf(B));
+                let (a, b) = pair_mut(&mut embedding, i, j);
+                for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+                    let g = (coef * (*x - *y)).clamp(-CLIP, CLIP);
+                    *x += g * lr;
+                    *y -= g * lr;
+                }
+            }
+
+            for _ in 0..NEGATIVE_SAMPLES {
+                let other = rng.gen_range(0..n);
+                if other == i {
+                    continue;
+                }
+                let d2 = squared_distance(&embedding[i], &embedding[other]);
+                let coef = 2.0 * B / ((0.001 + d2) * (1.0 + A * d2.powf(B)));
+                let (a, b) = pair_mut(&mut embedding, i, other);
+                for (x, y) in a.iter_mut().zip(b.iter()) {
+                    let g = (coef * (*x - *y)).clamp(-CLIP, CLIP);
+                    *x += g * lr;
+                }
+            }
+        }
+    }
+
+    embedding
+}
+
+/// Symmetrized fuzzy k-NN graph as `(i, j, weight)` with `i < j`
+fn fuzzy_graph(data: &[Vec<f32>], k: usize) -> Vec<(usize, usize, f32)> {
+    use std::collections::HashMap;
+
+    let n = data.len();
+    let mut directed: HashMap<(usize, usize), f32> = HashMap::new();
+
+    for i in 0..n {
+        let mut neighbors: Vec<(usize, f32)> = (0..n)
+            .filter(|&j| j != i)
+            .map(|j| (j, euclidean_distance(&data[i], &data[j])))
+            .collect();
+        neighbors.sort_by(|a, b| a.1.total_cmp(&b.1));
+        neighbors.truncate(k);
+
+        let rho = neighbors[0].1;
+        let sigma = (neighbors.iter().map(|(_, d)| d - rho).sum::<f32>() / k as f32).max(1e-3);
+        for (j, d) in neighbors {
+            directed.insert((i, j), (-(d - rho) / sigma).exp());
+        }
+    }
+
+    let mut edges = Vec::new();
+    for (&(i, j), &w) in &directed {
+        if i < j {
+            let back = directed.get(&(j, i)).copied().unwrap_or(0.0);
+            edges.push((i, j, w + back - w * back));
+        } else if !directed.contains_key(&(j, i)) {
+            edges.push((j, i, w));
+        }
+    }
+    edges.sort_by_key(|&(i, j, _)| (i, j));
+    edges
+}
+
+/// Mutable references to two distinct rows
+fn pair_mut<T>(rows: &mut [T], i: usize, j: usize) -> (&mut T, &mut T) {
+    if i < j {
+        let (lo, hi) = rows.split_at_mut(j);
+        (&mut lo[i], &mut hi[0])
+    } else {
+        let (lo, hi) = rows.split_at_mut(i);
+        (&mut hi[0], &mut lo[j])
+    }
+}
+
+fn squared_distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pca_recovers_main_axis() {
+        // Points spread along x with a little noise along y
+        let data: Vec<Vec<f32>> = (0..20)
+            .map(|i| vec![i as f32, (i % 2) as f32 * 0.1, 0.0])
+            .collect();
+        let projected = pca(&data, 2);
+
+        assert_eq!(projected.len(), 20);
+        let spread = |c: usize| {
+            let (lo, hi) = projected.iter().fold((f32::MAX, f32::MIN), |(lo, hi), p| {
+                (lo.min(p[c]), hi.max(p[c]))
+            });
+            hi - lo
+        };
+        assert!((spread(0) - 19.0).abs() < 1e-2);
+        assert!(spread(1) < 0.2);
+    }
+
+    #[test]
+    fn test_pca_pads_low_dimensional_input() {
+        let projected = pca(&[vec![1.0], vec![3.0]], 3);
+        assert_eq!(projected[0].len(), 3);
+        assert_eq!(projected[0][2], 0.0);
+    }
+
+    #[test]
+    fn test_umap_separates_clusters() {
+        let mut data = Vec::new();
+        for i in 0..15 {
+            let jitter = i as f32 * 0.01;
+            data.push(vec![jitter, 0.0, 0.0, 0.0]);
+            data.push(vec![10.0 + jitter, 10.0, 10.0, 10.0]);
+        }
+        let config = ProjectionConfig {
+            method: ProjectionMethod::Umap,
+            n_neighbors: 5,
+            epochs: 50,
+            ..Default::default()
+        };
+        let projected = project(&data, &config);
+
+        let centroid = |parity: usize| {
+            let points: Vec<&Vec<f32>> = projected.iter().skip(parity).step_by(2).collect();
+            let mut c = vec![0.0f32; 2];
+            for p in &points {
+                c[0] += p[0] / points.len() as f32;
+                c[1] += p[1] / points.len() as f32;
+            }
+            c
+        };
+        let (a, b) = (centroid(0), centroid(1));
+        assert!(squared_distance(&a, &b) > 1.0);
+        assert!(projected.iter().flatten().all(|x| x.is_finite()));
+    }
+}
@@ -1,6 +1,7 @@
 //! Quantization techniques for memory compression
 
 use crate::error::Result;
+use crate::types::DistanceMetric;
 use serde::{Deserialize, Serialize};
 
 /// Trait for quantized vector representations
@@ -76,6 +77,38 @@ impl QuantizedVector for ScalarQuantized {
     }
 }
 
+impl ScalarQuantized {
+    /// Distance from a full-precision query to these codes
+    ///
+    /// Dequantizes one component at a time instead of materializing the
+    /// reconstructed vector, so the query keeps full precision and nothing
+    /// is allocated per candidate.
+    pub fn asymmetric_distance(&self, query: &[f32], metric: DistanceMetric) -> f32 {
+        let values = self.data.iter().map(|&v| self.min + v as f32 * self.scale);
+        match metric {
+            DistanceMetric::Euclidean => query
+                .iter()
+                .zip(values)
+                .map(|(q, x)| (q - x) * (q - x))
+                .sum::<f32>()
+                .sqrt(),
+            DistanceMetric::Manhattan => query.iter().zip(values).map(|(q, x)| (q - x).abs()).sum(),
+            DistanceMetric::DotProduct => {
+                -query.iter().zip(values).map(|(q, x)| q * x).sum::<f32>()
+            }
+            DistanceMetric::Cosine => {
+                let (mut dot, mut q_norm, mut x_norm) = (0.0f32, 0.0f32, 0.0f32);
+                for (q, x) in query.iter().zip(values) {
+                    dot += q * x;
+                    q_norm += q * q;
+                    x_norm += x * x;
+                }
+                cosine_from_parts(dot, q_norm.sqrt(), x_norm.sqrt())
+            }
+        }
+    }
+}
+
 /// Product quantization (8-16x compression)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProductQuantized {
@@ -162,6 +195,85 @@ impl ProductQuantized {
 
         codes
     }
+
+    /// Norm of the vector reconstructed from `codes`
+    ///
+    /// Cosine lookups need it; compute it once at encode time.
+    pub fn code_norm(&self, codes: &[u8]) -> f32 {
+        codes
+            .iter()
+            .zip(&self.codebooks)
+            .map(|(&code, codebook)| codebook[code as usize].iter().map(|x| x * x).sum::<f32>())
+            .sum::<f32>()
+            .sqrt()
+    }
+
+    /// Precompute per-subspace distances from `query` to every centroid
+    ///
+    /// Scoring a candidate is then one table lookup per subspace.
+    pub fn distance_table(&self, query: &[f32], metric: DistanceMetric) -> DistanceTable {
+        let num_subspaces = self.codebooks.len();
+        let subspace_dim = query.len() / num_subspaces;
+
+        let tables = self
+            .codebooks
+            .iter()
+            .enumerate()
+            .map(|(subspace_idx, codebook)| {
+                let start = subspace_idx * subspace_dim;
+                let subquery = &query[start..start + subspace_dim];
+                codebook
+                    .iter()
+                    .map(|centroid| match metric {
+                        DistanceMetric::Euclidean => euclidean_squared(subquery, centroid),
+                        DistanceMetric::Manhattan => subquery
+                            .iter()
+                            .zip(centroid)
+                            .map(|(q, c)| (q - c).abs())
+                            .sum(),
+                        DistanceMetric::Cosine | DistanceMetric::DotProduct => {
+                            subquery.iter().zip(centroid).map(|(q, c)| q * c).sum()
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        DistanceTable {
+            tables,
+            metric,
+            query_norm: query.iter().map(|x| x * x).sum::<f32>().sqrt(),
+        }
+    }
+}
+
+/// Per-query lookup tables for asymmetric product-quantization distances
+#[derive(Debug, Clone)]
+pub struct DistanceTable {
+    /// `tables[subspace][code]`: partial distance (or dot product)
+    tables: Vec<Vec<f32>>,
+    metric: DistanceMetric,
+    query_norm: f32,
+}
+
+impl DistanceTable {
+    /// Distance from the query to the vector encoded as `codes`
+    ///
+    /// `code_norm` is only used for cosine; see [`ProductQuantized::code_norm`].
+    #[inline]
+    pub fn distance(&self, codes: &[u8], code_norm: f32) -> f32 {
+        let sum: f32 = codes
+            .iter()
+            .zip(&self.tables)
+            .map(|(&code, table)| table[code as usize])
+            .sum();
+        match self.metric {
+            DistanceMetric::Euclidean => sum.sqrt(),
+            DistanceMetric::Manhattan => sum,
+            DistanceMetric::DotProduct => -sum,
+            DistanceMetric::Cosine => cosine_from_parts(sum, self.query_norm, code_norm),
+        }
+    }
 }
 
 /// Binary quantization (32x compression)
@@ -217,6 +329,15 @@ impl QuantizedVector for BinaryQuantized {
 
 // Helper functions
 
+/// Cosine distance from a dot product and the two norms
+fn cosine_from_parts(dot: f32, norm_a: f32, norm_b: f32) -> f32 {
+    if norm_a > 1e-8 && norm_b > 1e-8 {
+        1.0 - dot / (norm_a * norm_b)
+    } else {
+        1.0
+    }
+}
+
 fn euclidean_squared(a: &[f32], b: &[f32]) -> f32 {
     a.iter()
         .zip(b)
@@ -427,4 +548,61 @@ mod tests {
             dist_ab, dist_ba
         );
     }
+
+    #[test]
+    fn test_scalar_asymmetric_distance() {
+        let vector = vec![0.5, -1.0, 2.0, 0.25];
+        let query = vec![1.0, 0.0, 1.5, -0.5];
+        let quantized = ScalarQuantized::quantize(&vector);
+
+        for metric in [
+            DistanceMetric::Euclidean,
+            DistanceMetric::Cosine,
+            DistanceMetric::DotProduct,
+            DistanceMetric::Manhattan,
+        ] {
+            let exact = crate::distance::distance(&query, &vector, metric).unwrap();
+            let approx = quantized.asymmetric_distance(&query, metric);
+            assert!(
+                (exact - approx).abs() < 0.05,
+                "{:?}: {} vs {}",
+                metric,
+                exact,
+                approx
+            );
+        }
+    }
+
+    #[test]
+    fn test_product_distance_table_matches_reconstruction() {
+        let vectors: Vec<Vec<f32>> = (0..64)
+            .map(|i| {
+                (0..8)
+                    .map(|j| ((i * 7 + j * 3) % 11) as f32 / 11.0)
+                    .collect()
+            })
+            .collect();
+        let pq = ProductQuantized::train(&vectors, 4, 16, 5).unwrap();
+        let query = vec![0.3, 0.1, 0.9, 0.4, 0.2, 0.8, 0.5, 0.6];
+
+        for metric in [
+            DistanceMetric::Euclidean,
+            DistanceMetric::Cosine,
+            DistanceMetric::DotProduct,
+            DistanceMetric::Manhattan,
+        ] {
+            let table = pq.distance_table(&query, metric);
+            for vector in vectors.iter().take(8) {
+                let codes = pq.encode(vector);
+                let reconstructed: Vec<f32> = codes
+                    .iter()
+                    .zip(&pq.codebooks)
+                    .flat_map(|(&c, codebook)| codebook[c as usize].clone())
+                    .collect();
+                let expected = crate::distance::distance(&query, &reconstructed, metric).unwrap();
+                let actual = table.distance(&codes, pq.code_norm(&codes));
+                assert!((expected - actual).abs() < 1e-4, "{:?}", metric);
+            }
+        }
+    }
 }
@@ -0,0 +1,199 @@
+//! Named query templates
+//!
+//! A template fixes `k`, a metadata filter and optional fusion settings under
+//! a name. Callers then send only the query vector and the filter parameters,
+//! and the registered templates document exactly how each named query
+//! behaves.
+//!
+//! Filter values of the form `{"$param": "name"}` are placeholders filled
+//! from the call's parameters:
+//!
+//! ```json
+//! {"k": 5, "filter": {"tenant": {"$param": "tenant"}, "status": "published"}}
+//! ```
+
+use crate::advanced_features::MMRConfig;
+use crate::error::{Result, RuvectorError};
+use crate::query_vector::QueryVector;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+
+/// Key marking a placeholder object in a filter template
+pub const PARAM_KEY: &str = "$param";
+
+/// How results or query vectors are combined before returning
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FusionSettings {
+    /// Rerank candidates for diversity with maximal marginal relevance
+    Mmr(MMRConfig),
+    /// Blend the caller's vector with a stored expression:
+    /// `(1 - weight) * query + weight * anchor`
+    Blend {
+        /// Expression the query is pulled towards
+        anchor: QueryVector,
+        /// Share of the anchor in `[0, 1]`
+        weight: f32,
+    },
+}
+
+/// A registered, parameterized query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryTemplate {
+    /// Number of results
+    pub k: usize,
+    /// Metadata filter, possibly containing placeholders
+    #[serde(default)]
+    pub filter: HashMap<String, Value>,
+    /// Optional HNSW search width
+    #[serde(default)]
+    pub ef_search: Option<usize>,
+    /// Optional fusion settings
+    #[serde(default)]
+    pub fusion: Option<FusionSettings>,
+}
+
+impl QueryTemplate {
+    /// Template returning `k` results without filtering
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            filter: HashMap::new(),
+            ef_search: None,
+            fusion: None,
+        }
+    }
+
+    /// Require `key` to equal a fixed value
+    pub fn with_filter(mut self, key: impl Into<String>, value: Value) -> Self {
+        self.filter.insert(key.into(), value);
+        self
+    }
+
+    /// Require `key` to equal the call parameter `param`
+    pub fn with_param(mut self, key: impl Into<String>, param: &str) -> Self {
+        let mut placeholder = serde_json::Map::new();
+        placeholder.insert(PARAM_KEY.to_string(), Value::String(param.to_string()));
+        self.filter.insert(key.into(), Value::Object(placeholder));
+        self
+    }
+
+    /// Set fusion settings
+    pub fn with_fusion(mut self, fusion: FusionSettings) -> Self {
+        self.fusion = Some(fusion);
+        self
+    }
+
+    /// Names of the parameters the template expects
+    pub fn parameters(&self) -> BTreeSet<&str> {
+        self.filter.values().filter_map(placeholder).collect()
+    }
+
+    /// Check the template is usable
+    pub fn validate(&self) -> Result<()> {
+        if self.k == 0 {
+            return Err(RuvectorError::InvalidParameter(
+                "Template k must be greater than zero".to_string(),
+            ));
+        }
+        match &self.fusion {
+            Some(FusionSettings::Mmr(config)) if !(0.0..=1.0).contains(&config.lambda) => {
+                Err(RuvectorError::InvalidParameter(format!(
+                    "MMR lambda must be in [0, 1], got {}",
+                    config.lambda
+                )))
+            }
+            Some(FusionSettings::Blend { weight, .. }) if !(0.0..=1.0).contains(weight) => {
+                Err(RuvectorError::InvalidParameter(format!(
+                    "Blend weight must be in [0, 1], got {}",
+                    weight
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Fill placeholders from `params`, returning the concrete filter
+    ///
+    /// Fails if a parameter is missing or an unknown parameter is passed.
+    pub fn bind_filter(
+        &self,
+        params: &HashMap<String, Value>,
+    ) -> Result<Option<HashMap<String, Value>>> {
+        let expected = self.parameters();
+        if let Some(unknown) = params.keys().find(|p| !expected.contains(p.as_str())) {
+            return Err(RuvectorError::InvalidInput(format!(
+                "Unknown template parameter: {}",
+                unknown
+            )));
+        }
+
+        if self.filter.is_empty() {
+            return Ok(None);
+        }
+
+        let mut bound = HashMap::with_capacity(self.filter.len());
+        for (key, value) in &self.filter {
+            let value = match placeholder(value) {
+                Some(param) => params.get(param).cloned().ok_or_else(|| {
+                    RuvectorError::InvalidInput(format!("Missing template parameter: {}", param))
+                })?,
+                None => value.clone(),
+            };
+            bound.insert(key.clone(), value);
+        }
+        Ok(Some(bound))
+    }
+}
+
+fn placeholder(value: &Value) -> Option<&str> {
+    match value {
+        Value::Object(map) if map.len() == 1 => map.get(PARAM_KEY)?.as_str(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_bind_filter() {
+        let template = QueryTemplate::new(5)
+            .with_filter("status", json!("published"))
+            .with_param("tenant", "tenant_id");
+        assert_eq!(template.parameters(), BTreeSet::from(["tenant_id"]));
+
+        let params = HashMap::from([("tenant_id".to_string(), json!("acme"))]);
+        let filter = template.bind_filter(&params).unwrap().unwrap();
+        assert_eq!(filter["tenant"], json!("acme"));
+        assert_eq!(filter["status"], json!("published"));
+
+        assert!(template.bind_filter(&HashMap::new()).is_err());
+        let extra = HashMap::from([
+            ("tenant_id".to_string(), json!("acme")),
+            ("other".to_string(), json!(1)),
+        ]);
+        assert!(template.bind_filter(&extra).is_err());
+    }
+
+    #[test]
+    fn test_template_from_json() {
+        let template: QueryTemplate = serde_json::from_value(json!({
+            "k": 3,
+            "filter": {"lang": {"$param": "lang"}},
+            "fusion": {"blend": {"anchor": {"terms": [{"id": "a"}]}, "weight": 0.25}}
+        }))
+        .unwrap();
+        assert!(template.validate().is_ok());
+        assert_eq!(template.parameters(), BTreeSet::from(["lang"]));
+
+        let invalid = QueryTemplate::new(3).with_fusion(FusionSettings::Blend {
+            anchor: QueryVector::from_id("a"),
+            weight: 2.0,
+        });
+        assert!(invalid.validate().is_err());
+    }
+}
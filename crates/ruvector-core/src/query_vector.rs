@@ -0,0 +1,222 @@
+//! Composable query vectors
+//!
+//! Builds a query from weighted stored ids and raw vectors, so analogy
+//! queries (`king - man + woman`) and centroid queries can be expressed
+//! without fetching vectors to the client first.
+//!
+//! ```rust,ignore
+//! let query = QueryVector::from_id("king").subtract("man").add("woman");
+//! let results = db.search_expression(&query, 10, None)?;
+//! ```
+
+use crate::error::{Result, RuvectorError};
+use crate::types::VectorId;
+use serde::{Deserialize, Serialize};
+
+/// Where a term's vector comes from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorSource {
+    /// A stored vector, looked up by id
+    Id(VectorId),
+    /// A literal vector
+    Vector(Vec<f32>),
+}
+
+impl From<&str> for VectorSource {
+    fn from(id: &str) -> Self {
+        VectorSource::Id(id.to_string())
+    }
+}
+
+impl From<String> for VectorSource {
+    fn from(id: String) -> Self {
+        VectorSource::Id(id)
+    }
+}
+
+impl From<Vec<f32>> for VectorSource {
+    fn from(vector: Vec<f32>) -> Self {
+        VectorSource::Vector(vector)
+    }
+}
+
+/// A source scaled by a weight
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeightedTerm {
+    /// Vector source
+    #[serde(flatten)]
+    pub source: VectorSource,
+    /// Weight applied to the vector (negative to subtract)
+    #[serde(default = "default_weight")]
+    pub weight: f32,
+}
+
+fn default_weight() -> f32 {
+    1.0
+}
+
+/// Weighted sum of vectors, resolved against a database at search time
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QueryVector {
+    /// Terms of the sum
+    pub terms: Vec<WeightedTerm>,
+    /// L2-normalize the result
+    #[serde(default)]
+    pub normalize: bool,
+}
+
+impl QueryVector {
+    /// Create an empty expression
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from a stored vector
+    pub fn from_id(id: impl Into<VectorId>) -> Self {
+        Self::new().add(VectorSource::Id(id.into()))
+    }
+
+    /// Start from a literal vector
+    pub fn from_vector(vector: Vec<f32>) -> Self {
+        Self::new().add(vector)
+    }
+
+    /// Mean of several sources
+    pub fn average<S: Into<VectorSource>>(sources: impl IntoIterator<Item = S>) -> Self {
+        let sources: Vec<VectorSource> = sources.into_iter().map(Into::into).collect();
+        let weight = 1.0 / sources.len().max(1) as f32;
+        sources
+            .into_iter()
+            .fold(Self::new(), |q, source| q.weighted(source, weight))
+    }
+
+    /// Add a source
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, source: impl Into<VectorSource>) -> Self {
+        self.weighted(source, 1.0)
+    }
+
+    /// Subtract a source
+    pub fn subtract(self, source: impl Into<VectorSource>) -> Self {
+        self.weighted(source, -1.0)
+    }
+
+    /// Add a source scaled by `weight`
+    pub fn weighted(mut self, source: impl Into<VectorSource>, weight: f32) -> Self {
+        self.terms.push(WeightedTerm {
+            source: source.into(),
+            weight,
+        });
+        self
+    }
+
+    /// Set whether the result is L2-normalized
+    pub fn normalized(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Ids referenced by the expression
+    pub fn ids(&self) -> Vec<&VectorId> {
+        self.terms
+            .iter()
+            .filter_map(|t| match &t.source {
+                VectorSource::Id(id) => Some(id),
+                VectorSource::Vector(_) => None,
+            })
+            .collect()
+    }
+
+    /// Evaluate the expression, looking stored ids up with `lookup`
+    pub fn resolve<F>(&self, dimensions: usize, mut lookup: F) -> Result<Vec<f32>>
+    where
+        F: FnMut(&str) -> Result<Option<Vec<f32>>>,
+    {
+        if self.terms.is_empty() {
+            return Err(RuvectorError::InvalidInput(
+                "Query expression has no terms".to_string(),
+            ));
+        }
+
+        let mut result = vec![0.0f32; dimensions];
+        for term in &self.terms {
+            let looked_up;
+            let vector = match &term.source {
+                VectorSource::Id(id) => {
+                    looked_up =
+                        lookup(id)?.ok_or_else(|| RuvectorError::VectorNotFound(id.clone()))?;
+                    &looked_up
+                }
+                VectorSource::Vector(v) => v,
+            };
+            if vector.len() != dimensions {
+                return Err(RuvectorError::DimensionMismatch {
+                    expected: dimensions,
+                    actual: vector.len(),
+                });
+            }
+            for (r, x) in result.iter_mut().zip(vector) {
+                *r += term.weight * x;
+            }
+        }
+
+        if self.normalize {
+            let norm = result.iter().map(|x| x * x).sum::<f32>().sqrt();
+            if norm > 1e-12 {
+                result.iter_mut().for_each(|x| *x /= norm);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn store() -> HashMap<&'static str, Vec<f32>> {
+        HashMap::from([
+            ("king", vec![1.0, 1.0]),
+            ("man", vec![1.0, 0.0]),
+            ("woman", vec![0.0, 2.0]),
+        ])
+    }
+
+    fn lookup(id: &str) -> Result<Option<Vec<f32>>> {
+        Ok(store().get(id).cloned())
+    }
+
+    #[test]
+    fn test_analogy() {
+        let q = QueryVector::from_id("king").subtract("man").add("woman");
+        assert_eq!(q.resolve(2, lookup).unwrap(), vec![0.0, 3.0]);
+        assert_eq!(q.ids().len(), 3);
+
+        let unit = q.normalized(true).resolve(2, lookup).unwrap();
+        assert_eq!(unit, vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_average_and_errors() {
+        let q = QueryVector::average(["man", "woman"]);
+        assert_eq!(q.resolve(2, lookup).unwrap(), vec![0.5, 1.0]);
+
+        assert!(QueryVector::from_id("queen").resolve(2, lookup).is_err());
+        assert!(QueryVector::from_vector(vec![1.0])
+            .resolve(2, lookup)
+            .is_err());
+        assert!(QueryVector::new().resolve(2, lookup).is_err());
+    }
+
+    #[test]
+    fn test_json_form() {
+        let q: QueryVector = serde_json::from_str(
+            r#"{"terms": [{"id": "king"}, {"id": "man", "weight": -1.0}, {"vector": [0.0, 1.0]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(q.resolve(2, lookup).unwrap(), vec![0.0, 2.0]);
+    }
+}
@@ -0,0 +1,200 @@
+//! Time-decayed relevance scoring
+//!
+//! Agent memories and feeds usually want recent entries to outrank slightly
+//! closer but stale ones. A [`TimeDecay`] reads a timestamp from each
+//! candidate's metadata and blends an exponential recency factor with the
+//! similarity in the same pass that ranks the results:
+//!
+//! ```text
+//! recency = 0.5 ^ (age / half_life)
+//! score   = (1 - weight) * similarity + weight * recency
+//! ```
+//!
+//! Timestamps may be numbers (in [`TimeUnit`]s since the Unix epoch) or
+//! RFC 3339 strings. Candidates without a readable timestamp get a recency
+//! of 0; timestamps in the future count as age 0.
+
+use crate::boost::{similarity, BoostedResult};
+use crate::error::{Result, RuvectorError};
+use crate::types::{DistanceMetric, SearchResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Unit of numeric timestamps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeUnit {
+    /// Seconds since the epoch
+    Seconds,
+    /// Milliseconds since the epoch, as from `Date.now()`
+    #[default]
+    Millis,
+}
+
+/// Exponential recency decay over a metadata timestamp
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeDecay {
+    /// Metadata field holding the timestamp
+    pub field: String,
+    /// Age at which the recency factor halves, in seconds
+    pub half_life_secs: f64,
+    /// Share of recency in the final score, in `[0, 1]`
+    pub weight: f32,
+    /// Unit of numeric timestamps
+    #[serde(default)]
+    pub unit: TimeUnit,
+    /// Reference time in milliseconds since the epoch; the current time if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub now_ms: Option<i64>,
+    /// Candidates fetched per requested result before rescoring
+    #[serde(default = "default_oversample")]
+    pub oversample: usize,
+}
+
+fn default_oversample() -> usize {
+    crate::boost::DEFAULT_OVERSAMPLE
+}
+
+impl TimeDecay {
+    /// Decay over `field` with the given half-life and recency weight
+    pub fn new(field: impl Into<String>, half_life_secs: f64, weight: f32) -> Self {
+        Self {
+            field: field.into(),
+            half_life_secs,
+            weight,
+            unit: TimeUnit::default(),
+            now_ms: None,
+            oversample: default_oversample(),
+        }
+    }
+
+    /// Check the half-life, weight and oversampling factor
+    pub fn validate(&self) -> Result<()> {
+        if !(self.half_life_secs.is_finite() && self.half_life_secs > 0.0) {
+            return Err(RuvectorError::InvalidParameter(format!(
+                "Half-life must be positive, got {}",
+                self.half_life_secs
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.weight) {
+            return Err(RuvectorError::InvalidParameter(format!(
+                "Recency weight must be in [0, 1], got {}",
+                self.weight
+            )));
+        }
+        if self.oversample == 0 {
+            return Err(RuvectorError::InvalidParameter(
+                "Time decay oversample must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Recency factor in `[0, 1]` of an entry with `metadata` at `now_ms`
+    pub fn recency(&self, metadata: Option<&HashMap<String, Value>>, now_ms: i64) -> f32 {
+        let Some(timestamp_ms) = metadata
+            .and_then(|m| m.get(&self.field))
+            .and_then(|v| timestamp_ms(v, self.unit))
+        else {
+            return 0.0;
+        };
+        let age_secs = (now_ms - timestamp_ms).max(0) as f64 / 1000.0;
+        0.5f64.powf(age_secs / self.half_life_secs) as f32
+    }
+
+    /// Blend `similarity` with the recency factor
+    pub fn score(&self, similarity: f32, recency: f32) -> f32 {
+        (1.0 - self.weight) * similarity + self.weight * recency
+    }
+
+    /// Rescore `candidates` measured with `metric` and keep the best `k`
+    pub fn rank(
+        &self,
+        candidates: Vec<SearchResult>,
+        metric: DistanceMetric,
+        k: usize,
+    ) -> Vec<BoostedResult> {
+        let now_ms = self
+            .now_ms
+            .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+        let mut ranked: Vec<BoostedResult> = candidates
+            .into_iter()
+            .map(|result| {
+                let similarity = similarity(metric, result.score);
+                let recency = self.recency(result.metadata.as_ref(), now_ms);
+                BoostedResult {
+                    score: self.score(similarity, recency),
+                    similarity,
+                    result,
+                }
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+        ranked.truncate(k);
+        ranked
+    }
+}
+
+/// Milliseconds since the epoch of a metadata timestamp
+///
+/// Numbers are read in `unit`; strings must be RFC 3339.
+pub fn timestamp_ms(value: &Value, unit: TimeUnit) -> Option<i64> {
+    match value {
+        Value::Number(n) => {
+            let t = n.as_f64()?;
+            Some(match unit {
+                TimeUnit::Seconds => (t * 1000.0) as i64,
+                TimeUnit::Millis => t as i64,
+            })
+        }
+        Value::String(s) => chrono::DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|t| t.timestamp_millis()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+    fn candidate(id: &str, distance: f32, created: Value) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            score: distance,
+            vector: None,
+            metadata: Some(HashMap::from([("created".to_string(), created)])),
+        }
+    }
+
+    #[test]
+    fn test_recency_decay() {
+        let now_ms = 100 * DAY_MS;
+        let mut decay = TimeDecay::new("created", 24.0 * 60.0 * 60.0, 0.5);
+        decay.now_ms = Some(now_ms);
+        decay.validate().unwrap();
+
+        let fresh = HashMap::from([("created".to_string(), Value::from(now_ms))]);
+        let day_old = HashMap::from([("created".to_string(), Value::from(now_ms - DAY_MS))]);
+        assert_eq!(decay.recency(Some(&fresh), now_ms), 1.0);
+        assert!((decay.recency(Some(&day_old), now_ms) - 0.5).abs() < 1e-6);
+        assert_eq!(decay.recency(None, now_ms), 0.0);
+
+        let ranked = decay.rank(
+            vec![
+                candidate("stale", 0.0, Value::from(now_ms - 10 * DAY_MS)),
+                candidate("fresh", 0.2, Value::from("1970-04-11T00:00:00Z")),
+            ],
+            DistanceMetric::Cosine,
+            2,
+        );
+        assert_eq!(ranked[0].result.id, "fresh");
+        assert!((ranked[0].score - 0.9).abs() < 1e-6);
+
+        decay.weight = 1.5;
+        assert!(decay.validate().is_err());
+    }
+}
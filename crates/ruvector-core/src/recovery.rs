@@ -0,0 +1,51 @@
+//! Salvaging a partially corrupted database
+//!
+//! [`VectorDB::recover`](crate::VectorDB::recover) reads whatever it can from
+//! a damaged storage file and writes it into a fresh database, instead of
+//! refusing to open. Rows that fail to read or decode are skipped and
+//! counted; the source file is never modified.
+//!
+//! Recovered alongside the vectors:
+//!
+//! - metadata (dropped per entry if its JSON is unreadable),
+//! - the stored configuration and settings (embedding model,
+//!   normalization, secondary metrics, trash retention, centroid groups),
+//! - trashed entries.
+//!
+//! Centroid entries are not copied; they are rebuilt from their members.
+
+use crate::types::VectorId;
+use serde::{Deserialize, Serialize};
+
+/// Entries inserted per batch while rebuilding the recovered database
+pub const RECOVERY_BATCH_SIZE: usize = 1000;
+
+/// Outcome of a recovery
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveryReport {
+    /// Entries written to the new database
+    pub recovered: usize,
+    /// Entries found but not recoverable
+    pub irrecoverable: usize,
+    /// Ids of the irrecoverable entries, sorted
+    pub irrecoverable_ids: Vec<VectorId>,
+    /// Recovered entries whose metadata was unreadable and dropped
+    pub metadata_lost: usize,
+    /// Trashed entries carried over
+    pub trash_recovered: usize,
+    /// Whether the source's configuration was readable; if not, the
+    /// caller's options were used
+    pub config_recovered: bool,
+    /// Whether every table was read to the end
+    ///
+    /// When false, a damaged region cut a scan short and entries stored
+    /// after it may be missing without being counted as irrecoverable.
+    pub complete: bool,
+}
+
+impl RecoveryReport {
+    /// Whether nothing was lost
+    pub fn is_lossless(&self) -> bool {
+        self.complete && self.irrecoverable == 0 && self.metadata_lost == 0
+    }
+}
@@ -0,0 +1,295 @@
+//! Query result cache with semantic keys
+//!
+//! Agents tend to issue the same (or nearly the same) query many times in a
+//! row. [`ResultCache`] answers those from memory. The key is built from
+//! the query vector quantized to a grid of `key_step` (or only its signs
+//! when `key_step` is 0), together with `k`, `ef_search` and a hash of the
+//! filter. Queries that land in the same grid cell share results.
+//!
+//! Every write to the database bumps a generation counter. An entry
+//! computed under an older generation is treated as a miss, so callers
+//! never see results from before a write. Entries also expire after `ttl`.
+
+use crate::types::{SearchQuery, SearchResult};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Result cache settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultCacheConfig {
+    /// Maximum cached queries; the oldest is evicted first
+    pub capacity: usize,
+    /// Time an entry stays valid
+    pub ttl: Duration,
+    /// Grid size for quantizing query components; 0 keys on signs only
+    pub key_step: f32,
+}
+
+impl Default for ResultCacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            ttl: Duration::from_secs(60),
+            key_step: 1e-3,
+        }
+    }
+}
+
+/// Cache counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultCacheStats {
+    /// Lookups answered from the cache
+    pub hits: u64,
+    /// Lookups that had to search
+    pub misses: u64,
+    /// Entries dropped because a write made them stale
+    pub invalidations: u64,
+    /// Entries dropped because they expired
+    pub expirations: u64,
+    /// Entries dropped to stay within capacity
+    pub evictions: u64,
+    /// Entries currently cached
+    pub entries: u64,
+}
+
+struct CachedResults {
+    results: Vec<SearchResult>,
+    generation: u64,
+    inserted_at: Instant,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<u64, CachedResults>,
+    /// Insertion order for eviction; may hold keys already removed
+    order: VecDeque<u64>,
+}
+
+/// Search result cache keyed by quantized query
+pub struct ResultCache {
+    config: ResultCacheConfig,
+    state: Mutex<CacheState>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    invalidations: AtomicU64,
+    expirations: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl ResultCache {
+    /// Create an empty cache
+    pub fn new(config: ResultCacheConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(CacheState::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            invalidations: AtomicU64::new(0),
+            expirations: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    /// Settings in use
+    pub fn config(&self) -> &ResultCacheConfig {
+        &self.config
+    }
+
+    /// Semantic key of `query`
+    pub fn key(&self, query: &SearchQuery) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        query.k.hash(&mut hasher);
+        query.ef_search.hash(&mut hasher);
+
+        if self.config.key_step > 0.0 {
+            for &v in &query.vector {
+                ((v / self.config.key_step).round() as i64).hash(&mut hasher);
+            }
+        } else {
+            let mut bits = 0u64;
+            for (i, &v) in query.vector.iter().enumerate() {
+                if v > 0.0 {
+                    bits |= 1 << (i % 64);
+                }
+                if i % 64 == 63 {
+                    bits.hash(&mut hasher);
+                    bits = 0;
+                }
+            }
+            bits.hash(&mut hasher);
+            query.vector.len().hash(&mut hasher);
+        }
+
+        // Filter maps iterate in arbitrary order; hash entries sorted by key
+        if let Some(filter) = &query.filter {
+            let mut entries: Vec<_> = filter.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            for (key, value) in entries {
+                key.hash(&mut hasher);
+                value.to_string().hash(&mut hasher);
+            }
+        }
+
+        hasher.finish()
+    }
+
+    /// Cached results for `key` if computed at `generation` and not expired
+    pub fn get(&self, key: u64, generation: u64) -> Option<Vec<SearchResult>> {
+        let mut state = self.state.lock();
+        let outcome = match state.entries.get(&key) {
+            None => None,
+            Some(entry) if entry.generation != generation => {
+                self.invalidations.fetch_add(1, Ordering::Relaxed);
+                state.entries.remove(&key);
+                None
+            }
+            Some(entry) if entry.inserted_at.elapsed() > self.config.ttl => {
+                self.expirations.fetch_add(1, Ordering::Relaxed);
+                state.entries.remove(&key);
+                None
+            }
+            Some(entry) => Some(entry.results.clone()),
+        };
+
+        match outcome {
+            Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+        outcome
+    }
+
+    /// Cache `results`, computed at `generation`
+    pub fn insert(&self, key: u64, generation: u64, results: Vec<SearchResult>) {
+        if self.config.capacity == 0 {
+            return;
+        }
+        let mut state = self.state.lock();
+        let previous = state.entries.insert(
+            key,
+            CachedResults {
+                results,
+                generation,
+                inserted_at: Instant::now(),
+            },
+        );
+        if previous.is_none() {
+            state.order.push_back(key);
+        }
+
+        while state.entries.len() > self.config.capacity {
+            match state.order.pop_front() {
+                Some(oldest) => {
+                    if state.entries.remove(&oldest).is_some() {
+                        self.evictions.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+                None => break,
+            }
+        }
+        // Drop keys of entries already removed by invalidation or expiry
+        if state.order.len() > 2 * self.config.capacity {
+            let CacheState { entries, order } = &mut *state;
+            order.retain(|key| entries.contains_key(key));
+        }
+    }
+
+    /// Drop every entry
+    pub fn clear(&self) {
+        let mut state = self.state.lock();
+        state.entries.clear();
+        state.order.clear();
+    }
+
+    /// Current counters
+    pub fn stats(&self) -> ResultCacheStats {
+        ResultCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+            expirations: self.expirations.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            entries: self.state.lock().entries.len() as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn query(vector: Vec<f32>) -> SearchQuery {
+        SearchQuery {
+            vector,
+            k: 5,
+            filter: None,
+            ef_search: None,
+        }
+    }
+
+    #[test]
+    fn test_key_quantization() {
+        let cache = ResultCache::new(ResultCacheConfig {
+            key_step: 0.1,
+            ..Default::default()
+        });
+        let a = cache.key(&query(vec![0.51, -0.2]));
+        assert_eq!(a, cache.key(&query(vec![0.52, -0.21])));
+        assert_ne!(a, cache.key(&query(vec![0.9, -0.2])));
+
+        let mut filtered = query(vec![0.51, -0.2]);
+        filtered.filter = Some(HashMap::from([(
+            "lang".to_string(),
+            serde_json::json!("en"),
+        )]));
+        assert_ne!(a, cache.key(&filtered));
+
+        let signs = ResultCache::new(ResultCacheConfig {
+            key_step: 0.0,
+            ..Default::default()
+        });
+        assert_eq!(
+            signs.key(&query(vec![0.1, -3.0])),
+            signs.key(&query(vec![7.0, -0.5]))
+        );
+    }
+
+    #[test]
+    fn test_generation_ttl_and_capacity() {
+        let cache = ResultCache::new(ResultCacheConfig {
+            capacity: 2,
+            ttl: Duration::from_secs(60),
+            key_step: 0.1,
+        });
+        cache.insert(1, 0, Vec::new());
+        assert!(cache.get(1, 0).is_some());
+        assert!(cache.get(1, 1).is_none());
+        assert!(cache.get(1, 1).is_none());
+
+        cache.insert(1, 1, Vec::new());
+        cache.insert(2, 1, Vec::new());
+        cache.insert(3, 1, Vec::new());
+        assert!(cache.get(1, 1).is_none());
+        assert!(cache.get(3, 1).is_some());
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.invalidations, 1);
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.entries, 2);
+
+        let expiring = ResultCache::new(ResultCacheConfig {
+            ttl: Duration::ZERO,
+            ..Default::default()
+        });
+        expiring.insert(1, 0, Vec::new());
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(expiring.get(1, 0).is_none());
+        assert_eq!(expiring.stats().expirations, 1);
+    }
+}
@@ -0,0 +1,260 @@
+//! Slow query log
+//!
+//! Searches that take longer than a threshold are kept in a bounded ring
+//! buffer so production slowdowns can be diagnosed after the fact. Query
+//! vectors are never stored; an entry only carries summary statistics of the
+//! vector and the shape (keys and value types) of the filter, never its
+//! values.
+//!
+//! The graph library behind the HNSW index does not report how many nodes a
+//! search visited, so entries record the number of index candidates examined
+//! instead: the sum of results fetched from the index, including adaptive
+//! efSearch probes and over-fetch rounds.
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Slow query log settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlowQueryConfig {
+    /// Searches at or above this latency are recorded
+    pub threshold: Duration,
+    /// Maximum retained entries; the oldest are evicted first
+    pub capacity: usize,
+}
+
+impl Default for SlowQueryConfig {
+    fn default() -> Self {
+        Self {
+            threshold: Duration::from_millis(100),
+            capacity: 1000,
+        }
+    }
+}
+
+/// Summary of a query vector that does not reveal its contents
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VectorStats {
+    /// Number of components
+    pub dimensions: usize,
+    /// L2 norm
+    pub norm: f32,
+    /// Smallest component
+    pub min: f32,
+    /// Largest component
+    pub max: f32,
+    /// Fraction of components that are zero
+    pub sparsity: f32,
+    /// Whether any component is NaN or infinite
+    pub non_finite: bool,
+}
+
+impl VectorStats {
+    /// Summarize `vector`
+    pub fn of(vector: &[f32]) -> Self {
+        let (min, max) = vector
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &x| {
+                (lo.min(x), hi.max(x))
+            });
+        let zeros = vector.iter().filter(|&&x| x == 0.0).count();
+        Self {
+            dimensions: vector.len(),
+            norm: vector.iter().map(|x| x * x).sum::<f32>().sqrt(),
+            min: if vector.is_empty() { 0.0 } else { min },
+            max: if vector.is_empty() { 0.0 } else { max },
+            sparsity: zeros as f32 / vector.len().max(1) as f32,
+            non_finite: vector.iter().any(|x| !x.is_finite()),
+        }
+    }
+}
+
+/// Filter keys with the JSON type of each value, sorted by key
+pub fn filter_shape(filter: &HashMap<String, serde_json::Value>) -> Vec<String> {
+    let mut shape: Vec<String> = filter
+        .iter()
+        .map(|(key, value)| {
+            let kind = match value {
+                serde_json::Value::Null => "null",
+                serde_json::Value::Bool(_) => "bool",
+                serde_json::Value::Number(_) => "number",
+                serde_json::Value::String(_) => "string",
+                serde_json::Value::Array(_) => "array",
+                serde_json::Value::Object(_) => "object",
+            };
+            format!("{}:{}", key, kind)
+        })
+        .collect();
+    shape.sort();
+    shape
+}
+
+/// One recorded slow search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlowQueryEntry {
+    /// Sequence number, increasing across the log's lifetime
+    pub id: u64,
+    /// Milliseconds since the Unix epoch when the search finished
+    pub timestamp_ms: i64,
+    /// Search latency in microseconds
+    pub latency_us: u64,
+    /// Requested number of results
+    pub k: usize,
+    /// Results returned
+    pub results: usize,
+    /// efSearch used, if one was set explicitly or chosen adaptively
+    pub ef_search: Option<usize>,
+    /// Whether `ef_search` came from adaptive efSearch
+    pub adaptive_ef: bool,
+    /// Index candidates examined
+    pub candidates: usize,
+    /// Filter keys and value types, e.g. `"lang:string"`
+    pub filter_shape: Vec<String>,
+    /// Query vector summary
+    pub vector: VectorStats,
+}
+
+/// Bounded log of searches slower than a threshold
+pub struct SlowQueryLog {
+    capacity: usize,
+    threshold_us: AtomicU64,
+    next_id: AtomicU64,
+    recorded: AtomicU64,
+    entries: RwLock<VecDeque<SlowQueryEntry>>,
+}
+
+impl SlowQueryLog {
+    /// Create an empty log
+    pub fn new(config: SlowQueryConfig) -> Self {
+        Self {
+            capacity: config.capacity.max(1),
+            threshold_us: AtomicU64::new(config.threshold.as_micros() as u64),
+            next_id: AtomicU64::new(1),
+            recorded: AtomicU64::new(0),
+            entries: RwLock::new(VecDeque::new()),
+        }
+    }
+
+    /// Current latency threshold
+    pub fn threshold(&self) -> Duration {
+        Duration::from_micros(self.threshold_us.load(Ordering::Relaxed))
+    }
+
+    /// Change the latency threshold; applies to subsequent searches
+    pub fn set_threshold(&self, threshold: Duration) {
+        self.threshold_us
+            .store(threshold.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Whether a search taking `latency` would be recorded
+    pub fn is_slow(&self, latency: Duration) -> bool {
+        latency.as_micros() as u64 >= self.threshold_us.load(Ordering::Relaxed)
+    }
+
+    /// Record a slow search; `id` and `timestamp_ms` are assigned here
+    pub fn record(&self, mut entry: SlowQueryEntry) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        entry.id = id;
+        entry.timestamp_ms = chrono::Utc::now().timestamp_millis();
+        self.recorded.fetch_add(1, Ordering::Relaxed);
+
+        let mut entries = self.entries.write();
+        entries.push_back(entry);
+        while entries.len() > self.capacity {
+            entries.pop_front();
+        }
+        id
+    }
+
+    /// Number of retained entries
+    pub fn len(&self) -> usize {
+        self.entries.read().len()
+    }
+
+    /// Whether the log is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Slow searches recorded since creation, including evicted ones
+    pub fn total_recorded(&self) -> u64 {
+        self.recorded.load(Ordering::Relaxed)
+    }
+
+    /// Snapshot of retained entries, oldest first
+    pub fn entries(&self) -> Vec<SlowQueryEntry> {
+        self.entries.read().iter().cloned().collect()
+    }
+
+    /// The `n` most recent entries, newest first
+    pub fn recent(&self, n: usize) -> Vec<SlowQueryEntry> {
+        self.entries.read().iter().rev().take(n).cloned().collect()
+    }
+
+    /// Drop all retained entries
+    pub fn clear(&self) {
+        self.entries.write().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(latency_us: u64) -> SlowQueryEntry {
+        SlowQueryEntry {
+            id: 0,
+            timestamp_ms: 0,
+            latency_us,
+            k: 10,
+            results: 10,
+            ef_search: None,
+            adaptive_ef: false,
+            candidates: 10,
+            filter_shape: Vec::new(),
+            vector: VectorStats::of(&[0.0, 3.0, -4.0]),
+        }
+    }
+
+    #[test]
+    fn test_vector_stats_and_filter_shape() {
+        let stats = VectorStats::of(&[0.0, 3.0, -4.0]);
+        assert_eq!(stats.dimensions, 3);
+        assert!((stats.norm - 5.0).abs() < 1e-6);
+        assert_eq!((stats.min, stats.max), (-4.0, 3.0));
+        assert!((stats.sparsity - 1.0 / 3.0).abs() < 1e-6);
+        assert!(!stats.non_finite);
+
+        let filter = HashMap::from([
+            ("lang".to_string(), serde_json::json!("en")),
+            ("year".to_string(), serde_json::json!(2024)),
+        ]);
+        assert_eq!(filter_shape(&filter), vec!["lang:string", "year:number"]);
+    }
+
+    #[test]
+    fn test_ring_buffer_and_threshold() {
+        let log = SlowQueryLog::new(SlowQueryConfig {
+            threshold: Duration::from_millis(5),
+            capacity: 2,
+        });
+        assert!(!log.is_slow(Duration::from_millis(1)));
+        assert!(log.is_slow(Duration::from_millis(5)));
+
+        for latency in [5_000, 6_000, 7_000] {
+            log.record(entry(latency));
+        }
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.total_recorded(), 3);
+        assert_eq!(log.recent(1)[0].latency_us, 7_000);
+        assert_eq!(log.entries()[0].id, 2);
+
+        log.set_threshold(Duration::ZERO);
+        assert!(log.is_slow(Duration::ZERO));
+        log.clear();
+        assert!(log.is_empty());
+    }
+}
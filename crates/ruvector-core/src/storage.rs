@@ -3,10 +3,20 @@
 //! This module is only available when the "storage" feature is enabled.
 //! For WASM builds, use the in-memory storage backend instead.
 
+#[cfg(feature = "storage")]
+use crate::content_id::IdStrategy;
+#[cfg(feature = "storage")]
+use crate::embedding_model::ModelGuard;
 #[cfg(feature = "storage")]
 use crate::error::{Result, RuvectorError};
 #[cfg(feature = "storage")]
-use crate::types::{DbOptions, VectorEntry, VectorId};
+use crate::normalization::NormalizationPolicy;
+#[cfg(feature = "storage")]
+use crate::privacy::PrivacyLedger;
+#[cfg(feature = "storage")]
+use crate::trash::TrashedEntry;
+#[cfg(feature = "storage")]
+use crate::types::{DbOptions, DistanceMetric, VectorEntry, VectorId};
 #[cfg(feature = "storage")]
 use bincode::config;
 #[cfg(feature = "storage")]
@@ -14,7 +24,9 @@ use once_cell::sync::Lazy;
 #[cfg(feature = "storage")]
 use parking_lot::Mutex;
 #[cfg(feature = "storage")]
-use redb::{Database, ReadableTable, ReadableTableMetadata, TableDefinition};
+use redb::{AccessGuard, Database, ReadableTable, ReadableTableMetadata, TableDefinition};
+#[cfg(feature = "storage")]
+use serde::{de::DeserializeOwned, Serialize};
 #[cfg(feature = "storage")]
 use serde_json;
 #[cfg(feature = "storage")]
@@ -29,10 +41,32 @@ use std::sync::Arc;
 const VECTORS_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("vectors");
 const METADATA_TABLE: TableDefinition<&str, &str> = TableDefinition::new("metadata");
 const CONFIG_TABLE: TableDefinition<&str, &str> = TableDefinition::new("config");
+const TRASH_TABLE: TableDefinition<&str, &str> = TableDefinition::new("trash");
 
 /// Key used to store database configuration in CONFIG_TABLE
 const DB_CONFIG_KEY: &str = "__ruvector_db_config__";
 
+/// Key used to store the embedding model guard in CONFIG_TABLE
+const EMBEDDING_MODEL_KEY: &str = "__ruvector_embedding_model__";
+
+/// Key used to store the normalization policy in CONFIG_TABLE
+const NORMALIZATION_KEY: &str = "__ruvector_normalization__";
+
+/// Key used to store secondary distance metrics in CONFIG_TABLE
+const SECONDARY_METRICS_KEY: &str = "__ruvector_secondary_metrics__";
+
+/// Key used to store the trash retention period (seconds) in CONFIG_TABLE
+const TRASH_RETENTION_KEY: &str = "__ruvector_trash_retention__";
+
+/// Key used to store centroid group keys in CONFIG_TABLE
+const CENTROID_GROUPS_KEY: &str = "__ruvector_centroid_groups__";
+
+/// Key used to store the privacy ledger of private exports in CONFIG_TABLE
+const PRIVACY_LEDGER_KEY: &str = "__ruvector_privacy_ledger__";
+
+/// Key used to store the id strategy in CONFIG_TABLE
+const ID_STRATEGY_KEY: &str = "__ruvector_id_strategy__";
+
 // Global database connection pool to allow multiple VectorDB instances
 // to share the same underlying database file
 static DB_POOL: Lazy<Mutex<HashMap<PathBuf, Arc<Database>>>> =
@@ -117,6 +151,7 @@ impl VectorStorage {
                     let _ = write_txn.open_table(VECTORS_TABLE)?;
                     let _ = write_txn.open_table(METADATA_TABLE)?;
                     let _ = write_txn.open_table(CONFIG_TABLE)?;
+                    let _ = write_txn.open_table(TRASH_TABLE)?;
                 }
                 write_txn.commit()?;
 
@@ -256,6 +291,149 @@ impl VectorStorage {
         Ok(deleted)
     }
 
+    /// Delete several vectors in a single transaction
+    ///
+    /// Returns the number of vectors that existed and were removed.
+    pub fn delete_batch(&self, ids: &[VectorId]) -> Result<usize> {
+        let write_txn = self.db.begin_write()?;
+        let mut deleted = 0;
+
+        {
+            let mut table = write_txn.open_table(VECTORS_TABLE)?;
+            let mut meta_table = write_txn.open_table(METADATA_TABLE)?;
+            for id in ids {
+                if table.remove(id.as_str())?.is_some() {
+                    deleted += 1;
+                }
+                let _ = meta_table.remove(id.as_str())?;
+            }
+        }
+
+        write_txn.commit()?;
+        Ok(deleted)
+    }
+
+    /// Move a vector and its metadata into the trash in one transaction
+    ///
+    /// Returns false if no vector with `id` exists.
+    pub fn move_to_trash(&self, id: &str, deleted_at_ms: i64) -> Result<bool> {
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(VECTORS_TABLE)?;
+            let Some(vector_data) = table.remove(id)? else {
+                return Ok(false);
+            };
+            let (vector, _): (Vec<f32>, usize) =
+                bincode::decode_from_slice(vector_data.value(), config::standard())
+                    .map_err(|e| RuvectorError::SerializationError(e.to_string()))?;
+
+            let mut meta_table = write_txn.open_table(METADATA_TABLE)?;
+            let metadata = match meta_table.remove(id)? {
+                Some(meta_data) => Some(
+                    serde_json::from_str(meta_data.value())
+                        .map_err(|e| RuvectorError::SerializationError(e.to_string()))?,
+                ),
+                None => None,
+            };
+
+            let trashed = TrashedEntry {
+                entry: VectorEntry {
+                    id: Some(id.to_string()),
+                    vector,
+                    metadata,
+                },
+                deleted_at_ms,
+            };
+            let trashed_json = serde_json::to_string(&trashed)
+                .map_err(|e| RuvectorError::SerializationError(e.to_string()))?;
+            let mut trash_table = write_txn.open_table(TRASH_TABLE)?;
+            trash_table.insert(id, trashed_json.as_str())?;
+        }
+        write_txn.commit()?;
+        Ok(true)
+    }
+
+    /// Get a trashed entry by ID
+    pub fn get_trashed(&self, id: &str) -> Result<Option<TrashedEntry>> {
+        let read_txn = self.db.begin_read()?;
+        // Databases created before soft deletion have no trash table
+        let table = match read_txn.open_table(TRASH_TABLE) {
+            Ok(t) => t,
+            Err(_) => return Ok(None),
+        };
+        let Some(data) = table.get(id)? else {
+            return Ok(None);
+        };
+        let trashed = serde_json::from_str(data.value())
+            .map_err(|e| RuvectorError::SerializationError(e.to_string()))?;
+        Ok(Some(trashed))
+    }
+
+    /// All trashed entries
+    pub fn trashed_entries(&self) -> Result<Vec<TrashedEntry>> {
+        let read_txn = self.db.begin_read()?;
+        let table = match read_txn.open_table(TRASH_TABLE) {
+            Ok(t) => t,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let mut entries = Vec::new();
+        for item in table.iter()? {
+            let (_, data) = item?;
+            entries.push(
+                serde_json::from_str(data.value())
+                    .map_err(|e| RuvectorError::SerializationError(e.to_string()))?,
+            );
+        }
+        Ok(entries)
+    }
+
+    /// Move a trashed entry back into the live set in one transaction
+    pub fn restore_from_trash(&self, id: &str) -> Result<Option<VectorEntry>> {
+        let write_txn = self.db.begin_write()?;
+        let entry = {
+            let mut trash_table = write_txn.open_table(TRASH_TABLE)?;
+            let Some(data) = trash_table.remove(id)? else {
+                return Ok(None);
+            };
+            let trashed: TrashedEntry = serde_json::from_str(data.value())
+                .map_err(|e| RuvectorError::SerializationError(e.to_string()))?;
+
+            let mut table = write_txn.open_table(VECTORS_TABLE)?;
+            let vector_data = bincode::encode_to_vec(&trashed.entry.vector, config::standard())
+                .map_err(|e| RuvectorError::SerializationError(e.to_string()))?;
+            table.insert(id, vector_data.as_slice())?;
+
+            if let Some(metadata) = &trashed.entry.metadata {
+                let mut meta_table = write_txn.open_table(METADATA_TABLE)?;
+                let metadata_json = serde_json::to_string(metadata)
+                    .map_err(|e| RuvectorError::SerializationError(e.to_string()))?;
+                meta_table.insert(id, metadata_json.as_str())?;
+            }
+            trashed.entry
+        };
+        write_txn.commit()?;
+        Ok(Some(entry))
+    }
+
+    /// Permanently remove entries from the trash
+    ///
+    /// Returns the number of entries that were in the trash.
+    pub fn remove_from_trash(&self, ids: &[VectorId]) -> Result<usize> {
+        let write_txn = self.db.begin_write()?;
+        let mut removed = 0;
+        {
+            let mut trash_table = write_txn.open_table(TRASH_TABLE)?;
+            for id in ids {
+                if trash_table.remove(id.as_str())?.is_some() {
+                    removed += 1;
+                }
+            }
+        }
+        write_txn.commit()?;
+        Ok(removed)
+    }
+
     /// Get the number of vectors stored
     pub fn len(&self) -> Result<usize> {
         let read_txn = self.db.begin_read()?;
@@ -318,10 +496,288 @@ impl VectorStorage {
         Ok(Some(config))
     }
 
+    /// Save the embedding model guard
+    pub fn save_embedding_model(&self, guard: &ModelGuard) -> Result<()> {
+        self.save_setting(EMBEDDING_MODEL_KEY, guard)
+    }
+
+    /// Load the embedding model guard, if one was saved
+    pub fn load_embedding_model(&self) -> Result<Option<ModelGuard>> {
+        self.load_setting(EMBEDDING_MODEL_KEY)
+    }
+
+    /// Save the normalization policy
+    pub fn save_normalization(&self, policy: NormalizationPolicy) -> Result<()> {
+        self.save_setting(NORMALIZATION_KEY, &policy)
+    }
+
+    /// Load the normalization policy, if one was saved
+    pub fn load_normalization(&self) -> Result<Option<NormalizationPolicy>> {
+        self.load_setting(NORMALIZATION_KEY)
+    }
+
+    /// Save the secondary distance metrics
+    pub fn save_secondary_metrics(&self, metrics: &[DistanceMetric]) -> Result<()> {
+        self.save_setting(SECONDARY_METRICS_KEY, &metrics)
+    }
+
+    /// Load the secondary distance metrics, if any were saved
+    pub fn load_secondary_metrics(&self) -> Result<Option<Vec<DistanceMetric>>> {
+        self.load_setting(SECONDARY_METRICS_KEY)
+    }
+
+    /// Save the trash retention period in seconds
+    pub fn save_trash_retention(&self, seconds: u64) -> Result<()> {
+        self.save_setting(TRASH_RETENTION_KEY, &seconds)
+    }
+
+    /// Load the trash retention period in seconds, if one was saved
+    pub fn load_trash_retention(&self) -> Result<Option<u64>> {
+        self.load_setting(TRASH_RETENTION_KEY)
+    }
+
+    /// Save the metadata keys centroid groups are declared on
+    pub fn save_centroid_groups(&self, keys: &[String]) -> Result<()> {
+        self.save_setting(CENTROID_GROUPS_KEY, &keys)
+    }
+
+    /// Load the centroid group keys, if any were saved
+    pub fn load_centroid_groups(&self) -> Result<Option<Vec<String>>> {
+        self.load_setting(CENTROID_GROUPS_KEY)
+    }
+
+    /// Save the privacy ledger
+    pub fn save_privacy_ledger(&self, ledger: &PrivacyLedger) -> Result<()> {
+        self.save_setting(PRIVACY_LEDGER_KEY, ledger)
+    }
+
+    /// Load the privacy ledger, if one was saved
+    pub fn load_privacy_ledger(&self) -> Result<Option<PrivacyLedger>> {
+        self.load_setting(PRIVACY_LEDGER_KEY)
+    }
+
+    /// Save how ids are chosen for entries inserted without one
+    pub fn save_id_strategy(&self, strategy: IdStrategy) -> Result<()> {
+        self.save_setting(ID_STRATEGY_KEY, &strategy)
+    }
+
+    /// Load the id strategy, if one was saved
+    pub fn load_id_strategy(&self) -> Result<Option<IdStrategy>> {
+        self.load_setting(ID_STRATEGY_KEY)
+    }
+
+    fn save_setting<T: Serialize>(&self, key: &str, value: &T) -> Result<()> {
+        let json = serde_json::to_string(value)
+            .map_err(|e| RuvectorError::SerializationError(e.to_string()))?;
+
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(CONFIG_TABLE)?;
+            table.insert(key, json.as_str())?;
+        }
+        write_txn.commit()?;
+
+        Ok(())
+    }
+
+    fn load_setting<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        let read_txn = self.db.begin_read()?;
+        let table = match read_txn.open_table(CONFIG_TABLE) {
+            Ok(t) => t,
+            Err(_) => return Ok(None),
+        };
+
+        let Some(data) = table.get(key)? else {
+            return Ok(None);
+        };
+
+        let value = serde_json::from_str(data.value())
+            .map_err(|e| RuvectorError::SerializationError(e.to_string()))?;
+        Ok(Some(value))
+    }
+
     /// Get the stored dimensions
     pub fn dimensions(&self) -> usize {
         self.dimensions
     }
+
+    /// Read every decodable row of the database at `path`
+    ///
+    /// Damaged rows and table regions are skipped and accounted for in the
+    /// returned [`Salvage`] instead of failing the whole read. Only a file
+    /// that redb cannot open at all is an error.
+    pub(crate) fn salvage<P: AsRef<Path>>(path: P) -> Result<Salvage> {
+        let path_ref = path.as_ref();
+        let path_buf = if path_ref.is_absolute() {
+            path_ref.to_path_buf()
+        } else {
+            std::env::current_dir()
+                .map_err(|e| RuvectorError::InvalidPath(format!("Failed to get cwd: {}", e)))?
+                .join(path_ref)
+        };
+        if !path_buf.exists() {
+            return Err(RuvectorError::InvalidPath(format!(
+                "No database at {}",
+                path_buf.display()
+            )));
+        }
+
+        // Reuse an open handle, but do not keep a damaged file in the pool
+        let pooled = DB_POOL.lock().get(&path_buf).cloned();
+        let db = match pooled {
+            Some(db) => db,
+            None => Arc::new(Database::builder().open(&path_buf)?),
+        };
+        let read_txn = db.begin_read()?;
+        let mut salvage = Salvage {
+            complete: true,
+            ..Salvage::default()
+        };
+
+        let mut settings = Vec::new();
+        if let Ok(table) = read_txn.open_table(CONFIG_TABLE) {
+            salvage.complete &= scan_table(&table, |key, value| {
+                settings.push((key.to_string(), value.value().to_string()));
+            });
+        }
+        for (key, value) in settings {
+            if key == DB_CONFIG_KEY {
+                salvage.config = serde_json::from_str(&value).ok();
+            } else {
+                salvage.settings.push((key, value));
+            }
+        }
+
+        let mut metadata = HashMap::new();
+        if let Ok(table) = read_txn.open_table(METADATA_TABLE) {
+            salvage.complete &= scan_table(&table, |key, value| {
+                metadata.insert(key.to_string(), value.value().to_string());
+            });
+        }
+
+        let mut vectors = HashMap::new();
+        let vector_table = read_txn.open_table(VECTORS_TABLE);
+        match &vector_table {
+            Ok(table) => {
+                salvage.complete &= scan_table(table, |key, value| {
+                    vectors.insert(key.to_string(), decode_vector(value.value()));
+                });
+            }
+            Err(_) => salvage.complete = false,
+        }
+
+        // Ids known from metadata but missed by a broken scan may still be
+        // reachable by direct lookup
+        if let Ok(table) = &vector_table {
+            for id in metadata.keys() {
+                if !vectors.contains_key(id) {
+                    let decoded = match table.get(id.as_str()) {
+                        Ok(Some(value)) => decode_vector(value.value()),
+                        Ok(None) => continue,
+                        Err(_) => None,
+                    };
+                    vectors.insert(id.clone(), decoded);
+                }
+            }
+        }
+
+        let mut ids: Vec<VectorId> = vectors.keys().cloned().collect();
+        ids.sort();
+        for id in ids {
+            let Some(vector) = vectors.remove(&id).flatten() else {
+                salvage.irrecoverable.push(id);
+                continue;
+            };
+            let metadata = match metadata.get(&id) {
+                Some(json) => match serde_json::from_str(json) {
+                    Ok(metadata) => Some(metadata),
+                    Err(_) => {
+                        salvage.metadata_lost += 1;
+                        None
+                    }
+                },
+                None => None,
+            };
+            salvage.entries.push(VectorEntry {
+                id: Some(id),
+                vector,
+                metadata,
+            });
+        }
+
+        if let Ok(table) = read_txn.open_table(TRASH_TABLE) {
+            let trash = &mut salvage.trash;
+            salvage.complete &= scan_table(&table, |key, value| {
+                if serde_json::from_str::<TrashedEntry>(value.value()).is_ok() {
+                    trash.push((key.to_string(), value.value().to_string()));
+                }
+            });
+        }
+
+        Ok(salvage)
+    }
+
+    /// Write the settings and trash rows of a salvaged database
+    pub(crate) fn import_salvaged(&self, salvage: &Salvage) -> Result<()> {
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(CONFIG_TABLE)?;
+            for (key, value) in &salvage.settings {
+                table.insert(key.as_str(), value.as_str())?;
+            }
+            let mut trash_table = write_txn.open_table(TRASH_TABLE)?;
+            for (id, trashed) in &salvage.trash {
+                trash_table.insert(id.as_str(), trashed.as_str())?;
+            }
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+}
+
+/// Rows read back from a possibly damaged database
+#[derive(Debug, Default)]
+pub(crate) struct Salvage {
+    /// Stored database configuration, if readable
+    pub(crate) config: Option<DbOptions>,
+    /// Other config rows (embedding model, normalization, ...) as raw JSON
+    pub(crate) settings: Vec<(String, String)>,
+    /// Entries whose vector decoded
+    pub(crate) entries: Vec<VectorEntry>,
+    /// Ids whose vector could not be read or decoded
+    pub(crate) irrecoverable: Vec<VectorId>,
+    /// Entries recovered without their unreadable metadata
+    pub(crate) metadata_lost: usize,
+    /// Trash rows as raw JSON
+    pub(crate) trash: Vec<(String, String)>,
+    /// Whether every table was scanned to the end
+    pub(crate) complete: bool,
+}
+
+fn decode_vector(bytes: &[u8]) -> Option<Vec<f32>> {
+    bincode::decode_from_slice(bytes, config::standard())
+        .ok()
+        .map(|(vector, _): (Vec<f32>, usize)| vector)
+}
+
+/// Visit rows of `table` in key order until the end or the first unreadable row
+///
+/// Returns whether the scan reached the end.
+fn scan_table<V, T>(table: &T, mut visit: impl FnMut(&str, AccessGuard<'_, V>)) -> bool
+where
+    V: redb::Value + 'static,
+    T: ReadableTable<&'static str, V>,
+{
+    let Ok(iter) = table.iter() else {
+        return false;
+    };
+    for item in iter {
+        match item {
+            Ok((key, value)) => visit(key.value(), value),
+            Err(_) => return false,
+        }
+    }
+    true
 }
 
 // Add uuid dependency
@@ -444,4 +900,41 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_salvage_skips_damaged_rows() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("damaged.db");
+        let storage = VectorStorage::new(&db_path, 3)?;
+        for id in ["a", "b", "c"] {
+            storage.insert(&VectorEntry {
+                id: Some(id.to_string()),
+                vector: vec![1.0, 2.0, 3.0],
+                metadata: Some(HashMap::from([("k".to_string(), serde_json::json!(id))])),
+            })?;
+        }
+
+        let write_txn = storage.db.begin_write()?;
+        {
+            let mut vectors = write_txn.open_table(VECTORS_TABLE)?;
+            vectors.insert("b", [0xff_u8; 3].as_slice())?;
+            let mut metadata = write_txn.open_table(METADATA_TABLE)?;
+            metadata.insert("c", "{not json")?;
+        }
+        write_txn.commit()?;
+
+        let salvage = VectorStorage::salvage(&db_path)?;
+        assert!(salvage.complete);
+        assert_eq!(salvage.irrecoverable, vec!["b".to_string()]);
+        assert_eq!(salvage.metadata_lost, 1);
+        let ids: Vec<_> = salvage
+            .entries
+            .iter()
+            .filter_map(|e| e.id.clone())
+            .collect();
+        assert_eq!(ids, vec!["a".to_string(), "c".to_string()]);
+        assert!(salvage.entries[1].metadata.is_none());
+
+        Ok(())
+    }
 }
@@ -4,6 +4,7 @@
 //! making it suitable for WebAssembly environments.
 
 use crate::error::{Result, RuvectorError};
+use crate::trash::TrashedEntry;
 use crate::types::{VectorEntry, VectorId};
 use dashmap::DashMap;
 use serde_json::Value as JsonValue;
@@ -13,6 +14,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 pub struct MemoryStorage {
     vectors: DashMap<String, Vec<f32>>,
     metadata: DashMap<String, JsonValue>,
+    trash: DashMap<String, TrashedEntry>,
     dimensions: usize,
     counter: AtomicU64,
 }
@@ -23,6 +25,7 @@ impl MemoryStorage {
         Ok(Self {
             vectors: DashMap::new(),
             metadata: DashMap::new(),
+            trash: DashMap::new(),
             dimensions,
             counter: AtomicU64::new(0),
         })
@@ -127,6 +130,66 @@ impl MemoryStorage {
         Ok(vector_removed)
     }
 
+    /// Delete several vectors
+    ///
+    /// Returns the number of vectors that existed and were removed.
+    pub fn delete_batch(&self, ids: &[VectorId]) -> Result<usize> {
+        let mut deleted = 0;
+        for id in ids {
+            if self.delete(id)? {
+                deleted += 1;
+            }
+        }
+        Ok(deleted)
+    }
+
+    /// Move a vector and its metadata into the trash
+    ///
+    /// Returns false if no vector with `id` exists.
+    pub fn move_to_trash(&self, id: &str, deleted_at_ms: i64) -> Result<bool> {
+        let Some(entry) = self.get(id)? else {
+            return Ok(false);
+        };
+        self.delete(id)?;
+        self.trash.insert(
+            id.to_string(),
+            TrashedEntry {
+                entry,
+                deleted_at_ms,
+            },
+        );
+        Ok(true)
+    }
+
+    /// Get a trashed entry by ID
+    pub fn get_trashed(&self, id: &str) -> Result<Option<TrashedEntry>> {
+        Ok(self.trash.get(id).map(|trashed| trashed.clone()))
+    }
+
+    /// All trashed entries
+    pub fn trashed_entries(&self) -> Result<Vec<TrashedEntry>> {
+        Ok(self.trash.iter().map(|trashed| trashed.clone()).collect())
+    }
+
+    /// Move a trashed entry back into the live set
+    pub fn restore_from_trash(&self, id: &str) -> Result<Option<VectorEntry>> {
+        let Some((_, trashed)) = self.trash.remove(id) else {
+            return Ok(None);
+        };
+        self.insert(&trashed.entry)?;
+        Ok(Some(trashed.entry))
+    }
+
+    /// Permanently remove entries from the trash
+    ///
+    /// Returns the number of entries that were in the trash.
+    pub fn remove_from_trash(&self, ids: &[VectorId]) -> Result<usize> {
+        Ok(ids
+            .iter()
+            .filter(|id| self.trash.remove(id.as_str()).is_some())
+            .count())
+    }
+
     /// Get the number of vectors stored
     pub fn len(&self) -> Result<usize> {
         Ok(self.vectors.len())
@@ -154,6 +217,7 @@ impl MemoryStorage {
     pub fn clear(&self) -> Result<()> {
         self.vectors.clear();
         self.metadata.clear();
+        self.trash.clear();
         Ok(())
     }
 }
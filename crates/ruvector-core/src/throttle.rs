@@ -0,0 +1,206 @@
+//! Write backpressure based on index health
+//!
+//! HNSW deletions leave tombstoned nodes in the graph, and heavy churn can
+//! cut parts of it off from the entry point. Both degrade recall without any
+//! error. With thresholds set in [`PerformanceConfig`], inserts and updates
+//! fail with [`RuvectorError::Backpressure`] once the graph is past them,
+//! so callers can queue writes and compact the index (see
+//! [`MaintenanceTask::CompactIndex`](crate::MaintenanceTask::CompactIndex))
+//! instead. Deletes are never throttled.
+//!
+//! Reachability walks the whole graph, so it is measured at most once per
+//! [`PerformanceConfig::link_check_interval`] writes and cached in between.
+
+use crate::error::{Result, RuvectorError};
+use crate::index::GraphStats;
+use serde::{Deserialize, Serialize};
+
+/// Writes between reachability measurements unless configured otherwise
+pub const DEFAULT_LINK_CHECK_INTERVAL: u64 = 1000;
+
+/// Index health thresholds past which writes are refused
+///
+/// Every threshold is off by default.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PerformanceConfig {
+    /// Deleted share of graph nodes above which writes are refused
+    #[serde(default)]
+    pub max_tombstone_ratio: Option<f32>,
+    /// Share of live nodes reachable from the entry point below which
+    /// writes are refused
+    #[serde(default)]
+    pub min_reachability: Option<f32>,
+    /// Writes between reachability measurements
+    #[serde(default = "default_link_check_interval")]
+    pub link_check_interval: u64,
+}
+
+fn default_link_check_interval() -> u64 {
+    DEFAULT_LINK_CHECK_INTERVAL
+}
+
+impl Default for PerformanceConfig {
+    fn default() -> Self {
+        Self {
+            max_tombstone_ratio: None,
+            min_reachability: None,
+            link_check_interval: DEFAULT_LINK_CHECK_INTERVAL,
+        }
+    }
+}
+
+impl PerformanceConfig {
+    /// Check that thresholds are ratios in `[0, 1]`
+    pub fn validate(&self) -> Result<()> {
+        let thresholds = [
+            ("max_tombstone_ratio", self.max_tombstone_ratio),
+            ("min_reachability", self.min_reachability),
+        ];
+        for (name, threshold) in thresholds {
+            if let Some(value) = threshold {
+                if !(0.0..=1.0).contains(&value) {
+                    return Err(RuvectorError::InvalidParameter(format!(
+                        "{} must be in [0, 1], got {}",
+                        name, value
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether any threshold is set
+    pub fn is_enabled(&self) -> bool {
+        self.max_tombstone_ratio.is_some() || self.min_reachability.is_some()
+    }
+}
+
+/// Thresholds with the last reachability measurement
+#[derive(Debug, Default)]
+pub(crate) struct WriteThrottle {
+    pub(crate) config: PerformanceConfig,
+    /// Write generation and reachable share at the last measurement
+    reachability: Option<(u64, f32)>,
+}
+
+impl WriteThrottle {
+    /// Replace the thresholds and forget the cached measurement
+    pub(crate) fn configure(&mut self, config: PerformanceConfig) {
+        self.config = config;
+        self.reachability = None;
+    }
+
+    /// Refuse the write if the graph is past a threshold
+    ///
+    /// `nodes` counts live and tombstoned nodes. `measure` is only called
+    /// when the cached reachability is older than the check interval.
+    pub(crate) fn check(
+        &mut self,
+        tombstones: usize,
+        nodes: usize,
+        generation: u64,
+        measure: impl FnOnce() -> Option<GraphStats>,
+    ) -> Result<()> {
+        if let Some(threshold) = self.config.max_tombstone_ratio {
+            let ratio = if nodes == 0 {
+                0.0
+            } else {
+                tombstones as f32 / nodes as f32
+            };
+            if ratio > threshold {
+                return Err(RuvectorError::Backpressure {
+                    signal: "tombstone_ratio".to_string(),
+                    value: ratio,
+                    threshold,
+                });
+            }
+        }
+
+        if let Some(threshold) = self.config.min_reachability {
+            let interval = self.config.link_check_interval;
+            let reachable = match self.reachability {
+                Some((measured_at, reachable))
+                    if generation.saturating_sub(measured_at) < interval =>
+                {
+                    reachable
+                }
+                _ => {
+                    let reachable = measure().map_or(1.0, |stats| reachable_share(&stats));
+                    self.reachability = Some((generation, reachable));
+                    reachable
+                }
+            };
+            if reachable < threshold {
+                return Err(RuvectorError::Backpressure {
+                    signal: "reachability".to_string(),
+                    value: reachable,
+                    threshold,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Share of live nodes reachable from the entry point; 1 for an empty graph
+fn reachable_share(stats: &GraphStats) -> f32 {
+    let live = stats.reachable_from_entry + stats.unreachable;
+    if live == 0 {
+        1.0
+    } else {
+        stats.reachable_from_entry as f32 / live as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(reachable: usize, unreachable: usize) -> GraphStats {
+        GraphStats {
+            levels: 1,
+            nodes_per_level: vec![reachable + unreachable],
+            avg_out_degree: vec![4.0],
+            entry_point: Some("e".to_string()),
+            reachable_from_entry: reachable,
+            unreachable,
+            isolated_nodes: 0,
+            removed_nodes: 0,
+        }
+    }
+
+    #[test]
+    fn test_thresholds() {
+        let mut throttle = WriteThrottle::default();
+        assert!(throttle.check(90, 100, 0, || None).is_ok());
+
+        throttle.configure(PerformanceConfig {
+            max_tombstone_ratio: Some(0.3),
+            min_reachability: Some(0.9),
+            link_check_interval: 10,
+        });
+        throttle.check(30, 100, 0, || Some(stats(95, 5))).unwrap();
+        match throttle.check(31, 100, 0, || unreachable!()) {
+            Err(RuvectorError::Backpressure { signal, .. }) => {
+                assert_eq!(signal, "tombstone_ratio")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        // Cached until the interval passes
+        throttle.check(0, 100, 9, || unreachable!()).unwrap();
+        match throttle.check(0, 100, 10, || Some(stats(80, 20))) {
+            Err(RuvectorError::Backpressure { signal, value, .. }) => {
+                assert_eq!(signal, "reachability");
+                assert!((value - 0.8).abs() < 1e-6);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let bad = PerformanceConfig {
+            min_reachability: Some(1.5),
+            ..Default::default()
+        };
+        assert!(bad.validate().is_err());
+    }
+}
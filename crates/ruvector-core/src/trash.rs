@@ -0,0 +1,65 @@
+//! Soft deletion with a restore window
+//!
+//! [`VectorDB::soft_delete`](crate::VectorDB::soft_delete) moves an entry,
+//! with its metadata, out of the live set and into a trash area instead of
+//! erasing it. It stops appearing in searches and lookups at once, but can
+//! be brought back with [`VectorDB::restore`](crate::VectorDB::restore)
+//! until the retention period has passed. Expired entries cannot be restored
+//! and are removed by [`VectorDB::purge_trash`](crate::VectorDB::purge_trash).
+
+use crate::types::{VectorEntry, VectorId};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How long soft-deleted entries stay restorable unless configured otherwise
+pub const DEFAULT_TRASH_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// A soft-deleted entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedEntry {
+    /// The entry as it was when deleted, including metadata
+    pub entry: VectorEntry,
+    /// Milliseconds since the Unix epoch when it was deleted
+    pub deleted_at_ms: i64,
+}
+
+impl TrashedEntry {
+    /// Id of the deleted entry
+    pub fn id(&self) -> VectorId {
+        self.entry.id.clone().unwrap_or_default()
+    }
+
+    /// When the entry stops being restorable, in milliseconds since the epoch
+    pub fn expires_at_ms(&self, retention: Duration) -> i64 {
+        self.deleted_at_ms
+            .saturating_add(retention.as_millis().min(i64::MAX as u128) as i64)
+    }
+
+    /// Whether the restore window has passed at `now_ms`
+    pub fn is_expired(&self, retention: Duration, now_ms: i64) -> bool {
+        now_ms >= self.expires_at_ms(retention)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expiry() {
+        let trashed = TrashedEntry {
+            entry: VectorEntry {
+                id: Some("a".to_string()),
+                vector: vec![1.0],
+                metadata: None,
+            },
+            deleted_at_ms: 1_000,
+        };
+        let retention = Duration::from_secs(1);
+        assert_eq!(trashed.id(), "a");
+        assert_eq!(trashed.expires_at_ms(retention), 2_000);
+        assert!(!trashed.is_expired(retention, 1_999));
+        assert!(trashed.is_expired(retention, 2_000));
+        assert!(!trashed.is_expired(Duration::MAX, i64::MAX - 1));
+    }
+}
@@ -1,15 +1,69 @@
 //! Main VectorDB interface
 
-use crate::error::Result;
+use crate::adaptive_ef::{
+    AdaptiveEf, AdaptiveEfConfig, DataStats, DifficultyModel, QueryDifficulty,
+};
+use crate::advanced_features::MMRSearch;
+use crate::anomaly::{
+    self, AnomalyConfig, AnomalyReport, ANOMALY_FLAG_KEY, ANOMALY_SCORE_KEY, ANOMALY_WRITE_BATCH,
+};
+use crate::audit::{AuditConfig, AuditLog, Feedback, FeedbackEvent, QueryId};
+use crate::boost::{BoostSpec, BoostedResult};
+use crate::bulk_load::{self, BulkLoadConfig, BulkLoadReport};
+use crate::centroid::{self, CentroidChange, CentroidTracker};
+use crate::classifier::{self, Classification, ClassifyConfig, ClassifyMethod};
+use crate::content_id::{content_id, IdStrategy};
+use crate::context_pack::{self, ContextPack, ContextPackConfig};
+use crate::dedupe::{
+    cosine_similarity, group_pairs, DedupeConfig, DedupeReport, DuplicateAction, DUPLICATE_OF_KEY,
+};
+use crate::delta::DeltaUpdate;
+use crate::distance::distance;
+use crate::embedding_model::{EmbeddingModel, ModelGuard};
+use crate::embedding_stats::{
+    two_nn, EmbeddingStats, StatsAccumulator, StatsConfig, INTRINSIC_DIM_CANDIDATES,
+};
+use crate::error::{Result, RuvectorError};
+use crate::health::{HealthCheckConfig, HealthReport, HealthStatus};
 use crate::index::flat::FlatIndex;
+use crate::index::graph::{NeighborGraphConfig, NeighborGraphIndex};
+use crate::index::quantized::{QuantizedIndex, SearchPrecision};
+use crate::index::tiered::{MemoryBudget, MemoryUsage, TieredIndex};
 
 #[cfg(feature = "hnsw")]
 use crate::index::hnsw::HnswIndex;
 
-use crate::index::VectorIndex;
+use crate::id_filter::IdFilter;
+use crate::index::{GraphNeighbor, GraphStats, VectorIndex};
+use crate::knn_graph::{KnnEdge, KnnGraph, Subgraph};
+use crate::maintenance::{
+    MaintenanceConfig, MaintenanceOutcome, MaintenanceRun, MaintenanceScheduler, MaintenanceTask,
+    ThrottledReader,
+};
+use crate::multi_metric::check_secondary_metric;
+use crate::normalization::{l2_normalize, NormalizationPolicy};
+use crate::op_log::{OpLog, OpLogConfig};
+use crate::privacy::{self, DpExport, DpExportConfig, PrivacyBudget, PrivacyCharge, PrivacyLedger};
+use crate::projection::{self, ProjectedPoint, ProjectionConfig, ProjectionMethod};
+use crate::quantization::ProductQuantized;
+use crate::query_template::{FusionSettings, QueryTemplate};
+use crate::query_vector::QueryVector;
+use crate::recency::TimeDecay;
+#[cfg(feature = "storage")]
+use crate::recovery::{RecoveryReport, RECOVERY_BATCH_SIZE};
+use crate::result_cache::{ResultCache, ResultCacheConfig};
+use crate::shutdown::{Lifecycle, ShutdownReport};
+use crate::slow_query::{self, SlowQueryConfig, SlowQueryEntry, SlowQueryLog, VectorStats};
+use crate::throttle::{PerformanceConfig, WriteThrottle};
+use crate::trash::{TrashedEntry, DEFAULT_TRASH_RETENTION};
 use crate::types::*;
-use parking_lot::RwLock;
+use crate::warmup::{self, WarmupConfig, WarmupPhase, WarmupProgress, WarmupReport};
+use parking_lot::{Mutex, RwLock, RwLockWriteGuard};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 // Import appropriate storage backend based on features
 #[cfg(feature = "storage")]
@@ -18,11 +72,63 @@ use crate::storage::VectorStorage;
 #[cfg(not(feature = "storage"))]
 use crate::storage_memory::MemoryStorage as VectorStorage;
 
+/// Index insertions per write-lock acquisition during batch ingest
+const INGEST_CHUNK: usize = 1024;
+
+/// Vectors sampled to train product-quantization codebooks
+const PQ_TRAINING_SAMPLE: usize = 10_000;
+
+/// k-means iterations when training product-quantization codebooks
+const PQ_TRAINING_ITERATIONS: usize = 10;
+
+/// Neighbors per node when extracting subgraphs from indexes without a graph
+const SUBGRAPH_KNN_FANOUT: usize = 16;
+
+/// Which kind of index is currently in use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IndexKind {
+    /// The index built from `options` when the database was opened
+    Configured,
+    /// [`VectorDB::build_index_from_neighbors`]
+    NeighborGraph,
+    /// [`VectorDB::build_quantized_index`]; `trained_on` is the number of
+    /// vectors product-quantization codebooks were trained on, 0 for scalar
+    Quantized { trained_on: usize },
+    /// [`VectorDB::enable_memory_budget`]
+    Tiered,
+}
+
 /// Main vector database
 pub struct VectorDB {
     storage: Arc<VectorStorage>,
     index: Arc<RwLock<Box<dyn VectorIndex>>>,
     options: DbOptions,
+    audit: RwLock<Option<Arc<AuditLog>>>,
+    templates: RwLock<HashMap<String, QueryTemplate>>,
+    /// Ids of uncommitted ingest batches, hidden from searches
+    pending: RwLock<HashSet<VectorId>>,
+    adaptive_ef: RwLock<Option<Arc<AdaptiveEf>>>,
+    result_cache: RwLock<Option<Arc<ResultCache>>>,
+    slow_queries: RwLock<Option<Arc<SlowQueryLog>>>,
+    /// Bumped by every write; cached results from older generations are stale
+    write_generation: AtomicU64,
+    lifecycle: Lifecycle,
+    index_kind: RwLock<IndexKind>,
+    /// Ids removed from the index since it was last rebuilt
+    tombstones: AtomicUsize,
+    maintenance: RwLock<Option<Arc<MaintenanceScheduler>>>,
+    model_guard: RwLock<Option<ModelGuard>>,
+    normalization: RwLock<NormalizationPolicy>,
+    secondary_metrics: RwLock<Vec<DistanceMetric>>,
+    op_log: RwLock<Option<Arc<OpLog>>>,
+    trash_retention: RwLock<Duration>,
+    delta_lock: Mutex<()>,
+    centroids: Mutex<CentroidTracker>,
+    throttle: Mutex<WriteThrottle>,
+    privacy: Mutex<PrivacyLedger>,
+    id_strategy: RwLock<IdStrategy>,
+    /// Every id stored through this handle; see [`crate::id_filter`]
+    id_filter: RwLock<IdFilter>,
 }
 
 impl VectorDB {
@@ -78,6 +184,47 @@ impl VectorDB {
         #[cfg(not(feature = "storage"))]
         let storage = Arc::new(VectorStorage::new(options.dimensions)?);
 
+        #[cfg(feature = "storage")]
+        let model_guard = storage.load_embedding_model()?;
+        #[cfg(not(feature = "storage"))]
+        let model_guard = None;
+
+        #[cfg(feature = "storage")]
+        let normalization = storage.load_normalization()?.unwrap_or_default();
+        #[cfg(not(feature = "storage"))]
+        let normalization = NormalizationPolicy::None;
+
+        #[cfg(feature = "storage")]
+        let secondary_metrics = storage.load_secondary_metrics()?.unwrap_or_default();
+        #[cfg(not(feature = "storage"))]
+        let secondary_metrics = Vec::new();
+
+        #[cfg(feature = "storage")]
+        let trash_retention = storage
+            .load_trash_retention()?
+            .map_or(DEFAULT_TRASH_RETENTION, Duration::from_secs);
+        #[cfg(not(feature = "storage"))]
+        let trash_retention = DEFAULT_TRASH_RETENTION;
+
+        #[cfg(feature = "storage")]
+        let centroid_groups = storage.load_centroid_groups()?.unwrap_or_default();
+        #[cfg(not(feature = "storage"))]
+        let centroid_groups: Vec<String> = Vec::new();
+        #[cfg(feature = "storage")]
+        let id_strategy = storage.load_id_strategy()?.unwrap_or_default();
+        #[cfg(not(feature = "storage"))]
+        let id_strategy = IdStrategy::default();
+
+        #[cfg(feature = "storage")]
+        let privacy = storage.load_privacy_ledger()?.unwrap_or_default();
+        #[cfg(not(feature = "storage"))]
+        let privacy = PrivacyLedger::default();
+
+        let mut centroids = CentroidTracker::default();
+        for key in &centroid_groups {
+            centroids.define(key);
+        }
+
         // Choose index based on configuration and available features
         let mut index: Box<dyn VectorIndex> = if let Some(hnsw_config) = &options.hnsw_config {
             #[cfg(feature = "hnsw")]
@@ -98,6 +245,9 @@ impl VectorDB {
             Box::new(FlatIndex::new(options.dimensions, options.distance_metric))
         };
 
+        // Sized with headroom so steady ingestion doesn't trigger a rebuild
+        let id_filter = RwLock::new(IdFilter::with_capacity(2 * storage.len()?));
+
         // Rebuild index from persisted vectors if storage is not empty
         // This fixes the bug where search() returns empty results after restart
         #[cfg(feature = "storage")]
@@ -112,6 +262,7 @@ impl VectorDB {
                 // Batch load all vectors for efficient index rebuilding
                 let mut entries = Vec::with_capacity(stored_ids.len());
                 for id in stored_ids {
+                    id_filter.write().insert(&id);
                     if let Some(entry) = storage.get(&id)? {
                         entries.push((id, entry.vector));
                     }
@@ -124,11 +275,38 @@ impl VectorDB {
             }
         }
 
-        Ok(Self {
+        let db = Self {
             storage,
             index: Arc::new(RwLock::new(index)),
             options,
-        })
+            audit: RwLock::new(None),
+            templates: RwLock::new(HashMap::new()),
+            pending: RwLock::new(HashSet::new()),
+            adaptive_ef: RwLock::new(None),
+            result_cache: RwLock::new(None),
+            slow_queries: RwLock::new(None),
+            write_generation: AtomicU64::new(0),
+            lifecycle: Lifecycle::new(),
+            index_kind: RwLock::new(IndexKind::Configured),
+            tombstones: AtomicUsize::new(0),
+            maintenance: RwLock::new(None),
+            model_guard: RwLock::new(model_guard),
+            normalization: RwLock::new(normalization),
+            secondary_metrics: RwLock::new(secondary_metrics),
+            op_log: RwLock::new(None),
+            trash_retention: RwLock::new(trash_retention),
+            delta_lock: Mutex::new(()),
+            centroids: Mutex::new(centroids),
+            throttle: Mutex::new(WriteThrottle::default()),
+            privacy: Mutex::new(privacy),
+            id_strategy: RwLock::new(id_strategy),
+            id_filter,
+        };
+        if db.centroids.lock().is_active() {
+            // Running sums are not persisted; recompute them from the members
+            db.rebuild_centroids()?;
+        }
+        Ok(db)
     }
 
     /// Create with default options
@@ -138,253 +316,5077 @@ impl VectorDB {
         Self::new(options)
     }
 
+    /// Salvage the readable entries of a damaged database into a new one
+    ///
+    /// `source` is the damaged storage file and is only read.
+    /// `options.storage_path` is where the new database is created; it must
+    /// not exist yet. The source's stored configuration is used when it is
+    /// readable, otherwise the rest of `options`. See [`crate::recovery`].
+    #[cfg(feature = "storage")]
+    pub fn recover<P: AsRef<std::path::Path>>(
+        source: P,
+        mut options: DbOptions,
+    ) -> Result<(Self, RecoveryReport)> {
+        if std::path::Path::new(&options.storage_path).exists() {
+            return Err(RuvectorError::InvalidPath(format!(
+                "Recovery target {} already exists",
+                options.storage_path
+            )));
+        }
+
+        let salvage = VectorStorage::salvage(source)?;
+        if let Some(config) = &salvage.config {
+            options = DbOptions {
+                storage_path: options.storage_path.clone(),
+                ..config.clone()
+            };
+        }
+
+        // Settings must be in place before the database loads them
+        VectorStorage::new(&options.storage_path, options.dimensions)?.import_salvaged(&salvage)?;
+        let db = Self::new(options)?;
+
+        let mut report = RecoveryReport {
+            irrecoverable_ids: salvage.irrecoverable,
+            metadata_lost: salvage.metadata_lost,
+            trash_recovered: salvage.trash.len(),
+            config_recovered: salvage.config.is_some(),
+            complete: salvage.complete,
+            ..RecoveryReport::default()
+        };
+        let mut entries = Vec::with_capacity(salvage.entries.len());
+        for entry in salvage.entries {
+            if centroid::is_centroid(&entry) {
+                continue;
+            }
+            if entry.vector.len() == db.options.dimensions {
+                entries.push(entry);
+            } else if let Some(id) = entry.id {
+                report.irrecoverable_ids.push(id);
+            }
+        }
+        report.irrecoverable_ids.sort();
+        report.irrecoverable = report.irrecoverable_ids.len();
+
+        let mut entries = entries.into_iter().peekable();
+        while entries.peek().is_some() {
+            let batch: Vec<VectorEntry> = entries.by_ref().take(RECOVERY_BATCH_SIZE).collect();
+            report.recovered += db.insert_batch(batch)?.len();
+        }
+        Ok((db, report))
+    }
+
     /// Insert a vector entry
-    pub fn insert(&self, entry: VectorEntry) -> Result<VectorId> {
+    pub fn insert(&self, mut entry: VectorEntry) -> Result<VectorId> {
+        let _write = self.lifecycle.begin_write()?;
+        self.check_backpressure()?;
+        self.normalize_inserts(std::slice::from_mut(&mut entry));
+        if let Some(id) = self.content_id_for(&entry) {
+            if self.storage.get(&id)?.is_some() {
+                return Ok(id);
+            }
+            entry.id = Some(id);
+        }
+        let replaced = self.centroid_members(entry.id.as_deref())?;
         let id = self.storage.insert(&entry)?;
+        self.remember_ids(std::slice::from_ref(&id))?;
+        self.log_ops(|log| log.record_insert(&id, &entry));
+        self.update_centroids(&replaced, std::slice::from_ref(&entry))?;
 
         // Add to index
-        let mut index = self.index.write();
+        let mut index = self.index_write();
         index.add(id.clone(), entry.vector)?;
 
         Ok(id)
     }
 
     /// Insert multiple vectors in a batch
+    ///
+    /// Searches running while the batch is indexed do not see any of it; the
+    /// whole batch becomes visible at once when it completes.
     pub fn insert_batch(&self, entries: Vec<VectorEntry>) -> Result<Vec<VectorId>> {
-        let ids = self.storage.insert_batch(&entries)?;
+        // Held until the commit so a shutdown waits for the whole batch
+        let _write = self.lifecycle.begin_write()?;
+        let mut batch = self.begin_ingest();
+        let ids = batch.insert_unguarded(entries)?;
+        batch.commit()?;
+        Ok(ids)
+    }
 
-        // Add to index
-        let mut index = self.index.write();
-        let index_entries: Vec<_> = ids
-            .iter()
-            .zip(entries.iter())
-            .map(|(id, entry)| (id.clone(), entry.vector.clone()))
-            .collect();
+    /// Load many entries with deferred index linking
+    ///
+    /// Much faster than repeated [`VectorDB::insert`] for initial loads; see
+    /// [`crate::bulk_load`] for how memory is bounded. If loading fails part
+    /// way, the entries stored so far are indexed and kept.
+    pub fn bulk_load<I>(&self, entries: I) -> Result<BulkLoadReport>
+    where
+        I: IntoIterator<Item = VectorEntry>,
+    {
+        self.bulk_load_with(entries, &BulkLoadConfig::default())
+    }
 
-        index.add_batch(index_entries)?;
+    /// [`VectorDB::bulk_load`] with explicit settings
+    pub fn bulk_load_with<I>(&self, entries: I, config: &BulkLoadConfig) -> Result<BulkLoadReport>
+    where
+        I: IntoIterator<Item = VectorEntry>,
+    {
+        let _write = self.lifecycle.begin_write()?;
+        self.check_backpressure()?;
+        let started = std::time::Instant::now();
+        let mut report = BulkLoadReport::default();
+        let mut staged = Vec::new();
+        let mut loaded_ids = Vec::new();
 
-        Ok(ids)
+        let stored = self.bulk_store(entries, config, &mut report, &mut staged, &mut loaded_ids);
+        let linked = self.link_staged(&mut staged, &mut report);
+
+        let mut pending = self.pending.write();
+        for id in &loaded_ids {
+            pending.remove(id);
+        }
+        self.mark_written();
+        drop(pending);
+        stored?;
+        linked?;
+
+        report.elapsed_ms = started.elapsed().as_millis() as u64;
+        tracing::info!(
+            "Bulk loaded {} vectors in {}ms ({} index passes, {} throttle waits)",
+            report.inserted,
+            report.elapsed_ms,
+            report.index_passes,
+            report.throttle_waits
+        );
+        Ok(report)
     }
 
-    /// Search for similar vectors
-    pub fn search(&self, query: SearchQuery) -> Result<Vec<SearchResult>> {
-        let index = self.index.read();
-        let mut results = index.search(&query.vector, query.k)?;
+    fn bulk_store<I>(
+        &self,
+        entries: I,
+        config: &BulkLoadConfig,
+        report: &mut BulkLoadReport,
+        staged: &mut Vec<(VectorId, Vec<f32>)>,
+        loaded_ids: &mut Vec<VectorId>,
+    ) -> Result<()>
+    where
+        I: IntoIterator<Item = VectorEntry>,
+    {
+        let mut entries = entries.into_iter();
+        let mut staged_bytes = 0;
 
-        // Enrich results with full data if needed
-        for result in &mut results {
-            if let Ok(Some(entry)) = self.storage.get(&result.id) {
-                result.vector = Some(entry.vector);
-                result.metadata = entry.metadata;
+        loop {
+            let mut chunk: Vec<VectorEntry> =
+                entries.by_ref().take(config.chunk_size.max(1)).collect();
+            if chunk.is_empty() {
+                return Ok(());
             }
-        }
+            self.normalize_inserts(&mut chunk);
+            let received = chunk.len();
+            self.assign_content_ids(&mut chunk)?;
+            report.skipped += received - chunk.len();
+            let replaced = self.centroid_members(chunk.iter().filter_map(|e| e.id.as_deref()))?;
 
-        // Apply metadata filters if specified
-        if let Some(filter) = &query.filter {
-            results.retain(|r| {
-                if let Some(metadata) = &r.metadata {
-                    filter
-                        .iter()
-                        .all(|(key, value)| metadata.get(key).map_or(false, |v| v == value))
-                } else {
-                    false
+            let ids = self.storage.insert_batch(&chunk)?;
+            self.remember_ids(&ids)?;
+            self.log_ops(|log| {
+                for (id, entry) in ids.iter().zip(&chunk) {
+                    log.record_insert(id, entry);
                 }
             });
+            self.update_centroids(&replaced, &chunk)?;
+            self.pending.write().extend(ids.iter().cloned());
+            loaded_ids.extend(ids.iter().cloned());
+            report.inserted += ids.len();
+            report.chunks += 1;
+            for (id, entry) in ids.into_iter().zip(chunk) {
+                staged_bytes += entry.vector.len() * std::mem::size_of::<f32>();
+                staged.push((id, entry.vector));
+            }
+
+            if staged_bytes >= config.max_staged_bytes {
+                self.link_staged(staged, report)?;
+                staged_bytes = 0;
+            }
+
+            if bulk_load::under_memory_pressure(config) {
+                // Release the staging buffer, then give the system time to recover
+                self.link_staged(staged, report)?;
+                staged_bytes = 0;
+                for _ in 0..config.max_throttle_waits {
+                    std::thread::sleep(config.throttle_delay);
+                    report.throttle_waits += 1;
+                    if !bulk_load::under_memory_pressure(config) {
+                        break;
+                    }
+                }
+            }
         }
+    }
 
-        Ok(results)
+    fn link_staged(
+        &self,
+        staged: &mut Vec<(VectorId, Vec<f32>)>,
+        report: &mut BulkLoadReport,
+    ) -> Result<()> {
+        if staged.is_empty() {
+            return Ok(());
+        }
+        self.index_write().add_batch(std::mem::take(staged))?;
+        report.index_passes += 1;
+        Ok(())
     }
 
-    /// Delete a vector by ID
-    pub fn delete(&self, id: &str) -> Result<bool> {
-        let deleted_storage = self.storage.delete(id)?;
+    /// Replace the index with a graph built from pre-computed neighbor lists
+    ///
+    /// `lists` maps stored ids to their nearest neighbors' ids, e.g. an exact
+    /// k-NN graph computed offline. Stored ids without a list are still
+    /// reachable through reverse edges. The graph lives in memory only;
+    /// reopening the database rebuilds the configured index.
+    pub fn build_index_from_neighbors(
+        &self,
+        lists: &HashMap<VectorId, Vec<VectorId>>,
+        config: NeighborGraphConfig,
+    ) -> Result<()> {
+        let mut ids = self.storage.all_ids()?;
+        ids.sort();
+        let positions: HashMap<&VectorId, usize> =
+            ids.iter().enumerate().map(|(i, id)| (id, i)).collect();
 
-        if deleted_storage {
-            let mut index = self.index.write();
-            let _ = index.remove(&id.to_string())?;
+        if let Some(unknown) = lists.keys().find(|id| !positions.contains_key(id)) {
+            return Err(RuvectorError::VectorNotFound(unknown.clone()));
         }
 
-        Ok(deleted_storage)
+        let mut neighbor_lists = Vec::with_capacity(ids.len());
+        for id in &ids {
+            let list = match lists.get(id) {
+                Some(neighbors) => neighbors
+                    .iter()
+                    .map(|n| {
+                        positions
+                            .get(n)
+                            .copied()
+                            .ok_or_else(|| RuvectorError::VectorNotFound(n.clone()))
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+                None => Vec::new(),
+            };
+            neighbor_lists.push(list);
+        }
+
+        let mut vectors = Vec::with_capacity(ids.len());
+        for id in &ids {
+            let entry = self
+                .storage
+                .get(id)?
+                .ok_or_else(|| RuvectorError::VectorNotFound(id.clone()))?;
+            vectors.push((id.clone(), entry.vector));
+        }
+
+        let graph = NeighborGraphIndex::from_neighbor_lists(
+            self.options.dimensions,
+            self.options.distance_metric,
+            vectors,
+            neighbor_lists,
+            config,
+        )?;
+        tracing::info!(
+            "Built graph index from {} neighbor lists over {} vectors",
+            lists.len(),
+            ids.len()
+        );
+        *self.index_write() = Box::new(graph);
+        *self.index_kind.write() = IndexKind::NeighborGraph;
+        Ok(())
     }
 
-    /// Get a vector by ID
-    pub fn get(&self, id: &str) -> Result<Option<VectorEntry>> {
-        self.storage.get(id)
+    /// Replace the index with one that searches quantized codes
+    ///
+    /// Uses `options.quantization`: `Scalar` quantizes each vector to int8,
+    /// `Product` trains codebooks on a sample of the stored vectors. Searches
+    /// then compare the full-precision query against codes through
+    /// asymmetric distances; use [`VectorDB::search_with_precision`] to
+    /// rerank with the stored vectors. Like
+    /// [`VectorDB::build_index_from_neighbors`], the index lives in memory
+    /// only.
+    pub fn build_quantized_index(&self) -> Result<()> {
+        let ids = self.storage.all_ids()?;
+        let mut vectors = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(entry) = self.storage.get(&id)? {
+                vectors.push((id, entry.vector));
+            }
+        }
+
+        let (index, kind) = self.quantized_index(vectors)?;
+        *self.index_write() = index;
+        *self.index_kind.write() = kind;
+        Ok(())
     }
 
-    /// Get the number of vectors
-    pub fn len(&self) -> Result<usize> {
-        self.storage.len()
+    fn quantized_index(
+        &self,
+        vectors: Vec<(VectorId, Vec<f32>)>,
+    ) -> Result<(Box<dyn VectorIndex>, IndexKind)> {
+        let dimensions = self.options.dimensions;
+        let metric = self.options.distance_metric;
+        let mut trained_on = 0;
+        let mut index = match &self.options.quantization {
+            Some(QuantizationConfig::Scalar) => QuantizedIndex::scalar(dimensions, metric),
+            Some(QuantizationConfig::Product { subspaces, k }) => {
+                let step = (vectors.len() / PQ_TRAINING_SAMPLE).max(1);
+                let sample: Vec<Vec<f32>> = vectors
+                    .iter()
+                    .step_by(step)
+                    .map(|(_, v)| v.clone())
+                    .collect();
+                let pq = ProductQuantized::train(&sample, *subspaces, *k, PQ_TRAINING_ITERATIONS)?;
+                trained_on = vectors.len();
+                QuantizedIndex::product(dimensions, metric, pq)?
+            }
+            other => {
+                return Err(RuvectorError::InvalidParameter(format!(
+                    "asymmetric search needs scalar or product quantization, got {:?}",
+                    other
+                )))
+            }
+        };
+
+        let count = vectors.len();
+        index.add_batch(vectors)?;
+        tracing::info!(
+            "Built quantized index over {} vectors ({} code bytes)",
+            count,
+            index.code_bytes()
+        );
+        Ok((Box::new(index), IndexKind::Quantized { trained_on }))
     }
 
-    /// Check if database is empty
-    pub fn is_empty(&self) -> Result<bool> {
-        self.storage.is_empty()
+    /// Keep resident vector data within `budget`
+    ///
+    /// Replaces the index with a [`TieredIndex`] built from storage: the
+    /// most frequently returned vectors stay in full precision, less used
+    /// ones are scalar-quantized, and the coldest keep only binary codes in
+    /// memory and are read back from storage, or from the budget's
+    /// [`ColdFile`](crate::index::tiered::ColdFile), to rescore them. See
+    /// [`crate::index::tiered`]. HNSW graphs need every vector resident, so
+    /// a budgeted database searches by brute force over the tiers. Like
+    /// [`VectorDB::build_quantized_index`], the choice lasts until the
+    /// database is reopened.
+    pub fn enable_memory_budget(&self, budget: MemoryBudget) -> Result<MemoryUsage> {
+        let ids = self.storage.all_ids()?;
+        let mut vectors = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(entry) = self.storage.get(&id)? {
+                vectors.push((id, entry.vector));
+            }
+        }
+
+        let mut index = TieredIndex::new(
+            self.options.dimensions,
+            self.options.distance_metric,
+            budget,
+            Arc::clone(&self.storage),
+        )?;
+        index.add_batch(vectors)?;
+        let usage = index.usage();
+        tracing::info!(
+            "Memory budget of {} bytes: {} hot, {} warm, {} cold ({} bytes resident)",
+            usage.budget_bytes,
+            usage.hot,
+            usage.warm,
+            usage.cold,
+            usage.used_bytes
+        );
+        *self.index_write() = Box::new(index);
+        *self.index_kind.write() = IndexKind::Tiered;
+        Ok(usage)
     }
 
-    /// Get database options
-    pub fn options(&self) -> &DbOptions {
-        &self.options
+    /// Resident memory of the index, if a memory budget is enabled
+    pub fn memory_usage(&self) -> Option<MemoryUsage> {
+        self.index.read().memory_usage()
     }
 
-    /// Get all vector IDs (for iteration/serialization)
-    pub fn keys(&self) -> Result<Vec<String>> {
-        self.storage.all_ids()
+    /// Shape of the HNSW graph, for debugging recall problems
+    ///
+    /// Fails with [`RuvectorError::InvalidParameter`] if the current index
+    /// is not graph-based (flat or quantized).
+    pub fn graph_stats(&self) -> Result<GraphStats> {
+        self.index.read().graph_stats().ok_or_else(|| {
+            RuvectorError::InvalidParameter("Index does not expose a graph".to_string())
+        })
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::path::Path;
-    use tempfile::tempdir;
+    /// Graph neighbors of `id` on `level`, nearest first
+    pub fn neighbors(&self, id: &str, level: usize) -> Result<Vec<GraphNeighbor>> {
+        let mut neighbors = self.index.read().neighbors(&id.to_string(), level)?;
+        neighbors.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        Ok(neighbors)
+    }
 
-    #[test]
-    fn test_vector_db_creation() -> Result<()> {
-        let dir = tempdir().unwrap();
-        let mut options = DbOptions::default();
-        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
-        options.dimensions = 3;
+    /// Extract the neighborhood within `hops` edges of `ids`
+    ///
+    /// Follows level-0 edges of the HNSW graph, or each vector's 16 nearest
+    /// neighbors for indexes without a graph. Nodes are added breadth-first
+    /// until there are `max_nodes`; edges are kept between any two nodes of
+    /// the result. Deleted nodes still linked in the graph are left out.
+    pub fn subgraph(&self, ids: &[VectorId], hops: usize, max_nodes: usize) -> Result<Subgraph> {
+        if max_nodes == 0 {
+            return Err(RuvectorError::InvalidParameter(
+                "max_nodes must be greater than zero".to_string(),
+            ));
+        }
 
-        let db = VectorDB::new(options)?;
-        assert!(db.is_empty()?);
+        let index = self.index.read();
+        let has_graph = index.has_graph();
+        let neighbors_of = |id: &VectorId| -> Result<Vec<(VectorId, f32)>> {
+            if has_graph {
+                return Ok(index
+                    .neighbors(id, 0)?
+                    .into_iter()
+                    .filter_map(|n| Some((n.id?, n.distance)))
+                    .collect());
+            }
+            let entry = self
+                .storage
+                .get(id)?
+                .ok_or_else(|| RuvectorError::VectorNotFound(id.clone()))?;
+            Ok(index
+                .search(&entry.vector, SUBGRAPH_KNN_FANOUT + 1)?
+                .into_iter()
+                .filter(|n| &n.id != id)
+                .take(SUBGRAPH_KNN_FANOUT)
+                .map(|n| (n.id, n.score))
+                .collect())
+        };
 
-        Ok(())
+        let mut graph = Subgraph {
+            nodes: Vec::new(),
+            hops: Vec::new(),
+            edges: Vec::new(),
+        };
+        let mut position = HashMap::new();
+        for id in ids {
+            if graph.nodes.len() < max_nodes && !position.contains_key(id) {
+                position.insert(id.clone(), graph.nodes.len());
+                graph.nodes.push(id.clone());
+                graph.hops.push(0);
+            }
+        }
+
+        // Nodes on the last hop are expanded too, for edges among the result
+        let mut next = 0;
+        while next < graph.nodes.len() {
+            let hop = graph.hops[next];
+            let source = graph.nodes[next].clone();
+            next += 1;
+            for (target, distance) in neighbors_of(&source)? {
+                if !position.contains_key(&target) {
+                    if hop == hops || graph.nodes.len() == max_nodes {
+                        continue;
+                    }
+                    position.insert(target.clone(), graph.nodes.len());
+                    graph.nodes.push(target.clone());
+                    graph.hops.push(hop + 1);
+                }
+                graph.edges.push(KnnEdge {
+                    source: source.clone(),
+                    target,
+                    distance,
+                });
+            }
+        }
+        Ok(graph)
     }
 
-    #[test]
-    fn test_insert_and_search() -> Result<()> {
-        let dir = tempdir().unwrap();
-        let mut options = DbOptions::default();
-        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
-        options.dimensions = 3;
-        options.distance_metric = DistanceMetric::Euclidean; // Use Euclidean for clearer test
-        options.hnsw_config = None; // Use flat index for testing
+    /// Search with an explicit accuracy/speed trade-off
+    ///
+    /// [`SearchPrecision::Approximate`] returns index distances as-is.
+    /// [`SearchPrecision::Rerank`] fetches `k * oversample` candidates and
+    /// rescores them against the stored full-precision vectors, recovering
+    /// most of the recall lost to quantization at the cost of extra reads.
+    pub fn search_with_precision(
+        &self,
+        query: SearchQuery,
+        precision: SearchPrecision,
+    ) -> Result<Vec<SearchResult>> {
+        let oversample = match precision {
+            SearchPrecision::Approximate => return self.search(query),
+            SearchPrecision::Rerank { oversample } => oversample.max(1),
+        };
 
-        let db = VectorDB::new(options)?;
+        let k = query.k;
+        let mut query = query;
+        query.k = k * oversample;
+        let mut results = self.search_unaudited(&query)?;
+        // Rescore against the query the index searched with
+        let searched = self.normalize_query(&query);
+        for result in &mut results {
+            if let Some(vector) = &result.vector {
+                result.score = distance(&searched.vector, vector, self.options.distance_metric)?;
+            }
+        }
+        results.sort_by(|a, b| a.score.total_cmp(&b.score));
+        results.truncate(k);
 
-        // Insert vectors
-        db.insert(VectorEntry {
-            id: Some("v1".to_string()),
-            vector: vec![1.0, 0.0, 0.0],
-            metadata: None,
-        })?;
+        if let Some(log) = self.audit_log() {
+            log.record_search(&query.vector, k, &results);
+        }
+        Ok(results)
+    }
 
-        db.insert(VectorEntry {
-            id: Some("v2".to_string()),
-            vector: vec![0.0, 1.0, 0.0],
-            metadata: None,
+    /// Pick `ef_search` per query from its predicted difficulty
+    ///
+    /// Samples stored vectors for normalization statistics and installs the
+    /// shipped difficulty model; call [`VectorDB::train_adaptive_ef`] to fit
+    /// it to this data. Queries that set `ef_search` are unaffected.
+    pub fn enable_adaptive_ef(&self, config: AdaptiveEfConfig) -> Result<()> {
+        let ids = self.storage.all_ids()?;
+        let step = (ids.len() / config.sample_size.max(1)).max(1);
+        let mut sample = Vec::new();
+        for id in ids.iter().step_by(step) {
+            if let Some(entry) = self.storage.get(id)? {
+                sample.push(entry.vector);
+            }
+        }
+        let stats = DataStats::from_vectors(&sample).ok_or_else(|| {
+            RuvectorError::InvalidInput("adaptive ef_search needs stored vectors".to_string())
         })?;
 
-        db.insert(VectorEntry {
-            id: Some("v3".to_string()),
-            vector: vec![0.0, 0.0, 1.0],
-            metadata: None,
-        })?;
+        *self.adaptive_ef.write() = Some(Arc::new(AdaptiveEf {
+            config,
+            stats,
+            model: DifficultyModel::default(),
+        }));
+        Ok(())
+    }
 
-        // Search for exact match
-        let results = db.search(SearchQuery {
-            vector: vec![1.0, 0.0, 0.0],
-            k: 2,
-            filter: None,
-            ef_search: None,
+    /// Fit the difficulty model to this database
+    ///
+    /// Builds `queries` synthetic queries (midpoints of random stored pairs),
+    /// finds for each the smallest efSearch on a doubling ladder that reaches
+    /// the configured recall@k against brute-force ground truth, and fits
+    /// the model to those labels. Cost is `queries` full scans; run it
+    /// offline or after large loads.
+    pub fn train_adaptive_ef(&self, queries: usize, k: usize) -> Result<DifficultyModel> {
+        use rand::Rng;
+
+        let adaptive = self.adaptive_ef().ok_or_else(|| {
+            RuvectorError::InvalidInput("adaptive ef_search is not enabled".to_string())
         })?;
+        let config = &adaptive.config;
 
-        assert!(results.len() >= 1);
-        assert_eq!(results[0].id, "v1", "First result should be exact match");
-        assert!(
-            results[0].score < 0.01,
-            "Exact match should have ~0 distance"
-        );
+        let mut data = Vec::new();
+        for id in self.storage.all_ids()? {
+            if let Some(entry) = self.storage.get(&id)? {
+                data.push((id, entry.vector));
+            }
+        }
+        if data.len() < 2 || k == 0 {
+            return Err(RuvectorError::InvalidInput(
+                "training needs at least two stored vectors and k > 0".to_string(),
+            ));
+        }
 
-        Ok(())
-    }
+        let mut ladder = Vec::new();
+        let mut ef = config.min_ef.max(1);
+        while ef < config.max_ef {
+            ladder.push(ef);
+            ef *= 2;
+        }
+        ladder.push(config.max_ef);
 
-    /// Test that search works after simulated restart (new VectorDB instance)
-    /// This verifies the fix for issue #30: HNSW index not rebuilt from storage
-    #[test]
-    #[cfg(feature = "storage")]
-    fn test_search_after_restart() -> Result<()> {
-        let dir = tempdir().unwrap();
-        let db_path = dir.path().join("persist.db").to_string_lossy().to_string();
+        let mut rng = rand::thread_rng();
+        let mut samples = Vec::with_capacity(queries);
+        let index = self.index.read();
+        for _ in 0..queries {
+            let a = &data[rng.gen_range(0..data.len())].1;
+            let b = &data[rng.gen_range(0..data.len())].1;
+            let query: Vec<f32> = a.iter().zip(b).map(|(x, y)| (x + y) / 2.0).collect();
 
-        // Phase 1: Create database and insert vectors
-        {
-            let mut options = DbOptions::default();
-            options.storage_path = db_path.clone();
-            options.dimensions = 3;
-            options.distance_metric = DistanceMetric::Euclidean;
-            options.hnsw_config = None;
+            let mut truth = data
+                .iter()
+                .map(|(id, v)| Ok((id, distance(&query, v, self.options.distance_metric)?)))
+                .collect::<Result<Vec<_>>>()?;
+            truth.sort_by(|x, y| x.1.total_cmp(&y.1));
+            let truth: HashSet<&str> = truth
+                .into_iter()
+                .take(k)
+                .map(|(id, _)| id.as_str())
+                .collect();
 
-            let db = VectorDB::new(options)?;
+            let probe = index.search_with_ef(&query, config.probe_k, config.probe_ef)?;
+            let features = adaptive.features(&query, &probe);
 
-            db.insert(VectorEntry {
-                id: Some("v1".to_string()),
-                vector: vec![1.0, 0.0, 0.0],
-                metadata: None,
-            })?;
+            let mut ef_needed = config.max_ef;
+            for &ef in &ladder {
+                let found = index.search_with_ef(&query, k, ef.max(k))?;
+                let hits = found
+                    .iter()
+                    .filter(|r| truth.contains(r.id.as_str()))
+                    .count();
+                if hits as f32 / truth.len() as f32 >= config.recall_target {
+                    ef_needed = ef;
+                    break;
+                }
+            }
+            samples.push((features, adaptive.label_for(ef_needed)));
+        }
+        drop(index);
 
-            db.insert(VectorEntry {
-                id: Some("v2".to_string()),
-                vector: vec![0.0, 1.0, 0.0],
-                metadata: None,
-            })?;
+        let model = DifficultyModel::fit(&samples, 300, 0.5);
+        *self.adaptive_ef.write() = Some(Arc::new(AdaptiveEf {
+            model: model.clone(),
+            ..(*adaptive).clone()
+        }));
+        Ok(model)
+    }
 
-            db.insert(VectorEntry {
-                id: Some("v3".to_string()),
-                vector: vec![0.7, 0.7, 0.0],
-                metadata: None,
+    /// Stop adapting `ef_search`, returning the predictor that was in use
+    pub fn disable_adaptive_ef(&self) -> Option<Arc<AdaptiveEf>> {
+        self.adaptive_ef.write().take()
+    }
+
+    /// The active adaptive efSearch predictor, if enabled
+    pub fn adaptive_ef(&self) -> Option<Arc<AdaptiveEf>> {
+        self.adaptive_ef.read().clone()
+    }
+
+    /// Cache search results under semantic keys
+    ///
+    /// See [`crate::result_cache`]. Any write invalidates cached results.
+    /// If a cache is already enabled it is kept.
+    pub fn enable_result_cache(&self, config: ResultCacheConfig) -> Arc<ResultCache> {
+        self.result_cache
+            .write()
+            .get_or_insert_with(|| Arc::new(ResultCache::new(config)))
+            .clone()
+    }
+
+    /// Refuse inserts and updates while the index is past health thresholds
+    ///
+    /// See [`crate::throttle`]. Thresholds are not persisted.
+    pub fn set_performance_config(&self, config: PerformanceConfig) -> Result<()> {
+        config.validate()?;
+        self.throttle.lock().configure(config);
+        Ok(())
+    }
+
+    /// Current write throttling thresholds
+    pub fn performance_config(&self) -> PerformanceConfig {
+        self.throttle.lock().config.clone()
+    }
+
+    /// Fail with [`RuvectorError::Backpressure`] if a write would be refused
+    ///
+    /// Lets callers hold writes in their own queue until the index has been
+    /// compacted.
+    pub fn check_backpressure(&self) -> Result<()> {
+        let mut throttle = self.throttle.lock();
+        if !throttle.config.is_enabled() || !self.has_graph() {
+            return Ok(());
+        }
+        let tombstones = self.tombstones.load(Ordering::Relaxed);
+        let nodes = self.index.read().len() + tombstones;
+        let generation = self.write_generation.load(Ordering::Acquire);
+        throttle.check(tombstones, nodes, generation, || {
+            self.index.read().graph_stats()
+        })
+    }
+
+    /// Whether the current index is a proximity graph that keeps tombstones
+    fn has_graph(&self) -> bool {
+        match *self.index_kind.read() {
+            IndexKind::Configured => self.options.hnsw_config.is_some(),
+            IndexKind::NeighborGraph => true,
+            IndexKind::Quantized { .. } | IndexKind::Tiered => false,
+        }
+    }
+
+    /// Stop caching results, returning the cache that was in use
+    pub fn disable_result_cache(&self) -> Option<Arc<ResultCache>> {
+        self.result_cache.write().take()
+    }
+
+    /// The active result cache, if enabled
+    pub fn result_cache(&self) -> Option<Arc<ResultCache>> {
+        self.result_cache.read().clone()
+    }
+
+    /// Record searches slower than a threshold
+    ///
+    /// See [`crate::slow_query`]. If a log is already enabled it is kept.
+    pub fn enable_slow_query_log(&self, config: SlowQueryConfig) -> Arc<SlowQueryLog> {
+        self.slow_queries
+            .write()
+            .get_or_insert_with(|| Arc::new(SlowQueryLog::new(config)))
+            .clone()
+    }
+
+    /// Stop recording slow searches, returning the log that was in use
+    pub fn disable_slow_query_log(&self) -> Option<Arc<SlowQueryLog>> {
+        self.slow_queries.write().take()
+    }
+
+    /// The active slow query log, if enabled
+    pub fn slow_query_log(&self) -> Option<Arc<SlowQueryLog>> {
+        self.slow_queries.read().clone()
+    }
+
+    /// Record every mutating operation for later replay
+    ///
+    /// See [`crate::op_log`]. If a log is already enabled it is kept.
+    pub fn enable_op_log(&self, config: OpLogConfig) -> Arc<OpLog> {
+        self.op_log
+            .write()
+            .get_or_insert_with(|| Arc::new(OpLog::new(config)))
+            .clone()
+    }
+
+    /// Stop recording operations, returning the log that was in use
+    pub fn disable_op_log(&self) -> Option<Arc<OpLog>> {
+        self.op_log.write().take()
+    }
+
+    /// The active op log, if enabled
+    pub fn op_log(&self) -> Option<Arc<OpLog>> {
+        self.op_log.read().clone()
+    }
+
+    /// Record the embedding model this database's vectors come from
+    ///
+    /// See [`crate::embedding_model`]. The record is persisted. Replacing it
+    /// with a different model is refused once vectors are stored; re-embed
+    /// into a new database instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuvectorError::DimensionMismatch`] if the model's dimensions
+    /// differ from the database's, and [`RuvectorError::ModelMismatch`] if a
+    /// different model is already recorded for stored vectors
+    pub fn set_embedding_model(&self, model: EmbeddingModel, strict: bool) -> Result<()> {
+        if model.dimensions != self.options.dimensions {
+            return Err(RuvectorError::DimensionMismatch {
+                expected: self.options.dimensions,
+                actual: model.dimensions,
+            });
+        }
+
+        let mut current = self.model_guard.write();
+        if let Some(existing) = current.as_ref() {
+            if existing.model != model && !self.storage.is_empty()? {
+                return Err(RuvectorError::ModelMismatch {
+                    expected: existing.model.reference(),
+                    actual: model.reference(),
+                });
+            }
+        }
+
+        let guard = ModelGuard { model, strict };
+        #[cfg(feature = "storage")]
+        self.storage.save_embedding_model(&guard)?;
+        *current = Some(guard);
+        Ok(())
+    }
+
+    /// The recorded embedding model and enforcement mode, if any
+    pub fn embedding_model(&self) -> Option<ModelGuard> {
+        self.model_guard.read().clone()
+    }
+
+    /// Check a declared `"name"` or `"name@version"` against the recorded
+    /// model; always passes when none is recorded
+    pub fn check_embedding_model(&self, declared: &str) -> Result<()> {
+        match self.model_guard.read().as_ref() {
+            Some(guard) => guard.check(declared),
+            None => Ok(()),
+        }
+    }
+
+    /// Search with a query embedded by the declared model
+    pub fn search_with_model(&self, query: SearchQuery, model: &str) -> Result<Vec<SearchResult>> {
+        self.check_embedding_model(model)?;
+        self.search(query)
+    }
+
+    /// Insert vectors embedded by the declared model
+    pub fn insert_batch_with_model(
+        &self,
+        entries: Vec<VectorEntry>,
+        model: &str,
+    ) -> Result<Vec<VectorId>> {
+        self.check_embedding_model(model)?;
+        self.insert_batch(entries)
+    }
+
+    /// Set when vectors are L2-normalized
+    ///
+    /// See [`crate::normalization`]. The policy is persisted. Starting to
+    /// normalize inserts is refused once unnormalized vectors are stored, so
+    /// a database never mixes the two.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuvectorError::InvalidInput`] if `policy` normalizes
+    /// inserts, the current one does not, and vectors are stored, and
+    /// [`RuvectorError::InvalidParameter`] if a declared secondary metric
+    /// needs the current policy
+    pub fn set_normalization(&self, policy: NormalizationPolicy) -> Result<()> {
+        let mut current = self.normalization.write();
+        for &metric in self.secondary_metrics.read().iter() {
+            check_secondary_metric(self.options.distance_metric, metric, policy)?;
+        }
+        if policy.normalizes_inserts()
+            && !current.normalizes_inserts()
+            && !self.storage.is_empty()?
+        {
+            return Err(RuvectorError::InvalidInput(
+                "cannot start normalizing inserts once unnormalized vectors are stored".to_string(),
+            ));
+        }
+
+        #[cfg(feature = "storage")]
+        self.storage.save_normalization(policy)?;
+        *current = policy;
+        Ok(())
+    }
+
+    /// The normalization policy in force
+    pub fn normalization(&self) -> NormalizationPolicy {
+        *self.normalization.read()
+    }
+
+    /// Choose how ids are assigned to entries inserted without one
+    ///
+    /// See [`crate::content_id`]. Ids of stored entries are unaffected. The
+    /// strategy is persisted.
+    pub fn set_id_strategy(&self, strategy: IdStrategy) -> Result<()> {
+        let mut current = self.id_strategy.write();
+        #[cfg(feature = "storage")]
+        self.storage.save_id_strategy(strategy)?;
+        *current = strategy;
+        Ok(())
+    }
+
+    /// How ids are assigned to entries inserted without one
+    pub fn id_strategy(&self) -> IdStrategy {
+        *self.id_strategy.read()
+    }
+
+    /// Declare metrics that searches may use besides the index's own
+    ///
+    /// See [`crate::multi_metric`]. Cosine, dot product and euclidean can
+    /// stand in for each other once inserts are normalized, so set the
+    /// normalization policy first. The declaration is persisted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuvectorError::InvalidParameter`] if a metric cannot be
+    /// served by the index; nothing is changed then
+    pub fn set_secondary_metrics(&self, metrics: Vec<DistanceMetric>) -> Result<()> {
+        let policy = self.normalization();
+        for &metric in &metrics {
+            check_secondary_metric(self.options.distance_metric, metric, policy)?;
+        }
+
+        #[cfg(feature = "storage")]
+        self.storage.save_secondary_metrics(&metrics)?;
+        *self.secondary_metrics.write() = metrics;
+        Ok(())
+    }
+
+    /// Metrics declared with [`VectorDB::set_secondary_metrics`]
+    pub fn secondary_metrics(&self) -> Vec<DistanceMetric> {
+        self.secondary_metrics.read().clone()
+    }
+
+    /// Search, scoring results with `metric` instead of the index's metric
+    ///
+    /// `metric` must be the index's own or a declared secondary metric. The
+    /// index ranks candidates with its own metric, which orders them the
+    /// same way; results are then rescored against their stored vectors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuvectorError::InvalidParameter`] for an undeclared metric
+    pub fn search_with_metric(
+        &self,
+        query: SearchQuery,
+        metric: DistanceMetric,
+    ) -> Result<Vec<SearchResult>> {
+        if metric == self.options.distance_metric {
+            return self.search(query);
+        }
+        if !self.secondary_metrics.read().contains(&metric) {
+            return Err(RuvectorError::InvalidParameter(format!(
+                "{:?} is not a declared metric of this database",
+                metric
+            )));
+        }
+
+        let scoring = self.normalize_query(&query).into_owned();
+        let mut results = self.search(query)?;
+        for result in &mut results {
+            if let Some(vector) = &result.vector {
+                result.score = distance(&scoring.vector, vector, metric)?;
+            }
+        }
+        results.sort_by(|a, b| a.score.total_cmp(&b.score));
+        Ok(results)
+    }
+
+    /// Search and rank candidates by similarity adjusted with metadata boosts
+    ///
+    /// See [`crate::boost`]. `spec.oversample * query.k` candidates are
+    /// fetched, boosted, and the best `query.k` are returned highest score
+    /// first.
+    pub fn search_boosted(
+        &self,
+        query: SearchQuery,
+        spec: &BoostSpec,
+    ) -> Result<Vec<BoostedResult>> {
+        spec.validate()?;
+        let k = query.k;
+        let candidates = self.search(SearchQuery {
+            k: k.saturating_mul(spec.oversample),
+            ..query
+        })?;
+        Ok(spec.rank(candidates, self.options.distance_metric, k))
+    }
+
+    /// Search and rank candidates by similarity blended with recency
+    ///
+    /// See [`crate::recency`]. `decay.oversample * query.k` candidates are
+    /// fetched and rescored, and the best `query.k` are returned highest
+    /// score first.
+    pub fn search_time_decayed(
+        &self,
+        query: SearchQuery,
+        decay: &TimeDecay,
+    ) -> Result<Vec<BoostedResult>> {
+        decay.validate()?;
+        let k = query.k;
+        let candidates = self.search(SearchQuery {
+            k: k.saturating_mul(decay.oversample),
+            ..query
+        })?;
+        Ok(decay.rank(candidates, self.options.distance_metric, k))
+    }
+
+    fn normalize_inserts(&self, entries: &mut [VectorEntry]) {
+        if self.normalization().normalizes_inserts() {
+            for entry in entries {
+                l2_normalize(&mut entry.vector);
+            }
+        }
+    }
+
+    /// The content id `entry` gets under [`IdStrategy::Content`] if it has
+    /// no id of its own
+    fn content_id_for(&self, entry: &VectorEntry) -> Option<VectorId> {
+        (entry.id.is_none() && self.id_strategy() == IdStrategy::Content)
+            .then(|| content_id(&entry.vector, entry.metadata.as_ref()))
+    }
+
+    /// Give id-less entries their content id and drop those already stored
+    ///
+    /// Under [`IdStrategy::Content`], returns the id of every entry in its
+    /// original order; `entries` keeps only the ones still to be written.
+    fn assign_content_ids(&self, entries: &mut Vec<VectorEntry>) -> Result<Option<Vec<VectorId>>> {
+        if self.id_strategy() != IdStrategy::Content {
+            return Ok(None);
+        }
+        let mut ids = Vec::with_capacity(entries.len());
+        let mut seen = HashSet::new();
+        let mut fresh = Vec::with_capacity(entries.len());
+        for mut entry in entries.drain(..) {
+            let Some(id) = self.content_id_for(&entry) else {
+                ids.extend(entry.id.clone());
+                fresh.push(entry);
+                continue;
+            };
+            if seen.insert(id.clone()) && self.storage.get(&id)?.is_none() {
+                entry.id = Some(id.clone());
+                fresh.push(entry);
+            }
+            ids.push(id);
+        }
+        *entries = fresh;
+        Ok(Some(ids))
+    }
+
+    fn normalize_query<'q>(&self, query: &'q SearchQuery) -> Cow<'q, SearchQuery> {
+        if !self.normalization().normalizes_queries() {
+            return Cow::Borrowed(query);
+        }
+        let mut query = query.clone();
+        l2_normalize(&mut query.vector);
+        Cow::Owned(query)
+    }
+
+    /// Start an ingest batch that stays invisible to searches until committed
+    ///
+    /// Use this to load data with several `insert_batch` calls while serving
+    /// queries against the pre-ingest view. Dropping the batch without
+    /// committing removes everything it inserted. The batch can only add new
+    /// ids; use [`VectorDB::insert_batch`] to overwrite committed vectors.
+    pub fn begin_ingest(&self) -> IngestBatch<'_> {
+        IngestBatch {
+            db: self,
+            ids: Vec::new(),
+            replaced: Vec::new(),
+            committed: false,
+        }
+    }
+
+    /// Search for similar vectors
+    ///
+    /// When auditing is enabled the search is recorded in the audit log.
+    pub fn search(&self, query: SearchQuery) -> Result<Vec<SearchResult>> {
+        self.search_audited(query).map(|(_, results)| results)
+    }
+
+    /// Search and return the audit query id alongside the results
+    ///
+    /// The id is `None` when auditing is disabled; otherwise it can be passed
+    /// to [`VectorDB::record_feedback`].
+    pub fn search_audited(
+        &self,
+        query: SearchQuery,
+    ) -> Result<(Option<QueryId>, Vec<SearchResult>)> {
+        let results = self.search_unaudited(&query)?;
+        let query_id = self
+            .audit_log()
+            .map(|log| log.record_search(&query.vector, query.k, &results));
+        Ok((query_id, results))
+    }
+
+    /// Search and also return the predicted difficulty of the query
+    ///
+    /// The difficulty is `None` when the query sets `ef_search` itself or
+    /// adaptive efSearch is not enabled.
+    pub fn search_with_difficulty(
+        &self,
+        query: SearchQuery,
+    ) -> Result<(Vec<SearchResult>, Option<QueryDifficulty>)> {
+        let (results, difficulty) = self.search_assessed(&query)?;
+        if let Some(log) = self.audit_log() {
+            log.record_search(&query.vector, query.k, &results);
+        }
+        Ok((results, difficulty))
+    }
+
+    fn search_unaudited(&self, query: &SearchQuery) -> Result<Vec<SearchResult>> {
+        let Some(cache) = self.result_cache() else {
+            return self.search_assessed(query).map(|(results, _)| results);
+        };
+        if self.is_shut_down() {
+            return Err(RuvectorError::ShutDown);
+        }
+
+        // Read the generation first: a write that lands during the search
+        // leaves the entry stale rather than caching pre-write results as new
+        let generation = self.write_generation.load(Ordering::Acquire);
+        let key = cache.key(query);
+        if let Some(results) = cache.get(key, generation) {
+            return Ok(results);
+        }
+        let (results, _) = self.search_assessed(query)?;
+        cache.insert(key, generation, results.clone());
+        Ok(results)
+    }
+
+    /// Take the index write lock and mark cached results stale
+    fn index_write(&self) -> RwLockWriteGuard<'_, Box<dyn VectorIndex>> {
+        let index = self.index.write();
+        self.mark_written();
+        index
+    }
+
+    fn mark_written(&self) {
+        self.write_generation.fetch_add(1, Ordering::Release);
+    }
+
+    fn log_ops(&self, record: impl FnOnce(&OpLog)) {
+        if let Some(log) = self.op_log.read().as_ref() {
+            record(log);
+        }
+    }
+
+    /// Store entries whose metadata was rewritten, leaving the index alone
+    ///
+    /// Each rewrite is logged as an insert of the whole entry, so a replay
+    /// reproduces it.
+    fn rewrite_entries(&self, entries: &[VectorEntry]) -> Result<()> {
+        let ids = self.storage.insert_batch(entries)?;
+        self.log_ops(|log| {
+            for (id, entry) in ids.iter().zip(entries) {
+                log.record_insert(id, entry);
+            }
+        });
+        self.mark_written();
+        Ok(())
+    }
+
+    fn search_assessed(
+        &self,
+        query: &SearchQuery,
+    ) -> Result<(Vec<SearchResult>, Option<QueryDifficulty>)> {
+        let _search = self.lifecycle.begin_search()?;
+        let query = self.normalize_query(query);
+        let query = query.as_ref();
+        let started = Instant::now();
+        let mut candidates = 0;
+
+        // Holding the pending set for the whole search keeps the view stable:
+        // a batch commit waits until in-flight searches finish.
+        let pending = self.pending.read();
+        let index = self.index.read();
+
+        let difficulty = match (query.ef_search, self.adaptive_ef()) {
+            (None, Some(adaptive)) => {
+                let probe = index.search_with_ef(
+                    &query.vector,
+                    adaptive.config.probe_k,
+                    adaptive.config.probe_ef,
+                )?;
+                candidates += probe.len();
+                let assessed = adaptive.assess(&query.vector, &probe, query.k);
+                tracing::debug!(
+                    "Query difficulty {:.3}, ef_search {}",
+                    assessed.difficulty,
+                    assessed.ef_search
+                );
+                Some(assessed)
+            }
+            _ => None,
+        };
+        let ef_search = query.ef_search.or(difficulty.map(|d| d.ef_search));
+        let run = |k: usize| match ef_search {
+            Some(ef) => index.search_with_ef(&query.vector, k, ef.max(k)),
+            None => index.search(&query.vector, k),
+        };
+
+        let mut results = if pending.is_empty() {
+            let found = run(query.k)?;
+            candidates += found.len();
+            found
+        } else {
+            // Over-fetch until k committed results are found or nothing is left
+            let mut fetch = (query.k * 2).max(query.k + 16);
+            loop {
+                let mut found = run(fetch)?;
+                candidates += found.len();
+                let exhausted = found.len() < fetch || fetch >= query.k + pending.len();
+                found.retain(|r| !pending.contains(&r.id));
+                if found.len() >= query.k || exhausted {
+                    found.truncate(query.k);
+                    break found;
+                }
+                fetch *= 2;
+            }
+        };
+
+        // Enrich results with full data if needed
+        for result in &mut results {
+            if let Ok(Some(entry)) = self.storage.get(&result.id) {
+                result.vector = Some(entry.vector);
+                result.metadata = entry.metadata;
+            }
+        }
+
+        // Apply metadata filters if specified
+        if let Some(filter) = &query.filter {
+            results.retain(|r| {
+                if let Some(metadata) = &r.metadata {
+                    filter
+                        .iter()
+                        .all(|(key, value)| metadata.get(key).map_or(false, |v| v == value))
+                } else {
+                    false
+                }
+            });
+        }
+
+        if let Some(log) = self.slow_query_log() {
+            let latency = started.elapsed();
+            if log.is_slow(latency) {
+                log.record(SlowQueryEntry {
+                    id: 0,
+                    timestamp_ms: 0,
+                    latency_us: latency.as_micros() as u64,
+                    k: query.k,
+                    results: results.len(),
+                    ef_search,
+                    adaptive_ef: difficulty.is_some(),
+                    candidates,
+                    filter_shape: query
+                        .filter
+                        .as_ref()
+                        .map(slow_query::filter_shape)
+                        .unwrap_or_default(),
+                    vector: VectorStats::of(&query.vector),
+                });
+            }
+        }
+
+        Ok((results, difficulty))
+    }
+
+    /// Delete a vector by ID
+    pub fn delete(&self, id: &str) -> Result<bool> {
+        let _write = self.lifecycle.begin_write()?;
+        let removed = self.centroid_members(Some(id))?;
+        let deleted_storage = self.storage.delete(id)?;
+
+        if deleted_storage {
+            self.log_ops(|log| log.record_delete(id));
+            self.update_centroids(&removed, &[])?;
+            let mut index = self.index_write();
+            if index.remove(&id.to_string())? {
+                self.tombstones.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        Ok(deleted_storage)
+    }
+
+    /// Update a stored vector in place by a delta
+    ///
+    /// See [`crate::delta`]. The read, update and write happen under a lock,
+    /// so concurrent delta updates to the same vector all take effect. The
+    /// normalization policy is applied to the result and metadata is kept.
+    /// Returns the updated vector.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuvectorError::VectorNotFound`] if `id` does not exist
+    pub fn update_delta(&self, id: &str, delta: &[f32], update: DeltaUpdate) -> Result<Vec<f32>> {
+        let _write = self.lifecycle.begin_write()?;
+        self.check_backpressure()?;
+        let _serialized = self.delta_lock.lock();
+        let mut entry = self
+            .storage
+            .get(id)?
+            .ok_or_else(|| RuvectorError::VectorNotFound(id.to_string()))?;
+        let replaced = self.centroid_members(Some(id))?;
+        entry.vector = update.apply(&entry.vector, delta)?;
+        self.normalize_inserts(std::slice::from_mut(&mut entry));
+
+        self.storage.insert(&entry)?;
+        self.log_ops(|log| log.record_insert(id, &entry));
+        self.update_centroids(&replaced, std::slice::from_ref(&entry))?;
+        let mut index = self.index_write();
+        if index.remove(&id.to_string())? {
+            self.tombstones.fetch_add(1, Ordering::Relaxed);
+        }
+        index.add(id.to_string(), entry.vector.clone())?;
+        Ok(entry.vector)
+    }
+
+    /// Move a vector to the trash instead of deleting it
+    ///
+    /// See [`crate::trash`]. The entry disappears from searches and lookups
+    /// but can be brought back with [`VectorDB::restore`] until the trash
+    /// retention period passes. Returns false if `id` does not exist.
+    pub fn soft_delete(&self, id: &str) -> Result<bool> {
+        let _write = self.lifecycle.begin_write()?;
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let removed = self.centroid_members(Some(id))?;
+        if !self.storage.move_to_trash(id, now_ms)? {
+            return Ok(false);
+        }
+
+        self.log_ops(|log| log.record_delete(id));
+        self.update_centroids(&removed, &[])?;
+        let mut index = self.index_write();
+        if index.remove(&id.to_string())? {
+            self.tombstones.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(true)
+    }
+
+    /// Bring a soft-deleted vector back with its metadata
+    ///
+    /// Returns false if `id` is not in the trash or its restore window has
+    /// passed; an expired entry is purged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuvectorError::InvalidInput`] if a live vector has since
+    /// been inserted under the same id
+    pub fn restore(&self, id: &str) -> Result<bool> {
+        let _write = self.lifecycle.begin_write()?;
+        self.check_backpressure()?;
+        let Some(trashed) = self.storage.get_trashed(id)? else {
+            return Ok(false);
+        };
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        if trashed.is_expired(self.trash_retention(), now_ms) {
+            self.storage.remove_from_trash(&[id.to_string()])?;
+            return Ok(false);
+        }
+        if self.storage.get(id)?.is_some() {
+            return Err(RuvectorError::InvalidInput(format!(
+                "Cannot restore {}: a vector with this id exists",
+                id
+            )));
+        }
+
+        let Some(entry) = self.storage.restore_from_trash(id)? else {
+            return Ok(false);
+        };
+        self.remember_ids(&[id.to_string()])?;
+        self.log_ops(|log| log.record_insert(id, &entry));
+        self.update_centroids(&[], std::slice::from_ref(&entry))?;
+        self.index_write().add(id.to_string(), entry.vector)?;
+        Ok(true)
+    }
+
+    /// Restorable soft-deleted entries, most recently deleted first
+    pub fn trash(&self) -> Result<Vec<TrashedEntry>> {
+        let retention = self.trash_retention();
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut entries: Vec<TrashedEntry> = self
+            .storage
+            .trashed_entries()?
+            .into_iter()
+            .filter(|trashed| !trashed.is_expired(retention, now_ms))
+            .collect();
+        entries.sort_by(|a, b| b.deleted_at_ms.cmp(&a.deleted_at_ms));
+        Ok(entries)
+    }
+
+    /// Permanently remove trashed entries whose restore window has passed
+    ///
+    /// Returns the number of entries removed.
+    pub fn purge_trash(&self) -> Result<usize> {
+        let _write = self.lifecycle.begin_write()?;
+        let retention = self.trash_retention();
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let expired: Vec<VectorId> = self
+            .storage
+            .trashed_entries()?
+            .iter()
+            .filter(|trashed| trashed.is_expired(retention, now_ms))
+            .map(TrashedEntry::id)
+            .collect();
+        self.storage.remove_from_trash(&expired)
+    }
+
+    /// Change how long soft-deleted entries stay restorable
+    ///
+    /// The period is persisted and applies to entries already in the trash.
+    pub fn set_trash_retention(&self, retention: Duration) -> Result<()> {
+        #[cfg(feature = "storage")]
+        self.storage.save_trash_retention(retention.as_secs())?;
+        *self.trash_retention.write() = retention;
+        Ok(())
+    }
+
+    /// How long soft-deleted entries stay restorable
+    pub fn trash_retention(&self) -> Duration {
+        *self.trash_retention.read()
+    }
+
+    /// Maintain a centroid for each value of the metadata field `key`
+    ///
+    /// See [`crate::centroid`]. Centroids of existing members are computed
+    /// and stored at once, and the group is persisted. Returns false if the
+    /// group was already declared.
+    pub fn define_centroid_group(&self, key: &str) -> Result<bool> {
+        let _write = self.lifecycle.begin_write()?;
+        if key.is_empty() {
+            return Err(RuvectorError::InvalidInput(
+                "Centroid group key must not be empty".to_string(),
+            ));
+        }
+        if !self.centroids.lock().define(key) {
+            return Ok(false);
+        }
+        #[cfg(feature = "storage")]
+        self.storage
+            .save_centroid_groups(&self.centroids.lock().keys())?;
+        self.rebuild_centroids()?;
+        Ok(true)
+    }
+
+    /// Stop maintaining the centroid group on `key` and delete its centroids
+    ///
+    /// Returns false if no such group was declared.
+    pub fn drop_centroid_group(&self, key: &str) -> Result<bool> {
+        let _write = self.lifecycle.begin_write()?;
+        let mut centroids = self.centroids.lock();
+        let Some(ids) = centroids.drop_group(key) else {
+            return Ok(false);
+        };
+        #[cfg(feature = "storage")]
+        self.storage.save_centroid_groups(&centroids.keys())?;
+        self.apply_centroid_changes(ids.into_iter().map(CentroidChange::Remove).collect())?;
+        Ok(true)
+    }
+
+    /// Metadata keys with a declared centroid group
+    pub fn centroid_groups(&self) -> Vec<String> {
+        self.centroids.lock().keys()
+    }
+
+    /// The centroid of members whose `key` field equals `value`
+    ///
+    /// Non-string values are matched by their JSON text, e.g. `"42"`.
+    pub fn centroid(&self, key: &str, value: &str) -> Result<Option<VectorEntry>> {
+        self.storage.get(&centroid::centroid_id(key, value))
+    }
+
+    /// Predict the label of `vector` from entries labelled in
+    /// `config.field`
+    ///
+    /// See [`crate::classifier`]. Returns `None` when no labelled entry is
+    /// close enough to vote, or no class has members yet. Centroid
+    /// classification needs a centroid group on the field.
+    pub fn classify(
+        &self,
+        vector: &[f32],
+        config: &ClassifyConfig,
+    ) -> Result<Option<Classification>> {
+        let metric = self.options.distance_metric;
+        match config.method {
+            ClassifyMethod::Centroid => {
+                let Some(mut classes) = self.centroids.lock().group_entries(&config.field) else {
+                    return Err(RuvectorError::InvalidInput(format!(
+                        "No centroid group on '{}'; declare one with define_centroid_group",
+                        config.field
+                    )));
+                };
+                self.normalize_inserts(&mut classes);
+                let mut query = vector.to_vec();
+                if self.normalization().normalizes_queries() {
+                    l2_normalize(&mut query);
+                }
+                let classes = classes
+                    .into_iter()
+                    .map(|entry| {
+                        let metadata = entry.metadata.unwrap_or_default();
+                        let label = metadata
+                            .get(centroid::CENTROID_VALUE_KEY)
+                            .and_then(centroid::member_value)
+                            .unwrap_or_default();
+                        let members = metadata
+                            .get(centroid::CENTROID_MEMBERS_KEY)
+                            .and_then(|m| m.as_u64())
+                            .unwrap_or_default() as usize;
+                        Ok((label, distance(&query, &entry.vector, metric)?, members))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(classifier::nearest_class(&classes, config.temperature))
+            }
+            ClassifyMethod::Knn { k } => {
+                if k == 0 {
+                    return Err(RuvectorError::InvalidInput(
+                        "k must be at least 1".to_string(),
+                    ));
+                }
+                // Centroid entries may take places among the results
+                let centroids = self.centroids.lock().len();
+                let neighbors: Vec<(String, f32)> = self
+                    .search(SearchQuery {
+                        vector: vector.to_vec(),
+                        k: k + centroids,
+                        filter: None,
+                        ef_search: None,
+                    })?
+                    .into_iter()
+                    .filter(|result| {
+                        !result
+                            .metadata
+                            .as_ref()
+                            .is_some_and(|m| m.contains_key(centroid::CENTROID_KEY))
+                    })
+                    .take(k)
+                    .filter_map(|result| {
+                        let label = centroid::member_value(result.metadata?.get(&config.field)?)?;
+                        Some((label, result.score))
+                    })
+                    .collect();
+                Ok(classifier::vote(&neighbors))
+            }
+        }
+    }
+
+    /// Recompute every centroid from the stored members
+    ///
+    /// Also removes centroid entries left behind by groups or values that no
+    /// longer have members.
+    fn rebuild_centroids(&self) -> Result<()> {
+        let mut centroids = self.centroids.lock();
+        centroids.reset();
+        let mut stale = HashSet::new();
+        for id in self.storage.all_ids()? {
+            let Some(entry) = self.storage.get(&id)? else {
+                continue;
+            };
+            if centroid::is_centroid(&entry) {
+                stale.insert(id);
+            } else {
+                centroids.add_member(&entry);
+            }
+        }
+
+        let entries = centroids.entries();
+        for entry in &entries {
+            if let Some(id) = &entry.id {
+                stale.remove(id);
+            }
+        }
+        let changes = stale
+            .into_iter()
+            .map(CentroidChange::Remove)
+            .chain(entries.into_iter().map(CentroidChange::Upsert))
+            .collect();
+        self.apply_centroid_changes(changes)
+    }
+
+    /// Stored entries for `ids`, when centroid groups need to see what a
+    /// write replaces or removes
+    fn centroid_members<'a>(
+        &self,
+        ids: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Vec<VectorEntry>> {
+        if !self.centroids.lock().is_active() {
+            return Ok(Vec::new());
+        }
+        let mut entries = Vec::new();
+        for id in ids {
+            entries.extend(self.storage.get(id)?);
+        }
+        Ok(entries)
+    }
+
+    /// Move centroids for members that were removed and added
+    fn update_centroids(&self, removed: &[VectorEntry], added: &[VectorEntry]) -> Result<()> {
+        if removed.is_empty() && added.is_empty() {
+            return Ok(());
+        }
+        // Held while writing so centroid entries are stored in update order
+        let mut centroids = self.centroids.lock();
+        if !centroids.is_active() {
+            return Ok(());
+        }
+        let changes = centroids.update(removed, added);
+        self.apply_centroid_changes(changes)
+    }
+
+    fn apply_centroid_changes(&self, changes: Vec<CentroidChange>) -> Result<()> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let mut upserts = Vec::new();
+        let mut removed = Vec::new();
+        for change in changes {
+            match change {
+                CentroidChange::Upsert(entry) => upserts.push(entry),
+                CentroidChange::Remove(id) => removed.push(id),
+            }
+        }
+        self.normalize_inserts(&mut upserts);
+        let upserted = self.storage.insert_batch(&upserts)?;
+        self.remember_ids(&upserted)?;
+        self.storage.delete_batch(&removed)?;
+
+        let mut index = self.index_write();
+        let replaced = upserts.iter().filter_map(|entry| entry.id.as_ref());
+        for id in removed.iter().chain(replaced) {
+            if index.remove(id)? {
+                self.tombstones.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        index.add_batch(
+            upserts
+                .into_iter()
+                .filter_map(|entry| Some((entry.id?, entry.vector)))
+                .collect(),
+        )
+    }
+
+    /// Get a vector by ID
+    pub fn get(&self, id: &str) -> Result<Option<VectorEntry>> {
+        self.storage.get(id)
+    }
+
+    /// Whether `id` may be stored, without reading storage
+    ///
+    /// See [`crate::id_filter`]. False means the id is definitely not
+    /// stored; true means it probably is and [`VectorDB::get`] can confirm.
+    pub fn maybe_contains(&self, id: &str) -> bool {
+        self.id_filter.read().maybe_contains(id)
+    }
+
+    /// Add newly stored ids to the id filter, rebuilding it once full
+    fn remember_ids(&self, ids: &[VectorId]) -> Result<()> {
+        let mut filter = self.id_filter.write();
+        ids.iter().for_each(|id| filter.insert(id));
+        if filter.is_saturated() {
+            let stored = self.storage.all_ids()?;
+            let mut rebuilt = IdFilter::with_capacity(2 * stored.len());
+            stored.iter().for_each(|id| rebuilt.insert(id));
+            *filter = rebuilt;
+        }
+        Ok(())
+    }
+
+    /// Get the number of vectors
+    pub fn len(&self) -> Result<usize> {
+        self.storage.len()
+    }
+
+    /// Check if database is empty
+    pub fn is_empty(&self) -> Result<bool> {
+        self.storage.is_empty()
+    }
+
+    /// Get database options
+    pub fn options(&self) -> &DbOptions {
+        &self.options
+    }
+
+    /// Get all vector IDs (for iteration/serialization)
+    pub fn keys(&self) -> Result<Vec<String>> {
+        self.storage.all_ids()
+    }
+
+    /// Summarize the distribution of stored vectors
+    ///
+    /// See [`crate::embedding_stats`]. Scans every vector; centroid entries
+    /// and uncommitted ingest batches are left out.
+    pub fn collection_stats(&self) -> Result<EmbeddingStats> {
+        self.collection_stats_with(&StatsConfig::default())
+    }
+
+    /// [`VectorDB::collection_stats`] with explicit settings
+    pub fn collection_stats_with(&self, config: &StatsConfig) -> Result<EmbeddingStats> {
+        let mut stats = StatsAccumulator::new(self.options.dimensions);
+        let mut ids = Vec::new();
+        {
+            let pending = self.pending.read();
+            for id in self.storage.all_ids()? {
+                if pending.contains(&id) {
+                    continue;
+                }
+                let Some(entry) = self.storage.get(&id)? else {
+                    continue;
+                };
+                if centroid::is_centroid(&entry) {
+                    continue;
+                }
+                stats.add(&entry.vector);
+                ids.push(id);
+            }
+        }
+
+        let intrinsic = if config.intrinsic_dim_sample == 0 {
+            None
+        } else {
+            two_nn(&self.nearest_distance_pairs(&ids, config)?)
+        };
+        Ok(stats.finish(intrinsic))
+    }
+
+    /// Euclidean distances from sampled vectors to their two nearest
+    /// distinct neighbors
+    fn nearest_distance_pairs(
+        &self,
+        ids: &[VectorId],
+        config: &StatsConfig,
+    ) -> Result<Vec<(f32, f32)>> {
+        use rand::rngs::StdRng;
+        use rand::seq::SliceRandom;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let index = self.index.read();
+        let mut pairs = Vec::new();
+        for id in ids.choose_multiple(&mut rng, config.intrinsic_dim_sample) {
+            let Some(entry) = self.storage.get(id)? else {
+                continue;
+            };
+            if entry.vector.iter().any(|x| !x.is_finite()) {
+                continue;
+            }
+            let mut distances = Vec::with_capacity(INTRINSIC_DIM_CANDIDATES);
+            for neighbor in index.search(&entry.vector, INTRINSIC_DIM_CANDIDATES + 1)? {
+                if &neighbor.id == id {
+                    continue;
+                }
+                let Some(other) = self.storage.get(&neighbor.id)? else {
+                    continue;
+                };
+                if centroid::is_centroid(&other) {
+                    continue;
+                }
+                let d = distance(&entry.vector, &other.vector, DistanceMetric::Euclidean)?;
+                // Exact duplicates would make the distance ratio infinite
+                if d > 0.0 && d.is_finite() {
+                    distances.push(d);
+                }
+            }
+            distances.sort_by(f32::total_cmp);
+            if let [r1, r2, ..] = distances[..] {
+                pairs.push((r1, r2));
+            }
+        }
+        Ok(pairs)
+    }
+
+    /// Materialize the k-nearest-neighbor graph of all stored vectors
+    ///
+    /// Each vector is searched against the current index, so the edges reflect
+    /// what the index actually returns (approximate for HNSW). Self-matches
+    /// are dropped. Centroid entries and uncommitted ingest batches are
+    /// neither nodes nor edge targets.
+    pub fn export_knn_graph(&self, k: usize) -> Result<KnnGraph> {
+        if k == 0 {
+            return Err(RuvectorError::InvalidParameter(
+                "k must be greater than zero".to_string(),
+            ));
+        }
+
+        let mut nodes = Vec::new();
+        let mut found = Vec::new();
+        {
+            let pending = self.pending.read();
+            let index = self.index.read();
+            for id in self.storage.all_ids()? {
+                if pending.contains(&id) {
+                    continue;
+                }
+                let Some(entry) = self.storage.get(&id)? else {
+                    continue;
+                };
+                if centroid::is_centroid(&entry) {
+                    continue;
+                }
+                found.push(index.search(&entry.vector, k + 1)?);
+                nodes.push(id);
+            }
+        }
+
+        let node_set: HashSet<&str> = nodes.iter().map(String::as_str).collect();
+        let mut edges = Vec::with_capacity(nodes.len() * k);
+        for (id, neighbors) in nodes.iter().zip(found) {
+            edges.extend(
+                neighbors
+                    .into_iter()
+                    .filter(|n| &n.id != id && node_set.contains(n.id.as_str()))
+                    .take(k)
+                    .map(|n| KnnEdge {
+                        source: id.clone(),
+                        target: n.id,
+                        distance: n.score,
+                    }),
+            );
+        }
+
+        Ok(KnnGraph {
+            k,
+            metric: self.options.distance_metric,
+            nodes,
+            edges,
+        })
+    }
+
+    /// Export every entry with differential-privacy noise
+    ///
+    /// See [`crate::privacy`]. The export is charged to the privacy ledger,
+    /// which is persisted before any records are returned. Centroid entries
+    /// and uncommitted ingest batches are left out.
+    pub fn export_private(&self, config: &DpExportConfig) -> Result<DpExport> {
+        config.validate()?;
+        // Held throughout so concurrent exports can't both fit the budget
+        let mut ledger = self.privacy.lock();
+        ledger.check(config.epsilon, config.delta)?;
+
+        let mut entries = Vec::new();
+        {
+            let pending = self.pending.read();
+            for id in self.storage.all_ids()? {
+                if pending.contains(&id) {
+                    continue;
+                }
+                if let Some(entry) = self.storage.get(&id)? {
+                    if !centroid::is_centroid(&entry) {
+                        entries.push(entry);
+                    }
+                }
+            }
+        }
+
+        let charge = PrivacyCharge {
+            epsilon: config.epsilon,
+            delta: config.delta,
+            records: entries.len(),
+            noise_scale: config.noise_scale(),
+            exported_at: chrono::Utc::now().timestamp(),
+        };
+        let mut updated = ledger.clone();
+        updated.charges.push(charge.clone());
+        #[cfg(feature = "storage")]
+        self.storage.save_privacy_ledger(&updated)?;
+        *ledger = updated;
+        drop(ledger);
+
+        Ok(DpExport {
+            entries: privacy::privatize(entries, config),
+            charge,
+        })
+    }
+
+    /// Limit the total privacy loss of future private exports
+    ///
+    /// The budget covers exports already charged to the ledger; `None`
+    /// removes the limit. The budget is persisted.
+    pub fn set_privacy_budget(&self, budget: Option<PrivacyBudget>) -> Result<()> {
+        if let Some(budget) = budget {
+            let valid = |x: f64| x.is_finite() && x >= 0.0;
+            if !valid(budget.epsilon) || !valid(budget.delta) {
+                return Err(RuvectorError::InvalidParameter(format!(
+                    "Privacy budget must be non-negative, got epsilon {}, delta {}",
+                    budget.epsilon, budget.delta
+                )));
+            }
+        }
+        let mut ledger = self.privacy.lock();
+        let mut updated = ledger.clone();
+        updated.budget = budget;
+        #[cfg(feature = "storage")]
+        self.storage.save_privacy_ledger(&updated)?;
+        *ledger = updated;
+        Ok(())
+    }
+
+    /// The privacy budget and every private export charged against it
+    pub fn privacy_ledger(&self) -> PrivacyLedger {
+        self.privacy.lock().clone()
+    }
+
+    /// Find near-duplicate entries and optionally tag or delete them
+    ///
+    /// Each entry is compared with its `config.candidates` nearest neighbors;
+    /// pairs with cosine similarity above `config.threshold` are grouped
+    /// transitively and the smallest id of each group is kept. Deletions are
+    /// applied in a single storage transaction while the index is write-locked.
+    /// Centroid entries and uncommitted ingest batches are neither examined
+    /// nor matched.
+    pub fn dedupe(&self, config: &DedupeConfig) -> Result<DedupeReport> {
+        let _write = match config.action {
+            DuplicateAction::Report => None,
+            DuplicateAction::Tag | DuplicateAction::Delete => Some(self.lifecycle.begin_write()?),
+        };
+        let mut pairs = Vec::new();
+        let mut vectors = HashMap::new();
+
+        {
+            let pending = self.pending.read();
+            let index = self.index.read();
+            for id in self.storage.all_ids()? {
+                if pending.contains(&id) {
+                    continue;
+                }
+                let Some(entry) = self.storage.get(&id)? else {
+                    continue;
+                };
+                if !centroid::is_centroid(&entry) {
+                    vectors.insert(id, entry.vector);
+                }
+            }
+            for (id, vector) in &vectors {
+                for neighbor in index.search(vector, config.candidates + 1)? {
+                    if &neighbor.id <= id {
+                        continue;
+                    }
+                    if let Some(other) = vectors.get(&neighbor.id) {
+                        if cosine_similarity(vector, other) >= config.threshold {
+                            pairs.push((id.clone(), neighbor.id));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut report = DedupeReport {
+            scanned: vectors.len(),
+            groups: group_pairs(&pairs),
+            ..Default::default()
+        };
+
+        match config.action {
+            DuplicateAction::Report => {}
+            DuplicateAction::Tag => {
+                let mut tagged = Vec::new();
+                for group in &report.groups {
+                    for id in &group.duplicates {
+                        if let Some(mut entry) = self.storage.get(id)? {
+                            entry.metadata.get_or_insert_with(Default::default).insert(
+                                DUPLICATE_OF_KEY.to_string(),
+                                serde_json::Value::String(group.keep.clone()),
+                            );
+                            tagged.push(entry);
+                        }
+                    }
+                }
+                self.rewrite_entries(&tagged)?;
+                report.tagged = tagged.len();
+            }
+            DuplicateAction::Delete => {
+                let doomed: Vec<VectorId> = report
+                    .groups
+                    .iter()
+                    .flat_map(|g| g.duplicates.iter().cloned())
+                    .collect();
+                let removed = self.centroid_members(doomed.iter().map(String::as_str))?;
+                let mut index = self.index_write();
+                report.deleted = self.storage.delete_batch(&doomed)?;
+                self.log_ops(|log| doomed.iter().for_each(|id| log.record_delete(id)));
+                for id in &doomed {
+                    if index.remove(id)? {
+                        self.tombstones.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+                drop(index);
+                self.update_centroids(&removed, &[])?;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Score every entry by how far it sits from its nearest neighbors
+    ///
+    /// See [`crate::anomaly`]. With `config.write_metadata`, scores are
+    /// written to [`ANOMALY_SCORE_KEY`] in batches of
+    /// [`ANOMALY_WRITE_BATCH`], and [`ANOMALY_FLAG_KEY`] is set on entries
+    /// above the threshold and cleared on the rest. Centroid entries and
+    /// uncommitted ingest batches are neither scored nor used as neighbors.
+    pub fn score_anomalies(&self, config: &AnomalyConfig) -> Result<AnomalyReport> {
+        if config.k == 0 {
+            return Err(RuvectorError::InvalidParameter(
+                "k must be greater than zero".to_string(),
+            ));
+        }
+        let _write = if config.write_metadata {
+            Some(self.lifecycle.begin_write()?)
+        } else {
+            None
+        };
+
+        let mut ids = Vec::new();
+        let mut found = Vec::new();
+        {
+            let pending = self.pending.read();
+            let index = self.index.read();
+            for id in self.storage.all_ids()? {
+                if pending.contains(&id) {
+                    continue;
+                }
+                let Some(entry) = self.storage.get(&id)? else {
+                    continue;
+                };
+                if centroid::is_centroid(&entry) {
+                    continue;
+                }
+                found.push(index.search(&entry.vector, config.k + 1)?);
+                ids.push(id);
+            }
+        }
+
+        let position: HashMap<&str, usize> = ids
+            .iter()
+            .enumerate()
+            .map(|(p, id)| (id.as_str(), p))
+            .collect();
+        let neighbors: Vec<Vec<(usize, f32)>> = found
+            .into_iter()
+            .enumerate()
+            .map(|(p, results)| {
+                results
+                    .into_iter()
+                    .filter_map(|n| position.get(n.id.as_str()).map(|&o| (o, n.score)))
+                    .filter(|&(o, _)| o != p)
+                    .take(config.k)
+                    .collect()
+            })
+            .collect();
+        let scores = anomaly::scores(&neighbors, config.method);
+        let mut report = anomaly::report(&ids, &scores, config);
+        if !config.write_metadata {
+            return Ok(report);
+        }
+
+        let scored = ids.iter().zip(&scores);
+        let mut scored = scored.peekable();
+        while scored.peek().is_some() {
+            let mut batch = Vec::with_capacity(ANOMALY_WRITE_BATCH);
+            for (id, &score) in scored.by_ref().take(ANOMALY_WRITE_BATCH) {
+                let Some(mut entry) = self.storage.get(id)? else {
+                    continue;
+                };
+                let metadata = entry.metadata.get_or_insert_with(Default::default);
+                metadata.insert(ANOMALY_SCORE_KEY.to_string(), serde_json::json!(score));
+                if config.threshold.is_some_and(|t| score > t) {
+                    metadata.insert(ANOMALY_FLAG_KEY.to_string(), serde_json::Value::Bool(true));
+                } else {
+                    metadata.remove(ANOMALY_FLAG_KEY);
+                }
+                batch.push(entry);
+            }
+            self.rewrite_entries(&batch)?;
+            report.written += batch.len();
+        }
+        Ok(report)
+    }
+
+    /// Evaluate a query expression against the stored vectors
+    pub fn resolve_query_vector(&self, query: &QueryVector) -> Result<Vec<f32>> {
+        query.resolve(self.options.dimensions, |id| {
+            Ok(self.storage.get(id)?.map(|entry| entry.vector))
+        })
+    }
+
+    /// Search with a query expression (e.g. `king - man + woman`)
+    ///
+    /// Ids referenced by the expression are left out of the results, as is
+    /// customary for analogy queries.
+    pub fn search_expression(
+        &self,
+        query: &QueryVector,
+        k: usize,
+        filter: Option<HashMap<String, serde_json::Value>>,
+    ) -> Result<Vec<SearchResult>> {
+        let vector = self.resolve_query_vector(query)?;
+        let inputs = query.ids();
+
+        let mut results = self.search(SearchQuery {
+            vector,
+            k: k + inputs.len(),
+            filter,
+            ef_search: None,
+        })?;
+        results.retain(|r| !inputs.contains(&&r.id));
+        results.truncate(k);
+        Ok(results)
+    }
+
+    /// Register (or replace) a named query template
+    pub fn register_template(&self, name: &str, template: QueryTemplate) -> Result<()> {
+        template.validate()?;
+        self.templates.write().insert(name.to_string(), template);
+        Ok(())
+    }
+
+    /// Remove a named query template
+    pub fn remove_template(&self, name: &str) -> bool {
+        self.templates.write().remove(name).is_some()
+    }
+
+    /// Look up a named query template
+    pub fn template(&self, name: &str) -> Option<QueryTemplate> {
+        self.templates.read().get(name).cloned()
+    }
+
+    /// Names of all registered templates, sorted
+    pub fn template_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.templates.read().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Run a named query template with the given vector and parameters
+    pub fn search_template(
+        &self,
+        name: &str,
+        vector: Vec<f32>,
+        params: &HashMap<String, serde_json::Value>,
+    ) -> Result<Vec<SearchResult>> {
+        let template = self.template(name).ok_or_else(|| {
+            RuvectorError::InvalidInput(format!("Unknown query template: {}", name))
+        })?;
+        let filter = template.bind_filter(params)?;
+
+        let mut query = SearchQuery {
+            vector,
+            k: template.k,
+            filter,
+            ef_search: template.ef_search,
+        };
+
+        match &template.fusion {
+            None => self.search(query),
+            Some(FusionSettings::Blend { anchor, weight }) => {
+                let anchor = self.resolve_query_vector(anchor)?;
+                if anchor.len() != query.vector.len() {
+                    return Err(RuvectorError::DimensionMismatch {
+                        expected: anchor.len(),
+                        actual: query.vector.len(),
+                    });
+                }
+                for (q, a) in query.vector.iter_mut().zip(&anchor) {
+                    *q = (1.0 - weight) * *q + weight * a;
+                }
+                self.search(query)
+            }
+            Some(FusionSettings::Mmr(config)) => {
+                let mmr = MMRSearch::new(config.clone())?;
+                let k = template.k;
+                let vector = query.vector.clone();
+                query.k = ((k as f32) * config.fetch_multiplier).ceil() as usize;
+                let candidates = self.search(query)?;
+                mmr.rerank(&vector, candidates, k)
+            }
+        }
+    }
+
+    /// Assemble a token-budgeted, deduplicated, diversified context for RAG
+    ///
+    /// Shorthand for [`VectorDB::build_context_with`] with default settings
+    /// apart from the budget, deduplication and MMR lambda. `dedupe` drops
+    /// chunks with cosine similarity of 0.95 or more to a kept chunk.
+    pub fn build_context(
+        &self,
+        query: SearchQuery,
+        token_budget: usize,
+        dedupe: bool,
+        mmr_lambda: Option<f32>,
+    ) -> Result<ContextPack> {
+        let defaults = ContextPackConfig::default();
+        self.build_context_with(
+            query,
+            &ContextPackConfig {
+                token_budget,
+                dedupe_threshold: if dedupe {
+                    defaults.dedupe_threshold
+                } else {
+                    None
+                },
+                mmr_lambda,
+                ..defaults
+            },
+        )
+    }
+
+    /// Search and assemble a context pack; see [`crate::context_pack`]
+    ///
+    /// The query's filter applies; its `k` is raised to `config.candidates`.
+    pub fn build_context_with(
+        &self,
+        mut query: SearchQuery,
+        config: &ContextPackConfig,
+    ) -> Result<ContextPack> {
+        query.k = query.k.max(config.candidates);
+        let results = self.search(query.clone())?;
+        Ok(context_pack::assemble(&query.vector, results, config))
+    }
+
+    /// Project a sample of the database to 2D with the given method
+    pub fn project_2d(&self, method: ProjectionMethod) -> Result<Vec<ProjectedPoint>> {
+        self.project(&ProjectionConfig {
+            method,
+            ..Default::default()
+        })
+    }
+
+    /// Project a sample of the database to low-dimensional coordinates
+    ///
+    /// Sampling is seeded by `config.seed`, so repeated calls on an unchanged
+    /// database return the same points. Centroid entries and uncommitted
+    /// ingest batches are left out.
+    pub fn project(&self, config: &ProjectionConfig) -> Result<Vec<ProjectedPoint>> {
+        use rand::rngs::StdRng;
+        use rand::seq::SliceRandom;
+        use rand::SeedableRng;
+
+        if !(1..=3).contains(&config.output_dims) {
+            return Err(RuvectorError::InvalidParameter(format!(
+                "output_dims must be 1, 2 or 3, got {}",
+                config.output_dims
+            )));
+        }
+
+        let mut ids = {
+            let pending = self.pending.read();
+            let mut ids = self.storage.all_ids()?;
+            ids.retain(|id| !pending.contains(id));
+            ids
+        };
+        ids.sort();
+        if config.sample_size > 0 && config.sample_size < ids.len() {
+            let mut rng = StdRng::seed_from_u64(config.seed);
+            ids = ids
+                .choose_multiple(&mut rng, config.sample_size)
+                .cloned()
+                .collect();
+        }
+
+        let mut sampled_ids = Vec::with_capacity(ids.len());
+        let mut vectors = Vec::with_capacity(ids.len());
+        for id in ids {
+            let Some(entry) = self.storage.get(&id)? else {
+                continue;
+            };
+            if centroid::is_centroid(&entry) {
+                continue;
+            }
+            sampled_ids.push(id);
+            vectors.push(entry.vector);
+        }
+
+        Ok(sampled_ids
+            .into_iter()
+            .zip(projection::project(&vectors, config))
+            .map(|(id, coords)| ProjectedPoint { id, coords })
+            .collect())
+    }
+
+    /// Preload storage pages and index structures before serving traffic
+    pub fn warmup(&self, config: &WarmupConfig) -> Result<WarmupReport> {
+        self.warmup_with_progress(config, |_| {})
+    }
+
+    /// Like [`VectorDB::warmup`], calling `on_progress` as each phase advances
+    pub fn warmup_with_progress<F>(
+        &self,
+        config: &WarmupConfig,
+        mut on_progress: F,
+    ) -> Result<WarmupReport>
+    where
+        F: FnMut(WarmupProgress),
+    {
+        let started = std::time::Instant::now();
+        let mut report = WarmupReport::default();
+        let mut progress = |phase, done, total| {
+            if warmup::should_report(done, total) {
+                on_progress(WarmupProgress { phase, done, total });
+            }
+        };
+
+        let mut ids = self.storage.all_ids()?;
+        ids.sort();
+
+        if config.touch_storage {
+            let total = ids.len();
+            for (i, id) in ids.iter().enumerate() {
+                if let Some(entry) = self.storage.get(id)? {
+                    report.vectors_read += 1;
+                    report.bytes_read += entry.vector.len() * std::mem::size_of::<f32>();
+                }
+                progress(WarmupPhase::Storage, i + 1, total);
+            }
+        }
+
+        // Probe from evenly spaced ids so every region of the graph is touched
+        let probes = config.index_probes.min(ids.len());
+        if probes > 0 {
+            let stride = ids.len() as f64 / probes as f64;
+            let index = self.index.read();
+            for i in 0..probes {
+                let id = &ids[(i as f64 * stride) as usize];
+                if let Some(entry) = self.storage.get(id)? {
+                    index.search(&entry.vector, config.probe_k.max(1))?;
+                    report.index_probes += 1;
+                }
+                progress(WarmupPhase::Index, i + 1, probes);
+            }
+        }
+
+        let rounds = config.canary_rounds.max(1);
+        let total = config.canary_queries.len() * rounds;
+        if total > 0 {
+            for round in 0..rounds {
+                report.canary_latencies_us.clear();
+                for (i, query) in config.canary_queries.iter().enumerate() {
+                    let timer = std::time::Instant::now();
+                    self.search_unaudited(query)?;
+                    report
+                        .canary_latencies_us
+                        .push(timer.elapsed().as_micros() as u64);
+                    progress(
+                        WarmupPhase::Canaries,
+                        round * config.canary_queries.len() + i + 1,
+                        total,
+                    );
+                }
+            }
+
+            let mut sorted = report.canary_latencies_us.clone();
+            sorted.sort_unstable();
+            report.canary_p50_us = warmup::percentile(&sorted, 50.0);
+            report.canary_p99_us = warmup::percentile(&sorted, 99.0);
+        }
+
+        report.elapsed_ms = started.elapsed().as_millis() as u64;
+        tracing::info!(
+            "Warm-up read {} vectors, ran {} index probes in {}ms (canary p99: {:?}us)",
+            report.vectors_read,
+            report.index_probes,
+            report.elapsed_ms,
+            report.canary_p99_us
+        );
+        Ok(report)
+    }
+
+    /// Quick health check comparing stored and indexed counts
+    ///
+    /// Cheap enough for a liveness probe; see [`crate::health`].
+    pub fn health_check(&self) -> HealthReport {
+        self.check_health(None)
+    }
+
+    /// Health check that also verifies a storage sample and measures canary
+    /// searches
+    pub fn health_check_deep(&self, config: &HealthCheckConfig) -> HealthReport {
+        self.check_health(Some(config))
+    }
+
+    fn check_health(&self, deep: Option<&HealthCheckConfig>) -> HealthReport {
+        let started = Instant::now();
+        let mut report = HealthReport::new(deep.is_some());
+
+        let ids = match self.storage.all_ids() {
+            Ok(ids) => ids,
+            Err(e) => {
+                report.push("storage", HealthStatus::Unhealthy, e.to_string());
+                report.elapsed_ms = started.elapsed().as_millis() as u64;
+                return report;
+            }
+        };
+        report.vectors = ids.len();
+        report.indexed = self.index.read().len();
+        report.pending = self.pending.read().len();
+        report.index_lag = report.vectors.saturating_sub(report.indexed);
+        report.push(
+            "storage",
+            HealthStatus::Healthy,
+            format!("{} entries", report.vectors),
+        );
+
+        let max_lag = deep.map_or(0, |config| config.max_index_lag);
+        let lag_status = if report.index_lag > max_lag {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Healthy
+        };
+        report.push(
+            "index_lag",
+            lag_status,
+            format!(
+                "{} of {} entries indexed, {} pending",
+                report.indexed, report.vectors, report.pending
+            ),
+        );
+
+        let Some(config) = deep else {
+            report.elapsed_ms = started.elapsed().as_millis() as u64;
+            return report;
+        };
+
+        // Sample evenly spaced ids so every region of storage is read
+        let mut ids = ids;
+        ids.sort();
+        let samples = config.sample_size.min(ids.len());
+        let stride = ids.len() as f64 / samples.max(1) as f64;
+        let mut vectors = Vec::with_capacity(samples);
+        for i in 0..samples {
+            let id = &ids[(i as f64 * stride) as usize];
+            report.sampled += 1;
+            match self.storage.get(id) {
+                Ok(Some(entry))
+                    if entry.vector.len() == self.options.dimensions
+                        && entry.vector.iter().all(|x| x.is_finite()) =>
+                {
+                    vectors.push((id.clone(), entry.vector));
+                }
+                // Deleted since the id listing; not corruption
+                Ok(None) => report.sampled -= 1,
+                _ => report.corrupt.push(id.clone()),
+            }
+        }
+        let sample_status = if report.corrupt.is_empty() {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Unhealthy
+        };
+        report.push(
+            "storage_sample",
+            sample_status,
+            format!(
+                "{} of {} sampled entries failed verification",
+                report.corrupt.len(),
+                report.sampled
+            ),
+        );
+
+        // Pending vectors are hidden from searches and cannot find themselves
+        let pool: Vec<_> = {
+            let pending = self.pending.read();
+            vectors
+                .iter()
+                .filter(|(id, _)| !pending.contains(id))
+                .collect()
+        };
+        let canaries = config.canary_queries.min(pool.len());
+        if canaries > 0 {
+            let stride = pool.len() as f64 / canaries as f64;
+            let mut latencies = Vec::with_capacity(canaries);
+            let mut found_self = 0;
+            let mut failures = 0;
+            for i in 0..canaries {
+                let (id, vector) = pool[(i as f64 * stride) as usize];
+                let query = SearchQuery {
+                    vector: vector.clone(),
+                    k: config.canary_k.max(1),
+                    filter: None,
+                    ef_search: None,
+                };
+                let timer = Instant::now();
+                match self.search_assessed(&query) {
+                    Ok((results, _)) => {
+                        latencies.push(timer.elapsed().as_micros() as u64);
+                        if results.iter().any(|r| &r.id == id) {
+                            found_self += 1;
+                        }
+                    }
+                    Err(_) => failures += 1,
+                }
+            }
+
+            latencies.sort_unstable();
+            report.canary_p50_us = latencies.get(latencies.len() / 2).copied();
+            report.canary_max_us = latencies.last().copied();
+            report.canary_self_recall = Some(found_self as f32 / canaries as f32);
+
+            let latency_status = if failures > 0 {
+                HealthStatus::Unhealthy
+            } else if report.canary_p50_us.unwrap_or(0) > config.latency_budget_ms * 1000 {
+                HealthStatus::Degraded
+            } else {
+                HealthStatus::Healthy
+            };
+            report.push(
+                "canary_latency",
+                latency_status,
+                format!(
+                    "p50 {}us, max {}us, {} failed",
+                    report.canary_p50_us.unwrap_or(0),
+                    report.canary_max_us.unwrap_or(0),
+                    failures
+                ),
+            );
+
+            let recall_status = if found_self * 2 >= canaries {
+                HealthStatus::Healthy
+            } else {
+                HealthStatus::Degraded
+            };
+            report.push(
+                "canary_recall",
+                recall_status,
+                format!("{} of {} canaries found themselves", found_self, canaries),
+            );
+        }
+
+        report.elapsed_ms = started.elapsed().as_millis() as u64;
+        if report.status != HealthStatus::Healthy {
+            tracing::warn!("Health check {:?}: {:?}", report.status, report.checks);
+        }
+        report
+    }
+
+    /// Run due maintenance tasks in the background when traffic is low
+    ///
+    /// See [`crate::maintenance`]. A scheduler that is already running is
+    /// stopped and replaced. The thread exits on its own once the last
+    /// `Arc` to the database is dropped.
+    pub fn start_maintenance(
+        self: &Arc<Self>,
+        config: MaintenanceConfig,
+    ) -> Arc<MaintenanceScheduler> {
+        let scheduler = MaintenanceScheduler::start(self, config);
+        let previous = self.maintenance.write().replace(Arc::clone(&scheduler));
+        if let Some(previous) = previous {
+            previous.stop();
+        }
+        scheduler
+    }
+
+    /// Stop the maintenance scheduler, returning it for its history
+    pub fn stop_maintenance(&self) -> Option<Arc<MaintenanceScheduler>> {
+        let scheduler = self.maintenance.write().take();
+        if let Some(scheduler) = &scheduler {
+            scheduler.stop();
+        }
+        scheduler
+    }
+
+    /// The running maintenance scheduler, if started
+    pub fn maintenance(&self) -> Option<Arc<MaintenanceScheduler>> {
+        self.maintenance.read().clone()
+    }
+
+    /// Maintenance tasks whose thresholds in `config` are reached
+    pub fn maintenance_due(&self, config: &MaintenanceConfig) -> Vec<MaintenanceTask> {
+        let mut due = Vec::new();
+        let kind = *self.index_kind.read();
+        match kind {
+            IndexKind::Configured if self.options.hnsw_config.is_some() => {
+                let tombstones = self.tombstones.load(Ordering::Relaxed);
+                let nodes = self.index.read().len() + tombstones;
+                if tombstones > 0 && tombstones as f32 >= config.tombstone_ratio * nodes as f32 {
+                    due.push(MaintenanceTask::CompactIndex);
+                }
+            }
+            IndexKind::Quantized { trained_on } if trained_on > 0 => {
+                let count = self.index.read().len();
+                let drift = count.abs_diff(trained_on) as f32 / trained_on as f32;
+                if drift >= config.codebook_drift {
+                    due.push(MaintenanceTask::RetrainCodebooks);
+                }
+            }
+            _ => {}
+        }
+        due
+    }
+
+    /// Run a maintenance task now, throttled by `config`
+    ///
+    /// Returns a [`MaintenanceOutcome::Superseded`] run, leaving the index
+    /// as it was, if a write landed while the task ran.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuvectorError::InvalidParameter`] if the task does not
+    /// apply to the index in use
+    pub fn run_maintenance(
+        &self,
+        task: MaintenanceTask,
+        config: &MaintenanceConfig,
+    ) -> Result<MaintenanceRun> {
+        self.run_maintenance_with(task, config, &AtomicBool::new(false))
+    }
+
+    pub(crate) fn run_maintenance_with(
+        &self,
+        task: MaintenanceTask,
+        config: &MaintenanceConfig,
+        cancel: &AtomicBool,
+    ) -> Result<MaintenanceRun> {
+        let kind = *self.index_kind.read();
+        match (task, kind) {
+            (MaintenanceTask::CompactIndex, IndexKind::Configured) => {}
+            (MaintenanceTask::RetrainCodebooks, IndexKind::Quantized { trained_on })
+                if trained_on > 0 => {}
+            _ => {
+                return Err(RuvectorError::InvalidParameter(format!(
+                    "{:?} does not apply to the {:?} index",
+                    task, kind
+                )))
+            }
+        }
+
+        let started = Instant::now();
+        let started_ms = chrono::Utc::now().timestamp_millis();
+        // Read before storage so any write from here on supersedes the run
+        let generation = self.write_generation.load(Ordering::Acquire);
+        let tombstones = self.tombstones.load(Ordering::Relaxed);
+
+        let mut reader = ThrottledReader {
+            config,
+            cancel,
+            throttled: Duration::ZERO,
+        };
+        let vectors = reader.read(self.storage.all_ids()?, |id| {
+            Ok(self.storage.get(id)?.map(|entry| entry.vector))
+        })?;
+
+        let mut run = MaintenanceRun {
+            task,
+            outcome: MaintenanceOutcome::Cancelled,
+            vectors: vectors.as_ref().map_or(0, Vec::len),
+            tombstones_removed: 0,
+            started_ms,
+            elapsed_ms: 0,
+            throttled_ms: 0,
+        };
+
+        if let Some(vectors) = vectors.filter(|_| !reader.cancelled() && !self.is_shut_down()) {
+            let (index, kind) = match task {
+                MaintenanceTask::CompactIndex => {
+                    let mut index = self.configured_index()?;
+                    index.add_batch(vectors)?;
+                    (index, IndexKind::Configured)
+                }
+                MaintenanceTask::RetrainCodebooks => self.quantized_index(vectors)?,
+            };
+            run.outcome = if self.swap_index(generation, index, kind) {
+                run.tombstones_removed = tombstones;
+                MaintenanceOutcome::Completed
+            } else {
+                MaintenanceOutcome::Superseded
+            };
+        }
+
+        run.elapsed_ms = started.elapsed().as_millis() as u64;
+        run.throttled_ms = reader.throttled.as_millis() as u64;
+        tracing::info!(
+            "Maintenance {:?} {:?} after {}ms ({} vectors, {}ms throttled)",
+            run.task,
+            run.outcome,
+            run.elapsed_ms,
+            run.vectors,
+            run.throttled_ms
+        );
+        Ok(run)
+    }
+
+    /// An empty index of the kind configured in `options`
+    fn configured_index(&self) -> Result<Box<dyn VectorIndex>> {
+        #[cfg(feature = "hnsw")]
+        if let Some(hnsw_config) = &self.options.hnsw_config {
+            return Ok(Box::new(HnswIndex::new(
+                self.options.dimensions,
+                self.options.distance_metric,
+                hnsw_config.clone(),
+            )?));
+        }
+        Ok(Box::new(FlatIndex::new(
+            self.options.dimensions,
+            self.options.distance_metric,
+        )))
+    }
+
+    /// Install an index rebuilt from storage, unless a write started or
+    /// landed since `generation` was read
+    fn swap_index(&self, generation: u64, index: Box<dyn VectorIndex>, kind: IndexKind) -> bool {
+        let mut current = self.index.write();
+        // A write in flight may already be in storage without being indexed
+        if self.write_generation.load(Ordering::Acquire) != generation
+            || self.lifecycle.writes_in_flight() > 0
+        {
+            return false;
+        }
+        *current = index;
+        *self.index_kind.write() = kind;
+        self.tombstones.store(0, Ordering::Relaxed);
+        self.mark_written();
+        true
+    }
+
+    /// Writes and searches admitted since the database was opened
+    pub(crate) fn ops_started(&self) -> u64 {
+        self.lifecycle.ops_started()
+    }
+
+    /// Stop accepting writes and searches, then wait up to `deadline` for
+    /// running ones to finish
+    ///
+    /// Every later write or search fails with [`RuvectorError::ShutDown`].
+    /// Calling it again only waits for whatever is still running. See
+    /// [`crate::shutdown`].
+    pub fn shutdown(&self, deadline: Duration) -> ShutdownReport {
+        self.stop_maintenance();
+        let mut report = self.lifecycle.close(deadline);
+        report.uncommitted_ingest = self.pending.read().len();
+
+        if report.is_clean() {
+            tracing::info!(
+                "Shut down after draining {} writes and {} searches in {}ms",
+                report.writes_drained,
+                report.searches_drained,
+                report.elapsed_ms
+            );
+        } else {
+            tracing::warn!(
+                "Shutdown deadline passed with {} writes and {} searches still running",
+                report.writes_abandoned,
+                report.searches_abandoned
+            );
+        }
+        report
+    }
+
+    /// Whether [`VectorDB::shutdown`] has been called
+    pub fn is_shut_down(&self) -> bool {
+        !self.lifecycle.is_open()
+    }
+
+    /// Start recording searches and feedback, returning the audit log
+    ///
+    /// If auditing is already enabled the existing log is kept.
+    pub fn enable_audit(&self, config: AuditConfig) -> Arc<AuditLog> {
+        self.audit
+            .write()
+            .get_or_insert_with(|| Arc::new(AuditLog::new(config)))
+            .clone()
+    }
+
+    /// Stop recording searches, returning the log that was in use
+    pub fn disable_audit(&self) -> Option<Arc<AuditLog>> {
+        self.audit.write().take()
+    }
+
+    /// The active audit log, if auditing is enabled
+    pub fn audit_log(&self) -> Option<Arc<AuditLog>> {
+        self.audit.read().clone()
+    }
+
+    /// Record downstream feedback for a result of an audited search
+    ///
+    /// The resulting [`FeedbackEvent`] is also delivered to the sinks
+    /// subscribed on the audit log.
+    pub fn record_feedback(
+        &self,
+        query_id: QueryId,
+        result_id: &str,
+        feedback: Feedback,
+    ) -> Result<FeedbackEvent> {
+        self.audit_log()
+            .ok_or_else(|| RuvectorError::InvalidInput("Auditing is not enabled".to_string()))?
+            .record_feedback(query_id, result_id, feedback)
+    }
+}
+
+/// Vectors inserted but not yet visible to searches
+///
+/// Created by [`VectorDB::begin_ingest`]. Inserted vectors are stored and
+/// indexed immediately but hidden from searches until [`IngestBatch::commit`].
+/// Point lookups with [`VectorDB::get`] see them right away. Centroid groups
+/// only take the batch into account once it is committed.
+///
+/// Which ids are uncommitted is only tracked in memory: if the process exits
+/// without dropping the batch, its vectors are visible after reopening.
+pub struct IngestBatch<'a> {
+    db: &'a VectorDB,
+    ids: Vec<VectorId>,
+    /// Committed entries the batch overwrote, restored on rollback
+    replaced: Vec<VectorEntry>,
+    committed: bool,
+}
+
+impl IngestBatch<'_> {
+    /// Store and index `entries` as part of this batch
+    ///
+    /// The index is updated in chunks, so concurrent searches are only
+    /// blocked briefly. Fails without storing anything if an entry would
+    /// overwrite a committed vector, since that would hide the committed
+    /// version until the batch commits.
+    pub fn insert_batch(&mut self, entries: Vec<VectorEntry>) -> Result<Vec<VectorId>> {
+        let _write = self.db.lifecycle.begin_write()?;
+        {
+            let pending = self.db.pending.read();
+            for id in entries.iter().filter_map(|e| e.id.as_deref()) {
+                if !pending.contains(id)
+                    && self.db.maybe_contains(id)
+                    && self.db.storage.get(id)?.is_some()
+                {
+                    return Err(RuvectorError::InvalidInput(format!(
+                        "Ingest batch cannot overwrite committed vector {}",
+                        id
+                    )));
+                }
+            }
+        }
+        self.insert_unguarded(entries)
+    }
+
+    fn insert_unguarded(&mut self, mut entries: Vec<VectorEntry>) -> Result<Vec<VectorId>> {
+        self.db.check_backpressure()?;
+        self.db.normalize_inserts(&mut entries);
+        let all_ids = self.db.assign_content_ids(&mut entries)?;
+        // Ids already pending have no committed version to restore or to
+        // take out of a centroid
+        let mut replaced = Vec::new();
+        {
+            let pending = self.db.pending.read();
+            let mut seen = HashSet::new();
+            for id in entries.iter().filter_map(|e| e.id.as_deref()) {
+                if seen.insert(id) && !pending.contains(id) && self.db.maybe_contains(id) {
+                    replaced.extend(self.db.storage.get(id)?);
+                }
+            }
+        }
+        let ids = self.db.storage.insert_batch(&entries)?;
+        self.db.remember_ids(&ids)?;
+        self.db.log_ops(|log| {
+            for (id, entry) in ids.iter().zip(&entries) {
+                log.record_insert(id, entry);
+            }
+        });
+        self.db.pending.write().extend(ids.iter().cloned());
+        self.ids.extend(ids.iter().cloned());
+        self.replaced.extend(replaced);
+
+        let mut index_entries = ids
+            .iter()
+            .cloned()
+            .zip(entries.into_iter().map(|entry| entry.vector));
+        loop {
+            let chunk: Vec<_> = index_entries.by_ref().take(INGEST_CHUNK).collect();
+            if chunk.is_empty() {
+                break;
+            }
+            self.db.index_write().add_batch(chunk)?;
+        }
+
+        Ok(all_ids.unwrap_or(ids))
+    }
+
+    /// Number of vectors in the batch
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Whether the batch is empty
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Make the whole batch visible to searches, returning its ids
+    ///
+    /// Centroid groups are updated first; if that fails the batch is rolled
+    /// back when dropped.
+    pub fn commit(mut self) -> Result<Vec<VectorId>> {
+        let mut seen = HashSet::new();
+        let added = self.db.centroid_members(
+            self.ids
+                .iter()
+                .filter(|id| seen.insert(id.as_str()))
+                .map(String::as_str),
+        )?;
+        self.db.update_centroids(&self.replaced, &added)?;
+
+        let mut pending = self.db.pending.write();
+        for id in &self.ids {
+            pending.remove(id);
+        }
+        self.db.mark_written();
+        self.committed = true;
+        Ok(std::mem::take(&mut self.ids))
+    }
+
+    /// Remove everything the batch inserted, restoring the committed
+    /// vectors it overwrote
+    pub fn abort(mut self) -> Result<usize> {
+        self.committed = true;
+        self.rollback()
+    }
+
+    fn rollback(&mut self) -> Result<usize> {
+        let ids = std::mem::take(&mut self.ids);
+        let replaced = std::mem::take(&mut self.replaced);
+        let deleted = self.db.storage.delete_batch(&ids)?;
+        self.db
+            .log_ops(|log| ids.iter().for_each(|id| log.record_delete(id)));
+        {
+            let mut index = self.db.index_write();
+            for id in &ids {
+                if index.remove(id)? {
+                    self.db.tombstones.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+
+        self.db.storage.insert_batch(&replaced)?;
+        self.db.log_ops(|log| {
+            for entry in &replaced {
+                if let Some(id) = &entry.id {
+                    log.record_insert(id, entry);
+                }
+            }
+        });
+        let restored = replaced.len();
+        self.db.index_write().add_batch(
+            replaced
+                .into_iter()
+                .filter_map(|entry| Some((entry.id?, entry.vector)))
+                .collect(),
+        )?;
+
+        let mut pending = self.db.pending.write();
+        for id in &ids {
+            pending.remove(id);
+        }
+        Ok(deleted.saturating_sub(restored))
+    }
+}
+
+impl Drop for IngestBatch<'_> {
+    fn drop(&mut self) {
+        if !self.committed && !self.ids.is_empty() {
+            if let Err(e) = self.rollback() {
+                tracing::warn!("Failed to roll back uncommitted ingest batch: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_vector_db_creation() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 3;
+
+        let db = VectorDB::new(options)?;
+        assert!(db.is_empty()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_and_search() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 3;
+        options.distance_metric = DistanceMetric::Euclidean; // Use Euclidean for clearer test
+        options.hnsw_config = None; // Use flat index for testing
+
+        let db = VectorDB::new(options)?;
+
+        // Insert vectors
+        db.insert(VectorEntry {
+            id: Some("v1".to_string()),
+            vector: vec![1.0, 0.0, 0.0],
+            metadata: None,
+        })?;
+
+        db.insert(VectorEntry {
+            id: Some("v2".to_string()),
+            vector: vec![0.0, 1.0, 0.0],
+            metadata: None,
+        })?;
+
+        db.insert(VectorEntry {
+            id: Some("v3".to_string()),
+            vector: vec![0.0, 0.0, 1.0],
+            metadata: None,
+        })?;
+
+        // Search for exact match
+        let results = db.search(SearchQuery {
+            vector: vec![1.0, 0.0, 0.0],
+            k: 2,
+            filter: None,
+            ef_search: None,
+        })?;
+
+        assert!(results.len() >= 1);
+        assert_eq!(results[0].id, "v1", "First result should be exact match");
+        assert!(
+            results[0].score < 0.01,
+            "Exact match should have ~0 distance"
+        );
+
+        Ok(())
+    }
+
+    /// Test that search works after simulated restart (new VectorDB instance)
+    /// This verifies the fix for issue #30: HNSW index not rebuilt from storage
+    #[test]
+    #[cfg(feature = "storage")]
+    fn test_search_after_restart() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("persist.db").to_string_lossy().to_string();
+
+        // Phase 1: Create database and insert vectors
+        {
+            let mut options = DbOptions::default();
+            options.storage_path = db_path.clone();
+            options.dimensions = 3;
+            options.distance_metric = DistanceMetric::Euclidean;
+            options.hnsw_config = None;
+
+            let db = VectorDB::new(options)?;
+
+            db.insert(VectorEntry {
+                id: Some("v1".to_string()),
+                vector: vec![1.0, 0.0, 0.0],
+                metadata: None,
+            })?;
+
+            db.insert(VectorEntry {
+                id: Some("v2".to_string()),
+                vector: vec![0.0, 1.0, 0.0],
+                metadata: None,
+            })?;
+
+            db.insert(VectorEntry {
+                id: Some("v3".to_string()),
+                vector: vec![0.7, 0.7, 0.0],
+                metadata: None,
+            })?;
+
+            // Verify search works before "restart"
+            let results = db.search(SearchQuery {
+                vector: vec![0.8, 0.6, 0.0],
+                k: 3,
+                filter: None,
+                ef_search: None,
+            })?;
+            assert_eq!(results.len(), 3, "Should find all 3 vectors before restart");
+        }
+        // db is dropped here, simulating application shutdown
+
+        // Phase 2: Create new database instance (simulates restart)
+        {
+            let mut options = DbOptions::default();
+            options.storage_path = db_path.clone();
+            options.dimensions = 3;
+            options.distance_metric = DistanceMetric::Euclidean;
+            options.hnsw_config = None;
+
+            let db = VectorDB::new(options)?;
+
+            // Verify vectors are still accessible
+            assert_eq!(db.len()?, 3, "Should have 3 vectors after restart");
+
+            // Verify get() works
+            let v1 = db.get("v1")?;
+            assert!(v1.is_some(), "get() should work after restart");
+
+            // Verify search() works - THIS WAS THE BUG
+            let results = db.search(SearchQuery {
+                vector: vec![0.8, 0.6, 0.0],
+                k: 3,
+                filter: None,
+                ef_search: None,
+            })?;
+
+            assert_eq!(
+                results.len(),
+                3,
+                "search() should return results after restart (was returning 0 before fix)"
+            );
+
+            // v3 should be closest to query [0.8, 0.6, 0.0]
+            assert_eq!(
+                results[0].id, "v3",
+                "v3 [0.7, 0.7, 0.0] should be closest to query [0.8, 0.6, 0.0]"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_audited_search_feedback() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        for (id, vector) in [("a", vec![1.0, 0.0]), ("b", vec![0.0, 1.0])] {
+            db.insert(VectorEntry {
+                id: Some(id.to_string()),
+                vector,
+                metadata: None,
+            })?;
+        }
+
+        let query = SearchQuery {
+            vector: vec![1.0, 0.1],
+            k: 2,
+            filter: None,
+            ef_search: None,
+        };
+        let (query_id, _) = db.search_audited(query.clone())?;
+        assert!(query_id.is_none());
+        assert!(db.record_feedback(1, "a", Feedback::Accepted).is_err());
+
+        let log = db.enable_audit(AuditConfig::default());
+        let (query_id, results) = db.search_audited(query)?;
+        let query_id = query_id.unwrap();
+        assert_eq!(results[0].id, "a");
+
+        let event = db.record_feedback(query_id, "a", Feedback::Accepted)?;
+        assert_eq!(event.rank, 0);
+        assert_eq!(event.query_vector.as_deref(), Some(&[1.0, 0.1][..]));
+        assert_eq!(log.trace("a")[0].feedback.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_knn_graph() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        for (id, vector) in [
+            ("a", vec![0.0, 0.0]),
+            ("b", vec![0.1, 0.0]),
+            ("c", vec![5.0, 5.0]),
+        ] {
+            db.insert(VectorEntry {
+                id: Some(id.to_string()),
+                vector,
+                metadata: None,
+            })?;
+        }
+
+        let graph = db.export_knn_graph(1)?;
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 3);
+        assert!(graph.edges.iter().all(|e| e.source != e.target));
+        let from_a = graph.edges.iter().find(|e| e.source == "a").unwrap();
+        assert_eq!(from_a.target, "b");
+
+        assert!(db.export_knn_graph(0).is_err());
+
+        // Centroids and uncommitted vectors sit right next to "a" but are
+        // neither nodes nor neighbors
+        db.insert(VectorEntry {
+            id: Some("b".to_string()),
+            vector: vec![0.1, 0.0],
+            metadata: Some(HashMap::from([(
+                "user".to_string(),
+                serde_json::json!("u1"),
+            )])),
+        })?;
+        db.define_centroid_group("user")?;
+        let mut batch = db.begin_ingest();
+        batch.insert_batch(vec![VectorEntry {
+            id: Some("pending".to_string()),
+            vector: vec![0.0, 0.01],
+            metadata: None,
+        }])?;
+        let graph = db.export_knn_graph(1)?;
+        assert_eq!(graph.node_count(), 3);
+        assert!(graph
+            .edges
+            .iter()
+            .all(|e| ["a", "b", "c"].contains(&e.target.as_str())));
+        batch.abort()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_subgraph() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("graph.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        let db = VectorDB::new(options)?;
+        for i in 0..50 {
+            db.insert(VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: vec![i as f32, 1.0],
+                metadata: None,
+            })?;
+        }
+
+        let seeds = ["v10".to_string(), "v40".to_string()];
+        let graph = db.subgraph(&seeds, 1, 100)?;
+        assert_eq!(&graph.nodes[..2], &seeds);
+        assert_eq!(graph.hops[..2], [0, 0]);
+        assert!(graph.hops.iter().all(|&h| h <= 1));
+        let first_hop = db.neighbors("v10", 0)?;
+        assert!(first_hop
+            .iter()
+            .all(|n| graph.nodes.contains(n.id.as_ref().unwrap())));
+        assert!(graph
+            .edges
+            .iter()
+            .all(|e| graph.nodes.contains(&e.source) && graph.nodes.contains(&e.target)));
+
+        let capped = db.subgraph(&seeds, 3, 5)?;
+        assert_eq!(capped.node_count(), 5);
+        assert_eq!(db.subgraph(&seeds, 0, 10)?.node_count(), 2);
+
+        assert!(db.subgraph(&seeds, 1, 0).is_err());
+        assert!(matches!(
+            db.subgraph(&["missing".to_string()], 1, 10),
+            Err(RuvectorError::VectorNotFound(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_subgraph_without_graph() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("flat.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.hnsw_config = None;
+        let db = VectorDB::new(options)?;
+        for i in 0..50 {
+            db.insert(VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: vec![i as f32, 1.0],
+                metadata: None,
+            })?;
+        }
+
+        // Each node links to its nearest neighbors instead
+        let graph = db.subgraph(&["v10".to_string()], 1, 100)?;
+        assert_eq!(graph.node_count(), 1 + SUBGRAPH_KNN_FANOUT);
+        assert!(graph.hops.iter().all(|&h| h <= 1));
+        assert!(matches!(
+            db.subgraph(&["missing".to_string()], 1, 10),
+            Err(RuvectorError::VectorNotFound(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dedupe_delete() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.distance_metric = DistanceMetric::Cosine;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        let log = db.enable_op_log(OpLogConfig {
+            capture: crate::op_log::VectorCapture::Hashed,
+            capture_metadata: true,
+        });
+        for (id, vector) in [
+            ("a", vec![1.0, 0.0]),
+            ("b", vec![2.0, 0.001]),
+            ("c", vec![0.0, 1.0]),
+        ] {
+            db.insert(VectorEntry {
+                id: Some(id.to_string()),
+                vector,
+                metadata: None,
+            })?;
+        }
+
+        let mut config = DedupeConfig {
+            threshold: 0.99,
+            candidates: 2,
+            action: DuplicateAction::Tag,
+        };
+        let report = db.dedupe(&config)?;
+        assert_eq!(report.duplicate_count(), 1);
+        assert_eq!(report.groups[0].keep, "a");
+        assert_eq!(
+            db.get("b")?.unwrap().metadata.unwrap()[DUPLICATE_OF_KEY],
+            serde_json::json!("a")
+        );
+        match &log.records()[3].op {
+            crate::op_log::Operation::Insert { id, metadata, .. } => {
+                assert_eq!(id, "b");
+                assert_eq!(metadata.as_ref().unwrap()[DUPLICATE_OF_KEY], "a");
+            }
+            other => panic!("unexpected op {:?}", other),
+        }
+
+        config.action = DuplicateAction::Delete;
+        let report = db.dedupe(&config)?;
+        assert_eq!(report.deleted, 1);
+        assert_eq!(db.len()?, 2);
+        assert!(db.get("b")?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_dedupe_skips_centroids() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        db.insert(VectorEntry {
+            id: Some("a".to_string()),
+            vector: vec![1.0, 0.0],
+            metadata: Some(HashMap::from([(
+                "user".to_string(),
+                serde_json::json!("u1"),
+            )])),
+        })?;
+        db.define_centroid_group("user")?;
+        // The centroid of a one-member group is an exact copy of the member
+        assert_eq!(db.centroid("user", "u1")?.unwrap().vector, vec![1.0, 0.0]);
+
+        let report = db.dedupe(&DedupeConfig {
+            threshold: 0.99,
+            candidates: 2,
+            action: DuplicateAction::Delete,
+        })?;
+        assert_eq!(report.scanned, 1);
+        assert!(report.groups.is_empty());
+        assert_eq!(report.deleted, 0);
+        assert!(db.get("a")?.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_project_2d() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 3;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        for i in 0..10 {
+            db.insert(VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: vec![i as f32, 1.0, 0.5],
+                metadata: None,
+            })?;
+        }
+
+        let points = db.project_2d(ProjectionMethod::Pca)?;
+        assert_eq!(points.len(), 10);
+        assert!(points.iter().all(|p| p.coords.len() == 2));
+
+        // Centroids and uncommitted vectors are not projected
+        db.insert(VectorEntry {
+            id: Some("v0".to_string()),
+            vector: vec![0.0, 1.0, 0.5],
+            metadata: Some(HashMap::from([(
+                "user".to_string(),
+                serde_json::json!("u1"),
+            )])),
+        })?;
+        db.define_centroid_group("user")?;
+        let mut batch = db.begin_ingest();
+        batch.insert_batch(vec![VectorEntry {
+            id: Some("pending".to_string()),
+            vector: vec![0.0, 0.0, 0.0],
+            metadata: None,
+        }])?;
+        let points = db.project_2d(ProjectionMethod::Pca)?;
+        assert_eq!(points.len(), 10);
+        assert!(points.iter().all(|p| p.id.starts_with('v')));
+        batch.abort()?;
+
+        let config = ProjectionConfig {
+            output_dims: 4,
+            ..Default::default()
+        };
+        assert!(db.project(&config).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_expression() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        for (id, vector) in [
+            ("king", vec![1.0, 1.0]),
+            ("man", vec![1.0, 0.0]),
+            ("woman", vec![0.0, 0.0]),
+            ("queen", vec![0.0, 1.0]),
+        ] {
+            db.insert(VectorEntry {
+                id: Some(id.to_string()),
+                vector,
+                metadata: None,
+            })?;
+        }
+
+        let query = QueryVector::from_id("king").subtract("man").add("woman");
+        assert_eq!(db.resolve_query_vector(&query)?, vec![0.0, 1.0]);
+
+        let results = db.search_expression(&query, 1, None)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "queen");
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_template() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        for (id, vector, tenant) in [
+            ("a", vec![0.0, 0.0], "acme"),
+            ("b", vec![0.1, 0.0], "globex"),
+            ("c", vec![1.0, 0.0], "acme"),
+        ] {
+            let mut metadata = HashMap::new();
+            metadata.insert("tenant".to_string(), serde_json::json!(tenant));
+            db.insert(VectorEntry {
+                id: Some(id.to_string()),
+                vector,
+                metadata: Some(metadata),
+            })?;
+        }
+
+        db.register_template(
+            "by_tenant",
+            QueryTemplate::new(3).with_param("tenant", "tenant"),
+        )?;
+        assert_eq!(db.template_names(), vec!["by_tenant".to_string()]);
+
+        let params = HashMap::from([("tenant".to_string(), serde_json::json!("acme"))]);
+        let results = db.search_template("by_tenant", vec![0.0, 0.0], &params)?;
+        let ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "c"]);
+
+        assert!(db
+            .search_template("by_tenant", vec![0.0, 0.0], &HashMap::new())
+            .is_err());
+        assert!(db
+            .search_template("missing", vec![0.0, 0.0], &params)
+            .is_err());
+        assert!(db.remove_template("by_tenant"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_warmup() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 3;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        for i in 0..20 {
+            db.insert(VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: vec![i as f32, 1.0, 0.0],
+                metadata: None,
+            })?;
+        }
+
+        let config = WarmupConfig {
+            index_probes: 5,
+            canary_queries: vec![SearchQuery {
+                vector: vec![1.0, 1.0, 0.0],
+                k: 3,
+                filter: None,
+                ef_search: None,
+            }],
+            canary_rounds: 2,
+            ..Default::default()
+        };
+        let mut events = Vec::new();
+        let report = db.warmup_with_progress(&config, |p| events.push(p))?;
+
+        assert_eq!(report.vectors_read, 20);
+        assert_eq!(report.bytes_read, 20 * 3 * 4);
+        assert_eq!(report.index_probes, 5);
+        assert_eq!(report.canary_latencies_us.len(), 1);
+        assert!(report.canary_p99_us.is_some());
+        assert_eq!(
+            events.last(),
+            Some(&WarmupProgress {
+                phase: WarmupPhase::Canaries,
+                done: 2,
+                total: 2,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ingest_batch_is_invisible_until_commit() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        db.insert(VectorEntry {
+            id: Some("old".to_string()),
+            vector: vec![5.0, 5.0],
+            metadata: None,
+        })?;
+
+        let entry = |id: &str, x: f32| VectorEntry {
+            id: Some(id.to_string()),
+            vector: vec![x, 0.0],
+            metadata: None,
+        };
+        let query = || SearchQuery {
+            vector: vec![0.0, 0.0],
+            k: 2,
+            filter: None,
+            ef_search: None,
+        };
+
+        let mut batch = db.begin_ingest();
+        batch.insert_batch(vec![entry("a", 0.0), entry("b", 0.1)])?;
+        batch.insert_batch(vec![entry("c", 0.2)])?;
+        assert_eq!(batch.len(), 3);
+
+        let ids: Vec<_> = db.search(query())?.into_iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec!["old".to_string()]);
+
+        batch.commit()?;
+        let ids: Vec<_> = db.search(query())?.into_iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec!["a".to_string(), "b".to_string()]);
+
+        // Dropping an uncommitted batch rolls it back
+        {
+            let mut batch = db.begin_ingest();
+            batch.insert_batch(vec![entry("d", 0.0)])?;
+            batch.insert_batch(vec![entry("d", 0.1)])?;
+            assert!(batch.insert_batch(vec![entry("old", 0.0)]).is_err());
+        }
+        assert!(db.get("d")?.is_none());
+        assert_eq!(db.get("old")?.unwrap().vector, vec![5.0, 5.0]);
+        assert_eq!(db.search(query())?.len(), 2);
+
+        // Rolling back an overwrite restores the committed version
+        let mut batch = db.begin_ingest();
+        batch.insert_unguarded(vec![entry("old", 0.0), entry("e", 0.0)])?;
+        assert_eq!(batch.abort()?, 1);
+        assert_eq!(db.get("old")?.unwrap().vector, vec![5.0, 5.0]);
+        let results = db.search(SearchQuery {
+            vector: vec![5.0, 5.0],
+            k: 1,
+            filter: None,
+            ef_search: None,
+        })?;
+        assert_eq!(results[0].id, "old");
+        Ok(())
+    }
+
+    #[test]
+    fn test_bulk_load() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 4;
+
+        let db = VectorDB::new(options)?;
+        let entries = (0..250).map(|i| VectorEntry {
+            id: Some(format!("v{}", i)),
+            vector: vec![i as f32, 1.0, 0.5, 0.25],
+            metadata: None,
+        });
+        let config = BulkLoadConfig {
+            chunk_size: 100,
+            max_staged_bytes: 150 * 4 * 4,
+            min_available_bytes: 0,
+            ..Default::default()
+        };
+
+        let report = db.bulk_load_with(entries, &config)?;
+        assert_eq!(report.inserted, 250);
+        assert_eq!(report.chunks, 3);
+        assert_eq!(report.index_passes, 2);
+        assert_eq!(db.len()?, 250);
+
+        let results = db.search(SearchQuery {
+            vector: vec![42.0, 1.0, 0.5, 0.25],
+            k: 1,
+            filter: None,
+            ef_search: None,
+        })?;
+        assert_eq!(results[0].id, "v42");
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_index_from_neighbors() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 1;
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        for i in 0..5 {
+            db.insert(VectorEntry {
+                id: Some(format!("n{}", i)),
+                vector: vec![i as f32],
+                metadata: None,
+            })?;
+        }
+
+        // A chain n0 - n1 - ... - n4, given one direction only
+        let lists: HashMap<VectorId, Vec<VectorId>> = (0..4)
+            .map(|i| (format!("n{}", i), vec![format!("n{}", i + 1)]))
+            .collect();
+        db.build_index_from_neighbors(&lists, NeighborGraphConfig::default())?;
+
+        let results = db.search(SearchQuery {
+            vector: vec![3.9],
+            k: 2,
+            filter: None,
+            ef_search: None,
+        })?;
+        let ids: Vec<_> = results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["n4", "n3"]);
+
+        let bad = HashMap::from([("n0".to_string(), vec!["missing".to_string()])]);
+        assert!(db
+            .build_index_from_neighbors(&bad, NeighborGraphConfig::default())
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_quantized_search_with_rerank() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 8;
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+        options.quantization = Some(QuantizationConfig::Product {
+            subspaces: 4,
+            k: 16,
+        });
+
+        let db = VectorDB::new(options)?;
+        let entries: Vec<VectorEntry> = (0..300)
+            .map(|i| VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: (0..8)
+                    .map(|j| ((i * 31 + j * 17) as f32 * 0.618).fract())
+                    .collect(),
+                metadata: None,
+            })
+            .collect();
+        let target = entries[123].vector.clone();
+        db.insert_batch(entries)?;
+        db.build_quantized_index()?;
+
+        let query = SearchQuery {
+            vector: target,
+            k: 5,
+            filter: None,
+            ef_search: None,
+        };
+        let approximate = db.search_with_precision(query.clone(), SearchPrecision::Approximate)?;
+        assert_eq!(approximate.len(), 5);
+
+        let reranked =
+            db.search_with_precision(query, SearchPrecision::Rerank { oversample: 20 })?;
+        assert_eq!(reranked[0].id, "v123");
+        assert!(reranked[0].score < 1e-6);
+        assert!(reranked.windows(2).all(|w| w[0].score <= w[1].score));
+
+        // Inserts after the build are encoded with the trained codebooks
+        db.insert(VectorEntry {
+            id: Some("late".to_string()),
+            vector: vec![0.5; 8],
+            metadata: None,
+        })?;
+        assert_eq!(db.index.read().len(), 301);
+        Ok(())
+    }
+
+    #[test]
+    fn test_adaptive_ef() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 4;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        let query = SearchQuery {
+            vector: vec![0.5; 4],
+            k: 3,
+            filter: None,
+            ef_search: None,
+        };
+        assert!(db.enable_adaptive_ef(AdaptiveEfConfig::default()).is_err());
+
+        for i in 0..50 {
+            db.insert(VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: (0..4)
+                    .map(|j| ((i * 7 + j * 3) % 11) as f32 / 11.0)
+                    .collect(),
+                metadata: None,
+            })?;
+        }
+        db.enable_adaptive_ef(AdaptiveEfConfig::default())?;
+
+        let (results, difficulty) = db.search_with_difficulty(query.clone())?;
+        assert_eq!(results.len(), 3);
+        let difficulty = difficulty.unwrap();
+        assert!((16..=512).contains(&difficulty.ef_search));
+
+        // An explicit ef_search bypasses the predictor
+        let explicit = SearchQuery {
+            ef_search: Some(100),
+            ..query
+        };
+        assert!(db.search_with_difficulty(explicit)?.1.is_none());
+
+        let model = db.train_adaptive_ef(20, 3)?;
+        assert_eq!(db.adaptive_ef().unwrap().model, model);
+        assert!(db.disable_adaptive_ef().is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_result_cache_invalidated_by_writes() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        db.insert(VectorEntry {
+            id: Some("far".to_string()),
+            vector: vec![5.0, 5.0],
+            metadata: None,
+        })?;
+        let cache = db.enable_result_cache(ResultCacheConfig::default());
+
+        let query = SearchQuery {
+            vector: vec![0.0, 0.0],
+            k: 1,
+            filter: None,
+            ef_search: None,
+        };
+        assert_eq!(db.search(query.clone())?[0].id, "far");
+        assert_eq!(db.search(query.clone())?[0].id, "far");
+        assert_eq!(cache.stats().hits, 1);
+
+        db.insert(VectorEntry {
+            id: Some("near".to_string()),
+            vector: vec![0.1, 0.0],
+            metadata: None,
+        })?;
+        assert_eq!(db.search(query.clone())?[0].id, "near");
+
+        db.insert_batch(vec![VectorEntry {
+            id: Some("nearest".to_string()),
+            vector: vec![0.0, 0.0],
+            metadata: None,
+        }])?;
+        assert_eq!(db.search(query)?[0].id, "nearest");
+        assert_eq!(cache.stats().invalidations, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_context() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        let docs = [
+            ("intro", vec![1.0, 0.0], "Ruvector stores vectors."),
+            ("intro_dup", vec![1.0, -0.0001], "Ruvector stores vectors!"),
+            ("search", vec![0.8, 0.6], "Search uses HNSW graphs."),
+            ("other", vec![0.0, 1.0], "Unrelated."),
+        ];
+        for (id, vector, text) in docs {
+            let mut metadata = HashMap::new();
+            metadata.insert("text".to_string(), serde_json::json!(text));
+            db.insert(VectorEntry {
+                id: Some(id.to_string()),
+                vector,
+                metadata: Some(metadata),
+            })?;
+        }
+
+        let query = SearchQuery {
+            vector: vec![1.0, 0.1],
+            k: 3,
+            filter: None,
+            ef_search: None,
+        };
+        let pack = db.build_context(query.clone(), 14, true, Some(0.7))?;
+        let ids: Vec<_> = pack.chunks.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["intro", "search"]);
+        assert_eq!(pack.duplicates_removed, 1);
+        assert!(pack.total_tokens <= 14);
+
+        let all = db.build_context(query, 1000, false, None)?;
+        assert_eq!(all.chunks.len(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_slow_query_log() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 3;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        for i in 0..10 {
+            let mut metadata = HashMap::new();
+            metadata.insert("lang".to_string(), serde_json::json!("en"));
+            db.insert(VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: vec![i as f32, 1.0, 0.0],
+                metadata: Some(metadata),
+            })?;
+        }
+
+        let log = db.enable_slow_query_log(SlowQueryConfig {
+            threshold: std::time::Duration::from_secs(3600),
+            capacity: 10,
+        });
+        let mut query = SearchQuery {
+            vector: vec![3.0, 4.0, 0.0],
+            k: 5,
+            filter: None,
+            ef_search: Some(32),
+        };
+        db.search(query.clone())?;
+        assert!(log.is_empty());
+
+        log.set_threshold(std::time::Duration::ZERO);
+        query.filter = Some(HashMap::from([(
+            "lang".to_string(),
+            serde_json::json!("en"),
+        )]));
+        db.search(query)?;
+
+        let entries = log.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].k, 5);
+        assert_eq!(entries[0].results, 5);
+        assert_eq!(entries[0].ef_search, Some(32));
+        assert!(entries[0].candidates >= 5);
+        assert_eq!(entries[0].filter_shape, vec!["lang:string"]);
+        assert!((entries[0].vector.norm - 5.0).abs() < 1e-6);
+
+        assert!(db.disable_slow_query_log().is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_health_check_deep() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 3;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        for i in 0..20 {
+            db.insert(VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: vec![i as f32, 1.0, (i % 3) as f32],
+                metadata: None,
+            })?;
+        }
+
+        let quick = db.health_check();
+        assert_eq!(quick.status, HealthStatus::Healthy);
+        assert!(!quick.deep);
+        assert_eq!((quick.vectors, quick.indexed, quick.index_lag), (20, 20, 0));
+
+        // Arena usage is reported alongside
+        let arena = crate::arena::Arena::with_tag(4096, "health_test");
+        arena.alloc_vec::<f32>(16);
+        let report = db.health_check();
+        assert!(report.arena.live_arenas >= 1);
+        assert!(report.arena.allocations >= 1);
+        drop(arena);
+
+        let deep = db.health_check_deep(&HealthCheckConfig {
+            sample_size: 10,
+            canary_queries: 4,
+            latency_budget_ms: 10_000,
+            ..Default::default()
+        });
+        assert_eq!(deep.status, HealthStatus::Healthy);
+        assert_eq!(deep.sampled, 10);
+        assert!(deep.corrupt.is_empty());
+        assert_eq!(deep.canary_self_recall, Some(1.0));
+        assert!(deep.canary_p50_us.is_some());
+        assert!(deep.is_ready());
+        Ok(())
+    }
+
+    #[test]
+    fn test_shutdown_rejects_new_operations() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        db.insert_batch(vec![VectorEntry {
+            id: Some("a".to_string()),
+            vector: vec![1.0, 0.0],
+            metadata: None,
+        }])?;
+
+        let mut batch = db.begin_ingest();
+        batch.insert_batch(vec![VectorEntry {
+            id: Some("b".to_string()),
+            vector: vec![0.0, 1.0],
+            metadata: None,
+        }])?;
+
+        let report = db.shutdown(Duration::from_secs(1));
+        assert!(report.initiated);
+        assert!(report.is_clean());
+        assert_eq!(report.uncommitted_ingest, 1);
+        assert!(db.is_shut_down());
+
+        let entry = VectorEntry {
+            id: Some("c".to_string()),
+            vector: vec![1.0, 1.0],
+            metadata: None,
+        };
+        assert!(matches!(db.insert(entry), Err(RuvectorError::ShutDown)));
+        let query = SearchQuery {
+            vector: vec![1.0, 0.0],
+            k: 1,
+            filter: None,
+            ef_search: None,
+        };
+        assert!(matches!(db.search(query), Err(RuvectorError::ShutDown)));
+
+        // Committed data is still readable
+        assert!(db.get("a")?.is_some());
+        drop(batch);
+        Ok(())
+    }
+
+    #[test]
+    fn test_compaction_drops_tombstones() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.hnsw_config = Some(HnswConfig {
+            max_elements: 1000,
+            ..Default::default()
+        });
+
+        let db = VectorDB::new(options)?;
+        db.insert_batch(
+            (0..10)
+                .map(|i| VectorEntry {
+                    id: Some(format!("v{}", i)),
+                    vector: vec![i as f32, 1.0],
+                    metadata: None,
+                })
+                .collect(),
+        )?;
+
+        let config = MaintenanceConfig {
+            max_reads_per_sec: None,
+            cpu_duty_cycle: 1.0,
+            ..Default::default()
+        };
+        db.delete("v0")?;
+        assert!(db.maintenance_due(&config).is_empty());
+        for i in 1..4 {
+            db.delete(&format!("v{}", i))?;
+        }
+        assert_eq!(
+            db.maintenance_due(&config),
+            vec![MaintenanceTask::CompactIndex]
+        );
+        assert!(db
+            .run_maintenance(MaintenanceTask::RetrainCodebooks, &config)
+            .is_err());
+
+        let run = db.run_maintenance(MaintenanceTask::CompactIndex, &config)?;
+        assert_eq!(run.outcome, MaintenanceOutcome::Completed);
+        assert_eq!((run.vectors, run.tombstones_removed), (6, 4));
+        assert!(db.maintenance_due(&config).is_empty());
+
+        let results = db.search(SearchQuery {
+            vector: vec![4.0, 1.0],
+            k: 10,
+            filter: None,
+            ef_search: None,
+        })?;
+        assert_eq!(results.len(), 6);
+        assert_eq!(results[0].id, "v4");
+        Ok(())
+    }
+
+    #[test]
+    fn test_memory_budget_tiers_vectors() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 16;
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        let vector =
+            |i: usize| -> Vec<f32> { (0..16).map(|j| ((i * 16 + j) as f32).sin()).collect() };
+        db.insert_batch(
+            (0..20)
+                .map(|i| VectorEntry {
+                    id: Some(format!("v{}", i)),
+                    vector: vector(i),
+                    metadata: None,
+                })
+                .collect(),
+        )?;
+
+        // Room for everything cold (6 bytes each) plus one hot upgrade
+        let budget = MemoryBudget {
+            max_bytes: 20 * 6 + 58,
+            rebalance_every: 5,
+            ..Default::default()
+        };
+        let usage = db.enable_memory_budget(budget)?;
+        assert!(usage.used_bytes <= usage.budget_bytes);
+        assert_eq!(usage.hot + usage.warm + usage.cold, 20);
+
+        for _ in 0..5 {
+            let results = db.search(SearchQuery {
+                vector: vector(7),
+                k: 1,
+                filter: None,
+                ef_search: None,
+            })?;
+            assert_eq!(results[0].id, "v7");
+            assert!(results[0].score < 1e-4);
+        }
+        let usage = db.memory_usage().unwrap();
+        assert_eq!((usage.hot, usage.warm, usage.cold), (1, 0, 19));
+        assert!(usage.rebalances >= 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_memory_budget_reads_cold_vectors_from_spill_file() -> Result<()> {
+        use crate::index::tiered::ColdFile;
+        use crate::vector_file::ReadBackend;
+
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 16;
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        let vector =
+            |i: usize| -> Vec<f32> { (0..16).map(|j| ((i * 16 + j) as f32).sin()).collect() };
+        db.insert_batch(
+            (0..20)
+                .map(|i| VectorEntry {
+                    id: Some(format!("v{}", i)),
+                    vector: vector(i),
+                    metadata: None,
+                })
+                .collect(),
+        )?;
+
+        // Everything cold; every vector is a rescoring candidate
+        let spill = dir.path().join("cold.bin");
+        let budget = MemoryBudget {
+            max_bytes: 20 * 6,
+            rebalance_every: 1000,
+            oversample: 20,
+            cold_file: Some(ColdFile {
+                path: spill.clone(),
+                backend: ReadBackend::Sync,
+            }),
+            ..Default::default()
+        };
+        let usage = db.enable_memory_budget(budget)?;
+        assert_eq!(usage.cold, 20);
+        assert_eq!(std::fs::metadata(&spill)?.len(), 20 * 16 * 4);
+
+        for i in [0, 7, 19] {
+            let results = db.search(SearchQuery {
+                vector: vector(i),
+                k: 1,
+                filter: None,
+                ef_search: None,
+            })?;
+            assert_eq!(results[0].id, format!("v{}", i));
+            assert!(results[0].score < 1e-4);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_embedding_model_guard_persists() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.hnsw_config = None;
+
+        {
+            let db = VectorDB::new(options.clone())?;
+            assert!(db
+                .set_embedding_model(EmbeddingModel::new("minilm", 3), true)
+                .is_err());
+            db.set_embedding_model(EmbeddingModel::new("minilm", 2).with_version("v2"), true)?;
+            db.insert_batch_with_model(
+                vec![VectorEntry {
+                    id: Some("a".to_string()),
+                    vector: vec![1.0, 0.0],
+                    metadata: None,
+                }],
+                "minilm@v2",
+            )?;
+            assert!(matches!(
+                db.set_embedding_model(EmbeddingModel::new("mpnet", 2), true),
+                Err(RuvectorError::ModelMismatch { .. })
+            ));
+        }
+
+        let db = VectorDB::new(options)?;
+        let guard = db.embedding_model().unwrap();
+        assert_eq!(guard.model.reference(), "minilm@v2");
+        assert!(guard.strict);
+
+        let query = SearchQuery {
+            vector: vec![1.0, 0.0],
+            k: 1,
+            filter: None,
+            ef_search: None,
+        };
+        assert_eq!(db.search_with_model(query.clone(), "minilm")?.len(), 1);
+        assert!(matches!(
+            db.search_with_model(query, "mpnet"),
+            Err(RuvectorError::ModelMismatch { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalization_policy() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options.clone())?;
+        db.set_normalization(NormalizationPolicy::InsertAndQuery)?;
+        db.insert(VectorEntry {
+            id: Some("a".to_string()),
+            vector: vec![3.0, 4.0],
+            metadata: None,
+        })?;
+        assert_eq!(db.get("a")?.unwrap().vector, vec![0.6, 0.8]);
+
+        let query = SearchQuery {
+            vector: vec![30.0, 40.0],
+            k: 1,
+            filter: None,
+            ef_search: None,
+        };
+        let results = db.search(query.clone())?;
+        assert!(results[0].score < 1e-6);
+        let reranked =
+            db.search_with_precision(query, SearchPrecision::Rerank { oversample: 2 })?;
+        assert!(reranked[0].score < 1e-6);
+
+        db.set_normalization(NormalizationPolicy::None)?;
+        assert!(db.set_normalization(NormalizationPolicy::Insert).is_err());
+        drop(db);
+
+        let db = VectorDB::new(options)?;
+        assert_eq!(db.normalization(), NormalizationPolicy::None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_with_secondary_metric() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.distance_metric = DistanceMetric::Cosine;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        assert!(db
+            .set_secondary_metrics(vec![DistanceMetric::DotProduct])
+            .is_err());
+        db.set_normalization(NormalizationPolicy::Insert)?;
+        db.set_secondary_metrics(vec![DistanceMetric::DotProduct])?;
+        assert!(db.set_normalization(NormalizationPolicy::None).is_err());
+
+        db.insert_batch(vec![
+            VectorEntry {
+                id: Some("x".to_string()),
+                vector: vec![2.0, 0.0],
+                metadata: None,
+            },
+            VectorEntry {
+                id: Some("y".to_string()),
+                vector: vec![0.0, 5.0],
+                metadata: None,
+            },
+        ])?;
+
+        let query = SearchQuery {
+            vector: vec![3.0, 1.0],
+            k: 2,
+            filter: None,
+            ef_search: None,
+        };
+        let results = db.search_with_metric(query.clone(), DistanceMetric::DotProduct)?;
+        assert_eq!(results[0].id, "x");
+        assert!((results[0].score + 3.0).abs() < 1e-5);
+        assert!((results[1].score + 1.0).abs() < 1e-5);
+
+        assert!(db
+            .search_with_metric(query, DistanceMetric::Manhattan)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_op_log_replay() -> Result<()> {
+        use crate::op_log::{self, VectorCapture};
+
+        let open = |name: &str, dir: &tempfile::TempDir| {
+            let mut options = DbOptions::default();
+            options.storage_path = dir.path().join(name).to_string_lossy().to_string();
+            options.dimensions = 3;
+            options.hnsw_config = None;
+            VectorDB::new(options)
+        };
+        let dir = tempdir().unwrap();
+        let db = open("source.db", &dir)?;
+        let log = db.enable_op_log(OpLogConfig {
+            capture: VectorCapture::Full,
+            capture_metadata: true,
+        });
+
+        db.insert(VectorEntry {
+            id: Some("a".to_string()),
+            vector: vec![1.0, 0.0, 0.0],
+            metadata: Some(HashMap::from([("k".to_string(), serde_json::json!(1))])),
+        })?;
+        db.insert_batch(vec![
+            VectorEntry {
+                id: Some("b".to_string()),
+                vector: vec![0.0, 1.0, 0.0],
+                metadata: None,
+            },
+            VectorEntry {
+                id: Some("c".to_string()),
+                vector: vec![0.0, 0.0, 1.0],
+                metadata: None,
+            },
+        ])?;
+        db.delete("b")?;
+        db.delete("missing")?;
+        assert_eq!(log.len(), 4);
+
+        let mut exported = Vec::new();
+        log.write_jsonl(&mut exported)?;
+        let records = op_log::read_jsonl(exported.as_slice())?;
+
+        let replica = open("replica.db", &dir)?;
+        let report = op_log::replay(&replica, &records);
+        assert_eq!(report.applied, 4);
+        assert_eq!(report.synthesized, 0);
+        assert!(report.failed.is_empty() && report.hash_mismatches.is_empty());
+
+        let mut keys = replica.keys()?;
+        keys.sort();
+        assert_eq!(keys, vec!["a", "c"]);
+        let a = replica.get("a")?.unwrap();
+        assert_eq!(a.vector, vec![1.0, 0.0, 0.0]);
+        assert_eq!(a.metadata.unwrap()["k"], 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_soft_delete_and_restore() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 3;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options.clone())?;
+        for (id, vector) in [("a", vec![1.0, 0.0, 0.0]), ("b", vec![0.0, 1.0, 0.0])] {
+            db.insert(VectorEntry {
+                id: Some(id.to_string()),
+                vector,
+                metadata: Some(HashMap::from([(
+                    "owner".to_string(),
+                    serde_json::json!(id),
+                )])),
             })?;
+        }
+
+        assert!(db.soft_delete("a")?);
+        assert!(!db.soft_delete("a")?);
+        assert!(db.get("a")?.is_none());
+        let results = db.search(SearchQuery {
+            vector: vec![1.0, 0.0, 0.0],
+            k: 2,
+            filter: None,
+            ef_search: None,
+        })?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(db.trash()?.len(), 1);
 
-            // Verify search works before "restart"
-            let results = db.search(SearchQuery {
-                vector: vec![0.8, 0.6, 0.0],
-                k: 3,
+        assert!(db.restore("a")?);
+        assert!(!db.restore("a")?);
+        let restored = db.get("a")?.unwrap();
+        assert_eq!(restored.metadata.unwrap()["owner"], "a");
+        assert_eq!(
+            db.search(SearchQuery {
+                vector: vec![1.0, 0.0, 0.0],
+                k: 1,
                 filter: None,
                 ef_search: None,
-            })?;
-            assert_eq!(results.len(), 3, "Should find all 3 vectors before restart");
+            })?[0]
+                .id,
+            "a"
+        );
+
+        // An expired entry can no longer be restored and is purged
+        db.soft_delete("b")?;
+        db.set_trash_retention(Duration::ZERO)?;
+        assert!(db.trash()?.is_empty());
+        assert_eq!(db.purge_trash()?, 1);
+        assert!(!db.restore("b")?);
+        drop(db);
+
+        let reopened = VectorDB::new(options)?;
+        assert_eq!(reopened.trash_retention(), Duration::ZERO);
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_delta() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.hnsw_config = None;
+
+        let db = Arc::new(VectorDB::new(options)?);
+        db.insert(VectorEntry {
+            id: Some("user".to_string()),
+            vector: vec![0.0, 0.0],
+            metadata: Some(HashMap::from([(
+                "name".to_string(),
+                serde_json::json!("u"),
+            )])),
+        })?;
+
+        // Concurrent increments are not lost
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let db = db.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..10 {
+                        db.update_delta("user", &[1.0, 0.5], DeltaUpdate::Add { alpha: 1.0 })
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
         }
-        // db is dropped here, simulating application shutdown
+        let entry = db.get("user")?.unwrap();
+        assert_eq!(entry.vector, vec![80.0, 40.0]);
+        assert_eq!(entry.metadata.unwrap()["name"], "u");
+
+        let moved =
+            db.update_delta("user", &[0.0, 0.0], DeltaUpdate::Interpolate { alpha: 0.5 })?;
+        assert_eq!(moved, vec![40.0, 20.0]);
+        let results = db.search(SearchQuery {
+            vector: vec![40.0, 20.0],
+            k: 1,
+            filter: None,
+            ef_search: None,
+        })?;
+        assert_eq!(results[0].id, "user");
+
+        assert!(matches!(
+            db.update_delta("missing", &[0.0, 0.0], DeltaUpdate::Add { alpha: 1.0 }),
+            Err(RuvectorError::VectorNotFound(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_centroid_groups() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.hnsw_config = None;
+
+        let member = |id: &str, user: &str, vector: Vec<f32>| VectorEntry {
+            id: Some(id.to_string()),
+            vector,
+            metadata: Some(HashMap::from([(
+                "user".to_string(),
+                serde_json::json!(user),
+            )])),
+        };
+        let centroid_of = |db: &VectorDB, user: &str| -> Result<Option<Vec<f32>>> {
+            Ok(db.centroid("user", user)?.map(|entry| entry.vector))
+        };
 
-        // Phase 2: Create new database instance (simulates restart)
         {
-            let mut options = DbOptions::default();
-            options.storage_path = db_path.clone();
-            options.dimensions = 3;
-            options.distance_metric = DistanceMetric::Euclidean;
-            options.hnsw_config = None;
+            let db = VectorDB::new(options.clone())?;
+            db.insert(member("a", "u1", vec![1.0, 0.0]))?;
+            assert!(db.define_centroid_group("user")?);
+            assert!(!db.define_centroid_group("user")?);
+            assert_eq!(centroid_of(&db, "u1")?, Some(vec![1.0, 0.0]));
 
-            let db = VectorDB::new(options)?;
+            db.insert_batch(vec![
+                member("b", "u1", vec![3.0, 2.0]),
+                member("c", "u2", vec![0.0, 4.0]),
+            ])?;
+            assert_eq!(centroid_of(&db, "u1")?, Some(vec![2.0, 1.0]));
 
-            // Verify vectors are still accessible
-            assert_eq!(db.len()?, 3, "Should have 3 vectors after restart");
+            // Overwriting a member replaces its contribution
+            db.insert(member("b", "u2", vec![0.0, 2.0]))?;
+            assert_eq!(centroid_of(&db, "u1")?, Some(vec![1.0, 0.0]));
+            assert_eq!(centroid_of(&db, "u2")?, Some(vec![0.0, 3.0]));
 
-            // Verify get() works
-            let v1 = db.get("v1")?;
-            assert!(v1.is_some(), "get() should work after restart");
+            db.update_delta("a", &[2.0, 0.0], DeltaUpdate::Add { alpha: 1.0 })?;
+            assert_eq!(centroid_of(&db, "u1")?, Some(vec![3.0, 0.0]));
 
-            // Verify search() works - THIS WAS THE BUG
+            // Centroids are searchable like any vector; u2's members tie with
+            // its centroid, so fetch past them before filtering
             let results = db.search(SearchQuery {
-                vector: vec![0.8, 0.6, 0.0],
-                k: 3,
-                filter: None,
+                vector: vec![0.0, 3.0],
+                k: 10,
+                filter: Some(HashMap::from([(
+                    centroid::CENTROID_KEY.to_string(),
+                    serde_json::json!("user"),
+                )])),
                 ef_search: None,
             })?;
+            assert_eq!(results[0].id, centroid::centroid_id("user", "u2"));
 
-            assert_eq!(
-                results.len(),
-                3,
-                "search() should return results after restart (was returning 0 before fix)"
-            );
+            assert!(db.soft_delete("a")?);
+            assert_eq!(centroid_of(&db, "u1")?, None);
+            assert!(db.restore("a")?);
+            assert_eq!(centroid_of(&db, "u1")?, Some(vec![3.0, 0.0]));
+        }
 
-            // v3 should be closest to query [0.8, 0.6, 0.0]
-            assert_eq!(
-                results[0].id, "v3",
-                "v3 [0.7, 0.7, 0.0] should be closest to query [0.8, 0.6, 0.0]"
-            );
+        // Groups persist and running sums are rebuilt on open
+        let db = VectorDB::new(options)?;
+        assert_eq!(db.centroid_groups(), vec!["user".to_string()]);
+        db.delete("c")?;
+        assert_eq!(centroid_of(&db, "u2")?, Some(vec![0.0, 2.0]));
+
+        assert!(db.drop_centroid_group("user")?);
+        assert_eq!(centroid_of(&db, "u2")?, None);
+        assert_eq!(db.len()?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ingest_batch_updates_centroids_on_commit() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.hnsw_config = None;
+
+        let member = |id: &str, vector: Vec<f32>| VectorEntry {
+            id: Some(id.to_string()),
+            vector,
+            metadata: Some(HashMap::from([(
+                "user".to_string(),
+                serde_json::json!("u1"),
+            )])),
+        };
+        let centroid_of = |db: &VectorDB| -> Result<Option<Vec<f32>>> {
+            Ok(db.centroid("user", "u1")?.map(|entry| entry.vector))
+        };
+
+        let db = VectorDB::new(options)?;
+        db.insert(member("a", vec![1.0, 0.0]))?;
+        assert!(db.define_centroid_group("user")?);
+
+        {
+            let mut batch = db.begin_ingest();
+            batch.insert_batch(vec![member("b", vec![3.0, 2.0])])?;
+            assert_eq!(centroid_of(&db)?, Some(vec![1.0, 0.0]));
+        }
+        assert_eq!(centroid_of(&db)?, Some(vec![1.0, 0.0]));
+
+        let mut batch = db.begin_ingest();
+        batch.insert_batch(vec![member("b", vec![3.0, 2.0])])?;
+        batch.insert_batch(vec![member("b", vec![5.0, 4.0])])?;
+        assert_eq!(centroid_of(&db)?, Some(vec![1.0, 0.0]));
+        batch.commit()?;
+        assert_eq!(centroid_of(&db)?, Some(vec![3.0, 2.0]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_boosted() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        for (id, vector, source) in [
+            ("near", vec![0.0, 0.0], "forum"),
+            ("mid", vec![1.0, 0.0], "docs"),
+            ("far", vec![5.0, 0.0], "docs"),
+        ] {
+            db.insert(VectorEntry {
+                id: Some(id.to_string()),
+                vector,
+                metadata: Some(HashMap::from([(
+                    "source".to_string(),
+                    serde_json::json!(source),
+                )])),
+            })?;
+        }
+
+        let query = SearchQuery {
+            vector: vec![0.0, 0.0],
+            k: 2,
+            filter: None,
+            ef_search: None,
+        };
+        let spec = BoostSpec {
+            boosts: vec![crate::boost::Boost::Lookup {
+                field: "source".to_string(),
+                values: HashMap::from([("docs".to_string(), 3.0)]),
+                default: 1.0,
+                op: crate::boost::BoostOp::Multiply,
+            }],
+            ..Default::default()
+        };
+        let results = db.search_boosted(query.clone(), &spec)?;
+        let ids: Vec<&str> = results.iter().map(|r| r.result.id.as_str()).collect();
+        assert_eq!(ids, vec!["mid", "near"]);
+        assert!((results[0].similarity - 0.5).abs() < 1e-6);
+        assert!((results[0].score - 1.5).abs() < 1e-6);
+
+        // No boosts keeps the plain similarity order
+        let plain = db.search_boosted(query, &BoostSpec::default())?;
+        assert_eq!(plain[0].result.id, "near");
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_time_decayed() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.hnsw_config = None;
+
+        let db = VectorDB::new(options)?;
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        for (id, vector, age_days) in [("old", vec![1.0, 0.0], 30), ("new", vec![1.0, 0.3], 0)] {
+            db.insert(VectorEntry {
+                id: Some(id.to_string()),
+                vector,
+                metadata: Some(HashMap::from([(
+                    "created".to_string(),
+                    serde_json::json!(now_ms - age_days * 24 * 60 * 60 * 1000),
+                )])),
+            })?;
+        }
+
+        let query = SearchQuery {
+            vector: vec![1.0, 0.0],
+            k: 2,
+            filter: None,
+            ef_search: None,
+        };
+        let mut decay = TimeDecay::new("created", 7.0 * 24.0 * 60.0 * 60.0, 0.3);
+        decay.now_ms = Some(now_ms);
+        let results = db.search_time_decayed(query.clone(), &decay)?;
+        assert_eq!(results[0].result.id, "new");
+        assert!(results[1].similarity > results[0].similarity);
+
+        // Without recency weight the closer vector wins
+        decay.weight = 0.0;
+        let results = db.search_time_decayed(query, &decay)?;
+        assert_eq!(results[0].result.id, "old");
+        Ok(())
+    }
+
+    #[test]
+    fn test_graph_introspection() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("graph.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        let db = VectorDB::new(options)?;
+        for i in 0..20 {
+            db.insert(VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: vec![i as f32, 1.0],
+                metadata: None,
+            })?;
+        }
+
+        let stats = db.graph_stats()?;
+        assert_eq!(stats.nodes_per_level[0], 20);
+        assert_eq!(stats.unreachable, 0);
+        let neighbors = db.neighbors("v3", 0)?;
+        assert!(!neighbors.is_empty());
+        assert!(neighbors.windows(2).all(|w| w[0].distance <= w[1].distance));
+        assert!(matches!(
+            db.neighbors("missing", 0),
+            Err(RuvectorError::VectorNotFound(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_recover_into_new_database() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("source.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.hnsw_config = None;
+        let source = VectorDB::new(options.clone())?;
+        for i in 0..5 {
+            source.insert(VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: vec![i as f32, 1.0],
+                metadata: Some(HashMap::from([("n".to_string(), serde_json::json!(i))])),
+            })?;
+        }
+        source.soft_delete("v4")?;
+
+        let mut target = DbOptions::default();
+        target.storage_path = dir
+            .path()
+            .join("recovered.db")
+            .to_string_lossy()
+            .to_string();
+        let (recovered, report) = VectorDB::recover(&options.storage_path, target.clone())?;
+        assert_eq!(report.recovered, 4);
+        assert_eq!(report.irrecoverable, 0);
+        assert_eq!(report.trash_recovered, 1);
+        assert!(report.config_recovered);
+        assert!(report.is_lossless());
+        assert_eq!(recovered.options().dimensions, 2);
+        assert_eq!(recovered.len()?, 4);
+        assert_eq!(recovered.get("v2")?.unwrap().metadata.unwrap()["n"], 2);
+        assert!(recovered.restore("v4")?);
+
+        // The target must be a fresh path
+        assert!(VectorDB::recover(&options.storage_path, target).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_recover_flipped_bytes() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("source.db").to_string_lossy().to_string();
+        options.dimensions = 4;
+        options.hnsw_config = None;
+        let source = VectorDB::new(options.clone())?;
+        source.insert_batch(
+            (0..2000)
+                .map(|i| VectorEntry {
+                    id: Some(format!("v{:04}", i)),
+                    vector: vec![i as f32, 0.5, 0.25, 1.0],
+                    metadata: Some(HashMap::from([("n".to_string(), serde_json::json!(i))])),
+                })
+                .collect(),
+        )?;
+        source.insert(VectorEntry {
+            id: Some("damaged".to_string()),
+            vector: vec![1234.5, -987.25, 42.125, 7.75],
+            metadata: None,
+        })?;
+
+        // Corrupt the last write in a copy of the file; the source stays open
+        let copy = dir.path().join("damaged.db");
+        std::fs::copy(&options.storage_path, &copy).unwrap();
+        let mut bytes = std::fs::read(&copy).unwrap();
+        let marker: Vec<u8> = [1234.5f32, -987.25]
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect();
+        let at = bytes
+            .windows(marker.len())
+            .position(|w| w == marker)
+            .unwrap();
+        for b in &mut bytes[at..at + marker.len()] {
+            *b ^= 0xff;
+        }
+        std::fs::write(&copy, &bytes).unwrap();
+
+        let mut target = DbOptions::default();
+        target.storage_path = dir
+            .path()
+            .join("recovered.db")
+            .to_string_lossy()
+            .to_string();
+        let (recovered, report) = VectorDB::recover(&copy, target)?;
+        // The damaged commit fails its checksums, so the file is read as of
+        // the commit before it, with every earlier row intact
+        assert_eq!(report.recovered, 2000);
+        assert_eq!(recovered.len()?, 2000);
+        assert!(recovered.get("damaged")?.is_none());
+        let entry = recovered.get("v1234")?.unwrap();
+        assert_eq!(entry.vector, vec![1234.0, 0.5, 0.25, 1.0]);
+        assert_eq!(entry.metadata.unwrap()["n"], 1234);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_backpressure() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("throttle.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        let db = VectorDB::new(options)?;
+        for i in 0..10 {
+            db.insert(VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: vec![i as f32, 1.0],
+                metadata: None,
+            })?;
+        }
+        db.set_performance_config(PerformanceConfig {
+            max_tombstone_ratio: Some(0.25),
+            ..Default::default()
+        })?;
+        for i in 0..3 {
+            db.delete(&format!("v{}", i))?;
+        }
+
+        let entry = VectorEntry {
+            id: Some("new".to_string()),
+            vector: vec![0.5, 1.0],
+            metadata: None,
+        };
+        match db.insert(entry.clone()) {
+            Err(RuvectorError::Backpressure { signal, .. }) => {
+                assert_eq!(signal, "tombstone_ratio")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(db.insert_batch(vec![entry.clone()]).is_err());
+        // Deletes still go through
+        assert!(db.delete("v3")?);
+
+        db.set_performance_config(PerformanceConfig::default())?;
+        db.insert(entry)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_private() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("dp.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        let db = VectorDB::new(options.clone())?;
+        for i in 0..5 {
+            let mut metadata = HashMap::new();
+            metadata.insert("owner".to_string(), serde_json::json!("alice"));
+            db.insert(VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: vec![i as f32, 1.0],
+                metadata: Some(metadata),
+            })?;
+        }
+        db.set_privacy_budget(Some(PrivacyBudget {
+            epsilon: 1.0,
+            delta: 1e-5,
+        }))?;
+
+        let config = DpExportConfig::new(0.5, 5e-6, 2.0);
+        let export = db.export_private(&config)?;
+        assert_eq!(export.entries.len(), 5);
+        assert_eq!(export.charge.records, 5);
+        assert!(export.entries.iter().all(|e| e.metadata.is_none()));
+        assert!(export
+            .entries
+            .iter()
+            .all(|e| !e.id.as_ref().unwrap().starts_with('v')));
+        db.export_private(&config)?;
+        assert!(matches!(
+            db.export_private(&config),
+            Err(RuvectorError::PrivacyBudgetExceeded { .. })
+        ));
+        assert!(db
+            .export_private(&DpExportConfig::new(2.0, 1e-6, 1.0))
+            .is_err());
+
+        // The ledger survives a reopen
+        drop(db);
+        let db = VectorDB::new(options)?;
+        let ledger = db.privacy_ledger();
+        assert_eq!(ledger.charges.len(), 2);
+        assert!((ledger.spent().epsilon - 1.0).abs() < 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn test_content_ids_make_inserts_idempotent() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("cas.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        let db = VectorDB::new(options.clone())?;
+        db.set_id_strategy(IdStrategy::Content)?;
+
+        let chunk = |i: usize| {
+            let mut metadata = HashMap::new();
+            metadata.insert("chunk".to_string(), serde_json::json!(i));
+            VectorEntry {
+                id: None,
+                vector: vec![i as f32, 1.0],
+                metadata: Some(metadata),
+            }
+        };
+        let first = db.insert(chunk(0))?;
+        assert_eq!(first, content_id(&[0.0, 1.0], chunk(0).metadata.as_ref()));
+        assert_eq!(db.insert(chunk(0))?, first);
+
+        // A retried batch overlapping the first insert, with a repeat inside
+        let ids = db.insert_batch(vec![chunk(0), chunk(1), chunk(1), chunk(2)])?;
+        assert_eq!(ids.len(), 4);
+        assert_eq!(ids[0], first);
+        assert_eq!(ids[1], ids[2]);
+        assert_eq!(db.len()?, 3);
+
+        let report = db.bulk_load((0..4).map(chunk))?;
+        assert_eq!(report.inserted, 1);
+        assert_eq!(report.skipped, 3);
+        assert_eq!(db.len()?, 4);
+
+        // Explicit ids are kept
+        let mut named = chunk(9);
+        named.id = Some("named".to_string());
+        assert_eq!(db.insert(named)?, "named");
+
+        drop(db);
+        let db = VectorDB::new(options)?;
+        assert_eq!(db.id_strategy(), IdStrategy::Content);
+        assert_eq!(db.insert(chunk(1))?, ids[1]);
+        assert_eq!(db.len()?, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_maybe_contains() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("bloom.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.hnsw_config = None;
+        let db = VectorDB::new(options.clone())?;
+        let entry = |i: usize| VectorEntry {
+            id: Some(format!("chunk-{}", i)),
+            vector: vec![i as f32, 1.0],
+            metadata: None,
+        };
+        db.insert(entry(0))?;
+        db.insert_batch((1..100).map(entry).collect())?;
+        db.bulk_load((100..3000).map(entry))?;
+        // Past the initial capacity, so the filter was rebuilt on the way
+        assert!((0..3000).all(|i| db.maybe_contains(&format!("chunk-{}", i))));
+        let unseen = (0..1000)
+            .filter(|i| db.maybe_contains(&format!("unseen-{}", i)))
+            .count();
+        assert!(unseen < 50, "{} false positives", unseen);
+
+        db.soft_delete("chunk-0")?;
+        db.restore("chunk-0")?;
+        assert!(db.maybe_contains("chunk-0"));
+
+        drop(db);
+        let db = VectorDB::new(options)?;
+        assert!((0..3000).all(|i| db.maybe_contains(&format!("chunk-{}", i))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_collection_stats() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("stats.db").to_string_lossy().to_string();
+        options.dimensions = 3;
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+        let db = VectorDB::new(options)?;
+
+        // Random points on a plane, plus two broken embeddings
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        let mut entries: Vec<VectorEntry> = (0..400)
+            .map(|i| VectorEntry {
+                id: Some(format!("p{}", i)),
+                vector: vec![rng.gen_range(1.0..10.0), rng.gen_range(1.0..10.0), 0.0],
+                metadata: None,
+            })
+            .collect();
+        for id in ["zero-a", "zero-b"] {
+            entries.push(VectorEntry {
+                id: Some(id.to_string()),
+                vector: vec![0.0; 3],
+                metadata: None,
+            });
         }
+        db.insert_batch(entries)?;
+
+        let stats = db.collection_stats()?;
+        assert_eq!(stats.dimensions, 3);
+        assert_eq!(stats.count, 402);
+        assert_eq!(stats.zero_vectors, 2);
+        assert_eq!(stats.non_finite, 0);
+        assert_eq!(stats.norms.min, 0.0);
+        assert!(stats.norms.p1 > 1.0);
+        assert!(stats.norms.max < 200f32.sqrt());
+        assert_eq!(stats.mean_vector[2], 0.0);
+        let dimension = stats.intrinsic_dimension.unwrap();
+        assert!((1.5..2.5).contains(&dimension), "estimated {}", dimension);
+
+        let skipped = db.collection_stats_with(&StatsConfig {
+            intrinsic_dim_sample: 0,
+            ..Default::default()
+        })?;
+        assert_eq!(skipped.intrinsic_dimension, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_score_anomalies() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("anomaly.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+        let db = VectorDB::new(options)?;
+        let mut entries: Vec<VectorEntry> = (0..50)
+            .map(|i| VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: vec![(i % 10) as f32 * 0.1, (i / 10) as f32 * 0.1],
+                metadata: None,
+            })
+            .collect();
+        entries.push(VectorEntry {
+            id: Some("garbage".to_string()),
+            vector: vec![25.0, -40.0],
+            metadata: None,
+        });
+        db.insert_batch(entries)?;
+        let log = db.enable_op_log(OpLogConfig::default());
+
+        let report = db.score_anomalies(&AnomalyConfig::default())?;
+        assert_eq!(report.scored, 51);
+        assert_eq!(report.written, 51);
+        assert_eq!(log.len(), 51);
+        assert_eq!(report.top[0].id, "garbage");
+        assert_eq!(report.flagged, 1);
+        let flagged = db.get("garbage")?.unwrap().metadata.unwrap();
+        assert_eq!(flagged[ANOMALY_FLAG_KEY], true);
+        let normal = db.get("v12")?.unwrap().metadata.unwrap();
+        assert!(normal[ANOMALY_SCORE_KEY].as_f64().unwrap() < 1.5);
+        assert!(!normal.contains_key(ANOMALY_FLAG_KEY));
+
+        // Report-only runs leave metadata alone
+        db.delete("garbage")?;
+        let report = db.score_anomalies(&AnomalyConfig {
+            method: crate::anomaly::AnomalyMethod::KthDistance,
+            threshold: None,
+            write_metadata: false,
+            ..Default::default()
+        })?;
+        assert_eq!(report.written, 0);
+        assert_eq!(report.flagged, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_classify() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let mut options = DbOptions::default();
+        options.storage_path = dir.path().join("test.db").to_string_lossy().to_string();
+        options.dimensions = 2;
+        options.distance_metric = DistanceMetric::Euclidean;
+        options.hnsw_config = None;
+        let db = VectorDB::new(options)?;
+
+        let labelled = |id: &str, topic: &str, vector: Vec<f32>| VectorEntry {
+            id: Some(id.to_string()),
+            vector,
+            metadata: Some(HashMap::from([(
+                "topic".to_string(),
+                serde_json::json!(topic),
+            )])),
+        };
+        db.insert_batch(vec![
+            labelled("s1", "sports", vec![1.0, 0.0]),
+            labelled("s2", "sports", vec![1.2, 0.2]),
+            labelled("p1", "politics", vec![-1.0, 0.0]),
+        ])?;
+
+        let centroid = ClassifyConfig::centroid("topic");
+        assert!(db.classify(&[1.0, 0.1], &centroid).is_err());
+        assert!(db.define_centroid_group("topic")?);
+        let result = db.classify(&[0.9, 0.1], &centroid)?.unwrap();
+        assert_eq!(result.label, "sports");
+        assert_eq!(result.support, 2);
+        assert!(result.confidence > 0.99);
+
+        let knn = ClassifyConfig::knn("topic", 3);
+        let result = db.classify(&[0.9, 0.1], &knn)?.unwrap();
+        assert_eq!(result.label, "sports");
+        assert_eq!(result.support, 2);
+        assert!((result.confidence - 2.0 / 3.0).abs() < 1e-6);
 
+        // New labelled entries update both classifiers online
+        db.insert(labelled("t1", "tech", vec![0.0, 5.0]))?;
+        assert_eq!(db.classify(&[0.1, 4.0], &centroid)?.unwrap().label, "tech");
+        db.insert_batch(vec![
+            labelled("t2", "tech", vec![0.2, 5.0]),
+            labelled("t3", "tech", vec![-0.2, 5.0]),
+        ])?;
+        assert_eq!(db.classify(&[0.1, 4.0], &knn)?.unwrap().label, "tech");
         Ok(())
     }
 }
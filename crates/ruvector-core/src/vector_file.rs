@@ -0,0 +1,401 @@
+//! Flat on-disk vector file with batched reads
+//!
+//! Vectors are stored back to back as little-endian `f32` records of
+//! `dimensions * 4` bytes, addressed by slot number. When a dataset does not
+//! fit in RAM, search has to fetch many candidate vectors from disk; reading
+//! them one `pread` at a time serializes the device latency.
+//! [`VectorFile::read_many`] instead hands the whole candidate set to the
+//! configured [`ReadBackend`]:
+//!
+//! - [`ReadBackend::Sync`]: one positioned read per slot (portable).
+//! - [`ReadBackend::IoUring`]: submits up to [`URING_QUEUE_DEPTH`] reads at
+//!   once so they overlap in the device queue. Linux only, behind the
+//!   `uring` feature.
+//!
+//! A memory-budgeted [`TieredIndex`](crate::index::tiered::TieredIndex)
+//! spills its cold vectors to a `VectorFile` when
+//! [`MemoryBudget::cold_file`](crate::index::tiered::MemoryBudget::cold_file)
+//! is set, and rescores the cold candidates of each search with one
+//! `read_many`.
+
+use crate::error::{Result, RuvectorError};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// Reads submitted to the ring per batch
+pub const URING_QUEUE_DEPTH: usize = 64;
+
+/// How batched reads are issued
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadBackend {
+    /// Positioned reads, one per slot
+    #[default]
+    Sync,
+    /// io_uring submission of all reads in a batch
+    IoUring,
+}
+
+impl ReadBackend {
+    /// Whether this backend can be used in the current build
+    pub fn is_available(self) -> bool {
+        match self {
+            ReadBackend::Sync => true,
+            ReadBackend::IoUring => cfg!(all(target_os = "linux", feature = "uring")),
+        }
+    }
+}
+
+/// Append-only file of fixed-size vectors
+pub struct VectorFile {
+    file: File,
+    dimensions: usize,
+    len: u64,
+    backend: ReadBackend,
+    #[cfg(all(target_os = "linux", feature = "uring"))]
+    ring: Option<parking_lot::Mutex<io_uring::IoUring>>,
+}
+
+impl VectorFile {
+    /// Create (or truncate) a vector file
+    pub fn create<P: AsRef<Path>>(path: P, dimensions: usize) -> Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        Self::from_file(file, dimensions)
+    }
+
+    /// Open an existing vector file
+    pub fn open<P: AsRef<Path>>(path: P, dimensions: usize) -> Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        Self::from_file(file, dimensions)
+    }
+
+    fn from_file(file: File, dimensions: usize) -> Result<Self> {
+        if dimensions == 0 {
+            return Err(RuvectorError::InvalidDimension(
+                "dimensions must be greater than zero".to_string(),
+            ));
+        }
+        let size = file.metadata()?.len();
+        let record = (dimensions * 4) as u64;
+        if size % record != 0 {
+            return Err(RuvectorError::StorageError(format!(
+                "file size {} is not a multiple of the {}-byte record size",
+                size, record
+            )));
+        }
+        Ok(Self {
+            file,
+            dimensions,
+            len: size / record,
+            backend: ReadBackend::Sync,
+            #[cfg(all(target_os = "linux", feature = "uring"))]
+            ring: None,
+        })
+    }
+
+    /// Switch the backend used by [`VectorFile::read_many`]
+    ///
+    /// Fails if the backend is not compiled in or the kernel refuses to
+    /// create a ring.
+    pub fn with_backend(mut self, backend: ReadBackend) -> Result<Self> {
+        if !backend.is_available() {
+            return Err(RuvectorError::InvalidParameter(format!(
+                "{:?} read backend is not available in this build",
+                backend
+            )));
+        }
+        #[cfg(all(target_os = "linux", feature = "uring"))]
+        {
+            self.ring = match backend {
+                ReadBackend::IoUring => Some(parking_lot::Mutex::new(io_uring::IoUring::new(
+                    URING_QUEUE_DEPTH as u32,
+                )?)),
+                ReadBackend::Sync => None,
+            };
+        }
+        self.backend = backend;
+        Ok(self)
+    }
+
+    /// Active read backend
+    pub fn backend(&self) -> ReadBackend {
+        self.backend
+    }
+
+    /// Vector dimensions
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Number of stored vectors
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether the file holds no vectors
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn record_size(&self) -> usize {
+        self.dimensions * 4
+    }
+
+    /// Append a vector, returning its slot
+    pub fn append(&mut self, vector: &[f32]) -> Result<u64> {
+        if vector.len() != self.dimensions {
+            return Err(RuvectorError::DimensionMismatch {
+                expected: self.dimensions,
+                actual: vector.len(),
+            });
+        }
+        let bytes: Vec<u8> = vector.iter().flat_map(|x| x.to_le_bytes()).collect();
+        write_at(&self.file, &bytes, self.len * self.record_size() as u64)?;
+        self.len += 1;
+        Ok(self.len - 1)
+    }
+
+    /// Flush written vectors to disk
+    pub fn sync(&mut self) -> Result<()> {
+        self.file.flush()?;
+        self.file.sync_data()?;
+        Ok(())
+    }
+
+    /// Read the vector in `slot`
+    pub fn read(&self, slot: u64) -> Result<Vec<f32>> {
+        self.check_slot(slot)?;
+        let mut buf = vec![0u8; self.record_size()];
+        read_at(&self.file, &mut buf, slot * self.record_size() as u64)?;
+        Ok(decode(&buf))
+    }
+
+    /// Read several vectors, in the order of `slots`
+    pub fn read_many(&self, slots: &[u64]) -> Result<Vec<Vec<f32>>> {
+        for &slot in slots {
+            self.check_slot(slot)?;
+        }
+
+        #[cfg(all(target_os = "linux", feature = "uring"))]
+        if let Some(ring) = &self.ring {
+            return self.read_many_uring(&mut ring.lock(), slots);
+        }
+
+        slots.iter().map(|&slot| self.read(slot)).collect()
+    }
+
+    fn check_slot(&self, slot: u64) -> Result<()> {
+        if slot >= self.len {
+            return Err(RuvectorError::VectorNotFound(format!(
+                "slot {} (file holds {})",
+                slot, self.len
+            )));
+        }
+        Ok(())
+    }
+
+    #[cfg(all(target_os = "linux", feature = "uring"))]
+    fn read_many_uring(
+        &self,
+        ring: &mut io_uring::IoUring,
+        slots: &[u64],
+    ) -> Result<Vec<Vec<f32>>> {
+        use io_uring::types;
+        use std::os::unix::io::AsRawFd;
+
+        let record = self.record_size();
+        let fd = types::Fd(self.file.as_raw_fd());
+        let mut buffers = vec![vec![0u8; record]; slots.len()];
+        // Bytes read so far into each buffer
+        let mut filled = vec![0usize; slots.len()];
+        let mut first_error: Option<RuvectorError> = None;
+
+        for start in (0..slots.len()).step_by(URING_QUEUE_DEPTH) {
+            let end = (start + URING_QUEUE_DEPTH).min(slots.len());
+            let mut pending = 0;
+            for i in start..end {
+                // SAFETY: every read pushed here is waited for below before
+                // `buffers` is touched again, dropped or returned.
+                let pushed =
+                    unsafe { push_read(ring, fd, &mut buffers[i], slots[i] * record as u64, i) };
+                if let Err(e) = pushed {
+                    first_error = Some(e);
+                    break;
+                }
+                pending += 1;
+            }
+
+            // Drain every read of the batch, even after a failure: the kernel
+            // may still write into `buffers` until its completion arrives
+            while pending > 0 {
+                if let Err(e) = ring.submit_and_wait(1) {
+                    if e.kind() == std::io::ErrorKind::Interrupted {
+                        continue;
+                    }
+                    // Reads may still be in flight, so the buffers must
+                    // outlive this call
+                    std::mem::forget(buffers);
+                    return Err(e.into());
+                }
+                let completions: Vec<(usize, i32)> = ring
+                    .completion()
+                    .map(|cqe| (cqe.user_data() as usize, cqe.result()))
+                    .collect();
+                for (i, res) in completions {
+                    pending -= 1;
+                    if res < 0 {
+                        first_error.get_or_insert_with(|| {
+                            std::io::Error::from_raw_os_error(-res).into()
+                        });
+                        continue;
+                    }
+                    filled[i] += res as usize;
+                    if filled[i] == record || first_error.is_some() {
+                        continue;
+                    }
+                    if res == 0 {
+                        first_error = Some(RuvectorError::StorageError(format!(
+                            "unexpected end of file reading slot {}: {} of {} bytes",
+                            slots[i], filled[i], record
+                        )));
+                        continue;
+                    }
+                    // Short read: ask for the rest of the record
+                    let offset = slots[i] * record as u64 + filled[i] as u64;
+                    // SAFETY: as above; the retry is counted in `pending`.
+                    match unsafe { push_read(ring, fd, &mut buffers[i][filled[i]..], offset, i) } {
+                        Ok(()) => pending += 1,
+                        Err(e) => first_error = Some(e),
+                    }
+                }
+            }
+            if let Some(e) = first_error {
+                return Err(e);
+            }
+        }
+
+        Ok(buffers.iter().map(|buf| decode(buf)).collect())
+    }
+}
+
+/// Queue a read of `buf.len()` bytes at `offset`, tagged with `user_data`
+///
+/// # Safety
+///
+/// `buf` must stay alive and in place until the read's completion has been
+/// reaped.
+#[cfg(all(target_os = "linux", feature = "uring"))]
+unsafe fn push_read(
+    ring: &mut io_uring::IoUring,
+    fd: io_uring::types::Fd,
+    buf: &mut [u8],
+    offset: u64,
+    user_data: usize,
+) -> Result<()> {
+    let entry = io_uring::opcode::Read::new(fd, buf.as_mut_ptr(), buf.len() as u32)
+        .offset(offset)
+        .build()
+        .user_data(user_data as u64);
+    ring.submission()
+        .push(&entry)
+        .map_err(|_| RuvectorError::StorageError("io_uring submission queue full".to_string()))
+}
+
+fn decode(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+#[cfg(unix)]
+fn read_at(file: &File, buf: &mut [u8], offset: u64) -> std::io::Result<()> {
+    std::os::unix::fs::FileExt::read_exact_at(file, buf, offset)
+}
+
+#[cfg(unix)]
+fn write_at(file: &File, buf: &[u8], offset: u64) -> std::io::Result<()> {
+    std::os::unix::fs::FileExt::write_all_at(file, buf, offset)
+}
+
+#[cfg(windows)]
+fn read_at(file: &File, mut buf: &mut [u8], mut offset: u64) -> std::io::Result<()> {
+    use std::os::windows::fs::FileExt;
+    while !buf.is_empty() {
+        match file.seek_read(buf, offset)? {
+            0 => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+            n => {
+                buf = &mut buf[n..];
+                offset += n as u64;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn write_at(file: &File, mut buf: &[u8], mut offset: u64) -> std::io::Result<()> {
+    use std::os::windows::fs::FileExt;
+    while !buf.is_empty() {
+        let n = file.seek_write(buf, offset)?;
+        buf = &buf[n..];
+        offset += n as u64;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn roundtrip(backend: ReadBackend) -> Result<()> {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("vectors.bin");
+
+        let mut file = VectorFile::create(&path, 3)?;
+        for i in 0..200 {
+            file.append(&[i as f32, -(i as f32), 0.5])?;
+        }
+        file.sync()?;
+        drop(file);
+
+        let file = VectorFile::open(&path, 3)?.with_backend(backend)?;
+        assert_eq!(file.len(), 200);
+        assert_eq!(file.read(7)?, vec![7.0, -7.0, 0.5]);
+
+        let slots: Vec<u64> = (0..200).rev().step_by(3).collect();
+        let vectors = file.read_many(&slots)?;
+        for (slot, vector) in slots.iter().zip(&vectors) {
+            assert_eq!(vector[0], *slot as f32);
+        }
+        assert!(file.read_many(&[0, 200]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync_backend() -> Result<()> {
+        roundtrip(ReadBackend::Sync)
+    }
+
+    #[cfg(all(target_os = "linux", feature = "uring"))]
+    #[test]
+    fn test_uring_backend() -> Result<()> {
+        roundtrip(ReadBackend::IoUring)
+    }
+
+    #[test]
+    fn test_rejects_truncated_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("vectors.bin");
+        std::fs::write(&path, [0u8; 10]).unwrap();
+        assert!(VectorFile::open(&path, 3).is_err());
+    }
+}
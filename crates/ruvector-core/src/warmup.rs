@@ -0,0 +1,122 @@
+//! Cache warm-up after cold start
+//!
+//! A freshly opened database pays for page faults and cold CPU caches on its
+//! first queries. [`VectorDB::warmup`](crate::VectorDB::warmup) front-loads
+//! that cost before traffic arrives:
+//!
+//! 1. **Storage**: reads every stored vector so its pages are resident.
+//! 2. **Index**: searches the index from a sample of stored vectors, touching
+//!    the graph's entry points and upper layers.
+//! 3. **Canaries**: runs caller-supplied queries for a few rounds and reports
+//!    the latency of the last round.
+//!
+//! Quantization settings are recorded in [`DbOptions`](crate::DbOptions) but
+//! `VectorDB` does not hold trained codebooks, so there is nothing further to
+//! preload.
+
+use crate::types::SearchQuery;
+use serde::{Deserialize, Serialize};
+
+/// Warm-up settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WarmupConfig {
+    /// Read every stored vector
+    pub touch_storage: bool,
+    /// Index searches issued from sampled stored vectors
+    pub index_probes: usize,
+    /// Neighbors requested per index probe
+    pub probe_k: usize,
+    /// Representative queries to replay
+    pub canary_queries: Vec<SearchQuery>,
+    /// Times the canary set is replayed
+    pub canary_rounds: usize,
+}
+
+impl Default for WarmupConfig {
+    fn default() -> Self {
+        Self {
+            touch_storage: true,
+            index_probes: 256,
+            probe_k: 10,
+            canary_queries: Vec::new(),
+            canary_rounds: 3,
+        }
+    }
+}
+
+/// Warm-up stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WarmupPhase {
+    /// Reading stored vectors
+    Storage,
+    /// Probing the index
+    Index,
+    /// Replaying canary queries
+    Canaries,
+}
+
+/// Progress within a phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WarmupProgress {
+    /// Current phase
+    pub phase: WarmupPhase,
+    /// Units completed in this phase
+    pub done: usize,
+    /// Units in this phase
+    pub total: usize,
+}
+
+/// Outcome of a warm-up
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WarmupReport {
+    /// Stored vectors read
+    pub vectors_read: usize,
+    /// Vector bytes read
+    pub bytes_read: usize,
+    /// Index searches issued
+    pub index_probes: usize,
+    /// Latency of each canary query in the last round, in microseconds
+    pub canary_latencies_us: Vec<u64>,
+    /// Median canary latency in the last round
+    pub canary_p50_us: Option<u64>,
+    /// 99th percentile canary latency in the last round
+    pub canary_p99_us: Option<u64>,
+    /// Total warm-up time in milliseconds
+    pub elapsed_ms: u64,
+}
+
+/// Nearest-rank percentile of ascending `sorted`, `p` in `[0, 100]`
+pub fn percentile(sorted: &[u64], p: f64) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// Call `on_progress` roughly every percent and at the end of a phase
+pub(crate) fn should_report(done: usize, total: usize) -> bool {
+    done == total || done % (total / 100).max(1) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        let sorted: Vec<u64> = (1..=100).collect();
+        assert_eq!(percentile(&sorted, 50.0), Some(50));
+        assert_eq!(percentile(&sorted, 99.0), Some(99));
+        assert_eq!(percentile(&sorted, 0.0), Some(1));
+        assert_eq!(percentile(&[7], 99.0), Some(7));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+
+    #[test]
+    fn test_should_report() {
+        assert!(should_report(3, 3));
+        assert!(should_report(10, 1000));
+        assert!(!should_report(11, 1000));
+    }
+}
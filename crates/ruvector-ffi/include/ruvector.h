@@ -0,0 +1,88 @@
+/*
+ * ruvector.h - C ABI for the Ruvector vector database
+ *
+ * Link against libruvector_ffi (cdylib or staticlib). All functions are
+ * thread-safe for a given handle. Errors are reported through the returned
+ * status code; the message is available from ruvector_last_error() on the
+ * calling thread.
+ */
+
+#ifndef RUVECTOR_H
+#define RUVECTOR_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define RUVECTOR_ABI_VERSION 1
+
+typedef enum RuvectorStatus {
+    RUVECTOR_OK = 0,
+    RUVECTOR_NULL_POINTER = 1,
+    RUVECTOR_INVALID_ARGUMENT = 2,
+    RUVECTOR_DATABASE_ERROR = 3,
+    RUVECTOR_PANIC = 4, /* the library panicked; stop using the handle */
+} RuvectorStatus;
+
+typedef enum RuvectorMetric {
+    RUVECTOR_METRIC_EUCLIDEAN = 0,
+    RUVECTOR_METRIC_COSINE = 1,
+    RUVECTOR_METRIC_DOT_PRODUCT = 2,
+    RUVECTOR_METRIC_MANHATTAN = 3,
+} RuvectorMetric;
+
+/* Opaque database handle */
+typedef struct RuvectorDb RuvectorDb;
+
+typedef struct RuvectorSearchResult {
+    char *id;    /* owned by the enclosing RuvectorSearchResults */
+    float score; /* distance, lower is closer */
+} RuvectorSearchResult;
+
+typedef struct RuvectorSearchResults {
+    RuvectorSearchResult *results;
+    size_t len;
+} RuvectorSearchResults;
+
+uint32_t ruvector_abi_version(void);
+const char *ruvector_version(void);
+const char *ruvector_last_error(void);
+
+/* metric is a RuvectorMetric value; anything else is RUVECTOR_INVALID_ARGUMENT */
+RuvectorStatus ruvector_open(const char *path,
+                             size_t dimensions,
+                             uint32_t metric,
+                             bool use_hnsw,
+                             RuvectorDb **out_db);
+void ruvector_close(RuvectorDb *db);
+
+/* id and metadata_json may be NULL; *out_id must be freed with ruvector_string_free */
+RuvectorStatus ruvector_insert(const RuvectorDb *db,
+                               const char *id,
+                               const float *vector,
+                               size_t len,
+                               const char *metadata_json,
+                               char **out_id);
+
+/* *out_results must be freed with ruvector_search_results_free */
+RuvectorStatus ruvector_search(const RuvectorDb *db,
+                               const float *query,
+                               size_t len,
+                               size_t k,
+                               RuvectorSearchResults **out_results);
+void ruvector_search_results_free(RuvectorSearchResults *results);
+
+RuvectorStatus ruvector_delete(const RuvectorDb *db, const char *id, bool *out_deleted);
+RuvectorStatus ruvector_len(const RuvectorDb *db, size_t *out_len);
+
+void ruvector_string_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* RUVECTOR_H */
@@ -0,0 +1,523 @@
+//! C ABI for Ruvector
+//!
+//! Exposes an opaque database handle with open/insert/search/delete so that
+//! Go (cgo), Java (JNI/Panama), .NET (P/Invoke) and other runtimes can embed
+//! Ruvector without a Rust toolchain. The matching header lives in
+//! `include/ruvector.h`.
+//!
+//! Conventions:
+//! - Every fallible function returns a [`RuvectorStatus`]; on failure the
+//!   message is available from [`ruvector_last_error`] on the same thread.
+//! - Panics never unwind into the caller; they are reported as
+//!   [`RuvectorStatus::Panic`].
+//! - Strings and result arrays allocated by the library must be released with
+//!   [`ruvector_string_free`] / [`ruvector_search_results_free`].
+//! - A handle may be used from multiple threads concurrently.
+
+#![deny(clippy::all)]
+#![allow(clippy::missing_safety_doc)]
+
+use ruvector_core::types::{DbOptions, HnswConfig};
+use ruvector_core::{DistanceMetric, SearchQuery, VectorDB, VectorEntry};
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+/// ABI version; bumped on any incompatible change to the C interface
+pub const RUVECTOR_ABI_VERSION: u32 = 1;
+
+/// Status codes returned by every fallible function
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuvectorStatus {
+    /// Success
+    Ok = 0,
+    /// A required pointer argument was NULL
+    NullPointer = 1,
+    /// An argument was invalid (bad UTF-8, bad JSON, wrong dimension, ...)
+    InvalidArgument = 2,
+    /// The database returned an error
+    DatabaseError = 3,
+    /// The library panicked; the handle should not be used further
+    Panic = 4,
+}
+
+/// Distance metrics accepted by [`ruvector_open`], passed as their `u32` value
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuvectorMetric {
+    Euclidean = 0,
+    Cosine = 1,
+    DotProduct = 2,
+    Manhattan = 3,
+}
+
+impl TryFrom<u32> for RuvectorMetric {
+    type Error = RuvectorStatus;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(RuvectorMetric::Euclidean),
+            1 => Ok(RuvectorMetric::Cosine),
+            2 => Ok(RuvectorMetric::DotProduct),
+            3 => Ok(RuvectorMetric::Manhattan),
+            _ => Err(fail(
+                RuvectorStatus::InvalidArgument,
+                format!("unknown metric {}", value),
+            )),
+        }
+    }
+}
+
+impl From<RuvectorMetric> for DistanceMetric {
+    fn from(metric: RuvectorMetric) -> Self {
+        match metric {
+            RuvectorMetric::Euclidean => DistanceMetric::Euclidean,
+            RuvectorMetric::Cosine => DistanceMetric::Cosine,
+            RuvectorMetric::DotProduct => DistanceMetric::DotProduct,
+            RuvectorMetric::Manhattan => DistanceMetric::Manhattan,
+        }
+    }
+}
+
+/// Opaque database handle
+pub struct RuvectorDb {
+    inner: VectorDB,
+}
+
+/// One search hit
+#[repr(C)]
+pub struct RuvectorSearchResult {
+    /// NUL-terminated id, owned by the enclosing [`RuvectorSearchResults`]
+    pub id: *mut c_char,
+    /// Distance (lower is closer)
+    pub score: f32,
+}
+
+/// Array of search hits returned by [`ruvector_search`]
+#[repr(C)]
+pub struct RuvectorSearchResults {
+    pub results: *mut RuvectorSearchResult,
+    pub len: usize,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: impl Into<String>) {
+    let message = message.into().replace('\0', " ");
+    LAST_ERROR.with(|e| *e.borrow_mut() = CString::new(message).ok());
+}
+
+fn fail(status: RuvectorStatus, message: impl Into<String>) -> RuvectorStatus {
+    set_last_error(message);
+    status
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
+
+/// Run an entry point body, turning a panic into [`RuvectorStatus::Panic`]
+fn ffi_call(body: impl FnOnce() -> RuvectorStatus) -> RuvectorStatus {
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|payload| {
+        fail(
+            RuvectorStatus::Panic,
+            format!("panic: {}", panic_message(&*payload)),
+        )
+    })
+}
+
+/// Like [`ffi_call`] for entry points without a status; a panic is dropped
+fn ffi_call_void(body: impl FnOnce()) {
+    if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(body)) {
+        set_last_error(format!("panic: {}", panic_message(&*payload)));
+    }
+}
+
+unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, RuvectorStatus> {
+    if ptr.is_null() {
+        return Err(fail(RuvectorStatus::NullPointer, format!("{} is NULL", name)));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| fail(RuvectorStatus::InvalidArgument, format!("{} is not valid UTF-8", name)))
+}
+
+unsafe fn opt_str_arg<'a>(ptr: *const c_char, name: &str) -> Result<Option<&'a str>, RuvectorStatus> {
+    if ptr.is_null() {
+        Ok(None)
+    } else {
+        str_arg(ptr, name).map(Some)
+    }
+}
+
+unsafe fn vector_arg<'a>(ptr: *const f32, len: usize) -> Result<&'a [f32], RuvectorStatus> {
+    if ptr.is_null() {
+        return Err(fail(RuvectorStatus::NullPointer, "vector is NULL"));
+    }
+    Ok(std::slice::from_raw_parts(ptr, len))
+}
+
+fn into_c_string(s: String) -> *mut c_char {
+    CString::new(s.replace('\0', " "))
+        .map(CString::into_raw)
+        .unwrap_or(ptr::null_mut())
+}
+
+/// ABI version implemented by this library
+#[no_mangle]
+pub extern "C" fn ruvector_abi_version() -> u32 {
+    RUVECTOR_ABI_VERSION
+}
+
+/// Library version as a static NUL-terminated string
+#[no_mangle]
+pub extern "C" fn ruvector_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr() as *const c_char
+}
+
+/// Message for the last failed call on this thread, or NULL
+///
+/// The pointer stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn ruvector_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+/// Open (or create) a database at `path`
+///
+/// An existing database keeps its stored dimensions and metric. `use_hnsw`
+/// selects the HNSW index; otherwise a flat (exact) index is used.
+#[no_mangle]
+pub unsafe extern "C" fn ruvector_open(
+    path: *const c_char,
+    dimensions: usize,
+    metric: u32,
+    use_hnsw: bool,
+    out_db: *mut *mut RuvectorDb,
+) -> RuvectorStatus {
+    ffi_call(|| {
+        if out_db.is_null() {
+            return fail(RuvectorStatus::NullPointer, "out_db is NULL");
+        }
+        let path = match str_arg(path, "path") {
+            Ok(p) => p,
+            Err(status) => return status,
+        };
+        if dimensions == 0 {
+            return fail(RuvectorStatus::InvalidArgument, "dimensions must be > 0");
+        }
+        let metric = match RuvectorMetric::try_from(metric) {
+            Ok(m) => m,
+            Err(status) => return status,
+        };
+
+        let options = DbOptions {
+            dimensions,
+            distance_metric: metric.into(),
+            storage_path: path.to_string(),
+            hnsw_config: use_hnsw.then(HnswConfig::default),
+            quantization: None,
+        };
+
+        match VectorDB::new(options) {
+            Ok(inner) => {
+                *out_db = Box::into_raw(Box::new(RuvectorDb { inner }));
+                RuvectorStatus::Ok
+            }
+            Err(e) => fail(RuvectorStatus::DatabaseError, e.to_string()),
+        }
+    })
+}
+
+/// Close a database handle; NULL is ignored
+#[no_mangle]
+pub unsafe extern "C" fn ruvector_close(db: *mut RuvectorDb) {
+    ffi_call_void(|| {
+        if !db.is_null() {
+            drop(Box::from_raw(db));
+        }
+    })
+}
+
+/// Insert a vector
+///
+/// `id` and `metadata_json` may be NULL. When `out_id` is non-NULL it receives
+/// the stored id, which must be freed with [`ruvector_string_free`].
+#[no_mangle]
+pub unsafe extern "C" fn ruvector_insert(
+    db: *const RuvectorDb,
+    id: *const c_char,
+    vector: *const f32,
+    len: usize,
+    metadata_json: *const c_char,
+    out_id: *mut *mut c_char,
+) -> RuvectorStatus {
+    ffi_call(|| {
+        let Some(db) = db.as_ref() else {
+            return fail(RuvectorStatus::NullPointer, "db is NULL");
+        };
+        let (id, vector, metadata) = match (
+            opt_str_arg(id, "id"),
+            vector_arg(vector, len),
+            opt_str_arg(metadata_json, "metadata_json"),
+        ) {
+            (Ok(id), Ok(v), Ok(m)) => (id, v, m),
+            (Err(s), _, _) | (_, Err(s), _) | (_, _, Err(s)) => return s,
+        };
+
+        let metadata = match metadata {
+            Some(json) => match serde_json::from_str::<HashMap<String, serde_json::Value>>(json) {
+                Ok(m) => Some(m),
+                Err(e) => {
+                    return fail(
+                        RuvectorStatus::InvalidArgument,
+                        format!("metadata_json must be a JSON object: {}", e),
+                    )
+                }
+            },
+            None => None,
+        };
+
+        let entry = VectorEntry {
+            id: id.map(str::to_string),
+            vector: vector.to_vec(),
+            metadata,
+        };
+
+        match db.inner.insert(entry) {
+            Ok(stored_id) => {
+                if !out_id.is_null() {
+                    *out_id = into_c_string(stored_id);
+                }
+                RuvectorStatus::Ok
+            }
+            Err(e) => fail(RuvectorStatus::DatabaseError, e.to_string()),
+        }
+    })
+}
+
+/// Search for the `k` nearest neighbours of `query`
+///
+/// On success `*out_results` points to an array that must be released with
+/// [`ruvector_search_results_free`].
+#[no_mangle]
+pub unsafe extern "C" fn ruvector_search(
+    db: *const RuvectorDb,
+    query: *const f32,
+    len: usize,
+    k: usize,
+    out_results: *mut *mut RuvectorSearchResults,
+) -> RuvectorStatus {
+    ffi_call(|| {
+        let Some(db) = db.as_ref() else {
+            return fail(RuvectorStatus::NullPointer, "db is NULL");
+        };
+        if out_results.is_null() {
+            return fail(RuvectorStatus::NullPointer, "out_results is NULL");
+        }
+        let query = match vector_arg(query, len) {
+            Ok(q) => q,
+            Err(status) => return status,
+        };
+
+        let results = match db.inner.search(SearchQuery {
+            vector: query.to_vec(),
+            k,
+            filter: None,
+            ef_search: None,
+        }) {
+            Ok(r) => r,
+            Err(e) => return fail(RuvectorStatus::DatabaseError, e.to_string()),
+        };
+
+        let hits: Box<[RuvectorSearchResult]> = results
+            .into_iter()
+            .map(|r| RuvectorSearchResult {
+                id: into_c_string(r.id),
+                score: r.score,
+            })
+            .collect();
+        let len = hits.len();
+        let results_ptr = Box::into_raw(hits) as *mut RuvectorSearchResult;
+
+        *out_results = Box::into_raw(Box::new(RuvectorSearchResults {
+            results: results_ptr,
+            len,
+        }));
+        RuvectorStatus::Ok
+    })
+}
+
+/// Free results returned by [`ruvector_search`]; NULL is ignored
+#[no_mangle]
+pub unsafe extern "C" fn ruvector_search_results_free(results: *mut RuvectorSearchResults) {
+    ffi_call_void(|| {
+        if results.is_null() {
+            return;
+        }
+        let results = Box::from_raw(results);
+        let hits = Box::from_raw(ptr::slice_from_raw_parts_mut(results.results, results.len));
+        for hit in hits.iter() {
+            if !hit.id.is_null() {
+                drop(CString::from_raw(hit.id));
+            }
+        }
+    })
+}
+
+/// Delete a vector by id; `*out_deleted` is false when the id was not found
+#[no_mangle]
+pub unsafe extern "C" fn ruvector_delete(
+    db: *const RuvectorDb,
+    id: *const c_char,
+    out_deleted: *mut bool,
+) -> RuvectorStatus {
+    ffi_call(|| {
+        let Some(db) = db.as_ref() else {
+            return fail(RuvectorStatus::NullPointer, "db is NULL");
+        };
+        let id = match str_arg(id, "id") {
+            Ok(id) => id,
+            Err(status) => return status,
+        };
+
+        match db.inner.delete(id) {
+            Ok(deleted) => {
+                if !out_deleted.is_null() {
+                    *out_deleted = deleted;
+                }
+                RuvectorStatus::Ok
+            }
+            Err(e) => fail(RuvectorStatus::DatabaseError, e.to_string()),
+        }
+    })
+}
+
+/// Number of stored vectors
+#[no_mangle]
+pub unsafe extern "C" fn ruvector_len(db: *const RuvectorDb, out_len: *mut usize) -> RuvectorStatus {
+    ffi_call(|| {
+        let Some(db) = db.as_ref() else {
+            return fail(RuvectorStatus::NullPointer, "db is NULL");
+        };
+        if out_len.is_null() {
+            return fail(RuvectorStatus::NullPointer, "out_len is NULL");
+        }
+        match db.inner.len() {
+            Ok(len) => {
+                *out_len = len;
+                RuvectorStatus::Ok
+            }
+            Err(e) => fail(RuvectorStatus::DatabaseError, e.to_string()),
+        }
+    })
+}
+
+/// Free a string returned by the library; NULL is ignored
+#[no_mangle]
+pub unsafe extern "C" fn ruvector_string_free(s: *mut c_char) {
+    ffi_call_void(|| {
+        if !s.is_null() {
+            drop(CString::from_raw(s));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_open_insert_search_delete() {
+        let dir = tempdir().unwrap();
+        let path = CString::new(dir.path().join("ffi.db").to_string_lossy().as_bytes()).unwrap();
+
+        unsafe {
+            let mut db: *mut RuvectorDb = ptr::null_mut();
+            assert_eq!(
+                ruvector_open(path.as_ptr(), 3, RuvectorMetric::Euclidean as u32, false, &mut db),
+                RuvectorStatus::Ok
+            );
+
+            let id = CString::new("a").unwrap();
+            let meta = CString::new(r#"{"tag":"x"}"#).unwrap();
+            let mut out_id: *mut c_char = ptr::null_mut();
+            let v = [1.0f32, 0.0, 0.0];
+            assert_eq!(
+                ruvector_insert(db, id.as_ptr(), v.as_ptr(), 3, meta.as_ptr(), &mut out_id),
+                RuvectorStatus::Ok
+            );
+            assert_eq!(CStr::from_ptr(out_id).to_str().unwrap(), "a");
+            ruvector_string_free(out_id);
+
+            let w = [0.0f32, 1.0, 0.0];
+            assert_eq!(
+                ruvector_insert(db, ptr::null(), w.as_ptr(), 3, ptr::null(), ptr::null_mut()),
+                RuvectorStatus::Ok
+            );
+
+            let mut len = 0usize;
+            assert_eq!(ruvector_len(db, &mut len), RuvectorStatus::Ok);
+            assert_eq!(len, 2);
+
+            let mut results: *mut RuvectorSearchResults = ptr::null_mut();
+            assert_eq!(ruvector_search(db, v.as_ptr(), 3, 1, &mut results), RuvectorStatus::Ok);
+            let hits = std::slice::from_raw_parts((*results).results, (*results).len);
+            assert_eq!(hits.len(), 1);
+            assert_eq!(CStr::from_ptr(hits[0].id).to_str().unwrap(), "a");
+            ruvector_search_results_free(results);
+
+            let mut deleted = false;
+            assert_eq!(ruvector_delete(db, id.as_ptr(), &mut deleted), RuvectorStatus::Ok);
+            assert!(deleted);
+
+            ruvector_close(db);
+        }
+    }
+
+    #[test]
+    fn test_errors_are_reported() {
+        unsafe {
+            let mut len = 0usize;
+            assert_eq!(ruvector_len(ptr::null(), &mut len), RuvectorStatus::NullPointer);
+            let msg = CStr::from_ptr(ruvector_last_error()).to_str().unwrap();
+            assert!(msg.contains("db is NULL"));
+
+            let mut db: *mut RuvectorDb = ptr::null_mut();
+            let path = CString::new("unused.db").unwrap();
+            assert_eq!(
+                ruvector_open(path.as_ptr(), 0, RuvectorMetric::Cosine as u32, true, &mut db),
+                RuvectorStatus::InvalidArgument
+            );
+            assert!(db.is_null());
+
+            assert_eq!(
+                ruvector_open(path.as_ptr(), 3, 7, true, &mut db),
+                RuvectorStatus::InvalidArgument
+            );
+            let msg = CStr::from_ptr(ruvector_last_error()).to_str().unwrap();
+            assert!(msg.contains("unknown metric 7"));
+            assert!(db.is_null());
+        }
+        assert_eq!(ruvector_abi_version(), RUVECTOR_ABI_VERSION);
+    }
+
+    #[test]
+    fn test_panics_become_status() {
+        let status = ffi_call(|| panic!("boom"));
+        assert_eq!(status, RuvectorStatus::Panic);
+        let msg = unsafe { CStr::from_ptr(ruvector_last_error()) };
+        assert_eq!(msg.to_str().unwrap(), "panic: boom");
+    }
+}
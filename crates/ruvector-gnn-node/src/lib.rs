@@ -9,23 +9,28 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 use ruvector_gnn::{
+    artifact,
     compress::{
         CompressedTensor as RustCompressedTensor, CompressionLevel as RustCompressionLevel,
         TensorCompress as RustTensorCompress,
     },
     layer::RuvectorLayer as RustRuvectorLayer,
     search::{
-        differentiable_search as rust_differentiable_search,
-        hierarchical_forward as rust_hierarchical_forward,
+        differentiable_search_stream as rust_differentiable_search_stream,
+        differentiable_search_v2 as rust_differentiable_search_v2,
+        hierarchical_forward_slices as rust_hierarchical_forward_slices,
+        CandidateSet as RustCandidateSet, PositionalEncoding as RustPositionalEncoding,
+        SearchOptions as RustSearchOptions,
     },
 };
+use std::sync::Arc;
 
 // ==================== RuvectorLayer Bindings ====================
 
 /// Graph Neural Network layer for HNSW topology
 #[napi]
 pub struct RuvectorLayer {
-    inner: RustRuvectorLayer,
+    inner: Arc<RustRuvectorLayer>,
 }
 
 #[napi]
@@ -52,12 +57,12 @@ impl RuvectorLayer {
         }
 
         Ok(Self {
-            inner: RustRuvectorLayer::new(
+            inner: Arc::new(RustRuvectorLayer::new(
                 input_dim as usize,
                 hidden_dim as usize,
                 heads as usize,
                 dropout as f32,
-            ),
+            )),
         })
     }
 
@@ -86,13 +91,13 @@ impl RuvectorLayer {
         edge_weights: Float32Array,
     ) -> Result<Float32Array> {
         let node_slice = node_embedding.as_ref();
-        let neighbors_vec: Vec<Vec<f32>> = neighbor_embeddings
-            .into_iter()
-            .map(|arr| arr.to_vec())
-            .collect();
+        // Borrowed views, valid while `neighbor_embeddings` is in scope
+        let neighbors: Vec<&[f32]> = neighbor_embeddings.iter().map(|arr| arr.as_ref()).collect();
         let weights_slice = edge_weights.as_ref();
 
-        let result = self.inner.forward(node_slice, &neighbors_vec, weights_slice);
+        let result = self
+            .inner
+            .forward_slices(node_slice, &neighbors, weights_slice);
 
         Ok(Float32Array::new(result))
     }
@@ -100,7 +105,7 @@ impl RuvectorLayer {
     /// Serialize the layer to JSON
     #[napi]
     pub fn to_json(&self) -> Result<String> {
-        serde_json::to_string(&self.inner).map_err(|e| {
+        artifact::to_json(self.inner.as_ref()).map_err(|e| {
             Error::new(
                 Status::GenericFailure,
                 format!("Serialization error: {}", e),
@@ -111,13 +116,68 @@ impl RuvectorLayer {
     /// Deserialize the layer from JSON
     #[napi(factory)]
     pub fn from_json(json: String) -> Result<Self> {
-        let inner: RustRuvectorLayer = serde_json::from_str(&json).map_err(|e| {
+        let inner: RustRuvectorLayer = artifact::from_json(&json).map_err(|e| {
             Error::new(
                 Status::GenericFailure,
                 format!("Deserialization error: {}", e),
             )
         })?;
-        Ok(Self { inner })
+        Ok(Self {
+            inner: Arc::new(inner),
+        })
+    }
+
+    /// Forward pass on a background thread, resolving with the updated
+    /// node embedding
+    ///
+    /// Unlike `forward`, the inputs are copied, since typed array views
+    /// can't be used off the JS thread.
+    ///
+    /// # Example
+    /// ```javascript
+    /// const output = await layer.forwardAsync(node, neighbors, weights);
+    /// ```
+    #[napi]
+    pub fn forward_async(
+        &self,
+        node_embedding: Float32Array,
+        neighbor_embeddings: Vec<Float32Array>,
+        edge_weights: Float32Array,
+    ) -> AsyncTask<ForwardTask> {
+        AsyncTask::new(ForwardTask {
+            layer: self.inner.clone(),
+            node_embedding: node_embedding.to_vec(),
+            neighbor_embeddings: neighbor_embeddings
+                .into_iter()
+                .map(|arr| arr.to_vec())
+                .collect(),
+            edge_weights: edge_weights.to_vec(),
+        })
+    }
+}
+
+/// Background task for `RuvectorLayer.forwardAsync`
+pub struct ForwardTask {
+    layer: Arc<RustRuvectorLayer>,
+    node_embedding: Vec<f32>,
+    neighbor_embeddings: Vec<Vec<f32>>,
+    edge_weights: Vec<f32>,
+}
+
+impl Task for ForwardTask {
+    type Output = Vec<f32>;
+    type JsValue = Float32Array;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(self.layer.forward(
+            &self.node_embedding,
+            &self.neighbor_embeddings,
+            &self.edge_weights,
+        ))
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(Float32Array::new(output))
     }
 }
 
@@ -210,7 +270,7 @@ impl TensorCompress {
             .compress(embedding_slice, access_freq as f32)
             .map_err(|e| Error::new(Status::GenericFailure, format!("Compression error: {}", e)))?;
 
-        serde_json::to_string(&compressed).map_err(|e| {
+        artifact::to_json(&compressed).map_err(|e| {
             Error::new(
                 Status::GenericFailure,
                 format!("Serialization error: {}", e),
@@ -247,7 +307,7 @@ impl TensorCompress {
             .compress_with_level(embedding_slice, &rust_level)
             .map_err(|e| Error::new(Status::GenericFailure, format!("Compression error: {}", e)))?;
 
-        serde_json::to_string(&compressed).map_err(|e| {
+        artifact::to_json(&compressed).map_err(|e| {
             Error::new(
                 Status::GenericFailure,
                 format!("Serialization error: {}", e),
@@ -270,7 +330,7 @@ impl TensorCompress {
     #[napi]
     pub fn decompress(&self, compressed_json: String) -> Result<Float32Array> {
         let compressed: RustCompressedTensor =
-            serde_json::from_str(&compressed_json).map_err(|e| {
+            artifact::from_json(&compressed_json).map_err(|e| {
                 Error::new(
                     Status::GenericFailure,
                     format!("Deserialization error: {}", e),
@@ -286,6 +346,56 @@ impl TensorCompress {
 
         Ok(Float32Array::new(result))
     }
+
+    /// Compress an embedding on a background thread, resolving with the
+    /// compressed tensor as JSON
+    ///
+    /// # Example
+    /// ```javascript
+    /// const compressed = await compressor.compressAsync(embedding, 0.5);
+    /// ```
+    #[napi]
+    pub fn compress_async(
+        &self,
+        embedding: Float32Array,
+        access_freq: f64,
+    ) -> AsyncTask<CompressTask> {
+        AsyncTask::new(CompressTask {
+            compressor: self.inner.clone(),
+            embedding: embedding.to_vec(),
+            access_freq: access_freq as f32,
+        })
+    }
+}
+
+/// Background task for `TensorCompress.compressAsync`
+pub struct CompressTask {
+    compressor: RustTensorCompress,
+    embedding: Vec<f32>,
+    access_freq: f32,
+}
+
+impl Task for CompressTask {
+    type Output = String;
+    type JsValue = String;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let compressed = self
+            .compressor
+            .compress(&self.embedding, self.access_freq)
+            .map_err(|e| Error::new(Status::GenericFailure, format!("Compression error: {}", e)))?;
+
+        artifact::to_json(&compressed).map_err(|e| {
+            Error::new(
+                Status::GenericFailure,
+                format!("Serialization error: {}", e),
+            )
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
 }
 
 // ==================== Search Functions ====================
@@ -325,14 +435,66 @@ pub fn differentiable_search(
     k: u32,
     temperature: f64,
 ) -> Result<SearchResult> {
-    let query_slice = query.as_ref();
-    let candidates_vec: Vec<Vec<f32>> = candidate_embeddings
-        .into_iter()
-        .map(|arr| arr.to_vec())
-        .collect();
+    let options = RustSearchOptions {
+        k: k as usize,
+        temperature: temperature as f32,
+        ..Default::default()
+    };
+    let candidates = candidate_embeddings.iter().map(|arr| arr.as_ref());
+    let (indices, weights) =
+        rust_differentiable_search_stream(query.as_ref(), candidates, &options)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("{}", e)))?;
 
+    Ok(SearchResult {
+        indices: indices.iter().map(|&i| i as u32).collect(),
+        weights: weights.iter().map(|&w| w as f64).collect(),
+    })
+}
+
+/// Differentiable search over candidates packed into one Float32Array
+///
+/// Candidates are read in place, `dim` values each, so no per-candidate
+/// arrays are created or copied across the JS boundary.
+///
+/// # Arguments
+/// * `query` - The query vector (Float32Array)
+/// * `candidates` - Candidate embeddings concatenated row by row
+/// * `dim` - Embedding dimension
+/// * `k` - Number of top results to return
+/// * `temperature` - Temperature for softmax (lower = sharper, higher = smoother)
+///
+/// # Example
+/// ```javascript
+/// const candidates = new Float32Array([1.0, 0.0, 0.0, 0.9, 0.1, 0.0, 0.0, 1.0, 0.0]);
+/// const result = differentiableSearchFlat(new Float32Array([1.0, 0.0, 0.0]), candidates, 3, 2, 1.0);
+/// ```
+#[napi]
+pub fn differentiable_search_flat(
+    query: Float32Array,
+    candidates: Float32Array,
+    dim: u32,
+    k: u32,
+    temperature: f64,
+) -> Result<SearchResult> {
+    let dim = dim as usize;
+    if dim == 0 || candidates.len() % dim != 0 {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!(
+                "Candidate buffer length {} is not a multiple of dim {}",
+                candidates.len(),
+                dim
+            ),
+        ));
+    }
+    let options = RustSearchOptions {
+        k: k as usize,
+        temperature: temperature as f32,
+        ..Default::default()
+    };
     let (indices, weights) =
-        rust_differentiable_search(query_slice, &candidates_vec, k as usize, temperature as f32);
+        rust_differentiable_search_stream(query.as_ref(), candidates.chunks_exact(dim), &options)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("{}", e)))?;
 
     Ok(SearchResult {
         indices: indices.iter().map(|&i| i as u32).collect(),
@@ -340,6 +502,239 @@ pub fn differentiable_search(
     })
 }
 
+/// Candidate embeddings for `differentiableSearchV2`, stored contiguously
+/// and optionally in half precision
+#[napi]
+pub struct CandidateSet {
+    /// Shared with in-flight `searchV2Async` tasks; copied on write
+    inner: Arc<RustCandidateSet>,
+}
+
+#[napi]
+impl CandidateSet {
+    /// Create an empty candidate set
+    ///
+    /// # Arguments
+    /// * `dim` - Embedding dimension
+    /// * `half` - Store embeddings as f16 to halve memory (default: false)
+    ///
+    /// # Example
+    /// ```javascript
+    /// const candidates = new CandidateSet(128, true);
+    /// ```
+    #[napi(constructor)]
+    pub fn new(dim: u32, half: Option<bool>) -> Self {
+        let inner = if half.unwrap_or(false) {
+            RustCandidateSet::new_f16(dim as usize)
+        } else {
+            RustCandidateSet::new(dim as usize)
+        };
+        Self {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Add one embedding
+    #[napi]
+    pub fn add(&mut self, embedding: Float32Array) -> Result<()> {
+        Arc::make_mut(&mut self.inner)
+            .push(embedding.as_ref())
+            .map_err(|e| Error::new(Status::InvalidArg, format!("{}", e)))
+    }
+
+    /// Add several embeddings
+    #[napi]
+    pub fn add_batch(&mut self, embeddings: Vec<Float32Array>) -> Result<()> {
+        for embedding in embeddings {
+            self.add(embedding)?;
+        }
+        Ok(())
+    }
+
+    /// Group embeddings into blocks of `block_size` with similarity bounds,
+    /// so searches with `earlyTermination` can skip whole blocks
+    ///
+    /// Embeddings added later join blocks as they fill up. Pass 0 to remove
+    /// the blocks.
+    #[napi]
+    pub fn index_blocks(&mut self, block_size: u32) {
+        Arc::make_mut(&mut self.inner).index_blocks(block_size as usize);
+    }
+
+    /// Number of embeddings
+    #[napi(getter)]
+    pub fn length(&self) -> u32 {
+        self.inner.len() as u32
+    }
+
+    /// Embedding dimension
+    #[napi(getter)]
+    pub fn dim(&self) -> u32 {
+        self.inner.dim() as u32
+    }
+
+    /// Bytes used by the stored embeddings
+    #[napi(getter)]
+    pub fn size_bytes(&self) -> u32 {
+        self.inner.size_bytes() as u32
+    }
+}
+
+/// Position-aware scoring for `differentiableSearchV2`
+#[napi(object)]
+pub struct PositionalOptions {
+    /// "rotary" or "proximity"
+    pub kind: String,
+    /// Position of the query among the candidates (default: 0)
+    pub query_position: Option<u32>,
+    /// Rotary frequency base (default: 10000)
+    pub base: Option<f64>,
+    /// Proximity bonus at the query's position (default: 0.1)
+    pub weight: Option<f64>,
+}
+
+impl PositionalOptions {
+    fn to_rust(&self) -> Result<RustPositionalEncoding> {
+        match self.kind.as_str() {
+            "rotary" => Ok(RustPositionalEncoding::Rotary {
+                base: self.base.unwrap_or(10000.0) as f32,
+            }),
+            "proximity" => Ok(RustPositionalEncoding::Proximity {
+                weight: self.weight.unwrap_or(0.1) as f32,
+            }),
+            other => Err(Error::new(
+                Status::InvalidArg,
+                format!(
+                    "Invalid positional encoding: {}. Use 'rotary' or 'proximity'",
+                    other
+                ),
+            )),
+        }
+    }
+}
+
+/// Pruning for `differentiableSearchV2`
+#[napi(object)]
+pub struct PruningOptions {
+    /// Skip blocks that cannot reach the top k (requires `indexBlocks`);
+    /// weights are then normalized over the candidates actually scored
+    pub early_termination: Option<bool>,
+    /// Drop candidates scoring below this similarity
+    pub min_similarity: Option<f64>,
+}
+
+/// Differentiable search over a `CandidateSet`
+///
+/// Same scores as `differentiableSearch`, without copying the candidates
+/// on every call. With `positional` set, candidate `i` is treated as being
+/// at position `i` and scored relative to the query's position.
+///
+/// # Example
+/// ```javascript
+/// const candidates = new CandidateSet(3, true);
+/// candidates.addBatch([new Float32Array([1.0, 0.0, 0.0]), new Float32Array([0.0, 1.0, 0.0])]);
+/// const result = differentiableSearchV2(new Float32Array([1.0, 0.0, 0.0]), candidates, 1, 1.0);
+/// const ordered = differentiableSearchV2(query, candidates, 5, 1.0, { kind: 'rotary', queryPosition: 12 });
+///
+/// candidates.indexBlocks(64);
+/// const fast = differentiableSearchV2(query, candidates, 10, 1.0, null, { earlyTermination: true });
+/// ```
+#[napi]
+pub fn differentiable_search_v2(
+    query: Float32Array,
+    candidates: &CandidateSet,
+    k: u32,
+    temperature: f64,
+    positional: Option<PositionalOptions>,
+    pruning: Option<PruningOptions>,
+) -> Result<SearchResult> {
+    let options = search_options(k, temperature, positional, pruning)?;
+    let (indices, weights) =
+        rust_differentiable_search_v2(query.as_ref(), &candidates.inner, &options)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("{}", e)))?;
+
+    Ok(SearchResult {
+        indices: indices.iter().map(|&i| i as u32).collect(),
+        weights: weights.iter().map(|&w| w as f64).collect(),
+    })
+}
+
+/// `differentiableSearchV2` on a background thread
+///
+/// Searches a snapshot of `candidates`; embeddings added while the search
+/// runs are not included.
+///
+/// # Example
+/// ```javascript
+/// const result = await searchV2Async(query, candidates, 10, 1.0);
+/// ```
+#[napi]
+pub fn search_v2_async(
+    query: Float32Array,
+    candidates: &CandidateSet,
+    k: u32,
+    temperature: f64,
+    positional: Option<PositionalOptions>,
+    pruning: Option<PruningOptions>,
+) -> Result<AsyncTask<SearchV2Task>> {
+    Ok(AsyncTask::new(SearchV2Task {
+        query: query.to_vec(),
+        candidates: candidates.inner.clone(),
+        options: search_options(k, temperature, positional, pruning)?,
+    }))
+}
+
+/// Background task for `searchV2Async`
+pub struct SearchV2Task {
+    query: Vec<f32>,
+    candidates: Arc<RustCandidateSet>,
+    options: RustSearchOptions,
+}
+
+impl Task for SearchV2Task {
+    type Output = (Vec<usize>, Vec<f32>);
+    type JsValue = SearchResult;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        rust_differentiable_search_v2(&self.query, &self.candidates, &self.options)
+            .map_err(|e| Error::new(Status::InvalidArg, format!("{}", e)))
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        let (indices, weights) = output;
+        Ok(SearchResult {
+            indices: indices.iter().map(|&i| i as u32).collect(),
+            weights: weights.iter().map(|&w| w as f64).collect(),
+        })
+    }
+}
+
+/// Search options shared by the sync and async v2 searches
+fn search_options(
+    k: u32,
+    temperature: f64,
+    positional: Option<PositionalOptions>,
+    pruning: Option<PruningOptions>,
+) -> Result<RustSearchOptions> {
+    Ok(RustSearchOptions {
+        k: k as usize,
+        temperature: temperature as f32,
+        positional: positional.as_ref().map(|p| p.to_rust()).transpose()?,
+        query_position: positional
+            .as_ref()
+            .and_then(|p| p.query_position)
+            .unwrap_or(0) as usize,
+        early_termination: pruning
+            .as_ref()
+            .and_then(|p| p.early_termination)
+            .unwrap_or(false),
+        min_similarity: pruning
+            .as_ref()
+            .and_then(|p| p.min_similarity)
+            .map(|s| s as f32),
+    })
+}
+
 /// Hierarchical forward pass through GNN layers
 ///
 /// # Arguments
@@ -366,20 +761,16 @@ pub fn hierarchical_forward(
 ) -> Result<Float32Array> {
     let query_slice = query.as_ref();
 
-    let embeddings_f32: Vec<Vec<Vec<f32>>> = layer_embeddings
-        .into_iter()
-        .map(|layer| {
-            layer
-                .into_iter()
-                .map(|arr| arr.to_vec())
-                .collect()
-        })
+    // Borrowed views, valid while `layer_embeddings` is in scope
+    let embeddings: Vec<Vec<&[f32]>> = layer_embeddings
+        .iter()
+        .map(|layer| layer.iter().map(|arr| arr.as_ref()).collect())
         .collect();
 
     let gnn_layers: Vec<RustRuvectorLayer> = gnn_layers_json
         .iter()
         .map(|json| {
-            serde_json::from_str(json).map_err(|e| {
+            artifact::from_json(json).map_err(|e| {
                 Error::new(
                     Status::GenericFailure,
                     format!("Layer deserialization error: {}", e),
@@ -388,7 +779,8 @@ pub fn hierarchical_forward(
         })
         .collect::<Result<Vec<_>>>()?;
 
-    let result = rust_hierarchical_forward(query_slice, &embeddings_f32, &gnn_layers);
+    let result = rust_hierarchical_forward_slices(query_slice, &embeddings, &gnn_layers)
+        .map_err(|e| Error::new(Status::InvalidArg, format!("{}", e)))?;
 
     Ok(Float32Array::new(result))
 }
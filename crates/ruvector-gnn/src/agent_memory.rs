@@ -0,0 +1,606 @@
+//! Episodic, semantic and procedural memory for agents on top of [`VectorDB`]
+//!
+//! Records are embedded into a vector database for relevance search and kept
+//! in a side table with their timestamps and access counts. Recall blends
+//! relevance with an exponential recency decay. Consolidation folds old
+//! episodes into semantic summaries and archives the originals through
+//! [`TensorCompress`], so they stop competing in search but can still be
+//! restored.
+
+use crate::compress::{CompressedTensor, TensorCompress};
+use crate::error::{GnnError, Result};
+use parking_lot::RwLock;
+use ruvector_core::types::{DbOptions, SearchQuery, VectorEntry};
+use ruvector_core::VectorDB;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Metadata key holding the memory kind in the vector database
+const KIND_KEY: &str = "memory_kind";
+
+/// Kind of memory record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MemoryKind {
+    /// Something that happened: an observation, action or conversation turn
+    Episodic,
+    /// A fact or summary distilled from episodes
+    Semantic,
+    /// How to do something: a skill, plan or tool recipe
+    Procedural,
+}
+
+impl MemoryKind {
+    /// Lower-case name used in metadata
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MemoryKind::Episodic => "episodic",
+            MemoryKind::Semantic => "semantic",
+            MemoryKind::Procedural => "procedural",
+        }
+    }
+}
+
+/// A stored memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryRecord {
+    /// Record id
+    pub id: String,
+    /// Memory kind
+    pub kind: MemoryKind,
+    /// Free-form content (text, serialized action, ...)
+    pub content: String,
+    /// Caller-supplied metadata
+    pub metadata: HashMap<String, serde_json::Value>,
+    /// Creation time in seconds since the Unix epoch
+    pub created_at: f64,
+    /// Last recall time in seconds since the Unix epoch
+    pub last_accessed: f64,
+    /// Number of times the record was returned by [`AgentMemory::recall`]
+    pub access_count: u64,
+}
+
+/// Scoring weights for recall
+#[derive(Debug, Clone)]
+pub struct MemoryConfig {
+    /// Weight of vector relevance in the blended score
+    pub relevance_weight: f32,
+    /// Weight of recency in the blended score
+    pub recency_weight: f32,
+    /// Age in seconds at which recency decays to 0.5
+    pub recency_half_life_secs: f64,
+    /// Candidates fetched per requested result before re-ranking
+    pub candidate_multiplier: usize,
+}
+
+impl Default for MemoryConfig {
+    fn default() -> Self {
+        Self {
+            relevance_weight: 0.7,
+            recency_weight: 0.3,
+            recency_half_life_secs: 24.0 * 3600.0,
+            candidate_multiplier: 4,
+        }
+    }
+}
+
+/// A recalled memory with its score components
+#[derive(Debug, Clone)]
+pub struct RecalledMemory {
+    /// The record
+    pub record: MemoryRecord,
+    /// Similarity to the query in `[0, 1]`
+    pub relevance: f32,
+    /// Recency in `[0, 1]`, 1 for a record created now
+    pub recency: f32,
+    /// Blended score used for ranking
+    pub score: f32,
+}
+
+/// Settings for [`AgentMemory::consolidate`]
+#[derive(Debug, Clone)]
+pub struct ConsolidationConfig {
+    /// Only episodes older than this (seconds) are consolidated
+    pub min_age_secs: f64,
+    /// Number of consecutive episodes folded into one summary
+    pub episodes_per_summary: usize,
+    /// Maximum characters of content copied into a summary
+    pub max_summary_chars: usize,
+}
+
+impl Default for ConsolidationConfig {
+    fn default() -> Self {
+        Self {
+            min_age_secs: 7.0 * 24.0 * 3600.0,
+            episodes_per_summary: 8,
+            max_summary_chars: 2048,
+        }
+    }
+}
+
+/// Outcome of a consolidation run
+#[derive(Debug, Clone, Default)]
+pub struct ConsolidationReport {
+    /// Episodes moved to the archive
+    pub episodes_archived: usize,
+    /// Ids of the semantic summaries created
+    pub summaries: Vec<String>,
+    /// Bytes of embedding data before compression
+    pub bytes_before: usize,
+    /// Bytes of embedding data after compression
+    pub bytes_after: usize,
+}
+
+/// An archived episode with its compressed embedding
+#[derive(Debug, Clone)]
+struct ArchivedMemory {
+    record: MemoryRecord,
+    embedding: CompressedTensor,
+    summary_id: String,
+}
+
+/// Typed agent memory backed by a vector database
+pub struct AgentMemory {
+    db: VectorDB,
+    config: MemoryConfig,
+    records: RwLock<HashMap<String, MemoryRecord>>,
+    archive: RwLock<HashMap<String, ArchivedMemory>>,
+    compressor: TensorCompress,
+    next_id: AtomicU64,
+}
+
+impl AgentMemory {
+    /// Create a memory store using the given database options
+    pub fn new(options: DbOptions, config: MemoryConfig) -> Result<Self> {
+        Ok(Self {
+            db: VectorDB::new(options)?,
+            config,
+            records: RwLock::new(HashMap::new()),
+            archive: RwLock::new(HashMap::new()),
+            compressor: TensorCompress::new(),
+            next_id: AtomicU64::new(0),
+        })
+    }
+
+    /// Number of live (non-archived) records
+    pub fn len(&self) -> usize {
+        self.records.read().len()
+    }
+
+    /// Whether there are no live records
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of archived episodes
+    pub fn archived_len(&self) -> usize {
+        self.archive.read().len()
+    }
+
+    /// Store a memory timestamped now
+    pub fn remember(
+        &self,
+        kind: MemoryKind,
+        content: impl Into<String>,
+        embedding: Vec<f32>,
+        metadata: HashMap<String, serde_json::Value>,
+    ) -> Result<String> {
+        self.remember_at(kind, content, embedding, metadata, now_secs())
+    }
+
+    /// Store a memory with an explicit creation time (seconds since the epoch)
+    pub fn remember_at(
+        &self,
+        kind: MemoryKind,
+        content: impl Into<String>,
+        embedding: Vec<f32>,
+        metadata: HashMap<String, serde_json::Value>,
+        created_at: f64,
+    ) -> Result<String> {
+        let id = format!(
+            "{}-{}",
+            kind.as_str(),
+            self.next_id.fetch_add(1, Ordering::Relaxed)
+        );
+
+        let mut db_meta = HashMap::new();
+        db_meta.insert(KIND_KEY.to_string(), serde_json::json!(kind.as_str()));
+        self.db.insert(VectorEntry {
+            id: Some(id.clone()),
+            vector: embedding,
+            metadata: Some(db_meta),
+        })?;
+
+        self.records.write().insert(
+            id.clone(),
+            MemoryRecord {
+                id: id.clone(),
+                kind,
+                content: content.into(),
+                metadata,
+                created_at,
+                last_accessed: created_at,
+                access_count: 0,
+            },
+        );
+
+        Ok(id)
+    }
+
+    /// Get a live record by id
+    pub fn get(&self, id: &str) -> Option<MemoryRecord> {
+        self.records.read().get(id).cloned()
+    }
+
+    /// Remove a record, live or archived
+    pub fn forget(&self, id: &str) -> Result<bool> {
+        if self.records.write().remove(id).is_some() {
+            self.db.delete(id)?;
+            return Ok(true);
+        }
+        Ok(self.archive.write().remove(id).is_some())
+    }
+
+    /// Recall the `k` best memories for `query`, optionally of one kind
+    pub fn recall(
+        &self,
+        query: &[f32],
+        k: usize,
+        kind: Option<MemoryKind>,
+    ) -> Result<Vec<RecalledMemory>> {
+        self.recall_at(query, k, kind, now_secs())
+    }
+
+    /// [`recall`](Self::recall) evaluated at time `now` (seconds since the epoch)
+    pub fn recall_at(
+        &self,
+        query: &[f32],
+        k: usize,
+        kind: Option<MemoryKind>,
+        now: f64,
+    ) -> Result<Vec<RecalledMemory>> {
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+
+        let candidates = self.db.search(SearchQuery {
+            vector: query.to_vec(),
+            k: k * self.config.candidate_multiplier.max(1),
+            filter: kind.map(|kind| {
+                let mut filter = HashMap::new();
+                filter.insert(KIND_KEY.to_string(), serde_json::json!(kind.as_str()));
+                filter
+            }),
+            ef_search: None,
+        })?;
+
+        let mut recalled: Vec<RecalledMemory> = {
+            let records = self.records.read();
+            candidates
+                .into_iter()
+                .filter_map(|hit| {
+                    let record = records.get(&hit.id)?.clone();
+                    let relevance = (1.0 - hit.score).clamp(0.0, 1.0);
+                    let recency = self.recency(now - record.created_at);
+                    let score = self.config.relevance_weight * relevance
+                        + self.config.recency_weight * recency;
+                    Some(RecalledMemory {
+                        record,
+                        relevance,
+                        recency,
+                        score,
+                    })
+                })
+                .collect()
+        };
+
+        recalled.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        recalled.truncate(k);
+
+        let mut records = self.records.write();
+        for memory in &mut recalled {
+            if let Some(record) = records.get_mut(&memory.record.id) {
+                record.access_count += 1;
+                record.last_accessed = now;
+                memory.record.access_count = record.access_count;
+                memory.record.last_accessed = now;
+            }
+        }
+
+        Ok(recalled)
+    }
+
+    /// Fold old episodes into semantic summaries and archive them compressed
+    pub fn consolidate(&self, config: &ConsolidationConfig) -> Result<ConsolidationReport> {
+        self.consolidate_at(config, now_secs())
+    }
+
+    /// [`consolidate`](Self::consolidate) evaluated at time `now`
+    ///
+    /// Eligible episodes are grouped in creation order; each group becomes one
+    /// semantic record whose embedding is the group mean. The originals leave
+    /// the search index and their embeddings are compressed with a level chosen
+    /// from how often they were recalled.
+    pub fn consolidate_at(
+        &self,
+        config: &ConsolidationConfig,
+        now: f64,
+    ) -> Result<ConsolidationReport> {
+        let mut eligible: Vec<MemoryRecord> = self
+            .records
+            .read()
+            .values()
+            .filter(|r| r.kind == MemoryKind::Episodic && now - r.created_at >= config.min_age_secs)
+            .cloned()
+            .collect();
+        eligible.sort_by(|a, b| {
+            a.created_at
+                .partial_cmp(&b.created_at)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let max_access = eligible.iter().map(|r| r.access_count).max().unwrap_or(0);
+        let mut report = ConsolidationReport::default();
+
+        for group in eligible.chunks(config.episodes_per_summary.max(1)) {
+            let mut embeddings = Vec::with_capacity(group.len());
+            for record in group {
+                let entry = self.db.get(&record.id)?.ok_or_else(|| {
+                    GnnError::Other(format!("missing embedding for {}", record.id))
+                })?;
+                embeddings.push(entry.vector);
+            }
+
+            let summary_id = self.remember_at(
+                MemoryKind::Semantic,
+                summarize(group, config.max_summary_chars),
+                mean_vector(&embeddings),
+                summary_metadata(group),
+                group.last().map_or(now, |r| r.created_at),
+            )?;
+
+            for (record, embedding) in group.iter().zip(embeddings) {
+                let freq = if max_access == 0 {
+                    0.0
+                } else {
+                    record.access_count as f32 / max_access as f32
+                };
+                let compressed = self.compressor.compress(&embedding, freq)?;
+
+                report.bytes_before += embedding.len() * std::mem::size_of::<f32>();
+                report.bytes_after += compressed_size(&compressed);
+
+                self.db.delete(&record.id)?;
+                self.records.write().remove(&record.id);
+                self.archive.write().insert(
+                    record.id.clone(),
+                    ArchivedMemory {
+                        record: record.clone(),
+                        embedding: compressed,
+                        summary_id: summary_id.clone(),
+                    },
+                );
+                report.episodes_archived += 1;
+            }
+
+            report.summaries.push(summary_id);
+        }
+
+        Ok(report)
+    }
+
+    /// Summary id an archived episode was folded into
+    pub fn summary_of(&self, id: &str) -> Option<String> {
+        self.archive.read().get(id).map(|a| a.summary_id.clone())
+    }
+
+    /// Move an archived episode back into the searchable store
+    ///
+    /// The embedding is decompressed, so it may be lossy depending on the
+    /// compression level used at archive time.
+    pub fn restore(&self, id: &str) -> Result<bool> {
+        let archived = match self.archive.write().remove(id) {
+            Some(archived) => archived,
+            None => return Ok(false),
+        };
+
+        let embedding = self.compressor.decompress(&archived.embedding)?;
+        let mut db_meta = HashMap::new();
+        db_meta.insert(
+            KIND_KEY.to_string(),
+            serde_json::json!(archived.record.kind.as_str()),
+        );
+        self.db.insert(VectorEntry {
+            id: Some(archived.record.id.clone()),
+            vector: embedding,
+            metadata: Some(db_meta),
+        })?;
+        self.records
+            .write()
+            .insert(archived.record.id.clone(), archived.record);
+
+        Ok(true)
+    }
+
+    fn recency(&self, age_secs: f64) -> f32 {
+        let half_life = self.config.recency_half_life_secs.max(f64::EPSILON);
+        (0.5f64).powf(age_secs.max(0.0) / half_life) as f32
+    }
+}
+
+fn now_secs() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
+fn mean_vector(vectors: &[Vec<f32>]) -> Vec<f32> {
+    let dim = vectors.first().map_or(0, |v| v.len());
+    let mut mean = vec![0.0f32; dim];
+    for v in vectors {
+        for (m, x) in mean.iter_mut().zip(v) {
+            *m += x;
+        }
+    }
+    let n = vectors.len().max(1) as f32;
+    mean.iter_mut().for_each(|m| *m /= n);
+    mean
+}
+
+fn summarize(group: &[MemoryRecord], max_chars: usize) -> String {
+    let mut summary = format!("Summary of {} episodes: ", group.len());
+    let joined = group
+        .iter()
+        .map(|r| r.content.as_str())
+        .collect::<Vec<_>>()
+        .join(" | ");
+    summary.extend(joined.chars().take(max_chars));
+    summary
+}
+
+fn summary_metadata(group: &[MemoryRecord]) -> HashMap<String, serde_json::Value> {
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "source_ids".to_string(),
+        serde_json::json!(group.iter().map(|r| r.id.as_str()).collect::<Vec<_>>()),
+    );
+    if let (Some(first), Some(last)) = (group.first(), group.last()) {
+        metadata.insert("from".to_string(), serde_json::json!(first.created_at));
+        metadata.insert("to".to_string(), serde_json::json!(last.created_at));
+    }
+    metadata
+}
+
+fn compressed_size(tensor: &CompressedTensor) -> usize {
+    match tensor {
+        CompressedTensor::Full { data } => data.len() * 4,
+        CompressedTensor::Half { data, .. } => data.len() * 2 + 4,
+        CompressedTensor::PQ8 {
+            codes, codebooks, ..
+        }
+        | CompressedTensor::PQ4 {
+            codes, codebooks, ..
+        } => codes.len() + codebooks.iter().map(|c| c.len() * 4).sum::<usize>(),
+        CompressedTensor::Binary { bits, .. } => bits.len() + 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruvector_core::types::DistanceMetric;
+    use tempfile::tempdir;
+
+    fn memory(dir: &tempfile::TempDir) -> AgentMemory {
+        let options = DbOptions {
+            dimensions: 4,
+            distance_metric: DistanceMetric::Cosine,
+            storage_path: dir.path().join("memory.db").to_string_lossy().to_string(),
+            hnsw_config: None,
+            quantization: None,
+        };
+        AgentMemory::new(options, MemoryConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_recall_blends_recency_and_relevance() {
+        let dir = tempdir().unwrap();
+        let mem = memory(&dir);
+        let now = 1_000_000.0;
+        let day = 24.0 * 3600.0;
+
+        let old = mem
+            .remember_at(
+                MemoryKind::Episodic,
+                "old",
+                vec![1.0, 0.0, 0.0, 0.0],
+                HashMap::new(),
+                now - 30.0 * day,
+            )
+            .unwrap();
+        let fresh = mem
+            .remember_at(
+                MemoryKind::Episodic,
+                "fresh",
+                vec![0.9, 0.1, 0.0, 0.0],
+                HashMap::new(),
+                now,
+            )
+            .unwrap();
+        mem.remember_at(
+            MemoryKind::Procedural,
+            "howto",
+            vec![1.0, 0.0, 0.0, 0.0],
+            HashMap::new(),
+            now,
+        )
+        .unwrap();
+
+        let recalled = mem
+            .recall_at(&[1.0, 0.0, 0.0, 0.0], 2, Some(MemoryKind::Episodic), now)
+            .unwrap();
+        assert_eq!(recalled.len(), 2);
+        assert_eq!(recalled[0].record.id, fresh);
+        assert_eq!(recalled[1].record.id, old);
+        assert!(recalled[1].relevance > recalled[0].relevance);
+        assert!(recalled[0].recency > 0.99);
+        assert_eq!(mem.get(&fresh).unwrap().access_count, 1);
+    }
+
+    #[test]
+    fn test_consolidate_archives_old_episodes() {
+        let dir = tempdir().unwrap();
+        let mem = memory(&dir);
+        let now = 10_000_000.0;
+        let config = ConsolidationConfig {
+            min_age_secs: 3600.0,
+            episodes_per_summary: 2,
+            max_summary_chars: 64,
+        };
+
+        let mut ids = Vec::new();
+        for i in 0..4 {
+            ids.push(
+                mem.remember_at(
+                    MemoryKind::Episodic,
+                    format!("event {}", i),
+                    vec![1.0, i as f32 * 0.1, 0.0, 0.0],
+                    HashMap::new(),
+                    now - 7200.0 + i as f64,
+                )
+                .unwrap(),
+            );
+        }
+        let recent = mem
+            .remember_at(
+                MemoryKind::Episodic,
+                "recent",
+                vec![0.0, 1.0, 0.0, 0.0],
+                HashMap::new(),
+                now,
+            )
+            .unwrap();
+
+        let report = mem.consolidate_at(&config, now).unwrap();
+        assert_eq!(report.episodes_archived, 4);
+        assert_eq!(report.summaries.len(), 2);
+        assert_eq!(mem.archived_len(), 4);
+        assert_eq!(mem.len(), 3);
+        assert!(mem.get(&recent).is_some());
+
+        let summary = mem.get(&report.summaries[0]).unwrap();
+        assert_eq!(summary.kind, MemoryKind::Semantic);
+        assert!(summary.content.contains("event 0"));
+        assert_eq!(mem.summary_of(&ids[1]), Some(report.summaries[0].clone()));
+
+        assert!(mem.restore(&ids[0]).unwrap());
+        assert!(mem.get(&ids[0]).is_some());
+        assert_eq!(mem.archived_len(), 3);
+    }
+}
@@ -0,0 +1,202 @@
+//! Versioned serialization for GNN artifacts
+//!
+//! Layers and compressed tensors (including their PQ codebooks) used to be
+//! exchanged as bare `serde_json` structures, so a field change in a new
+//! release broke old files silently or with an opaque serde error. Every
+//! artifact is now wrapped in an envelope:
+//!
+//! ```json
+//! {"magic": "ruvector-artifact", "kind": "layer", "schema_version": 1,
+//!  "crate_version": "0.1.2", "payload": { ... }}
+//! ```
+//!
+//! On read, the kind must match, artifacts from a newer schema are rejected
+//! with the release that wrote them, and older schemas are upgraded one step
+//! at a time through [`Artifact::upgrade`]. Headerless JSON from before this
+//! format is treated as schema version 0.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::compress::CompressedTensor;
+use crate::error::{GnnError, Result};
+use crate::layer::RuvectorLayer;
+
+/// Magic string identifying a versioned artifact
+pub const ARTIFACT_MAGIC: &str = "ruvector-artifact";
+
+/// Envelope fields describing a serialized artifact
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactHeader {
+    /// Always [`ARTIFACT_MAGIC`]
+    pub magic: String,
+    /// Artifact kind, e.g. `"layer"`
+    pub kind: String,
+    /// Schema version of the payload
+    pub schema_version: u32,
+    /// Version of ruvector-gnn that wrote the artifact
+    pub crate_version: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Envelope<T> {
+    #[serde(flatten)]
+    header: ArtifactHeader,
+    payload: T,
+}
+
+/// A type with a versioned serialized form
+pub trait Artifact: Serialize + DeserializeOwned {
+    /// Kind recorded in the header
+    const KIND: &'static str;
+
+    /// Schema version this build writes
+    const SCHEMA_VERSION: u32;
+
+    /// Upgrade a payload from schema `from` to schema `from + 1`
+    ///
+    /// The default maps headerless version 0 JSON unchanged onto schema 1;
+    /// types that change their layout add a step per version.
+    fn upgrade(from: u32, payload: Value) -> Result<Value> {
+        match from {
+            0 => Ok(payload),
+            _ => Err(GnnError::artifact(format!(
+                "No upgrade path for {} schema {}",
+                Self::KIND,
+                from
+            ))),
+        }
+    }
+}
+
+impl Artifact for RuvectorLayer {
+    const KIND: &'static str = "layer";
+    const SCHEMA_VERSION: u32 = 1;
+}
+
+impl Artifact for CompressedTensor {
+    const KIND: &'static str = "compressed_tensor";
+    const SCHEMA_VERSION: u32 = 1;
+}
+
+/// Serialize an artifact with a versioned header
+pub fn to_json<T: Artifact>(value: &T) -> Result<String> {
+    let envelope = Envelope {
+        header: ArtifactHeader {
+            magic: ARTIFACT_MAGIC.to_string(),
+            kind: T::KIND.to_string(),
+            schema_version: T::SCHEMA_VERSION,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        },
+        payload: value,
+    };
+    serde_json::to_string(&envelope)
+        .map_err(|e| GnnError::artifact(format!("Cannot serialize {}: {}", T::KIND, e)))
+}
+
+/// Deserialize an artifact, upgrading older schemas
+pub fn from_json<T: Artifact>(json: &str) -> Result<T> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|e| GnnError::artifact(format!("Invalid {} JSON: {}", T::KIND, e)))?;
+
+    let (mut version, mut payload) = match header_of(&value)? {
+        Some(header) => {
+            if header.kind != T::KIND {
+                return Err(GnnError::artifact(format!(
+                    "Expected a {} artifact, found {}",
+                    T::KIND,
+                    header.kind
+                )));
+            }
+            if header.schema_version > T::SCHEMA_VERSION {
+                return Err(GnnError::artifact(format!(
+                    "{} schema {} was written by ruvector-gnn {}; this build ({}) reads up to schema {}",
+                    T::KIND,
+                    header.schema_version,
+                    header.crate_version,
+                    env!("CARGO_PKG_VERSION"),
+                    T::SCHEMA_VERSION
+                )));
+            }
+            let payload = match value {
+                Value::Object(mut map) => map.remove("payload").unwrap_or(Value::Null),
+                _ => Value::Null,
+            };
+            (header.schema_version, payload)
+        }
+        None => (0, value),
+    };
+
+    while version < T::SCHEMA_VERSION {
+        payload = T::upgrade(version, payload)?;
+        version += 1;
+    }
+
+    serde_json::from_value(payload).map_err(|e| {
+        GnnError::artifact(format!(
+            "Malformed {} schema {} payload: {}",
+            T::KIND,
+            T::SCHEMA_VERSION,
+            e
+        ))
+    })
+}
+
+/// Read the header of a serialized artifact, or `None` for headerless JSON
+pub fn read_header(json: &str) -> Result<Option<ArtifactHeader>> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|e| GnnError::artifact(format!("Invalid artifact JSON: {}", e)))?;
+    header_of(&value)
+}
+
+fn header_of(value: &Value) -> Result<Option<ArtifactHeader>> {
+    if value.get("magic").and_then(Value::as_str) != Some(ARTIFACT_MAGIC) {
+        return Ok(None);
+    }
+    let header = ArtifactHeader::deserialize(value)
+        .map_err(|e| GnnError::artifact(format!("Malformed artifact header: {}", e)))?;
+    Ok(Some(header))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compress::TensorCompress;
+
+    #[test]
+    fn test_artifact_roundtrip_and_legacy() {
+        let layer = RuvectorLayer::new(4, 8, 2, 0.0);
+        let json = to_json(&layer).unwrap();
+        let header = read_header(&json).unwrap().unwrap();
+        assert_eq!(header.kind, "layer");
+        assert_eq!(header.schema_version, 1);
+        let restored: RuvectorLayer = from_json(&json).unwrap();
+        assert_eq!(
+            serde_json::to_value(&restored).unwrap(),
+            serde_json::to_value(&layer).unwrap()
+        );
+
+        // Headerless JSON from earlier releases still loads
+        let legacy = serde_json::to_string(&layer).unwrap();
+        assert!(read_header(&legacy).unwrap().is_none());
+        assert!(from_json::<RuvectorLayer>(&legacy).is_ok());
+
+        // Wrong kind is rejected
+        let tensor = TensorCompress::new().compress(&[1.0, 2.0], 1.0).unwrap();
+        let tensor_json = to_json(&tensor).unwrap();
+        assert!(from_json::<RuvectorLayer>(&tensor_json).is_err());
+        assert!(from_json::<CompressedTensor>(&tensor_json).is_ok());
+    }
+
+    #[test]
+    fn test_newer_schema_rejected() {
+        let layer = RuvectorLayer::new(4, 8, 2, 0.0);
+        let mut value: Value = serde_json::from_str(&to_json(&layer).unwrap()).unwrap();
+        value["schema_version"] = Value::from(99);
+        value["crate_version"] = Value::from("9.9.9");
+
+        let err = from_json::<RuvectorLayer>(&value.to_string()).unwrap_err();
+        assert!(err.to_string().contains("9.9.9"));
+    }
+}
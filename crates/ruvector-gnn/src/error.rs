@@ -46,6 +46,10 @@ pub enum GnnError {
     #[error("Memory mapping error: {0}")]
     Mmap(String),
 
+    /// Serialized artifact is malformed or from an incompatible release
+    #[error("Artifact error: {0}")]
+    Artifact(String),
+
     /// I/O error
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
@@ -99,6 +103,11 @@ impl GnnError {
         Self::Mmap(msg.into())
     }
 
+    /// Create an artifact error
+    pub fn artifact(msg: impl Into<String>) -> Self {
+        Self::Artifact(msg.into())
+    }
+
     /// Create an invalid input error
     pub fn invalid_input(msg: impl Into<String>) -> Self {
         Self::InvalidInput(msg.into())
@@ -10,8 +10,27 @@
 /// - θ_i is the current weight
 /// - θ*_i is the anchor weight from the previous task
 
+use crate::replay::{ReplayBuffer, ReplayEntry};
 use std::f32;
 
+/// Settings for estimating Fisher information from a replay buffer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FisherConfig {
+    /// Replay entries used for the estimate (capped at the buffer size)
+    pub sample_count: usize,
+    /// Entries per mini-batch passed to the gradient function
+    pub batch_size: usize,
+}
+
+impl Default for FisherConfig {
+    fn default() -> Self {
+        Self {
+            sample_count: 256,
+            batch_size: 32,
+        }
+    }
+}
+
 /// Elastic Weight Consolidation implementation
 ///
 /// Prevents catastrophic forgetting by penalizing changes to important weights
@@ -94,6 +113,95 @@ impl ElasticWeightConsolidation {
         }
     }
 
+    /// Estimate the Fisher information diagonal from replay-buffer samples
+    ///
+    /// Draws `config.sample_count` distinct entries from `buffer` and passes
+    /// them to `gradients` in mini-batches of `config.batch_size`. For each
+    /// batch, `gradients` returns one loss gradient per entry, computed at
+    /// the current weights; the Fisher diagonal is their mean square, as in
+    /// [`compute_fisher`](Self::compute_fisher).
+    ///
+    /// # Arguments
+    /// * `buffer` - Replay buffer holding samples of the finished task
+    /// * `config` - Sample count and mini-batch size
+    /// * `gradients` - Per-entry gradients for a mini-batch
+    ///
+    /// # Returns
+    /// Number of samples used; 0 (leaving the Fisher diagonal unchanged) if
+    /// the buffer is empty
+    pub fn estimate_fisher<F>(
+        &mut self,
+        buffer: &ReplayBuffer,
+        config: &FisherConfig,
+        mut gradients: F,
+    ) -> usize
+    where
+        F: FnMut(&[&ReplayEntry]) -> Vec<Vec<f32>>,
+    {
+        let samples = buffer.sample(config.sample_count);
+        if samples.is_empty() {
+            return 0;
+        }
+
+        let mut fisher: Vec<f32> = Vec::new();
+        let mut count = 0;
+        for batch in samples.chunks(config.batch_size.max(1)) {
+            let grads = gradients(batch);
+            assert_eq!(
+                grads.len(),
+                batch.len(),
+                "Gradient function must return one gradient per entry"
+            );
+            for grad in &grads {
+                if fisher.is_empty() {
+                    fisher = vec![0.0; grad.len()];
+                }
+                assert_eq!(
+                    grad.len(),
+                    fisher.len(),
+                    "All gradient vectors must have the same length"
+                );
+                for (f, &g) in fisher.iter_mut().zip(grad) {
+                    *f += g * g;
+                }
+            }
+            count += grads.len();
+        }
+
+        let normalization = 1.0 / count as f32;
+        for f in &mut fisher {
+            *f *= normalization;
+        }
+        self.fisher_diag = fisher;
+        count
+    }
+
+    /// Finish a task: estimate Fisher information from `buffer` and
+    /// consolidate `weights`
+    ///
+    /// Combines [`estimate_fisher`](Self::estimate_fisher) and
+    /// [`consolidate`](Self::consolidate) for use at task boundaries.
+    ///
+    /// # Returns
+    /// Number of samples used; 0 (leaving EWC unchanged) if the buffer is
+    /// empty
+    pub fn consolidate_from_replay<F>(
+        &mut self,
+        weights: &[f32],
+        buffer: &ReplayBuffer,
+        config: &FisherConfig,
+        gradients: F,
+    ) -> usize
+    where
+        F: FnMut(&[&ReplayEntry]) -> Vec<Vec<f32>>,
+    {
+        let count = self.estimate_fisher(buffer, config, gradients);
+        if count > 0 {
+            self.consolidate(weights);
+        }
+        count
+    }
+
     /// Save current weights as anchor and activate EWC
     ///
     /// This should be called after training on a task, before moving to the next task.
@@ -580,4 +688,42 @@ mod tests {
         assert!(grad[1] > 0.0);
         assert!(grad[2] > 0.0);
     }
+
+    #[test]
+    fn test_estimate_fisher_from_replay() {
+        let mut buffer = ReplayBuffer::new(100);
+        for i in 0..50 {
+            buffer.add(&[i as f32, 1.0], &[i]);
+        }
+
+        let mut ewc = ElasticWeightConsolidation::new(10.0);
+        let config = FisherConfig {
+            sample_count: 20,
+            batch_size: 8,
+        };
+        let mut batch_sizes = Vec::new();
+        // Gradient of each sample is its query: weight 1 always sees 1.0
+        let used = ewc.consolidate_from_replay(&[0.5, 0.5], &buffer, &config, |batch| {
+            batch_sizes.push(batch.len());
+            batch.iter().map(|entry| entry.query.clone()).collect()
+        });
+
+        assert_eq!(used, 20);
+        assert_eq!(batch_sizes, vec![8, 8, 4]);
+        assert!(ewc.is_active());
+        assert!((ewc.fisher_diag()[1] - 1.0).abs() < 1e-6);
+        assert!(ewc.fisher_diag()[0] > 1.0);
+        assert_eq!(ewc.anchor_weights(), &[0.5, 0.5]);
+
+        // Empty buffers leave EWC untouched
+        let mut fresh = ElasticWeightConsolidation::new(10.0);
+        let used = fresh.consolidate_from_replay(
+            &[0.5],
+            &ReplayBuffer::new(10),
+            &config,
+            |_| unreachable!(),
+        );
+        assert_eq!(used, 0);
+        assert!(!fresh.is_active());
+    }
 }
@@ -121,8 +121,22 @@ impl MultiHeadAttention {
     /// # Returns
     /// Attention-weighted output vector
     pub fn forward(&self, query: &[f32], keys: &[Vec<f32>], values: &[Vec<f32>]) -> Vec<f32> {
+        self.forward_with_weights(query, keys, values).0
+    }
+
+    /// Forward pass that also returns the attention weights
+    ///
+    /// # Returns
+    /// Attention-weighted output vector and, per head, the softmax weights
+    /// over `keys` (empty when there are no keys)
+    pub fn forward_with_weights(
+        &self,
+        query: &[f32],
+        keys: &[Vec<f32>],
+        values: &[Vec<f32>],
+    ) -> (Vec<f32>, Vec<Vec<f32>>) {
         if keys.is_empty() || values.is_empty() {
-            return query.to_vec();
+            return (query.to_vec(), Vec::new());
         }
 
         // Project query, keys, and values
@@ -137,20 +151,22 @@ impl MultiHeadAttention {
 
         // Compute attention for each head
         let mut head_outputs = Vec::new();
+        let mut head_weights = Vec::new();
         for h in 0..self.num_heads {
             let q_h = &q_heads[h];
             let k_h: Vec<&Vec<f32>> = k_heads.iter().map(|heads| &heads[h]).collect();
             let v_h: Vec<&Vec<f32>> = v_heads.iter().map(|heads| &heads[h]).collect();
 
-            let head_output = self.scaled_dot_product_attention(q_h, &k_h, &v_h);
+            let (head_output, weights) = self.scaled_dot_product_attention(q_h, &k_h, &v_h);
             head_outputs.push(head_output);
+            head_weights.push(weights);
         }
 
         // Concatenate heads
         let concat: Vec<f32> = head_outputs.into_iter().flatten().collect();
 
         // Final linear projection
-        self.out_linear.forward(&concat)
+        (self.out_linear.forward(&concat), head_weights)
     }
 
     /// Split vector into multiple heads
@@ -170,9 +186,9 @@ impl MultiHeadAttention {
         query: &[f32],
         keys: &[&Vec<f32>],
         values: &[&Vec<f32>],
-    ) -> Vec<f32> {
+    ) -> (Vec<f32>, Vec<f32>) {
         if keys.is_empty() {
-            return query.to_vec();
+            return (query.to_vec(), Vec::new());
         }
 
         let scale = (self.head_dim as f32).sqrt();
@@ -200,7 +216,7 @@ impl MultiHeadAttention {
             }
         }
 
-        output
+        (output, attention_weights)
     }
 }
 
@@ -365,23 +381,62 @@ impl RuvectorLayer {
         neighbor_embeddings: &[Vec<f32>],
         edge_weights: &[f32],
     ) -> Vec<f32> {
+        self.forward_with_attention(node_embedding, neighbor_embeddings, edge_weights)
+            .0
+    }
+
+    /// Forward pass over borrowed neighbor embeddings
+    ///
+    /// Same as [`forward`](Self::forward), for callers whose neighbor
+    /// embeddings live in buffers they don't own, e.g. JS typed arrays,
+    /// so they need not be copied into `Vec`s first.
+    pub fn forward_slices(
+        &self,
+        node_embedding: &[f32],
+        neighbor_embeddings: &[&[f32]],
+        edge_weights: &[f32],
+    ) -> Vec<f32> {
+        self.forward_impl(node_embedding, neighbor_embeddings, edge_weights)
+            .0
+    }
+
+    /// Forward pass that also reports how much attention each neighbor got
+    ///
+    /// # Returns
+    /// Updated node embedding and, per neighbor, the attention weight
+    /// averaged over heads (sums to 1; empty when there are no neighbors)
+    pub fn forward_with_attention(
+        &self,
+        node_embedding: &[f32],
+        neighbor_embeddings: &[Vec<f32>],
+        edge_weights: &[f32],
+    ) -> (Vec<f32>, Vec<f32>) {
+        self.forward_impl(node_embedding, neighbor_embeddings, edge_weights)
+    }
+
+    fn forward_impl<R: AsRef<[f32]>>(
+        &self,
+        node_embedding: &[f32],
+        neighbor_embeddings: &[R],
+        edge_weights: &[f32],
+    ) -> (Vec<f32>, Vec<f32>) {
         if neighbor_embeddings.is_empty() {
             // No neighbors: return normalized projection
             let projected = self.w_msg.forward(node_embedding);
-            return self.norm.forward(&projected);
+            return (self.norm.forward(&projected), Vec::new());
         }
 
         // Step 1: Message passing - transform node and neighbor embeddings
         let node_msg = self.w_msg.forward(node_embedding);
         let neighbor_msgs: Vec<Vec<f32>> = neighbor_embeddings
             .iter()
-            .map(|n| self.w_msg.forward(n))
+            .map(|n| self.w_msg.forward(n.as_ref()))
             .collect();
 
         // Step 2: Attention-based aggregation
-        let attention_output = self
-            .attention
-            .forward(&node_msg, &neighbor_msgs, &neighbor_msgs);
+        let (attention_output, head_weights) =
+            self.attention
+                .forward_with_weights(&node_msg, &neighbor_msgs, &neighbor_msgs);
 
         // Step 3: Weighted aggregation using edge weights
         let weighted_msgs = self.aggregate_messages(&neighbor_msgs, edge_weights);
@@ -396,8 +451,16 @@ impl RuvectorLayer {
         // Step 6: Apply dropout (simplified - always apply scaling)
         let dropped = self.apply_dropout(&updated);
 
+        // Per-neighbor attention, averaged over heads
+        let mut attention = vec![0.0; neighbor_embeddings.len()];
+        for weights in &head_weights {
+            for (a, &w) in attention.iter_mut().zip(weights.iter()) {
+                *a += w / head_weights.len() as f32;
+            }
+        }
+
         // Step 7: Layer normalization
-        self.norm.forward(&dropped)
+        (self.norm.forward(&dropped), attention)
     }
 
     /// Aggregate neighbor messages with edge weights
@@ -406,13 +469,7 @@ impl RuvectorLayer {
             return vec![0.0; self.w_msg.output_dim()];
         }
 
-        // Normalize weights to sum to 1
-        let weight_sum: f32 = weights.iter().sum();
-        let normalized_weights: Vec<f32> = if weight_sum > 0.0 {
-            weights.iter().map(|&w| w / weight_sum).collect()
-        } else {
-            vec![1.0 / weights.len() as f32; weights.len()]
-        };
+        let normalized_weights = normalize_edge_weights(weights);
 
         // Weighted sum
         let dim = messages[0].len();
@@ -439,6 +496,17 @@ impl RuvectorLayer {
     }
 }
 
+/// Normalize edge weights to sum to 1, falling back to uniform weights
+/// when they sum to zero or less
+pub(crate) fn normalize_edge_weights(weights: &[f32]) -> Vec<f32> {
+    let weight_sum: f32 = weights.iter().sum();
+    if weight_sum > 0.0 {
+        weights.iter().map(|&w| w / weight_sum).collect()
+    } else {
+        vec![1.0 / weights.len() as f32; weights.len()]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -493,6 +561,9 @@ mod tests {
 
         let output = layer.forward(&node, &neighbors, &weights);
         assert_eq!(output.len(), 8);
+
+        let borrowed: Vec<&[f32]> = neighbors.iter().map(|n| n.as_slice()).collect();
+        assert_eq!(layer.forward_slices(&node, &borrowed, &weights), output);
     }
 
     #[test]
@@ -506,4 +577,21 @@ mod tests {
         let output = layer.forward(&node, &neighbors, &weights);
         assert_eq!(output.len(), 8);
     }
+
+    #[test]
+    fn test_attention_weights_sum_to_one() {
+        let layer = RuvectorLayer::new(4, 8, 2, 0.0);
+
+        let node = vec![1.0, 2.0, 3.0, 4.0];
+        let neighbors = vec![vec![0.5, 1.0, 1.5, 2.0], vec![2.0, 3.0, 4.0, 5.0]];
+        let weights = vec![0.3, 0.7];
+
+        let (output, attention) = layer.forward_with_attention(&node, &neighbors, &weights);
+        assert_eq!(output, layer.forward(&node, &neighbors, &weights));
+        assert_eq!(attention.len(), 2);
+        assert!((attention.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+
+        let (_, none) = layer.forward_with_attention(&node, &[], &[]);
+        assert!(none.is_empty());
+    }
 }
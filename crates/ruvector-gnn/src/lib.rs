@@ -46,11 +46,15 @@
 #![warn(missing_docs)]
 #![deny(unsafe_op_in_unsafe_fn)]
 
+pub mod agent_memory;
+pub mod artifact;
 pub mod compress;
 pub mod error;
 pub mod ewc;
+pub mod explain;
 pub mod layer;
 pub mod query;
+pub mod refresh;
 pub mod replay;
 pub mod scheduler;
 pub mod search;
@@ -61,21 +65,36 @@ pub mod training;
 pub mod mmap;
 
 // Re-export commonly used types
+pub use agent_memory::{
+    AgentMemory, ConsolidationConfig, ConsolidationReport, MemoryConfig, MemoryKind,
+    MemoryRecord, RecalledMemory,
+};
+pub use artifact::{Artifact, ArtifactHeader};
 pub use compress::{CompressedTensor, CompressionLevel, TensorCompress};
 pub use error::{GnnError, Result};
-pub use ewc::ElasticWeightConsolidation;
+pub use ewc::{ElasticWeightConsolidation, FisherConfig};
+pub use explain::{explain_node, NeighborAttribution, NeighborContext, ResultExplanation};
 pub use layer::RuvectorLayer;
-pub use query::{QueryMode, QueryResult, RuvectorQuery, SubGraph};
+pub use query::{GraphQuery, GraphStep, QueryMode, QueryResult, RuvectorQuery, SubGraph};
+pub use refresh::EmbeddingRefresher;
 pub use replay::{DistributionStats, ReplayBuffer, ReplayEntry};
 pub use scheduler::{LearningRateScheduler, SchedulerType};
-pub use search::{cosine_similarity, differentiable_search, hierarchical_forward};
+pub use search::{
+    cosine_similarity, differentiable_search, differentiable_search_db,
+    differentiable_search_stream, differentiable_search_v2, hierarchical_forward,
+    hierarchical_forward_slices, CandidateSet, PositionalEncoding, SearchOptions,
+};
 pub use training::{
-    info_nce_loss, local_contrastive_loss, sgd_step, Loss, LossType, OnlineConfig, Optimizer,
-    OptimizerType, TrainConfig,
+    clip_grad_norm, info_nce_loss, local_contrastive_loss, sgd_step, Loss, LossType,
+    MixedPrecisionAccumulator, OnlineConfig, Optimizer, OptimizerType, TrainConfig,
 };
 
 #[cfg(all(not(target_arch = "wasm32"), feature = "mmap"))]
 pub use mmap::{AtomicBitmap, MmapGradientAccumulator, MmapManager};
+#[cfg(all(unix, feature = "mmap"))]
+pub use mmap::SharedGradientAccumulator;
+#[cfg(all(not(target_arch = "wasm32"), feature = "mmap"))]
+pub use search::hierarchical_forward_mmap;
 
 #[cfg(test)]
 mod tests {
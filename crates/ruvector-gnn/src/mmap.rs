@@ -4,6 +4,8 @@
 //! that don't fit in RAM. It includes:
 //! - `MmapManager`: Memory-mapped embedding storage with dirty tracking
 //! - `MmapGradientAccumulator`: Lock-free gradient accumulation
+//! - `SharedGradientAccumulator`: Gradient accumulation shared by several
+//!   processes (Unix only)
 //! - `AtomicBitmap`: Thread-safe bitmap for access/dirty tracking
 //!
 //! Only available on non-WASM targets.
@@ -567,6 +569,352 @@ impl MmapGradientAccumulator {
     }
 }
 
+/// Magic bytes at the start of a shared gradient file
+#[cfg(unix)]
+const SHARED_GRAD_MAGIC: u64 = u64::from_le_bytes(*b"RVGRAD01");
+
+/// Size of the shared gradient file header
+#[cfg(unix)]
+const SHARED_GRAD_HEADER: usize = 64;
+
+/// Header of a shared gradient file, shared between processes.
+#[cfg(unix)]
+#[repr(C)]
+struct SharedGradHeader {
+    magic: AtomicU64,
+    d_embed: AtomicU64,
+    n_nodes: AtomicU64,
+    /// Number of completed `apply` rounds
+    generation: AtomicU64,
+    /// Number of attached accumulators
+    workers: AtomicU64,
+}
+
+/// Exclusive advisory lock on a whole file, held until dropped.
+///
+/// `flock` locks belong to the open file description, so they exclude other
+/// processes (and other handles in the same process) but not threads sharing
+/// one handle.
+#[cfg(unix)]
+struct FileLock<'a>(&'a File);
+
+#[cfg(unix)]
+impl<'a> FileLock<'a> {
+    fn exclusive(file: &'a File) -> io::Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        // Safety: flock only reads the descriptor, which `file` keeps open
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self(file))
+    }
+}
+
+#[cfg(unix)]
+impl Drop for FileLock<'_> {
+    fn drop(&mut self) {
+        use std::os::unix::io::AsRawFd;
+
+        // Safety: as above
+        unsafe {
+            libc::flock(self.0.as_raw_fd(), libc::LOCK_UN);
+        }
+    }
+}
+
+/// Gradient accumulator shared by several processes through one mmap file.
+///
+/// For data-parallel training on one host: each worker process opens the
+/// same file and accumulates into it concurrently, and one of them
+/// periodically applies the sum to the embeddings.
+///
+/// - Gradients are added with atomic compare-and-swap on the mapped memory,
+///   so accumulation takes no locks, across threads or processes.
+/// - A dirty bitmap in the file marks nodes with pending gradients. `apply`
+///   hands off the dirty set by atomically swapping bitmap words to zero,
+///   then takes each gradient with an atomic swap, so gradients added while
+///   applying are kept for the next round rather than lost.
+/// - An exclusive file lock serializes file initialization and `apply`
+///   rounds between processes.
+///
+/// File layout: a 64-byte header, the dirty bitmap (one `u64` per 64 nodes),
+/// then `max_nodes * d_embed` f32 gradients.
+#[cfg(unix)]
+pub struct SharedGradientAccumulator {
+    /// Shared mapping of the whole file
+    mmap: MmapMut,
+    /// Gradient file, used for file locks
+    file: File,
+    /// Serializes `apply` between threads of this process
+    apply_lock: parking_lot::Mutex<()>,
+    /// Number of nodes
+    n_nodes: usize,
+    /// Embedding dimension
+    d_embed: usize,
+    /// Byte offset of the gradients
+    grad_offset: usize,
+}
+
+#[cfg(unix)]
+impl SharedGradientAccumulator {
+    /// Open or create a shared gradient file.
+    ///
+    /// The first process creates and zeroes the file; later ones attach to
+    /// it and must pass the same dimensions.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the gradient file
+    /// * `d_embed` - Embedding dimension
+    /// * `max_nodes` - Maximum number of nodes
+    ///
+    /// # Returns
+    /// A new `SharedGradientAccumulator`, or an error if the file exists with
+    /// different dimensions
+    pub fn open(path: &Path, d_embed: usize, max_nodes: usize) -> Result<Self> {
+        let bitmap_bytes = (max_nodes + 63) / 64 * std::mem::size_of::<u64>();
+        let grad_offset = SHARED_GRAD_HEADER + bitmap_bytes;
+        let file_size = max_nodes
+            .checked_mul(d_embed)
+            .and_then(|n| n.checked_mul(std::mem::size_of::<f32>()))
+            .and_then(|n| n.checked_add(grad_offset))
+            .ok_or_else(|| GnnError::mmap("Gradient file size overflow"))?;
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(path)
+            .map_err(|e| GnnError::mmap(format!("Failed to open gradient file: {}", e)))?;
+
+        let _lock = FileLock::exclusive(&file)
+            .map_err(|e| GnnError::mmap(format!("Failed to lock gradient file: {}", e)))?;
+
+        let existing = file
+            .metadata()
+            .map_err(|e| GnnError::mmap(format!("Failed to stat gradient file: {}", e)))?
+            .len();
+        if existing == 0 {
+            // New file: set_len zero-fills the header, bitmap and gradients
+            file.set_len(file_size as u64)
+                .map_err(|e| GnnError::mmap(format!("Failed to set gradient file size: {}", e)))?;
+        } else if existing != file_size as u64 {
+            return Err(GnnError::mmap(format!(
+                "Gradient file has {} bytes, expected {}",
+                existing, file_size
+            )));
+        }
+
+        let mmap = unsafe {
+            MmapOptions::new()
+                .len(file_size)
+                .map_mut(&file)
+                .map_err(|e| GnnError::mmap(format!("Failed to create gradient mmap: {}", e)))?
+        };
+
+        // Safety: the mapping is page-aligned and at least the header long
+        let header = unsafe { &*(mmap.as_ptr() as *const SharedGradHeader) };
+        if existing == 0 {
+            header.d_embed.store(d_embed as u64, Ordering::Relaxed);
+            header.n_nodes.store(max_nodes as u64, Ordering::Relaxed);
+            header.magic.store(SHARED_GRAD_MAGIC, Ordering::Release);
+        } else if header.magic.load(Ordering::Acquire) != SHARED_GRAD_MAGIC
+            || header.d_embed.load(Ordering::Relaxed) != d_embed as u64
+            || header.n_nodes.load(Ordering::Relaxed) != max_nodes as u64
+        {
+            return Err(GnnError::mmap(
+                "Gradient file was created with different dimensions",
+            ));
+        }
+        header.workers.fetch_add(1, Ordering::AcqRel);
+
+        Ok(Self {
+            mmap,
+            // The lock guard borrows `file`, so keep a second handle; both
+            // share one file description and thus one flock
+            file: file.try_clone()?,
+            apply_lock: parking_lot::Mutex::new(()),
+            n_nodes: max_nodes,
+            d_embed,
+            grad_offset,
+        })
+    }
+
+    fn header(&self) -> &SharedGradHeader {
+        // Safety: the mapping is page-aligned and at least the header long
+        unsafe { &*(self.mmap.as_ptr() as *const SharedGradHeader) }
+    }
+
+    fn bitmap(&self) -> &[AtomicU64] {
+        // Safety: the bitmap follows the 64-byte header, so it is 8-aligned
+        unsafe {
+            std::slice::from_raw_parts(
+                self.mmap.as_ptr().add(SHARED_GRAD_HEADER) as *const AtomicU64,
+                (self.n_nodes + 63) / 64,
+            )
+        }
+    }
+
+    fn grad_cells(&self, node_id: u64) -> &[AtomicU32] {
+        assert!(
+            (node_id as usize) < self.n_nodes,
+            "node_id {} out of bounds (max: {})",
+            node_id,
+            self.n_nodes
+        );
+        let offset = self.grad_offset + node_id as usize * self.d_embed * 4;
+        // Safety: in bounds (checked above) and 4-aligned; f32 bits are
+        // only accessed atomically through this view
+        unsafe {
+            std::slice::from_raw_parts(
+                self.mmap.as_ptr().add(offset) as *const AtomicU32,
+                self.d_embed,
+            )
+        }
+    }
+
+    /// Accumulate gradients for a specific node.
+    ///
+    /// Safe to call concurrently from any number of threads and processes.
+    ///
+    /// # Panics
+    /// Panics if node_id is out of bounds or grad length doesn't match d_embed
+    pub fn accumulate(&self, node_id: u64, grad: &[f32]) {
+        assert_eq!(
+            grad.len(),
+            self.d_embed,
+            "Gradient length must match d_embed"
+        );
+
+        for (cell, &g) in self.grad_cells(node_id).iter().zip(grad) {
+            let _ = cell.fetch_update(Ordering::AcqRel, Ordering::Acquire, |bits| {
+                Some((f32::from_bits(bits) + g).to_bits())
+            });
+        }
+
+        // Mark dirty after adding, so a concurrent apply either takes this
+        // gradient or leaves the bit set for the next round
+        let node = node_id as usize;
+        self.bitmap()[node / 64].fetch_or(1u64 << (node % 64), Ordering::Release);
+    }
+
+    /// Read a node's pending gradient.
+    pub fn get_grad(&self, node_id: u64) -> Vec<f32> {
+        self.grad_cells(node_id)
+            .iter()
+            .map(|cell| f32::from_bits(cell.load(Ordering::Acquire)))
+            .collect()
+    }
+
+    /// Get the nodes with pending gradients.
+    pub fn dirty_nodes(&self) -> Vec<u64> {
+        let mut nodes = Vec::new();
+        for (word_idx, word) in self.bitmap().iter().enumerate() {
+            let mut w = word.load(Ordering::Acquire);
+            while w != 0 {
+                nodes.push((word_idx * 64 + w.trailing_zeros() as usize) as u64);
+                w &= w - 1;
+            }
+        }
+        nodes
+    }
+
+    /// Take all pending gradients, passing each dirty node's gradient to `f`.
+    ///
+    /// Only one process (and thread) drains at a time; the others block on
+    /// the file lock. Gradients accumulated meanwhile stay pending.
+    ///
+    /// # Returns
+    /// Number of nodes drained
+    pub fn drain<F: FnMut(u64, &[f32])>(&self, mut f: F) -> Result<usize> {
+        let _guard = self.apply_lock.lock();
+        let _lock = FileLock::exclusive(&self.file)
+            .map_err(|e| GnnError::mmap(format!("Failed to lock gradient file: {}", e)))?;
+
+        let mut grad = vec![0.0f32; self.d_embed];
+        let mut drained = 0;
+        for (word_idx, word) in self.bitmap().iter().enumerate() {
+            let mut w = word.swap(0, Ordering::AcqRel);
+            while w != 0 {
+                let node_id = (word_idx * 64 + w.trailing_zeros() as usize) as u64;
+                w &= w - 1;
+                for (g, cell) in grad.iter_mut().zip(self.grad_cells(node_id)) {
+                    *g = f32::from_bits(cell.swap(0, Ordering::AcqRel));
+                }
+                f(node_id, &grad);
+                drained += 1;
+            }
+        }
+
+        self.header().generation.fetch_add(1, Ordering::AcqRel);
+        Ok(drained)
+    }
+
+    /// Apply pending gradients to embeddings with gradient descent.
+    ///
+    /// Only dirty nodes are touched; their gradients are zeroed.
+    ///
+    /// # Returns
+    /// Number of nodes updated
+    pub fn apply(&self, learning_rate: f32, embeddings: &mut MmapManager) -> Result<usize> {
+        if self.d_embed != embeddings.d_embed {
+            return Err(GnnError::dimension_mismatch(
+                embeddings.d_embed.to_string(),
+                self.d_embed.to_string(),
+            ));
+        }
+
+        self.drain(|node_id, grad| {
+            if node_id as usize >= embeddings.max_nodes {
+                return;
+            }
+            let updated: Vec<f32> = embeddings
+                .get_embedding(node_id)
+                .iter()
+                .zip(grad)
+                .map(|(e, g)| e - learning_rate * g)
+                .collect();
+            embeddings.set_embedding(node_id, &updated);
+        })
+    }
+
+    /// Number of completed `drain`/`apply` rounds, across all processes.
+    ///
+    /// Workers can compare it against a value seen earlier to learn that the
+    /// embeddings were updated.
+    pub fn generation(&self) -> u64 {
+        self.header().generation.load(Ordering::Acquire)
+    }
+
+    /// Number of accumulators currently attached to the file.
+    pub fn workers(&self) -> u64 {
+        self.header().workers.load(Ordering::Acquire)
+    }
+
+    /// Flush the gradient file to disk.
+    pub fn flush(&self) -> Result<()> {
+        Ok(self.mmap.flush()?)
+    }
+
+    /// Get the embedding dimension.
+    pub fn d_embed(&self) -> usize {
+        self.d_embed
+    }
+
+    /// Get the number of nodes.
+    pub fn n_nodes(&self) -> usize {
+        self.n_nodes
+    }
+}
+
+#[cfg(unix)]
+impl Drop for SharedGradientAccumulator {
+    fn drop(&mut self) {
+        self.header().workers.fetch_sub(1, Ordering::AcqRel);
+        let _ = self.mmap.flush();
+    }
+}
+
 // Implement Drop to ensure proper cleanup
 impl Drop for MmapManager {
     fn drop(&mut self) {
@@ -914,4 +1262,60 @@ mod tests {
         let retrieved = manager.get_embedding(2);
         assert_eq!(retrieved[0], 2.0);
     }
+
+    #[test]
+    fn test_shared_accumulator_handles() {
+        use std::thread;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("shared_gradients.bin");
+
+        // Separate handles stand in for worker processes: each has its own
+        // file description and mapping
+        let first = std::sync::Arc::new(SharedGradientAccumulator::open(&path, 4, 100).unwrap());
+        let second = std::sync::Arc::new(SharedGradientAccumulator::open(&path, 4, 100).unwrap());
+        assert_eq!(first.workers(), 2);
+        assert!(SharedGradientAccumulator::open(&path, 8, 100).is_err());
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let acc = if i % 2 == 0 {
+                    first.clone()
+                } else {
+                    second.clone()
+                };
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        acc.accumulate(3, &[1.0, 0.5, 0.0, -1.0]);
+                    }
+                    acc.accumulate(70, &[1.0; 4]);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(second.get_grad(3), vec![800.0, 400.0, 0.0, -800.0]);
+        assert_eq!(first.dirty_nodes(), vec![3, 70]);
+
+        let emb_path = temp_dir.path().join("embeddings.bin");
+        let mut embeddings = MmapManager::new(&emb_path, 4, 100).unwrap();
+        embeddings.set_embedding(3, &[1.0; 4]);
+        assert_eq!(first.apply(0.001, &mut embeddings).unwrap(), 2);
+
+        let updated = embeddings.get_embedding(3);
+        assert!((updated[0] - 0.2).abs() < 1e-5);
+        assert!((updated[3] - 1.8).abs() < 1e-5);
+        assert!((embeddings.get_embedding(70)[0] + 0.008).abs() < 1e-6);
+
+        // The hand-off cleared the gradients for every handle
+        assert!(second.dirty_nodes().is_empty());
+        assert_eq!(second.get_grad(3), vec![0.0; 4]);
+        assert_eq!(second.generation(), 1);
+        assert_eq!(second.drain(|_, _| unreachable!()).unwrap(), 0);
+
+        drop(second);
+        assert_eq!(first.workers(), 1);
+    }
 }
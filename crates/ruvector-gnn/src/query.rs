@@ -1,9 +1,17 @@
 //! Query API for RuVector GNN
 //!
 //! Provides high-level query interfaces for vector search, neural search,
-//! and subgraph extraction.
-
+//! and subgraph extraction. [`GraphQuery`] builds multi-hop queries over the
+//! similarity graph of a [`VectorDB`] and returns the result as a
+//! [`SubGraph`].
+
+use crate::error::Result;
+use crate::explain::ResultExplanation;
+use ruvector_core::boost::similarity;
+use ruvector_core::types::{SearchQuery, VectorEntry};
+use ruvector_core::VectorDB;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 /// Query mode for different search strategies
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -37,7 +45,7 @@ pub struct RuvectorQuery {
     pub gnn_depth: usize,
     /// Temperature for differentiable search (higher = softer)
     pub temperature: f32,
-    /// Whether to return attention weights
+    /// Whether to return attention weights and per-result explanations
     pub return_attention: bool,
 }
 
@@ -175,6 +183,10 @@ pub struct SubGraph {
     pub nodes: Vec<u64>,
     /// Edges as (from, to, weight) tuples
     pub edges: Vec<(u64, u64, f32)>,
+    /// Database ids of the nodes, parallel to `nodes`, for subgraphs built
+    /// from a [`VectorDB`]; empty otherwise
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ids: Vec<String>,
 }
 
 impl SubGraph {
@@ -183,12 +195,23 @@ impl SubGraph {
         Self {
             nodes: Vec::new(),
             edges: Vec::new(),
+            ids: Vec::new(),
         }
     }
 
     /// Create subgraph with nodes and edges
     pub fn with_edges(nodes: Vec<u64>, edges: Vec<(u64, u64, f32)>) -> Self {
-        Self { nodes, edges }
+        Self {
+            nodes,
+            edges,
+            ids: Vec::new(),
+        }
+    }
+
+    /// Database id of `node_id`, for subgraphs built from a [`VectorDB`]
+    pub fn id_of(&self, node_id: u64) -> Option<&str> {
+        let i = self.nodes.iter().position(|&n| n == node_id)?;
+        self.ids.get(i).map(String::as_str)
     }
 
     /// Get number of nodes
@@ -233,6 +256,8 @@ pub struct QueryResult {
     pub embeddings: Option<Vec<Vec<f32>>>,
     /// Optional attention weights from differentiable search
     pub attention_weights: Option<Vec<Vec<f32>>>,
+    /// Optional per-result neighbor attributions, parallel to `nodes`
+    pub explanations: Option<Vec<ResultExplanation>>,
     /// Optional subgraph extraction
     pub subgraph: Option<SubGraph>,
     /// Query latency in milliseconds
@@ -247,6 +272,7 @@ impl QueryResult {
             scores: Vec::new(),
             embeddings: None,
             attention_weights: None,
+            explanations: None,
             subgraph: None,
             latency_ms: 0,
         }
@@ -271,6 +297,7 @@ impl QueryResult {
             scores,
             embeddings: None,
             attention_weights: None,
+            explanations: None,
             subgraph: None,
             latency_ms: 0,
         }
@@ -288,6 +315,18 @@ impl QueryResult {
         self
     }
 
+    /// Add per-result explanations, in the same order as `nodes`
+    pub fn with_explanations(mut self, explanations: Vec<ResultExplanation>) -> Self {
+        self.explanations = Some(explanations);
+        self
+    }
+
+    /// Explanation for `node_id`, if explanations were attached
+    pub fn explanation(&self, node_id: u64) -> Option<&ResultExplanation> {
+        let i = self.nodes.iter().position(|&n| n == node_id)?;
+        self.explanations.as_ref()?.get(i)
+    }
+
     /// Add subgraph to the result
     pub fn with_subgraph(mut self, subgraph: SubGraph) -> Self {
         self.subgraph = Some(subgraph);
@@ -318,6 +357,7 @@ impl QueryResult {
             scores: self.scores[..k].to_vec(),
             embeddings: self.embeddings.as_ref().map(|e| e[..k].to_vec()),
             attention_weights: self.attention_weights.as_ref().map(|a| a[..k].to_vec()),
+            explanations: self.explanations.as_ref().map(|e| e[..k].to_vec()),
             subgraph: self.subgraph.clone(),
             latency_ms: self.latency_ms,
         }
@@ -338,6 +378,7 @@ impl QueryResult {
         let mut filtered_scores = Vec::new();
         let mut filtered_embeddings = Vec::new();
         let mut filtered_attention = Vec::new();
+        let mut filtered_explanations = Vec::new();
 
         for i in 0..self.nodes.len() {
             if self.scores[i] >= min_score {
@@ -351,6 +392,10 @@ impl QueryResult {
                 if let Some(ref att) = self.attention_weights {
                     filtered_attention.push(att[i].clone());
                 }
+
+                if let Some(ref exp) = self.explanations {
+                    filtered_explanations.push(exp[i].clone());
+                }
             }
         }
 
@@ -365,6 +410,10 @@ impl QueryResult {
             self.attention_weights = Some(filtered_attention);
         }
 
+        if self.explanations.is_some() {
+            self.explanations = Some(filtered_explanations);
+        }
+
         self
     }
 }
@@ -375,6 +424,276 @@ impl Default for QueryResult {
     }
 }
 
+/// One step of a [`GraphQuery`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphStep {
+    /// Link each frontier node to its `k` nearest entries whose similarity
+    /// (see [`ruvector_core::boost::similarity`] for the database's metric)
+    /// is at least `min_similarity`; entries not yet in the subgraph become
+    /// the next frontier
+    Expand {
+        /// Neighbors searched per frontier node
+        k: usize,
+        /// Minimum similarity of an added edge
+        min_similarity: f32,
+    },
+    /// Drop nodes whose metadata lacks any of these key/value pairs
+    Filter(HashMap<String, serde_json::Value>),
+}
+
+/// Fluent builder for multi-hop queries over a [`VectorDB`]
+///
+/// A query starts from seed entries, given by id or found by a vector
+/// search, and applies its steps in order. The result is a [`SubGraph`]
+/// whose nodes are numbered by discovery order, with their database ids in
+/// [`SubGraph::ids`] and similarities as edge weights.
+///
+/// Queries are serializable, so bindings can accept them as JSON.
+///
+/// # Example
+/// ```no_run
+/// use ruvector_gnn::query::GraphQuery;
+/// # fn run(db: &ruvector_core::VectorDB) -> ruvector_gnn::Result<()> {
+/// let subgraph = GraphQuery::from_ids(["doc-1", "doc-2"])
+///     .expand_hops(2, 8, 0.8)
+///     .filter("lang", "en")
+///     .max_nodes(100)
+///     .execute(db)?;
+/// println!("{} nodes, {} edges", subgraph.node_count(), subgraph.edge_count());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphQuery {
+    /// Seed entry ids
+    pub seeds: Vec<String>,
+    /// Vector whose nearest entries are added as seeds
+    pub seed_vector: Option<Vec<f32>>,
+    /// Number of seeds taken from `seed_vector`
+    pub seed_k: usize,
+    /// Steps applied in order
+    pub steps: Vec<GraphStep>,
+    /// Maximum number of nodes in the result
+    pub max_nodes: Option<usize>,
+}
+
+impl GraphQuery {
+    /// Start from the entries with these ids
+    pub fn from_ids<I, S>(ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            seeds: ids.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Start from the `k` entries nearest to `vector`
+    pub fn from_vector(vector: Vec<f32>, k: usize) -> Self {
+        Self {
+            seed_vector: Some(vector),
+            seed_k: k,
+            ..Default::default()
+        }
+    }
+
+    /// Expand one hop; see [`GraphStep::Expand`]
+    pub fn expand(mut self, k: usize, min_similarity: f32) -> Self {
+        self.steps.push(GraphStep::Expand { k, min_similarity });
+        self
+    }
+
+    /// Expand `hops` hops
+    pub fn expand_hops(mut self, hops: usize, k: usize, min_similarity: f32) -> Self {
+        for _ in 0..hops {
+            self = self.expand(k, min_similarity);
+        }
+        self
+    }
+
+    /// Keep only nodes with metadata `key` equal to `value`
+    ///
+    /// Consecutive filters combine into one step.
+    pub fn filter(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        if let Some(GraphStep::Filter(conditions)) = self.steps.last_mut() {
+            conditions.insert(key.to_string(), value.into());
+        } else {
+            let mut conditions = HashMap::new();
+            conditions.insert(key.to_string(), value.into());
+            self.steps.push(GraphStep::Filter(conditions));
+        }
+        self
+    }
+
+    /// Stop adding nodes once the subgraph has `max_nodes`
+    pub fn max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    /// Run the query against `db`
+    ///
+    /// Seed ids that are not in the database are skipped.
+    pub fn execute(&self, db: &VectorDB) -> Result<SubGraph> {
+        let metric = db.options().distance_metric;
+        let mut graph = GraphBuilder::new(self.max_nodes.unwrap_or(usize::MAX));
+
+        for id in &self.seeds {
+            if let Some(entry) = db.get(id)? {
+                graph.add(entry);
+            }
+        }
+        if let Some(vector) = &self.seed_vector {
+            let hits = db.search(SearchQuery {
+                vector: vector.clone(),
+                k: self.seed_k,
+                filter: None,
+                ef_search: None,
+            })?;
+            for hit in hits {
+                if let Some(entry) = graph.lookup(db, &hit.id)? {
+                    graph.add(entry);
+                }
+            }
+        }
+
+        let mut frontier: Vec<usize> = (0..graph.entries.len()).collect();
+        for step in &self.steps {
+            match step {
+                GraphStep::Expand { k, min_similarity } => {
+                    let mut next = Vec::new();
+                    for &from in &frontier {
+                        if !graph.alive[from] {
+                            continue;
+                        }
+                        let hits = db.search(SearchQuery {
+                            vector: graph.entries[from].vector.clone(),
+                            k: k + 1,
+                            filter: None,
+                            ef_search: None,
+                        })?;
+                        for hit in hits {
+                            let similarity = similarity(metric, hit.score);
+                            if similarity < *min_similarity {
+                                continue;
+                            }
+                            let to = match graph.index.get(&hit.id) {
+                                Some(&to) => to,
+                                None => {
+                                    let entry = graph.lookup(db, &hit.id)?;
+                                    match entry.and_then(|e| graph.add(e)) {
+                                        Some(to) => {
+                                            next.push(to);
+                                            to
+                                        }
+                                        None => continue,
+                                    }
+                                }
+                            };
+                            graph.link(from, to, similarity);
+                        }
+                    }
+                    frontier = next;
+                }
+                GraphStep::Filter(conditions) => {
+                    for (node, entry) in graph.entries.iter().enumerate() {
+                        let matches = conditions.iter().all(|(key, value)| {
+                            entry.metadata.as_ref().and_then(|m| m.get(key)) == Some(value)
+                        });
+                        if !matches {
+                            graph.alive[node] = false;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(graph.finish())
+    }
+}
+
+/// Subgraph under construction by [`GraphQuery::execute`]
+struct GraphBuilder {
+    entries: Vec<VectorEntry>,
+    index: HashMap<String, usize>,
+    alive: Vec<bool>,
+    edges: Vec<(usize, usize, f32)>,
+    linked: HashSet<(usize, usize)>,
+    max_nodes: usize,
+}
+
+impl GraphBuilder {
+    fn new(max_nodes: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            index: HashMap::new(),
+            alive: Vec::new(),
+            edges: Vec::new(),
+            linked: HashSet::new(),
+            max_nodes,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.entries.len() >= self.max_nodes
+    }
+
+    /// Fetch an entry that can become a node: stored and not a centroid
+    fn lookup(&self, db: &VectorDB, id: &str) -> Result<Option<VectorEntry>> {
+        Ok(db
+            .get(id)?
+            .filter(|entry| !ruvector_core::centroid::is_centroid(entry)))
+    }
+
+    /// Add an entry unless present or full, returning its node
+    fn add(&mut self, entry: VectorEntry) -> Option<usize> {
+        let id = entry.id.clone()?;
+        if let Some(&node) = self.index.get(&id) {
+            return Some(node);
+        }
+        if self.is_full() {
+            return None;
+        }
+        let node = self.entries.len();
+        self.index.insert(id, node);
+        self.entries.push(entry);
+        self.alive.push(true);
+        Some(node)
+    }
+
+    fn link(&mut self, from: usize, to: usize, weight: f32) {
+        if from != to && self.linked.insert((from, to)) {
+            self.edges.push((from, to, weight));
+        }
+    }
+
+    /// Renumber the surviving nodes and drop edges to removed ones
+    fn finish(self) -> SubGraph {
+        let mut position = vec![None; self.entries.len()];
+        let mut ids = Vec::new();
+        for (node, entry) in self.entries.into_iter().enumerate() {
+            if self.alive[node] {
+                position[node] = Some(ids.len() as u64);
+                ids.push(entry.id.unwrap_or_default());
+            }
+        }
+        let edges = self
+            .edges
+            .into_iter()
+            .filter_map(|(from, to, w)| Some((position[from]?, position[to]?, w)))
+            .collect();
+        SubGraph {
+            nodes: (0..ids.len() as u64).collect(),
+            edges,
+            ids,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -667,4 +986,132 @@ mod tests {
             QueryMode::DifferentiableSearch
         );
     }
+
+    #[test]
+    fn test_explanations_follow_filtering() {
+        let explanation = |node: u64, score: f32| ResultExplanation {
+            node,
+            score,
+            contributors: Vec::new(),
+        };
+        let result =
+            QueryResult::with_nodes(vec![1, 2, 3], vec![0.9, 0.5, 0.8]).with_explanations(vec![
+                explanation(1, 0.9),
+                explanation(2, 0.5),
+                explanation(3, 0.8),
+            ]);
+
+        assert_eq!(result.explanation(3).map(|e| e.node), Some(3));
+        assert_eq!(result.top_k(1).explanations.unwrap().len(), 1);
+
+        let filtered = result.filter_by_score(0.7);
+        assert!(filtered.explanation(2).is_none());
+        assert_eq!(filtered.explanation(3).map(|e| e.score), Some(0.8));
+    }
+
+    #[test]
+    fn test_graph_query() {
+        use ruvector_core::types::{DbOptions, DistanceMetric};
+
+        let dir = tempfile::tempdir().unwrap();
+        let db = VectorDB::new(DbOptions {
+            dimensions: 3,
+            distance_metric: DistanceMetric::Cosine,
+            storage_path: dir.path().join("graph.db").to_string_lossy().to_string(),
+            hnsw_config: None,
+            quantization: None,
+        })
+        .unwrap();
+        // A chain a - b - c, with d far from all of them
+        let vectors = [
+            ("a", [1.0, 0.0, 0.0], "en"),
+            ("b", [0.9, 0.3, 0.0], "en"),
+            ("c", [0.8, 0.6, 0.0], "de"),
+            ("d", [0.0, 0.0, 1.0], "en"),
+        ];
+        for (id, vector, lang) in vectors {
+            let mut metadata = HashMap::new();
+            metadata.insert("lang".to_string(), serde_json::json!(lang));
+            db.insert(VectorEntry {
+                id: Some(id.to_string()),
+                vector: vector.to_vec(),
+                metadata: Some(metadata),
+            })
+            .unwrap();
+        }
+
+        let one_hop = GraphQuery::from_ids(["a"])
+            .expand(3, 0.9)
+            .execute(&db)
+            .unwrap();
+        assert_eq!(one_hop.ids, ["a", "b"]);
+        assert_eq!(one_hop.edge_count(), 1);
+        assert_eq!(one_hop.id_of(one_hop.edges[0].1), Some("b"));
+
+        let two_hops = GraphQuery::from_ids(["a", "missing"])
+            .expand_hops(2, 3, 0.9)
+            .execute(&db)
+            .unwrap();
+        assert_eq!(two_hops.ids, ["a", "b", "c"]);
+
+        let filtered = GraphQuery::from_vector(vec![1.0, 0.1, 0.0], 1)
+            .expand_hops(2, 3, 0.9)
+            .filter("lang", "en")
+            .execute(&db)
+            .unwrap();
+        assert_eq!(filtered.ids, ["a", "b"]);
+        assert!(filtered
+            .edges
+            .iter()
+            .all(|&(from, to, _)| from < 2 && to < 2));
+
+        let capped = GraphQuery::from_ids(["a"])
+            .expand_hops(2, 3, 0.0)
+            .max_nodes(2)
+            .execute(&db)
+            .unwrap();
+        assert_eq!(capped.node_count(), 2);
+
+        // Queries round-trip through JSON for the bindings
+        let query = GraphQuery::from_ids(["a"])
+            .expand(2, 0.5)
+            .filter("lang", "en");
+        let json = serde_json::to_string(&query).unwrap();
+        assert_eq!(serde_json::from_str::<GraphQuery>(&json).unwrap(), query);
+    }
+
+    #[test]
+    fn test_graph_query_euclidean_similarity() {
+        use ruvector_core::types::{DbOptions, DistanceMetric};
+
+        let dir = tempfile::tempdir().unwrap();
+        let db = VectorDB::new(DbOptions {
+            dimensions: 3,
+            distance_metric: DistanceMetric::Euclidean,
+            storage_path: dir.path().join("graph.db").to_string_lossy().to_string(),
+            hnsw_config: None,
+            quantization: None,
+        })
+        .unwrap();
+        for (id, vector) in [
+            ("a", [0.0, 0.0, 0.0]),
+            ("b", [1.0, 0.0, 0.0]),
+            ("c", [5.0, 0.0, 0.0]),
+        ] {
+            db.insert(VectorEntry {
+                id: Some(id.to_string()),
+                vector: vector.to_vec(),
+                metadata: None,
+            })
+            .unwrap();
+        }
+
+        // Distance 1 is similarity 1/2, distance 5 is 1/6
+        let graph = GraphQuery::from_ids(["a"])
+            .expand(3, 0.4)
+            .execute(&db)
+            .unwrap();
+        assert_eq!(graph.ids, ["a", "b"]);
+        assert!((graph.edges[0].2 - 0.5).abs() < 1e-5);
+    }
 }
@@ -0,0 +1,249 @@
+//! Incremental GNN embedding refresh
+//!
+//! A GNN-augmented embedding is the output of a [`RuvectorLayer`] over a
+//! vector and its HNSW neighbors, so inserting a vector changes the
+//! augmented embeddings of the nodes around it as well as its own. Instead
+//! of recomputing every node after each ingest batch, [`EmbeddingRefresher`]
+//! marks the changed ids and their neighborhoods, up to a hop limit, dirty
+//! and recomputes only those.
+//!
+//! ```rust,ignore
+//! let mut refresher = EmbeddingRefresher::new(layer, 1);
+//! refresher.mark_all(&db)?;
+//! refresher.refresh(&db)?;
+//!
+//! let ids = db.insert_batch(entries)?;
+//! refresher.mark_changed(&db, &ids)?;
+//! refresher.refresh(&db)?;
+//! ```
+
+use crate::error::Result;
+use crate::layer::RuvectorLayer;
+use ruvector_core::types::VectorId;
+use ruvector_core::VectorDB;
+use std::collections::{HashMap, HashSet};
+
+/// Cache of GNN-augmented embeddings kept current with a [`VectorDB`]
+///
+/// Neighborhoods are read from the database's HNSW graph, so the database
+/// must use a graph-based index.
+#[derive(Debug, Clone)]
+pub struct EmbeddingRefresher {
+    /// Layer producing the augmented embeddings
+    layer: RuvectorLayer,
+    /// Hops around a changed node whose embeddings are recomputed
+    hop_limit: usize,
+    /// Augmented embeddings by vector id
+    embeddings: HashMap<VectorId, Vec<f32>>,
+    /// Nodes whose embeddings are missing or stale
+    dirty: HashSet<VectorId>,
+}
+
+impl EmbeddingRefresher {
+    /// Create an empty refresher
+    ///
+    /// # Arguments
+    /// * `layer` - Layer applied to each vector and its neighbors
+    /// * `hop_limit` - How far a change propagates; 1 matches a single
+    ///   layer, stacked layers need one hop per layer
+    pub fn new(layer: RuvectorLayer, hop_limit: usize) -> Self {
+        Self {
+            layer,
+            hop_limit,
+            embeddings: HashMap::new(),
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Layer producing the augmented embeddings
+    pub fn layer(&self) -> &RuvectorLayer {
+        &self.layer
+    }
+
+    /// Hops a change propagates
+    pub fn hop_limit(&self) -> usize {
+        self.hop_limit
+    }
+
+    /// Mark inserted or updated vectors and their neighborhoods dirty
+    ///
+    /// Ids no longer in the database are evicted; mark a deleted vector's
+    /// neighbors before deleting it for them to be refreshed too.
+    ///
+    /// # Returns
+    /// Number of nodes that became dirty
+    pub fn mark_changed(&mut self, db: &VectorDB, ids: &[VectorId]) -> Result<usize> {
+        let mut live = Vec::with_capacity(ids.len());
+        for id in ids {
+            if db.get(id)?.is_some() {
+                live.push(id.clone());
+            } else {
+                self.embeddings.remove(id);
+                self.dirty.remove(id);
+            }
+        }
+        if live.is_empty() {
+            return Ok(0);
+        }
+
+        let affected = db.subgraph(&live, self.hop_limit, usize::MAX)?;
+        let before = self.dirty.len();
+        self.dirty.extend(affected.nodes);
+        Ok(self.dirty.len() - before)
+    }
+
+    /// Mark every vector in the database dirty, for a full rebuild
+    pub fn mark_all(&mut self, db: &VectorDB) -> Result<usize> {
+        let ids: HashSet<VectorId> = db.keys()?.into_iter().collect();
+        self.embeddings.retain(|id, _| ids.contains(id));
+        let before = self.dirty.len();
+        self.dirty.extend(ids);
+        Ok(self.dirty.len() - before)
+    }
+
+    /// Recompute every dirty embedding
+    ///
+    /// # Returns
+    /// Number of embeddings recomputed
+    pub fn refresh(&mut self, db: &VectorDB) -> Result<usize> {
+        self.refresh_batch(db, usize::MAX)
+    }
+
+    /// Recompute at most `limit` dirty embeddings, to spread the work over
+    /// several ingest batches
+    ///
+    /// Nodes that fail to refresh stay dirty.
+    pub fn refresh_batch(&mut self, db: &VectorDB, limit: usize) -> Result<usize> {
+        let batch: Vec<VectorId> = self.dirty.iter().take(limit).cloned().collect();
+        let mut refreshed = 0;
+        for id in batch {
+            match self.compute(db, &id)? {
+                Some(embedding) => {
+                    self.embeddings.insert(id.clone(), embedding);
+                    refreshed += 1;
+                }
+                None => {
+                    self.embeddings.remove(&id);
+                }
+            }
+            self.dirty.remove(&id);
+        }
+        Ok(refreshed)
+    }
+
+    /// Augmented embedding of `id`, possibly stale if it is dirty
+    pub fn get(&self, id: &str) -> Option<&[f32]> {
+        self.embeddings.get(id).map(Vec::as_slice)
+    }
+
+    /// Iterate over all cached embeddings
+    pub fn embeddings(&self) -> impl Iterator<Item = (&VectorId, &[f32])> {
+        self.embeddings.iter().map(|(id, e)| (id, e.as_slice()))
+    }
+
+    /// Whether `id` awaits a refresh
+    pub fn is_dirty(&self, id: &str) -> bool {
+        self.dirty.contains(id)
+    }
+
+    /// Number of nodes awaiting a refresh
+    pub fn dirty_count(&self) -> usize {
+        self.dirty.len()
+    }
+
+    /// Number of cached embeddings
+    pub fn len(&self) -> usize {
+        self.embeddings.len()
+    }
+
+    /// Check if no embedding is cached
+    pub fn is_empty(&self) -> bool {
+        self.embeddings.is_empty()
+    }
+
+    /// Run the layer over `id` and its level-0 neighbors, weighting each
+    /// neighbor by `1 / (1 + distance)`; `None` if `id` was deleted
+    fn compute(&self, db: &VectorDB, id: &str) -> Result<Option<Vec<f32>>> {
+        let Some(entry) = db.get(id)? else {
+            return Ok(None);
+        };
+        let mut neighbors = Vec::new();
+        let mut edge_weights = Vec::new();
+        for neighbor in db.neighbors(id, 0)? {
+            let Some(neighbor_id) = neighbor.id else {
+                continue;
+            };
+            if let Some(neighbor_entry) = db.get(&neighbor_id)? {
+                neighbors.push(neighbor_entry.vector);
+                edge_weights.push(1.0 / (1.0 + neighbor.distance));
+            }
+        }
+        Ok(Some(self.layer.forward(
+            &entry.vector,
+            &neighbors,
+            &edge_weights,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruvector_core::types::{DbOptions, DistanceMetric, HnswConfig, VectorEntry};
+
+    #[test]
+    fn test_incremental_refresh() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = VectorDB::new(DbOptions {
+            dimensions: 4,
+            distance_metric: DistanceMetric::Euclidean,
+            storage_path: dir.path().join("refresh.db").to_string_lossy().to_string(),
+            hnsw_config: Some(HnswConfig {
+                m: 4,
+                ..Default::default()
+            }),
+            quantization: None,
+        })
+        .unwrap();
+        let insert = |i: usize| {
+            db.insert(VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: vec![i as f32, 1.0, 0.5, (i % 3) as f32],
+                metadata: None,
+            })
+            .unwrap()
+        };
+        for i in 0..60 {
+            insert(i);
+        }
+
+        let mut refresher = EmbeddingRefresher::new(RuvectorLayer::new(4, 8, 2, 0.0), 1);
+        assert_eq!(refresher.mark_all(&db).unwrap(), 60);
+        assert_eq!(refresher.refresh(&db).unwrap(), 60);
+        assert_eq!(refresher.len(), 60);
+        assert_eq!(refresher.dirty_count(), 0);
+        assert_eq!(refresher.get("v0").unwrap().len(), 8);
+
+        // Only the new node and its neighbors are recomputed
+        let id = insert(60);
+        let marked = refresher.mark_changed(&db, &[id.clone()]).unwrap();
+        assert!(marked > 1 && marked < 20);
+        assert!(refresher.is_dirty(&id));
+        for neighbor in db.neighbors(&id, 0).unwrap() {
+            assert!(refresher.is_dirty(&neighbor.id.unwrap()));
+        }
+        assert_eq!(refresher.refresh(&db).unwrap(), marked);
+        assert_eq!(refresher.len(), 61);
+
+        // A refreshed node matches a full recomputation
+        let mut full = EmbeddingRefresher::new(refresher.layer().clone(), 1);
+        full.mark_all(&db).unwrap();
+        full.refresh(&db).unwrap();
+        assert_eq!(full.get(&id), refresher.get(&id));
+
+        // Deleted nodes are evicted
+        db.delete(&id).unwrap();
+        assert_eq!(refresher.mark_changed(&db, &[id.clone()]).unwrap(), 0);
+        assert!(refresher.get(&id).is_none());
+    }
+}
@@ -46,6 +46,39 @@ pub enum SchedulerType {
         patience: usize,
         min_lr: f32,
     },
+
+    /// Linear warmup from 0 to base_lr over warmup_steps, then constant
+    Warmup {
+        warmup_steps: usize,
+    },
+
+    /// One-cycle policy: cosine ramp from base_lr / div_factor up to base_lr
+    /// over the first pct_start of total_steps, then cosine annealing down to
+    /// base_lr / (div_factor * final_div_factor)
+    OneCycle {
+        total_steps: usize,
+        pct_start: f32,
+        div_factor: f32,
+        final_div_factor: f32,
+    },
+
+    /// Cosine annealing with warm restarts (SGDR)
+    /// The first cycle lasts t_0 steps and each following cycle t_mult times
+    /// as long as the one before
+    /// Formula: lr = eta_min + 0.5 * (base_lr - eta_min) * (1 + cos(pi * t_cur / t_i))
+    Sgdr {
+        t_0: usize,
+        t_mult: usize,
+        eta_min: f32,
+    },
+
+    /// Run schedules one after another, e.g. warmup -> cosine -> plateau
+    /// Each phase runs for its step count, counted from 0 when it starts;
+    /// the last phase runs indefinitely. A ReduceOnPlateau phase starts
+    /// from the learning rate the previous phase ended at.
+    Chain {
+        phases: Vec<(SchedulerType, usize)>,
+    },
 }
 
 /// Learning rate scheduler for GNN training
@@ -113,7 +146,7 @@ impl LearningRateScheduler {
     pub fn step_with_metric(&mut self, metric: f32) -> f32 {
         self.step_count += 1;
 
-        match &self.scheduler_type {
+        match Self::active_type(&self.scheduler_type, self.step_count).0 {
             SchedulerType::ReduceOnPlateau { factor, patience, min_lr } => {
                 // Check if metric improved
                 if metric < self.best_metric - 1e-8 {
@@ -153,32 +186,53 @@ impl LearningRateScheduler {
 
     /// Calculates the learning rate based on the current step and scheduler type
     fn calculate_lr(&self) -> f32 {
-        match &self.scheduler_type {
+        let (scheduler_type, step) = Self::active_type(&self.scheduler_type, self.step_count);
+        self.lr_at(scheduler_type, step)
+    }
+
+    /// Resolves chains to the phase active at `step`, and the step within it
+    fn active_type(scheduler_type: &SchedulerType, step: usize) -> (&SchedulerType, usize) {
+        let SchedulerType::Chain { phases } = scheduler_type else {
+            return (scheduler_type, step);
+        };
+        let mut local = step;
+        for (i, (phase, steps)) in phases.iter().enumerate() {
+            if local < *steps || i + 1 == phases.len() {
+                return Self::active_type(phase, local);
+            }
+            local -= steps;
+        }
+        (&SchedulerType::Constant, local)
+    }
+
+    /// Calculates the learning rate of a (non-chain) schedule at `step`
+    fn lr_at(&self, scheduler_type: &SchedulerType, step: usize) -> f32 {
+        match scheduler_type {
             SchedulerType::Constant => self.base_lr,
 
             SchedulerType::StepDecay { step_size, gamma } => {
-                let decay_factor = (*gamma).powi((self.step_count / step_size) as i32);
+                let decay_factor = (*gamma).powi((step / step_size) as i32);
                 self.base_lr * decay_factor
             }
 
             SchedulerType::Exponential { gamma } => {
-                let decay_factor = (*gamma).powi(self.step_count as i32);
+                let decay_factor = (*gamma).powi(step as i32);
                 self.base_lr * decay_factor
             }
 
             SchedulerType::CosineAnnealing { t_max, eta_min } => {
-                let cycle_step = self.step_count % t_max;
+                let cycle_step = step % t_max;
                 let cos_term = (PI * cycle_step as f32 / *t_max as f32).cos();
                 eta_min + 0.5 * (self.base_lr - eta_min) * (1.0 + cos_term)
             }
 
             SchedulerType::WarmupLinear { warmup_steps, total_steps } => {
-                if self.step_count < *warmup_steps {
+                if step < *warmup_steps {
                     // Warmup phase: linear increase
-                    self.base_lr * (self.step_count as f32 / *warmup_steps as f32)
-                } else if self.step_count < *total_steps {
+                    self.base_lr * (step as f32 / *warmup_steps as f32)
+                } else if step < *total_steps {
                     // Decay phase: linear decrease
-                    let remaining_steps = *total_steps - self.step_count;
+                    let remaining_steps = *total_steps - step;
                     let total_decay_steps = *total_steps - *warmup_steps;
                     self.base_lr * (remaining_steps as f32 / total_decay_steps as f32)
                 } else {
@@ -191,10 +245,53 @@ impl LearningRateScheduler {
                 // For plateau scheduler, lr is updated in step_with_metric
                 self.current_lr
             }
+
+            SchedulerType::Warmup { warmup_steps } => {
+                self.base_lr * (step as f32 / (*warmup_steps).max(1) as f32).min(1.0)
+            }
+
+            SchedulerType::OneCycle {
+                total_steps,
+                pct_start,
+                div_factor,
+                final_div_factor,
+            } => {
+                let initial_lr = self.base_lr / div_factor;
+                let min_lr = initial_lr / final_div_factor;
+                let up_steps = (pct_start * *total_steps as f32).max(1.0);
+                let down_steps = (*total_steps as f32 - up_steps).max(1.0);
+                let step = step as f32;
+                if step <= up_steps {
+                    cosine_anneal(initial_lr, self.base_lr, step / up_steps)
+                } else {
+                    cosine_anneal(self.base_lr, min_lr, ((step - up_steps) / down_steps).min(1.0))
+                }
+            }
+
+            SchedulerType::Sgdr { t_0, t_mult, eta_min } => {
+                // Find the position within the current restart cycle
+                let mut t_i = (*t_0).max(1);
+                let mut t_cur = step;
+                while t_cur >= t_i {
+                    t_cur -= t_i;
+                    t_i *= (*t_mult).max(1);
+                }
+                cosine_anneal(self.base_lr, *eta_min, t_cur as f32 / t_i as f32)
+            }
+
+            SchedulerType::Chain { .. } => {
+                let (phase, local) = Self::active_type(scheduler_type, step);
+                self.lr_at(phase, local)
+            }
         }
     }
 }
 
+/// Cosine interpolation from `start` (pct = 0) to `end` (pct = 1)
+fn cosine_anneal(start: f32, end: f32, pct: f32) -> f32 {
+    end + 0.5 * (start - end) * (1.0 + (PI * pct).cos())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -488,4 +585,98 @@ mod tests {
         assert!(scheduler.get_lr() > 0.0, "LR should remain positive");
         assert!(scheduler.get_lr() < 1e-8, "LR should be very small");
     }
+
+    #[test]
+    fn test_one_cycle() {
+        let mut scheduler = LearningRateScheduler::new(
+            SchedulerType::OneCycle {
+                total_steps: 100,
+                pct_start: 0.3,
+                div_factor: 25.0,
+                final_div_factor: 1e4,
+            },
+            0.1,
+        );
+
+        let lrs: Vec<f32> = (0..100).map(|_| scheduler.step()).collect();
+
+        // Ramps up to the peak at 30% of the cycle, then anneals down
+        assert!(lrs[0] > 0.1 / 25.0 && lrs[0] < 0.01);
+        assert_close(lrs[29], 0.1, "Peak LR");
+        assert!(lrs[..29].windows(2).all(|w| w[0] < w[1]));
+        assert!(lrs[29..].windows(2).all(|w| w[0] > w[1]));
+        assert_close(lrs[99], 0.1 / 25.0 / 1e4, "Final LR");
+
+        // Stays at the minimum after the cycle
+        assert_close(scheduler.step(), 0.1 / 25.0 / 1e4, "LR after cycle");
+    }
+
+    #[test]
+    fn test_sgdr() {
+        let mut scheduler = LearningRateScheduler::new(
+            SchedulerType::Sgdr {
+                t_0: 4,
+                t_mult: 2,
+                eta_min: 0.0,
+            },
+            1.0,
+        );
+
+        let lrs: Vec<f32> = (0..13).map(|_| scheduler.step()).collect();
+
+        // First cycle: steps 1-3, restart at step 4
+        assert_close(lrs[1], 0.5, "Mid first cycle");
+        assert_close(lrs[3], 1.0, "First restart");
+        // Second cycle is twice as long: restart at step 12
+        assert_close(lrs[7], 0.5, "Mid second cycle");
+        assert!(lrs[10] < 0.1);
+        assert_close(lrs[11], 1.0, "Second restart");
+    }
+
+    #[test]
+    fn test_chain() {
+        let mut scheduler = LearningRateScheduler::new(
+            SchedulerType::Chain {
+                phases: vec![
+                    (SchedulerType::Warmup { warmup_steps: 4 }, 4),
+                    (
+                        SchedulerType::CosineAnnealing {
+                            t_max: 8,
+                            eta_min: 0.1,
+                        },
+                        8,
+                    ),
+                    (
+                        SchedulerType::ReduceOnPlateau {
+                            factor: 0.5,
+                            patience: 2,
+                            min_lr: 0.01,
+                        },
+                        0,
+                    ),
+                ],
+            },
+            1.0,
+        );
+
+        // Warmup
+        assert_close(scheduler.step(), 0.25, "Warmup step 1");
+        scheduler.step();
+        assert_close(scheduler.step(), 0.75, "Warmup step 3");
+
+        // Cosine starts at base_lr and anneals toward eta_min
+        assert_close(scheduler.step(), 1.0, "Cosine start");
+        let mut lr = 1.0;
+        for _ in 0..7 {
+            let next = scheduler.step_with_metric(1.0);
+            assert!(next < lr);
+            lr = next;
+        }
+        assert!(lr > 0.1 && lr < 0.2);
+
+        // Plateau continues from where cosine ended
+        assert_close(scheduler.step_with_metric(1.0), lr, "Plateau start");
+        scheduler.step_with_metric(1.0);
+        assert_close(scheduler.step_with_metric(1.0), lr * 0.5, "Plateau reduction");
+    }
 }
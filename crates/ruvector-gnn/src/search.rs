@@ -1,4 +1,18 @@
+//! Differentiable search
+//!
+//! [`differentiable_search`] scores a list of candidate vectors against a
+//! query. [`differentiable_search_v2`] does the same over a [`CandidateSet`],
+//! which keeps the candidates in one contiguous buffer with precomputed
+//! norms and, with the `f16` feature, can store them in half precision.
+//! [`differentiable_search_stream`] scores candidates straight from an
+//! iterator, and [`differentiable_search_db`] from a [`VectorDB`] search.
+
+use crate::error::{GnnError, Result};
 use crate::layer::RuvectorLayer;
+use ruvector_core::types::{SearchQuery, VectorId};
+use ruvector_core::VectorDB;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
 
 /// Compute cosine similarity between two vectors with improved precision
 pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
@@ -85,6 +99,729 @@ pub fn differentiable_search(
     (indices, weights)
 }
 
+/// Candidate embeddings stored contiguously for [`differentiable_search_v2`]
+///
+/// Search over a large candidate set is bound by memory bandwidth. Rows are
+/// kept back to back with their norms precomputed, and with the `f16`
+/// feature they can be stored in half precision, halving the bytes read per
+/// search. Scores are accumulated in f32 either way.
+#[derive(Debug, Clone)]
+pub struct CandidateSet {
+    /// Row width
+    dim: usize,
+    /// Row data
+    rows: CandidateRows,
+    /// Euclidean norm of each row
+    norms: Vec<f32>,
+    /// Rows per block; 0 when blocks are not indexed
+    block_size: usize,
+    /// Bounds for each complete block of `block_size` rows
+    blocks: Vec<CandidateBlock>,
+}
+
+/// Similarity bound for a contiguous block of rows
+///
+/// Every non-zero row in the block is within `radius` radians of the unit
+/// `centroid`, so a query at angle `φ` from the centroid has cosine at most
+/// `cos(φ - radius)` with any of them.
+#[derive(Debug, Clone)]
+struct CandidateBlock {
+    centroid: Vec<f32>,
+    radius: f32,
+    /// Whether the block holds a zero row, whose cosine is always 0
+    has_zero: bool,
+}
+
+impl CandidateBlock {
+    /// Upper bound on the cosine between a query and any row in the block
+    fn bound(&self, query: &[f32], query_norm: f32) -> f32 {
+        if query_norm == 0.0 {
+            return 0.0;
+        }
+        let cos: f32 = self.centroid.iter().zip(query).map(|(c, q)| c * q).sum();
+        let angle = (cos / query_norm).clamp(-1.0, 1.0).acos();
+        let bound = if angle <= self.radius {
+            1.0
+        } else {
+            (angle - self.radius).cos()
+        };
+        if self.has_zero {
+            bound.max(0.0)
+        } else {
+            bound
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum CandidateRows {
+    F32(Vec<f32>),
+    #[cfg(feature = "f16")]
+    F16(Vec<half::f16>),
+}
+
+impl CandidateSet {
+    /// Create an empty set of `dim`-wide f32 rows
+    pub fn new(dim: usize) -> Self {
+        Self {
+            dim,
+            rows: CandidateRows::F32(Vec::new()),
+            norms: Vec::new(),
+            block_size: 0,
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Create an empty set of `dim`-wide rows stored as f16
+    #[cfg(feature = "f16")]
+    pub fn new_f16(dim: usize) -> Self {
+        Self {
+            dim,
+            rows: CandidateRows::F16(Vec::new()),
+            norms: Vec::new(),
+            block_size: 0,
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Build an f32 set from rows of equal length
+    pub fn from_rows<R: AsRef<[f32]>>(rows: &[R]) -> Result<Self> {
+        let mut set = Self::new(rows.first().map_or(0, |r| r.as_ref().len()));
+        set.extend(rows)?;
+        Ok(set)
+    }
+
+    /// Build an f16 set from rows of equal length
+    #[cfg(feature = "f16")]
+    pub fn from_rows_f16<R: AsRef<[f32]>>(rows: &[R]) -> Result<Self> {
+        let mut set = Self::new_f16(rows.first().map_or(0, |r| r.as_ref().len()));
+        set.extend(rows)?;
+        Ok(set)
+    }
+
+    /// Append a row
+    pub fn push(&mut self, row: &[f32]) -> Result<()> {
+        if row.len() != self.dim {
+            return Err(GnnError::dimension_mismatch(
+                self.dim.to_string(),
+                row.len().to_string(),
+            ));
+        }
+        match &mut self.rows {
+            CandidateRows::F32(data) => data.extend_from_slice(row),
+            #[cfg(feature = "f16")]
+            CandidateRows::F16(data) => data.extend(row.iter().map(|&x| half::f16::from_f32(x))),
+        }
+        // Norm of the stored row, so rounding to f16 doesn't skew cosines
+        let norm = self.dot(self.norms.len(), &self.row(self.norms.len()));
+        self.norms.push(norm.sqrt());
+        if self.block_size > 0 && self.len() % self.block_size == 0 {
+            let block = self.build_block(self.blocks.len());
+            self.blocks.push(block);
+        }
+        Ok(())
+    }
+
+    /// Group rows into blocks of `block_size` with similarity bounds, for
+    /// early termination in [`differentiable_search_v2`]
+    ///
+    /// Rows pushed later are added to blocks as they fill up. Blocks work
+    /// best when nearby rows are similar, e.g. rows inserted cluster by
+    /// cluster. A `block_size` of 0 removes the blocks.
+    pub fn index_blocks(&mut self, block_size: usize) {
+        self.block_size = block_size;
+        self.blocks.clear();
+        if block_size == 0 {
+            return;
+        }
+        for b in 0..self.len() / block_size {
+            let block = self.build_block(b);
+            self.blocks.push(block);
+        }
+    }
+
+    /// Rows per indexed block, or 0 when blocks are not indexed
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    fn build_block(&self, b: usize) -> CandidateBlock {
+        let range = b * self.block_size..(b + 1) * self.block_size;
+        let mut centroid = vec![0.0; self.dim];
+        let mut has_zero = false;
+        for i in range.clone() {
+            if self.norms[i] == 0.0 {
+                has_zero = true;
+                continue;
+            }
+            for (c, x) in centroid.iter_mut().zip(self.row(i)) {
+                *c += x / self.norms[i];
+            }
+        }
+        let norm = centroid.iter().map(|c| c * c).sum::<f32>().sqrt();
+        if norm == 0.0 {
+            // No direction to bound by
+            return CandidateBlock {
+                centroid,
+                radius: std::f32::consts::PI,
+                has_zero,
+            };
+        }
+        centroid.iter_mut().for_each(|c| *c /= norm);
+
+        let radius = range
+            .filter(|&i| self.norms[i] > 0.0)
+            .map(|i| {
+                (self.dot(i, &centroid) / self.norms[i])
+                    .clamp(-1.0, 1.0)
+                    .acos()
+            })
+            .fold(0.0, f32::max);
+        CandidateBlock {
+            centroid,
+            // Margin for rounding in the angle computations
+            radius: radius + 1e-3,
+            has_zero,
+        }
+    }
+
+    /// Append several rows
+    pub fn extend<R: AsRef<[f32]>>(&mut self, rows: &[R]) -> Result<()> {
+        for row in rows {
+            self.push(row.as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Row width
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.norms.len()
+    }
+
+    /// Check if the set has no rows
+    pub fn is_empty(&self) -> bool {
+        self.norms.is_empty()
+    }
+
+    /// Whether rows are stored in half precision
+    pub fn is_f16(&self) -> bool {
+        !matches!(self.rows, CandidateRows::F32(_))
+    }
+
+    /// Row `i` as f32
+    pub fn row(&self, i: usize) -> Vec<f32> {
+        let range = i * self.dim..(i + 1) * self.dim;
+        match &self.rows {
+            CandidateRows::F32(data) => data[range].to_vec(),
+            #[cfg(feature = "f16")]
+            CandidateRows::F16(data) => data[range].iter().map(|x| x.to_f32()).collect(),
+        }
+    }
+
+    /// Bytes used by the rows, norms and block bounds
+    pub fn size_bytes(&self) -> usize {
+        let rows = match &self.rows {
+            CandidateRows::F32(data) => data.len() * std::mem::size_of::<f32>(),
+            #[cfg(feature = "f16")]
+            CandidateRows::F16(data) => data.len() * std::mem::size_of::<half::f16>(),
+        };
+        let blocks = self.blocks.len() * self.dim * std::mem::size_of::<f32>();
+        rows + blocks + self.norms.len() * std::mem::size_of::<f32>()
+    }
+
+    /// Dot product of row `i` with `query`, accumulated in f32
+    fn dot(&self, i: usize, query: &[f32]) -> f32 {
+        let range = i * self.dim..(i + 1) * self.dim;
+        match &self.rows {
+            CandidateRows::F32(data) => data[range].iter().zip(query).map(|(x, q)| x * q).sum(),
+            #[cfg(feature = "f16")]
+            CandidateRows::F16(data) => data[range]
+                .iter()
+                .zip(query)
+                .map(|(x, q)| x.to_f32() * q)
+                .sum(),
+        }
+    }
+
+    /// Score of row `i` against a query of norm `query_norm`
+    fn cosine(&self, i: usize, query: &[f32], query_norm: f32, encoding: &Encoding) -> f32 {
+        let range = i * self.dim..(i + 1) * self.dim;
+        let norm = self.norms[i];
+        match &self.rows {
+            CandidateRows::F32(data) => {
+                encoding.cosine(i, query, query_norm, data[range].iter().copied(), norm)
+            }
+            #[cfg(feature = "f16")]
+            CandidateRows::F16(data) => encoding.cosine(
+                i,
+                query,
+                query_norm,
+                data[range].iter().map(|x| x.to_f32()),
+                norm,
+            ),
+        }
+    }
+}
+
+/// `q · R(offset) c`, where `R` rotates each pair of dimensions `(2j, 2j + 1)`
+/// by `offset * inv_freq[j]`; a trailing odd dimension is not rotated
+fn rotary_dot(
+    query: &[f32],
+    mut row: impl Iterator<Item = f32>,
+    offset: f32,
+    inv_freq: &[f32],
+) -> f32 {
+    let mut sum = 0.0;
+    for (j, pair) in query.chunks(2).enumerate() {
+        match *pair {
+            [q0, q1] => {
+                let (c0, c1) = (row.next().unwrap_or(0.0), row.next().unwrap_or(0.0));
+                let (sin, cos) = (offset * inv_freq[j]).sin_cos();
+                sum += (q0 * c0 + q1 * c1) * cos + (q1 * c0 - q0 * c1) * sin;
+            }
+            [q] => sum += q * row.next().unwrap_or(0.0),
+            _ => {}
+        }
+    }
+    sum
+}
+
+/// Position-aware scoring for ordered candidate sets
+///
+/// Candidate `i` in a [`CandidateSet`] is at position `i`; the query is at
+/// [`SearchOptions::query_position`]. Useful when the order of the
+/// candidates carries meaning, e.g. timeline memories or the chunks of a
+/// document, and a candidate's score should depend on where it sits
+/// relative to the query.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PositionalEncoding {
+    /// Rotary position embeddings (RoPE): query and candidates are rotated
+    /// by their positions, so similarity depends on the relative offset
+    /// between them rather than on absolute positions, and is highest for
+    /// a candidate identical to the query at the query's position
+    Rotary {
+        /// Frequency base; larger values vary more slowly with distance
+        base: f32,
+    },
+    /// Add `weight / (1 + |offset|)` to each cosine similarity, favoring
+    /// candidates contiguous with the query
+    Proximity {
+        /// Bonus for a candidate at the query's own position
+        weight: f32,
+    },
+}
+
+impl PositionalEncoding {
+    /// Rotary encoding with the usual base of 10000
+    pub fn rotary() -> Self {
+        Self::Rotary { base: 10000.0 }
+    }
+}
+
+/// [`PositionalEncoding`] prepared for one search
+#[derive(Debug)]
+enum Encoding {
+    None,
+    Rotary {
+        query_position: f32,
+        inv_freq: Vec<f32>,
+    },
+    Proximity {
+        query_position: f32,
+        weight: f32,
+    },
+}
+
+impl Encoding {
+    /// Score of the row at position `i` against a query of norm
+    /// `query_norm`: cosine similarity adjusted for position
+    fn cosine(
+        &self,
+        i: usize,
+        query: &[f32],
+        query_norm: f32,
+        row: impl Iterator<Item = f32>,
+        row_norm: f32,
+    ) -> f32 {
+        if row_norm == 0.0 || query_norm == 0.0 {
+            return 0.0;
+        }
+        let norms = row_norm * query_norm;
+        match self {
+            Encoding::None => row.zip(query).map(|(x, q)| x * q).sum::<f32>() / norms,
+            Encoding::Rotary {
+                query_position,
+                inv_freq,
+            } => {
+                let offset = i as f32 - query_position;
+                // Rotations preserve norms, so the unrotated norms still apply
+                rotary_dot(query, row, offset, inv_freq) / norms
+            }
+            Encoding::Proximity {
+                query_position,
+                weight,
+            } => {
+                let offset = (i as f32 - query_position).abs();
+                let cosine = row.zip(query).map(|(x, q)| x * q).sum::<f32>() / norms;
+                cosine + weight / (1.0 + offset)
+            }
+        }
+    }
+
+    /// Upper bound on the score of any row in `range`, given an upper
+    /// bound on their cosine similarities
+    fn bound(&self, cosine_bound: f32, range: std::ops::Range<usize>) -> f32 {
+        match self {
+            Encoding::None => cosine_bound,
+            // Rotation moves rows away from the block centroid
+            Encoding::Rotary { .. } => 1.0,
+            Encoding::Proximity {
+                query_position,
+                weight,
+            } => {
+                let (start, end) = (range.start as f32, (range.end - 1) as f32);
+                let nearest = if *query_position < start {
+                    start - query_position
+                } else if *query_position > end {
+                    query_position - end
+                } else {
+                    0.0
+                };
+                let farthest = (query_position - start).abs().max(end - query_position);
+                let bonus = (weight / (1.0 + nearest)).max(weight / (1.0 + farthest));
+                cosine_bound + bonus
+            }
+        }
+    }
+
+    fn new(options: &SearchOptions, dim: usize) -> Result<Self> {
+        let query_position = options.query_position as f32;
+        match options.positional {
+            None => Ok(Self::None),
+            Some(PositionalEncoding::Rotary { base }) => {
+                if !(base > 1.0 && base.is_finite()) {
+                    return Err(GnnError::invalid_input(format!(
+                        "rotary base must be greater than 1, got {}",
+                        base
+                    )));
+                }
+                let inv_freq = (0..dim / 2)
+                    .map(|j| base.powf(-2.0 * j as f32 / dim as f32))
+                    .collect();
+                Ok(Self::Rotary {
+                    query_position,
+                    inv_freq,
+                })
+            }
+            Some(PositionalEncoding::Proximity { weight }) => {
+                if !weight.is_finite() {
+                    return Err(GnnError::invalid_input(
+                        "proximity weight must be finite".to_string(),
+                    ));
+                }
+                Ok(Self::Proximity {
+                    query_position,
+                    weight,
+                })
+            }
+        }
+    }
+}
+
+/// Options for [`differentiable_search_v2`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchOptions {
+    /// Number of top results to return
+    pub k: usize,
+    /// Temperature for softmax (lower = sharper, higher = smoother)
+    pub temperature: f32,
+    /// Position-aware scoring; `None` scores candidates independently of
+    /// their order
+    pub positional: Option<PositionalEncoding>,
+    /// Position of the query among the candidates, used by `positional`
+    pub query_position: usize,
+    /// Skip blocks that cannot reach the top `k`, if the candidate set
+    /// has [blocks](CandidateSet::index_blocks)
+    ///
+    /// The returned indices are the same as with a full pass; the weights
+    /// are normalized over the candidates actually scored, so they are
+    /// larger than the full-pass weights.
+    pub early_termination: bool,
+    /// Drop candidates scoring below this, skipping whole blocks whose
+    /// bound is below it when `early_termination` is set
+    pub min_similarity: Option<f32>,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            k: 10,
+            temperature: 1.0,
+            positional: None,
+            query_position: 0,
+            early_termination: false,
+            min_similarity: None,
+        }
+    }
+}
+
+/// Differentiable search over a [`CandidateSet`]
+///
+/// Computes the same soft attention as [`differentiable_search`], without
+/// per-candidate allocations and with f32 accumulation for f16 sets.
+/// With [`SearchOptions::positional`] set, similarities also depend on each
+/// candidate's position relative to the query.
+///
+/// # Returns
+/// * Tuple of (indices, soft_weights) for top-k candidates
+pub fn differentiable_search_v2(
+    query: &[f32],
+    candidates: &CandidateSet,
+    options: &SearchOptions,
+) -> Result<(Vec<usize>, Vec<f32>)> {
+    if query.len() != candidates.dim() {
+        return Err(GnnError::dimension_mismatch(
+            candidates.dim().to_string(),
+            query.len().to_string(),
+        ));
+    }
+    let (scored, similarities) = score_candidates(query, candidates, options)?;
+    Ok(top_k(&scored, &similarities, options))
+}
+
+/// Differentiable search over candidates streamed from an iterator
+///
+/// Scores each candidate as it is produced, keeping only its similarity,
+/// so candidates never have to be collected into a `Vec<Vec<f32>>` or a
+/// [`CandidateSet`]. Candidate positions for [`SearchOptions::positional`]
+/// are their positions in the stream; `early_termination` has no effect,
+/// since a stream has no block bounds.
+///
+/// # Returns
+/// * Tuple of (indices into the stream, soft_weights) for top-k candidates
+pub fn differentiable_search_stream<I, R>(
+    query: &[f32],
+    candidates: I,
+    options: &SearchOptions,
+) -> Result<(Vec<usize>, Vec<f32>)>
+where
+    I: IntoIterator<Item = R>,
+    R: AsRef<[f32]>,
+{
+    let encoding = Encoding::new(options, query.len())?;
+    let query_norm = l2_norm(query);
+    let min_similarity = options.min_similarity.unwrap_or(f32::NEG_INFINITY);
+
+    let mut scored = Scored::new(0);
+    for (i, row) in candidates.into_iter().enumerate() {
+        let row = row.as_ref();
+        if row.len() != query.len() {
+            return Err(GnnError::dimension_mismatch(
+                query.len().to_string(),
+                row.len().to_string(),
+            ));
+        }
+        let similarity = encoding.cosine(i, query, query_norm, row.iter().copied(), l2_norm(row));
+        if similarity >= min_similarity {
+            scored.push(i, similarity);
+        }
+    }
+    Ok(top_k(&scored.indices, &scored.similarities, options))
+}
+
+/// Differentiable search over the nearest neighbors of `query` in a
+/// [`VectorDB`]
+///
+/// Fetches `pool` candidates with an index search and streams their stored
+/// vectors into [`differentiable_search_stream`], so the candidate pool
+/// never leaves Rust. Positions for [`SearchOptions::positional`] are
+/// ranks in the index search.
+///
+/// # Returns
+/// * Top-k vector ids with their soft weights
+pub fn differentiable_search_db(
+    db: &VectorDB,
+    query: &[f32],
+    pool: usize,
+    options: &SearchOptions,
+) -> Result<Vec<(VectorId, f32)>> {
+    let hits = db.search(SearchQuery {
+        vector: query.to_vec(),
+        k: pool.max(options.k),
+        filter: None,
+        ef_search: None,
+    })?;
+
+    let mut ids = Vec::with_capacity(hits.len());
+    let mut vectors = Vec::with_capacity(hits.len());
+    for hit in hits {
+        let vector = match hit.vector {
+            Some(vector) => vector,
+            None => match db.get(&hit.id)? {
+                Some(entry) => entry.vector,
+                None => continue,
+            },
+        };
+        ids.push(hit.id);
+        vectors.push(vector);
+    }
+
+    let (indices, weights) = differentiable_search_stream(query, &vectors, options)?;
+    Ok(indices
+        .into_iter()
+        .map(|i| ids[i].clone())
+        .zip(weights)
+        .collect())
+}
+
+/// Softmax over `similarities` and the top `options.k` of `scored` by weight
+fn top_k(
+    scored: &[usize],
+    similarities: &[f32],
+    options: &SearchOptions,
+) -> (Vec<usize>, Vec<f32>) {
+    if scored.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+    let soft_weights = softmax(similarities, options.temperature);
+
+    let k = options.k.min(scored.len());
+    let mut order: Vec<usize> = (0..scored.len()).collect();
+    let by_weight = |a: &usize, b: &usize| soft_weights[*b].total_cmp(&soft_weights[*a]);
+    if k < order.len() && k > 0 {
+        order.select_nth_unstable_by(k - 1, by_weight);
+    }
+    order.truncate(k);
+    order.sort_by(by_weight);
+
+    let weights = order.iter().map(|&i| soft_weights[i]).collect();
+    let indices = order.iter().map(|&i| scored[i]).collect();
+    (indices, weights)
+}
+
+/// Euclidean norm, accumulated in f64
+fn l2_norm(values: &[f32]) -> f32 {
+    values
+        .iter()
+        .map(|&x| (x as f64) * (x as f64))
+        .sum::<f64>()
+        .sqrt() as f32
+}
+
+/// Indices and similarities of the candidates scored for a search, with
+/// blocks skipped and low scores dropped per `options`
+fn score_candidates(
+    query: &[f32],
+    candidates: &CandidateSet,
+    options: &SearchOptions,
+) -> Result<(Vec<usize>, Vec<f32>)> {
+    let encoding = Encoding::new(options, candidates.dim())?;
+    let query_norm = l2_norm(query);
+    let min_similarity = options.min_similarity.unwrap_or(f32::NEG_INFINITY);
+    let score_range = |scored: &mut Scored, range: std::ops::Range<usize>| {
+        for i in range {
+            let similarity = candidates.cosine(i, query, query_norm, &encoding);
+            if similarity >= min_similarity {
+                scored.push(i, similarity);
+            }
+        }
+    };
+
+    let block_size = candidates.block_size;
+    if !options.early_termination || candidates.blocks.is_empty() || options.k == 0 {
+        let mut scored = Scored::new(0);
+        score_range(&mut scored, 0..candidates.len());
+        return Ok((scored.indices, scored.similarities));
+    }
+
+    // Rows past the last complete block have no bound
+    let mut scored = Scored::new(options.k);
+    score_range(
+        &mut scored,
+        candidates.blocks.len() * block_size..candidates.len(),
+    );
+
+    let mut bounds: Vec<(usize, f32)> = candidates
+        .blocks
+        .iter()
+        .enumerate()
+        .map(|(b, block)| {
+            let range = b * block_size..(b + 1) * block_size;
+            (b, encoding.bound(block.bound(query, query_norm), range))
+        })
+        .collect();
+    bounds.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    for (b, bound) in bounds {
+        if bound < min_similarity || scored.kth().is_some_and(|kth| kth >= bound) {
+            break;
+        }
+        score_range(&mut scored, b * block_size..(b + 1) * block_size);
+    }
+    Ok((scored.indices, scored.similarities))
+}
+
+/// Scores collected during a search, tracking the `k` largest
+struct Scored {
+    k: usize,
+    indices: Vec<usize>,
+    similarities: Vec<f32>,
+    top: BinaryHeap<Reverse<Score>>,
+}
+
+impl Scored {
+    fn new(k: usize) -> Self {
+        Self {
+            k,
+            indices: Vec::new(),
+            similarities: Vec::new(),
+            top: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+
+    fn push(&mut self, index: usize, similarity: f32) {
+        self.indices.push(index);
+        self.similarities.push(similarity);
+        if self.k > 0 {
+            self.top.push(Reverse(Score(similarity)));
+            if self.top.len() > self.k {
+                self.top.pop();
+            }
+        }
+    }
+
+    /// `k`-th largest similarity, once `k` have been scored
+    fn kth(&self) -> Option<f32> {
+        if self.top.len() < self.k {
+            return None;
+        }
+        self.top.peek().map(|Reverse(Score(s))| *s)
+    }
+}
+
+/// Similarity ordered by [`f32::total_cmp`]
+#[derive(Debug, PartialEq)]
+struct Score(f32);
+
+impl Eq for Score {}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
 /// Hierarchical forward pass through GNN layers
 ///
 /// # Arguments
@@ -121,36 +858,152 @@ pub fn hierarchical_forward(
             1.0,                     // Default temperature
         );
 
-        // Aggregate embeddings from top nodes using soft weights
-        let mut aggregated = vec![0.0; current_embedding.len()];
-        for (&idx, &weight) in top_indices.iter().zip(weights.iter()) {
-            for (i, &val) in embeddings[idx].iter().enumerate() {
-                if i < aggregated.len() {
-                    aggregated[i] += weight * val;
-                }
-            }
+        current_embedding = forward_layer(
+            &current_embedding,
+            &top_indices,
+            &weights,
+            |idx| embeddings[idx].as_slice(),
+            gnn_layer,
+        );
+    }
+
+    current_embedding
+}
+
+/// Hierarchical forward pass over borrowed layer embeddings
+///
+/// Same as [`hierarchical_forward`], for callers whose embeddings live in
+/// buffers they don't own, so they need not be copied into `Vec`s first.
+/// Returns an error instead of panicking when an embedding's dimension
+/// doesn't match the running embedding.
+pub fn hierarchical_forward_slices(
+    query: &[f32],
+    layer_embeddings: &[Vec<&[f32]>],
+    gnn_layers: &[RuvectorLayer],
+) -> Result<Vec<f32>> {
+    let mut current_embedding = query.to_vec();
+    for (embeddings, gnn_layer) in layer_embeddings.iter().zip(gnn_layers) {
+        if embeddings.is_empty() {
+            continue;
         }
 
-        // Combine with current embedding
-        let combined: Vec<f32> = current_embedding
-            .iter()
-            .zip(&aggregated)
-            .map(|(curr, agg)| (curr + agg) / 2.0)
-            .collect();
+        let options = SearchOptions {
+            k: 5,
+            ..Default::default()
+        };
+        let (top_indices, weights) =
+            differentiable_search_stream(&current_embedding, embeddings, &options)?;
 
-        // Apply GNN layer transformation
-        // Extract neighbor embeddings and compute edge weights
-        let neighbor_embs: Vec<Vec<f32>> = top_indices
-            .iter()
-            .map(|&idx| embeddings[idx].clone())
-            .collect();
+        current_embedding = forward_layer(
+            &current_embedding,
+            &top_indices,
+            &weights,
+            |idx| embeddings[idx],
+            gnn_layer,
+        );
+    }
+
+    Ok(current_embedding)
+}
+
+/// Hierarchical forward pass reading layer embeddings from a memory-mapped
+/// store
+///
+/// Same as [`hierarchical_forward`], for graphs whose embeddings do not fit
+/// in RAM: each layer is given as node ids into `store` rather than as
+/// embeddings, and the embeddings are read in place. The next layer's
+/// embeddings are prefetched while the current layer is processed.
+///
+/// # Arguments
+/// * `query` - The query vector
+/// * `store` - Embeddings of all nodes
+/// * `layer_nodes` - Node ids organized by layer
+/// * `gnn_layers` - The GNN layers to process through
+///
+/// # Returns
+/// * Final embedding after hierarchical processing
+#[cfg(all(not(target_arch = "wasm32"), feature = "mmap"))]
+pub fn hierarchical_forward_mmap(
+    query: &[f32],
+    store: &crate::mmap::MmapManager,
+    layer_nodes: &[Vec<u64>],
+    gnn_layers: &[RuvectorLayer],
+) -> Result<Vec<f32>> {
+    if let Some(&id) = layer_nodes
+        .iter()
+        .flatten()
+        .find(|&&id| id >= store.max_nodes() as u64)
+    {
+        return Err(GnnError::invalid_input(format!(
+            "node id {} out of bounds (max: {})",
+            id,
+            store.max_nodes()
+        )));
+    }
 
-        let edge_weights_vec: Vec<f32> = weights.clone();
+    let mut current_embedding = query.to_vec();
+    if let Some(first) = layer_nodes.first() {
+        store.prefetch(first);
+    }
+    for (layer_idx, (nodes, gnn_layer)) in layer_nodes.iter().zip(gnn_layers).enumerate() {
+        if let Some(next) = layer_nodes.get(layer_idx + 1) {
+            store.prefetch(next);
+        }
+        if nodes.is_empty() {
+            continue;
+        }
 
-        current_embedding = gnn_layer.forward(&combined, &neighbor_embs, &edge_weights_vec);
+        let options = SearchOptions {
+            k: 5,
+            ..Default::default()
+        };
+        let candidates = nodes.iter().map(|&id| store.get_embedding(id));
+        let (top_indices, weights) =
+            differentiable_search_stream(&current_embedding, candidates, &options)?;
+
+        current_embedding = forward_layer(
+            &current_embedding,
+            &top_indices,
+            &weights,
+            |idx| store.get_embedding(nodes[idx]),
+            gnn_layer,
+        );
     }
 
-    current_embedding
+    Ok(current_embedding)
+}
+
+/// One step of the hierarchical forward pass: blend the soft-weighted top
+/// nodes into `current` and apply `gnn_layer` over them
+fn forward_layer<'a>(
+    current: &[f32],
+    top_indices: &[usize],
+    weights: &[f32],
+    embedding: impl Fn(usize) -> &'a [f32],
+    gnn_layer: &RuvectorLayer,
+) -> Vec<f32> {
+    // Aggregate embeddings from top nodes using soft weights
+    let mut aggregated = vec![0.0; current.len()];
+    for (&idx, &weight) in top_indices.iter().zip(weights.iter()) {
+        for (i, &val) in embedding(idx).iter().enumerate() {
+            if i < aggregated.len() {
+                aggregated[i] += weight * val;
+            }
+        }
+    }
+
+    // Combine with current embedding
+    let combined: Vec<f32> = current
+        .iter()
+        .zip(&aggregated)
+        .map(|(curr, agg)| (curr + agg) / 2.0)
+        .collect();
+
+    // Apply GNN layer transformation
+    // Extract neighbor embeddings and compute edge weights
+    let neighbor_embs: Vec<&[f32]> = top_indices.iter().map(|&idx| embedding(idx)).collect();
+
+    gnn_layer.forward_slices(&combined, &neighbor_embs, weights)
 }
 
 #[cfg(test)]
@@ -216,6 +1069,324 @@ mod tests {
         assert!(sum <= 1.0 + 1e-6);
     }
 
+    fn candidates(n: usize, dim: usize) -> Vec<Vec<f32>> {
+        (0..n)
+            .map(|i| {
+                (0..dim)
+                    .map(|j| (((i * 13 + j * 5) % 19) as f32 - 9.0) * 0.1 + (i * j) as f32 * 1e-3)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_differentiable_search_v2_matches_v1() {
+        let rows = candidates(200, 16);
+        let query = rows[7].clone();
+        let set = CandidateSet::from_rows(&rows).unwrap();
+        assert_eq!(set.len(), 200);
+        assert!(!set.is_f16());
+        assert_eq!(set.row(3), rows[3]);
+
+        let options = SearchOptions {
+            k: 5,
+            temperature: 0.5,
+            ..Default::default()
+        };
+        let (indices, weights) = differentiable_search_v2(&query, &set, &options).unwrap();
+        let (expected_indices, expected_weights) = differentiable_search(&query, &rows, 5, 0.5);
+        assert_eq!(indices[0], 7);
+        assert_eq!(indices.len(), 5);
+        for (w, e) in weights.iter().zip(&expected_weights) {
+            assert!((w - e).abs() < 1e-5);
+        }
+        assert_eq!(indices[0], expected_indices[0]);
+
+        assert!(differentiable_search_v2(&[1.0; 3], &set, &options).is_err());
+        assert!(set.clone().push(&[1.0; 3]).is_err());
+        let empty = CandidateSet::new(16);
+        assert!(differentiable_search_v2(&query, &empty, &options)
+            .unwrap()
+            .0
+            .is_empty());
+    }
+
+    #[test]
+    fn test_differentiable_search_v2_positional() {
+        // Identical candidates are told apart only by position
+        let rows = vec![vec![0.3, -0.2, 0.5, 0.1, 0.4]; 50];
+        let set = CandidateSet::from_rows(&rows).unwrap();
+        let query = rows[0].clone();
+
+        let plain = SearchOptions::default();
+        let (_, weights) = differentiable_search_v2(&query, &set, &plain).unwrap();
+        assert!((weights[0] - weights[9]).abs() < 1e-6);
+
+        for encoding in [
+            PositionalEncoding::rotary(),
+            PositionalEncoding::Rotary { base: 100.0 },
+            PositionalEncoding::Proximity { weight: 0.5 },
+        ] {
+            let options = SearchOptions {
+                k: 50,
+                positional: Some(encoding),
+                query_position: 20,
+                ..Default::default()
+            };
+            let (indices, weights) = differentiable_search_v2(&query, &set, &options).unwrap();
+            assert_eq!(indices[0], 20, "{:?}", encoding);
+            assert!(weights[0] > weights[1], "{:?}", encoding);
+
+            // Scores depend on the offset, not its sign
+            let mut by_index = vec![0.0; 50];
+            for (&i, &w) in indices.iter().zip(&weights) {
+                by_index[i] = w;
+            }
+            for d in 1..=20 {
+                assert!((by_index[20 - d] - by_index[20 + d]).abs() < 1e-6);
+            }
+        }
+
+        // Proximity favors contiguous candidates
+        let options = SearchOptions {
+            k: 3,
+            positional: Some(PositionalEncoding::Proximity { weight: 0.5 }),
+            query_position: 20,
+            ..Default::default()
+        };
+        let (mut indices, _) = differentiable_search_v2(&query, &set, &options).unwrap();
+        indices.sort_unstable();
+        assert_eq!(indices, [19, 20, 21]);
+
+        // Rotary similarity at offset 0 is the plain cosine
+        let encoding = Encoding::new(
+            &SearchOptions {
+                positional: Some(PositionalEncoding::rotary()),
+                query_position: 4,
+                ..Default::default()
+            },
+            5,
+        )
+        .unwrap();
+        let other = CandidateSet::from_rows(&candidates(10, 5)).unwrap();
+        let query = [0.5, 0.1, -0.3, 0.2, 0.7];
+        let norm = query.iter().map(|x| x * x).sum::<f32>().sqrt();
+        let rotated = other.cosine(4, &query, norm, &encoding);
+        let plain = other.cosine(4, &query, norm, &Encoding::None);
+        assert!((rotated - plain).abs() < 1e-6);
+        let rotated = other.cosine(5, &query, norm, &encoding);
+        let plain = other.cosine(5, &query, norm, &Encoding::None);
+        assert!((rotated - plain).abs() > 1e-4);
+
+        let invalid = SearchOptions {
+            positional: Some(PositionalEncoding::Rotary { base: 0.5 }),
+            ..Default::default()
+        };
+        assert!(differentiable_search_v2(&query, &other, &invalid).is_err());
+    }
+
+    /// `clusters` groups of 64 rows, each around its own direction
+    fn clustered(clusters: usize, dim: usize) -> Vec<Vec<f32>> {
+        let mut state = 12345u64;
+        let mut next = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            ((state >> 33) as f32 / (1u64 << 31) as f32) - 0.5
+        };
+        let mut rows = Vec::new();
+        for _ in 0..clusters {
+            let center: Vec<f32> = (0..dim).map(|_| next()).collect();
+            for _ in 0..64 {
+                rows.push(center.iter().map(|c| c + next() * 0.1).collect());
+            }
+        }
+        rows
+    }
+
+    #[test]
+    fn test_differentiable_search_v2_early_termination() {
+        let rows = clustered(200, 16);
+        let mut set = CandidateSet::from_rows(&rows).unwrap();
+        set.index_blocks(64);
+        // A partial block past the indexed ones is always scored
+        set.extend(&rows[..10]).unwrap();
+        let query: Vec<f32> = rows[37 * 64 + 5].iter().map(|x| x + 0.01).collect();
+
+        let full = SearchOptions::default();
+        let pruned = SearchOptions {
+            early_termination: true,
+            ..Default::default()
+        };
+        let (full_indices, _) = differentiable_search_v2(&query, &set, &full).unwrap();
+        let (indices, weights) = differentiable_search_v2(&query, &set, &pruned).unwrap();
+        assert_eq!(indices, full_indices);
+        assert_eq!(weights.len(), 10);
+
+        let (scored, _) = score_candidates(&query, &set, &pruned).unwrap();
+        assert!(scored.len() < set.len() / 4, "scored {}", scored.len());
+
+        // Positional bonuses are accounted for in the bounds
+        let positional = SearchOptions {
+            positional: Some(PositionalEncoding::Proximity { weight: 2.0 }),
+            query_position: 150 * 64,
+            ..pruned.clone()
+        };
+        let (indices, _) = differentiable_search_v2(&query, &set, &positional).unwrap();
+        let full_positional = SearchOptions {
+            early_termination: false,
+            ..positional.clone()
+        };
+        let (expected, _) = differentiable_search_v2(&query, &set, &full_positional).unwrap();
+        assert_eq!(indices, expected);
+
+        // Threshold pruning drops weak candidates and the blocks holding them
+        let threshold = SearchOptions {
+            k: 1000,
+            min_similarity: Some(0.9),
+            ..pruned.clone()
+        };
+        let (indices, _) = differentiable_search_v2(&query, &set, &threshold).unwrap();
+        assert!(!indices.is_empty() && indices.len() < 1000);
+        for &i in &indices {
+            assert!(cosine_similarity(&query, &rows[i % rows.len()]) >= 0.9 - 1e-4);
+        }
+        let (scored, _) = score_candidates(&query, &set, &threshold).unwrap();
+        assert_eq!(scored.len(), indices.len());
+        let all = SearchOptions {
+            early_termination: false,
+            ..threshold
+        };
+        let (mut expected, _) = differentiable_search_v2(&query, &set, &all).unwrap();
+        let mut indices = indices;
+        indices.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(indices, expected);
+    }
+
+    #[test]
+    fn test_differentiable_search_stream() {
+        let rows = candidates(100, 8);
+        let query = rows[42].clone();
+        let options = SearchOptions {
+            k: 5,
+            positional: Some(PositionalEncoding::rotary()),
+            query_position: 40,
+            ..Default::default()
+        };
+        let set = CandidateSet::from_rows(&rows).unwrap();
+        let expected = differentiable_search_v2(&query, &set, &options).unwrap();
+
+        // Rows are produced lazily, never collected
+        let stream = (0..100).map(|i| candidates(i + 1, 8).pop().unwrap());
+        let streamed = differentiable_search_stream(&query, stream, &options).unwrap();
+        assert_eq!(streamed.0, expected.0);
+        for (s, e) in streamed.1.iter().zip(&expected.1) {
+            assert!((s - e).abs() < 1e-6);
+        }
+
+        let flat: Vec<f32> = rows.concat();
+        let chunked = differentiable_search_stream(&query, flat.chunks_exact(8), &options).unwrap();
+        assert_eq!(chunked.0, expected.0);
+
+        let bad = [vec![1.0; 8], vec![1.0; 7]];
+        assert!(differentiable_search_stream(&query, &bad, &options).is_err());
+    }
+
+    #[test]
+    fn test_differentiable_search_db() {
+        use ruvector_core::types::{DbOptions, VectorEntry};
+
+        let dir = tempfile::tempdir().unwrap();
+        let db = VectorDB::new(DbOptions {
+            dimensions: 8,
+            storage_path: dir.path().join("search.db").to_string_lossy().to_string(),
+            quantization: None,
+            ..Default::default()
+        })
+        .unwrap();
+        let rows = candidates(50, 8);
+        for (i, row) in rows.iter().enumerate() {
+            db.insert(VectorEntry {
+                id: Some(format!("v{}", i)),
+                vector: row.clone(),
+                metadata: None,
+            })
+            .unwrap();
+        }
+
+        let options = SearchOptions {
+            k: 3,
+            ..Default::default()
+        };
+        let results = differentiable_search_db(&db, &rows[17], 20, &options).unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].0, "v17");
+        assert!(results[0].1 >= results[1].1);
+    }
+
+    #[cfg(feature = "f16")]
+    #[test]
+    fn test_differentiable_search_v2_f16() {
+        let rows = candidates(200, 16);
+        let query = rows[11].clone();
+        let full = CandidateSet::from_rows(&rows).unwrap();
+        let half = CandidateSet::from_rows_f16(&rows).unwrap();
+        assert!(half.is_f16());
+        assert!(half.size_bytes() < full.size_bytes());
+
+        let options = SearchOptions::default();
+        let (full_indices, full_weights) =
+            differentiable_search_v2(&query, &full, &options).unwrap();
+        let (half_indices, half_weights) =
+            differentiable_search_v2(&query, &half, &options).unwrap();
+        assert_eq!(half_indices[0], full_indices[0]);
+        for (h, f) in half_weights.iter().zip(&full_weights) {
+            assert!((h - f).abs() < 1e-3, "f16: {}, f32: {}", h, f);
+        }
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), feature = "mmap"))]
+    #[test]
+    fn test_hierarchical_forward_mmap() {
+        use crate::mmap::MmapManager;
+
+        let dir = tempfile::tempdir().unwrap();
+        let rows = candidates(20, 4);
+        let mut store = MmapManager::new(&dir.path().join("nodes.bin"), 4, 20).unwrap();
+        for (id, row) in rows.iter().enumerate() {
+            store.set_embedding(id as u64, row);
+        }
+
+        let layer_nodes: Vec<Vec<u64>> = vec![(0..12).collect(), (12..20).collect()];
+        let layer_embeddings: Vec<Vec<Vec<f32>>> = layer_nodes
+            .iter()
+            .map(|nodes| nodes.iter().map(|&id| rows[id as usize].clone()).collect())
+            .collect();
+        let gnn_layers = vec![
+            RuvectorLayer::new(4, 4, 1, 0.0),
+            RuvectorLayer::new(4, 4, 1, 0.0),
+        ];
+        let query = vec![0.3, -0.1, 0.8, 0.2];
+
+        let expected = hierarchical_forward(&query, &layer_embeddings, &gnn_layers);
+        let result = hierarchical_forward_mmap(&query, &store, &layer_nodes, &gnn_layers).unwrap();
+        assert_eq!(result.len(), expected.len());
+        for (r, e) in result.iter().zip(&expected) {
+            assert!((r - e).abs() < 1e-5, "mmap: {}, in memory: {}", r, e);
+        }
+
+        let borrowed: Vec<Vec<&[f32]>> = layer_embeddings
+            .iter()
+            .map(|layer| layer.iter().map(|e| e.as_slice()).collect())
+            .collect();
+        let result = hierarchical_forward_slices(&query, &borrowed, &gnn_layers).unwrap();
+        for (r, e) in result.iter().zip(&expected) {
+            assert!((r - e).abs() < 1e-5, "borrowed: {}, owned: {}", r, e);
+        }
+
+        let out_of_bounds = vec![vec![0, 20]];
+        assert!(hierarchical_forward_mmap(&query, &store, &out_of_bounds, &gnn_layers).is_err());
+    }
+
     #[test]
     fn test_hierarchical_forward() {
         // Use consistent dimensions throughout
@@ -4,6 +4,7 @@
 
 use crate::error::{GnnError, Result};
 use crate::search::cosine_similarity;
+use half::f16;
 use ndarray::Array2;
 
 /// Optimizer types
@@ -663,6 +664,226 @@ pub fn sgd_step(embedding: &mut [f32], grad: &[f32], learning_rate: f32) {
     }
 }
 
+/// Clip gradients by their global L2 norm
+///
+/// Computes the norm over all gradient tensors together and, if it exceeds
+/// `max_norm`, scales every gradient by `max_norm / norm`. This bounds the
+/// update size without changing its direction, which keeps training stable
+/// when a few long neighbor lists produce outsized gradients.
+///
+/// # Arguments
+/// * `grads` - Gradient tensors, modified in-place
+/// * `max_norm` - Maximum allowed global norm
+///
+/// # Returns
+/// * The global norm before clipping
+///
+/// # Example
+/// ```
+/// use ruvector_gnn::training::clip_grad_norm;
+///
+/// let mut a = vec![3.0, 0.0];
+/// let mut b = vec![0.0, 4.0];
+/// let norm = clip_grad_norm(&mut [&mut a, &mut b], 1.0);
+///
+/// assert!((norm - 5.0).abs() < 1e-6);
+/// assert!((a[0] - 0.6).abs() < 1e-6);
+/// assert!((b[1] - 0.8).abs() < 1e-6);
+/// ```
+pub fn clip_grad_norm<G: AsMut<[f32]>>(grads: &mut [G], max_norm: f32) -> f32 {
+    let norm = grads
+        .iter_mut()
+        .flat_map(|g| g.as_mut().iter())
+        .map(|&g| g * g)
+        .sum::<f32>()
+        .sqrt();
+
+    if norm > max_norm && norm > 0.0 {
+        let scale = max_norm / norm;
+        for g in grads.iter_mut().flat_map(|g| g.as_mut().iter_mut()) {
+            *g *= scale;
+        }
+    }
+
+    norm
+}
+
+/// Mixed-precision gradient accumulator
+///
+/// Accumulates gradients over micro-batches in f16, halving the memory of
+/// the accumulation buffer, while the weights are kept and updated in f32
+/// ("master weights"). Gradients are multiplied by a loss scale before
+/// accumulation so small values do not underflow f16; the scale is halved
+/// whenever the buffer overflows (the step is then skipped) and doubled
+/// after `growth_interval` clean steps.
+#[derive(Debug, Clone)]
+pub struct MixedPrecisionAccumulator {
+    /// f32 master weights
+    master_weights: Vec<f32>,
+    /// Scaled gradient sum in f16
+    grads: Vec<f16>,
+    /// Number of accumulated micro-batches
+    count: usize,
+    /// Current loss scale
+    loss_scale: f32,
+    /// Whether the buffer overflowed since the last step
+    overflowed: bool,
+    /// Clean steps required before growing the loss scale
+    growth_interval: usize,
+    /// Clean steps since the loss scale last changed
+    clean_steps: usize,
+}
+
+impl MixedPrecisionAccumulator {
+    /// Default initial loss scale
+    pub const DEFAULT_LOSS_SCALE: f32 = 1024.0;
+
+    /// Create an accumulator over `weights` with the default loss scale
+    pub fn new(weights: &[f32]) -> Self {
+        Self::with_loss_scale(weights, Self::DEFAULT_LOSS_SCALE)
+    }
+
+    /// Create an accumulator over `weights` with an initial loss scale
+    pub fn with_loss_scale(weights: &[f32], loss_scale: f32) -> Self {
+        Self {
+            master_weights: weights.to_vec(),
+            grads: vec![f16::ZERO; weights.len()],
+            count: 0,
+            loss_scale,
+            overflowed: false,
+            growth_interval: 2000,
+            clean_steps: 0,
+        }
+    }
+
+    /// Set how many clean steps pass before the loss scale doubles
+    pub fn with_growth_interval(mut self, growth_interval: usize) -> Self {
+        self.growth_interval = growth_interval.max(1);
+        self
+    }
+
+    /// Accumulate one micro-batch gradient
+    ///
+    /// # Returns
+    /// * `Err(GnnError)` if the gradient length doesn't match the weights
+    pub fn accumulate(&mut self, grad: &[f32]) -> Result<()> {
+        if grad.len() != self.grads.len() {
+            return Err(GnnError::dimension_mismatch(
+                self.grads.len().to_string(),
+                grad.len().to_string(),
+            ));
+        }
+
+        for (acc, &g) in self.grads.iter_mut().zip(grad) {
+            // Sum in f32, store in f16
+            let sum = f16::from_f32(acc.to_f32() + g * self.loss_scale);
+            if !sum.is_finite() {
+                self.overflowed = true;
+            }
+            *acc = sum;
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Take the mean accumulated gradient in f32 and clear the buffer
+    ///
+    /// # Returns
+    /// * `None` if nothing was accumulated, or if the buffer overflowed, in
+    ///   which case the loss scale is halved and the step should be skipped
+    pub fn take_gradients(&mut self) -> Option<Vec<f32>> {
+        let count = std::mem::take(&mut self.count);
+        let overflowed = std::mem::take(&mut self.overflowed);
+        if count == 0 {
+            return None;
+        }
+
+        let divisor = self.loss_scale * count as f32;
+        let grads: Vec<f32> = self.grads.iter().map(|g| g.to_f32() / divisor).collect();
+        self.grads.fill(f16::ZERO);
+
+        if overflowed {
+            self.loss_scale = (self.loss_scale * 0.5).max(1.0);
+            self.clean_steps = 0;
+            return None;
+        }
+
+        self.clean_steps += 1;
+        if self.clean_steps >= self.growth_interval {
+            self.loss_scale *= 2.0;
+            self.clean_steps = 0;
+        }
+        Some(grads)
+    }
+
+    /// Apply the accumulated gradient to the master weights with SGD
+    ///
+    /// # Arguments
+    /// * `learning_rate` - Step size
+    /// * `max_norm` - Clip the gradient to this global norm, if set
+    ///
+    /// # Returns
+    /// * Whether a step was taken (false when skipped on overflow or when
+    ///   nothing was accumulated)
+    pub fn step(&mut self, learning_rate: f32, max_norm: Option<f32>) -> bool {
+        let Some(mut grads) = self.take_gradients() else {
+            return false;
+        };
+        if let Some(max_norm) = max_norm {
+            clip_grad_norm(&mut [&mut grads], max_norm);
+        }
+        sgd_step(&mut self.master_weights, &grads, learning_rate);
+        true
+    }
+
+    /// Apply the accumulated gradient to the master weights with `optimizer`
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - whether a step was taken, as for [`step`](Self::step)
+    pub fn step_with(&mut self, optimizer: &mut Optimizer, max_norm: Option<f32>) -> Result<bool> {
+        let Some(mut grads) = self.take_gradients() else {
+            return Ok(false);
+        };
+        if let Some(max_norm) = max_norm {
+            clip_grad_norm(&mut [&mut grads], max_norm);
+        }
+
+        let n = self.master_weights.len();
+        let to_array = |v: Vec<f32>| {
+            Array2::from_shape_vec((1, n), v)
+                .map_err(|e| GnnError::training(format!("Failed to reshape weights: {}", e)))
+        };
+        let mut params = to_array(std::mem::take(&mut self.master_weights))?;
+        let result = optimizer.step(&mut params, &to_array(grads)?);
+        self.master_weights = params.into_iter().collect();
+        result.map(|_| true)
+    }
+
+    /// Get the f32 master weights
+    pub fn master_weights(&self) -> &[f32] {
+        &self.master_weights
+    }
+
+    /// Get the master weights rounded to f16, e.g. for a half-precision
+    /// forward pass
+    pub fn weights_f16(&self) -> Vec<f16> {
+        self.master_weights
+            .iter()
+            .map(|&w| f16::from_f32(w))
+            .collect()
+    }
+
+    /// Get the current loss scale
+    pub fn loss_scale(&self) -> f32 {
+        self.loss_scale
+    }
+
+    /// Get the number of micro-batches accumulated since the last step
+    pub fn pending(&self) -> usize {
+        self.count
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1303,4 +1524,65 @@ mod tests {
 
         assert!(final_loss < initial_loss, "Loss should decrease during training");
     }
+
+    #[test]
+    fn test_clip_grad_norm() {
+        let mut a = vec![3.0, 0.0];
+        let mut b = vec![0.0, 4.0];
+        let norm = clip_grad_norm(&mut [&mut a, &mut b], 10.0);
+        // Below the limit: unchanged
+        assert!((norm - 5.0).abs() < 1e-6);
+        assert_eq!(a, vec![3.0, 0.0]);
+
+        let norm = clip_grad_norm(&mut [&mut a, &mut b], 2.5);
+        assert!((norm - 5.0).abs() < 1e-6);
+        assert!((a[0] - 1.5).abs() < 1e-6);
+        assert!((b[1] - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_mixed_precision_accumulator() {
+        let mut acc = MixedPrecisionAccumulator::new(&[1.0, 1.0, 1.0]);
+        // Small gradients survive f16 thanks to loss scaling
+        acc.accumulate(&[1e-6, 0.5, -0.25]).unwrap();
+        acc.accumulate(&[3e-6, 0.5, -0.75]).unwrap();
+        assert_eq!(acc.pending(), 2);
+        assert!(acc.accumulate(&[1.0]).is_err());
+
+        let grads = acc.take_gradients().unwrap();
+        assert!((grads[0] - 2e-6).abs() < 1e-8);
+        assert!((grads[1] - 0.5).abs() < 1e-3);
+        assert!((grads[2] + 0.5).abs() < 1e-3);
+        assert!(acc.take_gradients().is_none());
+
+        // Clipped SGD step on the master weights
+        acc.accumulate(&[3.0, 4.0, 0.0]).unwrap();
+        assert!(acc.step(0.1, Some(1.0)));
+        let weights = acc.master_weights();
+        assert!((weights[0] - 0.94).abs() < 1e-3);
+        assert!((weights[1] - 0.92).abs() < 1e-3);
+        assert_eq!(weights[2], 1.0);
+
+        // Overflow skips the step and halves the loss scale
+        acc.accumulate(&[1e5, 0.0, 0.0]).unwrap();
+        assert!(!acc.step(0.1, None));
+        assert_eq!(acc.loss_scale(), 512.0);
+        assert!((acc.master_weights()[0] - 0.94).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_mixed_precision_step_with_optimizer() {
+        let mut acc =
+            MixedPrecisionAccumulator::with_loss_scale(&[1.0, -1.0], 8.0).with_growth_interval(1);
+        let mut optimizer = Optimizer::new(OptimizerType::Sgd {
+            learning_rate: 0.5,
+            momentum: 0.0,
+        });
+        acc.accumulate(&[1.0, -1.0]).unwrap();
+        assert!(acc.step_with(&mut optimizer, None).unwrap());
+        assert_eq!(acc.master_weights(), &[0.5, -0.5]);
+        // The loss scale grows after a clean step
+        assert_eq!(acc.loss_scale(), 16.0);
+        assert_eq!(acc.weights_f16()[0].to_f32(), 0.5);
+    }
 }
@@ -68,6 +68,24 @@ lazy_static! {
         Opts::new("ruvector_memory_usage_bytes", "Memory usage in bytes")
     ).unwrap();
 
+    // Admission control metrics
+    pub static ref ADMISSION_IN_FLIGHT_SEARCHES: Gauge = register_gauge!(
+        Opts::new("ruvector_admission_in_flight_searches", "Searches currently executing")
+    ).unwrap();
+
+    pub static ref ADMISSION_QUEUED_SEARCHES: Gauge = register_gauge!(
+        Opts::new("ruvector_admission_queued_searches", "Searches waiting for a slot")
+    ).unwrap();
+
+    pub static ref ADMISSION_SATURATION: Gauge = register_gauge!(
+        Opts::new("ruvector_admission_saturation", "Share of search slots in use (0-1)")
+    ).unwrap();
+
+    pub static ref ADMISSION_REJECTED_TOTAL: CounterVec = register_counter_vec!(
+        Opts::new("ruvector_admission_rejected_total", "Requests rejected by admission control"),
+        &["reason"]
+    ).unwrap();
+
     pub static ref UPTIME_SECONDS: Counter = register_counter!(
         Opts::new("ruvector_uptime_seconds", "Uptime in seconds")
     ).unwrap();
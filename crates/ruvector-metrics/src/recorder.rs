@@ -1,7 +1,8 @@
 use crate::{
-    COLLECTIONS_TOTAL, DELETE_REQUESTS_TOTAL, INSERT_LATENCY_SECONDS, INSERT_REQUESTS_TOTAL,
-    MEMORY_USAGE_BYTES, SEARCH_LATENCY_SECONDS, SEARCH_REQUESTS_TOTAL, VECTORS_INSERTED_TOTAL,
-    VECTORS_TOTAL,
+    ADMISSION_IN_FLIGHT_SEARCHES, ADMISSION_QUEUED_SEARCHES, ADMISSION_REJECTED_TOTAL,
+    ADMISSION_SATURATION, COLLECTIONS_TOTAL, DELETE_REQUESTS_TOTAL, INSERT_LATENCY_SECONDS,
+    INSERT_REQUESTS_TOTAL, MEMORY_USAGE_BYTES, SEARCH_LATENCY_SECONDS, SEARCH_REQUESTS_TOTAL,
+    VECTORS_INSERTED_TOTAL, VECTORS_TOTAL,
 };
 
 /// Helper struct for recording metrics
@@ -93,6 +94,26 @@ impl MetricsRecorder {
         MEMORY_USAGE_BYTES.set(bytes as f64);
     }
 
+    /// Update search admission gauges
+    ///
+    /// # Arguments
+    /// * `in_flight` - Searches currently executing
+    /// * `queued` - Searches waiting for a slot
+    /// * `saturation` - Share of search slots in use
+    pub fn set_admission(in_flight: usize, queued: usize, saturation: f64) {
+        ADMISSION_IN_FLIGHT_SEARCHES.set(in_flight as f64);
+        ADMISSION_QUEUED_SEARCHES.set(queued as f64);
+        ADMISSION_SATURATION.set(saturation);
+    }
+
+    /// Record a request rejected by admission control
+    ///
+    /// # Arguments
+    /// * `reason` - Why it was rejected (e.g. "rate_limited", "queue_timeout")
+    pub fn record_rejection(reason: &str) {
+        ADMISSION_REJECTED_TOTAL.with_label_values(&[reason]).inc();
+    }
+
     /// Record a batch of operations
     ///
     /// # Arguments
@@ -167,6 +188,13 @@ mod tests {
         // Metrics are recorded, no panic
     }
 
+    #[test]
+    fn test_admission() {
+        MetricsRecorder::set_admission(3, 1, 0.75);
+        MetricsRecorder::record_rejection("rate_limited");
+        // Metrics are recorded, no panic
+    }
+
     #[test]
     fn test_record_batch() {
         MetricsRecorder::record_batch("test", 100, 50, 10);
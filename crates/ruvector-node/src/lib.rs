@@ -7,18 +7,27 @@
 #![warn(clippy::pedantic)]
 
 use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
 use napi_derive::napi;
 use ruvector_core::{
+    arena::{self, GlobalArenaStats},
     types::{DbOptions, HnswConfig, QuantizationConfig},
-    DistanceMetric, SearchQuery, SearchResult, VectorDB as CoreVectorDB, VectorEntry,
+    BoostSpec, BoostedResult, Classification, ClassifyConfig, DistanceMetric, EmbeddingModel,
+    GraphAnalytics, GraphNeighbor, GraphStats, HealthCheckConfig, HealthReport,
+    KnnEdge, NormalizationPolicy, SearchQuery, SearchResult, Subgraph, TimeDecay,
+    VectorDB as CoreVectorDB, VectorEntry, WarmupConfig, WarmupPhase, WarmupProgress, WarmupReport,
 };
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::sync::RwLock;
+use std::sync::{Mutex, OnceLock, RwLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 // Import new crates
 use ruvector_collections::CollectionManager as CoreCollectionManager;
 use ruvector_filter::FilterExpression;
+use ruvector_gnn::query::{GraphQuery, SubGraph};
+use ruvector_gnn::search::{differentiable_search_db, SearchOptions};
 use ruvector_metrics::{gather_metrics, HealthChecker, HealthStatus};
 use std::path::PathBuf;
 
@@ -220,6 +229,464 @@ impl From<SearchResult> for JsSearchResult {
     }
 }
 
+/// Search result ranked by a boost spec
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsBoostedResult {
+    /// Vector ID
+    pub id: String,
+    /// Score after boosting (higher is better)
+    pub score: f64,
+    /// Similarity before boosting (higher is better)
+    pub similarity: f64,
+    /// Metadata as JSON string (use JSON.parse to convert to object)
+    pub metadata: Option<String>,
+}
+
+impl From<BoostedResult> for JsBoostedResult {
+    fn from(boosted: BoostedResult) -> Self {
+        JsBoostedResult {
+            id: boosted.result.id,
+            score: f64::from(boosted.score),
+            similarity: f64::from(boosted.similarity),
+            metadata: boosted
+                .result
+                .metadata
+                .and_then(|m| serde_json::to_string(&m).ok()),
+        }
+    }
+}
+
+/// A node with its in-degree in the k-NN graph
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsHubNode {
+    /// Vector ID
+    pub id: String,
+    /// Number of nodes that list this one as a neighbor
+    pub in_degree: u32,
+}
+
+/// Connectivity summary of the k-NN graph
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsGraphAnalytics {
+    /// Number of nodes
+    pub node_count: u32,
+    /// Number of edges
+    pub edge_count: u32,
+    /// Weakly connected component sizes, largest first
+    pub component_sizes: Vec<u32>,
+    /// Share of nodes in the largest component
+    pub largest_component_fraction: f64,
+    /// In-degree histogram as JSON string (`{"<degree>": <count>}`)
+    pub in_degree_histogram: String,
+    /// Mean in-degree
+    pub mean_in_degree: f64,
+    /// Skewness of the in-degree distribution
+    pub in_degree_skewness: f64,
+    /// IDs no other node points to
+    pub unreachable: Vec<String>,
+    /// Highest in-degree nodes, descending
+    pub hubs: Vec<JsHubNode>,
+}
+
+impl From<GraphAnalytics> for JsGraphAnalytics {
+    fn from(analytics: GraphAnalytics) -> Self {
+        JsGraphAnalytics {
+            node_count: analytics.node_count as u32,
+            edge_count: analytics.edge_count as u32,
+            component_sizes: analytics.component_sizes.iter().map(|&s| s as u32).collect(),
+            largest_component_fraction: analytics.largest_component_fraction,
+            in_degree_histogram: serde_json::to_string(&analytics.in_degree_histogram)
+                .unwrap_or_default(),
+            mean_in_degree: analytics.mean_in_degree,
+            in_degree_skewness: analytics.in_degree_skewness,
+            unreachable: analytics.unreachable,
+            hubs: analytics
+                .hubs
+                .into_iter()
+                .map(|h| JsHubNode {
+                    id: h.id,
+                    in_degree: h.in_degree as u32,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Shape of the HNSW graph
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsGraphStats {
+    /// Number of levels; level 0 holds every node
+    pub levels: u32,
+    /// Live nodes on each level
+    pub nodes_per_level: Vec<u32>,
+    /// Mean out-degree of live nodes on each level
+    pub avg_out_degree: Vec<f64>,
+    /// Node searches start from
+    pub entry_point: Option<String>,
+    /// Live nodes reachable from the entry point on level 0
+    pub reachable_from_entry: u32,
+    /// Live nodes a search can never visit
+    pub unreachable: u32,
+    /// Live nodes without edges on level 0
+    pub isolated_nodes: u32,
+    /// Deleted nodes still linked into the graph
+    pub removed_nodes: u32,
+}
+
+impl From<GraphStats> for JsGraphStats {
+    fn from(stats: GraphStats) -> Self {
+        JsGraphStats {
+            levels: stats.levels as u32,
+            nodes_per_level: stats.nodes_per_level.iter().map(|&n| n as u32).collect(),
+            avg_out_degree: stats.avg_out_degree.iter().map(|&d| d as f64).collect(),
+            entry_point: stats.entry_point,
+            reachable_from_entry: stats.reachable_from_entry as u32,
+            unreachable: stats.unreachable as u32,
+            isolated_nodes: stats.isolated_nodes as u32,
+            removed_nodes: stats.removed_nodes as u32,
+        }
+    }
+}
+
+/// One out-edge of a graph node
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsGraphNeighbor {
+    /// Neighbor ID; absent for a deleted node still linked in the graph
+    pub id: Option<String>,
+    /// Distance between the two nodes
+    pub distance: f64,
+}
+
+impl From<GraphNeighbor> for JsGraphNeighbor {
+    fn from(neighbor: GraphNeighbor) -> Self {
+        JsGraphNeighbor {
+            id: neighbor.id,
+            distance: neighbor.distance as f64,
+        }
+    }
+}
+
+/// A directed edge between two vectors
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsKnnEdge {
+    /// Source vector ID
+    pub source: String,
+    /// Neighbor vector ID
+    pub target: String,
+    /// Distance between the two vectors
+    pub distance: f64,
+}
+
+impl From<KnnEdge> for JsKnnEdge {
+    fn from(edge: KnnEdge) -> Self {
+        JsKnnEdge {
+            source: edge.source,
+            target: edge.target,
+            distance: edge.distance as f64,
+        }
+    }
+}
+
+/// Local graph neighborhood around a set of vectors
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsNeighborhood {
+    /// Node IDs, seeds first, then in order of discovery
+    pub ids: Vec<String>,
+    /// Hops from the nearest seed for each node
+    pub hops: Vec<u32>,
+    /// Directed edges between nodes
+    pub edges: Vec<JsKnnEdge>,
+}
+
+impl From<Subgraph> for JsNeighborhood {
+    fn from(subgraph: Subgraph) -> Self {
+        JsNeighborhood {
+            ids: subgraph.nodes,
+            hops: subgraph.hops.iter().map(|&h| h as u32).collect(),
+            edges: subgraph.edges.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// An edge of a query subgraph
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsSubGraphEdge {
+    /// Index of the source node in `ids`
+    pub from: u32,
+    /// Index of the target node in `ids`
+    pub to: u32,
+    /// Similarity between the two nodes
+    pub weight: f64,
+}
+
+/// Subgraph returned by a graph query
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsSubGraph {
+    /// Vector IDs of the nodes, in discovery order
+    pub ids: Vec<String>,
+    /// Similarity edges between nodes
+    pub edges: Vec<JsSubGraphEdge>,
+}
+
+impl From<SubGraph> for JsSubGraph {
+    fn from(subgraph: SubGraph) -> Self {
+        JsSubGraph {
+            ids: subgraph.ids,
+            edges: subgraph
+                .edges
+                .into_iter()
+                .map(|(from, to, weight)| JsSubGraphEdge {
+                    from: from as u32,
+                    to: to as u32,
+                    weight: weight as f64,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Candidate weighted by a differentiable search
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsSoftMatch {
+    /// Vector ID
+    pub id: String,
+    /// Softmax weight
+    pub weight: f64,
+}
+
+/// Warm-up options
+#[napi(object)]
+pub struct JsWarmupOptions {
+    /// Read every stored vector (default: true)
+    pub touch_storage: Option<bool>,
+    /// Index searches issued from sampled stored vectors (default: 256)
+    pub index_probes: Option<u32>,
+    /// Representative queries to replay
+    pub canary_queries: Option<Vec<JsSearchQuery>>,
+    /// Times the canary set is replayed (default: 3)
+    pub canary_rounds: Option<u32>,
+}
+
+impl JsWarmupOptions {
+    fn to_core(&self) -> Result<WarmupConfig> {
+        let defaults = WarmupConfig::default();
+        let canary_queries = self
+            .canary_queries
+            .iter()
+            .flatten()
+            .map(JsSearchQuery::to_core)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(WarmupConfig {
+            touch_storage: self.touch_storage.unwrap_or(defaults.touch_storage),
+            index_probes: self
+                .index_probes
+                .map_or(defaults.index_probes, |n| n as usize),
+            canary_queries,
+            canary_rounds: self
+                .canary_rounds
+                .map_or(defaults.canary_rounds, |n| n as usize),
+            ..defaults
+        })
+    }
+}
+
+/// Warm-up progress event
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsWarmupProgress {
+    /// Phase: "storage", "index" or "canaries"
+    pub phase: String,
+    /// Units completed in this phase
+    pub done: u32,
+    /// Units in this phase
+    pub total: u32,
+}
+
+impl From<WarmupProgress> for JsWarmupProgress {
+    fn from(progress: WarmupProgress) -> Self {
+        let phase = match progress.phase {
+            WarmupPhase::Storage => "storage",
+            WarmupPhase::Index => "index",
+            WarmupPhase::Canaries => "canaries",
+        };
+        JsWarmupProgress {
+            phase: phase.to_string(),
+            done: progress.done as u32,
+            total: progress.total as u32,
+        }
+    }
+}
+
+/// Warm-up outcome
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsWarmupReport {
+    /// Stored vectors read
+    pub vectors_read: u32,
+    /// Vector bytes read
+    pub bytes_read: f64,
+    /// Index searches issued
+    pub index_probes: u32,
+    /// Canary latencies of the last round in microseconds
+    pub canary_latencies_us: Vec<f64>,
+    /// Median canary latency in microseconds
+    pub canary_p50_us: Option<f64>,
+    /// 99th percentile canary latency in microseconds
+    pub canary_p99_us: Option<f64>,
+    /// Total warm-up time in milliseconds
+    pub elapsed_ms: f64,
+}
+
+impl From<WarmupReport> for JsWarmupReport {
+    fn from(report: WarmupReport) -> Self {
+        JsWarmupReport {
+            vectors_read: report.vectors_read as u32,
+            bytes_read: report.bytes_read as f64,
+            index_probes: report.index_probes as u32,
+            canary_latencies_us: report
+                .canary_latencies_us
+                .iter()
+                .map(|&us| us as f64)
+                .collect(),
+            canary_p50_us: report.canary_p50_us.map(|us| us as f64),
+            canary_p99_us: report.canary_p99_us.map(|us| us as f64),
+            elapsed_ms: report.elapsed_ms as f64,
+        }
+    }
+}
+
+fn health_label(status: ruvector_core::HealthStatus) -> String {
+    match status {
+        ruvector_core::HealthStatus::Healthy => "healthy",
+        ruvector_core::HealthStatus::Degraded => "degraded",
+        ruvector_core::HealthStatus::Unhealthy => "unhealthy",
+    }
+    .to_string()
+}
+
+/// One health check outcome
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsHealthCheck {
+    /// Check name, e.g. "storage_sample"
+    pub name: String,
+    /// "healthy", "degraded" or "unhealthy"
+    pub status: String,
+    /// Human-readable detail
+    pub detail: String,
+}
+
+/// Database health report
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsHealthReport {
+    /// Worst status across checks: "healthy", "degraded" or "unhealthy"
+    pub status: String,
+    /// Whether storage sampling and canary searches ran
+    pub deep: bool,
+    /// Stored entries
+    pub vectors: u32,
+    /// Indexed entries
+    pub indexed: u32,
+    /// Stored entries missing from the index
+    pub index_lag: u32,
+    /// Entries of uncommitted ingest batches
+    pub pending: u32,
+    /// Stored entries read and verified
+    pub sampled: u32,
+    /// IDs of sampled entries that failed verification
+    pub corrupt: Vec<String>,
+    /// Median canary latency in microseconds
+    pub canary_p50_us: Option<f64>,
+    /// Slowest canary in microseconds
+    pub canary_max_us: Option<f64>,
+    /// Share of canaries that found their own vector
+    pub canary_self_recall: Option<f64>,
+    /// Individual checks
+    pub checks: Vec<JsHealthCheck>,
+    /// Time spent in milliseconds
+    pub elapsed_ms: f64,
+}
+
+impl From<HealthReport> for JsHealthReport {
+    fn from(report: HealthReport) -> Self {
+        JsHealthReport {
+            status: health_label(report.status),
+            deep: report.deep,
+            vectors: report.vectors as u32,
+            indexed: report.indexed as u32,
+            index_lag: report.index_lag as u32,
+            pending: report.pending as u32,
+            sampled: report.sampled as u32,
+            corrupt: report.corrupt,
+            canary_p50_us: report.canary_p50_us.map(|us| us as f64),
+            canary_max_us: report.canary_max_us.map(|us| us as f64),
+            canary_self_recall: report.canary_self_recall.map(f64::from),
+            checks: report
+                .checks
+                .into_iter()
+                .map(|check| JsHealthCheck {
+                    name: check.name,
+                    status: health_label(check.status),
+                    detail: check.detail,
+                })
+                .collect(),
+            elapsed_ms: report.elapsed_ms as f64,
+        }
+    }
+}
+
+/// Predicted label of a vector
+#[napi(object)]
+#[derive(Clone)]
+pub struct JsClassification {
+    /// Predicted label
+    pub label: String,
+    /// Distance to the class centroid, or mean distance of the winning votes
+    pub distance: f64,
+    /// Lead over the runner-up class
+    pub margin: f64,
+    /// Softmax probability for centroids, vote share for k-NN
+    pub confidence: f64,
+    /// Members of the class for centroids, votes for k-NN
+    pub support: u32,
+}
+
+impl From<Classification> for JsClassification {
+    fn from(result: Classification) -> Self {
+        JsClassification {
+            label: result.label,
+            distance: result.distance as f64,
+            margin: result.margin as f64,
+            confidence: result.confidence as f64,
+            support: result.support as u32,
+        }
+    }
+}
+
+/// Databases shared with worker threads, keyed by handle string.
+///
+/// The addon is loaded once per process, so every `worker_thread` sees the same
+/// registry and can attach to a database opened on the main thread without
+/// loading a second copy of the index.
+fn shared_registry() -> &'static Mutex<HashMap<String, Arc<RwLock<CoreVectorDB>>>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<String, Arc<RwLock<CoreVectorDB>>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+static NEXT_SHARED_ID: AtomicU64 = AtomicU64::new(1);
+
 /// High-performance vector database with HNSW indexing
 #[napi]
 pub struct VectorDB {
@@ -270,6 +737,67 @@ impl VectorDB {
         })
     }
 
+    /// Share this database with `worker_threads` in the same process
+    ///
+    /// Returns a handle string that can be posted to a worker and passed to
+    /// `VectorDB.fromShared()`. All handles refer to the same in-memory index,
+    /// so workers do not pay for their own copy. Calling `share()` again
+    /// returns the existing handle.
+    ///
+    /// # Example
+    /// ```javascript
+    /// const handle = db.share();
+    /// new Worker('./worker.js', { workerData: { handle } });
+    /// // worker.js
+    /// const db = VectorDB.fromShared(workerData.handle);
+    /// ```
+    #[napi]
+    pub fn share(&self) -> String {
+        let mut registry = shared_registry().lock().expect("Mutex poisoned");
+        if let Some((handle, _)) = registry
+            .iter()
+            .find(|(_, db)| Arc::ptr_eq(db, &self.inner))
+        {
+            return handle.clone();
+        }
+
+        let handle = format!(
+            "ruvector-shared:{}:{}",
+            std::process::id(),
+            NEXT_SHARED_ID.fetch_add(1, Ordering::Relaxed)
+        );
+        registry.insert(handle.clone(), self.inner.clone());
+        handle
+    }
+
+    /// Attach to a database shared by another thread via `share()`
+    ///
+    /// Handles are only valid inside the process that created them.
+    #[napi(factory)]
+    pub fn from_shared(handle: String) -> Result<Self> {
+        let registry = shared_registry().lock().expect("Mutex poisoned");
+        let inner = registry
+            .get(&handle)
+            .cloned()
+            .ok_or_else(|| Error::from_reason(format!("Unknown shared handle: {}", handle)))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Stop sharing a database
+    ///
+    /// Instances already attached with `fromShared()` keep working; the handle
+    /// simply can no longer be used to attach new ones. Returns false if the
+    /// handle was not registered.
+    #[napi]
+    pub fn release_shared(handle: String) -> bool {
+        shared_registry()
+            .lock()
+            .expect("Mutex poisoned")
+            .remove(&handle)
+            .is_some()
+    }
+
     /// Insert a vector entry into the database
     ///
     /// Returns the ID of the inserted vector (auto-generated if not provided)
@@ -348,6 +876,74 @@ impl VectorDB {
         .map(|results| results.into_iter().map(Into::into).collect())
     }
 
+    /// Search and rank by similarity adjusted with metadata boosts
+    ///
+    /// `boost` is a JSON boost spec; results are sorted by boosted score,
+    /// highest first
+    ///
+    /// # Example
+    /// ```javascript
+    /// const results = await db.searchBoosted(
+    ///   { vector: new Float32Array([1, 2, 3]), k: 10 },
+    ///   JSON.stringify({ boosts: [
+    ///     { type: 'lookup', field: 'source', values: { docs: 1.5 } }
+    ///   ] })
+    /// );
+    /// ```
+    #[napi]
+    pub async fn search_boosted(
+        &self,
+        query: JsSearchQuery,
+        boost: String,
+    ) -> Result<Vec<JsBoostedResult>> {
+        let core_query = query.to_core()?;
+        let spec: BoostSpec = serde_json::from_str(&boost)
+            .map_err(|e| Error::from_reason(format!("Invalid boost spec: {}", e)))?;
+        let db = self.inner.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let db = db.read().expect("RwLock poisoned");
+            db.search_boosted(core_query, &spec)
+        })
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+        .map_err(|e| Error::from_reason(format!("Search failed: {}", e)))
+        .map(|results| results.into_iter().map(Into::into).collect())
+    }
+
+    /// Search and rank by similarity blended with recency
+    ///
+    /// `decay` is a JSON time decay spec over a timestamp metadata field;
+    /// results are sorted by blended score, highest first
+    ///
+    /// # Example
+    /// ```javascript
+    /// const results = await db.searchTimeDecayed(
+    ///   { vector: new Float32Array([1, 2, 3]), k: 10 },
+    ///   JSON.stringify({ field: 'createdAt', half_life_secs: 86400, weight: 0.3 })
+    /// );
+    /// ```
+    #[napi]
+    pub async fn search_time_decayed(
+        &self,
+        query: JsSearchQuery,
+        decay: String,
+    ) -> Result<Vec<JsBoostedResult>> {
+        let core_query = query.to_core()?;
+        let decay: TimeDecay = serde_json::from_str(&decay)
+            .map_err(|e| Error::from_reason(format!("Invalid time decay: {}", e)))?;
+        let db = self.inner.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let db = db.read().expect("RwLock poisoned");
+            db.search_time_decayed(core_query, &decay)
+        })
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+        .map_err(|e| Error::from_reason(format!("Search failed: {}", e)))
+        .map(|results| results.into_iter().map(Into::into).collect())
+    }
+
     /// Delete a vector by ID
     ///
     /// Returns true if the vector was deleted, false if not found
@@ -406,6 +1002,82 @@ impl VectorDB {
         }))
     }
 
+    /// Whether a vector with this ID may be stored, answered from memory
+    ///
+    /// `false` means the ID is definitely not stored. `true` may be a false
+    /// positive (about 1%); confirm with `get` when it matters.
+    ///
+    /// # Example
+    /// ```javascript
+    /// if (!db.maybeContains(chunkId)) {
+    ///   await db.insert({ id: chunkId, vector });
+    /// }
+    /// ```
+    #[napi]
+    pub fn maybe_contains(&self, id: String) -> bool {
+        let db = self.inner.read().expect("RwLock poisoned");
+        db.maybe_contains(&id)
+    }
+
+    /// Maintain a centroid for each value of the metadata field `key`
+    ///
+    /// Returns false if the group already existed.
+    ///
+    /// # Example
+    /// ```javascript
+    /// await db.defineCentroidGroup('topic');
+    /// ```
+    #[napi]
+    pub async fn define_centroid_group(&self, key: String) -> Result<bool> {
+        let db = self.inner.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let db = db.read().expect("RwLock poisoned");
+            db.define_centroid_group(&key)
+        })
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+        .map_err(|e| Error::from_reason(format!("Failed to define centroid group: {}", e)))
+    }
+
+    /// Predict the `field` label of a vector from labelled entries
+    ///
+    /// With `k`, the k nearest entries vote; otherwise the nearest class
+    /// centroid wins, which needs a centroid group on `field`. Returns null
+    /// when no labelled entry is available.
+    ///
+    /// # Example
+    /// ```javascript
+    /// await db.defineCentroidGroup('topic');
+    /// const match = await db.classify(embedding, 'topic');
+    /// if (match && match.confidence > 0.8) {
+    ///   await db.insert({ vector: embedding, metadata: { topic: match.label } });
+    /// }
+    /// ```
+    #[napi]
+    pub async fn classify(
+        &self,
+        vector: Float32Array,
+        field: String,
+        k: Option<u32>,
+    ) -> Result<Option<JsClassification>> {
+        let db = self.inner.clone();
+        let vector = vector.to_vec();
+        let config = match k {
+            Some(k) => ClassifyConfig::knn(&field, k as usize),
+            None => ClassifyConfig::centroid(&field),
+        };
+
+        tokio::task::spawn_blocking(move || {
+            let db = db.read().expect("RwLock poisoned");
+            db.classify(&vector, &config)
+        })
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+        .map_err(|e| Error::from_reason(format!("Classification failed: {}", e)))
+        .map(|result| result.map(Into::into))
+    }
+
     /// Get the number of vectors in the database
     ///
     /// # Example
@@ -447,6 +1119,271 @@ impl VectorDB {
         .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
         .map_err(|e| Error::from_reason(format!("IsEmpty failed: {}", e)))
     }
+
+    /// Analyze connectivity of the k-NN graph (components, degrees, hubs)
+    ///
+    /// # Example
+    /// ```javascript
+    /// const stats = await db.analyzeGraph(10, 5);
+    /// console.log(`${stats.componentSizes.length} components`, stats.hubs);
+    /// ```
+    #[napi]
+    pub async fn analyze_graph(&self, k: u32, hubs: Option<u32>) -> Result<JsGraphAnalytics> {
+        let db = self.inner.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let db = db.read().expect("RwLock poisoned");
+            db.export_knn_graph(k as usize)
+                .map(|graph| graph.analyze(hubs.unwrap_or(10) as usize))
+        })
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+        .map_err(|e| Error::from_reason(format!("Graph analysis failed: {}", e)))
+        .map(Into::into)
+    }
+
+    /// Inspect the HNSW graph (levels, degrees, entry point connectivity)
+    ///
+    /// # Example
+    /// ```javascript
+    /// const stats = await db.graphStats();
+    /// console.log(`${stats.unreachable} nodes unreachable from ${stats.entryPoint}`);
+    /// ```
+    #[napi]
+    pub async fn graph_stats(&self) -> Result<JsGraphStats> {
+        let db = self.inner.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let db = db.read().expect("RwLock poisoned");
+            db.graph_stats()
+        })
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+        .map_err(|e| Error::from_reason(format!("Graph stats failed: {}", e)))
+        .map(Into::into)
+    }
+
+    /// Graph neighbors of a vector on a level (default 0), nearest first
+    ///
+    /// # Example
+    /// ```javascript
+    /// const neighbors = await db.neighbors('vector-id', 0);
+    /// ```
+    #[napi]
+    pub async fn neighbors(
+        &self,
+        id: String,
+        level: Option<u32>,
+    ) -> Result<Vec<JsGraphNeighbor>> {
+        let db = self.inner.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let db = db.read().expect("RwLock poisoned");
+            db.neighbors(&id, level.unwrap_or(0) as usize)
+        })
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+        .map_err(|e| Error::from_reason(format!("Neighbors failed: {}", e)))
+        .map(|neighbors| neighbors.into_iter().map(Into::into).collect())
+    }
+
+    /// Extract the graph neighborhood within `hops` (default 1) edges of
+    /// `ids`, with at most `maxNodes` (default 100) nodes
+    ///
+    /// # Example
+    /// ```javascript
+    /// const results = await db.search({ vector: new Float32Array([1, 2, 3]), k: 5 });
+    /// const { ids, edges } = await db.subgraph(results.map((r) => r.id), 2, 200);
+    /// ```
+    #[napi]
+    pub async fn subgraph(
+        &self,
+        ids: Vec<String>,
+        hops: Option<u32>,
+        max_nodes: Option<u32>,
+    ) -> Result<JsNeighborhood> {
+        let db = self.inner.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let db = db.read().expect("RwLock poisoned");
+            db.subgraph(
+                &ids,
+                hops.unwrap_or(1) as usize,
+                max_nodes.unwrap_or(100) as usize,
+            )
+        })
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+        .map_err(|e| Error::from_reason(format!("Subgraph failed: {}", e)))
+        .map(Into::into)
+    }
+
+    /// Run a multi-hop graph query and return the matched subgraph
+    ///
+    /// `query` is a JSON graph query: seeds by id or by vector, then
+    /// `expand` and `filter` steps applied in order
+    ///
+    /// # Example
+    /// ```javascript
+    /// const subgraph = await db.graphQuery(JSON.stringify({
+    ///   seeds: ['doc-1'],
+    ///   steps: [
+    ///     { expand: { k: 8, min_similarity: 0.8 } },
+    ///     { expand: { k: 8, min_similarity: 0.8 } },
+    ///     { filter: { lang: 'en' } }
+    ///   ],
+    ///   max_nodes: 100
+    /// }));
+    /// for (const { from, to, weight } of subgraph.edges) {
+    ///   console.log(`${subgraph.ids[from]} -> ${subgraph.ids[to]}: ${weight}`);
+    /// }
+    /// ```
+    #[napi]
+    pub async fn graph_query(&self, query: String) -> Result<JsSubGraph> {
+        let query: GraphQuery = serde_json::from_str(&query)
+            .map_err(|e| Error::from_reason(format!("Invalid graph query: {}", e)))?;
+        let db = self.inner.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let db = db.read().expect("RwLock poisoned");
+            query.execute(&db)
+        })
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+        .map_err(|e| Error::from_reason(format!("Graph query failed: {}", e)))
+        .map(Into::into)
+    }
+
+    /// Differentiable search over the `pool` (default 100) nearest
+    /// neighbors of `query`, returning the top `k` with softmax weights
+    ///
+    /// Candidates are read from the database instead of being passed in
+    /// from JavaScript.
+    ///
+    /// # Example
+    /// ```javascript
+    /// const matches = await db.differentiableSearch(new Float32Array([1, 2, 3]), 5, 200, 0.5);
+    /// for (const { id, weight } of matches) console.log(id, weight);
+    /// ```
+    #[napi]
+    pub async fn differentiable_search(
+        &self,
+        query: Float32Array,
+        k: u32,
+        pool: Option<u32>,
+        temperature: Option<f64>,
+    ) -> Result<Vec<JsSoftMatch>> {
+        let query = query.to_vec();
+        let options = SearchOptions {
+            k: k as usize,
+            temperature: temperature.unwrap_or(1.0) as f32,
+            ..Default::default()
+        };
+        let db = self.inner.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let db = db.read().expect("RwLock poisoned");
+            differentiable_search_db(&db, &query, pool.unwrap_or(100) as usize, &options)
+        })
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+        .map_err(|e| Error::from_reason(format!("Differentiable search failed: {}", e)))
+        .map(|matches| {
+            matches
+                .into_iter()
+                .map(|(id, weight)| JsSoftMatch {
+                    id,
+                    weight: weight as f64,
+                })
+                .collect()
+        })
+    }
+
+    /// Preload storage and index pages and replay canary queries
+    ///
+    /// `onProgress` is called with `{ phase, done, total }` as each phase advances.
+    ///
+    /// # Example
+    /// ```javascript
+    /// const report = await db.warmup(
+    ///   { canaryQueries: [{ vector: new Float32Array([1, 2, 3]), k: 10 }] },
+    ///   (p) => console.log(`${p.phase}: ${p.done}/${p.total}`)
+    /// );
+    /// console.log(`canary p99: ${report.canaryP99Us}us`);
+    /// ```
+    #[napi]
+    pub async fn warmup(
+        &self,
+        options: Option<JsWarmupOptions>,
+        on_progress: Option<ThreadsafeFunction<JsWarmupProgress, ErrorStrategy::Fatal>>,
+    ) -> Result<JsWarmupReport> {
+        let config = match &options {
+            Some(options) => options.to_core()?,
+            None => WarmupConfig::default(),
+        };
+        let db = self.inner.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let db = db.read().expect("RwLock poisoned");
+            db.warmup_with_progress(&config, |progress| {
+                if let Some(callback) = &on_progress {
+                    callback.call(progress.into(), ThreadsafeFunctionCallMode::NonBlocking);
+                }
+            })
+        })
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+        .map_err(|e| Error::from_reason(format!("Warmup failed: {}", e)))
+        .map(Into::into)
+    }
+
+    /// Write the k-NN graph edge list to a file (`.csv`, or `.json` for the full graph)
+    ///
+    /// Returns the number of edges written.
+    ///
+    /// # Example
+    /// ```javascript
+    /// const edges = await db.exportKnnGraph(10, './knn.csv');
+    /// ```
+    #[napi]
+    pub async fn export_knn_graph(&self, k: u32, path: String) -> Result<u32> {
+        let db = self.inner.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let db = db.read().expect("RwLock poisoned");
+            let graph = db.export_knn_graph(k as usize)?;
+            graph.write_to_file(&path)?;
+            Ok::<_, ruvector_core::RuvectorError>(graph.edge_count() as u32)
+        })
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+        .map_err(|e| Error::from_reason(format!("Export failed: {}", e)))
+    }
+
+    /// Check database health; `deep` also verifies a storage sample and runs
+    /// canary searches
+    ///
+    /// # Example
+    /// ```javascript
+    /// const report = await db.healthCheck(true);
+    /// if (report.status === 'unhealthy') console.error(report.checks);
+    /// ```
+    #[napi]
+    pub async fn health_check(&self, deep: Option<bool>) -> Result<JsHealthReport> {
+        let db = self.inner.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let db = db.read().expect("RwLock poisoned");
+            if deep.unwrap_or(false) {
+                db.health_check_deep(&HealthCheckConfig::default())
+            } else {
+                db.health_check()
+            }
+        })
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))
+        .map(Into::into)
+    }
 }
 
 /// Get the version of the Ruvector library
@@ -455,6 +1392,58 @@ pub fn version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
 
+/// Features available to this build, for diagnosing installation issues
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct JsCapabilities {
+    /// Library version
+    pub version: String,
+    /// Best SIMD instruction set detected: "avx512", "avx2", "sse4.1", "neon" or "scalar"
+    pub simd_level: String,
+    /// Whether SIMD distance kernels are compiled in
+    pub simd_kernels: bool,
+    /// Whether a GPU backend is available
+    pub gpu: bool,
+    /// Storage backend: "redb" or "memory"
+    pub storage_backend: String,
+    /// Whether HNSW indexing is compiled in
+    pub hnsw: bool,
+    /// Whether operations run on a parallel thread pool
+    pub parallel: bool,
+    /// Worker threads available for parallel operations
+    pub threads: u32,
+    /// Quantization codecs
+    pub quantization: Vec<String>,
+    /// Attention mechanisms
+    pub attention: Vec<String>,
+    /// Whether API-based embeddings are compiled in
+    pub api_embeddings: bool,
+}
+
+impl From<ruvector_core::Capabilities> for JsCapabilities {
+    fn from(caps: ruvector_core::Capabilities) -> Self {
+        JsCapabilities {
+            version: caps.version,
+            simd_level: caps.simd_level,
+            simd_kernels: caps.simd_kernels,
+            gpu: caps.gpu,
+            storage_backend: caps.storage_backend,
+            hnsw: caps.hnsw,
+            parallel: caps.parallel,
+            threads: caps.threads as u32,
+            quantization: caps.quantization,
+            attention: caps.attention,
+            api_embeddings: caps.api_embeddings,
+        }
+    }
+}
+
+/// Report enabled features: SIMD level, storage backend, threads, codecs
+#[napi]
+pub fn capabilities() -> JsCapabilities {
+    ruvector_core::capabilities().into()
+}
+
 /// Test function to verify the bindings are working
 #[napi]
 pub fn hello() -> String {
@@ -506,11 +1495,34 @@ pub struct JsCollectionConfig {
     pub hnsw_config: Option<JsHnswConfig>,
     /// Quantization configuration
     pub quantization: Option<JsQuantizationConfig>,
+    /// Embedding model name the collection's vectors come from
+    pub embedding_model: Option<String>,
+    /// Embedding model version
+    pub embedding_model_version: Option<String>,
+    /// Reject searches that declare a different model
+    pub strict_model: Option<bool>,
+    /// Normalization policy: "none", "insert" or "insert_and_query"
+    pub normalization: Option<String>,
+    /// Metrics searches may select besides `distanceMetric`
+    pub secondary_metrics: Option<Vec<JsDistanceMetric>>,
 }
 
-impl From<JsCollectionConfig> for ruvector_collections::CollectionConfig {
-    fn from(config: JsCollectionConfig) -> Self {
-        ruvector_collections::CollectionConfig {
+impl TryFrom<JsCollectionConfig> for ruvector_collections::CollectionConfig {
+    type Error = Error;
+
+    fn try_from(config: JsCollectionConfig) -> Result<Self> {
+        let normalization = match config.normalization.as_deref() {
+            None | Some("none") => NormalizationPolicy::None,
+            Some("insert") => NormalizationPolicy::Insert,
+            Some("insert_and_query") => NormalizationPolicy::InsertAndQuery,
+            Some(other) => {
+                return Err(Error::from_reason(format!(
+                    "Unknown normalization policy: {}",
+                    other
+                )))
+            }
+        };
+        Ok(ruvector_collections::CollectionConfig {
             dimensions: config.dimensions as usize,
             distance_metric: config
                 .distance_metric
@@ -519,7 +1531,20 @@ impl From<JsCollectionConfig> for ruvector_collections::CollectionConfig {
             hnsw_config: config.hnsw_config.map(Into::into),
             quantization: config.quantization.map(Into::into),
             on_disk_payload: true,
-        }
+            embedding_model: config.embedding_model.map(|name| {
+                let model = EmbeddingModel::new(name, config.dimensions as usize);
+                match config.embedding_model_version {
+                    Some(version) => model.with_version(version),
+                    None => model,
+                }
+            }),
+            strict_model: config.strict_model.unwrap_or(false),
+            normalization,
+            secondary_metrics: config
+                .secondary_metrics
+                .map(|metrics| metrics.into_iter().map(Into::into).collect())
+                .unwrap_or_default(),
+        })
     }
 }
 
@@ -601,7 +1626,7 @@ impl CollectionManager {
     /// ```
     #[napi]
     pub async fn create_collection(&self, name: String, config: JsCollectionConfig) -> Result<()> {
-        let core_config: ruvector_collections::CollectionConfig = config.into();
+        let core_config = ruvector_collections::CollectionConfig::try_from(config)?;
         let manager = self.inner.clone();
 
         tokio::task::spawn_blocking(move || {
@@ -744,6 +1769,41 @@ pub struct JsHealthResponse {
     pub version: String,
     /// Uptime in seconds
     pub uptime_seconds: i64,
+    /// Arena allocator usage
+    pub arena: JsArenaStats,
+}
+
+/// Process-wide arena allocator usage
+#[napi(object)]
+pub struct JsArenaStats {
+    /// Arenas not yet dropped
+    pub live_arenas: u32,
+    /// Chunks held by live arenas
+    pub live_chunks: u32,
+    /// Bytes reserved by live arenas
+    pub reserved_bytes: f64,
+    /// Bytes in use across live arenas
+    pub used_bytes: f64,
+    /// Peak bytes in use since process start
+    pub high_water_bytes: f64,
+    /// Share of reserved bytes not in use (0-1)
+    pub fragmentation: f64,
+    /// Per-subsystem breakdown as JSON string (empty object unless debug tracking is on)
+    pub by_tag: String,
+}
+
+impl From<GlobalArenaStats> for JsArenaStats {
+    fn from(stats: GlobalArenaStats) -> Self {
+        JsArenaStats {
+            live_arenas: stats.live_arenas as u32,
+            live_chunks: stats.live_chunks as u32,
+            reserved_bytes: stats.reserved_bytes as f64,
+            used_bytes: stats.used_bytes as f64,
+            high_water_bytes: stats.high_water_bytes as f64,
+            fragmentation: stats.fragmentation,
+            by_tag: serde_json::to_string(&stats.by_tag).unwrap_or_default(),
+        }
+    }
 }
 
 /// Get Prometheus metrics
@@ -779,5 +1839,19 @@ pub fn get_health() -> JsHealthResponse {
         },
         version: health.version,
         uptime_seconds: health.uptime_seconds as i64,
+        arena: arena::global_stats().into(),
     }
 }
+
+/// Attribute arena memory to subsystem tags in `getHealth().arena.byTag`
+///
+/// Only arenas created after enabling are tracked.
+///
+/// # Example
+/// ```javascript
+/// setArenaDebugTracking(true);
+/// ```
+#[napi]
+pub fn set_arena_debug_tracking(enabled: bool) {
+    arena::set_debug_tracking(enabled);
+}
@@ -3,6 +3,7 @@ import { VectorDB } from '../index.js';
 import { mkdtempSync, rmSync } from 'fs';
 import { tmpdir } from 'os';
 import { join } from 'path';
+import { Worker } from 'worker_threads';
 
 // Helper to create temp directory
 function createTempDir() {
@@ -384,3 +385,38 @@ test('VectorDB - concurrent operations', async (t) => {
   t.is(results.length, 10);
   results.forEach((r) => t.truthy(r.length >= 1));
 });
+
+test('VectorDB - share with worker_threads', async (t) => {
+  const tempDir = createTempDir();
+  t.teardown(() => cleanupTempDir(tempDir));
+
+  const db = new VectorDB({
+    dimensions: 3,
+    storagePath: join(tempDir, 'test.db'),
+  });
+  await db.insert({ id: 'shared', vector: new Float32Array([1, 2, 3]) });
+
+  const handle = db.share();
+  t.is(db.share(), handle); // Sharing twice returns the same handle
+
+  const workerSource = `
+    const { parentPort, workerData } = require('worker_threads');
+    const { VectorDB } = require(workerData.binding);
+    const db = VectorDB.fromShared(workerData.handle);
+    db.search({ vector: new Float32Array([1, 2, 3]), k: 1 })
+      .then((results) => parentPort.postMessage(results[0].id));
+  `;
+  const worker = new Worker(workerSource, {
+    eval: true,
+    workerData: { handle, binding: new URL('../index.js', import.meta.url).pathname },
+  });
+  const id = await new Promise((resolve, reject) => {
+    worker.once('message', resolve);
+    worker.once('error', reject);
+  });
+  t.is(id, 'shared');
+
+  t.true(VectorDB.releaseShared(handle));
+  t.false(VectorDB.releaseShared(handle));
+  t.throws(() => VectorDB.fromShared(handle));
+});
@@ -27,6 +27,20 @@ RETURNS jsonb
 AS 'MODULE_PATHNAME', 'ruvector_memory_stats_wrapper'
 LANGUAGE C VOLATILE PARALLEL SAFE;
 
+-- Get index statistics; load_indexes loads every hnsw index into the session
+CREATE OR REPLACE FUNCTION ruvector_index_stats(load_indexes boolean DEFAULT false)
+RETURNS TABLE(name text, index_type text, vector_count bigint, dimensions integer,
+              index_size_mb double precision, fragmentation_pct double precision,
+              recall_estimate double precision)
+AS 'MODULE_PATHNAME', 'ruvector_index_stats_wrapper'
+LANGUAGE C VOLATILE PARALLEL SAFE;
+
+-- Reclaim deleted hnsw node pages, or compact a registered in-memory index
+CREATE OR REPLACE FUNCTION ruvector_index_maintenance(index_name text)
+RETURNS text
+AS 'MODULE_PATHNAME', 'ruvector_index_maintenance_wrapper'
+LANGUAGE C VOLATILE PARALLEL UNSAFE;
+
 -- ============================================================================
 -- Native RuVector Type (pgvector-compatible)
 -- ============================================================================
@@ -735,6 +749,49 @@ RETURNS boolean
 AS 'MODULE_PATHNAME', 'ruvector_sparql_update_wrapper'
 LANGUAGE C VOLATILE PARALLEL SAFE;
 
+-- Managed RDF triple table (terms stored in N-Triples syntax)
+CREATE TABLE IF NOT EXISTS ruvector_rdf_triples (
+    id bigserial PRIMARY KEY,
+    store_name text NOT NULL,
+    subject text NOT NULL,
+    predicate text NOT NULL,
+    object text NOT NULL,
+    graph text
+);
+
+CREATE INDEX IF NOT EXISTS ruvector_rdf_triples_spo
+    ON ruvector_rdf_triples (store_name, subject, predicate, object);
+CREATE INDEX IF NOT EXISTS ruvector_rdf_triples_pos
+    ON ruvector_rdf_triples (store_name, predicate, object, subject);
+CREATE INDEX IF NOT EXISTS ruvector_rdf_triples_osp
+    ON ruvector_rdf_triples (store_name, object, subject, predicate);
+CREATE INDEX IF NOT EXISTS ruvector_rdf_triples_terms
+    ON ruvector_rdf_triples USING gin ((ARRAY[subject, predicate, object]));
+
+-- Execute SPARQL query on an in-memory store, one row per solution
+CREATE OR REPLACE FUNCTION ruvector_sparql_rows(store_name text, query text)
+RETURNS TABLE(row_num bigint, bindings jsonb)
+AS 'MODULE_PATHNAME', 'ruvector_sparql_rows_wrapper'
+LANGUAGE C VOLATILE PARALLEL SAFE;
+
+-- Execute SPARQL query over a store persisted in the managed triple table
+CREATE OR REPLACE FUNCTION ruvector_sparql(store_name text, query text)
+RETURNS TABLE(row_num bigint, bindings jsonb)
+AS 'MODULE_PATHNAME', 'ruvector_sparql_table_wrapper'
+LANGUAGE C VOLATILE PARALLEL UNSAFE;
+
+-- Persist an in-memory store to the managed triple table
+CREATE OR REPLACE FUNCTION ruvector_rdf_persist(store_name text)
+RETURNS bigint
+AS 'MODULE_PATHNAME', 'ruvector_rdf_persist_wrapper'
+LANGUAGE C VOLATILE PARALLEL UNSAFE;
+
+-- Load an in-memory store from the managed triple table
+CREATE OR REPLACE FUNCTION ruvector_rdf_load(store_name text)
+RETURNS bigint
+AS 'MODULE_PATHNAME', 'ruvector_rdf_load_wrapper'
+LANGUAGE C VOLATILE PARALLEL UNSAFE;
+
 -- ============================================================================
 -- Comments
 -- ============================================================================
@@ -742,6 +799,8 @@ LANGUAGE C VOLATILE PARALLEL SAFE;
 COMMENT ON FUNCTION ruvector_version() IS 'Returns RuVector extension version';
 COMMENT ON FUNCTION ruvector_simd_info() IS 'Returns SIMD capability information';
 COMMENT ON FUNCTION ruvector_memory_stats() IS 'Returns memory statistics for the extension';
+COMMENT ON FUNCTION ruvector_index_stats(boolean) IS 'Returns vector count, size, fragmentation and recall estimate per index';
+COMMENT ON FUNCTION ruvector_index_maintenance(text) IS 'Reclaims node pages of deleted rows and compacts tombstoned edges for an index';
 COMMENT ON FUNCTION l2_distance_arr(real[], real[]) IS 'Compute L2 (Euclidean) distance between two vectors';
 COMMENT ON FUNCTION cosine_distance_arr(real[], real[]) IS 'Compute cosine distance between two vectors';
 COMMENT ON FUNCTION cosine_distance_normalized_arr(real[], real[]) IS 'Fast cosine distance for pre-normalized vectors (3x faster)';
@@ -784,6 +843,10 @@ COMMENT ON FUNCTION ruvector_clear_rdf_store(text) IS 'Clear all triples from RD
 COMMENT ON FUNCTION ruvector_delete_rdf_store(text) IS 'Delete RDF triple store completely';
 COMMENT ON FUNCTION ruvector_list_rdf_stores() IS 'List all RDF triple stores';
 COMMENT ON FUNCTION ruvector_sparql_update(text, text) IS 'Execute SPARQL UPDATE operations (INSERT DATA, DELETE DATA, DELETE/INSERT WHERE)';
+COMMENT ON FUNCTION ruvector_sparql_rows(text, text) IS 'Execute SPARQL query on a store, returning one JSONB row per solution';
+COMMENT ON FUNCTION ruvector_sparql(text, text) IS 'Execute SPARQL query over a store persisted in ruvector_rdf_triples';
+COMMENT ON FUNCTION ruvector_rdf_persist(text) IS 'Save an in-memory RDF store to ruvector_rdf_triples';
+COMMENT ON FUNCTION ruvector_rdf_load(text) IS 'Load an RDF store from ruvector_rdf_triples';
 COMMENT ON FUNCTION graph_bipartite_score(real[], real[], real) IS 'Compute bipartite matching score for RAG';
 -- ============================================================================
 -- ============================================================================
@@ -898,3 +961,13 @@ CREATE OPERATOR CLASS ruvector_ip_ops
 
 COMMENT ON OPERATOR CLASS ruvector_ip_ops USING hnsw IS
 'ruvector HNSW operator class for inner product (max similarity)';
+
+-- HNSW Operator Class for a jsonb metadata column, e.g.
+--   CREATE INDEX ON items USING hnsw (embedding, metadata);
+-- `metadata @> '{...}'` quals are then applied during graph traversal
+CREATE OPERATOR CLASS ruvector_jsonb_ops
+    DEFAULT FOR TYPE jsonb USING hnsw AS
+    OPERATOR 2 @> (jsonb, jsonb);
+
+COMMENT ON OPERATOR CLASS ruvector_jsonb_ops USING hnsw IS
+'ruvector HNSW operator class for filtering on jsonb metadata';
@@ -10,8 +10,10 @@ use super::cypher::query as cypher_query;
 use super::traversal::{bfs, shortest_path_dijkstra};
 use super::sparql::{
     get_or_create_store, get_store, delete_store, list_stores,
-    parse_sparql, execute_sparql, Triple,
+    parse_sparql, execute_sparql, SparqlQuery, Triple,
     results::{format_results, ResultFormat},
+    executor::QueryResult,
+    table::{self as rdf_table, format_term, parse_term},
 };
 
 /// Create a new graph
@@ -633,58 +635,150 @@ fn ruvector_sparql_update(
     Ok(true)
 }
 
-// Helper functions for SPARQL operators
-
-fn parse_term(s: &str) -> super::sparql::ast::RdfTerm {
-    use super::sparql::ast::{Iri, RdfTerm};
-
-    let s = s.trim();
-
-    if s.starts_with('<') && s.ends_with('>') {
-        // IRI
-        RdfTerm::Iri(Iri::new(&s[1..s.len() - 1]))
-    } else if s.starts_with("_:") {
-        // Blank node
-        RdfTerm::BlankNode(s[2..].to_string())
-    } else if s.starts_with('"') {
-        // Literal
-        let end_quote = s[1..].find('"').map(|i| i + 1).unwrap_or(s.len() - 1);
-        let value = &s[1..end_quote];
-
-        let remainder = &s[end_quote + 1..];
-        if remainder.starts_with("@") {
-            // Language tag
-            let lang = remainder[1..].to_string();
-            RdfTerm::lang_literal(value, lang)
-        } else if remainder.starts_with("^^<") && remainder.ends_with('>') {
-            // Datatype
-            let datatype = &remainder[3..remainder.len() - 1];
-            RdfTerm::typed_literal(value, Iri::new(datatype))
-        } else {
-            RdfTerm::literal(value)
-        }
-    } else {
-        // Assume IRI without brackets
-        RdfTerm::Iri(Iri::new(s))
+// ============================================================================
+// Set-returning SPARQL and managed-table storage
+// ============================================================================
+
+/// Turn a query result into one JSONB object per row
+///
+/// SELECT yields one object per solution keyed by variable name, ASK a single
+/// `{"ask": bool}` row, CONSTRUCT/DESCRIBE one `{subject, predicate, object}`
+/// row per triple, and updates no rows.
+fn result_rows(result: &QueryResult) -> Vec<JsonValue> {
+    match result {
+        QueryResult::Select(select) => select
+            .bindings
+            .iter()
+            .map(|binding| {
+                let row: serde_json::Map<String, JsonValue> = select
+                    .variables
+                    .iter()
+                    .map(|var| {
+                        let value = binding
+                            .get(var)
+                            .map(|term| JsonValue::String(format_term(term)))
+                            .unwrap_or(JsonValue::Null);
+                        (var.clone(), value)
+                    })
+                    .collect();
+                JsonValue::Object(row)
+            })
+            .collect(),
+        QueryResult::Ask(answer) => vec![json!({ "ask": answer })],
+        QueryResult::Construct(triples) | QueryResult::Describe(triples) => triples
+            .iter()
+            .map(|t| {
+                json!({
+                    "subject": format_term(&t.subject),
+                    "predicate": format!("<{}>", t.predicate.as_str()),
+                    "object": format_term(&t.object)
+                })
+            })
+            .collect(),
+        QueryResult::Update => Vec::new(),
     }
 }
 
-fn format_term(term: &super::sparql::ast::RdfTerm) -> String {
-    use super::sparql::ast::RdfTerm;
+fn run_sparql_rows(
+    store: &super::sparql::TripleStore,
+    parsed: &SparqlQuery,
+) -> Result<Vec<(i64, JsonB)>, String> {
+    let result = execute_sparql(store, parsed)
+        .map_err(|e| format!("Execution error: {}", e))?;
 
-    match term {
-        RdfTerm::Iri(iri) => format!("<{}>", iri.as_str()),
-        RdfTerm::Literal(lit) => {
-            if let Some(lang) = &lit.language {
-                format!("\"{}\"@{}", lit.value, lang)
-            } else if lit.datatype.as_str() != "http://www.w3.org/2001/XMLSchema#string" {
-                format!("\"{}\"^^<{}>", lit.value, lit.datatype.as_str())
-            } else {
-                format!("\"{}\"", lit.value)
-            }
-        }
-        RdfTerm::BlankNode(id) => format!("_:{}", id),
-    }
+    Ok(result_rows(&result)
+        .into_iter()
+        .enumerate()
+        .map(|(i, row)| (i as i64 + 1, JsonB(row)))
+        .collect())
+}
+
+/// Execute a SPARQL query on an in-memory store, one row per solution
+///
+/// # Example
+/// ```sql
+/// SELECT bindings->>'name' AS name
+/// FROM ruvector_sparql_rows('my_store', '
+///     PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+///     SELECT ?name WHERE { ?person foaf:name ?name }
+/// ');
+/// ```
+#[pg_extern]
+fn ruvector_sparql_rows(
+    store_name: &str,
+    query: &str,
+) -> Result<TableIterator<'static, (name!(row_num, i64), name!(bindings, JsonB))>, String> {
+    let store = get_store(store_name)
+        .ok_or_else(|| format!("Triple store '{}' does not exist", store_name))?;
+    let parsed = parse_sparql(query)
+        .map_err(|e| format!("Parse error: {}", e))?;
+
+    Ok(TableIterator::new(run_sparql_rows(&store, &parsed)?))
+}
+
+/// Execute a SPARQL query over the triples persisted for `store_name` in the
+/// managed `ruvector_rdf_triples` table
+///
+/// Named graphs are preserved. Each triple pattern that fixes a term becomes
+/// an indexed lookup, and only the matching rows are loaded into a transient
+/// store for the duration of the call; queries with a pattern that fixes no
+/// term, property paths or DESCRIBE read all of the store's rows. The
+/// in-memory stores of this backend are not touched.
+///
+/// # Example
+/// ```sql
+/// SELECT * FROM ruvector_sparql('my_store', 'SELECT ?s ?o WHERE { ?s <http://example.org/knows> ?o }');
+/// ```
+#[pg_extern(name = "ruvector_sparql")]
+fn ruvector_sparql_table(
+    store_name: &str,
+    query: &str,
+) -> Result<TableIterator<'static, (name!(row_num, i64), name!(bindings, JsonB))>, String> {
+    let parsed = parse_sparql(query)
+        .map_err(|e| format!("Parse error: {}", e))?;
+    let rows = match rdf_table::query_lookups(&parsed) {
+        Some(lookups) => rdf_table::lookup_rows(store_name, &lookups)?,
+        None => rdf_table::load_rows(store_name)?,
+    };
+    let store = super::sparql::TripleStore::new();
+    rdf_table::fill_store(&store, &rows);
+
+    Ok(TableIterator::new(run_sparql_rows(&store, &parsed)?))
+}
+
+/// Persist an in-memory store to the managed `ruvector_rdf_triples` table
+///
+/// Replaces any rows previously saved under the same store name and returns
+/// the number of triples written.
+///
+/// # Example
+/// ```sql
+/// SELECT ruvector_rdf_persist('my_store');
+/// ```
+#[pg_extern]
+fn ruvector_rdf_persist(store_name: &str) -> Result<i64, String> {
+    let store = get_store(store_name)
+        .ok_or_else(|| format!("Triple store '{}' does not exist", store_name))?;
+
+    rdf_table::save_store(store_name, &store)
+}
+
+/// Load a store from the managed `ruvector_rdf_triples` table
+///
+/// The in-memory store is cleared (or created) first and then filled with the
+/// persisted triples. Returns the number of triples loaded.
+///
+/// # Example
+/// ```sql
+/// SELECT ruvector_rdf_load('my_store');
+/// ```
+#[pg_extern]
+fn ruvector_rdf_load(store_name: &str) -> Result<i64, String> {
+    let rows = rdf_table::load_rows(store_name)?;
+    let store = get_or_create_store(store_name);
+    store.clear();
+
+    Ok(rdf_table::fill_store(&store, &rows) as i64)
 }
 
 #[cfg(any(test, feature = "pg_test"))]
@@ -1054,4 +1148,81 @@ mod tests {
 
         ruvector_delete_rdf_store("test_rdf_store");
     }
+
+    #[pg_test]
+    fn test_sparql_rows() {
+        ruvector_create_rdf_store("test_rdf_store");
+
+        ruvector_insert_triple(
+            "test_rdf_store",
+            "<http://example.org/person/1>",
+            "<http://example.org/name>",
+            "\"Alice\"",
+        ).unwrap();
+
+        ruvector_insert_triple(
+            "test_rdf_store",
+            "<http://example.org/person/2>",
+            "<http://example.org/name>",
+            "\"Bob\"",
+        ).unwrap();
+
+        let rows: Vec<_> = ruvector_sparql_rows(
+            "test_rdf_store",
+            "SELECT ?name WHERE { ?p <http://example.org/name> ?name }",
+        ).unwrap().collect();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].0, 1);
+        assert!(rows.iter().all(|(_, b)| b.0.get("name").is_some()));
+
+        ruvector_delete_rdf_store("test_rdf_store");
+    }
+
+    #[pg_test]
+    fn test_rdf_persist_and_load() {
+        ruvector_create_rdf_store("test_rdf_persist");
+
+        ruvector_insert_triple(
+            "test_rdf_persist",
+            "<http://example.org/s>",
+            "<http://example.org/p>",
+            "\"v\"",
+        ).unwrap();
+        ruvector_insert_triple_graph(
+            "test_rdf_persist",
+            "<http://example.org/s>",
+            "<http://example.org/q>",
+            "<http://example.org/o>",
+            "http://example.org/g",
+        ).unwrap();
+
+        assert_eq!(ruvector_rdf_persist("test_rdf_persist").unwrap(), 2);
+
+        let rows: Vec<_> = ruvector_sparql_table(
+            "test_rdf_persist",
+            "SELECT ?o WHERE { <http://example.org/s> <http://example.org/p> ?o }",
+        ).unwrap().collect();
+        assert_eq!(rows.len(), 1);
+        let rows: Vec<_> = ruvector_sparql_table(
+            "test_rdf_persist",
+            "SELECT ?p WHERE { <http://example.org/s> ?p \"v\" }",
+        ).unwrap().collect();
+        assert_eq!(rows.len(), 1);
+        let rows: Vec<_> = ruvector_sparql_table(
+            "other_store",
+            "SELECT ?p WHERE { <http://example.org/s> ?p \"v\" }",
+        ).unwrap().collect();
+        assert!(rows.is_empty());
+
+        ruvector_clear_rdf_store("test_rdf_persist").unwrap();
+        assert_eq!(ruvector_rdf_load("test_rdf_persist").unwrap(), 2);
+
+        let stats = ruvector_rdf_stats("test_rdf_persist").unwrap();
+        let stats_obj = stats.0.as_object().unwrap();
+        assert_eq!(stats_obj["triple_count"].as_u64().unwrap(), 2);
+        assert_eq!(stats_obj["named_graphs"].as_array().unwrap().len(), 1);
+
+        ruvector_delete_rdf_store("test_rdf_persist");
+    }
 }
@@ -24,6 +24,7 @@ pub mod executor;
 pub mod triple_store;
 pub mod functions;
 pub mod results;
+pub mod table;
 
 pub use ast::{
     SparqlQuery, QueryForm, SelectQuery, ConstructQuery, AskQuery, DescribeQuery,
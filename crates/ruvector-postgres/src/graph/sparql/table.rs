@@ -0,0 +1,428 @@
+// Managed-table persistence for RDF triple stores
+//
+// In-memory stores live in the backend that created them. This module copies
+// them to and from the `ruvector_rdf_triples` table (created by the extension
+// script with SPO/POS/OSP b-tree indexes and a GIN index over the terms) so
+// triples survive restarts and are visible to every session. Queries over the
+// table only read the rows their triple patterns can match.
+
+use pgrx::prelude::*;
+use pgrx::PgBuiltInOids;
+use std::collections::BTreeMap;
+
+use super::ast::{
+    GraphPattern, Iri, PropertyPath, QueryBody, RdfTerm, SparqlQuery, TermOrVariable,
+};
+use super::triple_store::{Triple, TripleStore};
+
+/// Name of the managed triples table
+pub const TRIPLES_TABLE: &str = "ruvector_rdf_triples";
+
+/// A triple as stored in the managed table, terms in N-Triples syntax
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TripleRow {
+    pub subject: String,
+    pub predicate: String,
+    pub object: String,
+    pub graph: Option<String>,
+}
+
+impl TripleRow {
+    /// Convert a store triple into its row representation
+    pub fn from_triple(triple: &Triple, graph: Option<&str>) -> Self {
+        Self {
+            subject: format_term(&triple.subject),
+            predicate: format!("<{}>", triple.predicate.as_str()),
+            object: format_term(&triple.object),
+            graph: graph.map(str::to_string),
+        }
+    }
+
+    /// Convert the row back into a store triple
+    pub fn to_triple(&self) -> Triple {
+        let predicate = self
+            .predicate
+            .trim()
+            .trim_start_matches('<')
+            .trim_end_matches('>');
+        Triple::new(
+            parse_term(&self.subject),
+            Iri::new(predicate),
+            parse_term(&self.object),
+        )
+    }
+}
+
+/// Collect every triple of a store, tagged with its named graph
+pub fn store_rows(store: &TripleStore) -> Vec<TripleRow> {
+    let mut rows: Vec<TripleRow> = store
+        .get_default_graph()
+        .iter()
+        .map(|t| TripleRow::from_triple(t, None))
+        .collect();
+
+    for graph in store.list_graphs() {
+        rows.extend(
+            store
+                .get_graph(&graph)
+                .iter()
+                .map(|t| TripleRow::from_triple(t, Some(&graph))),
+        );
+    }
+
+    rows
+}
+
+/// Replace the table contents for `store_name` with the triples of `store`
+///
+/// Returns the number of rows written.
+pub fn save_store(store_name: &str, store: &TripleStore) -> Result<i64, String> {
+    let rows = store_rows(store);
+
+    Spi::connect(|mut client| {
+        client
+            .update(
+                &format!("DELETE FROM {} WHERE store_name = $1", TRIPLES_TABLE),
+                None,
+                Some(vec![text_arg(store_name)]),
+            )
+            .map_err(|e| format!("Failed to clear stored triples: {}", e))?;
+
+        let insert = format!(
+            "INSERT INTO {} (store_name, subject, predicate, object, graph) \
+             VALUES ($1, $2, $3, $4, $5)",
+            TRIPLES_TABLE
+        );
+        for row in &rows {
+            client
+                .update(
+                    &insert,
+                    None,
+                    Some(vec![
+                        text_arg(store_name),
+                        text_arg(&row.subject),
+                        text_arg(&row.predicate),
+                        text_arg(&row.object),
+                        (
+                            PgBuiltInOids::TEXTOID.oid(),
+                            row.graph.as_deref().into_datum(),
+                        ),
+                    ]),
+                )
+                .map_err(|e| format!("Failed to store triple: {}", e))?;
+        }
+
+        Ok(rows.len() as i64)
+    })
+}
+
+/// Read the rows stored for `store_name`
+pub fn load_rows(store_name: &str) -> Result<Vec<TripleRow>, String> {
+    Spi::connect(|client| {
+        let query = format!(
+            "SELECT subject, predicate, object, graph FROM {} \
+             WHERE store_name = $1 ORDER BY id",
+            TRIPLES_TABLE
+        );
+        let table = client
+            .select(&query, None, Some(vec![text_arg(store_name)]))
+            .map_err(|e| format!("Failed to read stored triples: {}", e))?;
+
+        let mut rows = Vec::new();
+        for tuple in table {
+            let get = |name: &str| -> Result<Option<String>, String> {
+                tuple
+                    .get_by_name::<String, _>(name)
+                    .map_err(|e| format!("Failed to read column '{}': {}", name, e))
+            };
+            rows.push(TripleRow {
+                subject: get("subject")?.unwrap_or_default(),
+                predicate: get("predicate")?.unwrap_or_default(),
+                object: get("object")?.unwrap_or_default(),
+                graph: get("graph")?,
+            });
+        }
+        Ok(rows)
+    })
+}
+
+/// Terms a triple pattern fixes, in N-Triples syntax; `None` matches any term
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TripleLookup {
+    pub subject: Option<String>,
+    pub predicate: Option<String>,
+    pub object: Option<String>,
+}
+
+impl TripleLookup {
+    fn is_unbounded(&self) -> bool {
+        self.subject.is_none() && self.predicate.is_none() && self.object.is_none()
+    }
+}
+
+/// Lookups that together cover every triple `query` can match
+///
+/// Returns `None` when the query may need the whole store: DESCRIBE,
+/// updates, property paths other than a plain or inverted predicate, and
+/// patterns that fix no term.
+pub fn query_lookups(query: &SparqlQuery) -> Option<Vec<TripleLookup>> {
+    let mut lookups = Vec::new();
+    match &query.body {
+        QueryBody::Select(select) => pattern_lookups(&select.where_clause, &mut lookups)?,
+        QueryBody::Construct(construct) => {
+            pattern_lookups(&construct.where_clause, &mut lookups)?
+        }
+        QueryBody::Ask(ask) => pattern_lookups(&ask.where_clause, &mut lookups)?,
+        QueryBody::Describe(_) | QueryBody::Update(_) => return None,
+    }
+    lookups.dedup();
+    Some(lookups)
+}
+
+fn pattern_lookups(pattern: &GraphPattern, lookups: &mut Vec<TripleLookup>) -> Option<()> {
+    match pattern {
+        GraphPattern::Empty | GraphPattern::Values(_) => Some(()),
+        GraphPattern::Bgp(triples) => {
+            for triple in triples {
+                let (predicate, inverse) = match &triple.predicate {
+                    PropertyPath::Iri(iri) => (Some(iri), false),
+                    PropertyPath::Variable(_) => (None, false),
+                    PropertyPath::Inverse(path) => match path.as_ref() {
+                        PropertyPath::Iri(iri) => (Some(iri), true),
+                        PropertyPath::Variable(_) => (None, true),
+                        _ => return None,
+                    },
+                    _ => return None,
+                };
+                let (subject, object) = if inverse {
+                    (&triple.object, &triple.subject)
+                } else {
+                    (&triple.subject, &triple.object)
+                };
+                let lookup = TripleLookup {
+                    subject: fixed_term(subject),
+                    predicate: predicate.map(|iri| format!("<{}>", iri.as_str())),
+                    object: fixed_term(object),
+                };
+                if lookup.is_unbounded() {
+                    return None;
+                }
+                lookups.push(lookup);
+            }
+            Some(())
+        }
+        GraphPattern::Join(left, right)
+        | GraphPattern::LeftJoin(left, right, _)
+        | GraphPattern::Union(left, right)
+        | GraphPattern::Minus(left, right) => {
+            pattern_lookups(left, lookups)?;
+            pattern_lookups(right, lookups)
+        }
+        GraphPattern::Filter(inner, _)
+        | GraphPattern::Graph(_, inner)
+        | GraphPattern::Exists(inner, _)
+        | GraphPattern::Bind(_, _, inner)
+        | GraphPattern::Group(inner, _, _) => pattern_lookups(inner, lookups),
+        GraphPattern::SubSelect(select) => pattern_lookups(&select.where_clause, lookups),
+        GraphPattern::Service(..) => None,
+    }
+}
+
+fn fixed_term(term: &TermOrVariable) -> Option<String> {
+    match term {
+        TermOrVariable::Term(term) => Some(format_term(term)),
+        TermOrVariable::Variable(_) | TermOrVariable::BlankNode(_) => None,
+    }
+}
+
+/// Read the rows of `store_name` that match any of `lookups`
+///
+/// Each lookup is an equality query on the fixed columns, served by the
+/// SPO/POS/OSP indexes. Rows come back in insertion order, without
+/// duplicates.
+pub fn lookup_rows(store_name: &str, lookups: &[TripleLookup]) -> Result<Vec<TripleRow>, String> {
+    Spi::connect(|client| {
+        let mut rows = BTreeMap::new();
+        for lookup in lookups {
+            let mut query = format!(
+                "SELECT id, subject, predicate, object, graph FROM {} WHERE store_name = $1",
+                TRIPLES_TABLE
+            );
+            let mut args = vec![text_arg(store_name)];
+            for (column, value) in [
+                ("subject", &lookup.subject),
+                ("predicate", &lookup.predicate),
+                ("object", &lookup.object),
+            ] {
+                if let Some(value) = value {
+                    args.push(text_arg(value));
+                    query.push_str(&format!(" AND {} = ${}", column, args.len()));
+                }
+            }
+
+            let table = client
+                .select(&query, None, Some(args))
+                .map_err(|e| format!("Failed to read stored triples: {}", e))?;
+            for tuple in table {
+                let id = tuple
+                    .get_by_name::<i64, _>("id")
+                    .map_err(|e| format!("Failed to read column 'id': {}", e))?
+                    .unwrap_or_default();
+                if rows.contains_key(&id) {
+                    continue;
+                }
+                let get = |name: &str| -> Result<Option<String>, String> {
+                    tuple
+                        .get_by_name::<String, _>(name)
+                        .map_err(|e| format!("Failed to read column '{}': {}", name, e))
+                };
+                rows.insert(
+                    id,
+                    TripleRow {
+                        subject: get("subject")?.unwrap_or_default(),
+                        predicate: get("predicate")?.unwrap_or_default(),
+                        object: get("object")?.unwrap_or_default(),
+                        graph: get("graph")?,
+                    },
+                );
+            }
+        }
+        Ok(rows.into_values().collect())
+    })
+}
+
+/// Insert rows into an in-memory store, returning how many were added
+pub fn fill_store(store: &TripleStore, rows: &[TripleRow]) -> usize {
+    for row in rows {
+        store.insert_into_graph(row.to_triple(), row.graph.as_deref());
+    }
+    rows.len()
+}
+
+fn text_arg(value: &str) -> (PgOid, Option<pg_sys::Datum>) {
+    (PgBuiltInOids::TEXTOID.oid(), value.into_datum())
+}
+
+/// Parse a term written in N-Triples syntax
+pub fn parse_term(s: &str) -> RdfTerm {
+    let s = s.trim();
+
+    if s.starts_with('<') && s.ends_with('>') {
+        RdfTerm::Iri(Iri::new(&s[1..s.len() - 1]))
+    } else if let Some(id) = s.strip_prefix("_:") {
+        RdfTerm::BlankNode(id.to_string())
+    } else if s.starts_with('"') {
+        let end_quote = s[1..].rfind('"').map(|i| i + 1).unwrap_or(s.len() - 1);
+        let value = &s[1..end_quote];
+
+        let remainder = &s[end_quote + 1..];
+        if let Some(lang) = remainder.strip_prefix('@') {
+            RdfTerm::lang_literal(value, lang.to_string())
+        } else if remainder.starts_with("^^<") && remainder.ends_with('>') {
+            let datatype = &remainder[3..remainder.len() - 1];
+            RdfTerm::typed_literal(value, Iri::new(datatype))
+        } else {
+            RdfTerm::literal(value)
+        }
+    } else {
+        RdfTerm::Iri(Iri::new(s))
+    }
+}
+
+/// Format a term in N-Triples syntax
+pub fn format_term(term: &RdfTerm) -> String {
+    match term {
+        RdfTerm::Iri(iri) => format!("<{}>", iri.as_str()),
+        RdfTerm::Literal(lit) => {
+            if let Some(lang) = &lit.language {
+                format!("\"{}\"@{}", lit.value, lang)
+            } else if lit.datatype.as_str() != "http://www.w3.org/2001/XMLSchema#string" {
+                format!("\"{}\"^^<{}>", lit.value, lit.datatype.as_str())
+            } else {
+                format!("\"{}\"", lit.value)
+            }
+        }
+        RdfTerm::BlankNode(id) => format!("_:{}", id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_round_trip() {
+        let triple = Triple::new(
+            RdfTerm::Iri(Iri::new("http://example.org/s")),
+            Iri::new("http://example.org/p"),
+            RdfTerm::lang_literal("bonjour", "fr".to_string()),
+        );
+        let row = TripleRow::from_triple(&triple, Some("http://example.org/g"));
+        assert_eq!(row.subject, "<http://example.org/s>");
+        assert_eq!(row.predicate, "<http://example.org/p>");
+        assert_eq!(row.object, "\"bonjour\"@fr");
+        assert_eq!(row.to_triple(), triple);
+    }
+
+    #[test]
+    fn test_query_lookups() {
+        let parse = |q: &str| super::super::parse_sparql(q).unwrap();
+        let iri = |s: &str| Some(format!("<http://example.org/{}>", s));
+
+        let query = parse(
+            "SELECT ?p ?q WHERE {
+                 ?p <http://example.org/knows> <http://example.org/alice> .
+                 ?p ?q \"Alice\"
+             }",
+        );
+        assert_eq!(
+            query_lookups(&query).unwrap(),
+            vec![
+                TripleLookup {
+                    subject: None,
+                    predicate: iri("knows"),
+                    object: iri("alice"),
+                },
+                TripleLookup {
+                    subject: None,
+                    predicate: None,
+                    object: Some("\"Alice\"".to_string()),
+                },
+            ]
+        );
+
+        // A pattern fixing nothing needs every triple
+        assert!(query_lookups(&parse("SELECT * WHERE { ?s ?p ?o }")).is_none());
+        assert!(query_lookups(&parse(
+            "SELECT ?o WHERE { <http://example.org/s> <http://example.org/p>+ ?o }"
+        ))
+        .is_none());
+    }
+
+    #[test]
+    fn test_store_rows_keeps_graphs() {
+        let store = TripleStore::new();
+        store.insert(Triple::from_strings(
+            "http://example.org/a",
+            "http://example.org/p",
+            "http://example.org/b",
+        ));
+        store.insert_into_graph(
+            Triple::from_strings(
+                "http://example.org/c",
+                "http://example.org/p",
+                "\"x\"",
+            ),
+            Some("http://example.org/g"),
+        );
+
+        let rows = store_rows(&store);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows.iter().filter(|r| r.graph.is_none()).count(), 1);
+
+        let copy = TripleStore::new();
+        assert_eq!(fill_store(&copy, &rows), 2);
+        assert_eq!(copy.count(), 2);
+        assert_eq!(copy.list_graphs(), vec!["http://example.org/g".to_string()]);
+    }
+}
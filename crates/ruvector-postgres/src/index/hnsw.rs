@@ -328,6 +328,23 @@ impl HnswIndex {
         ef: usize,
         layer: usize,
     ) -> Vec<Neighbor> {
+        self.search_layer_filtered(query, entry_id, ef, layer, None)
+    }
+
+    /// Beam search that only admits nodes accepted by `filter` into the results
+    ///
+    /// Rejected nodes are still expanded so the traversal can route through
+    /// them to matching regions of the graph.
+    fn search_layer_filtered(
+        &self,
+        query: &[f32],
+        entry_id: NodeId,
+        ef: usize,
+        layer: usize,
+        filter: Option<&dyn Fn(NodeId) -> bool>,
+    ) -> Vec<Neighbor> {
+        let accepts = |id: NodeId| filter.map_or(true, |f| f(id));
+
         let mut visited = HashSet::new();
         let mut candidates = BinaryHeap::new();
         let mut results = BinaryHeap::new();
@@ -341,10 +358,12 @@ impl HnswIndex {
             id: entry_id,
             distance: entry_dist,
         });
-        results.push(Neighbor {
-            id: entry_id,
-            distance: -entry_dist,
-        }); // Negative for max-heap
+        if accepts(entry_id) {
+            results.push(Neighbor {
+                id: entry_id,
+                distance: -entry_dist,
+            }); // Negative for max-heap
+        }
 
         while let Some(current) = candidates.pop() {
             let furthest_result = results.peek().map(|n| -n.distance).unwrap_or(f32::MAX);
@@ -386,6 +405,9 @@ impl HnswIndex {
                         id: neighbor_id,
                         distance: dist,
                     });
+                    if !accepts(neighbor_id) {
+                        continue;
+                    }
                     results.push(Neighbor {
                         id: neighbor_id,
                         distance: -dist,
@@ -459,6 +481,33 @@ impl HnswIndex {
 
     /// Search for k nearest neighbors
     pub fn search(&self, query: &[f32], k: usize, ef_search: Option<usize>) -> Vec<(NodeId, f32)> {
+        self.search_with_filter(query, k, ef_search, None)
+    }
+
+    /// Search for k nearest neighbors that satisfy `filter`
+    ///
+    /// The predicate is evaluated during layer-0 traversal rather than on the
+    /// final top-k, so selective filters still return up to `k` matches.
+    pub fn search_filtered<F>(
+        &self,
+        query: &[f32],
+        k: usize,
+        ef_search: Option<usize>,
+        filter: F,
+    ) -> Vec<(NodeId, f32)>
+    where
+        F: Fn(NodeId) -> bool,
+    {
+        self.search_with_filter(query, k, ef_search, Some(&filter))
+    }
+
+    fn search_with_filter(
+        &self,
+        query: &[f32],
+        k: usize,
+        ef_search: Option<usize>,
+        filter: Option<&dyn Fn(NodeId) -> bool>,
+    ) -> Vec<(NodeId, f32)> {
         assert_eq!(query.len(), self.dimensions, "Query dimension mismatch");
 
         let ef = ef_search.unwrap_or(self.config.ef_search).max(k);
@@ -477,7 +526,7 @@ impl HnswIndex {
         }
 
         // Search at layer 0
-        let results = self.search_layer(query, curr_id, ef, 0);
+        let results = self.search_layer_filtered(query, curr_id, ef, 0, filter);
 
         // Return top k
         results
@@ -493,8 +542,88 @@ impl HnswIndex {
     }
 
     /// Delete a vector (marks as deleted, doesn't reclaim space)
+    ///
+    /// Edges pointing at the removed node are left in place as tombstones until
+    /// [`compact`](Self::compact) runs.
     pub fn delete(&self, id: NodeId) -> bool {
-        self.nodes.remove(&id).is_some()
+        let removed = self.nodes.remove(&id).is_some();
+        if removed {
+            self.node_count.fetch_sub(1, AtomicOrdering::Relaxed);
+        }
+        removed
+    }
+
+    /// IDs of all live nodes
+    pub fn ids(&self) -> Vec<NodeId> {
+        self.nodes.iter().map(|entry| *entry.key()).collect()
+    }
+
+    /// Vector dimensions
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Distance metric used by the index
+    pub fn metric(&self) -> DistanceMetric {
+        self.config.metric
+    }
+
+    /// Count `(total_edges, dangling_edges)` across all layers
+    ///
+    /// Dangling edges point at deleted nodes.
+    pub fn edge_stats(&self) -> (usize, usize) {
+        let mut total = 0;
+        let mut dangling = 0;
+        for node in self.nodes.iter() {
+            for layer in &node.neighbors {
+                let neighbors = layer.read();
+                total += neighbors.len();
+                dangling += neighbors
+                    .iter()
+                    .filter(|id| !self.nodes.contains_key(id))
+                    .count();
+            }
+        }
+        (total, dangling)
+    }
+
+    /// Remove dangling edges and repair the entry point
+    ///
+    /// Returns the number of edges removed.
+    pub fn compact(&self) -> usize {
+        let mut removed = 0;
+        for node in self.nodes.iter() {
+            for layer in &node.neighbors {
+                let mut neighbors = layer.write();
+                let before = neighbors.len();
+                neighbors.retain(|id| self.nodes.contains_key(id));
+                removed += before - neighbors.len();
+            }
+        }
+
+        let entry_alive = self
+            .entry_point
+            .read()
+            .map_or(false, |ep| self.nodes.contains_key(&ep));
+        if !entry_alive {
+            let replacement = self
+                .nodes
+                .iter()
+                .max_by_key(|entry| entry.neighbors.len())
+                .map(|entry| (*entry.key(), entry.neighbors.len().saturating_sub(1)));
+            match replacement {
+                Some((id, layer)) => {
+                    *self.entry_point.write() = Some(id);
+                    self.max_layer.store(layer, AtomicOrdering::Relaxed);
+                }
+                None => {
+                    *self.entry_point.write() = None;
+                    self.max_layer.store(0, AtomicOrdering::Relaxed);
+                }
+            }
+        }
+
+        removed
     }
 
     /// Get approximate memory usage in bytes
@@ -572,6 +701,44 @@ mod tests {
         assert!(results[0].1 < 0.01);
     }
 
+    #[test]
+    fn test_search_filtered() {
+        let index = HnswIndex::new(2, HnswConfig::default());
+
+        for i in 0..50 {
+            index.insert(vec![i as f32, 0.0]);
+        }
+
+        // Only odd ids match; the nearest neighbours of the query are even
+        let results = index.search_filtered(&[10.0, 0.0], 5, None, |id| id % 2 == 1);
+        assert_eq!(results.len(), 5);
+        assert!(results.iter().all(|(id, _)| id % 2 == 1));
+        assert!(results[0].1 <= 1.0 + 1e-5);
+
+        let none = index.search_filtered(&[10.0, 0.0], 5, None, |_| false);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_delete_and_compact() {
+        let index = HnswIndex::new(2, HnswConfig::default());
+        let ids: Vec<NodeId> = (0..20).map(|i| index.insert(vec![i as f32, 1.0])).collect();
+
+        for id in ids.iter().step_by(2) {
+            assert!(index.delete(*id));
+        }
+        assert_eq!(index.len(), 10);
+
+        let (total, dangling) = index.edge_stats();
+        assert!(dangling > 0 && dangling <= total);
+
+        assert_eq!(index.compact(), dangling);
+        assert_eq!(index.edge_stats().1, 0);
+
+        let results = index.search(&[3.0, 1.0], 3, None);
+        assert!(results.iter().all(|(id, _)| id % 2 == 1));
+    }
+
     #[test]
     fn test_high_dimensional() {
         let dims = 128;
@@ -9,12 +9,22 @@ use pgrx::pg_sys::{self, Relation, IndexInfo, IndexBuildResult, IndexVacuumInfo,
     Cost, Selectivity, IndexScanDesc, ScanDirection, TIDBitmap, ScanKey,
     IndexUniqueCheck, ItemPointer, Datum, Buffer, BlockNumber, Page,
     IndexAmRoutine, NodeTag, bytea, ItemPointerData, PageHeaderData, Size};
-use pgrx::Internal;
+use pgrx::{FromDatum, Internal, JsonB};
+use std::collections::{HashMap, HashSet};
+use std::ffi::CStr;
 use std::ptr;
 use std::mem::size_of;
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 
 use crate::distance::{DistanceMetric, distance};
-use crate::index::HnswConfig;
+use crate::index::{
+    register_hnsw_index, HnswConfig, HnswIndex, MaintenanceStats, MetadataFilter, NodeId,
+};
+use crate::types::RuVector;
+use super::parallel_build::{self, BuildTuple};
 
 // ============================================================================
 // Page Layout Constants
@@ -26,7 +36,6 @@ const HNSW_MAGIC: u32 = 0x484E5357;
 /// Page type identifiers
 const HNSW_PAGE_META: u8 = 0;
 const HNSW_PAGE_NODE: u8 = 1;
-#[allow(dead_code)]
 const HNSW_PAGE_DELETED: u8 = 2;
 
 /// Maximum neighbors per node (aligned with default M)
@@ -60,6 +69,10 @@ struct HnswMetaPage {
     _padding: u8,
     node_count: u64,
     next_block: BlockNumber,
+    /// Bumped whenever VACUUM removes nodes or compaction moves them
+    generation: u32,
+    /// Time of the build that created the pages
+    build_id: i64,
 }
 
 impl Default for HnswMetaPage {
@@ -77,6 +90,8 @@ impl Default for HnswMetaPage {
             _padding: 0,
             node_count: 0,
             next_block: 1,  // First node page
+            generation: 0,
+            build_id: 0,
         }
     }
 }
@@ -106,17 +121,25 @@ struct HnswNeighbor {
 // ============================================================================
 
 /// State for scanning an HNSW index
-#[allow(dead_code)]
 struct HnswScanState {
-    query_vector: Vec<f32>,
+    /// ORDER BY query vector; without one every matching node is returned
+    query_vector: Option<Vec<f32>>,
+    /// Number of neighbors to fetch in the next search
     k: usize,
     ef_search: usize,
-    metric: DistanceMetric,
-    dimensions: usize,
-    results: Vec<(BlockNumber, ItemPointerData, f32)>,
+    results: Vec<ItemPointerData>,
     current_pos: usize,
+    /// Nodes already returned by earlier searches of this scan
+    returned: HashSet<NodeId>,
+    /// No further results exist
+    exhausted: bool,
+    /// Metadata quals pushed down from the WHERE clause, applied during traversal
+    filter: MetadataFilter,
 }
 
+/// Strategy number of `@>` in the jsonb metadata operator class
+const HNSW_STRATEGY_CONTAINS: u16 = 2;
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -164,28 +187,88 @@ unsafe fn read_metadata(page: Page) -> HnswMetaPage {
 }
 
 /// Write metadata to page
+///
+/// `pd_lower` is moved past the metadata so that full-page images keep it.
 unsafe fn write_metadata(page: Page, meta: &HnswMetaPage) {
     let header = page as *mut PageHeaderData;
     let data_ptr = (header as *mut u8).add(std::mem::size_of::<PageHeaderData>()) as *mut HnswMetaPage;
     ptr::write(data_ptr, *meta);
+    (*header).pd_lower = (size_of::<PageHeaderData>() + size_of::<HnswMetaPage>()) as u16;
 }
 
-/// Allocate a new node page
-#[allow(dead_code)]
-unsafe fn allocate_node_page(
-    index_rel: Relation,
-    vector: &[f32],
-    tid: ItemPointerData,
-    max_layer: usize,
-) -> BlockNumber {
-    // Get a new buffer using InvalidBlockNumber (equivalent to P_NEW)
-    let buffer = pg_sys::ReadBuffer(index_rel, P_NEW_BLOCK);
-    let block = pg_sys::BufferGetBlockNumber(buffer);
+/// Bytes of node data on a node page: header, vector, metadata length and
+/// metadata
+fn node_data_size(dimensions: usize, metadata_len: usize) -> usize {
+    size_of::<HnswNodePageHeader>()
+        + dimensions * size_of::<f32>()
+        + size_of::<u32>()
+        + metadata_len
+}
 
-    pg_sys::LockBuffer(buffer, pg_sys::BUFFER_LOCK_EXCLUSIVE as i32);
-    let page = pg_sys::BufferGetPage(buffer);
+/// Encode a distance metric for the metadata page
+fn metric_code(metric: DistanceMetric) -> u8 {
+    match metric {
+        DistanceMetric::Euclidean => 0,
+        DistanceMetric::Cosine => 1,
+        DistanceMetric::InnerProduct => 2,
+        _ => 0,
+    }
+}
+
+/// Decode a distance metric from the metadata page
+fn metric_from_code(code: u8) -> DistanceMetric {
+    match code {
+        1 => DistanceMetric::Cosine,
+        2 => DistanceMetric::InnerProduct,
+        _ => DistanceMetric::Euclidean,
+    }
+}
+
+/// Schema-qualified name of a relation
+unsafe fn relation_name(rel: Relation) -> String {
+    let relname = CStr::from_ptr((*(*rel).rd_rel).relname.data.as_ptr()).to_string_lossy();
+    let namespace = pg_sys::get_namespace_name((*(*rel).rd_rel).relnamespace);
+    if namespace.is_null() {
+        return relname.into_owned();
+    }
+    format!("{}.{}", CStr::from_ptr(namespace).to_string_lossy(), relname)
+}
+
+/// Distance metric of an index, from its opclass's distance support function
+unsafe fn index_metric(index: Relation) -> DistanceMetric {
+    let proc_name = pg_sys::get_func_name(pg_sys::index_getprocid(index, 1, 1));
+    if proc_name.is_null() {
+        return DistanceMetric::Euclidean;
+    }
+    match CStr::from_ptr(proc_name).to_str().unwrap_or_default() {
+        "ruvector_cosine_distance" => DistanceMetric::Cosine,
+        "ruvector_inner_product" => DistanceMetric::InnerProduct,
+        _ => DistanceMetric::Euclidean,
+    }
+}
+
+/// Register an exclusively locked buffer in a generic WAL record
+///
+/// The returned page is a working copy; `GenericXLogFinish` applies the
+/// changes, logs them and marks the buffers dirty (relations that need no
+/// WAL, such as unlogged ones, are only written). With `full_image` the
+/// whole page is logged, as needed for pages that are (re)initialized;
+/// otherwise only the changed bytes are.
+unsafe fn register_page(
+    state: *mut pg_sys::GenericXLogState,
+    buffer: Buffer,
+    full_image: bool,
+) -> Page {
+    let flags = if full_image {
+        pg_sys::GENERIC_XLOG_FULL_IMAGE as i32
+    } else {
+        0
+    };
+    pg_sys::GenericXLogRegisterBuffer(state, buffer, flags)
+}
 
-    // Initialize page
+/// Initialize `page` as a node page holding one tuple
+unsafe fn write_node(page: Page, tuple: &BuildTuple, max_layer: usize) {
     pg_sys::PageInit(page, pg_sys::BLCKSZ as Size, 0);
 
     // Write node header
@@ -195,21 +278,130 @@ unsafe fn allocate_node_page(
         page_type: HNSW_PAGE_NODE,
         max_layer: max_layer as u8,
         _padding: [0; 2],
-        item_id: tid,
+        item_id: tuple.tid,
     };
     ptr::write(data_ptr as *mut HnswNodePageHeader, node_header);
 
     // Write vector data after header
+    let vector = &tuple.vector;
     let vector_ptr = data_ptr.add(std::mem::size_of::<HnswNodePageHeader>()) as *mut f32;
     for (i, &val) in vector.iter().enumerate() {
-        ptr::write(vector_ptr.add(i), val);
+        ptr::write_unaligned(vector_ptr.add(i), val);
     }
 
-    // Mark buffer dirty and unlock
-    pg_sys::MarkBufferDirty(buffer);
+    // Metadata follows the vector, length first; a zero length means none
+    let metadata = tuple.metadata.as_deref().unwrap_or_default();
+    let metadata_ptr = vector_ptr.add(vector.len()) as *mut u8;
+    ptr::write_unaligned(metadata_ptr as *mut u32, metadata.len() as u32);
+    ptr::copy_nonoverlapping(
+        metadata.as_ptr(),
+        metadata_ptr.add(size_of::<u32>()),
+        metadata.len(),
+    );
+    (*header).pd_lower += node_data_size(vector.len(), metadata.len()) as u16;
+}
+
+/// Append a node page for `tuple` and count it on the metadata page
+///
+/// Both pages change in one WAL record, so after a crash a node page is
+/// either counted on the metadata page or not there at all.
+unsafe fn append_node(
+    index_rel: Relation,
+    meta_buffer: Buffer,
+    meta: &mut HnswMetaPage,
+    tuple: &BuildTuple,
+) {
+    let nblocks =
+        pg_sys::RelationGetNumberOfBlocksInFork(index_rel, pg_sys::ForkNumber::MAIN_FORKNUM);
+    let buffer = if meta.next_block < nblocks {
+        // Reuse a block freed by compaction
+        pg_sys::ReadBuffer(index_rel, meta.next_block)
+    } else {
+        // Get a new buffer using InvalidBlockNumber (equivalent to P_NEW)
+        pg_sys::LockRelationForExtension(index_rel, pg_sys::ExclusiveLock as pg_sys::LOCKMODE);
+        let buffer = pg_sys::ReadBuffer(index_rel, P_NEW_BLOCK);
+        pg_sys::UnlockRelationForExtension(index_rel, pg_sys::ExclusiveLock as pg_sys::LOCKMODE);
+        buffer
+    };
+    let block = pg_sys::BufferGetBlockNumber(buffer);
+    pg_sys::LockBuffer(buffer, pg_sys::BUFFER_LOCK_EXCLUSIVE as i32);
+
+    let state = pg_sys::GenericXLogStart(index_rel);
+    write_node(register_page(state, buffer, true), tuple, 0);
+    meta.node_count += 1;
+    meta.next_block = block + 1;
+    write_metadata(register_page(state, meta_buffer, false), meta);
+    pg_sys::GenericXLogFinish(state);
+
     pg_sys::UnlockReleaseBuffer(buffer);
+}
+
+/// Append a node page for each tuple and record them on the metadata page
+///
+/// The metadata page stays exclusively locked while nodes are added, so
+/// concurrent inserts allocate consecutive blocks.
+unsafe fn store_nodes(index: Relation, tuples: &[BuildTuple]) {
+    let (meta_page, meta_buffer) = get_or_create_meta_page(index, true);
+    let mut meta = read_metadata(meta_page);
+
+    for tuple in tuples {
+        if meta.dimensions == 0 {
+            meta.dimensions = tuple.vector.len() as u32;
+        }
+        if tuple.vector.len() != meta.dimensions as usize {
+            error!(
+                "HNSW: vector has {} dimensions, index expects {}",
+                tuple.vector.len(),
+                meta.dimensions
+            );
+        }
+        let metadata_len = tuple.metadata.as_ref().map_or(0, Vec::len);
+        if node_data_size(tuple.vector.len(), metadata_len)
+            > pg_sys::BLCKSZ as usize - size_of::<PageHeaderData>()
+        {
+            error!(
+                "HNSW: {}-dimensional vector with {} bytes of metadata does not fit on an index page",
+                tuple.vector.len(),
+                metadata_len
+            );
+        }
+
+        append_node(index, meta_buffer, &mut meta, tuple);
+    }
 
-    block
+    pg_sys::UnlockReleaseBuffer(meta_buffer);
+}
+
+/// A node page as read back: heap TID, vector and metadata
+type StoredNode = (ItemPointerData, Vec<f32>, Option<serde_json::Value>);
+
+/// Read a node page; `None` for deleted pages
+unsafe fn read_node(
+    index_rel: Relation,
+    block: BlockNumber,
+    dimensions: usize,
+) -> Option<StoredNode> {
+    let buffer = pg_sys::ReadBuffer(index_rel, block);
+    pg_sys::LockBuffer(buffer, pg_sys::BUFFER_LOCK_SHARE as i32);
+    let page = pg_sys::BufferGetPage(buffer);
+
+    let data_ptr = (page as *const u8).add(size_of::<PageHeaderData>());
+    let header = ptr::read(data_ptr as *const HnswNodePageHeader);
+    let node = (header.page_type == HNSW_PAGE_NODE).then(|| {
+        let vector_ptr = data_ptr.add(size_of::<HnswNodePageHeader>()) as *const f32;
+        let vector = (0..dimensions)
+            .map(|i| ptr::read_unaligned(vector_ptr.add(i)))
+            .collect();
+        let metadata_ptr = vector_ptr.add(dimensions) as *const u8;
+        let metadata_len = ptr::read_unaligned(metadata_ptr as *const u32) as usize;
+        let metadata = (metadata_len > 0)
+            .then(|| std::slice::from_raw_parts(metadata_ptr.add(size_of::<u32>()), metadata_len))
+            .and_then(|bytes| serde_json::from_slice(bytes).ok());
+        (header.item_id, vector, metadata)
+    });
+
+    pg_sys::UnlockReleaseBuffer(buffer);
+    node
 }
 
 /// Read vector from node page
@@ -233,7 +425,7 @@ unsafe fn read_vector(
 
     let mut vector = Vec::with_capacity(dimensions);
     for i in 0..dimensions {
-        vector.push(ptr::read(vector_ptr.add(i)));
+        vector.push(ptr::read_unaligned(vector_ptr.add(i)));
     }
 
     pg_sys::UnlockReleaseBuffer(buffer);
@@ -255,55 +447,276 @@ unsafe fn calculate_distance(
     }
 }
 
+// ============================================================================
+// In-Memory Graph
+// ============================================================================
+
+/// Graph over an index's node pages, as loaded by this backend
+///
+/// Pages store vectors and heap TIDs only. Each backend builds the graph the
+/// first time it uses the index and extends it with pages added since; the
+/// graph is registered under the index name so that `ruvector_index_stats()`
+/// and `ruvector_index_maintenance()` see it.
+struct LoadedIndex {
+    graph: Arc<HnswIndex>,
+    /// Heap TID of each graph node
+    tids: HashMap<NodeId, ItemPointerData>,
+    /// Metadata of graph nodes that have any
+    metadata: HashMap<NodeId, serde_json::Value>,
+    /// Graph node of each node page
+    nodes: HashMap<BlockNumber, NodeId>,
+    /// First node page not yet loaded
+    next_block: BlockNumber,
+    /// Metadata page state the graph was built from
+    build_id: i64,
+    generation: u32,
+    dimensions: u32,
+}
+
+/// Graphs loaded by this backend, keyed by index OID
+static LOADED_INDEXES: Lazy<Mutex<HashMap<pg_sys::Oid, LoadedIndex>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Bring this backend's graph of `index` up to date and run `f` on it
+///
+/// The graph is rebuilt from scratch after a rebuild of the index, after
+/// VACUUM or compaction changed node pages, or when the dimensions were fixed
+/// by the first insert.
+unsafe fn with_loaded_index<R>(index: Relation, f: impl FnOnce(&mut LoadedIndex) -> R) -> R {
+    let mut loaded_indexes = LOADED_INDEXES.lock();
+    let oid = (*index).rd_id;
+    loop {
+        let (meta_page, meta_buffer) = get_meta_page(index);
+        let meta = read_metadata(meta_page);
+        pg_sys::UnlockReleaseBuffer(meta_buffer);
+
+        let current = loaded_indexes.get(&oid).is_some_and(|loaded| {
+            loaded.build_id == meta.build_id
+                && loaded.generation == meta.generation
+                && loaded.dimensions == meta.dimensions
+        });
+        if !current {
+            let config = HnswConfig {
+                m: meta.m as usize,
+                m0: meta.m0 as usize,
+                ef_construction: meta.ef_construction as usize,
+                metric: metric_from_code(meta.metric),
+                ..HnswConfig::default()
+            };
+            loaded_indexes.insert(
+                oid,
+                LoadedIndex {
+                    graph: Arc::new(HnswIndex::new(meta.dimensions as usize, config)),
+                    tids: HashMap::new(),
+                    metadata: HashMap::new(),
+                    nodes: HashMap::new(),
+                    next_block: 1,
+                    build_id: meta.build_id,
+                    generation: meta.generation,
+                    dimensions: meta.dimensions,
+                },
+            );
+        }
+        let loaded = loaded_indexes.get_mut(&oid).expect("graph was just loaded");
+
+        for block in loaded.next_block..meta.next_block {
+            if let Some((tid, vector, metadata)) = read_node(index, block, meta.dimensions as usize)
+            {
+                let id = loaded.graph.insert(vector);
+                loaded.tids.insert(id, tid);
+                if let Some(metadata) = metadata {
+                    loaded.metadata.insert(id, metadata);
+                }
+                loaded.nodes.insert(block, id);
+            }
+        }
+        loaded.next_block = loaded.next_block.max(meta.next_block);
+
+        // Compaction moves node pages while holding the metadata page, so
+        // the pages just read are only consistent if the generation held
+        let (meta_page, meta_buffer) = get_meta_page(index);
+        let after = read_metadata(meta_page);
+        pg_sys::UnlockReleaseBuffer(meta_buffer);
+        if after.build_id == meta.build_id && after.generation == meta.generation {
+            break;
+        }
+    }
+
+    let loaded = loaded_indexes.get_mut(&oid).expect("graph was just loaded");
+    register_hnsw_index(oid, &relation_name(index), loaded.graph.clone());
+    f(loaded)
+}
+
+/// Load the hnsw index `oid` into this backend and register it
+///
+/// Returns false if `oid` is not an hnsw index.
+pub fn load_hnsw_index(oid: pg_sys::Oid) -> bool {
+    unsafe {
+        let lockmode = pg_sys::AccessShareLock as pg_sys::LOCKMODE;
+        let rel = pg_sys::try_relation_open(oid, lockmode);
+        if rel.is_null() {
+            return false;
+        }
+        let is_hnsw = is_hnsw_relation(rel);
+        if is_hnsw {
+            with_loaded_index(rel, |_| ());
+        }
+        pg_sys::relation_close(rel, lockmode);
+        is_hnsw
+    }
+}
+
+/// Whether `rel` is an index of the hnsw access method
+unsafe fn is_hnsw_relation(rel: Relation) -> bool {
+    let hnsw_am = pg_sys::get_am_oid(b"hnsw\0".as_ptr() as *const _, true);
+    (*(*rel).rd_rel).relam == hnsw_am
+}
+
+/// Move live node pages of the hnsw index `oid` over those VACUUM deleted
+///
+/// The metadata page generation is bumped, so every backend rebuilds its
+/// graph without the deleted nodes on next use; this backend does so right
+/// away. The freed blocks at the end of the relation are reused by later
+/// inserts. Returns `None` if `oid` is not an hnsw index.
+pub fn compact_hnsw_index(oid: pg_sys::Oid) -> Option<MaintenanceStats> {
+    let start = std::time::Instant::now();
+    unsafe {
+        // VACUUM holds this lock on the table while it marks pages deleted
+        let heap_oid = pg_sys::IndexGetRelation(oid, true);
+        if heap_oid == pg_sys::InvalidOid {
+            return None;
+        }
+        pg_sys::LockRelationOid(heap_oid, pg_sys::ShareUpdateExclusiveLock as pg_sys::LOCKMODE);
+
+        let lockmode = pg_sys::AccessShareLock as pg_sys::LOCKMODE;
+        let index = pg_sys::relation_open(oid, lockmode);
+        if !is_hnsw_relation(index) {
+            pg_sys::relation_close(index, lockmode);
+            return None;
+        }
+        let dangling = with_loaded_index(index, |loaded| loaded.graph.edge_stats().1);
+
+        // Inserts wait on the metadata page until the pages are in place
+        let (meta_page, meta_buffer) = get_or_create_meta_page(index, true);
+        let mut meta = read_metadata(meta_page);
+        let mut target = 1;
+        let mut moved = 0;
+        for block in 1..meta.next_block {
+            let buffer = pg_sys::ReadBuffer(index, block);
+            pg_sys::LockBuffer(buffer, pg_sys::BUFFER_LOCK_SHARE as i32);
+            let page = pg_sys::BufferGetPage(buffer);
+            let header = (page as *const u8).add(size_of::<PageHeaderData>())
+                as *const HnswNodePageHeader;
+            let live = (*header).page_type == HNSW_PAGE_NODE;
+
+            if live && block != target {
+                let target_buffer = pg_sys::ReadBuffer(index, target);
+                pg_sys::LockBuffer(target_buffer, pg_sys::BUFFER_LOCK_EXCLUSIVE as i32);
+                let state = pg_sys::GenericXLogStart(index);
+                let copy = register_page(state, target_buffer, true);
+                ptr::copy_nonoverlapping(page as *const u8, copy as *mut u8, pg_sys::BLCKSZ as usize);
+                pg_sys::GenericXLogFinish(state);
+                pg_sys::UnlockReleaseBuffer(target_buffer);
+                moved += 1;
+            }
+            pg_sys::UnlockReleaseBuffer(buffer);
+            if live {
+                target += 1;
+            }
+        }
+
+        let freed = (meta.next_block - target) as usize;
+        if freed > 0 {
+            meta.next_block = target;
+            meta.generation = meta.generation.wrapping_add(1);
+            let state = pg_sys::GenericXLogStart(index);
+            write_metadata(register_page(state, meta_buffer, false), &meta);
+            pg_sys::GenericXLogFinish(state);
+        }
+        pg_sys::UnlockReleaseBuffer(meta_buffer);
+
+        let nodes = with_loaded_index(index, |loaded| loaded.graph.len());
+        pg_sys::relation_close(index, lockmode);
+
+        pgrx::log!(
+            "HNSW: compaction moved {} of {} node pages and freed {} blocks",
+            moved,
+            nodes,
+            freed
+        );
+        Some(MaintenanceStats {
+            nodes_updated: moved,
+            connections_optimized: dangling,
+            memory_reclaimed_bytes: freed * pg_sys::BLCKSZ as usize,
+            duration_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+}
+
 // ============================================================================
 // Access Method Callbacks
 // ============================================================================
 
 /// Build callback - builds the index from scratch
+///
+/// Only the heap scan runs in parallel. The graph is built from the node
+/// pages afterwards, by the leader here and by every other backend the first
+/// time it uses the index.
 #[pg_guard]
 unsafe extern "C" fn hnsw_build(
-    _heap: Relation,
+    heap: Relation,
     index: Relation,
-    _index_info: *mut IndexInfo,
+    index_info: *mut IndexInfo,
 ) -> *mut IndexBuildResult {
     pgrx::log!("HNSW: Starting index build");
 
+    // A vector column, optionally followed by a jsonb metadata column
+    let key_columns = (*(*index).rd_index).indnkeyatts;
+    if key_columns > 2 || (key_columns == 2 && *(*index).rd_opcintype.add(1) != pg_sys::JSONBOID) {
+        error!("HNSW: only a jsonb metadata column can follow the vector column");
+    }
+
     // Parse index options
-    let dimensions = 128; // TODO: Extract from index definition
-    let config = HnswConfig::default();
+    let config = HnswConfig {
+        metric: index_metric(index),
+        ..HnswConfig::default()
+    };
 
-    // Initialize metadata page
-    let (page, buffer) = get_or_create_meta_page(index, true);
+    // Initialize metadata page; dimensions are taken from the first vector
+    let (_, buffer) = get_or_create_meta_page(index, true);
+    let state = pg_sys::GenericXLogStart(index);
+    let page = register_page(state, buffer, true);
     pg_sys::PageInit(page, pg_sys::BLCKSZ as Size, 0);
 
     let meta = HnswMetaPage {
-        dimensions: dimensions as u32,
         m: config.m as u16,
         m0: config.m0 as u16,
         ef_construction: config.ef_construction as u32,
-        metric: match config.metric {
-            DistanceMetric::Euclidean => 0,
-            DistanceMetric::Cosine => 1,
-            DistanceMetric::InnerProduct => 2,
-            _ => 0,
-        },
+        metric: metric_code(config.metric),
+        build_id: pg_sys::GetCurrentTimestamp(),
         ..Default::default()
     };
 
     write_metadata(page, &meta);
-    pg_sys::MarkBufferDirty(buffer);
+    pg_sys::GenericXLogFinish(state);
     pg_sys::UnlockReleaseBuffer(buffer);
 
-    // Scan heap and build index
-    // This is a simplified version - full implementation would use IndexBuildHeapScan
-    let tuple_count = 0.0;
+    // Scan the heap, with parallel workers if the planner granted any; node
+    // pages are written by the leader once the scan is over
+    let mut tuples = Vec::new();
+    let heap_tuples =
+        parallel_build::scan_heap_for_build(heap, index, index_info, &mut |tuple| tuples.push(tuple));
+    store_nodes(index, &tuples);
+
+    // Load the graph now so that the new index is registered right away
+    with_loaded_index(index, |_| ());
 
-    pgrx::log!("HNSW: Index build complete, {} tuples indexed", tuple_count as u64);
+    pgrx::log!("HNSW: Index build complete, {} tuples indexed", tuples.len());
 
     // Return build result
     let mut result = PgBox::<IndexBuildResult>::alloc0();
-    result.heap_tuples = tuple_count;
-    result.index_tuples = tuple_count;
+    result.heap_tuples = heap_tuples;
+    result.index_tuples = tuples.len() as f64;
     result.into_pg()
 }
 
@@ -313,13 +726,15 @@ unsafe extern "C" fn hnsw_buildempty(index: Relation) {
     pgrx::log!("HNSW: Building empty index");
 
     // Initialize metadata page only
-    let (page, buffer) = get_or_create_meta_page(index, true);
+    let (_, buffer) = get_or_create_meta_page(index, true);
+    let state = pg_sys::GenericXLogStart(index);
+    let page = register_page(state, buffer, true);
     pg_sys::PageInit(page, pg_sys::BLCKSZ as Size, 0);
 
     let meta = HnswMetaPage::default();
     write_metadata(page, &meta);
 
-    pg_sys::MarkBufferDirty(buffer);
+    pg_sys::GenericXLogFinish(state);
     pg_sys::UnlockReleaseBuffer(buffer);
 }
 
@@ -327,46 +742,95 @@ unsafe extern "C" fn hnsw_buildempty(index: Relation) {
 #[pg_guard]
 unsafe extern "C" fn hnsw_insert(
     index: Relation,
-    _values: *mut Datum,
+    values: *mut Datum,
     isnull: *mut bool,
-    _heap_tid: ItemPointer,
+    heap_tid: ItemPointer,
     _heap: Relation,
     _check_unique: IndexUniqueCheck::Type,
     _index_unchanged: bool,
     _index_info: *mut IndexInfo,
 ) -> bool {
-    // Check for null
-    if *isnull {
+    // NULL vectors are not indexed
+    let Some(tuple) = BuildTuple::from_index_values(index, *heap_tid, values, isnull) else {
         return false;
-    }
-
-    // Get metadata
-    let (meta_page, meta_buffer) = get_meta_page(index);
-    let _meta = read_metadata(meta_page);
-    pg_sys::UnlockReleaseBuffer(meta_buffer);
+    };
 
-    // TODO: Extract vector from datum and insert into graph
-    // For now, just return success
+    store_nodes(index, std::slice::from_ref(&tuple));
     true
 }
 
 /// Bulk delete callback
+///
+/// Node pages of dead heap tuples are marked deleted. The nodes are also
+/// removed from this backend's graph, leaving tombstone edges until
+/// `ruvector_index_maintenance()` compacts it; other backends rebuild their
+/// graphs on next use.
 #[pg_guard]
 unsafe extern "C" fn hnsw_bulkdelete(
-    _info: *mut IndexVacuumInfo,
+    info: *mut IndexVacuumInfo,
     stats: *mut IndexBulkDeleteResult,
-    _callback: IndexBulkDeleteCallback,
-    _callback_state: *mut ::std::os::raw::c_void,
+    callback: IndexBulkDeleteCallback,
+    callback_state: *mut ::std::os::raw::c_void,
 ) -> *mut IndexBulkDeleteResult {
     pgrx::log!("HNSW: Bulk delete called");
 
-    // Return stats (simplified implementation)
-    if stats.is_null() {
-        let new_stats = PgBox::<IndexBulkDeleteResult>::alloc0();
-        new_stats.into_pg()
+    let stats = if stats.is_null() {
+        PgBox::<IndexBulkDeleteResult>::alloc0().into_pg()
     } else {
         stats
-    }
+    };
+    let Some(callback) = callback else {
+        return stats;
+    };
+    let index = (*info).index;
+
+    with_loaded_index(index, |loaded| {
+        let (meta_page, meta_buffer) = get_meta_page(index);
+        let last_block = read_metadata(meta_page).next_block;
+        pg_sys::UnlockReleaseBuffer(meta_buffer);
+
+        let mut removed = 0u64;
+        for block in 1..last_block {
+            let buffer = pg_sys::ReadBuffer(index, block);
+            pg_sys::LockBuffer(buffer, pg_sys::BUFFER_LOCK_EXCLUSIVE as i32);
+            let page = pg_sys::BufferGetPage(buffer);
+            let header = (page as *mut u8).add(size_of::<PageHeaderData>()) as *mut HnswNodePageHeader;
+
+            let mut tid = (*header).item_id;
+            if (*header).page_type == HNSW_PAGE_NODE && callback(&mut tid, callback_state) {
+                let state = pg_sys::GenericXLogStart(index);
+                let page = register_page(state, buffer, false);
+                let header =
+                    (page as *mut u8).add(size_of::<PageHeaderData>()) as *mut HnswNodePageHeader;
+                (*header).page_type = HNSW_PAGE_DELETED;
+                pg_sys::GenericXLogFinish(state);
+                removed += 1;
+                if let Some(id) = loaded.nodes.remove(&block) {
+                    loaded.graph.delete(id);
+                    loaded.tids.remove(&id);
+                    loaded.metadata.remove(&id);
+                }
+            }
+            pg_sys::UnlockReleaseBuffer(buffer);
+        }
+
+        let (meta_page, meta_buffer) = get_or_create_meta_page(index, true);
+        let mut meta = read_metadata(meta_page);
+        meta.node_count = meta.node_count.saturating_sub(removed);
+        if removed > 0 {
+            meta.generation = meta.generation.wrapping_add(1);
+            loaded.generation = meta.generation;
+        }
+        let state = pg_sys::GenericXLogStart(index);
+        write_metadata(register_page(state, meta_buffer, false), &meta);
+        pg_sys::GenericXLogFinish(state);
+        pg_sys::UnlockReleaseBuffer(meta_buffer);
+
+        (*stats).tuples_removed += removed as f64;
+        (*stats).num_index_tuples = meta.node_count as f64;
+    });
+
+    stats
 }
 
 /// Vacuum cleanup callback
@@ -418,24 +882,118 @@ unsafe extern "C" fn hnsw_costestimate(
     *index_pages = (tuples / 100.0).max(1.0);  // Rough estimate
 }
 
+/// Scan state of a scan started by [`hnsw_beginscan`]
+unsafe fn scan_state<'a>(scan: IndexScanDesc) -> &'a mut HnswScanState {
+    &mut *((*scan).opaque as *mut HnswScanState)
+}
+
+/// Add the members of a `metadata @> doc` qual that can be checked during
+/// traversal to `filter`
+///
+/// Strings, booleans and nulls compare the same way in jsonb and serde_json.
+/// Numbers (`1` vs `1.0`), arrays and objects (matched by containment) are
+/// left to the executor's recheck.
+fn add_containment_quals(mut filter: MetadataFilter, doc: &serde_json::Value) -> MetadataFilter {
+    if let Some(members) = doc.as_object() {
+        for (key, value) in members {
+            if value.is_string() || value.is_boolean() || value.is_null() {
+                filter = filter.eq(key.clone(), value.clone());
+            }
+        }
+    }
+    filter
+}
+
+/// Fetch the next batch of results into the scan state
+///
+/// With a query vector the graph is searched for twice as many neighbors as
+/// the previous batch and nodes returned before are skipped, so the scan keeps
+/// going for as long as the executor asks for rows. Without one, every node
+/// passing the filter is returned in one batch.
+unsafe fn fetch_results(index: Relation, state: &mut HnswScanState) {
+    with_loaded_index(index, |loaded| {
+        let metadata = &loaded.metadata;
+        let matches = state.filter.predicate(|id| metadata.get(&id).cloned());
+        state.current_pos = 0;
+
+        let Some(query) = &state.query_vector else {
+            state.results = loaded
+                .tids
+                .iter()
+                .filter(|(id, _)| matches(**id))
+                .map(|(_, tid)| *tid)
+                .collect();
+            state.exhausted = true;
+            return;
+        };
+        if loaded.graph.is_empty() {
+            state.results.clear();
+            state.exhausted = true;
+            return;
+        }
+        if query.len() != loaded.graph.dimensions() {
+            error!(
+                "HNSW: query has {} dimensions, index expects {}",
+                query.len(),
+                loaded.graph.dimensions()
+            );
+        }
+
+        let hits = loaded.graph.search_filtered(
+            query,
+            state.k,
+            Some(state.ef_search.max(state.k)),
+            matches,
+        );
+        state.exhausted = hits.len() < state.k;
+        state.k *= 2;
+        state.results = hits
+            .into_iter()
+            .filter(|(id, _)| state.returned.insert(*id))
+            .filter_map(|(id, _)| loaded.tids.get(&id).copied())
+            .collect();
+    });
+}
+
 /// Get tuple callback (for index scans)
+///
+/// Returns heap tuples nearest first. Metadata quals are applied during graph
+/// traversal, so rows failing them never take up a top-k slot; the executor
+/// still rechecks the quals.
 #[pg_guard]
-unsafe extern "C" fn hnsw_gettuple(_scan: IndexScanDesc, _direction: ScanDirection::Type) -> bool {
-    pgrx::log!("HNSW: Get tuple called");
+unsafe extern "C" fn hnsw_gettuple(scan: IndexScanDesc, _direction: ScanDirection::Type) -> bool {
+    let state = scan_state(scan);
+    while state.current_pos == state.results.len() {
+        if state.exhausted {
+            return false;
+        }
+        fetch_results((*scan).indexRelation, state);
+    }
 
-    // TODO: Implement actual index scan
-    // For now, return false (no more tuples)
-    false
+    (*scan).xs_heaptid = state.results[state.current_pos];
+    (*scan).xs_recheck = (*scan).numberOfKeys > 0;
+    (*scan).xs_recheckorderby = false;
+    state.current_pos += 1;
+    true
 }
 
 /// Get bitmap callback (for bitmap scans)
+///
+/// Only used without ORDER BY: adds every node passing the metadata quals.
 #[pg_guard]
-unsafe extern "C" fn hnsw_getbitmap(_scan: IndexScanDesc, _tbm: *mut TIDBitmap) -> i64 {
-    pgrx::log!("HNSW: Get bitmap called");
+unsafe extern "C" fn hnsw_getbitmap(scan: IndexScanDesc, tbm: *mut TIDBitmap) -> i64 {
+    let state = scan_state(scan);
+    if !state.exhausted {
+        fetch_results((*scan).indexRelation, state);
+    }
 
-    // TODO: Implement bitmap scan
-    // Return number of tuples
-    0
+    pg_sys::tbm_add_tuples(
+        tbm,
+        state.results.as_mut_ptr(),
+        state.results.len() as i32,
+        true,
+    );
+    state.results.len() as i64
 }
 
 /// Begin scan callback
@@ -445,37 +1003,89 @@ unsafe extern "C" fn hnsw_beginscan(
     nkeys: ::std::os::raw::c_int,
     norderbys: ::std::os::raw::c_int,
 ) -> IndexScanDesc {
-    pgrx::log!("HNSW: Begin scan");
-
     let scan = pg_sys::RelationGetIndexScan(index, nkeys, norderbys);
+    let state = HnswScanState {
+        query_vector: None,
+        k: 0,
+        ef_search: crate::EF_SEARCH.get().max(1) as usize,
+        results: Vec::new(),
+        current_pos: 0,
+        returned: HashSet::new(),
+        exhausted: true,
+        filter: MetadataFilter::new(),
+    };
+    (*scan).opaque = Box::into_raw(Box::new(state)) as *mut ::std::os::raw::c_void;
     scan
 }
 
 /// Rescan callback
+///
+/// Takes the query vector from the ORDER BY key and builds the metadata
+/// filter from `@>` quals on the jsonb column.
 #[pg_guard]
 unsafe extern "C" fn hnsw_rescan(
-    _scan: IndexScanDesc,
-    _keys: ScanKey,
-    _nkeys: ::std::os::raw::c_int,
-    _orderbys: ScanKey,
-    _norderbys: ::std::os::raw::c_int,
+    scan: IndexScanDesc,
+    keys: ScanKey,
+    nkeys: ::std::os::raw::c_int,
+    orderbys: ScanKey,
+    norderbys: ::std::os::raw::c_int,
 ) {
-    pgrx::log!("HNSW: Rescan");
-    // Reset scan state
+    if !keys.is_null() && nkeys > 0 {
+        ptr::copy(keys, (*scan).keyData, nkeys as usize);
+    }
+    if !orderbys.is_null() && norderbys > 0 {
+        ptr::copy(orderbys, (*scan).orderByData, norderbys as usize);
+    }
+
+    let state = scan_state(scan);
+    state.filter = MetadataFilter::new();
+    state.exhausted = false;
+    for i in 0..(*scan).numberOfKeys as usize {
+        let key = &*(*scan).keyData.add(i);
+        if key.sk_flags & pg_sys::SK_ISNULL as i32 != 0 {
+            // `@>` against NULL matches nothing
+            state.exhausted = true;
+        } else if key.sk_attno == 2 && key.sk_strategy == HNSW_STRATEGY_CONTAINS {
+            if let Some(doc) =
+                JsonB::from_polymorphic_datum(key.sk_argument, false, pg_sys::JSONBOID)
+            {
+                state.filter = add_containment_quals(std::mem::take(&mut state.filter), &doc.0);
+            }
+        }
+    }
+
+    state.query_vector = None;
+    if (*scan).numberOfOrderBys > 0 {
+        let key = &*(*scan).orderByData;
+        state.query_vector = RuVector::from_polymorphic_datum(
+            key.sk_argument,
+            key.sk_flags & pg_sys::SK_ISNULL as i32 != 0,
+            pg_sys::InvalidOid,
+        )
+        .map(RuVector::into_vec);
+    }
+
+    state.k = state.ef_search;
+    state.results.clear();
+    state.current_pos = 0;
+    state.returned.clear();
 }
 
 /// End scan callback
 #[pg_guard]
-unsafe extern "C" fn hnsw_endscan(_scan: IndexScanDesc) {
-    pgrx::log!("HNSW: End scan");
-    // Clean up scan state
+unsafe extern "C" fn hnsw_endscan(scan: IndexScanDesc) {
+    let state = (*scan).opaque as *mut HnswScanState;
+    if !state.is_null() {
+        drop(Box::from_raw(state));
+        (*scan).opaque = ptr::null_mut();
+    }
 }
 
 /// Can return callback - indicates if index can return indexed data
 #[pg_guard]
-unsafe extern "C" fn hnsw_canreturn(_index: Relation, attno: ::std::os::raw::c_int) -> bool {
-    // HNSW can return the vector column
-    attno == 1
+unsafe extern "C" fn hnsw_canreturn(_index: Relation, _attno: ::std::os::raw::c_int) -> bool {
+    // Scans return heap TIDs only, never index tuples
+    false
 }
 
 /// Options callback - parse index options
@@ -501,14 +1111,14 @@ static HNSW_AM_HANDLER: IndexAmRoutine = IndexAmRoutine {
     type_: NodeTag::T_IndexAmRoutine,
 
     // Index structure capabilities
-    amstrategies: 1,              // One strategy: nearest neighbor
+    amstrategies: 2,              // Nearest neighbor, metadata containment
     amsupport: 1,                 // One support function: distance
     amoptsprocnum: 0,
     amcanorder: false,
     amcanorderbyop: true,         // Supports ORDER BY with distance operators
     amcanbackward: false,
     amcanunique: false,
-    amcanmulticol: false,         // Single column only (vector)
+    amcanmulticol: true,          // Vector plus optional jsonb metadata
     amoptionalkey: true,
     amsearcharray: false,
     amsearchnulls: false,
@@ -549,7 +1159,7 @@ static HNSW_AM_HANDLER: IndexAmRoutine = IndexAmRoutine {
     amparallelrescan: None,
     // PG17 additions
     #[cfg(any(feature = "pg17"))]
-    amcanbuildparallel: false,
+    amcanbuildparallel: true,     // Heap scan shared by maintenance workers
     #[cfg(any(feature = "pg17"))]
     aminsertcleanup: None,
 };
@@ -605,3 +1215,101 @@ mod tests {
         assert!(std::mem::size_of::<HnswNodePageHeader>() < 100);
     }
 }
+
+#[cfg(any(test, feature = "pg_test"))]
+#[pg_schema]
+mod pg_tests {
+    use pgrx::prelude::*;
+
+    /// Create the access method and the L2 and jsonb metadata operator
+    /// classes unless the extension script already did
+    fn ensure_hnsw_am() {
+        Spi::run(
+            "DO $$ BEGIN
+                IF NOT EXISTS (SELECT 1 FROM pg_operator WHERE oprname = '<->'
+                               AND oprleft = 'ruvector'::regtype) THEN
+                    CREATE OPERATOR <-> (LEFTARG = ruvector, RIGHTARG = ruvector,
+                                         FUNCTION = ruvector_l2_distance, COMMUTATOR = '<->');
+                END IF;
+                IF NOT EXISTS (SELECT 1 FROM pg_am WHERE amname = 'hnsw') THEN
+                    CREATE ACCESS METHOD hnsw TYPE INDEX HANDLER hnsw_handler;
+                END IF;
+                IF NOT EXISTS (SELECT 1 FROM pg_opclass WHERE opcname = 'ruvector_l2_ops') THEN
+                    CREATE OPERATOR CLASS ruvector_l2_ops
+                        DEFAULT FOR TYPE ruvector USING hnsw AS
+                        OPERATOR 1 <-> (ruvector, ruvector) FOR ORDER BY float_ops,
+                        FUNCTION 1 ruvector_l2_distance(ruvector, ruvector);
+                END IF;
+                IF NOT EXISTS (SELECT 1 FROM pg_opclass WHERE opcname = 'ruvector_jsonb_ops') THEN
+                    CREATE OPERATOR CLASS ruvector_jsonb_ops
+                        DEFAULT FOR TYPE jsonb USING hnsw AS
+                        OPERATOR 2 @> (jsonb, jsonb);
+                END IF;
+            END $$;",
+        )
+        .unwrap();
+    }
+
+    #[pg_test]
+    fn test_hnsw_index_registered_on_build() {
+        ensure_hnsw_am();
+        Spi::run("CREATE TABLE hnsw_stats_items (id int, embedding ruvector)").unwrap();
+        Spi::run(
+            "INSERT INTO hnsw_stats_items
+             SELECT i, ('[' || i % 17 || ',' || i % 13 || ',' || i % 7 || ']')::ruvector
+             FROM generate_series(1, 200) i",
+        )
+        .unwrap();
+        Spi::run("CREATE INDEX hnsw_stats_idx ON hnsw_stats_items USING hnsw (embedding)").unwrap();
+
+        let (count, dims, recall) = Spi::get_three::<i64, i32, f64>(
+            "SELECT vector_count, dimensions, recall_estimate
+             FROM ruvector_index_stats() WHERE name = 'hnsw_stats_idx'",
+        )
+        .unwrap();
+        assert_eq!(count, Some(200));
+        assert_eq!(dims, Some(3));
+        assert!(recall.unwrap() > 0.5);
+
+        let report = Spi::get_one::<String>("SELECT ruvector_index_maintenance('hnsw_stats_idx')")
+            .unwrap()
+            .unwrap();
+        assert!(report.starts_with("Maintenance completed"), "{}", report);
+    }
+
+    #[pg_test]
+    fn test_hnsw_scan_filters_on_metadata() {
+        ensure_hnsw_am();
+        Spi::run("CREATE TABLE hnsw_filter_items (id int, embedding ruvector, metadata jsonb)")
+            .unwrap();
+        // Odd ids are books, even ids music; id i lies at distance i from the query
+        Spi::run(
+            "INSERT INTO hnsw_filter_items
+             SELECT i, ('[' || i || ',0]')::ruvector,
+                    jsonb_build_object('category', CASE WHEN i % 2 = 1 THEN 'books' ELSE 'music' END)
+             FROM generate_series(1, 300) i",
+        )
+        .unwrap();
+        Spi::run(
+            "CREATE INDEX hnsw_filter_idx ON hnsw_filter_items USING hnsw (embedding, metadata)",
+        )
+        .unwrap();
+        Spi::run("SET enable_seqscan = off").unwrap();
+
+        let query = "SELECT id FROM hnsw_filter_items WHERE metadata @> '{\"category\": \"music\"}'
+                     ORDER BY embedding <-> '[0,0]' LIMIT 10";
+        let plan = Spi::explain(query).unwrap().0.to_string();
+        assert!(
+            plan.contains("\"Index Name\":\"hnsw_filter_idx\""),
+            "{}",
+            plan
+        );
+
+        let mut ids =
+            Spi::get_one::<Vec<i32>>(&format!("SELECT array_agg(id) FROM ({}) AS nearest", query))
+                .unwrap()
+                .unwrap();
+        ids.sort_unstable();
+        assert_eq!(ids, (1..=10).map(|i| 2 * i).collect::<Vec<_>>());
+    }
+}
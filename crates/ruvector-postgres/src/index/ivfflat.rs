@@ -313,6 +313,59 @@ impl IvfFlatIndex {
         results
     }
 
+    /// Search for k nearest neighbors that satisfy `filter`
+    ///
+    /// Non-matching entries are skipped while scanning the probed lists, so the
+    /// heap only ever holds candidates that can be returned.
+    pub fn search_filtered<F>(
+        &self,
+        query: &[f32],
+        k: usize,
+        probes: Option<usize>,
+        filter: F,
+    ) -> Vec<(VectorId, f32)>
+    where
+        F: Fn(VectorId) -> bool,
+    {
+        assert_eq!(query.len(), self.dimensions, "Query dimension mismatch");
+
+        if !self.is_trained() {
+            return Vec::new();
+        }
+
+        let n_probes = probes.unwrap_or(self.config.probes);
+        let centroids = self.centroids.read();
+
+        let mut centroid_dists: Vec<(usize, f32)> = centroids
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (i, self.calc_distance(query, c)))
+            .collect();
+
+        centroid_dists.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+
+        drop(centroids);
+
+        let mut heap = BinaryHeap::new();
+
+        for (cluster_id, _) in centroid_dists.iter().take(n_probes) {
+            if let Some(list) = self.lists.get(cluster_id) {
+                for entry in list.iter().filter(|e| filter(e.id)) {
+                    let dist = self.calc_distance(query, &entry.vector);
+                    heap.push(SearchResult { id: entry.id, distance: dist });
+
+                    if heap.len() > k {
+                        heap.pop();
+                    }
+                }
+            }
+        }
+
+        let mut results: Vec<_> = heap.into_iter().map(|r| (r.id, r.distance)).collect();
+        results.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+        results
+    }
+
     /// Parallel search
     pub fn search_parallel(&self, query: &[f32], k: usize, probes: Option<usize>) -> Vec<(VectorId, f32)> {
         assert_eq!(query.len(), self.dimensions, "Query dimension mismatch");
@@ -391,6 +444,41 @@ impl IvfFlatIndex {
         let centroid_bytes = self.config.lists * self.dimensions * 4;
         vector_bytes + centroid_bytes
     }
+
+    /// IDs of all indexed vectors
+    pub fn ids(&self) -> Vec<VectorId> {
+        self.id_to_cluster.iter().map(|entry| *entry.key()).collect()
+    }
+
+    /// Vector dimensions
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Distance metric used by the index
+    pub fn metric(&self) -> DistanceMetric {
+        self.config.metric
+    }
+
+    /// Count `(total_lists, empty_lists)`
+    pub fn list_stats(&self) -> (usize, usize) {
+        let total = self.lists.len();
+        let empty = self.lists.iter().filter(|list| list.is_empty()).count();
+        (total, empty)
+    }
+
+    /// Release spare capacity held by the inverted lists
+    ///
+    /// Returns the approximate number of bytes reclaimed.
+    pub fn shrink_lists(&self) -> usize {
+        let entry_size = std::mem::size_of::<ClusterEntry>();
+        let mut reclaimed = 0;
+        for mut list in self.lists.iter_mut() {
+            reclaimed += (list.capacity() - list.len()) * entry_size;
+            list.shrink_to_fit();
+        }
+        reclaimed
+    }
 }
 
 // ============================================================================
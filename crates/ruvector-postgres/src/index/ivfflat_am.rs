@@ -31,6 +31,7 @@ use std::ptr;
 use std::ffi::CStr;
 
 use crate::distance::{DistanceMetric, distance};
+use super::parallel_build;
 use super::scan::parse_distance_metric;
 
 // ============================================================================
@@ -389,9 +390,11 @@ unsafe extern "C" fn ambuild(
     let mut training_sample: Vec<Vec<f32>> = Vec::new();
     let mut all_vectors: Vec<(pg_sys::ItemPointerData, Vec<f32>)> = Vec::new();
 
-    // Scan heap to collect vectors
-    // TODO: Implement proper heap scan using table_beginscan_catalog
-    // For now, this is a placeholder
+    // Scan heap to collect vectors, with parallel workers if the planner
+    // granted any
+    let heap_tuples = parallel_build::scan_heap_for_build(heap, index, index_info, &mut |tuple| {
+        all_vectors.push((tuple.tid, tuple.vector))
+    });
 
     info!("IVFFlat: Collected {} vectors for training", all_vectors.len());
 
@@ -427,6 +430,7 @@ unsafe extern "C" fn ambuild(
     let mut lists: Vec<Vec<(pg_sys::ItemPointerData, Vec<f32>)>> =
         vec![Vec::new(); n_clusters];
 
+    let index_tuples = all_vectors.len();
     for (tid, vector) in all_vectors {
         let cluster = find_nearest_centroid(&vector, &centroids, metric);
         lists[cluster].push((tid, vector));
@@ -447,8 +451,8 @@ unsafe extern "C" fn ambuild(
     // Return build result
     let result = pg_sys::palloc0(std::mem::size_of::<pg_sys::IndexBuildResult>())
         as *mut pg_sys::IndexBuildResult;
-    (*result).heap_tuples = 0.0;
-    (*result).index_tuples = 0.0;
+    (*result).heap_tuples = heap_tuples;
+    (*result).index_tuples = index_tuples as f64;
 
     result
 }
@@ -619,7 +623,7 @@ unsafe fn ruivfflat_handler(_fcinfo: pg_sys::FunctionCallInfo) -> pg_sys::Datum
     (*amroutine).amclusterable = false;
     (*amroutine).ampredlocks = false;
     (*amroutine).amcanparallel = false;
-    (*amroutine).amcanbuildparallel = false;
+    (*amroutine).amcanbuildparallel = true; // Heap scan shared by maintenance workers
     (*amroutine).amcaninclude = false;
     (*amroutine).amusemaintenanceworkmem = false;
     (*amroutine).amsummarizing = false;
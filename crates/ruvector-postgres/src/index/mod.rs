@@ -9,6 +9,7 @@ mod scan;
 
 // Access Method implementations
 mod hnsw_am;
+mod parallel_build;
 // mod ivfflat_am;  // Enable after hnsw_am is fixed
 // mod ivfflat_storage;
 // pub mod parallel;
@@ -16,10 +17,18 @@ mod hnsw_am;
 // pub mod parallel_ops;
 
 pub use hnsw::*;
+pub use hnsw_am::{compact_hnsw_index, load_hnsw_index};
 pub use ivfflat::*;
 pub use scan::*;
 
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use pgrx::pg_sys;
+
+use crate::distance::DistanceMetric;
 
 /// Global index memory tracking
 static INDEX_MEMORY_BYTES: AtomicUsize = AtomicUsize::new(0);
@@ -39,6 +48,46 @@ pub fn track_index_deallocation(bytes: usize) {
     INDEX_MEMORY_BYTES.fetch_sub(bytes, Ordering::Relaxed);
 }
 
+/// In-memory index registered for statistics and maintenance
+#[derive(Clone)]
+pub enum RegisteredIndex {
+    Hnsw(Arc<HnswIndex>),
+    IvfFlat(Arc<IvfFlatIndex>),
+}
+
+/// Registry of in-memory indexes, keyed by relation OID, with their names
+///
+/// `hnsw` access-method indexes are registered under their schema-qualified
+/// name when this backend builds or opens them.
+static INDEX_REGISTRY: Lazy<DashMap<pg_sys::Oid, (String, RegisteredIndex)>> =
+    Lazy::new(DashMap::new);
+
+/// Register an HNSW index for relation `oid`, replacing any previous entry
+pub fn register_hnsw_index(oid: pg_sys::Oid, name: &str, index: Arc<HnswIndex>) {
+    INDEX_REGISTRY.insert(oid, (name.to_string(), RegisteredIndex::Hnsw(index)));
+}
+
+/// Register an IVFFlat index for relation `oid`, replacing any previous entry
+pub fn register_ivfflat_index(oid: pg_sys::Oid, name: &str, index: Arc<IvfFlatIndex>) {
+    INDEX_REGISTRY.insert(oid, (name.to_string(), RegisteredIndex::IvfFlat(index)));
+}
+
+/// Remove an index from the registry
+pub fn unregister_index(oid: pg_sys::Oid) -> bool {
+    INDEX_REGISTRY.remove(&oid).is_some()
+}
+
+/// Look up a registered index
+pub fn get_registered_index(oid: pg_sys::Oid) -> Option<RegisteredIndex> {
+    INDEX_REGISTRY.get(&oid).map(|entry| entry.1.clone())
+}
+
+/// Number of sampled queries used for recall estimation
+pub const RECALL_SAMPLE_QUERIES: usize = 32;
+
+/// k used for recall estimation
+pub const RECALL_K: usize = 10;
+
 /// Index statistics
 #[derive(Debug, Clone)]
 pub struct IndexStats {
@@ -47,14 +96,131 @@ pub struct IndexStats {
     pub vector_count: i64,
     pub dimensions: i32,
     pub index_size_mb: f64,
+    /// HNSW: share of edges pointing at deleted nodes; IVFFlat: share of empty lists
     pub fragmentation_pct: f64,
+    /// Recall@k of sampled self-queries against exact search, if measurable
+    pub recall_estimate: Option<f64>,
+}
+
+impl RegisteredIndex {
+    /// Compute statistics for this index
+    pub fn stats(&self, name: &str) -> IndexStats {
+        match self {
+            RegisteredIndex::Hnsw(index) => {
+                let (edges, dangling) = index.edge_stats();
+                IndexStats {
+                    name: name.to_string(),
+                    index_type: "hnsw".to_string(),
+                    vector_count: index.len() as i64,
+                    dimensions: index.dimensions() as i32,
+                    index_size_mb: index.memory_usage() as f64 / (1024.0 * 1024.0),
+                    fragmentation_pct: percent(dangling, edges),
+                    recall_estimate: estimate_recall(
+                        &index.ids(),
+                        |id| index.get_vector(id),
+                        index.metric(),
+                        |q, k| index.search(q, k, None),
+                    ),
+                }
+            }
+            RegisteredIndex::IvfFlat(index) => {
+                let (lists, empty) = index.list_stats();
+                IndexStats {
+                    name: name.to_string(),
+                    index_type: "ruivfflat".to_string(),
+                    vector_count: index.len() as i64,
+                    dimensions: index.dimensions() as i32,
+                    index_size_mb: index.memory_usage() as f64 / (1024.0 * 1024.0),
+                    fragmentation_pct: percent(empty, lists),
+                    recall_estimate: estimate_recall(
+                        &index.ids(),
+                        |id| index.get_vector(id),
+                        index.metric(),
+                        |q, k| index.search(q, k, None),
+                    ),
+                }
+            }
+        }
+    }
 }
 
-/// Get statistics for all indexes
-pub fn get_all_index_stats() -> Vec<IndexStats> {
-    // This would query PostgreSQL's system catalogs
-    // For now, return empty
-    Vec::new()
+fn percent(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / total as f64
+    }
+}
+
+/// Estimate recall@k by querying with stored vectors
+///
+/// Up to [`RECALL_SAMPLE_QUERIES`] ids, spread evenly over `ids`, are used as
+/// queries; each approximate result set is compared with the exact top k,
+/// which is found in a single pass over the vectors. Only the query vectors
+/// are held at once. Returns `None` for an empty index.
+pub fn estimate_recall<V, S>(
+    ids: &[u64],
+    vector_of: V,
+    metric: DistanceMetric,
+    search: S,
+) -> Option<f64>
+where
+    V: Fn(u64) -> Option<Vec<f32>>,
+    S: Fn(&[f32], usize) -> Vec<(u64, f32)>,
+{
+    let mut ids = ids.to_vec();
+    ids.sort_unstable();
+    let step = (ids.len() / RECALL_SAMPLE_QUERIES).max(1);
+    let queries: Vec<Vec<f32>> = ids
+        .iter()
+        .step_by(step)
+        .filter_map(|&id| vector_of(id))
+        .take(RECALL_SAMPLE_QUERIES)
+        .collect();
+    if queries.is_empty() {
+        return None;
+    }
+
+    // Exact top k of every query, nearest first
+    let k = RECALL_K.min(ids.len());
+    let mut exact: Vec<Vec<(f32, u64)>> = vec![Vec::with_capacity(k + 1); queries.len()];
+    for &id in &ids {
+        let Some(vector) = vector_of(id) else {
+            continue;
+        };
+        for (query, top) in queries.iter().zip(exact.iter_mut()) {
+            let d = crate::distance::distance(query, &vector, metric);
+            if top.len() == k && top.last().is_some_and(|&(worst, _)| d >= worst) {
+                continue;
+            }
+            let at = top.partition_point(|&(other, _)| other <= d);
+            top.insert(at, (d, id));
+            top.truncate(k);
+        }
+    }
+
+    let mut hits = 0usize;
+    let mut expected = 0usize;
+    for (query, top) in queries.iter().zip(&exact) {
+        let truth: std::collections::HashSet<u64> = top.iter().map(|&(_, id)| id).collect();
+        hits += search(query, truth.len())
+            .iter()
+            .filter(|(id, _)| truth.contains(id))
+            .count();
+        expected += truth.len();
+    }
+
+    Some(hits as f64 / expected as f64)
+}
+
+/// Get statistics for all registered indexes, with their relation OIDs
+pub fn get_all_index_stats() -> Vec<(pg_sys::Oid, IndexStats)> {
+    let mut stats: Vec<(pg_sys::Oid, IndexStats)> = INDEX_REGISTRY
+        .iter()
+        .map(|entry| (*entry.key(), entry.value().1.stats(&entry.value().0)))
+        .collect();
+    stats.sort_by(|a, b| a.1.name.cmp(&b.1.name));
+    stats
 }
 
 /// Maintenance result
@@ -66,13 +232,102 @@ pub struct MaintenanceStats {
     pub duration_ms: u64,
 }
 
-/// Perform index maintenance
-pub fn perform_maintenance(_index_name: &str) -> Result<MaintenanceStats, String> {
-    // Would perform actual maintenance operations
+/// Perform maintenance on a registered in-memory index
+///
+/// HNSW indexes drop edges to deleted nodes and repair their entry point;
+/// IVFFlat indexes release spare list capacity. Only this backend's copy is
+/// changed; see [`compact_hnsw_index`] for `hnsw` access-method indexes.
+pub fn perform_maintenance(oid: pg_sys::Oid) -> Result<MaintenanceStats, String> {
+    let index = get_registered_index(oid)
+        .ok_or_else(|| format!("Index {} is not registered", oid))?;
+    let start = std::time::Instant::now();
+
+    let stats = match index {
+        RegisteredIndex::Hnsw(index) => {
+            let before = index.memory_usage();
+            let removed = index.compact();
+            MaintenanceStats {
+                nodes_updated: index.len(),
+                connections_optimized: removed,
+                memory_reclaimed_bytes: removed * std::mem::size_of::<NodeId>()
+                    + before.saturating_sub(index.memory_usage()),
+                duration_ms: 0,
+            }
+        }
+        RegisteredIndex::IvfFlat(index) => MaintenanceStats {
+            nodes_updated: 0,
+            connections_optimized: 0,
+            memory_reclaimed_bytes: index.shrink_lists(),
+            duration_ms: 0,
+        },
+    };
+
     Ok(MaintenanceStats {
-        nodes_updated: 0,
-        connections_optimized: 0,
-        memory_reclaimed_bytes: 0,
-        duration_ms: 0,
+        duration_ms: start.elapsed().as_millis() as u64,
+        ..stats
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_index_stats_and_maintenance() {
+        let index = Arc::new(HnswIndex::new(2, HnswConfig::default()));
+        for i in 0..30 {
+            index.insert(vec![i as f32, (i % 3) as f32]);
+        }
+        let oid = pg_sys::Oid::from(900_001);
+        register_hnsw_index(oid, "test_stats_hnsw", index.clone());
+
+        let stats = get_registered_index(oid).unwrap().stats("test_stats_hnsw");
+        assert_eq!(stats.vector_count, 30);
+        assert_eq!(stats.dimensions, 2);
+        assert_eq!(stats.fragmentation_pct, 0.0);
+        assert!(stats.recall_estimate.unwrap() > 0.5);
+
+        for id in 0..10 {
+            index.delete(id);
+        }
+        let fragmented = get_registered_index(oid).unwrap().stats("test_stats_hnsw");
+        assert!(fragmented.fragmentation_pct > 0.0);
+
+        let result = perform_maintenance(oid).unwrap();
+        assert!(result.connections_optimized > 0);
+        let compacted = get_registered_index(oid).unwrap().stats("test_stats_hnsw");
+        assert_eq!(compacted.fragmentation_pct, 0.0);
+        assert_eq!(compacted.vector_count, 20);
+
+        assert!(unregister_index(oid));
+        assert!(perform_maintenance(oid).is_err());
+    }
+
+    #[test]
+    fn test_estimate_recall_exact_search() {
+        let vectors: Vec<Vec<f32>> = (0..20).map(|i| vec![i as f32]).collect();
+        let ids: Vec<u64> = (0..20).collect();
+        let exact = |q: &[f32], k: usize| {
+            let mut all: Vec<(u64, f32)> = vectors
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (i as u64, (v[0] - q[0]).abs()))
+                .collect();
+            all.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+            all.truncate(k);
+            all
+        };
+
+        let recall = estimate_recall(
+            &ids,
+            |id| vectors.get(id as usize).cloned(),
+            DistanceMetric::Manhattan,
+            exact,
+        );
+        assert_eq!(recall, Some(1.0));
+        assert_eq!(
+            estimate_recall(&[], |_| None, DistanceMetric::Euclidean, |_, _| Vec::new()),
+            None
+        );
+    }
+}
@@ -0,0 +1,610 @@
+//! Parallel index build for the HNSW and IVFFlat access methods
+//!
+//! `CREATE INDEX ... USING hnsw` (or `ruivfflat`) asks PostgreSQL for
+//! maintenance workers (`amcanbuildparallel`). The granted workers share a parallel heap scan,
+//! extract the indexed values and ship them to the leader over one
+//! shared-memory queue each. Only the leader writes index pages, once the
+//! workers are done and parallel mode has ended.
+//!
+//! ## Shared memory layout
+//!
+//! - [`PARALLEL_KEY_SHARED`]: [`ParallelBuildShared`] (relation OIDs, lock mode)
+//! - [`PARALLEL_KEY_TABLE_SCAN`]: the parallel table scan descriptor
+//! - [`PARALLEL_KEY_QUEUES`]: one `shm_mq` of [`QUEUE_SIZE`] bytes per worker
+
+use pgrx::itemptr::{item_pointer_get_both, item_pointer_set_all};
+use pgrx::pg_sys::{self, Datum, IndexInfo, ItemPointer, ItemPointerData, Relation};
+use pgrx::prelude::*;
+use pgrx::{FromDatum, JsonB};
+use std::ffi::c_void;
+use std::mem::size_of;
+use std::ptr;
+
+use crate::types::RuVector;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Library that holds the worker entry point
+const LIBRARY_NAME: &[u8] = b"$libdir/ruvector\0";
+
+/// Worker entry point, looked up by name in each worker
+const WORKER_MAIN: &[u8] = b"ruhnsw_parallel_build_main\0";
+
+/// shm_toc keys
+const PARALLEL_KEY_SHARED: u64 = 0xB000_0000_0000_0001;
+const PARALLEL_KEY_TABLE_SCAN: u64 = 0xB000_0000_0000_0002;
+const PARALLEL_KEY_QUEUES: u64 = 0xB000_0000_0000_0003;
+
+/// Size of each worker's tuple queue
+const QUEUE_SIZE: usize = 1 << 20;
+
+/// `ALIGNOF_BUFFER` from pg_config_manual.h
+const ALIGNOF_BUFFER: usize = 32;
+
+/// Minimum tuples per build worker; below this the coordination overhead wins
+const MIN_TUPLES_PER_BUILD_WORKER: i64 = 50_000;
+
+/// Approximate memory (KB) a build worker needs per 10k vectors of 128 dims
+const BUILD_WORKER_MEM_KB_PER_10K: i64 = 8 * 1024;
+
+// ============================================================================
+// Build Tuples
+// ============================================================================
+
+/// One indexed heap tuple: its TID, vector and optional metadata
+#[derive(Clone)]
+pub struct BuildTuple {
+    pub tid: ItemPointerData,
+    pub vector: Vec<f32>,
+    /// jsonb metadata column serialized as JSON text, if the index has one
+    pub metadata: Option<Vec<u8>>,
+}
+
+impl BuildTuple {
+    /// Read the indexed values of a heap tuple; `None` if the vector is NULL
+    ///
+    /// A second key column holds jsonb metadata; NULL metadata is `None`.
+    pub unsafe fn from_index_values(
+        index: Relation,
+        tid: ItemPointerData,
+        values: *mut Datum,
+        isnull: *mut bool,
+    ) -> Option<Self> {
+        let vector = RuVector::from_polymorphic_datum(*values, *isnull, pg_sys::InvalidOid)?;
+        let metadata = if (*(*index).rd_index).indnkeyatts > 1 {
+            JsonB::from_polymorphic_datum(*values.add(1), *isnull.add(1), pg_sys::JSONBOID)
+                .and_then(|doc| serde_json::to_vec(&doc.0).ok())
+        } else {
+            None
+        };
+        Some(Self {
+            tid,
+            vector: vector.into_vec(),
+            metadata,
+        })
+    }
+}
+
+/// Message sent from a worker to the leader
+#[derive(Clone)]
+enum BuildMessage {
+    /// A tuple to index
+    Tuple(BuildTuple),
+    /// The worker's scan is complete, having seen `heap_tuples` heap tuples
+    Done { heap_tuples: f64 },
+}
+
+const MESSAGE_TUPLE: u8 = 0;
+const MESSAGE_DONE: u8 = 1;
+
+impl BuildMessage {
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            BuildMessage::Tuple(tuple) => {
+                let (block, offset) = item_pointer_get_both(tuple.tid);
+                let metadata = tuple.metadata.as_deref().unwrap_or_default();
+                let mut buf = Vec::with_capacity(15 + tuple.vector.len() * 4 + metadata.len());
+                buf.push(MESSAGE_TUPLE);
+                buf.extend_from_slice(&block.to_ne_bytes());
+                buf.extend_from_slice(&offset.to_ne_bytes());
+                buf.extend_from_slice(&(tuple.vector.len() as u32).to_ne_bytes());
+                for x in &tuple.vector {
+                    buf.extend_from_slice(&x.to_ne_bytes());
+                }
+                // Length 0 means no metadata; serialized JSON is never empty
+                buf.extend_from_slice(&(metadata.len() as u32).to_ne_bytes());
+                buf.extend_from_slice(metadata);
+                buf
+            }
+            BuildMessage::Done { heap_tuples } => {
+                let mut buf = vec![MESSAGE_DONE];
+                buf.extend_from_slice(&heap_tuples.to_ne_bytes());
+                buf
+            }
+        }
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let (&tag, rest) = bytes.split_first()?;
+        match tag {
+            MESSAGE_TUPLE => {
+                let block = u32::from_ne_bytes(rest.get(0..4)?.try_into().ok()?);
+                let offset = u16::from_ne_bytes(rest.get(4..6)?.try_into().ok()?);
+                let mut tid = ItemPointerData::default();
+                item_pointer_set_all(&mut tid, block, offset);
+                let dims = u32::from_ne_bytes(rest.get(6..10)?.try_into().ok()?) as usize;
+                let data = rest.get(10..10 + dims * 4)?;
+                let vector = data
+                    .chunks_exact(4)
+                    .map(|c| f32::from_ne_bytes(c.try_into().unwrap()))
+                    .collect();
+                let rest = &rest[10 + dims * 4..];
+                let len = u32::from_ne_bytes(rest.get(0..4)?.try_into().ok()?) as usize;
+                let metadata = rest.get(4..)?;
+                if metadata.len() != len {
+                    return None;
+                }
+                Some(BuildMessage::Tuple(BuildTuple {
+                    tid,
+                    vector,
+                    metadata: (len > 0).then(|| metadata.to_vec()),
+                }))
+            }
+            MESSAGE_DONE => Some(BuildMessage::Done {
+                heap_tuples: f64::from_ne_bytes(rest.try_into().ok()?),
+            }),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// Heap Scan
+// ============================================================================
+
+/// Callback state handed to the table AM during the heap scan
+struct ScanState<'a> {
+    sink: &'a mut dyn FnMut(BuildTuple),
+}
+
+#[pg_guard]
+unsafe extern "C" fn build_callback(
+    index: Relation,
+    tid: ItemPointer,
+    values: *mut Datum,
+    isnull: *mut bool,
+    _tuple_is_alive: bool,
+    state: *mut c_void,
+) {
+    let state = &mut *(state as *mut ScanState);
+    if let Some(tuple) = BuildTuple::from_index_values(index, *tid, values, isnull) {
+        (state.sink)(tuple);
+    }
+}
+
+/// Run the table AM's index build scan, passing every tuple to `sink`
+///
+/// With a null `scan` the table AM scans the whole heap itself; otherwise it
+/// consumes (and ends) the given parallel scan. Returns the number of heap
+/// tuples seen.
+unsafe fn scan_heap(
+    heap: Relation,
+    index: Relation,
+    index_info: *mut IndexInfo,
+    scan: pg_sys::TableScanDesc,
+    sink: &mut dyn FnMut(BuildTuple),
+) -> f64 {
+    let mut state = ScanState { sink };
+    let build_range_scan = (*(*heap).rd_tableam)
+        .index_build_range_scan
+        .expect("table access method supports index builds");
+    build_range_scan(
+        heap,
+        index,
+        index_info,
+        true,
+        false,
+        true,
+        0,
+        pg_sys::InvalidBlockNumber,
+        Some(build_callback),
+        &mut state as *mut ScanState as *mut c_void,
+        scan,
+    )
+}
+
+/// Scan `heap` for an index build, in parallel when the planner granted
+/// maintenance workers
+///
+/// Every indexable tuple is passed to `sink` in the leader. Returns the
+/// number of heap tuples seen.
+pub unsafe fn scan_heap_for_build(
+    heap: Relation,
+    index: Relation,
+    index_info: *mut IndexInfo,
+    sink: &mut dyn FnMut(BuildTuple),
+) -> f64 {
+    let nworkers = (*index_info).ii_ParallelWorkers;
+    if nworkers > 0 {
+        if let Some(heap_tuples) = parallel_scan(heap, index, index_info, nworkers, sink) {
+            return heap_tuples;
+        }
+    }
+    scan_heap(heap, index, index_info, ptr::null_mut(), sink)
+}
+
+// ============================================================================
+// Leader
+// ============================================================================
+
+/// State shared with every worker
+#[repr(C)]
+struct ParallelBuildShared {
+    heap_oid: pg_sys::Oid,
+    index_oid: pg_sys::Oid,
+    is_concurrent: bool,
+    queue_size: usize,
+}
+
+/// Lock modes for the heap and index, matching the leader's
+fn build_lock_modes(is_concurrent: bool) -> (pg_sys::LOCKMODE, pg_sys::LOCKMODE) {
+    if is_concurrent {
+        (
+            pg_sys::ShareUpdateExclusiveLock as pg_sys::LOCKMODE,
+            pg_sys::RowExclusiveLock as pg_sys::LOCKMODE,
+        )
+    } else {
+        (
+            pg_sys::ShareLock as pg_sys::LOCKMODE,
+            pg_sys::AccessExclusiveLock as pg_sys::LOCKMODE,
+        )
+    }
+}
+
+/// `shm_toc_estimate_chunk`
+unsafe fn estimate_chunk(pcxt: *mut pg_sys::ParallelContext, size: usize) {
+    let estimator = &mut (*pcxt).estimator;
+    let aligned = (size + ALIGNOF_BUFFER - 1) & !(ALIGNOF_BUFFER - 1);
+    estimator.space_for_chunks = pg_sys::add_size(estimator.space_for_chunks, aligned);
+}
+
+/// `shm_toc_estimate_keys`
+unsafe fn estimate_keys(pcxt: *mut pg_sys::ParallelContext, count: usize) {
+    let estimator = &mut (*pcxt).estimator;
+    estimator.number_of_keys = pg_sys::add_size(estimator.number_of_keys, count);
+}
+
+/// Launch workers and collect their tuples
+///
+/// Returns `None` if no dynamic shared memory was available, in which case
+/// the caller falls back to a serial scan.
+unsafe fn parallel_scan(
+    heap: Relation,
+    index: Relation,
+    index_info: *mut IndexInfo,
+    nworkers: i32,
+    sink: &mut dyn FnMut(BuildTuple),
+) -> Option<f64> {
+    let is_concurrent = (*index_info).ii_Concurrent;
+    let snapshot = if is_concurrent {
+        pg_sys::RegisterSnapshot(pg_sys::GetTransactionSnapshot())
+    } else {
+        ptr::addr_of_mut!(pg_sys::SnapshotAnyData)
+    };
+
+    pg_sys::EnterParallelMode();
+    let pcxt = pg_sys::CreateParallelContext(
+        LIBRARY_NAME.as_ptr() as *const _,
+        WORKER_MAIN.as_ptr() as *const _,
+        nworkers,
+    );
+
+    let scan_size = pg_sys::table_parallelscan_estimate(heap, snapshot);
+    let queues_size = QUEUE_SIZE * nworkers as usize;
+    estimate_chunk(pcxt, size_of::<ParallelBuildShared>());
+    estimate_chunk(pcxt, scan_size);
+    estimate_chunk(pcxt, queues_size);
+    estimate_keys(pcxt, 3);
+
+    pg_sys::InitializeParallelDSM(pcxt);
+    if (*pcxt).seg.is_null() {
+        pg_sys::DestroyParallelContext(pcxt);
+        pg_sys::ExitParallelMode();
+        if is_concurrent {
+            pg_sys::UnregisterSnapshot(snapshot);
+        }
+        return None;
+    }
+
+    let toc = (*pcxt).toc;
+    let shared =
+        pg_sys::shm_toc_allocate(toc, size_of::<ParallelBuildShared>()) as *mut ParallelBuildShared;
+    shared.write(ParallelBuildShared {
+        heap_oid: (*heap).rd_id,
+        index_oid: (*index).rd_id,
+        is_concurrent,
+        queue_size: QUEUE_SIZE,
+    });
+
+    let pscan = pg_sys::shm_toc_allocate(toc, scan_size) as pg_sys::ParallelTableScanDesc;
+    pg_sys::table_parallelscan_initialize(heap, pscan, snapshot);
+
+    let queue_space = pg_sys::shm_toc_allocate(toc, queues_size) as *mut u8;
+    let queues: Vec<*mut pg_sys::shm_mq_handle> = (0..nworkers as usize)
+        .map(|i| {
+            let mq =
+                pg_sys::shm_mq_create(queue_space.add(i * QUEUE_SIZE) as *mut c_void, QUEUE_SIZE);
+            pg_sys::shm_mq_set_receiver(mq, pg_sys::MyProc);
+            pg_sys::shm_mq_attach(mq, (*pcxt).seg, ptr::null_mut())
+        })
+        .collect();
+
+    pg_sys::shm_toc_insert(toc, PARALLEL_KEY_SHARED, shared as *mut c_void);
+    pg_sys::shm_toc_insert(toc, PARALLEL_KEY_TABLE_SCAN, pscan as *mut c_void);
+    pg_sys::shm_toc_insert(toc, PARALLEL_KEY_QUEUES, queue_space as *mut c_void);
+
+    pg_sys::LaunchParallelWorkers(pcxt);
+    let launched = (*pcxt).nworkers_launched as usize;
+    for (i, &mqh) in queues.iter().take(launched).enumerate() {
+        pg_sys::shm_mq_set_handle(mqh, (*(*pcxt).worker.add(i)).bgwhandle);
+    }
+
+    let heap_tuples = if launched == 0 {
+        // No worker could be started; the leader works through the scan alone
+        scan_heap(
+            heap,
+            index,
+            index_info,
+            pg_sys::table_beginscan_parallel(heap, pscan),
+            sink,
+        )
+    } else {
+        drain_queues(&queues[..launched], sink)
+    };
+
+    pg_sys::WaitForParallelWorkersToFinish(pcxt);
+    pg_sys::DestroyParallelContext(pcxt);
+    pg_sys::ExitParallelMode();
+    if is_concurrent {
+        pg_sys::UnregisterSnapshot(snapshot);
+    }
+
+    pgrx::log!(
+        "HNSW: parallel build scanned {} heap tuples with {} workers",
+        heap_tuples,
+        launched
+    );
+    Some(heap_tuples)
+}
+
+/// Receive from every worker queue, round-robin, until all workers are done
+///
+/// Returns the sum of the heap tuple counts the workers reported.
+unsafe fn drain_queues(
+    queues: &[*mut pg_sys::shm_mq_handle],
+    sink: &mut dyn FnMut(BuildTuple),
+) -> f64 {
+    let mut live = queues.to_vec();
+    let mut heap_tuples = 0.0;
+
+    while !live.is_empty() {
+        live.retain(|&mqh| {
+            let mut nbytes: pg_sys::Size = 0;
+            let mut data: *mut c_void = ptr::null_mut();
+            let result = pg_sys::shm_mq_receive(mqh, &mut nbytes, &mut data, false);
+            if result != pg_sys::shm_mq_result::SHM_MQ_SUCCESS {
+                // The worker exited early; WaitForParallelWorkersToFinish
+                // reports its error
+                return false;
+            }
+
+            let bytes = std::slice::from_raw_parts(data as *const u8, nbytes);
+            match BuildMessage::decode(bytes) {
+                Some(BuildMessage::Tuple(tuple)) => {
+                    sink(tuple);
+                    true
+                }
+                Some(BuildMessage::Done { heap_tuples: seen }) => {
+                    heap_tuples += seen;
+                    false
+                }
+                None => error!("HNSW: malformed message from parallel build worker"),
+            }
+        });
+    }
+
+    heap_tuples
+}
+
+// ============================================================================
+// Worker
+// ============================================================================
+
+/// Send `message` to the leader, blocking while the queue is full
+unsafe fn send(mqh: *mut pg_sys::shm_mq_handle, message: &BuildMessage) {
+    let bytes = message.encode();
+    let flush = matches!(message, BuildMessage::Done { .. });
+
+    #[cfg(feature = "pg14")]
+    let result = {
+        let _ = flush;
+        pg_sys::shm_mq_send(mqh, bytes.len(), bytes.as_ptr() as *const c_void, false)
+    };
+    #[cfg(not(feature = "pg14"))]
+    let result = pg_sys::shm_mq_send(
+        mqh,
+        bytes.len(),
+        bytes.as_ptr() as *const c_void,
+        false,
+        flush,
+    );
+
+    if result != pg_sys::shm_mq_result::SHM_MQ_SUCCESS {
+        error!("HNSW: leader detached from parallel build queue");
+    }
+}
+
+/// Entry point of a parallel build worker, started by `CreateParallelContext`
+#[pg_guard]
+#[no_mangle]
+pub unsafe extern "C" fn ruhnsw_parallel_build_main(
+    seg: *mut pg_sys::dsm_segment,
+    toc: *mut pg_sys::shm_toc,
+) {
+    let shared =
+        pg_sys::shm_toc_lookup(toc, PARALLEL_KEY_SHARED, false) as *const ParallelBuildShared;
+    let (heap_lock, index_lock) = build_lock_modes((*shared).is_concurrent);
+
+    let heap = pg_sys::table_open((*shared).heap_oid, heap_lock);
+    let index = pg_sys::index_open((*shared).index_oid, index_lock);
+    let index_info = pg_sys::BuildIndexInfo(index);
+    (*index_info).ii_Concurrent = (*shared).is_concurrent;
+
+    let queue_space = pg_sys::shm_toc_lookup(toc, PARALLEL_KEY_QUEUES, false) as *mut u8;
+    let mq = queue_space.add(pg_sys::ParallelWorkerNumber as usize * (*shared).queue_size)
+        as *mut pg_sys::shm_mq;
+    pg_sys::shm_mq_set_sender(mq, pg_sys::MyProc);
+    let mqh = pg_sys::shm_mq_attach(mq, seg, ptr::null_mut());
+
+    let pscan = pg_sys::shm_toc_lookup(toc, PARALLEL_KEY_TABLE_SCAN, false)
+        as pg_sys::ParallelTableScanDesc;
+    let heap_tuples = scan_heap(
+        heap,
+        index,
+        index_info,
+        pg_sys::table_beginscan_parallel(heap, pscan),
+        &mut |tuple| send(mqh, &BuildMessage::Tuple(tuple)),
+    );
+    send(mqh, &BuildMessage::Done { heap_tuples });
+
+    pg_sys::shm_mq_detach(mqh);
+    pg_sys::index_close(index, index_lock);
+    pg_sys::table_close(heap, heap_lock);
+}
+
+// ============================================================================
+// Worker Estimation
+// ============================================================================
+
+/// Estimate the number of build workers (including the leader)
+///
+/// Mirrors how PostgreSQL plans parallel B-tree builds: bounded by
+/// `max_parallel_maintenance_workers`, by table size, and by how many workers
+/// fit into `maintenance_work_mem`.
+pub fn estimate_build_workers(
+    num_tuples: i64,
+    dimensions: i32,
+    maintenance_work_mem_kb: i64,
+    max_parallel_maintenance_workers: i32,
+) -> usize {
+    if num_tuples < 2 * MIN_TUPLES_PER_BUILD_WORKER || max_parallel_maintenance_workers <= 0 {
+        return 1;
+    }
+
+    let by_size = num_tuples / MIN_TUPLES_PER_BUILD_WORKER;
+
+    let per_worker_tuples = num_tuples / (max_parallel_maintenance_workers as i64 + 1);
+    let dim_factor = (dimensions.max(1) as i64 + 127) / 128;
+    let per_worker_mem_kb =
+        (per_worker_tuples / 10_000).max(1) * BUILD_WORKER_MEM_KB_PER_10K * dim_factor;
+    let by_mem = (maintenance_work_mem_kb / per_worker_mem_kb.max(1)).max(1);
+
+    let workers = by_size
+        .min(by_mem)
+        .min(max_parallel_maintenance_workers as i64 + 1)
+        .max(1);
+    workers as usize
+}
+
+/// Estimate build workers from the session's maintenance settings
+///
+/// # SQL Example
+/// ```sql
+/// SELECT ruvector_estimate_build_workers((SELECT count(*) FROM items), 384);
+/// ```
+#[pg_extern]
+pub fn ruvector_estimate_build_workers(num_tuples: i64, dimensions: i32) -> i32 {
+    let (mem_kb, max_workers) = unsafe {
+        (
+            pg_sys::maintenance_work_mem as i64,
+            pg_sys::max_parallel_maintenance_workers,
+        )
+    };
+    estimate_build_workers(num_tuples, dimensions, mem_kb, max_workers) as i32
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_worker_estimation() {
+        // Small tables build serially
+        assert_eq!(estimate_build_workers(10_000, 128, 65_536, 4), 1);
+        // No maintenance workers configured
+        assert_eq!(estimate_build_workers(10_000_000, 128, 1 << 20, 0), 1);
+        // Large table with plenty of memory uses leader + all workers
+        assert_eq!(estimate_build_workers(10_000_000, 128, 64 << 20, 4), 5);
+        // Tight memory limits parallelism
+        let constrained = estimate_build_workers(10_000_000, 1536, 65_536, 4);
+        assert!(constrained >= 1 && constrained < 5);
+    }
+
+    #[test]
+    fn test_build_message_roundtrip() {
+        let mut tid = ItemPointerData::default();
+        item_pointer_set_all(&mut tid, 65_543, 42);
+        let tuple = BuildMessage::Tuple(BuildTuple {
+            tid,
+            vector: vec![1.0, -2.5, 3.25],
+            metadata: None,
+        });
+
+        match BuildMessage::decode(&tuple.encode()) {
+            Some(BuildMessage::Tuple(decoded)) => {
+                assert_eq!(item_pointer_get_both(decoded.tid), (65_543, 42));
+                assert_eq!(decoded.vector, vec![1.0, -2.5, 3.25]);
+                assert!(decoded.metadata.is_none());
+            }
+            _ => panic!("expected a tuple message"),
+        }
+
+        let with_metadata = BuildMessage::Tuple(BuildTuple {
+            tid,
+            vector: vec![0.5],
+            metadata: Some(br#"{"category":"books"}"#.to_vec()),
+        });
+        let encoded = with_metadata.encode();
+        match BuildMessage::decode(&encoded) {
+            Some(BuildMessage::Tuple(decoded)) => {
+                assert_eq!(decoded.vector, vec![0.5]);
+                assert_eq!(
+                    decoded.metadata.as_deref(),
+                    Some(&br#"{"category":"books"}"#[..])
+                );
+            }
+            _ => panic!("expected a tuple message"),
+        }
+        assert!(BuildMessage::decode(&encoded[..encoded.len() - 1]).is_none());
+
+        let done = BuildMessage::Done {
+            heap_tuples: 1234.0,
+        };
+        assert!(matches!(
+            BuildMessage::decode(&done.encode()),
+            Some(BuildMessage::Done { heap_tuples }) if heap_tuples == 1234.0
+        ));
+
+        // Truncated and unknown messages are rejected
+        let encoded = tuple.encode();
+        assert!(BuildMessage::decode(&encoded[..encoded.len() - 1]).is_none());
+        assert!(BuildMessage::decode(&[9]).is_none());
+        assert!(BuildMessage::decode(&[]).is_none());
+    }
+}
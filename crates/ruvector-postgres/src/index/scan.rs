@@ -118,6 +118,110 @@ impl IndexScanState {
     }
 }
 
+// ============================================================================
+// Metadata Filter Pushdown
+// ============================================================================
+
+/// Equality qual on a jsonb metadata key, e.g. `metadata->>'category' = 'books'`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetadataQual {
+    pub key: String,
+    pub value: serde_json::Value,
+}
+
+impl MetadataQual {
+    /// Check the qual against a metadata document
+    ///
+    /// A string value also matches a scalar with the same text form, mirroring
+    /// how `->>` compares numbers and booleans as text.
+    pub fn matches(&self, metadata: &serde_json::Value) -> bool {
+        match (metadata.get(&self.key), &self.value) {
+            (None, _) => false,
+            (Some(actual), expected) if actual == expected => true,
+            (Some(serde_json::Value::Number(n)), serde_json::Value::String(s)) => {
+                n.to_string() == *s
+            }
+            (Some(serde_json::Value::Bool(b)), serde_json::Value::String(s)) => {
+                b.to_string() == *s
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Conjunction of metadata quals evaluated during index traversal
+///
+/// Passed to `HnswIndex::search_filtered` / `IvfFlatIndex::search_filtered`
+/// through [`MetadataFilter::predicate`] so that rows failing the WHERE clause
+/// never occupy a top-k slot.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetadataFilter {
+    quals: Vec<MetadataQual>,
+}
+
+impl MetadataFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a filter from a containment document (`metadata @> '{...}'`)
+    ///
+    /// Each top-level key becomes an equality qual; non-object input yields an
+    /// empty filter.
+    pub fn from_jsonb(doc: &serde_json::Value) -> Self {
+        let quals = doc
+            .as_object()
+            .map(|obj| {
+                obj.iter()
+                    .map(|(key, value)| MetadataQual {
+                        key: key.clone(),
+                        value: value.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { quals }
+    }
+
+    /// Add an equality qual on `key`
+    pub fn eq(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.quals.push(MetadataQual {
+            key: key.into(),
+            value,
+        });
+        self
+    }
+
+    pub fn quals(&self) -> &[MetadataQual] {
+        &self.quals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.quals.is_empty()
+    }
+
+    /// True when every qual matches `metadata`
+    pub fn matches(&self, metadata: &serde_json::Value) -> bool {
+        self.quals.iter().all(|q| q.matches(metadata))
+    }
+
+    /// Turn the filter into an id predicate for index traversal
+    ///
+    /// `lookup` resolves an index id to its metadata; ids without metadata only
+    /// pass an empty filter.
+    pub fn predicate<'a, L>(&'a self, lookup: L) -> impl Fn(u64) -> bool + 'a
+    where
+        L: Fn(u64) -> Option<serde_json::Value> + 'a,
+    {
+        move |id| {
+            if self.is_empty() {
+                return true;
+            }
+            lookup(id).map_or(false, |meta| self.matches(&meta))
+        }
+    }
+}
+
 // ============================================================================
 // SQL Interface for Index Options
 // ============================================================================
@@ -184,6 +288,42 @@ mod tests {
         assert_eq!(parse_distance_metric("<+>"), DistanceMetric::Manhattan);
     }
 
+    #[test]
+    fn test_metadata_filter() {
+        use serde_json::json;
+
+        let filter = MetadataFilter::from_jsonb(&json!({"category": "books", "year": "2020"}));
+        assert_eq!(filter.quals().len(), 2);
+
+        assert!(filter.matches(&json!({"category": "books", "year": 2020, "extra": true})));
+        assert!(!filter.matches(&json!({"category": "music", "year": 2020})));
+        assert!(!filter.matches(&json!({"category": "books"})));
+
+        assert!(MetadataFilter::new().matches(&json!({})));
+        assert!(MetadataFilter::from_jsonb(&json!([1, 2])).is_empty());
+    }
+
+    #[test]
+    fn test_filtered_hnsw_search() {
+        use super::super::hnsw::HnswIndex;
+        use serde_json::json;
+
+        let index = HnswIndex::new(2, HnswConfig::default());
+        let mut metadata = std::collections::HashMap::new();
+        for i in 0..40u64 {
+            let id = index.insert(vec![i as f32, 0.0]);
+            let tag = if i % 4 == 0 { "keep" } else { "skip" };
+            metadata.insert(id, json!({ "tag": tag }));
+        }
+
+        let filter = MetadataFilter::new().eq("tag", json!("keep"));
+        let predicate = filter.predicate(|id| metadata.get(&id).cloned());
+        let results = index.search_filtered(&[21.0, 0.0], 3, None, predicate);
+
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|(id, _)| metadata[id]["tag"] == "keep"));
+    }
+
     #[test]
     fn test_scan_state() {
         let results = vec![(1, 0.1), (2, 0.2), (3, 0.3)];
@@ -4,7 +4,7 @@
 //! A drop-in replacement for pgvector with SIMD optimizations.
 
 use pgrx::prelude::*;
-use pgrx::{GucContext, GucFlags, GucRegistry, GucSetting};
+use pgrx::{GucContext, GucFlags, GucRegistry, GucSetting, PgBuiltInOids};
 
 // Initialize the extension
 ::pgrx::pg_module_magic!();
@@ -47,7 +47,7 @@ pub const DEFAULT_IVFFLAT_LISTS: usize = 100;
 pub const DEFAULT_IVFFLAT_PROBES: usize = 1;
 
 // GUC variables
-static EF_SEARCH: GucSetting<i32> = GucSetting::<i32>::new(DEFAULT_HNSW_EF_SEARCH as i32);
+pub(crate) static EF_SEARCH: GucSetting<i32> = GucSetting::<i32>::new(DEFAULT_HNSW_EF_SEARCH as i32);
 static PROBES: GucSetting<i32> = GucSetting::<i32>::new(DEFAULT_IVFFLAT_PROBES as i32);
 
 // ============================================================================
@@ -122,14 +122,124 @@ fn ruvector_memory_stats() -> pgrx::JsonB {
 }
 
 /// Perform index maintenance
+///
+/// `index_name` may be schema-qualified. For an hnsw index, node pages of
+/// rows removed by VACUUM are reclaimed on disk and every session rebuilds
+/// its graph without them; other registered indexes are compacted in memory.
 #[pg_extern]
 fn ruvector_index_maintenance(index_name: &str) -> String {
-    match index::perform_maintenance(index_name) {
+    let oid = Spi::get_one_with_args::<pg_sys::Oid>(
+        "SELECT to_regclass($1)::oid",
+        vec![(PgBuiltInOids::TEXTOID.oid(), index_name.into_datum())],
+    );
+    let Ok(Some(oid)) = oid else {
+        return format!("Maintenance failed: index '{}' does not exist", index_name);
+    };
+    let result = match index::compact_hnsw_index(oid) {
+        Some(stats) => Ok(stats),
+        None => index::perform_maintenance(oid),
+    };
+    match result {
         Ok(stats) => format!("Maintenance completed: {:?}", stats),
         Err(e) => format!("Maintenance failed: {}", e),
     }
 }
 
+/// Statistics for registered in-memory indexes and hnsw/ruivfflat relations
+///
+/// Indexes this session has already loaded report full statistics. Other
+/// access-method relations report catalog-level figures only (tuple estimate
+/// and on-disk size), unless `load_indexes` is set, which loads every hnsw
+/// index into the session first.
+///
+/// # Example
+/// ```sql
+/// SELECT * FROM ruvector_index_stats();
+/// SELECT * FROM ruvector_index_stats(load_indexes => true);
+/// ```
+#[pg_extern]
+fn ruvector_index_stats(
+    load_indexes: default!(bool, false),
+) -> TableIterator<
+    'static,
+    (
+        name!(name, String),
+        name!(index_type, String),
+        name!(vector_count, i64),
+        name!(dimensions, i32),
+        name!(index_size_mb, f64),
+        name!(fragmentation_pct, f64),
+        name!(recall_estimate, Option<f64>),
+    ),
+> {
+    let catalog: Result<Vec<(pg_sys::Oid, index::IndexStats)>, pgrx::spi::Error> =
+        Spi::connect(|client| {
+            let table = client.select(
+                "SELECT c.oid, c.oid::regclass::text AS name, am.amname::text AS amname, \
+                        c.reltuples::float8 AS tuples, pg_relation_size(c.oid)::float8 AS bytes \
+                 FROM pg_class c JOIN pg_am am ON c.relam = am.oid \
+                 WHERE am.amname IN ('hnsw', 'ruivfflat') ORDER BY c.relname",
+                None,
+                None,
+            )?;
+            let mut rows = Vec::new();
+            for row in table {
+                let oid = row.get_by_name::<pg_sys::Oid, _>("oid")?.unwrap_or(pg_sys::InvalidOid);
+                rows.push((
+                    oid,
+                    index::IndexStats {
+                        name: row.get_by_name::<String, _>("name")?.unwrap_or_default(),
+                        index_type: row.get_by_name::<String, _>("amname")?.unwrap_or_default(),
+                        vector_count: row.get_by_name::<f64, _>("tuples")?.unwrap_or(0.0).max(0.0) as i64,
+                        dimensions: 0,
+                        index_size_mb: row.get_by_name::<f64, _>("bytes")?.unwrap_or(0.0) / (1024.0 * 1024.0),
+                        fragmentation_pct: 0.0,
+                        recall_estimate: None,
+                    },
+                ));
+            }
+            Ok(rows)
+        });
+    let catalog = catalog.unwrap_or_else(|e| {
+        pgrx::warning!("ruvector_index_stats: catalog lookup failed: {}", e);
+        Vec::new()
+    });
+
+    if load_indexes {
+        for (oid, row) in &catalog {
+            if row.index_type == "hnsw" {
+                index::load_hnsw_index(*oid);
+            }
+        }
+    }
+
+    let mut loaded = index::get_all_index_stats();
+    let mut stats = Vec::with_capacity(loaded.len() + catalog.len());
+    for (oid, row) in catalog {
+        match loaded.iter().position(|(loaded_oid, _)| *loaded_oid == oid) {
+            // Catalog names are only qualified when off the search path
+            Some(at) => stats.push(index::IndexStats {
+                name: row.name,
+                ..loaded.swap_remove(at).1
+            }),
+            None => stats.push(row),
+        }
+    }
+    stats.extend(loaded.into_iter().map(|(_, s)| s));
+
+    TableIterator::new(stats.into_iter().map(|s| {
+        (
+            s.name,
+            s.index_type,
+            s.vector_count,
+            s.dimensions,
+            s.index_size_mb,
+            s.fragmentation_pct,
+            s.recall_estimate,
+        )
+    }))
+}
+
 // ============================================================================
 // Quantization Functions (Array-based)
 // ============================================================================
@@ -0,0 +1,306 @@
+//! Rate limiting and admission control
+//!
+//! Every API request is charged against a per-client token bucket, and
+//! searches additionally need one of a fixed number of execution slots.
+//! Searches that cannot get a slot wait in a queue for at most
+//! `queue_timeout_ms` before being rejected, so a single busy client cannot
+//! starve the others.
+//!
+//! Clients are identified by their peer address; a client-supplied id would
+//! let a caller dodge its limit by sending a new id with every request.
+
+use crate::{error::Error, state::AppState, Result};
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+use dashmap::DashMap;
+use parking_lot::RwLock;
+use ruvector_metrics::MetricsRecorder;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Buckets idle for longer than this are dropped when the table is pruned
+const BUCKET_IDLE: Duration = Duration::from_secs(60);
+
+/// Most clients tracked at once; a new client past this first prunes idle
+/// buckets and then evicts the least recently seen one
+const MAX_BUCKETS: usize = 10_000;
+
+/// Admission control settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdmissionConfig {
+    /// Sustained requests per second per client; 0 disables rate limiting
+    pub per_client_qps: f64,
+    /// Requests a client may burst above the sustained rate
+    pub burst: u32,
+    /// Searches executing at once; 0 disables the limit
+    pub max_concurrent_searches: usize,
+    /// How long a search may wait for a slot before being rejected
+    pub queue_timeout_ms: u64,
+}
+
+impl Default for AdmissionConfig {
+    fn default() -> Self {
+        Self {
+            per_client_qps: 0.0,
+            burst: 50,
+            max_concurrent_searches: 64,
+            queue_timeout_ms: 1000,
+        }
+    }
+}
+
+/// Current admission state, as reported by the metrics endpoint
+#[derive(Debug, Clone, Serialize)]
+pub struct AdmissionStats {
+    /// Searches currently executing
+    pub in_flight: usize,
+    /// Searches waiting for a slot
+    pub queued: usize,
+    /// Configured search slots (0 = unlimited)
+    pub max_concurrent_searches: usize,
+    /// Share of search slots in use, in `[0, 1]`
+    pub saturation: f64,
+    /// Requests rejected by the per-client rate limit
+    pub rate_limited: u64,
+    /// Searches rejected after waiting `queue_timeout_ms`
+    pub queue_timeouts: u64,
+    /// Clients with a live token bucket
+    pub tracked_clients: usize,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Per-client rate limiter and search concurrency gate
+#[derive(Debug)]
+pub struct AdmissionController {
+    config: RwLock<AdmissionConfig>,
+    buckets: DashMap<String, TokenBucket>,
+    slots: Arc<Semaphore>,
+    in_flight: AtomicUsize,
+    queued: AtomicUsize,
+    rate_limited: AtomicU64,
+    queue_timeouts: AtomicU64,
+}
+
+impl AdmissionController {
+    /// Create a controller with the given settings
+    pub fn new(config: AdmissionConfig) -> Self {
+        let slots = match config.max_concurrent_searches {
+            0 => Semaphore::MAX_PERMITS,
+            n => n,
+        };
+        Self {
+            config: RwLock::new(config),
+            buckets: DashMap::new(),
+            slots: Arc::new(Semaphore::new(slots)),
+            in_flight: AtomicUsize::new(0),
+            queued: AtomicUsize::new(0),
+            rate_limited: AtomicU64::new(0),
+            queue_timeouts: AtomicU64::new(0),
+        }
+    }
+
+    /// Active settings
+    pub fn config(&self) -> AdmissionConfig {
+        self.config.read().clone()
+    }
+
+    /// Change the rate limits and queue timeout of a running controller
+    ///
+    /// `max_concurrent_searches` is fixed at creation; the value in `config`
+    /// is ignored.
+    pub fn reconfigure(&self, config: &AdmissionConfig) {
+        let mut current = self.config.write();
+        current.per_client_qps = config.per_client_qps;
+        current.burst = config.burst;
+        current.queue_timeout_ms = config.queue_timeout_ms;
+    }
+
+    /// Charge one request to `client`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::RateLimited`] if the client's bucket is empty
+    pub fn check_rate(&self, client: &str) -> Result<()> {
+        let (qps, burst) = {
+            let config = self.config.read();
+            (config.per_client_qps, config.burst)
+        };
+        if qps <= 0.0 {
+            return Ok(());
+        }
+
+        if self.buckets.len() >= MAX_BUCKETS && !self.buckets.contains_key(client) {
+            self.make_room();
+        }
+
+        let capacity = qps + burst as f64;
+        let now = Instant::now();
+        let mut bucket = self
+            .buckets
+            .entry(client.to_string())
+            .or_insert_with(|| TokenBucket {
+                tokens: capacity,
+                updated: now,
+            });
+
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * qps).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens < 1.0 {
+            let retry_after = (1.0 - bucket.tokens) / qps;
+            drop(bucket);
+            self.rate_limited.fetch_add(1, Ordering::Relaxed);
+            MetricsRecorder::record_rejection("rate_limited");
+            return Err(Error::RateLimited(format!(
+                "client '{}' exceeded {} requests/s, retry in {:.0}ms",
+                client,
+                qps,
+                retry_after * 1000.0
+            )));
+        }
+        bucket.tokens -= 1.0;
+        Ok(())
+    }
+
+    /// Drop idle buckets, or the least recently seen one if none are idle
+    fn make_room(&self) {
+        self.buckets
+            .retain(|_, b| b.updated.elapsed() < BUCKET_IDLE);
+        if self.buckets.len() < MAX_BUCKETS {
+            return;
+        }
+        let stalest = self
+            .buckets
+            .iter()
+            .min_by_key(|b| b.updated)
+            .map(|b| b.key().clone());
+        if let Some(client) = stalest {
+            self.buckets.remove(&client);
+        }
+    }
+
+    /// Wait for a search slot, up to the configured queue timeout
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Overloaded`] if no slot frees up in time
+    pub async fn acquire_search(self: &Arc<Self>) -> Result<SearchPermit> {
+        self.queued.fetch_add(1, Ordering::Relaxed);
+        self.publish();
+
+        let timeout_ms = self.config.read().queue_timeout_ms;
+        let timeout = Duration::from_millis(timeout_ms);
+        let acquired = tokio::time::timeout(timeout, self.slots.clone().acquire_owned()).await;
+        self.queued.fetch_sub(1, Ordering::Relaxed);
+
+        match acquired {
+            Ok(Ok(permit)) => {
+                self.in_flight.fetch_add(1, Ordering::Relaxed);
+                self.publish();
+                Ok(SearchPermit {
+                    _permit: permit,
+                    controller: Arc::clone(self),
+                })
+            }
+            Ok(Err(_)) => Err(Error::Internal("search semaphore closed".to_string())),
+            Err(_) => {
+                self.queue_timeouts.fetch_add(1, Ordering::Relaxed);
+                MetricsRecorder::record_rejection("queue_timeout");
+                self.publish();
+                Err(Error::Overloaded(format!(
+                    "no search slot available within {}ms",
+                    timeout_ms
+                )))
+            }
+        }
+    }
+
+    /// Snapshot of the current admission state
+    pub fn stats(&self) -> AdmissionStats {
+        let in_flight = self.in_flight.load(Ordering::Relaxed);
+        let max = self.config.read().max_concurrent_searches;
+        AdmissionStats {
+            in_flight,
+            queued: self.queued.load(Ordering::Relaxed),
+            max_concurrent_searches: max,
+            saturation: if max == 0 {
+                0.0
+            } else {
+                (in_flight as f64 / max as f64).min(1.0)
+            },
+            rate_limited: self.rate_limited.load(Ordering::Relaxed),
+            queue_timeouts: self.queue_timeouts.load(Ordering::Relaxed),
+            tracked_clients: self.buckets.len(),
+        }
+    }
+
+    /// Push the current gauges to the metrics registry
+    pub fn publish(&self) {
+        let stats = self.stats();
+        MetricsRecorder::set_admission(stats.in_flight, stats.queued, stats.saturation);
+    }
+}
+
+impl Default for AdmissionController {
+    fn default() -> Self {
+        Self::new(AdmissionConfig::default())
+    }
+}
+
+/// A held search slot, released on drop
+#[derive(Debug)]
+pub struct SearchPermit {
+    _permit: OwnedSemaphorePermit,
+    controller: Arc<AdmissionController>,
+}
+
+impl Drop for SearchPermit {
+    fn drop(&mut self) {
+        self.controller.in_flight.fetch_sub(1, Ordering::Relaxed);
+        self.controller.publish();
+    }
+}
+
+/// Middleware applying rate limits to all API routes and the search queue
+/// to search routes
+pub async fn admit(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response> {
+    let client = client_id(&request);
+    state.admission.check_rate(&client)?;
+
+    let is_search =
+        request.method() == Method::POST && request.uri().path().contains("/points/search");
+    let _permit = if is_search {
+        Some(state.admission.acquire_search().await?)
+    } else {
+        None
+    };
+
+    Ok(next.run(request).await)
+}
+
+fn client_id(request: &Request) -> String {
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip().to_string())
+        .unwrap_or_else(|| "anonymous".to_string())
+}
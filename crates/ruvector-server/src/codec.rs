@@ -0,0 +1,110 @@
+//! Compressed query vectors
+//!
+//! Clients on slow links can send a search query as half-precision floats or
+//! as scaled 8-bit integers instead of a JSON float array, which shrinks the
+//! query several-fold. Components are packed little-endian and
+//! base64-encoded, and the server dequantizes them before searching:
+//!
+//! ```json
+//! {"encoding": "f16", "data": "ADwAQA=="}
+//! {"encoding": "int8", "data": "f4EA", "scale": 0.0079}
+//! ```
+//!
+//! An int8 component `q` decodes to `q * scale`; clients usually pick
+//! `scale = max(|x|) / 127`.
+
+use crate::error::{Error, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+/// A query vector in a compact wire encoding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "encoding", rename_all = "snake_case")]
+pub enum EncodedVector {
+    /// IEEE 754 half-precision components
+    F16 {
+        /// Base64 of 2 bytes per component
+        data: String,
+    },
+    /// Signed 8-bit components times `scale`
+    Int8 {
+        /// Base64 of 1 byte per component
+        data: String,
+        /// Value of one quantization step
+        scale: f32,
+    },
+}
+
+impl EncodedVector {
+    /// Encode `vector` as half-precision floats
+    pub fn f16(vector: &[f32]) -> Self {
+        let bytes: Vec<u8> = vector
+            .iter()
+            .flat_map(|&x| half::f16::from_f32(x).to_le_bytes())
+            .collect();
+        EncodedVector::F16 {
+            data: STANDARD.encode(bytes),
+        }
+    }
+
+    /// Encode `vector` as 8-bit integers scaled to its largest magnitude
+    pub fn int8(vector: &[f32]) -> Self {
+        let max = vector.iter().fold(0.0f32, |m, x| m.max(x.abs()));
+        let scale = if max > 0.0 { max / 127.0 } else { 1.0 };
+        let bytes: Vec<u8> = vector
+            .iter()
+            .map(|&x| (x / scale).round().clamp(-127.0, 127.0) as i8 as u8)
+            .collect();
+        EncodedVector::Int8 {
+            data: STANDARD.encode(bytes),
+            scale,
+        }
+    }
+
+    /// Dequantize to full-precision components
+    pub fn decode(&self) -> Result<Vec<f32>> {
+        match self {
+            EncodedVector::F16 { data } => {
+                let bytes = decode_base64(data)?;
+                if bytes.len() % 2 != 0 {
+                    return Err(Error::InvalidRequest(format!(
+                        "f16 vector has {} bytes, expected an even number",
+                        bytes.len()
+                    )));
+                }
+                Ok(bytes
+                    .chunks_exact(2)
+                    .map(|pair| half::f16::from_le_bytes([pair[0], pair[1]]).to_f32())
+                    .collect())
+            }
+            EncodedVector::Int8 { data, scale } => {
+                if !scale.is_finite() || *scale <= 0.0 {
+                    return Err(Error::InvalidRequest(format!(
+                        "int8 scale must be positive, got {}",
+                        scale
+                    )));
+                }
+                let bytes = decode_base64(data)?;
+                Ok(bytes.iter().map(|&b| b as i8 as f32 * scale).collect())
+            }
+        }
+    }
+}
+
+fn decode_base64(data: &str) -> Result<Vec<u8>> {
+    STANDARD
+        .decode(data)
+        .map_err(|e| Error::InvalidRequest(format!("Invalid base64 vector: {}", e)))
+}
+
+/// The query vector of a request that may carry either encoding
+pub fn query_vector(vector: Vec<f32>, encoded: Option<&EncodedVector>) -> Result<Vec<f32>> {
+    match encoded {
+        Some(_) if !vector.is_empty() => Err(Error::InvalidRequest(
+            "Send either vector or encoded_vector, not both".to_string(),
+        )),
+        Some(encoded) => encoded.decode(),
+        None => Ok(vector),
+    }
+}
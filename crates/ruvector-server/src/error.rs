@@ -29,6 +29,14 @@ pub enum Error {
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
 
+    /// Client exceeded its request rate
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
+    /// No capacity to run the request in time
+    #[error("Overloaded: {0}")]
+    Overloaded(String),
+
     /// Core library error
     #[error("Core error: {0}")]
     Core(#[from] ruvector_core::RuvectorError),
@@ -58,6 +66,11 @@ impl IntoResponse for Error {
             }
             Error::CollectionExists(_) => (StatusCode::CONFLICT, self.to_string()),
             Error::InvalidRequest(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            Error::RateLimited(_) => (StatusCode::TOO_MANY_REQUESTS, self.to_string()),
+            Error::Overloaded(_) => (StatusCode::SERVICE_UNAVAILABLE, self.to_string()),
+            Error::Core(e @ ruvector_core::RuvectorError::Backpressure { .. }) => {
+                (StatusCode::SERVICE_UNAVAILABLE, e.to_string())
+            }
             Error::Core(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
             Error::Server(_) | Error::Internal(_) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
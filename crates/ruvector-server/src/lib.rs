@@ -2,20 +2,29 @@
 //!
 //! This crate provides a REST API server built on axum for interacting with rUvector.
 
+pub mod admission;
+pub mod codec;
 pub mod error;
 pub mod routes;
+pub mod runtime;
 pub mod state;
 
-use axum::{routing::get, Router};
+use axum::{middleware, routing::get, Router};
+use ruvector_core::{MaintenanceConfig, SlowQueryConfig};
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
 use tower_http::{
     compression::CompressionLayer,
     cors::{Any, CorsLayer},
     trace::TraceLayer,
 };
 
+pub use admission::{AdmissionConfig, AdmissionController, AdmissionStats};
+pub use codec::EncodedVector;
 pub use error::{Error, Result};
+pub use runtime::{ConfigWatch, LogLevelHook, RuntimeConfig, RuntimeConfigUpdate, RuntimeTuning};
 pub use state::AppState;
 
 /// Server configuration
@@ -29,6 +38,15 @@ pub struct Config {
     pub enable_cors: bool,
     /// Enable compression
     pub enable_compression: bool,
+    /// Rate limiting and search admission control
+    #[serde(default)]
+    pub admission: AdmissionConfig,
+    /// Slow query log applied to every collection
+    #[serde(default)]
+    pub slow_query: SlowQueryConfig,
+    /// Background maintenance for every collection; off when unset
+    #[serde(default)]
+    pub maintenance: Option<MaintenanceConfig>,
 }
 
 impl Default for Config {
@@ -38,6 +56,9 @@ impl Default for Config {
             port: 6333,
             enable_cors: true,
             enable_compression: true,
+            admission: AdmissionConfig::default(),
+            slow_query: SlowQueryConfig::default(),
+            maintenance: None,
         }
     }
 }
@@ -46,6 +67,7 @@ impl Default for Config {
 pub struct RuvectorServer {
     config: Config,
     state: AppState,
+    config_watch: Option<ConfigWatch>,
 }
 
 impl RuvectorServer {
@@ -54,24 +76,63 @@ impl RuvectorServer {
         Self {
             config: Config::default(),
             state: AppState::new(),
+            config_watch: None,
         }
     }
 
     /// Create a new server instance with custom configuration
     pub fn with_config(config: Config) -> Self {
+        let state = AppState::with_admission(config.admission.clone())
+            .with_slow_query(config.slow_query.clone())
+            .with_maintenance(config.maintenance.clone());
         Self {
             config,
-            state: AppState::new(),
+            state,
+            config_watch: None,
         }
     }
 
+    /// Reload runtime settings from a JSON file whenever it changes
+    ///
+    /// The file holds a [`RuntimeConfigUpdate`] and is checked every
+    /// `interval`. See [`runtime`] for which settings can be reloaded.
+    pub fn watch_config(mut self, path: impl Into<PathBuf>, interval: Duration) -> Self {
+        self.config_watch = Some(ConfigWatch {
+            path: path.into(),
+            interval,
+        });
+        self
+    }
+
+    /// Register the hook applying `log_level` changes, typically backed by a
+    /// `tracing_subscriber` reload handle
+    pub fn on_log_level(self, hook: LogLevelHook) -> Self {
+        self.state.runtime.set_log_level_hook(hook);
+        self
+    }
+
+    /// Shared state, for embedding processes that tune settings directly
+    pub fn state(&self) -> &AppState {
+        &self.state
+    }
+
     /// Build the router with all routes
     fn build_router(&self) -> Router {
+        // Health, metrics and admin stay reachable when clients are being throttled
+        let api = Router::new()
+            .nest("/collections", routes::collections::routes())
+            .merge(routes::points::routes())
+            .layer(middleware::from_fn_with_state(
+                self.state.clone(),
+                admission::admit,
+            ));
+
         let mut router = Router::new()
             .route("/health", get(routes::health::health_check))
             .route("/ready", get(routes::health::readiness))
-            .nest("/collections", routes::collections::routes())
-            .merge(routes::points::routes())
+            .route("/metrics", get(routes::health::metrics))
+            .nest("/admin", routes::admin::routes())
+            .merge(api)
             .with_state(self.state.clone());
 
         // Add middleware layers
@@ -104,13 +165,20 @@ impl RuvectorServer {
 
         let router = self.build_router();
 
+        if let Some(watch) = self.config_watch.clone() {
+            runtime::spawn_watch(self.state.clone(), watch);
+        }
+
         tracing::info!("Starting ruvector-server on {}", addr);
 
         let listener = tokio::net::TcpListener::bind(addr)
             .await
             .map_err(|e| Error::Server(format!("Failed to bind to {}: {}", addr, e)))?;
 
-        axum::serve(listener, router)
+        axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
             .await
             .map_err(|e| Error::Server(format!("Server error: {}", e)))?;
 
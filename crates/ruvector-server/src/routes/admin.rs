@@ -0,0 +1,174 @@
+//! Administrative endpoints
+
+use crate::{error::Error, runtime::RuntimeConfigUpdate, state::AppState, Result};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use ruvector_core::{MaintenanceRun, SlowQueryEntry};
+use serde::{Deserialize, Serialize};
+
+/// Slow query listing parameters
+#[derive(Debug, Deserialize)]
+pub struct SlowQueryParams {
+    /// Restrict to one collection
+    pub collection: Option<String>,
+    /// Most recent entries per collection (defaults to all retained)
+    pub limit: Option<usize>,
+}
+
+/// Slow queries of one collection
+#[derive(Debug, Serialize)]
+pub struct CollectionSlowQueries {
+    /// Collection name
+    pub collection: String,
+    /// Current threshold in milliseconds
+    pub threshold_ms: f64,
+    /// Slow searches recorded since the collection was created
+    pub total_recorded: u64,
+    /// Retained entries, newest first
+    pub entries: Vec<SlowQueryEntry>,
+}
+
+/// Slow query listing response
+#[derive(Debug, Serialize)]
+pub struct SlowQueriesResponse {
+    /// Per-collection slow queries
+    pub collections: Vec<CollectionSlowQueries>,
+}
+
+/// Maintenance history of one collection
+#[derive(Debug, Serialize)]
+pub struct CollectionMaintenance {
+    /// Collection name
+    pub collection: String,
+    /// Whether a maintenance scheduler is running
+    pub scheduled: bool,
+    /// Recent runs, oldest first
+    pub runs: Vec<MaintenanceRun>,
+}
+
+/// Maintenance listing response
+#[derive(Debug, Serialize)]
+pub struct MaintenanceResponse {
+    /// Per-collection maintenance history
+    pub collections: Vec<CollectionMaintenance>,
+}
+
+/// Maintenance listing parameters
+#[derive(Debug, Deserialize)]
+pub struct MaintenanceParams {
+    /// Restrict to one collection
+    pub collection: Option<String>,
+}
+
+/// Create admin routes
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/slow-queries",
+            get(list_slow_queries).delete(clear_slow_queries),
+        )
+        .route("/config", get(get_config).patch(update_config))
+        .route("/maintenance", get(list_maintenance))
+}
+
+fn selected_collections(state: &AppState, collection: Option<String>) -> Result<Vec<String>> {
+    match collection {
+        Some(name) if !state.contains_collection(&name) => Err(Error::CollectionNotFound(name)),
+        Some(name) => Ok(vec![name]),
+        None => {
+            let mut names = state.collection_names();
+            names.sort();
+            Ok(names)
+        }
+    }
+}
+
+/// List recorded slow queries
+///
+/// GET /admin/slow-queries?collection=<name>&limit=<n>
+async fn list_slow_queries(
+    State(state): State<AppState>,
+    Query(params): Query<SlowQueryParams>,
+) -> Result<impl IntoResponse> {
+    let mut collections = Vec::new();
+    for name in selected_collections(&state, params.collection)? {
+        let Some(log) = state
+            .get_collection(&name)
+            .and_then(|db| db.slow_query_log())
+        else {
+            continue;
+        };
+        collections.push(CollectionSlowQueries {
+            collection: name,
+            threshold_ms: log.threshold().as_secs_f64() * 1000.0,
+            total_recorded: log.total_recorded(),
+            entries: log.recent(params.limit.unwrap_or(usize::MAX)),
+        });
+    }
+
+    Ok(Json(SlowQueriesResponse { collections }))
+}
+
+/// Clear recorded slow queries
+///
+/// DELETE /admin/slow-queries?collection=<name>
+async fn clear_slow_queries(
+    State(state): State<AppState>,
+    Query(params): Query<SlowQueryParams>,
+) -> Result<impl IntoResponse> {
+    for name in selected_collections(&state, params.collection)? {
+        if let Some(log) = state
+            .get_collection(&name)
+            .and_then(|db| db.slow_query_log())
+        {
+            log.clear();
+        }
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Current runtime settings
+///
+/// GET /admin/config
+async fn get_config(State(state): State<AppState>) -> Result<impl IntoResponse> {
+    Ok(Json(state.runtime.config()))
+}
+
+/// Change runtime settings without a restart
+///
+/// PATCH /admin/config
+async fn update_config(
+    State(state): State<AppState>,
+    Json(update): Json<RuntimeConfigUpdate>,
+) -> Result<impl IntoResponse> {
+    Ok(Json(state.apply_runtime_update(&update)?))
+}
+
+/// Background maintenance runs
+///
+/// GET /admin/maintenance?collection=<name>
+async fn list_maintenance(
+    State(state): State<AppState>,
+    Query(params): Query<MaintenanceParams>,
+) -> Result<impl IntoResponse> {
+    let mut collections = Vec::new();
+    for name in selected_collections(&state, params.collection)? {
+        let Some(db) = state.get_collection(&name) else {
+            continue;
+        };
+        let scheduler = db.maintenance();
+        collections.push(CollectionMaintenance {
+            collection: name,
+            scheduled: scheduler.is_some(),
+            runs: scheduler.map(|s| s.history()).unwrap_or_default(),
+        });
+    }
+
+    Ok(Json(MaintenanceResponse { collections }))
+}
@@ -1,14 +1,34 @@
 //! Health check endpoints
 
-use crate::{state::AppState, Result};
-use axum::{extract::State, response::IntoResponse, Json};
-use serde::Serialize;
+use crate::{error::Error, state::AppState, Result};
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use ruvector_core::arena::{self, GlobalArenaStats};
+use ruvector_core::{HealthCheckConfig, HealthReport, HealthStatus as CheckStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Health status response
 #[derive(Debug, Serialize)]
 pub struct HealthStatus {
-    /// Server status
+    /// Server status: "healthy", "degraded" or "unhealthy"
     pub status: String,
+    /// Arena allocator usage
+    pub arena: GlobalArenaStats,
+    /// Per-collection reports
+    pub collections: BTreeMap<String, HealthReport>,
+}
+
+/// Health check parameters
+#[derive(Debug, Default, Deserialize)]
+pub struct HealthParams {
+    /// Verify a storage sample and run canary searches
+    #[serde(default)]
+    pub deep: bool,
 }
 
 /// Readiness status response
@@ -22,13 +42,63 @@ pub struct ReadinessStatus {
     pub total_points: usize,
 }
 
-/// Simple health check endpoint
+/// Health check endpoint
 ///
-/// GET /health
-pub async fn health_check() -> Result<impl IntoResponse> {
-    Ok(Json(HealthStatus {
-        status: "healthy".to_string(),
-    }))
+/// GET /health?deep=<bool>
+///
+/// Responds 503 when any collection is unhealthy, so the endpoint can back
+/// Kubernetes liveness (quick) and readiness (deep) probes directly.
+pub async fn health_check(
+    State(state): State<AppState>,
+    Query(params): Query<HealthParams>,
+) -> Result<impl IntoResponse> {
+    let collections: Vec<_> = state
+        .collections
+        .iter()
+        .map(|entry| (entry.key().clone(), entry.value().clone()))
+        .collect();
+
+    let reports = tokio::task::spawn_blocking(move || {
+        let config = HealthCheckConfig::default();
+        collections
+            .into_iter()
+            .map(|(name, db)| {
+                let report = if params.deep {
+                    db.health_check_deep(&config)
+                } else {
+                    db.health_check()
+                };
+                (name, report)
+            })
+            .collect::<BTreeMap<_, _>>()
+    })
+    .await
+    .map_err(|e| Error::Internal(format!("Health check failed: {}", e)))?;
+
+    let status = reports
+        .values()
+        .map(|r| r.status)
+        .max()
+        .unwrap_or(CheckStatus::Healthy);
+    let code = if status == CheckStatus::Unhealthy {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    let label = match status {
+        CheckStatus::Healthy => "healthy",
+        CheckStatus::Degraded => "degraded",
+        CheckStatus::Unhealthy => "unhealthy",
+    };
+
+    Ok((
+        code,
+        Json(HealthStatus {
+            status: label.to_string(),
+            arena: arena::global_stats(),
+            collections: reports,
+        }),
+    ))
 }
 
 /// Readiness check endpoint with stats
@@ -44,3 +114,15 @@ pub async fn readiness(State(state): State<AppState>) -> Result<impl IntoRespons
         total_points: 0, // Would require tracking or querying each DB
     }))
 }
+
+/// Prometheus metrics, including admission saturation
+///
+/// GET /metrics
+pub async fn metrics(State(state): State<AppState>) -> Result<impl IntoResponse> {
+    state.admission.publish();
+
+    Ok((
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        ruvector_metrics::gather_metrics(),
+    ))
+}
@@ -1,5 +1,6 @@
 //! API routes
 
+pub mod admin;
 pub mod collections;
 pub mod health;
 pub mod points;
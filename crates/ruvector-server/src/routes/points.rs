@@ -1,6 +1,11 @@
 //! Point operations endpoints
 
-use crate::{error::Error, state::AppState, Result};
+use crate::{
+    codec::{query_vector, EncodedVector},
+    error::Error,
+    state::AppState,
+    Result,
+};
 use axum::{
     extract::{Path, State},
     http::StatusCode,
@@ -8,7 +13,7 @@ use axum::{
     routing::{get, post, put},
     Json, Router,
 };
-use ruvector_core::{SearchQuery, SearchResult, VectorEntry};
+use ruvector_core::{QueryVector, SearchQuery, SearchResult, VectorEntry};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -23,7 +28,10 @@ pub struct UpsertPointsRequest {
 #[derive(Debug, Deserialize)]
 pub struct SearchRequest {
     /// Query vector
+    #[serde(default)]
     pub vector: Vec<f32>,
+    /// Compressed query vector, sent instead of `vector`
+    pub encoded_vector: Option<EncodedVector>,
     /// Number of results to return
     #[serde(default = "default_limit")]
     pub k: usize,
@@ -31,12 +39,31 @@ pub struct SearchRequest {
     pub score_threshold: Option<f32>,
     /// Optional metadata filters
     pub filter: Option<HashMap<String, serde_json::Value>>,
+    /// Optional efSearch; defaults to the runtime setting
+    pub ef_search: Option<usize>,
 }
 
 fn default_limit() -> usize {
     10
 }
 
+/// Search request with a query built from stored ids and vectors
+///
+/// ```json
+/// {"terms": [{"id": "king"}, {"id": "man", "weight": -1.0}, {"id": "woman"}], "k": 5}
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct ExpressionSearchRequest {
+    /// Weighted terms and normalization
+    #[serde(flatten)]
+    pub query: QueryVector,
+    /// Number of results to return
+    #[serde(default = "default_limit")]
+    pub k: usize,
+    /// Optional metadata filters
+    pub filter: Option<HashMap<String, serde_json::Value>>,
+}
+
 /// Search response
 #[derive(Debug, Serialize)]
 pub struct SearchResponse {
@@ -56,6 +83,10 @@ pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/collections/:name/points", put(upsert_points))
         .route("/collections/:name/points/search", post(search_points))
+        .route(
+            "/collections/:name/points/search/expression",
+            post(search_expression),
+        )
         .route("/collections/:name/points/:id", get(get_point))
 }
 
@@ -89,10 +120,10 @@ async fn search_points(
         .ok_or_else(|| Error::CollectionNotFound(name))?;
 
     let query = SearchQuery {
-        vector: req.vector,
+        vector: query_vector(req.vector, req.encoded_vector.as_ref())?,
         k: req.k,
         filter: req.filter,
-        ef_search: None,
+        ef_search: req.ef_search.or(state.runtime.default_ef_search()),
     };
 
     let mut results = db.search(query).map_err(Error::Core)?;
@@ -105,6 +136,25 @@ async fn search_points(
     Ok(Json(SearchResponse { results }))
 }
 
+/// Search with a vector-arithmetic query
+///
+/// POST /collections/:name/points/search/expression
+async fn search_expression(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(req): Json<ExpressionSearchRequest>,
+) -> Result<impl IntoResponse> {
+    let db = state
+        .get_collection(&name)
+        .ok_or_else(|| Error::CollectionNotFound(name))?;
+
+    let results = db
+        .search_expression(&req.query, req.k, req.filter)
+        .map_err(Error::Core)?;
+
+    Ok(Json(SearchResponse { results }))
+}
+
 /// Get a point by ID
 ///
 /// GET /collections/:name/points/:id
@@ -0,0 +1,253 @@
+//! Runtime tuning and config hot-reload
+//!
+//! A subset of settings can change while the server runs, either through
+//! `PATCH /admin/config` or by editing a JSON file registered with
+//! [`RuvectorServer::watch_config`](crate::RuvectorServer::watch_config).
+//! Both take a [`RuntimeConfigUpdate`]; fields left out keep their current
+//! value.
+//!
+//! Hot-reloadable:
+//! - default efSearch for searches that do not set one,
+//! - result cache capacity per collection (resizing clears the cache),
+//! - slow query threshold,
+//! - log level, through a hook registered by the embedding process,
+//! - per-client rate limits and the search queue timeout.
+//!
+//! The number of concurrent search slots is fixed at startup.
+
+use crate::{admission::AdmissionConfig, error::Error, state::AppState, Result};
+use parking_lot::RwLock;
+use ruvector_core::{ResultCacheConfig, VectorDB};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// Callback applying a log filter directive such as `"info"` or
+/// `"ruvector_server=debug"`
+pub type LogLevelHook = Arc<dyn Fn(&str) -> std::result::Result<(), String> + Send + Sync>;
+
+/// Settings that can change without a restart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeConfig {
+    /// efSearch for searches that do not set one; `None` uses the index default
+    pub default_ef_search: Option<usize>,
+    /// Result cache entries per collection; 0 disables the cache
+    pub result_cache_capacity: usize,
+    /// Searches at or above this latency go to the slow query log
+    pub slow_query_threshold_ms: u64,
+    /// Last log filter directive applied, if any
+    pub log_level: Option<String>,
+    /// Rate limits and queue timeout
+    pub admission: AdmissionConfig,
+}
+
+/// Partial update of [`RuntimeConfig`]
+///
+/// ```json
+/// {"default_ef_search": 128, "per_client_qps": 200.0, "log_level": "debug"}
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RuntimeConfigUpdate {
+    /// New default efSearch; 0 clears it
+    pub default_ef_search: Option<usize>,
+    /// New result cache capacity per collection
+    pub result_cache_capacity: Option<usize>,
+    /// New slow query threshold
+    pub slow_query_threshold_ms: Option<u64>,
+    /// New log filter directive
+    pub log_level: Option<String>,
+    /// New sustained requests per second per client; 0 disables the limit
+    pub per_client_qps: Option<f64>,
+    /// New burst allowance
+    pub burst: Option<u32>,
+    /// New search queue timeout
+    pub queue_timeout_ms: Option<u64>,
+}
+
+/// Current runtime settings and the hooks used to apply them
+pub struct RuntimeTuning {
+    config: RwLock<RuntimeConfig>,
+    log_level_hook: RwLock<Option<LogLevelHook>>,
+    updates: AtomicU64,
+}
+
+impl RuntimeTuning {
+    /// Start from `config`
+    pub fn new(config: RuntimeConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            log_level_hook: RwLock::new(None),
+            updates: AtomicU64::new(0),
+        }
+    }
+
+    /// Snapshot of the current settings
+    pub fn config(&self) -> RuntimeConfig {
+        self.config.read().clone()
+    }
+
+    /// efSearch to use for a search that did not set one
+    pub fn default_ef_search(&self) -> Option<usize> {
+        self.config.read().default_ef_search
+    }
+
+    /// Updates applied since startup
+    pub fn update_count(&self) -> u64 {
+        self.updates.load(Ordering::Relaxed)
+    }
+
+    /// Register the hook that applies log level changes
+    pub fn set_log_level_hook(&self, hook: LogLevelHook) {
+        *self.log_level_hook.write() = Some(hook);
+    }
+
+    /// Validate `update` and merge it into the current settings
+    ///
+    /// Returns the previous and the new settings. Nothing changes if the
+    /// update is invalid or the log level hook rejects it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] for out-of-range values, a log level
+    /// with no hook registered, or a log level the hook rejects
+    pub(crate) fn merge(
+        &self,
+        update: &RuntimeConfigUpdate,
+    ) -> Result<(RuntimeConfig, RuntimeConfig)> {
+        if update
+            .per_client_qps
+            .map_or(false, |qps| qps < 0.0 || !qps.is_finite())
+        {
+            return Err(Error::InvalidRequest(
+                "per_client_qps must be a non-negative number".to_string(),
+            ));
+        }
+
+        let mut config = self.config.write();
+        let previous = config.clone();
+
+        if let Some(level) = &update.log_level {
+            let hook = self.log_level_hook.read().clone().ok_or_else(|| {
+                Error::InvalidRequest(
+                    "log level changes are not supported by this process".to_string(),
+                )
+            })?;
+            hook(level).map_err(|e| {
+                Error::InvalidRequest(format!("Invalid log level '{}': {}", level, e))
+            })?;
+            config.log_level = Some(level.clone());
+        }
+        if let Some(ef) = update.default_ef_search {
+            config.default_ef_search = (ef > 0).then_some(ef);
+        }
+        if let Some(capacity) = update.result_cache_capacity {
+            config.result_cache_capacity = capacity;
+        }
+        if let Some(threshold) = update.slow_query_threshold_ms {
+            config.slow_query_threshold_ms = threshold;
+        }
+        if let Some(qps) = update.per_client_qps {
+            config.admission.per_client_qps = qps;
+        }
+        if let Some(burst) = update.burst {
+            config.admission.burst = burst;
+        }
+        if let Some(timeout) = update.queue_timeout_ms {
+            config.admission.queue_timeout_ms = timeout;
+        }
+
+        self.updates.fetch_add(1, Ordering::Relaxed);
+        Ok((previous, config.clone()))
+    }
+}
+
+/// Bring a collection's result cache and slow query log in line with `config`
+///
+/// `previous` is the setting the collection was configured with, or `None`
+/// for a new collection.
+pub(crate) fn apply_to_collection(
+    db: &VectorDB,
+    config: &RuntimeConfig,
+    previous: Option<&RuntimeConfig>,
+) {
+    let capacity_changed = previous.map_or(true, |p| {
+        p.result_cache_capacity != config.result_cache_capacity
+    });
+    if capacity_changed {
+        db.disable_result_cache();
+        if config.result_cache_capacity > 0 {
+            db.enable_result_cache(ResultCacheConfig {
+                capacity: config.result_cache_capacity,
+                ..Default::default()
+            });
+        }
+    }
+
+    if let Some(log) = db.slow_query_log() {
+        log.set_threshold(Duration::from_millis(config.slow_query_threshold_ms));
+    }
+}
+
+/// A JSON file holding a [`RuntimeConfigUpdate`], polled for changes
+#[derive(Debug, Clone)]
+pub struct ConfigWatch {
+    /// File to watch
+    pub path: PathBuf,
+    /// How often the modification time is checked
+    pub interval: Duration,
+}
+
+impl ConfigWatch {
+    /// Read and parse the file
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] if the file cannot be read and
+    /// [`Error::Serialization`] if it is not a valid update
+    pub fn load(&self) -> Result<RuntimeConfigUpdate> {
+        let text = std::fs::read_to_string(&self.path)
+            .map_err(|e| Error::Config(format!("Failed to read {}: {}", self.path.display(), e)))?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    /// Modification time of the file, if it exists
+    pub fn modified(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .ok()
+    }
+}
+
+/// Poll `watch` and apply the file whenever its modification time changes
+///
+/// The file is applied once at startup if it exists. Invalid files are
+/// logged and skipped, leaving the current settings in place.
+pub(crate) fn spawn_watch(state: AppState, watch: ConfigWatch) {
+    tokio::spawn(async move {
+        let mut last_modified = None;
+        let mut ticker = tokio::time::interval(watch.interval);
+        loop {
+            ticker.tick().await;
+            let modified = watch.modified();
+            if modified.is_none() || modified == last_modified {
+                continue;
+            }
+            last_modified = modified;
+
+            match watch
+                .load()
+                .and_then(|update| state.apply_runtime_update(&update))
+            {
+                Ok(_) => tracing::info!("Reloaded runtime settings from {}", watch.path.display()),
+                Err(e) => tracing::warn!(
+                    "Ignoring runtime settings in {}: {}",
+                    watch.path.display(),
+                    e
+                ),
+            }
+        }
+    });
+}
@@ -1,7 +1,10 @@
 //! Shared application state
 
+use crate::admission::{AdmissionConfig, AdmissionController};
+use crate::runtime::{self, RuntimeConfig, RuntimeConfigUpdate, RuntimeTuning};
+use crate::Result;
 use dashmap::DashMap;
-use ruvector_core::VectorDB;
+use ruvector_core::{MaintenanceConfig, SlowQueryConfig, VectorDB};
 use std::sync::Arc;
 
 /// Shared application state
@@ -9,23 +12,78 @@ use std::sync::Arc;
 pub struct AppState {
     /// Map of collection name to VectorDB
     pub collections: Arc<DashMap<String, Arc<VectorDB>>>,
+    /// Rate limiter and search concurrency gate
+    pub admission: Arc<AdmissionController>,
+    /// Slow query log settings for new collections
+    pub slow_query: SlowQueryConfig,
+    /// Settings that can change without a restart
+    pub runtime: Arc<RuntimeTuning>,
+    /// Maintenance scheduler settings for new collections
+    pub maintenance: Option<MaintenanceConfig>,
 }
 
 impl AppState {
     /// Create a new application state
     pub fn new() -> Self {
+        Self::with_admission(AdmissionConfig::default())
+    }
+
+    /// Create a new application state with the given admission settings
+    pub fn with_admission(config: AdmissionConfig) -> Self {
+        let slow_query = SlowQueryConfig::default();
         Self {
             collections: Arc::new(DashMap::new()),
+            runtime: Arc::new(RuntimeTuning::new(initial_runtime(&config, &slow_query))),
+            admission: Arc::new(AdmissionController::new(config)),
+            slow_query,
+            maintenance: None,
         }
     }
 
+    /// Use `config` for the slow query logs of collections inserted later
+    pub fn with_slow_query(mut self, config: SlowQueryConfig) -> Self {
+        self.runtime = Arc::new(RuntimeTuning::new(initial_runtime(
+            &self.admission.config(),
+            &config,
+        )));
+        self.slow_query = config;
+        self
+    }
+
+    /// Run background maintenance on collections inserted later
+    pub fn with_maintenance(mut self, config: Option<MaintenanceConfig>) -> Self {
+        self.maintenance = config;
+        self
+    }
+
+    /// Apply a runtime settings update to the server and every collection
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the update is invalid; nothing is changed then
+    pub fn apply_runtime_update(&self, update: &RuntimeConfigUpdate) -> Result<RuntimeConfig> {
+        let (previous, config) = self.runtime.merge(update)?;
+        self.admission.reconfigure(&config.admission);
+        for entry in self.collections.iter() {
+            runtime::apply_to_collection(entry.value(), &config, Some(&previous));
+        }
+        tracing::info!("Applied runtime settings update: {:?}", update);
+        Ok(config)
+    }
+
     /// Get a collection by name
     pub fn get_collection(&self, name: &str) -> Option<Arc<VectorDB>> {
         self.collections.get(name).map(|c| c.clone())
     }
 
-    /// Insert a collection
+    /// Insert a collection, enabling its slow query log and maintenance
+    /// scheduler and applying the current runtime settings
     pub fn insert_collection(&self, name: String, db: Arc<VectorDB>) {
+        db.enable_slow_query_log(self.slow_query.clone());
+        if let Some(config) = &self.maintenance {
+            db.start_maintenance(config.clone());
+        }
+        runtime::apply_to_collection(&db, &self.runtime.config(), None);
         self.collections.insert(name, db);
     }
 
@@ -53,6 +111,16 @@ impl AppState {
     }
 }
 
+fn initial_runtime(admission: &AdmissionConfig, slow_query: &SlowQueryConfig) -> RuntimeConfig {
+    RuntimeConfig {
+        default_ef_search: None,
+        result_cache_capacity: 0,
+        slow_query_threshold_ms: slow_query.threshold.as_millis() as u64,
+        log_level: None,
+        admission: admission.clone(),
+    }
+}
+
 impl Default for AppState {
     fn default() -> Self {
         Self::new()
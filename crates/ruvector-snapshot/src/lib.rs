@@ -2,15 +2,27 @@
 //!
 //! This crate provides backup and restore capabilities for vector collections,
 //! including compression, checksums, and multiple storage backends.
+//!
+//! With default features disabled only the snapshot types and the
+//! [`portable`] single-file format are built, which is what WASM builds use.
 
 mod error;
+#[cfg(feature = "storage")]
 mod manager;
+pub mod portable;
 mod snapshot;
+#[cfg(feature = "storage")]
 mod storage;
 
 pub use error::{Result, SnapshotError};
+#[cfg(feature = "storage")]
 pub use manager::SnapshotManager;
-pub use snapshot::{Snapshot, SnapshotData, SnapshotMetadata, VectorRecord};
+pub use portable::{PortableSnapshot, PORTABLE_FORMAT_VERSION};
+pub use snapshot::{
+    CollectionConfig, DistanceMetric, HnswConfig, Snapshot, SnapshotData, SnapshotMetadata,
+    VectorRecord,
+};
+#[cfg(feature = "storage")]
 pub use storage::{LocalStorage, SnapshotStorage};
 
 #[cfg(test)]
@@ -18,6 +30,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg(feature = "storage")]
     fn test_module_exports() {
         // Verify all public exports are accessible
         let _: Option<SnapshotError> = None;
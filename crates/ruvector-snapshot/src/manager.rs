@@ -1,4 +1,5 @@
 use crate::error::{Result, SnapshotError};
+use crate::portable::PortableSnapshot;
 use crate::snapshot::{Snapshot, SnapshotData};
 use crate::storage::SnapshotStorage;
 
@@ -144,6 +145,23 @@ impl SnapshotManager {
         Ok(deleted)
     }
 
+    /// Export a stored snapshot as a portable snapshot file
+    ///
+    /// See [`crate::portable`]; the bytes can be imported here or by rvlite.
+    pub async fn export_portable(&self, id: &str) -> Result<Vec<u8>> {
+        let data = self.restore_snapshot(id).await?;
+        PortableSnapshot::new(data).to_bytes()
+    }
+
+    /// Import a portable snapshot file and store it
+    ///
+    /// Extension sections are not kept; only the collection configuration
+    /// and vectors are.
+    pub async fn import_portable(&self, bytes: &[u8]) -> Result<Snapshot> {
+        let portable = PortableSnapshot::from_bytes(bytes)?;
+        self.create_snapshot(portable.data).await
+    }
+
     /// Get the total size of all snapshots in bytes
     pub async fn total_size(&self) -> Result<u64> {
         let snapshots = self.storage.list().await?;
@@ -0,0 +1,265 @@
+//! Portable single-file snapshots
+//!
+//! [`LocalStorage`](crate::LocalStorage) keeps a snapshot as two files. To
+//! move a collection between machines, or between the native engine and
+//! rvlite in the browser, a snapshot is instead written as one byte string:
+//!
+//! ```text
+//! "RVSNAP" | format version (u16 LE) | SHA-256 of payload (32 bytes)
+//!          | writer version length (u8) | writer crate version | gzip(payload)
+//! ```
+//!
+//! where the payload is the bincode encoding of a [`PortableSnapshot`]:
+//! the same [`SnapshotData`] the native side stores, plus named extension
+//! sections for state a reader may not understand (rvlite's graph, for
+//! example). Readers ignore extensions they do not know.
+//!
+//! Format 1 files, which lack the writer version, are still readable. Files
+//! from a newer format are rejected naming the release that wrote them.
+
+use bincode::{Decode, Encode};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use crate::error::{Result, SnapshotError};
+use crate::snapshot::SnapshotData;
+
+/// Leading bytes of every portable snapshot
+pub const PORTABLE_MAGIC: &[u8; 6] = b"RVSNAP";
+
+/// Current portable format version
+pub const PORTABLE_FORMAT_VERSION: u16 = 2;
+
+const HEADER_LEN: usize = PORTABLE_MAGIC.len() + 2 + 32;
+
+/// Snapshot data plus named extension sections
+#[derive(Debug, Encode, Decode)]
+pub struct PortableSnapshot {
+    /// Collection configuration and vectors
+    pub data: SnapshotData,
+
+    /// Extension sections by name, e.g. `"rvlite.graph"`
+    pub extensions: BTreeMap<String, String>,
+}
+
+impl PortableSnapshot {
+    /// Wrap snapshot data without extensions
+    pub fn new(data: SnapshotData) -> Self {
+        Self {
+            data,
+            extensions: BTreeMap::new(),
+        }
+    }
+
+    /// Add an extension section
+    pub fn with_extension(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extensions.insert(name.into(), value.into());
+        self
+    }
+
+    /// Get an extension section by name
+    pub fn extension(&self, name: &str) -> Option<&str> {
+        self.extensions.get(name).map(String::as_str)
+    }
+
+    /// Crate version that wrote a portable snapshot file, if recorded
+    pub fn writer_version(bytes: &[u8]) -> Result<Option<String>> {
+        let (writer, _) = parse_header(bytes)?;
+        Ok(writer.map(str::to_string))
+    }
+
+    /// Encode into a portable snapshot file
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let payload = bincode::encode_to_vec(self, bincode::config::standard())
+            .map_err(|e| SnapshotError::SerializationError(e.to_string()))?;
+        let digest = Sha256::digest(&payload);
+        let compressed = compress(&payload)?;
+
+        let writer = env!("CARGO_PKG_VERSION").as_bytes();
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + 1 + writer.len() + compressed.len());
+        bytes.extend_from_slice(PORTABLE_MAGIC);
+        bytes.extend_from_slice(&PORTABLE_FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&digest);
+        bytes.push(writer.len() as u8);
+        bytes.extend_from_slice(writer);
+        bytes.extend_from_slice(&compressed);
+        Ok(bytes)
+    }
+
+    /// Decode a portable snapshot file, verifying its checksum
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let (_, body) = parse_header(bytes)?;
+
+        let expected = hex(&bytes[8..HEADER_LEN]);
+        let payload = decompress(body)?;
+        let actual = calculate_checksum(&payload);
+        if actual != expected {
+            return Err(SnapshotError::InvalidChecksum { expected, actual });
+        }
+
+        let (snapshot, _): (PortableSnapshot, usize) =
+            bincode::decode_from_slice(&payload, bincode::config::standard())
+                .map_err(|e| SnapshotError::SerializationError(e.to_string()))?;
+        Ok(snapshot)
+    }
+}
+
+/// Split a portable snapshot into its writer version and compressed body
+fn parse_header(bytes: &[u8]) -> Result<(Option<&str>, &[u8])> {
+    if bytes.len() < HEADER_LEN || &bytes[..PORTABLE_MAGIC.len()] != PORTABLE_MAGIC {
+        return Err(SnapshotError::corrupted("Not a portable snapshot"));
+    }
+
+    let version = u16::from_le_bytes([bytes[6], bytes[7]]);
+    if version == 1 {
+        return Ok((None, &bytes[HEADER_LEN..]));
+    }
+
+    // Formats from 2 on record the writer version right after the checksum
+    let writer = bytes.get(HEADER_LEN).and_then(|&len| {
+        let start = HEADER_LEN + 1;
+        bytes
+            .get(start..start + len as usize)
+            .and_then(|w| std::str::from_utf8(w).ok())
+    });
+    let Some(writer) = writer else {
+        return Err(SnapshotError::corrupted(
+            "Truncated portable snapshot header",
+        ));
+    };
+
+    if version > PORTABLE_FORMAT_VERSION {
+        return Err(SnapshotError::corrupted(format!(
+            "Portable snapshot format {} was written by ruvector-snapshot {}; \
+             this build ({}) reads up to format {}",
+            version,
+            writer,
+            env!("CARGO_PKG_VERSION"),
+            PORTABLE_FORMAT_VERSION
+        )));
+    }
+
+    Ok((Some(writer), &bytes[HEADER_LEN + 1 + writer.len()..]))
+}
+
+/// Compress data using gzip
+pub(crate) fn compress(data: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(data)
+        .map_err(|e| SnapshotError::compression(format!("Compression failed: {}", e)))?;
+    encoder
+        .finish()
+        .map_err(|e| SnapshotError::compression(format!("Finish compression failed: {}", e)))
+}
+
+/// Decompress gzip data
+pub(crate) fn decompress(data: &[u8]) -> Result<Vec<u8>> {
+    let mut decoder = GzDecoder::new(data);
+    let mut decompressed = Vec::new();
+    decoder
+        .read_to_end(&mut decompressed)
+        .map_err(|e| SnapshotError::compression(format!("Decompression failed: {}", e)))?;
+    Ok(decompressed)
+}
+
+/// Calculate SHA-256 checksum
+pub(crate) fn calculate_checksum(data: &[u8]) -> String {
+    hex(&Sha256::digest(data))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{CollectionConfig, DistanceMetric, VectorRecord};
+
+    #[test]
+    fn test_compression_roundtrip() {
+        let data = b"Hello, World! This is test data for compression.";
+        let compressed = compress(data).unwrap();
+        let decompressed = decompress(&compressed).unwrap();
+        assert_eq!(data.to_vec(), decompressed);
+    }
+
+    #[test]
+    fn test_checksum_calculation() {
+        let data = b"test data";
+        let checksum = calculate_checksum(data);
+        assert_eq!(checksum.len(), 64); // SHA-256 produces 64 hex characters
+    }
+
+    #[test]
+    fn test_portable_roundtrip() {
+        let config = CollectionConfig {
+            dimension: 2,
+            metric: DistanceMetric::Cosine,
+            hnsw_config: None,
+        };
+        let vectors = vec![VectorRecord::new(
+            "v1".to_string(),
+            vec![1.0, 0.0],
+            Some(serde_json::json!({"label": "a"})),
+        )];
+        let snapshot =
+            PortableSnapshot::new(SnapshotData::new("docs".to_string(), config, vectors))
+                .with_extension("rvlite.graph", "{}");
+
+        let mut bytes = snapshot.to_bytes().unwrap();
+        assert_eq!(
+            PortableSnapshot::writer_version(&bytes).unwrap().as_deref(),
+            Some(env!("CARGO_PKG_VERSION"))
+        );
+        let restored = PortableSnapshot::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.data.collection_name(), "docs");
+        assert_eq!(restored.data.vectors[0].payload().unwrap()["label"], "a");
+        assert_eq!(restored.extension("rvlite.graph"), Some("{}"));
+
+        // A corrupted checksum is detected
+        bytes[10] ^= 0xff;
+        assert!(matches!(
+            PortableSnapshot::from_bytes(&bytes),
+            Err(SnapshotError::InvalidChecksum { .. })
+        ));
+        assert!(PortableSnapshot::from_bytes(b"nope").is_err());
+    }
+
+    #[test]
+    fn test_portable_format_versions() {
+        let config = CollectionConfig {
+            dimension: 1,
+            metric: DistanceMetric::Euclidean,
+            hnsw_config: None,
+        };
+        let snapshot = PortableSnapshot::new(SnapshotData::new("v".to_string(), config, vec![]));
+        let bytes = snapshot.to_bytes().unwrap();
+
+        // Format 1 had no writer version between the checksum and the body
+        let writer_len = bytes[HEADER_LEN] as usize;
+        let mut v1 = bytes[..HEADER_LEN].to_vec();
+        v1[6..8].copy_from_slice(&1u16.to_le_bytes());
+        v1.extend_from_slice(&bytes[HEADER_LEN + 1 + writer_len..]);
+        assert_eq!(PortableSnapshot::writer_version(&v1).unwrap(), None);
+        assert_eq!(
+            PortableSnapshot::from_bytes(&v1)
+                .unwrap()
+                .data
+                .collection_name(),
+            "v"
+        );
+
+        // A newer format names the release that wrote it
+        let mut newer = bytes.clone();
+        newer[6..8].copy_from_slice(&(PORTABLE_FORMAT_VERSION + 1).to_le_bytes());
+        let err = PortableSnapshot::from_bytes(&newer).unwrap_err();
+        assert!(err.to_string().contains(env!("CARGO_PKG_VERSION")));
+    }
+}
@@ -108,6 +108,7 @@ pub enum DistanceMetric {
     Cosine,
     Euclidean,
     DotProduct,
+    Manhattan,
 }
 
 /// HNSW index configuration
@@ -1,13 +1,9 @@
 use async_trait::async_trait;
-use flate2::read::GzDecoder;
-use flate2::write::GzEncoder;
-use flate2::Compression;
-use sha2::{Digest, Sha256};
-use std::io::{Read, Write};
 use std::path::PathBuf;
 use tokio::fs;
 
 use crate::error::{Result, SnapshotError};
+use crate::portable::{calculate_checksum, compress, decompress};
 use crate::snapshot::{Snapshot, SnapshotData};
 
 /// Trait for snapshot storage backends
@@ -47,34 +43,6 @@ impl LocalStorage {
         self.base_path.join(format!("{}.metadata.json", id))
     }
 
-    /// Compress data using gzip
-    fn compress(data: &[u8]) -> Result<Vec<u8>> {
-        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
-        encoder
-            .write_all(data)
-            .map_err(|e| SnapshotError::compression(format!("Compression failed: {}", e)))?;
-        encoder
-            .finish()
-            .map_err(|e| SnapshotError::compression(format!("Finish compression failed: {}", e)))
-    }
-
-    /// Decompress gzip data
-    fn decompress(data: &[u8]) -> Result<Vec<u8>> {
-        let mut decoder = GzDecoder::new(data);
-        let mut decompressed = Vec::new();
-        decoder
-            .read_to_end(&mut decompressed)
-            .map_err(|e| SnapshotError::compression(format!("Decompression failed: {}", e)))?;
-        Ok(decompressed)
-    }
-
-    /// Calculate SHA-256 checksum
-    fn calculate_checksum(data: &[u8]) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(data);
-        format!("{:x}", hasher.finalize())
-    }
-
     /// Ensure the base directory exists
     async fn ensure_dir(&self) -> Result<()> {
         if !self.base_path.exists() {
@@ -99,10 +67,10 @@ impl SnapshotStorage for LocalStorage {
             .map_err(|e| SnapshotError::SerializationError(e.to_string()))?;
 
         // Calculate checksum before compression
-        let checksum = Self::calculate_checksum(&serialized);
+        let checksum = calculate_checksum(&serialized);
 
         // Compress data
-        let compressed = Self::compress(&serialized)?;
+        let compressed = compress(&serialized)?;
         let size_bytes = compressed.len() as u64;
 
         // Write compressed data
@@ -146,10 +114,10 @@ impl SnapshotStorage for LocalStorage {
         let compressed = fs::read(&snapshot_path).await?;
 
         // Decompress
-        let decompressed = Self::decompress(&compressed)?;
+        let decompressed = decompress(&compressed)?;
 
         // Verify checksum
-        let actual_checksum = Self::calculate_checksum(&decompressed);
+        let actual_checksum = calculate_checksum(&decompressed);
         if actual_checksum != snapshot.checksum {
             return Err(SnapshotError::InvalidChecksum {
                 expected: snapshot.checksum,
@@ -219,21 +187,6 @@ mod tests {
     use super::*;
     use crate::snapshot::{CollectionConfig, DistanceMetric, VectorRecord};
 
-    #[test]
-    fn test_compression_roundtrip() {
-        let data = b"Hello, World! This is test data for compression.";
-        let compressed = LocalStorage::compress(data).unwrap();
-        let decompressed = LocalStorage::decompress(&compressed).unwrap();
-        assert_eq!(data.to_vec(), decompressed);
-    }
-
-    #[test]
-    fn test_checksum_calculation() {
-        let data = b"test data";
-        let checksum = LocalStorage::calculate_checksum(data);
-        assert_eq!(checksum.len(), 64); // SHA-256 produces 64 hex characters
-    }
-
     #[tokio::test]
     async fn test_local_storage_roundtrip() {
         let temp_dir = std::env::temp_dir().join("ruvector-snapshot-test");
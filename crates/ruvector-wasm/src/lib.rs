@@ -15,6 +15,7 @@ use ruvector_collections::{
     CollectionConfig as CoreCollectionConfig, CollectionManager as CoreCollectionManager,
 };
 use ruvector_core::{
+    classifier::ClassifyConfig,
     error::RuvectorError,
     types::{DbOptions, DistanceMetric, HnswConfig, SearchQuery, SearchResult, VectorEntry},
     vector_db::VectorDB as CoreVectorDB,
@@ -354,6 +355,56 @@ impl VectorDB {
         Ok(entry.map(|e| JsVectorEntry { inner: e }))
     }
 
+    /// Check whether a vector ID may be stored, without reading storage
+    ///
+    /// # Returns
+    /// false if the ID is definitely not stored; true if it probably is
+    #[wasm_bindgen(js_name = maybeContains)]
+    pub fn maybe_contains(&self, id: &str) -> bool {
+        let db = self.db.lock();
+        db.maybe_contains(id)
+    }
+
+    /// Maintain a centroid for each value of the metadata field `key`
+    ///
+    /// # Returns
+    /// false if the group already existed
+    #[wasm_bindgen(js_name = defineCentroidGroup)]
+    pub fn define_centroid_group(&self, key: &str) -> Result<bool, JsValue> {
+        let db = self.db.lock();
+        db.define_centroid_group(key)
+            .map_err(|e| JsValue::from(WasmError::from(e)))
+    }
+
+    /// Predict the `field` label of a vector from labelled entries
+    ///
+    /// # Arguments
+    /// * `vector` - Float32Array to classify
+    /// * `field` - Metadata field holding labels
+    /// * `k` - Neighbors that vote; if omitted the nearest centroid of the
+    ///   group on `field` wins
+    ///
+    /// # Returns
+    /// `{label, distance, margin, confidence, support}`, or undefined when no
+    /// labelled entry is available
+    #[wasm_bindgen]
+    pub fn classify(
+        &self,
+        vector: Float32Array,
+        field: &str,
+        k: Option<usize>,
+    ) -> Result<JsValue, JsValue> {
+        let config = match k {
+            Some(k) => ClassifyConfig::knn(field, k),
+            None => ClassifyConfig::centroid(field),
+        };
+        let db = self.db.lock();
+        let result = db
+            .classify(&vector.to_vec(), &config)
+            .map_err(|e| JsValue::from(WasmError::from(e)))?;
+        to_value(&result).map_err(|e| JsValue::from_str(&format!("Serialization failed: {}", e)))
+    }
+
     /// Get the number of vectors in the database
     #[wasm_bindgen]
     pub fn len(&self) -> Result<usize, JsValue> {
@@ -413,6 +464,16 @@ pub fn detect_simd() -> bool {
     }
 }
 
+/// Report enabled features (SIMD level, storage backend, threads, codecs)
+#[wasm_bindgen]
+pub fn capabilities() -> Result<JsValue, JsValue> {
+    let mut caps = ruvector_core::capabilities();
+    if detect_simd() {
+        caps.simd_level = "simd128".to_string();
+    }
+    to_value(&caps).map_err(|e| JsValue::from_str(&format!("Serialization failed: {}", e)))
+}
+
 /// Get version information
 #[wasm_bindgen]
 pub fn version() -> String {
@@ -517,6 +578,10 @@ impl CollectionManager {
             hnsw_config: Some(HnswConfig::default()),
             quantization: None,
             on_disk_payload: false, // Disable for WASM
+            embedding_model: None,
+            strict_model: false,
+            normalization: Default::default(),
+            secondary_metrics: Vec::new(),
         };
 
         let manager = self.inner.lock();
@@ -23,6 +23,11 @@
 //! // Search
 //! const results = db.search([0.1, 0.2, 0.3, ...], 10);
 //!
+//! // Text, with any async embedder (e.g. a transformers.js pipeline)
+//! db.set_embedder(async (text) => embed(text));
+//! await db.insertText("RvLite runs in the browser", null);
+//! const hits = await db.searchText("browser database", 5);
+//!
 //! // Cypher queries
 //! db.cypher("CREATE (n:Person {name: 'Alice'})");
 //!
@@ -33,12 +38,21 @@
 //! // Persistence
 //! await db.save();  // Save to IndexedDB
 //! const db2 = await RvLite.load(config);  // Load from IndexedDB
+//!
+//! // Transactions: vectors and the graph nodes pointing at them are
+//! // persisted together or not at all
+//! db.begin_transaction();
+//! db.insert_with_id("doc1", [0.1, 0.2, 0.3, ...], null);
+//! db.cypher("CREATE (d:Doc {vector_id: 'doc1'})");
+//! await db.commit_transaction();
 //! ```
 
 use wasm_bindgen::prelude::*;
-use wasm_bindgen_futures::future_to_promise;
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 // Import ruvector-core
 use ruvector_core::{
@@ -55,6 +69,7 @@ pub mod storage;
 
 // Re-export storage types
 pub use storage::{RvLiteState, VectorState, GraphState, TripleStoreState};
+pub use storage::{QuotaLevel, QuotaPolicy, QuotaStatus};
 
 #[wasm_bindgen(start)]
 pub fn init() {
@@ -181,12 +196,18 @@ impl RvLiteConfig {
 /// Main RvLite database
 #[wasm_bindgen]
 pub struct RvLite {
-    db: VectorDB,
+    db: Arc<VectorDB>,
     config: RvLiteConfig,
     cypher_engine: cypher::CypherEngine,
     sql_engine: sql::SqlEngine,
     triple_store: sparql::TripleStore,
     storage: Option<storage::IndexedDBStorage>,
+    /// State when the open transaction began
+    transaction: Option<RvLiteState>,
+    quota_policy: QuotaPolicy,
+    quota_listener: Option<js_sys::Function>,
+    insertion_order: Arc<Mutex<storage::state::InsertionOrder>>,
+    embedder: Option<js_sys::Function>,
 }
 
 #[wasm_bindgen]
@@ -198,12 +219,17 @@ impl RvLite {
             .map_err(|e| RvLiteError::from(e))?;
 
         Ok(RvLite {
-            db,
+            db: Arc::new(db),
             config,
             cypher_engine: cypher::CypherEngine::new(),
             sql_engine: sql::SqlEngine::new(),
             triple_store: sparql::TripleStore::new(),
             storage: None,
+            transaction: None,
+            quota_policy: QuotaPolicy::default(),
+            quota_listener: None,
+            insertion_order: Arc::new(Mutex::new(Default::default())),
+            embedder: None,
         })
     }
 
@@ -257,15 +283,14 @@ impl RvLite {
 
     /// Save database state to IndexedDB
     /// Returns a Promise that resolves when save is complete
+    ///
+    /// While a transaction is open this saves the state from before it began.
     pub fn save(&self) -> js_sys::Promise {
-        let state = self.export_state();
-        let mut storage = storage::IndexedDBStorage::new();
-
-        future_to_promise(async move {
-            storage.init().await?;
-            storage.save(&state).await?;
-            Ok(JsValue::TRUE)
-        })
+        let state = self
+            .transaction
+            .clone()
+            .unwrap_or_else(|| self.export_state());
+        self.persist(state)
     }
 
     /// Load database from IndexedDB
@@ -322,6 +347,160 @@ impl RvLite {
         self.import_state(&state)
     }
 
+    /// Set the storage quota policy
+    ///
+    /// Accepts `{ warn_ratio, critical_ratio, auto_compress,
+    /// compress_fraction }`; omitted fields keep their defaults (0.8, 0.95,
+    /// false, 0.25). Compression is lossy: it keeps one byte per dimension
+    /// of the oldest vectors in saved state.
+    pub fn set_quota_policy(&mut self, policy: JsValue) -> Result<(), JsValue> {
+        self.quota_policy = serde_wasm_bindgen::from_value(policy)
+            .map_err(|e| RvLiteError {
+                message: format!("Invalid quota policy: {}", e),
+                kind: ErrorKind::WasmError,
+            })?;
+        Ok(())
+    }
+
+    /// Register a callback invoked with a quota status object whenever a
+    /// save finds storage past the warning ratio
+    pub fn on_quota_pressure(&mut self, callback: js_sys::Function) {
+        self.quota_listener = Some(callback);
+    }
+
+    /// Estimate storage usage
+    /// Returns a Promise with `{ usage, quota, level, compressed }`, or null
+    /// where the browser does not report it
+    pub fn storage_estimate(&self) -> js_sys::Promise {
+        let policy = self.quota_policy.clone();
+
+        future_to_promise(async move {
+            match storage::quota::estimate().await? {
+                Some((usage, quota)) => {
+                    let status = QuotaStatus {
+                        usage,
+                        quota,
+                        level: policy.level(usage, quota),
+                        compressed: 0,
+                    };
+                    Ok(serde_wasm_bindgen::to_value(&status)?)
+                }
+                None => Ok(JsValue::NULL),
+            }
+        })
+    }
+
+    // ===== Transactions =====
+
+    /// Begin a transaction spanning vector inserts, Cypher and triple writes
+    ///
+    /// Changes apply immediately but are only persisted by
+    /// `commit_transaction()`; SQL tables are not covered.
+    pub fn begin_transaction(&mut self) -> Result<(), JsValue> {
+        if self.transaction.is_some() {
+            return Err(RvLiteError {
+                message: "A transaction is already open".to_string(),
+                kind: ErrorKind::StorageError,
+            }.into());
+        }
+        self.transaction = Some(self.export_state());
+        Ok(())
+    }
+
+    /// Commit the open transaction and persist the database to IndexedDB in
+    /// a single write
+    ///
+    /// Rejects, leaving the transaction open, if a graph node's `vector_id`
+    /// property names a vector that does not exist.
+    pub fn commit_transaction(&mut self) -> js_sys::Promise {
+        if self.transaction.is_none() {
+            return js_sys::Promise::reject(&RvLiteError {
+                message: "No transaction is open".to_string(),
+                kind: ErrorKind::StorageError,
+            }.into());
+        }
+
+        let state = self.export_state();
+        let missing = state.missing_vector_refs();
+        if !missing.is_empty() {
+            return js_sys::Promise::reject(&RvLiteError {
+                message: format!("Nodes reference missing vectors: {}", missing.join(", ")),
+                kind: ErrorKind::StorageError,
+            }.into());
+        }
+        self.transaction = None;
+        self.persist(state)
+    }
+
+    /// Discard all changes made since `begin_transaction()`
+    pub fn rollback_transaction(&mut self) -> Result<(), JsValue> {
+        let snapshot = self.transaction.take().ok_or_else(|| RvLiteError {
+            message: "No transaction is open".to_string(),
+            kind: ErrorKind::StorageError,
+        })?;
+
+        self.replace_state(&snapshot)
+    }
+
+    /// Check if a transaction is open
+    pub fn in_transaction(&self) -> bool {
+        self.transaction.is_some()
+    }
+
+    // ===== Portable Snapshots =====
+
+    /// Export the database as a portable snapshot file
+    ///
+    /// The bytes use the same versioned snapshot format as the native side,
+    /// so they can be restored there or by `importSnapshot` on another
+    /// device. SQL tables are not included.
+    #[wasm_bindgen(js_name = exportSnapshot)]
+    pub fn export_snapshot(&self) -> Result<Vec<u8>, JsValue> {
+        let state = self.transaction.clone().unwrap_or_else(|| self.export_state());
+        let created_at = String::from(js_sys::Date::new_0().to_iso_string());
+
+        storage::portable::to_portable(&state, created_at)
+            .and_then(|snapshot| snapshot.to_bytes().map_err(|e| e.to_string()))
+            .map_err(|message| RvLiteError {
+                message: format!("Snapshot export failed: {}", message),
+                kind: ErrorKind::StorageError,
+            }.into())
+    }
+
+    /// Replace the database contents with a portable snapshot file
+    ///
+    /// The snapshot must have the configured dimensions. Fails while a
+    /// transaction is open.
+    #[wasm_bindgen(js_name = importSnapshot)]
+    pub fn import_snapshot(&mut self, bytes: &[u8]) -> Result<(), JsValue> {
+        if self.transaction.is_some() {
+            return Err(RvLiteError {
+                message: "Cannot import a snapshot inside a transaction".to_string(),
+                kind: ErrorKind::StorageError,
+            }.into());
+        }
+
+        let state = ruvector_snapshot::PortableSnapshot::from_bytes(bytes)
+            .map_err(|e| e.to_string())
+            .and_then(|snapshot| storage::portable::from_portable(&snapshot))
+            .map_err(|message| RvLiteError {
+                message: format!("Snapshot import failed: {}", message),
+                kind: ErrorKind::StorageError,
+            })?;
+
+        if state.vectors.dimensions != self.config.dimensions {
+            return Err(RvLiteError {
+                message: format!(
+                    "Snapshot has {} dimensions, database has {}",
+                    state.vectors.dimensions, self.config.dimensions
+                ),
+                kind: ErrorKind::StorageError,
+            }.into());
+        }
+
+        self.replace_state(&state)
+    }
+
     // ===== Vector Operations =====
 
     /// Insert a vector with optional metadata
@@ -343,8 +522,10 @@ impl RvLite {
             metadata: metadata_map,
         };
 
-        self.db.insert(entry)
-            .map_err(|e| RvLiteError::from(e).into())
+        let id = self.db.insert(entry)
+            .map_err(|e| RvLiteError::from(e))?;
+        self.insertion_order.lock().record(&id);
+        Ok(id)
     }
 
     /// Insert a vector with a specific ID
@@ -365,12 +546,94 @@ impl RvLite {
             metadata: metadata_map,
         };
 
-        self.db.insert(entry)
+        let id = self.db.insert(entry)
             .map_err(|e| RvLiteError::from(e))?;
+        self.insertion_order.lock().record(&id);
 
         Ok(())
     }
 
+    // ===== Text Operations =====
+
+    /// Set the embedder used by `insertText` and `searchText`
+    ///
+    /// The embedder is called with a string and returns an array or
+    /// `Float32Array` of the configured dimensions, or a Promise of one, so a
+    /// transformers.js pipeline plugs in directly:
+    ///
+    /// ```javascript
+    /// const extractor = await pipeline('feature-extraction', 'Xenova/all-MiniLM-L6-v2');
+    /// db.set_embedder(async (text) =>
+    ///     (await extractor(text, { pooling: 'mean', normalize: true })).data);
+    /// ```
+    pub fn set_embedder(&mut self, embedder: js_sys::Function) {
+        self.embedder = Some(embedder);
+    }
+
+    /// Embed text and insert it with optional metadata
+    /// Returns a Promise with the vector ID
+    ///
+    /// The text is stored in the metadata under `text` unless the metadata
+    /// already sets that key.
+    #[wasm_bindgen(js_name = insertText)]
+    pub fn insert_text(&self, text: String, metadata: JsValue) -> js_sys::Promise {
+        let embedder = match self.text_embedder() {
+            Ok(embedder) => embedder,
+            Err(e) => return js_sys::Promise::reject(&e.into()),
+        };
+        let metadata = match metadata_from_js(metadata) {
+            Ok(metadata) => metadata.unwrap_or_default(),
+            Err(e) => return js_sys::Promise::reject(&e.into()),
+        };
+        let db = Arc::clone(&self.db);
+        let insertion_order = Arc::clone(&self.insertion_order);
+
+        future_to_promise(async move {
+            let vector = embed_text(&embedder, &text).await?;
+            let mut metadata = metadata;
+            metadata
+                .entry("text".to_string())
+                .or_insert(serde_json::Value::String(text));
+
+            let id = db.insert(VectorEntry {
+                id: None,
+                vector,
+                metadata: Some(metadata),
+            })
+            .map_err(|e| RvLiteError::from(e))?;
+            insertion_order.lock().record(&id);
+            Ok(JsValue::from_str(&id))
+        })
+    }
+
+    /// Embed text and search for similar vectors
+    /// Returns a Promise with the same results as `search`
+    #[wasm_bindgen(js_name = searchText)]
+    pub fn search_text(&self, text: String, k: usize) -> js_sys::Promise {
+        let embedder = match self.text_embedder() {
+            Ok(embedder) => embedder,
+            Err(e) => return js_sys::Promise::reject(&e.into()),
+        };
+        let db = Arc::clone(&self.db);
+
+        future_to_promise(async move {
+            let query = SearchQuery {
+                vector: embed_text(&embedder, &text).await?,
+                k,
+                filter: None,
+                ef_search: None,
+            };
+            let results = db.search(query)
+                .map_err(|e| RvLiteError::from(e))?;
+
+            serde_wasm_bindgen::to_value(&results)
+                .map_err(|e| RvLiteError {
+                    message: format!("Failed to serialize results: {}", e),
+                    kind: ErrorKind::WasmError,
+                }.into())
+        })
+    }
+
     /// Search for similar vectors
     pub fn search(&self, query_vector: Vec<f32>, k: usize) -> Result<JsValue, JsValue> {
         let query = SearchQuery {
@@ -434,8 +697,10 @@ impl RvLite {
 
     /// Delete a vector by ID
     pub fn delete(&self, id: String) -> Result<bool, JsValue> {
-        self.db.delete(&id)
-            .map_err(|e| RvLiteError::from(e).into())
+        let deleted = self.db.delete(&id)
+            .map_err(|e| RvLiteError::from(e))?;
+        self.insertion_order.lock().remove(&id);
+        Ok(deleted)
     }
 
     /// Get the number of vectors in the database
@@ -595,8 +860,8 @@ impl RvLite {
         // Get current timestamp
         let saved_at = js_sys::Date::now() as u64;
 
-        // Export vector state
-        let vector_entries = self.db.keys()
+        // Export vector state, oldest first
+        let mut vector_entries: Vec<_> = self.db.keys()
             .unwrap_or_default()
             .iter()
             .filter_map(|id| {
@@ -605,10 +870,12 @@ impl RvLite {
                         id: entry.id.unwrap_or_default(),
                         vector: entry.vector,
                         metadata: entry.metadata,
+                        compressed: None,
                     }
                 })
             })
             .collect();
+        self.insertion_order.lock().sort(&mut vector_entries);
 
         let vectors = VectorState {
             entries: vector_entries,
@@ -636,17 +903,77 @@ impl RvLite {
         }
     }
 
+    /// Save `state` to IndexedDB, checking the storage quota first
+    fn persist(&self, mut state: RvLiteState) -> js_sys::Promise {
+        let policy = self.quota_policy.clone();
+        let listener = self.quota_listener.clone();
+
+        future_to_promise(async move {
+            // The estimate is advisory; browsers without it just save
+            if let Ok(Some((usage, quota))) = storage::quota::estimate().await {
+                let level = policy.level(usage, quota);
+                let compressed = if level == QuotaLevel::Critical && policy.auto_compress {
+                    let count = policy.compress_count(state.vectors.entries.len());
+                    state.compress_oldest(count)
+                } else {
+                    0
+                };
+
+                if level != QuotaLevel::Ok {
+                    if let Some(listener) = &listener {
+                        let status = QuotaStatus { usage, quota, level, compressed };
+                        listener.call1(&JsValue::NULL, &serde_wasm_bindgen::to_value(&status)?)?;
+                    }
+                }
+            }
+
+            let mut storage = storage::IndexedDBStorage::new();
+            storage.init().await?;
+            storage.save(&state).await.map_err(|e| {
+                if storage::quota::is_quota_exceeded(&e) {
+                    RvLiteError {
+                        message: "Browser storage quota exceeded; delete data or enable \
+                                  auto_compress with set_quota_policy()"
+                            .to_string(),
+                        kind: ErrorKind::StorageError,
+                    }
+                    .into()
+                } else {
+                    e
+                }
+            })?;
+            Ok(JsValue::TRUE)
+        })
+    }
+
+    /// The embedder set with `set_embedder`
+    fn text_embedder(&self) -> Result<js_sys::Function, RvLiteError> {
+        self.embedder.clone().ok_or_else(|| RvLiteError {
+            message: "No embedder set. Call set_embedder() first.".to_string(),
+            kind: ErrorKind::WasmError,
+        })
+    }
+
+    /// Discard all data and load `state`
+    fn replace_state(&mut self, state: &RvLiteState) -> Result<(), JsValue> {
+        self.db = Arc::new(VectorDB::new(self.config.to_db_options())
+            .map_err(|e| RvLiteError::from(e))?);
+        self.insertion_order.lock().clear();
+        self.import_state(state)
+    }
+
     /// Import state into the database
     fn import_state(&mut self, state: &RvLiteState) -> Result<(), JsValue> {
         // Import vectors
         for entry in &state.vectors.entries {
             let vector_entry = VectorEntry {
                 id: Some(entry.id.clone()),
-                vector: entry.vector.clone(),
+                vector: entry.full_vector(),
                 metadata: entry.metadata.clone(),
             };
             self.db.insert(vector_entry)
                 .map_err(|e| RvLiteError::from(e))?;
+            self.insertion_order.lock().record(&entry.id);
         }
 
         // Import graph
@@ -788,6 +1115,35 @@ fn convert_sparql_result(result: &sparql::executor::QueryResult) -> serde_json::
     }
 }
 
+// Helper function to parse optional metadata passed from JS
+fn metadata_from_js(
+    metadata: JsValue,
+) -> Result<Option<HashMap<String, serde_json::Value>>, RvLiteError> {
+    if metadata.is_null() || metadata.is_undefined() {
+        return Ok(None);
+    }
+    serde_wasm_bindgen::from_value(metadata)
+        .map(Some)
+        .map_err(|e| RvLiteError {
+            message: format!("Invalid metadata: {}", e),
+            kind: ErrorKind::WasmError,
+        })
+}
+
+// Helper function to call a JS embedder, awaiting its result if it is async
+async fn embed_text(embedder: &js_sys::Function, text: &str) -> Result<Vec<f32>, JsValue> {
+    let result = embedder.call1(&JsValue::NULL, &JsValue::from_str(text))?;
+    let value = JsFuture::from(js_sys::Promise::resolve(&result)).await?;
+
+    if let Some(array) = value.dyn_ref::<js_sys::Float32Array>() {
+        return Ok(array.to_vec());
+    }
+    serde_wasm_bindgen::from_value(value).map_err(|e| RvLiteError {
+        message: format!("Embedder must return an array of numbers: {}", e),
+        kind: ErrorKind::WasmError,
+    }.into())
+}
+
 // Helper functions for parsing RDF terms
 fn parse_rdf_term(s: &str) -> Result<sparql::RdfTerm, JsValue> {
     let s = s.trim();
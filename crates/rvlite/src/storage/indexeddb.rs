@@ -85,12 +85,11 @@ impl IndexedDBStorage {
         let store = transaction.object_store(STORE_NAME)?;
 
         // Put state with key
-        let request = store.put_with_key(&js_state, &JsValue::from_str(STATE_KEY))?;
+        store.put_with_key(&js_state, &JsValue::from_str(STATE_KEY))?;
 
-        // Wait for completion
-        wait_for_request(&request).await?;
-
-        Ok(())
+        // A successful request can still be rolled back if the transaction
+        // aborts, so the save is only durable once the transaction completes
+        wait_for_transaction(&transaction).await
     }
 
     /// Load state from IndexedDB
@@ -220,6 +219,42 @@ async fn wait_for_request(request: &IdbRequest) -> Result<JsValue, JsValue> {
     request.result()
 }
 
+/// Wait for an IdbTransaction to commit; rejects if it errors or aborts
+async fn wait_for_transaction(transaction: &IdbTransaction) -> Result<(), JsValue> {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        let oncomplete = Closure::once(Box::new(move |_event: web_sys::Event| {
+            resolve.call0(&JsValue::NULL).unwrap();
+        }) as Box<dyn FnOnce(_)>);
+
+        let reject_clone = reject.clone();
+        let onerror = Closure::once(Box::new(move |_event: web_sys::Event| {
+            reject_clone
+                .call1(&JsValue::NULL, &JsValue::from_str("IndexedDB transaction error"))
+                .unwrap();
+        }) as Box<dyn FnOnce(_)>);
+
+        let onabort = Closure::once(Box::new(move |_event: web_sys::Event| {
+            reject
+                .call1(&JsValue::NULL, &JsValue::from_str("IndexedDB transaction aborted"))
+                .unwrap();
+        }) as Box<dyn FnOnce(_)>);
+
+        transaction.set_oncomplete(Some(oncomplete.as_ref().unchecked_ref()));
+        transaction.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+        transaction.set_onabort(Some(onabort.as_ref().unchecked_ref()));
+
+        oncomplete.forget();
+        onerror.forget();
+        onabort.forget();
+    });
+
+    if let Err(e) = JsFuture::from(promise).await {
+        // Surface the DOMException (e.g. QuotaExceededError) when there is one
+        return Err(transaction.error().map(JsValue::from).unwrap_or(e));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,9 +4,14 @@
 //! - Vector database state
 //! - Cypher graph state
 //! - SPARQL triple store state
+//!
+//! and watches the browser storage quota before saving.
 
 pub mod indexeddb;
+pub mod portable;
+pub mod quota;
 pub mod state;
 
 pub use indexeddb::IndexedDBStorage;
+pub use quota::{QuotaLevel, QuotaPolicy, QuotaStatus};
 pub use state::{RvLiteState, VectorState, GraphState, TripleStoreState};
@@ -0,0 +1,152 @@
+//! Conversion between RvLite state and portable snapshot files
+//!
+//! Portable snapshots use the native snapshot format (see
+//! `ruvector_snapshot::portable`), so a browser database can be restored by
+//! the server and vice versa. Vectors map onto the snapshot's records; the
+//! Cypher graph and RDF triples travel as JSON extension sections, which the
+//! native side ignores.
+
+use super::state::{GraphState, RvLiteState, TripleStoreState, VectorEntry, VectorState};
+use ruvector_snapshot::{
+    CollectionConfig, DistanceMetric, PortableSnapshot, SnapshotData, SnapshotMetadata,
+    VectorRecord,
+};
+
+/// Extension section holding the Cypher graph
+pub const GRAPH_EXTENSION: &str = "rvlite.graph";
+/// Extension section holding the RDF triple store
+pub const TRIPLES_EXTENSION: &str = "rvlite.triples";
+
+/// Build a portable snapshot from `state`
+///
+/// `created_at` is an RFC 3339 timestamp.
+pub fn to_portable(state: &RvLiteState, created_at: String) -> Result<PortableSnapshot, String> {
+    let metric = match state.vectors.distance_metric.to_lowercase().as_str() {
+        "euclidean" => DistanceMetric::Euclidean,
+        "dotproduct" => DistanceMetric::DotProduct,
+        "manhattan" => DistanceMetric::Manhattan,
+        _ => DistanceMetric::Cosine,
+    };
+
+    let vectors = state
+        .vectors
+        .entries
+        .iter()
+        .map(|entry| {
+            let payload = entry
+                .metadata
+                .as_ref()
+                .map(|metadata| serde_json::Value::Object(metadata.clone().into_iter().collect()));
+            VectorRecord::new(entry.id.clone(), entry.full_vector(), payload)
+        })
+        .collect();
+
+    let data = SnapshotData {
+        metadata: SnapshotMetadata {
+            id: format!("rvlite-{}", state.saved_at),
+            collection_name: "rvlite".to_string(),
+            created_at,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        },
+        config: CollectionConfig {
+            dimension: state.vectors.dimensions,
+            metric,
+            hnsw_config: None,
+        },
+        vectors,
+    };
+
+    let graph = serde_json::to_string(&state.graph).map_err(|e| e.to_string())?;
+    let triples = serde_json::to_string(&state.triples).map_err(|e| e.to_string())?;
+    Ok(PortableSnapshot::new(data)
+        .with_extension(GRAPH_EXTENSION, graph)
+        .with_extension(TRIPLES_EXTENSION, triples))
+}
+
+/// Rebuild RvLite state from a portable snapshot
+///
+/// Snapshots written by the native side have no graph or triples.
+pub fn from_portable(snapshot: &PortableSnapshot) -> Result<RvLiteState, String> {
+    let config = &snapshot.data.config;
+    let distance_metric = match config.metric {
+        DistanceMetric::Cosine => "cosine",
+        DistanceMetric::Euclidean => "euclidean",
+        DistanceMetric::DotProduct => "dotproduct",
+        DistanceMetric::Manhattan => "manhattan",
+    };
+
+    let entries = snapshot
+        .data
+        .vectors
+        .iter()
+        .map(|record| VectorEntry {
+            id: record.id.clone(),
+            vector: record.vector.clone(),
+            metadata: match record.payload() {
+                Some(serde_json::Value::Object(map)) => Some(map.into_iter().collect()),
+                _ => None,
+            },
+            compressed: None,
+        })
+        .collect();
+
+    let graph: GraphState = match snapshot.extension(GRAPH_EXTENSION) {
+        Some(json) => serde_json::from_str(json).map_err(|e| e.to_string())?,
+        None => GraphState::default(),
+    };
+    let triples: TripleStoreState = match snapshot.extension(TRIPLES_EXTENSION) {
+        Some(json) => serde_json::from_str(json).map_err(|e| e.to_string())?,
+        None => TripleStoreState::default(),
+    };
+
+    Ok(RvLiteState {
+        vectors: VectorState {
+            entries,
+            dimensions: config.dimension,
+            distance_metric: distance_metric.to_string(),
+            next_id: 0,
+        },
+        graph,
+        triples,
+        ..RvLiteState::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::state::NodeState;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_portable_roundtrip() {
+        let mut state = RvLiteState::default();
+        state.vectors.dimensions = 2;
+        state.vectors.distance_metric = "euclidean".to_string();
+        state.vectors.entries.push(VectorEntry {
+            id: "v1".to_string(),
+            vector: vec![1.0, 2.0],
+            metadata: Some(HashMap::from([("k".to_string(), serde_json::json!(1))])),
+            compressed: None,
+        });
+        state.graph.nodes.push(NodeState {
+            id: "n1".to_string(),
+            labels: vec!["Doc".to_string()],
+            properties: HashMap::new(),
+        });
+
+        let bytes = to_portable(&state, "2024-01-01T00:00:00Z".to_string())
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        let restored = from_portable(&PortableSnapshot::from_bytes(&bytes).unwrap()).unwrap();
+
+        assert_eq!(restored.vectors.distance_metric, "euclidean");
+        assert_eq!(restored.vectors.entries[0].vector, vec![1.0, 2.0]);
+        assert_eq!(
+            restored.vectors.entries[0].metadata.as_ref().unwrap()["k"],
+            1
+        );
+        assert_eq!(restored.graph.nodes[0].id, "n1");
+    }
+}
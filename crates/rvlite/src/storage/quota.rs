@@ -0,0 +1,130 @@
+//! Browser storage quota monitoring
+//!
+//! Browsers cap how much an origin may store and fail writes past the cap
+//! with an opaque `QuotaExceededError`. Before each save RvLite asks
+//! `navigator.storage.estimate()` how close it is to the cap, notifies the
+//! registered listener when past [`QuotaPolicy::warn_ratio`], and, if
+//! enabled, compresses the oldest vectors once past
+//! [`QuotaPolicy::critical_ratio`].
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use js_sys::Reflect;
+use web_sys::{DomException, StorageManager};
+
+/// When to warn and when to compress
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuotaPolicy {
+    /// Fraction of the quota in use at which listeners are notified
+    pub warn_ratio: f64,
+    /// Fraction of the quota in use at which compression kicks in
+    pub critical_ratio: f64,
+    /// Compress the oldest vectors in saved state once critical
+    pub auto_compress: bool,
+    /// Fraction of vectors, oldest first, compressed per critical save
+    pub compress_fraction: f64,
+}
+
+impl Default for QuotaPolicy {
+    fn default() -> Self {
+        Self {
+            warn_ratio: 0.8,
+            critical_ratio: 0.95,
+            auto_compress: false,
+            compress_fraction: 0.25,
+        }
+    }
+}
+
+impl QuotaPolicy {
+    /// Classify `usage` bytes out of `quota`
+    pub fn level(&self, usage: f64, quota: f64) -> QuotaLevel {
+        if quota <= 0.0 {
+            return QuotaLevel::Ok;
+        }
+        let ratio = usage / quota;
+        if ratio >= self.critical_ratio {
+            QuotaLevel::Critical
+        } else if ratio >= self.warn_ratio {
+            QuotaLevel::Warning
+        } else {
+            QuotaLevel::Ok
+        }
+    }
+
+    /// How many of `total` vectors to compress on a critical save
+    pub fn compress_count(&self, total: usize) -> usize {
+        ((total as f64 * self.compress_fraction.clamp(0.0, 1.0)).ceil() as usize).min(total)
+    }
+}
+
+/// How close storage is to the quota
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotaLevel {
+    Ok,
+    Warning,
+    Critical,
+}
+
+/// Storage usage as reported to quota listeners
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaStatus {
+    /// Bytes used by the origin
+    pub usage: f64,
+    /// Bytes the origin may use
+    pub quota: f64,
+    /// Classification under the active policy
+    pub level: QuotaLevel,
+    /// Vectors compressed by this save
+    pub compressed: usize,
+}
+
+/// Query `navigator.storage.estimate()`
+///
+/// Returns `(usage, quota)` in bytes, or `None` where the API is missing.
+pub async fn estimate() -> Result<Option<(f64, f64)>, JsValue> {
+    let window = match web_sys::window() {
+        Some(window) => window,
+        None => return Ok(None),
+    };
+
+    // Not every browser (or insecure context) exposes navigator.storage
+    let manager = Reflect::get(&window.navigator(), &"storage".into())?;
+    if manager.is_undefined() || manager.is_null() {
+        return Ok(None);
+    }
+    let manager: StorageManager = manager.unchecked_into();
+
+    let estimate = JsFuture::from(manager.estimate()?).await?;
+    let usage = Reflect::get(&estimate, &"usage".into())?.as_f64();
+    let quota = Reflect::get(&estimate, &"quota".into())?.as_f64();
+    Ok(usage.zip(quota))
+}
+
+/// Whether `error` is the browser's `QuotaExceededError`
+pub fn is_quota_exceeded(error: &JsValue) -> bool {
+    error
+        .dyn_ref::<DomException>()
+        .map_or(false, |e| e.name() == "QuotaExceededError")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quota_levels() {
+        let policy = QuotaPolicy::default();
+        assert_eq!(policy.level(10.0, 100.0), QuotaLevel::Ok);
+        assert_eq!(policy.level(85.0, 100.0), QuotaLevel::Warning);
+        assert_eq!(policy.level(99.0, 100.0), QuotaLevel::Critical);
+        assert_eq!(policy.level(10.0, 0.0), QuotaLevel::Ok);
+
+        assert_eq!(policy.compress_count(10), 3);
+        assert_eq!(policy.compress_count(0), 0);
+    }
+}
@@ -4,7 +4,10 @@
 //! in a format that can be serialized to/from IndexedDB.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+/// Node property that links a graph node to a vector by ID
+pub const VECTOR_REF_PROPERTY: &str = "vector_id";
 
 /// Complete serializable state for RvLite
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +39,42 @@ impl Default for RvLiteState {
     }
 }
 
+impl RvLiteState {
+    /// IDs of graph nodes whose `vector_id` property names a vector that is
+    /// not part of this state
+    pub fn missing_vector_refs(&self) -> Vec<String> {
+        let ids: HashSet<&str> = self.vectors.entries.iter().map(|e| e.id.as_str()).collect();
+
+        self.graph
+            .nodes
+            .iter()
+            .filter(|node| match node.properties.get(VECTOR_REF_PROPERTY) {
+                Some(PropertyValue::String(id)) => !ids.contains(id.as_str()),
+                _ => false,
+            })
+            .map(|node| node.id.clone())
+            .collect()
+    }
+
+    /// Compress up to `count` uncompressed vectors, oldest first
+    ///
+    /// Entries are expected in insertion order. Returns how many were
+    /// compressed.
+    pub fn compress_oldest(&mut self, count: usize) -> usize {
+        let mut compressed = 0;
+        for entry in self.vectors.entries.iter_mut() {
+            if compressed == count {
+                break;
+            }
+            if entry.compressed.is_none() {
+                entry.compress();
+                compressed += 1;
+            }
+        }
+        compressed
+    }
+}
+
 /// Serializable vector database state
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct VectorState {
@@ -53,8 +92,96 @@ pub struct VectorState {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VectorEntry {
     pub id: String,
+    /// Full-precision vector; empty once compressed
     pub vector: Vec<f32>,
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Lossy compressed form, replacing `vector` under quota pressure
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compressed: Option<CompressedVector>,
+}
+
+impl VectorEntry {
+    /// Replace the vector with its compressed form
+    pub fn compress(&mut self) {
+        if self.compressed.is_none() {
+            self.compressed = Some(CompressedVector::compress(&self.vector));
+            self.vector = Vec::new();
+        }
+    }
+
+    /// The vector, decompressed if necessary
+    pub fn full_vector(&self) -> Vec<f32> {
+        match &self.compressed {
+            Some(compressed) => compressed.decompress(),
+            None => self.vector.clone(),
+        }
+    }
+}
+
+/// Scalar-quantized vector, one byte per dimension
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompressedVector {
+    pub min: f32,
+    pub scale: f32,
+    pub codes: Vec<u8>,
+}
+
+impl CompressedVector {
+    /// Quantize `vector` onto 256 levels between its min and max
+    pub fn compress(vector: &[f32]) -> Self {
+        if vector.is_empty() {
+            return Self { min: 0.0, scale: 0.0, codes: Vec::new() };
+        }
+        let min = vector.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = vector.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let scale = if max > min { (max - min) / 255.0 } else { 0.0 };
+
+        let codes = vector
+            .iter()
+            .map(|&v| if scale == 0.0 { 0 } else { ((v - min) / scale).round() as u8 })
+            .collect();
+
+        Self { min, scale, codes }
+    }
+
+    /// Approximate original vector
+    pub fn decompress(&self) -> Vec<f32> {
+        self.codes
+            .iter()
+            .map(|&c| self.min + c as f32 * self.scale)
+            .collect()
+    }
+}
+
+/// Insertion sequence of vector IDs, so saved entries are ordered oldest
+/// first
+#[derive(Debug, Default)]
+pub(crate) struct InsertionOrder {
+    next: u64,
+    seq: HashMap<String, u64>,
+}
+
+impl InsertionOrder {
+    /// Record `id` as the newest vector
+    pub(crate) fn record(&mut self, id: &str) {
+        self.seq.insert(id.to_string(), self.next);
+        self.next += 1;
+    }
+
+    /// Forget a deleted vector
+    pub(crate) fn remove(&mut self, id: &str) {
+        self.seq.remove(id);
+    }
+
+    /// Forget everything
+    pub(crate) fn clear(&mut self) {
+        self.seq.clear();
+    }
+
+    /// Sort `entries` oldest first; unrecorded IDs go last
+    pub(crate) fn sort(&self, entries: &mut [VectorEntry]) {
+        entries.sort_by_key(|e| self.seq.get(&e.id).copied().unwrap_or(u64::MAX));
+    }
 }
 
 /// Serializable Cypher graph state
@@ -152,3 +279,60 @@ pub struct SqlColumnState {
     pub data_type: String,
     pub dimensions: Option<usize>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_vector_refs() {
+        let node = |id: &str, vector_id: &str| NodeState {
+            id: id.to_string(),
+            labels: vec!["Doc".to_string()],
+            properties: HashMap::from([(
+                VECTOR_REF_PROPERTY.to_string(),
+                PropertyValue::String(vector_id.to_string()),
+            )]),
+        };
+
+        let mut state = RvLiteState::default();
+        state.vectors.entries.push(VectorEntry {
+            id: "v1".to_string(),
+            vector: vec![1.0, 0.0],
+            metadata: None,
+            compressed: None,
+        });
+        state.graph.nodes = vec![node("n1", "v1"), node("n2", "v2")];
+
+        assert_eq!(state.missing_vector_refs(), vec!["n2".to_string()]);
+    }
+
+    #[test]
+    fn test_compress_oldest() {
+        let mut state = RvLiteState::default();
+        let mut order = InsertionOrder::default();
+        for id in ["b", "a", "c"] {
+            order.record(id);
+        }
+        let vectors = [("a", vec![0.0, 1.0]), ("b", vec![-1.0, 1.0]), ("c", vec![2.0, 2.0])];
+        for (id, vector) in vectors {
+            state.vectors.entries.push(VectorEntry {
+                id: id.to_string(),
+                vector,
+                metadata: None,
+                compressed: None,
+            });
+        }
+        order.sort(&mut state.vectors.entries);
+
+        assert_eq!(state.compress_oldest(2), 2);
+        let entries = &state.vectors.entries;
+        assert_eq!(entries[0].id, "b");
+        assert!(entries[0].vector.is_empty());
+        let restored = entries[0].full_vector();
+        assert!((restored[0] + 1.0).abs() < 0.01 && (restored[1] - 1.0).abs() < 0.01);
+        assert!(entries[1].compressed.is_some());
+        assert!(entries[2].compressed.is_none());
+        assert_eq!(entries[2].full_vector(), vec![2.0, 2.0]);
+    }
+}
@@ -0,0 +1,182 @@
+//! Adapter Registry - Named per-task LoRA adapters with routing
+//!
+//! Holds several MicroLoRA adapters, each registered with a task embedding,
+//! so one engine can serve multiple tenants or skills. Task embeddings are
+//! stored as patterns in a dedicated ReasoningBank and an input is routed
+//! to the adapter whose task embedding is most similar to the input's.
+
+use crate::lora::MicroLoRA;
+use crate::reasoning_bank::{PatternConfig, ReasoningBank};
+use crate::types::LearnedPattern;
+use std::collections::HashMap;
+
+/// Default minimum cosine similarity for routing to an adapter
+pub const DEFAULT_MIN_ROUTE_SIMILARITY: f32 = 0.5;
+
+/// A registered adapter
+#[derive(Clone, Debug)]
+pub struct TaskAdapter {
+    /// Adapter weights
+    pub lora: MicroLoRA,
+    /// Embedding describing the task the adapter was trained for
+    pub task_embedding: Vec<f32>,
+    /// Pattern holding the task embedding in the registry's bank
+    pattern_id: u64,
+}
+
+/// Registry of named adapters routed by task similarity
+#[derive(Clone, Debug)]
+pub struct AdapterRegistry {
+    /// Adapters by name
+    adapters: HashMap<String, TaskAdapter>,
+    /// Adapter name by task pattern ID
+    routes: HashMap<u64, String>,
+    /// Task embeddings
+    bank: ReasoningBank,
+    /// Similarity below which no adapter is chosen
+    min_similarity: f32,
+}
+
+impl Default for AdapterRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_MIN_ROUTE_SIMILARITY)
+    }
+}
+
+impl AdapterRegistry {
+    /// Create empty registry
+    pub fn new(min_similarity: f32) -> Self {
+        Self {
+            adapters: HashMap::new(),
+            routes: HashMap::new(),
+            bank: ReasoningBank::new(PatternConfig::default()),
+            min_similarity,
+        }
+    }
+
+    /// Register `lora` under `name`, replacing any adapter with that name
+    pub fn register(&mut self, name: &str, lora: MicroLoRA, task_embedding: Vec<f32>) {
+        self.remove(name);
+        let pattern_id = self
+            .bank
+            .insert_pattern(LearnedPattern::new(0, task_embedding.clone()));
+        self.routes.insert(pattern_id, name.to_string());
+        self.adapters.insert(
+            name.to_string(),
+            TaskAdapter {
+                lora,
+                task_embedding,
+                pattern_id,
+            },
+        );
+    }
+
+    /// Remove an adapter; returns false if there was none with that name
+    pub fn remove(&mut self, name: &str) -> bool {
+        let Some(adapter) = self.adapters.remove(name) else {
+            return false;
+        };
+        self.routes.remove(&adapter.pattern_id);
+        self.bank.remove_pattern(adapter.pattern_id);
+        true
+    }
+
+    /// Get adapter by name
+    pub fn get(&self, name: &str) -> Option<&TaskAdapter> {
+        self.adapters.get(name)
+    }
+
+    /// Get registered names, sorted
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.adapters.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Get number of adapters
+    pub fn len(&self) -> usize {
+        self.adapters.len()
+    }
+
+    /// Check if no adapter is registered
+    pub fn is_empty(&self) -> bool {
+        self.adapters.is_empty()
+    }
+
+    /// Get minimum routing similarity
+    pub fn min_similarity(&self) -> f32 {
+        self.min_similarity
+    }
+
+    /// Set minimum routing similarity
+    pub fn set_min_similarity(&mut self, min_similarity: f32) {
+        self.min_similarity = min_similarity;
+    }
+
+    /// Name and similarity of the adapter for `task_embedding`, or `None`
+    /// if no task is at least `min_similarity` similar
+    pub fn route(&self, task_embedding: &[f32]) -> Option<(&str, f32)> {
+        let pattern = self.bank.find_similar(task_embedding, 1).into_iter().next()?;
+        let similarity = pattern.similarity(task_embedding);
+        if similarity < self.min_similarity {
+            return None;
+        }
+        let name = self.routes.get(&pattern.id)?;
+        Some((name.as_str(), similarity))
+    }
+
+    /// Apply the adapter routed for `task_embedding` to `input`
+    ///
+    /// Returns the adapter's name, or `None` (leaving `output` untouched)
+    /// when no adapter matches.
+    pub fn forward(&self, task_embedding: &[f32], input: &[f32], output: &mut [f32]) -> Option<&str> {
+        let (name, _) = self.route(task_embedding)?;
+        self.adapters[name].lora.forward(input, output);
+        Some(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adapter(gradient: f32) -> MicroLoRA {
+        let mut lora = MicroLoRA::new(4, 1);
+        let signal = crate::types::LearningSignal::with_gradient(vec![0.0; 4], vec![gradient; 4], 1.0);
+        lora.accumulate_gradient(&signal);
+        lora.apply_accumulated(1.0);
+        lora
+    }
+
+    #[test]
+    fn test_routing() {
+        let mut registry = AdapterRegistry::default();
+        registry.register("code", adapter(1.0), vec![1.0, 0.0, 0.0]);
+        registry.register("legal", adapter(-1.0), vec![0.0, 1.0, 0.0]);
+        assert_eq!(registry.names(), ["code", "legal"]);
+
+        let (name, similarity) = registry.route(&[0.9, 0.1, 0.0]).unwrap();
+        assert_eq!(name, "code");
+        assert!(similarity > 0.9);
+        assert_eq!(registry.route(&[0.1, 0.9, 0.0]).unwrap().0, "legal");
+        // Unrelated tasks use no adapter
+        assert!(registry.route(&[0.0, 0.0, 1.0]).is_none());
+
+        // Routed adapters produce their own outputs
+        let input = [1.0, -1.0, 0.5, 0.25];
+        let (mut routed, mut direct) = (vec![0.0; 4], vec![0.0; 4]);
+        assert_eq!(registry.forward(&[0.2, 1.0, 0.0], &input, &mut routed), Some("legal"));
+        registry.get("legal").unwrap().lora.forward(&input, &mut direct);
+        assert_eq!(routed, direct);
+
+        // Re-registering replaces the task embedding
+        registry.register("code", adapter(1.0), vec![0.0, 0.0, 1.0]);
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.route(&[0.0, 0.0, 1.0]).unwrap().0, "code");
+        assert!(registry.route(&[1.0, 0.0, 0.0]).is_none());
+
+        assert!(registry.remove("legal"));
+        assert!(!registry.remove("legal"));
+        assert!(registry.route(&[0.0, 1.0, 0.0]).is_none());
+    }
+}
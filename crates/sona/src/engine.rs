@@ -1,8 +1,10 @@
 //! SONA Engine - Main interface for self-optimizing neural architecture
 
+use crate::adapter_registry::AdapterRegistry;
+use crate::feedback::FeedbackTracker;
 use crate::loops::coordinator::{CoordinatorStats, LoopCoordinator};
-use crate::lora::MicroLoRA;
-use crate::trajectory::TrajectoryBuilder;
+use crate::lora::{MicroLoRA, QuantizedBaseLoRA, QuantizedMicroLoRA};
+use crate::trajectory::{TrajectoryBuilder, TrajectoryMatch, TrajectorySimilarity};
 use crate::types::{QueryTrajectory, SonaConfig};
 use parking_lot::RwLock;
 use std::sync::Arc;
@@ -11,10 +13,14 @@ use std::sync::Arc;
 pub struct SonaEngine {
     /// Loop coordinator
     coordinator: LoopCoordinator,
+    /// Searches awaiting feedback
+    feedback: FeedbackTracker,
     /// Configuration
     config: SonaConfig,
     /// Whether engine is enabled
     enabled: bool,
+    /// Named per-task adapters
+    adapters: RwLock<AdapterRegistry>,
 }
 
 impl SonaEngine {
@@ -31,8 +37,10 @@ impl SonaEngine {
     pub fn with_config(config: SonaConfig) -> Self {
         Self {
             coordinator: LoopCoordinator::with_config(config.clone()),
+            feedback: FeedbackTracker::default(),
             config,
             enabled: true,
+            adapters: RwLock::new(AdapterRegistry::default()),
         }
     }
 
@@ -59,6 +67,34 @@ impl SonaEngine {
         }
     }
 
+    /// Track a search so its results can receive feedback
+    ///
+    /// `results` holds the returned ids with their embeddings, in rank order.
+    /// `query_id` is caller-chosen, e.g. the id from a VectorDB audit log.
+    pub fn track_search(&self, query_id: u64, query_embedding: Vec<f32>, results: Vec<(String, Vec<f32>)>) {
+        self.feedback.track(query_id, query_embedding, results);
+    }
+
+    /// Report whether a returned result was useful
+    ///
+    /// The reward in `[0, 1]` becomes a learning signal for the instant loop
+    /// and a trajectory for the background loop. Returns false if the search
+    /// is not tracked, the result was not part of it, or the engine is disabled.
+    pub fn record_feedback(&self, query_id: u64, result_id: &str, reward: f32) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
+        let trajectory_id = self.coordinator.next_trajectory_id();
+        match self.feedback.ingest(trajectory_id, query_id, result_id, reward) {
+            Some((trajectory, signal)) => {
+                self.coordinator.on_feedback(trajectory, signal);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Apply micro-LoRA to hidden states
     pub fn apply_micro_lora(&self, input: &[f32], output: &mut [f32]) {
         if !self.enabled {
@@ -81,6 +117,84 @@ impl SonaEngine {
         }
     }
 
+    /// Register a named adapter for the task described by `task_embedding`
+    ///
+    /// Replaces any adapter with the same name.
+    pub fn register_adapter(&self, name: &str, adapter: MicroLoRA, task_embedding: Vec<f32>) {
+        self.adapters.write().register(name, adapter, task_embedding);
+    }
+
+    /// Register a copy of the current micro-LoRA as a named adapter
+    pub fn snapshot_adapter(&self, name: &str, task_embedding: Vec<f32>) {
+        let adapter = self.coordinator.micro_lora().read().clone();
+        self.register_adapter(name, adapter, task_embedding);
+    }
+
+    /// Remove a named adapter
+    pub fn remove_adapter(&self, name: &str) -> bool {
+        self.adapters.write().remove(name)
+    }
+
+    /// Get registered adapter names
+    pub fn adapter_names(&self) -> Vec<String> {
+        self.adapters.read().names()
+    }
+
+    /// Set minimum task similarity for routing to an adapter
+    pub fn set_adapter_min_similarity(&self, min_similarity: f32) {
+        self.adapters.write().set_min_similarity(min_similarity);
+    }
+
+    /// Name and similarity of the adapter `task_embedding` routes to
+    pub fn route_adapter(&self, task_embedding: &[f32]) -> Option<(String, f32)> {
+        self.adapters
+            .read()
+            .route(task_embedding)
+            .map(|(name, similarity)| (name.to_string(), similarity))
+    }
+
+    /// Apply the adapter routed for `task_embedding`
+    ///
+    /// Falls back to the engine's own micro-LoRA when no adapter matches.
+    /// Returns the name of the adapter used, if any.
+    pub fn apply_routed_lora(&self, task_embedding: &[f32], input: &[f32], output: &mut [f32]) -> Option<String> {
+        if !self.enabled {
+            return None;
+        }
+
+        let routed = self
+            .adapters
+            .read()
+            .forward(task_embedding, input, output)
+            .map(str::to_string);
+        if routed.is_none() {
+            self.apply_micro_lora(input, output);
+        }
+        routed
+    }
+
+    /// Quantize the engine's LoRA weights to int8 for compact export
+    pub fn quantize_lora(&self) -> (QuantizedMicroLoRA, QuantizedBaseLoRA) {
+        (
+            self.coordinator.micro_lora().read().quantize(),
+            self.coordinator.base_lora().read().quantize(),
+        )
+    }
+
+    /// Replace the engine's LoRA weights with dequantized int8 weights
+    pub fn load_quantized_lora(&self, micro: &QuantizedMicroLoRA, base: &QuantizedBaseLoRA) -> Result<(), String> {
+        let hidden_dim = self.config.hidden_dim;
+        if micro.hidden_dim != hidden_dim || base.hidden_dim != hidden_dim {
+            return Err(format!(
+                "Quantized LoRA has hidden dims {}/{}, engine has {}",
+                micro.hidden_dim, base.hidden_dim, hidden_dim
+            ));
+        }
+        *self.coordinator.micro_lora().write() = micro.dequantize();
+        *self.coordinator.base_lora().write() = base.dequantize();
+        Ok(())
+    }
+
     /// Run background learning cycle if due
     pub fn tick(&self) -> Option<String> {
         if !self.enabled {
@@ -126,6 +240,27 @@ impl SonaEngine {
             .collect()
     }
 
+    /// Find past reasoning sessions similar to `current`
+    ///
+    /// Compares against the most recent trajectories; use
+    /// [`TrajectoryBuilder::snapshot`] for a session still in progress.
+    pub fn find_similar_trajectories(
+        &self,
+        current: &QueryTrajectory,
+        k: usize,
+        method: TrajectorySimilarity,
+    ) -> Vec<TrajectoryMatch> {
+        self.coordinator.find_similar_trajectories(current, k, method)
+    }
+
+    /// Add patterns learned elsewhere to the ReasoningBank
+    ///
+    /// Patterns get fresh IDs. Returns the number added.
+    pub fn import_patterns<I: IntoIterator<Item = crate::LearnedPattern>>(&self, patterns: I) -> usize {
+        let mut bank = self.coordinator.reasoning_bank().write();
+        patterns.into_iter().map(|pattern| bank.insert_pattern(pattern)).count()
+    }
+
     /// Get engine statistics
     pub fn stats(&self) -> CoordinatorStats {
         self.coordinator.stats()
@@ -382,6 +517,19 @@ mod tests {
         assert!(result.contains("150 trajectories"));
     }
 
+    #[test]
+    fn test_search_feedback() {
+        let engine = SonaEngine::new(64);
+        engine.track_search(42, vec![0.1; 64], vec![("doc-1".to_string(), vec![0.5; 64])]);
+
+        assert!(engine.record_feedback(42, "doc-1", 1.0));
+        assert!(!engine.record_feedback(42, "doc-2", 1.0));
+        assert!(!engine.record_feedback(43, "doc-1", 1.0));
+
+        let stats = engine.stats();
+        assert_eq!(stats.trajectories_buffered, 1);
+    }
+
     #[test]
     fn test_disabled_engine() {
         let mut engine = SonaEngine::new(64);
@@ -394,4 +542,30 @@ mod tests {
         let stats = engine.stats();
         assert_eq!(stats.trajectories_buffered, 0);
     }
+
+    #[test]
+    fn test_routed_adapters() {
+        let engine = SonaEngine::new(64);
+        let mut adapter = MicroLoRA::new(64, 1);
+        adapter.accumulate_gradient(&crate::types::LearningSignal::with_gradient(vec![0.0; 64], vec![1.0; 64], 1.0));
+        adapter.apply_accumulated(1.0);
+
+        let mut task = vec![0.0; 8];
+        task[0] = 1.0;
+        engine.register_adapter("summarize", adapter, task.clone());
+        assert_eq!(engine.adapter_names(), ["summarize"]);
+
+        let input = vec![1.0; 64];
+        let mut output = vec![0.0; 64];
+        assert_eq!(engine.apply_routed_lora(&task, &input, &mut output).as_deref(), Some("summarize"));
+        assert!(output.iter().any(|&x| x != 0.0));
+
+        // Unmatched tasks fall back to the untrained default adapter
+        let mut output = vec![0.0; 64];
+        assert!(engine.apply_routed_lora(&[0.0, 1.0], &input, &mut output).is_none());
+        assert!(output.iter().all(|&x| x == 0.0));
+
+        assert!(engine.remove_adapter("summarize"));
+        assert!(engine.route_adapter(&task).is_none());
+    }
 }
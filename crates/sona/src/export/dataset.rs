@@ -48,19 +48,7 @@ impl<'a> DatasetExporter<'a> {
                 continue;
             }
 
-            let record = PatternRecord {
-                id: pattern.id.to_string(),
-                embedding: pattern.centroid.clone(),
-                cluster_size: pattern.cluster_size,
-                avg_quality: pattern.avg_quality,
-                pattern_type: pattern.pattern_type.to_string(),
-                access_count: pattern.access_count as u64,
-                metadata: PatternMetadata {
-                    source: "sona".to_string(),
-                    version: env!("CARGO_PKG_VERSION").to_string(),
-                    target_model: self.config.target_architecture.clone(),
-                },
-            };
+            let record = PatternRecord::from_pattern(&pattern, &self.config.target_architecture);
 
             let json = serde_json::to_string(&record).map_err(ExportError::Serialization)?;
             writeln!(writer, "{}", json).map_err(ExportError::Io)?;
@@ -235,6 +223,47 @@ pub struct PatternRecord {
     pub metadata: PatternMetadata,
 }
 
+impl PatternRecord {
+    /// Record for `pattern`, exported for `target_model`
+    pub fn from_pattern(pattern: &LearnedPattern, target_model: &str) -> Self {
+        Self {
+            id: pattern.id.to_string(),
+            embedding: pattern.centroid.clone(),
+            cluster_size: pattern.cluster_size,
+            avg_quality: pattern.avg_quality,
+            pattern_type: pattern.pattern_type.to_string(),
+            access_count: pattern.access_count as u64,
+            metadata: PatternMetadata {
+                source: "sona".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                target_model: target_model.to_string(),
+            },
+        }
+    }
+
+    /// Pattern described by this record
+    ///
+    /// Timestamps are set to now and unknown pattern types become
+    /// [`PatternType::General`](crate::types::PatternType::General). The ID
+    /// is kept when numeric.
+    pub fn into_pattern(self) -> LearnedPattern {
+        let now = crate::time_compat::SystemTime::now()
+            .duration_since_epoch()
+            .as_secs();
+        LearnedPattern {
+            id: self.id.parse().unwrap_or(0),
+            total_weight: self.avg_quality * self.cluster_size as f32,
+            centroid: self.embedding,
+            cluster_size: self.cluster_size,
+            avg_quality: self.avg_quality,
+            created_at: now,
+            last_accessed: now,
+            access_count: self.access_count.min(u32::MAX as u64) as u32,
+            pattern_type: self.pattern_type.parse().unwrap_or_default(),
+        }
+    }
+}
+
 /// Pattern export metadata
 #[cfg_attr(feature = "serde-support", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
@@ -9,6 +9,8 @@
 //! - **JSONL Dataset**: ReasoningBank patterns as HuggingFace datasets
 //! - **Preference Pairs**: Quality trajectories for DPO/RLHF training
 //! - **Distillation Targets**: Routing decisions for knowledge distillation
+//! - **Sharded Datasets**: Patterns streamed to JSONL shards with a schema,
+//!   importable back into an engine
 //!
 //! # Example
 //!
@@ -31,11 +33,13 @@ pub mod safetensors;
 pub mod dataset;
 pub mod huggingface_hub;
 pub mod pretrain;
+pub mod streaming;
 
 pub use safetensors::SafeTensorsExporter;
 pub use dataset::DatasetExporter;
 pub use huggingface_hub::HuggingFaceHub;
 pub use pretrain::{PretrainConfig, PretrainPipeline};
+pub use streaming::{DatasetInfo, ShardInfo, StreamingExporter};
 
 use crate::engine::SonaEngine;
 use crate::types::{LearnedPattern, SonaConfig};
@@ -119,6 +123,11 @@ impl<'a> HuggingFaceExporter<'a> {
         exporter.export_preferences(self.engine, output_path)
     }
 
+    /// Export patterns as a sharded dataset, with the adapter alongside
+    pub fn export_streaming<P: AsRef<Path>>(&self, output_dir: P) -> Result<Vec<ExportResult>, ExportError> {
+        StreamingExporter::new(&self.config).export_engine(self.engine, output_dir)
+    }
+
     /// Export all to HuggingFace Hub
     pub fn push_to_hub(&self, repo_id: &str, token: Option<&str>) -> Result<ExportResult, ExportError> {
         let hub = HuggingFaceHub::new(token);
@@ -8,6 +8,8 @@ use crate::lora::{MicroLoRA, BaseLoRA};
 use super::{ExportConfig, ExportResult, ExportType, ExportError};
 use std::path::Path;
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
 
 #[cfg(feature = "serde-support")]
 use serde::{Deserialize, Serialize};
@@ -125,10 +127,8 @@ impl<'a> SafeTensorsExporter<'a> {
 
         // Serialize to SafeTensors format
         let safetensors_path = output_dir.join("adapter_model.safetensors");
-        let bytes = self.serialize_safetensors(&tensors)?;
-        std::fs::write(&safetensors_path, &bytes).map_err(ExportError::Io)?;
-
-        let size_bytes = bytes.len() as u64;
+        let file = File::create(&safetensors_path).map_err(ExportError::Io)?;
+        let size_bytes = write_safetensors(BufWriter::new(file), &tensors)?;
 
         Ok(ExportResult {
             export_type: ExportType::SafeTensors,
@@ -137,65 +137,97 @@ impl<'a> SafeTensorsExporter<'a> {
             size_bytes,
         })
     }
+}
 
-    /// Serialize tensors to SafeTensors binary format
-    fn serialize_safetensors(&self, tensors: &HashMap<String, TensorData>) -> Result<Vec<u8>, ExportError> {
-        // SafeTensors format:
-        // 8 bytes: header size (little endian u64)
-        // N bytes: JSON header with tensor metadata
-        // ... tensor data (aligned to 8 bytes)
-
-        let mut header_data: HashMap<String, TensorMetadata> = HashMap::new();
-        let mut data_offset: usize = 0;
-        let mut tensor_bytes: Vec<u8> = Vec::new();
-
-        // Sort keys for deterministic output
-        let mut keys: Vec<_> = tensors.keys().collect();
-        keys.sort();
-
-        for key in keys {
-            let tensor = &tensors[key];
-            let tensor_size = tensor.data.len() * 4; // f32 = 4 bytes
-
-            // Align to 8 bytes
-            let padding = (8 - (tensor_bytes.len() % 8)) % 8;
-            tensor_bytes.extend(vec![0u8; padding]);
-
-            let start_offset = tensor_bytes.len();
-
-            // Write tensor data
-            for &val in &tensor.data {
-                tensor_bytes.extend_from_slice(&val.to_le_bytes());
-            }
-
-            let end_offset = tensor_bytes.len();
+/// Write tensors in SafeTensors format, returning the bytes written
+///
+/// The header is computed from tensor shapes up front, so tensor data is
+/// streamed to `writer` without building the file in memory. Tensors are
+/// stored contiguously in key order and the header is padded with spaces
+/// to 8 bytes, as the format requires.
+pub fn write_safetensors<W: Write>(
+    mut writer: W,
+    tensors: &HashMap<String, TensorData>,
+) -> Result<u64, ExportError> {
+    // Sort keys for deterministic output
+    let mut keys: Vec<_> = tensors.keys().collect();
+    keys.sort();
+
+    let mut header_data: HashMap<String, TensorMetadata> = HashMap::new();
+    let mut data_offset: usize = 0;
+    for key in &keys {
+        let tensor = &tensors[*key];
+        let tensor_size = tensor.data.len() * 4; // f32 = 4 bytes
+        header_data.insert((*key).clone(), TensorMetadata {
+            dtype: tensor.dtype.clone(),
+            shape: tensor.shape.clone(),
+            data_offsets: [data_offset, data_offset + tensor_size],
+        });
+        data_offset += tensor_size;
+    }
 
-            header_data.insert(key.clone(), TensorMetadata {
-                dtype: tensor.dtype.clone(),
-                shape: tensor.shape.clone(),
-                data_offsets: [start_offset, end_offset],
-            });
+    let mut header_json = serde_json::to_string(&header_data)
+        .map_err(ExportError::Serialization)?;
+    let padding = (8 - header_json.len() % 8) % 8;
+    header_json.push_str(&" ".repeat(padding));
+
+    // 8 bytes: header size (little endian u64), then the JSON header
+    writer.write_all(&(header_json.len() as u64).to_le_bytes())?;
+    writer.write_all(header_json.as_bytes())?;
+    for key in &keys {
+        for &val in &tensors[*key].data {
+            writer.write_all(&val.to_le_bytes())?;
         }
+    }
+    writer.flush()?;
 
-        // Serialize header to JSON
-        let header_json = serde_json::to_string(&header_data)
-            .map_err(ExportError::Serialization)?;
-        let header_bytes = header_json.as_bytes();
-
-        // Build final buffer
-        let mut result = Vec::new();
-
-        // Header size (8 bytes, little endian)
-        result.extend_from_slice(&(header_bytes.len() as u64).to_le_bytes());
-
-        // Header JSON
-        result.extend_from_slice(header_bytes);
-
-        // Tensor data
-        result.extend(tensor_bytes);
+    Ok((8 + header_json.len() + data_offset) as u64)
+}
 
-        Ok(result)
+/// Read F32 tensors from a SafeTensors file
+///
+/// Tensors are read in file order, one at a time.
+pub fn read_safetensors<P: AsRef<Path>>(path: P) -> Result<HashMap<String, TensorData>, ExportError> {
+    let mut reader = BufReader::new(File::open(path).map_err(ExportError::Io)?);
+
+    let mut size = [0u8; 8];
+    reader.read_exact(&mut size)?;
+    let header_len = u64::from_le_bytes(size) as usize;
+    let mut header = vec![0u8; header_len];
+    reader.read_exact(&mut header)?;
+    let header: HashMap<String, serde_json::Value> = serde_json::from_slice(&header)?;
+
+    let mut entries: Vec<(String, TensorMetadata)> = header
+        .into_iter()
+        .filter(|(key, _)| key != "__metadata__")
+        .map(|(key, value)| Ok((key, serde_json::from_value(value)?)))
+        .collect::<Result<_, ExportError>>()?;
+    entries.sort_by_key(|(_, meta)| meta.data_offsets[0]);
+
+    let mut tensors = HashMap::with_capacity(entries.len());
+    let mut position = 0;
+    for (key, meta) in entries {
+        if meta.dtype != "F32" {
+            return Err(ExportError::InvalidData(format!(
+                "tensor {} has dtype {}, only F32 is supported", key, meta.dtype
+            )));
+        }
+        let [start, end] = meta.data_offsets;
+        if start < position || end < start || (end - start) % 4 != 0 {
+            return Err(ExportError::InvalidData(format!("tensor {} has invalid offsets", key)));
+        }
+        std::io::copy(&mut (&mut reader).take((start - position) as u64), &mut std::io::sink())?;
+        let mut bytes = vec![0u8; end - start];
+        reader.read_exact(&mut bytes)?;
+        position = end;
+
+        let data = bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+        tensors.insert(key, TensorData { data, shape: meta.shape, dtype: meta.dtype });
     }
+    Ok(tensors)
 }
 
 /// Tensor data for export
@@ -242,6 +274,47 @@ pub struct LoRAState {
     pub base_lora_layers: Vec<LoRALayerState>,
 }
 
+impl LoRAState {
+    /// Read the state back from an `adapter_model.safetensors` written by
+    /// [`SafeTensorsExporter::export_engine`]
+    pub fn read_safetensors<P: AsRef<Path>>(path: P) -> Result<Self, ExportError> {
+        Self::from_tensors(&read_safetensors(path)?)
+    }
+
+    /// Rebuild the state from exported tensors
+    ///
+    /// BaseLoRA layers are read from the `q_proj` tensors; the other
+    /// projections carry the same weights.
+    pub fn from_tensors(tensors: &HashMap<String, TensorData>) -> Result<Self, ExportError> {
+        let layers = |module: &str| -> Result<Vec<LoRALayerState>, ExportError> {
+            let mut layers = Vec::new();
+            loop {
+                let prefix = format!("base_model.model.layers.{}.self_attn.{}", layers.len(), module);
+                let (Some(a), Some(b)) = (
+                    tensors.get(&format!("{}A.weight", prefix)),
+                    tensors.get(&format!("{}B.weight", prefix)),
+                ) else {
+                    return Ok(layers);
+                };
+                let (&[rank, input_dim], &[output_dim, _]) = (a.shape.as_slice(), b.shape.as_slice()) else {
+                    return Err(ExportError::InvalidData(format!("{} is not a pair of matrices", prefix)));
+                };
+                layers.push(LoRALayerState {
+                    lora_a: a.data.clone(),
+                    lora_b: b.data.clone(),
+                    rank,
+                    input_dim,
+                    output_dim,
+                });
+            }
+        };
+        Ok(Self {
+            micro_lora_layers: layers("micro_lora_")?,
+            base_lora_layers: layers("q_proj.lora_")?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -258,6 +331,42 @@ mod tests {
         assert_eq!(tensor.shape, vec![2, 2]);
     }
 
+    #[test]
+    fn test_safetensors_round_trip() {
+        let layer = |scale: f32| LoRALayerState {
+            lora_a: (0..6).map(|i| i as f32 * scale).collect(),
+            lora_b: (0..9).map(|i| -(i as f32) * scale).collect(),
+            rank: 3,
+            input_dim: 2,
+            output_dim: 3,
+        };
+        let mut tensors = HashMap::new();
+        for (i, state) in [layer(1.0), layer(0.5)].iter().enumerate() {
+            let prefix = format!("base_model.model.layers.{}.self_attn.micro_lora_", i);
+            tensors.insert(format!("{}A.weight", prefix), TensorData {
+                data: state.lora_a.clone(),
+                shape: vec![state.rank, state.input_dim],
+                dtype: "F32".to_string(),
+            });
+            tensors.insert(format!("{}B.weight", prefix), TensorData {
+                data: state.lora_b.clone(),
+                shape: vec![state.output_dim, state.rank],
+                dtype: "F32".to_string(),
+            });
+        }
+
+        let path = std::env::temp_dir().join(format!("sona-st-{}.safetensors", std::process::id()));
+        let written = write_safetensors(BufWriter::new(File::create(&path).unwrap()), &tensors).unwrap();
+        assert_eq!(written, std::fs::metadata(&path).unwrap().len());
+
+        let state = LoRAState::read_safetensors(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(state.micro_lora_layers.len(), 2);
+        assert!(state.base_lora_layers.is_empty());
+        assert_eq!(state.micro_lora_layers[1].lora_a, layer(0.5).lora_a);
+        assert_eq!(state.micro_lora_layers[0].output_dim, 3);
+    }
+
     #[test]
     fn test_lora_layer_state() {
         let state = LoRALayerState {
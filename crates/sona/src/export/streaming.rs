@@ -0,0 +1,382 @@
+//! Streaming Export - Sharded HuggingFace datasets
+//!
+//! Writes records as JSONL shards under `data/`, named the way the Hub
+//! names split files (`train-00000-of-00004.jsonl`), plus a
+//! `dataset_info.json` with the feature schema and the list of shards.
+//! Records are written one at a time and read back lazily, so millions of
+//! patterns never need to be held in memory. The adapter goes alongside as
+//! `adapter_model.safetensors`.
+//!
+//! The same layout is read back by [`import_patterns`] and
+//! [`import_adapter`].
+
+use crate::engine::SonaEngine;
+use crate::types::LearnedPattern;
+use super::dataset::PatternRecord;
+use super::safetensors::{LoRAState, SafeTensorsExporter};
+use super::{ExportConfig, ExportResult, ExportType, ExportError};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Lines, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+/// Records per shard by default
+pub const DEFAULT_SHARD_SIZE: usize = 100_000;
+
+/// Name of the file describing a streamed dataset
+pub const DATASET_INFO_FILE: &str = "dataset_info.json";
+
+/// Name of the adapter weights file
+pub const ADAPTER_FILE: &str = "adapter_model.safetensors";
+
+/// Patterns imported into an engine per batch
+const IMPORT_BATCH: usize = 10_000;
+
+/// One shard of a streamed dataset
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ShardInfo {
+    /// Path relative to the dataset directory
+    pub file: String,
+    /// Records in the shard
+    pub records: usize,
+    /// File size in bytes
+    pub size_bytes: u64,
+}
+
+/// Contents of `dataset_info.json`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DatasetInfo {
+    /// Dataset name
+    pub dataset_name: String,
+    /// SONA version that wrote the dataset
+    pub version: String,
+    /// Split the shards belong to
+    pub split: String,
+    /// Feature schema in HuggingFace `datasets` form
+    pub features: serde_json::Value,
+    /// Total records
+    pub num_records: usize,
+    /// Shards in record order
+    pub shards: Vec<ShardInfo>,
+}
+
+/// Writes records to JSONL shards of a fixed size
+pub struct ShardedJsonlWriter<T> {
+    dir: PathBuf,
+    info: DatasetInfo,
+    shard_size: usize,
+    current: Option<BufWriter<File>>,
+    current_records: usize,
+    _record: PhantomData<fn(&T)>,
+}
+
+impl<T: Serialize> ShardedJsonlWriter<T> {
+    /// Start a dataset named `name` in `dir` with the given feature schema
+    pub fn create<P: AsRef<Path>>(
+        dir: P,
+        name: &str,
+        features: serde_json::Value,
+        shard_size: usize,
+    ) -> Result<Self, ExportError> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(dir.join("data")).map_err(ExportError::Io)?;
+        Ok(Self {
+            dir,
+            info: DatasetInfo {
+                dataset_name: name.to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                split: "train".to_string(),
+                features,
+                num_records: 0,
+                shards: Vec::new(),
+            },
+            shard_size: shard_size.max(1),
+            current: None,
+            current_records: 0,
+            _record: PhantomData,
+        })
+    }
+
+    /// Append one record, starting a new shard when the current one is full
+    pub fn write(&mut self, record: &T) -> Result<(), ExportError> {
+        if self.current_records == self.shard_size {
+            self.close_shard()?;
+        }
+        if self.current.is_none() {
+            let file = File::create(self.shard_path(self.info.shards.len()))?;
+            self.current = Some(BufWriter::new(file));
+        }
+        let writer = self.current.as_mut().expect("shard is open");
+        serde_json::to_writer(&mut *writer, record)?;
+        writer.write_all(b"\n")?;
+        self.current_records += 1;
+        self.info.num_records += 1;
+        Ok(())
+    }
+
+    /// Close the last shard, give shards their final names and write
+    /// `dataset_info.json`
+    pub fn finish(mut self) -> Result<DatasetInfo, ExportError> {
+        self.close_shard()?;
+        let total = self.info.shards.len();
+        for (i, shard) in self.info.shards.iter_mut().enumerate() {
+            let file = format!("data/{}-{:05}-of-{:05}.jsonl", self.info.split, i, total);
+            std::fs::rename(self.dir.join(&shard.file), self.dir.join(&file))?;
+            shard.file = file;
+        }
+        let info_json = serde_json::to_string_pretty(&self.info)?;
+        std::fs::write(self.dir.join(DATASET_INFO_FILE), info_json)?;
+        Ok(self.info)
+    }
+
+    fn shard_path(&self, index: usize) -> PathBuf {
+        self.dir
+            .join(format!("data/{}-{:05}.jsonl.partial", self.info.split, index))
+    }
+
+    fn close_shard(&mut self) -> Result<(), ExportError> {
+        let Some(mut writer) = self.current.take() else {
+            return Ok(());
+        };
+        writer.flush()?;
+        let path = self.shard_path(self.info.shards.len());
+        self.info.shards.push(ShardInfo {
+            file: path
+                .strip_prefix(&self.dir)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .to_string(),
+            records: self.current_records,
+            size_bytes: std::fs::metadata(&path)?.len(),
+        });
+        self.current_records = 0;
+        Ok(())
+    }
+}
+
+/// Reads records back from the shards of a streamed dataset, in order
+pub struct ShardedJsonlReader<T> {
+    dir: PathBuf,
+    info: DatasetInfo,
+    next_shard: usize,
+    lines: Option<Lines<BufReader<File>>>,
+    _record: PhantomData<fn() -> T>,
+}
+
+impl<T> ShardedJsonlReader<T> {
+    /// Open the dataset in `dir`
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self, ExportError> {
+        let dir = dir.as_ref().to_path_buf();
+        let info_json = std::fs::read_to_string(dir.join(DATASET_INFO_FILE))?;
+        Ok(Self {
+            dir,
+            info: serde_json::from_str(&info_json)?,
+            next_shard: 0,
+            lines: None,
+            _record: PhantomData,
+        })
+    }
+
+    /// Description of the dataset
+    pub fn info(&self) -> &DatasetInfo {
+        &self.info
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for ShardedJsonlReader<T> {
+    type Item = Result<T, ExportError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(lines) = self.lines.as_mut() {
+                match lines.next() {
+                    Some(Ok(line)) if line.trim().is_empty() => continue,
+                    Some(Ok(line)) => return Some(serde_json::from_str(&line).map_err(Into::into)),
+                    Some(Err(e)) => return Some(Err(e.into())),
+                    None => self.lines = None,
+                }
+            }
+            let shard = self.info.shards.get(self.next_shard)?;
+            self.next_shard += 1;
+            match File::open(self.dir.join(&shard.file)) {
+                Ok(file) => self.lines = Some(BufReader::new(file).lines()),
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+    }
+}
+
+/// Feature schema of [`PatternRecord`] in HuggingFace `datasets` form
+pub fn pattern_features() -> serde_json::Value {
+    let value = |dtype: &str| serde_json::json!({ "dtype": dtype, "_type": "Value" });
+    serde_json::json!({
+        "id": value("string"),
+        "embedding": { "feature": value("float32"), "_type": "Sequence" },
+        "cluster_size": value("int64"),
+        "avg_quality": value("float32"),
+        "pattern_type": value("string"),
+        "access_count": value("int64"),
+        "metadata": {
+            "source": value("string"),
+            "version": value("string"),
+            "target_model": value("string"),
+        },
+    })
+}
+
+/// Streaming exporter writing sharded datasets
+pub struct StreamingExporter<'a> {
+    config: &'a ExportConfig,
+    shard_size: usize,
+}
+
+impl<'a> StreamingExporter<'a> {
+    /// Create new streaming exporter
+    pub fn new(config: &'a ExportConfig) -> Self {
+        Self {
+            config,
+            shard_size: DEFAULT_SHARD_SIZE,
+        }
+    }
+
+    /// Set records per shard
+    pub fn with_shard_size(mut self, shard_size: usize) -> Self {
+        self.shard_size = shard_size;
+        self
+    }
+
+    /// Export patterns from any source as a sharded dataset
+    ///
+    /// Patterns below the quality threshold are skipped.
+    pub fn export_patterns<I, P>(&self, patterns: I, output_dir: P) -> Result<ExportResult, ExportError>
+    where
+        I: IntoIterator<Item = LearnedPattern>,
+        P: AsRef<Path>,
+    {
+        let output_dir = output_dir.as_ref();
+        let mut writer = ShardedJsonlWriter::create(
+            output_dir,
+            &format!("{}-patterns", self.config.model_name),
+            pattern_features(),
+            self.shard_size,
+        )?;
+        for pattern in patterns {
+            if pattern.avg_quality < self.config.min_quality_threshold {
+                continue;
+            }
+            writer.write(&PatternRecord::from_pattern(&pattern, &self.config.target_architecture))?;
+        }
+        let info = writer.finish()?;
+
+        Ok(ExportResult {
+            export_type: ExportType::PatternsDataset,
+            items_exported: info.num_records,
+            output_path: output_dir.to_string_lossy().to_string(),
+            size_bytes: info.shards.iter().map(|s| s.size_bytes).sum(),
+        })
+    }
+
+    /// Export an engine's patterns and, if configured, its adapter
+    pub fn export_engine<P: AsRef<Path>>(
+        &self,
+        engine: &SonaEngine,
+        output_dir: P,
+    ) -> Result<Vec<ExportResult>, ExportError> {
+        let output_dir = output_dir.as_ref();
+        let mut results = Vec::new();
+        if self.config.include_patterns {
+            results.push(self.export_patterns(engine.get_all_patterns(), output_dir)?);
+        }
+        if self.config.include_lora {
+            results.push(SafeTensorsExporter::new(self.config).export_engine(engine, output_dir)?);
+        }
+        Ok(results)
+    }
+
+    /// Stream the patterns of a dataset into an engine's ReasoningBank
+    ///
+    /// Returns the number of patterns imported.
+    pub fn import_into<P: AsRef<Path>>(engine: &SonaEngine, input_dir: P) -> Result<usize, ExportError> {
+        let mut patterns = import_patterns(input_dir)?;
+        let mut imported = 0;
+        loop {
+            let batch = patterns
+                .by_ref()
+                .take(IMPORT_BATCH)
+                .collect::<Result<Vec<_>, _>>()?;
+            if batch.is_empty() {
+                return Ok(imported);
+            }
+            imported += engine.import_patterns(batch);
+        }
+    }
+}
+
+/// Read patterns lazily from a dataset written by [`StreamingExporter`]
+pub fn import_patterns<P: AsRef<Path>>(
+    input_dir: P,
+) -> Result<impl Iterator<Item = Result<LearnedPattern, ExportError>>, ExportError> {
+    let records = ShardedJsonlReader::<PatternRecord>::open(input_dir)?;
+    Ok(records.map(|record| record.map(PatternRecord::into_pattern)))
+}
+
+/// Read the adapter written alongside a streamed dataset
+pub fn import_adapter<P: AsRef<Path>>(input_dir: P) -> Result<LoRAState, ExportError> {
+    LoRAState::read_safetensors(input_dir.as_ref().join(ADAPTER_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PatternType;
+
+    fn pattern(id: u64, quality: f32) -> LearnedPattern {
+        LearnedPattern {
+            id,
+            centroid: vec![id as f32, 1.0],
+            cluster_size: 4,
+            total_weight: 4.0 * quality,
+            avg_quality: quality,
+            created_at: 0,
+            last_accessed: 0,
+            access_count: 2,
+            pattern_type: PatternType::Reasoning,
+        }
+    }
+
+    #[test]
+    fn test_sharded_round_trip() {
+        let dir = std::env::temp_dir().join(format!("sona-stream-{}", std::process::id()));
+        let config = ExportConfig::default();
+        let exporter = StreamingExporter::new(&config).with_shard_size(10);
+
+        // Every fifth pattern is below the quality threshold
+        let patterns = (0..30).map(|i| pattern(i, if i % 5 == 0 { 0.1 } else { 0.9 }));
+        let result = exporter.export_patterns(patterns, &dir).unwrap();
+        assert_eq!(result.items_exported, 24);
+
+        let reader = ShardedJsonlReader::<PatternRecord>::open(&dir).unwrap();
+        let files: Vec<&str> = reader.info().shards.iter().map(|s| s.file.as_str()).collect();
+        assert_eq!(
+            files,
+            [
+                "data/train-00000-of-00003.jsonl",
+                "data/train-00001-of-00003.jsonl",
+                "data/train-00002-of-00003.jsonl",
+            ]
+        );
+        assert_eq!(reader.info().shards[2].records, 4);
+
+        let imported: Vec<LearnedPattern> = import_patterns(&dir)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(imported.len(), 24);
+        assert_eq!(imported[0].id, 1);
+        assert_eq!(imported[0].centroid, vec![1.0, 1.0]);
+        assert_eq!(imported[0].pattern_type, PatternType::Reasoning);
+    }
+}
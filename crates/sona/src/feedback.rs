@@ -0,0 +1,222 @@
+//! Search feedback ingestion
+//!
+//! Tracks recent searches (query embedding plus returned result embeddings)
+//! so that later usefulness reports can be turned into learning signals. The
+//! query ids are chosen by the caller, which lets them match the ids handed
+//! out by a vector database's search audit log.
+
+use crate::types::{LearningSignal, QueryTrajectory, SignalMetadata, TrajectoryStep};
+use crate::time_compat::Instant;
+use parking_lot::RwLock;
+use std::collections::{HashMap, VecDeque};
+
+/// Configuration for feedback tracking
+#[derive(Clone, Debug)]
+pub struct FeedbackConfig {
+    /// Maximum number of searches kept for feedback
+    pub max_tracked_searches: usize,
+    /// Smoothing factor for the running reward baseline
+    pub baseline_decay: f32,
+}
+
+impl Default for FeedbackConfig {
+    fn default() -> Self {
+        Self {
+            max_tracked_searches: 4096,
+            baseline_decay: 0.95,
+        }
+    }
+}
+
+/// A search awaiting feedback
+#[derive(Clone, Debug)]
+struct TrackedSearch {
+    query_embedding: Vec<f32>,
+    results: Vec<(String, Vec<f32>)>,
+}
+
+/// Turns search feedback into trajectories and learning signals
+pub struct FeedbackTracker {
+    config: FeedbackConfig,
+    searches: RwLock<HashMap<u64, TrackedSearch>>,
+    order: RwLock<VecDeque<u64>>,
+    baseline: RwLock<Option<f32>>,
+}
+
+impl FeedbackTracker {
+    /// Create new tracker
+    pub fn new(config: FeedbackConfig) -> Self {
+        Self {
+            config,
+            searches: RwLock::new(HashMap::new()),
+            order: RwLock::new(VecDeque::new()),
+            baseline: RwLock::new(None),
+        }
+    }
+
+    /// Remember a search so feedback on its results can be ingested
+    ///
+    /// `results` holds the returned ids with their embeddings, in rank order.
+    /// Re-tracking an existing id replaces it.
+    pub fn track(&self, query_id: u64, query_embedding: Vec<f32>, results: Vec<(String, Vec<f32>)>) {
+        let mut searches = self.searches.write();
+        let mut order = self.order.write();
+
+        if searches.insert(query_id, TrackedSearch { query_embedding, results }).is_none() {
+            order.push_back(query_id);
+        }
+
+        while order.len() > self.config.max_tracked_searches.max(1) {
+            if let Some(oldest) = order.pop_front() {
+                searches.remove(&oldest);
+            }
+        }
+    }
+
+    /// Number of tracked searches
+    pub fn len(&self) -> usize {
+        self.searches.read().len()
+    }
+
+    /// Check if no searches are tracked
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Current reward baseline
+    pub fn baseline(&self) -> Option<f32> {
+        *self.baseline.read()
+    }
+
+    /// Build the trajectory and learning signal for one feedback report
+    ///
+    /// The gradient points along the result embedding, signed by the reward's
+    /// advantage over the running baseline. Returns `None` if the query is not
+    /// tracked or the result was not part of it.
+    pub fn ingest(
+        &self,
+        trajectory_id: u64,
+        query_id: u64,
+        result_id: &str,
+        reward: f32,
+    ) -> Option<(QueryTrajectory, LearningSignal)> {
+        let searches = self.searches.read();
+        let search = searches.get(&query_id)?;
+        let (rank, embedding) = search
+            .results
+            .iter()
+            .enumerate()
+            .find(|(_, (id, _))| id == result_id)
+            .map(|(rank, (_, embedding))| (rank, embedding))?;
+
+        let reward = reward.clamp(0.0, 1.0);
+        let baseline = self.update_baseline(reward);
+        let advantage = reward - baseline;
+
+        let dim = search.query_embedding.len();
+        let mut gradient = vec![0.0f32; dim];
+        for (g, &x) in gradient.iter_mut().zip(embedding.iter()) {
+            *g = advantage * x;
+        }
+        let norm: f32 = gradient.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 1e-8 {
+            gradient.iter_mut().for_each(|x| *x /= norm);
+        }
+
+        let mut trajectory = QueryTrajectory::new(trajectory_id, search.query_embedding.clone());
+        trajectory.add_step(TrajectoryStep::new(embedding.clone(), vec![], reward, 0).with_layer("search_feedback"));
+        trajectory.context_ids.push(result_id.to_string());
+        trajectory.finalize(reward, 0);
+
+        let mut tags = HashMap::new();
+        tags.insert("source".to_string(), "search_feedback".to_string());
+        tags.insert("query_id".to_string(), query_id.to_string());
+        tags.insert("result_id".to_string(), result_id.to_string());
+        tags.insert("rank".to_string(), rank.to_string());
+
+        let signal = LearningSignal {
+            query_embedding: search.query_embedding.clone(),
+            gradient_estimate: gradient,
+            quality_score: reward,
+            timestamp: Some(Instant::now()),
+            metadata: SignalMetadata {
+                trajectory_id,
+                step_count: 1,
+                model_route: None,
+                tags,
+            },
+        };
+
+        Some((trajectory, signal))
+    }
+
+    /// Fold a reward into the baseline, returning the baseline before the update
+    fn update_baseline(&self, reward: f32) -> f32 {
+        let mut baseline = self.baseline.write();
+        let previous = baseline.unwrap_or(0.5);
+        let decay = self.config.baseline_decay.clamp(0.0, 1.0);
+        *baseline = Some(decay * previous + (1.0 - decay) * reward);
+        previous
+    }
+}
+
+impl Default for FeedbackTracker {
+    fn default() -> Self {
+        Self::new(FeedbackConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker_with_search() -> FeedbackTracker {
+        let tracker = FeedbackTracker::default();
+        tracker.track(
+            7,
+            vec![1.0, 0.0, 0.0],
+            vec![
+                ("a".to_string(), vec![0.0, 2.0, 0.0]),
+                ("b".to_string(), vec![0.0, 0.0, 1.0]),
+            ],
+        );
+        tracker
+    }
+
+    #[test]
+    fn test_ingest_builds_signal() {
+        let tracker = tracker_with_search();
+
+        let (trajectory, signal) = tracker.ingest(1, 7, "b", 1.0).unwrap();
+        assert_eq!(trajectory.final_quality, 1.0);
+        assert_eq!(trajectory.context_ids, vec!["b".to_string()]);
+        assert_eq!(signal.gradient_estimate, vec![0.0, 0.0, 1.0]);
+        assert_eq!(signal.metadata.tags["rank"], "1");
+
+        // Rejection after a positive baseline pushes the other way
+        let (_, signal) = tracker.ingest(2, 7, "a", 0.0).unwrap();
+        assert_eq!(signal.gradient_estimate, vec![0.0, -1.0, 0.0]);
+        assert_eq!(signal.quality_score, 0.0);
+    }
+
+    #[test]
+    fn test_unknown_feedback_ignored() {
+        let tracker = tracker_with_search();
+        assert!(tracker.ingest(1, 8, "a", 1.0).is_none());
+        assert!(tracker.ingest(1, 7, "zzz", 1.0).is_none());
+        assert!(tracker.baseline().is_none());
+    }
+
+    #[test]
+    fn test_eviction() {
+        let tracker = FeedbackTracker::new(FeedbackConfig {
+            max_tracked_searches: 2,
+            ..Default::default()
+        });
+        for id in 0..3 {
+            tracker.track(id, vec![0.0], vec![]);
+        }
+        assert_eq!(tracker.len(), 2);
+        assert!(tracker.ingest(1, 0, "a", 1.0).is_none());
+    }
+}
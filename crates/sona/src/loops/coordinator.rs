@@ -5,7 +5,8 @@ use crate::lora::{BaseLoRA, MicroLoRA};
 use crate::loops::background::{BackgroundLoop, BackgroundLoopConfig, BackgroundResult};
 use crate::loops::instant::{InstantLoop, InstantLoopConfig};
 use crate::reasoning_bank::{PatternConfig, ReasoningBank};
-use crate::types::{QueryTrajectory, SonaConfig};
+use crate::trajectory::{TrajectoryMatch, TrajectorySimilarity};
+use crate::types::{LearningSignal, QueryTrajectory, SonaConfig};
 use crate::time_compat::Instant;
 use parking_lot::RwLock;
 use std::sync::Arc;
@@ -84,6 +85,13 @@ impl LoopCoordinator {
         }
     }
 
+    /// Process feedback-derived trajectory and signal (Loop A)
+    pub fn on_feedback(&self, trajectory: QueryTrajectory, signal: LearningSignal) {
+        if self.instant_enabled {
+            self.instant.on_signal(trajectory, signal);
+        }
+    }
+
     /// Generate next trajectory ID
     pub fn next_trajectory_id(&self) -> u64 {
         self.instant.next_id()
@@ -116,6 +124,16 @@ impl LoopCoordinator {
         self.instant.flush();
     }
 
+    /// Find recent trajectories similar to `query`
+    pub fn find_similar_trajectories(
+        &self,
+        query: &QueryTrajectory,
+        k: usize,
+        method: TrajectorySimilarity,
+    ) -> Vec<TrajectoryMatch> {
+        self.instant.buffer().find_similar(query, k, method)
+    }
+
     /// Get micro-LoRA for inference
     pub fn micro_lora(&self) -> &Arc<RwLock<MicroLoRA>> {
         self.instant.micro_lora()
@@ -3,7 +3,7 @@
 //! Per-request adaptation with <1ms overhead.
 
 use crate::lora::MicroLoRA;
-use crate::trajectory::{TrajectoryBuffer, TrajectoryIdGen};
+use crate::trajectory::{TrajectoryBuffer, TrajectoryIdGen, DEFAULT_HISTORY_CAPACITY};
 use crate::types::{LearningSignal, QueryTrajectory, SonaConfig};
 use parking_lot::RwLock;
 use std::sync::Arc;
@@ -20,6 +20,8 @@ pub struct InstantLoopConfig {
     pub buffer_capacity: usize,
     /// Flush threshold (apply updates every N signals)
     pub flush_threshold: usize,
+    /// Recent trajectories kept for similarity search
+    pub history_capacity: usize,
 }
 
 impl Default for InstantLoopConfig {
@@ -29,6 +31,7 @@ impl Default for InstantLoopConfig {
             micro_lora_lr: 0.001,
             buffer_capacity: 10000,
             flush_threshold: 100,
+            history_capacity: DEFAULT_HISTORY_CAPACITY,
         }
     }
 }
@@ -40,6 +43,7 @@ impl From<&SonaConfig> for InstantLoopConfig {
             micro_lora_lr: config.micro_lora_lr,
             buffer_capacity: config.trajectory_capacity,
             flush_threshold: 100,
+            history_capacity: DEFAULT_HISTORY_CAPACITY,
         }
     }
 }
@@ -77,7 +81,10 @@ impl InstantLoop {
     /// Create new instant loop
     pub fn new(hidden_dim: usize, config: InstantLoopConfig) -> Self {
         Self {
-            trajectory_buffer: Arc::new(TrajectoryBuffer::new(config.buffer_capacity)),
+            trajectory_buffer: Arc::new(TrajectoryBuffer::with_history(
+                config.buffer_capacity,
+                config.history_capacity,
+            )),
             micro_lora: Arc::new(RwLock::new(MicroLoRA::new(hidden_dim, config.micro_lora_rank))),
             id_gen: TrajectoryIdGen::new(),
             pending_signals: AtomicU64::new(0),
@@ -98,12 +105,16 @@ impl InstantLoop {
 
     /// Process completed trajectory
     pub fn on_trajectory(&self, trajectory: QueryTrajectory) {
-        // Record to buffer
-        self.trajectory_buffer.record(trajectory.clone());
-        self.metrics.trajectories_processed.fetch_add(1, Ordering::Relaxed);
-
         // Generate learning signal
         let signal = LearningSignal::from_trajectory(&trajectory);
+        self.on_signal(trajectory, signal);
+    }
+
+    /// Process trajectory with an externally computed learning signal
+    pub fn on_signal(&self, trajectory: QueryTrajectory, signal: LearningSignal) {
+        // Record to buffer
+        self.trajectory_buffer.record(trajectory);
+        self.metrics.trajectories_processed.fetch_add(1, Ordering::Relaxed);
 
         // Accumulate gradient (non-blocking)
         if let Some(mut lora) = self.micro_lora.try_write() {
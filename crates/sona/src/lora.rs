@@ -3,6 +3,12 @@
 //! Two-tier LoRA system:
 //! - MicroLoRA: Rank 1-2, per-request adaptation (<100μs)
 //! - BaseLoRA: Rank 4-16, background adaptation (hourly)
+//!
+//! Separately trained adapters can be merged into one ([`MergeMethod`]) or
+//! applied side by side with per-adapter scales ([`LoRAComposition`]).
+//! For edge deployment, adapters quantize to int8 with per-channel scales
+//! ([`QuantizedMicroLoRA`], [`QuantizedBaseLoRA`]), about a quarter of
+//! their f32 size.
 
 use crate::types::LearningSignal;
 use serde::{Deserialize, Serialize};
@@ -382,6 +388,620 @@ impl BaseLoRA {
     }
 }
 
+/// How learned adapters are merged into one
+///
+/// Merging works on the LoRA factors. A factor shared by every adapter,
+/// such as MicroLoRA's deterministic down projection, is kept as is, so
+/// merging MicroLoRA adapters of the same shape is exact on their deltas;
+/// where both factors differ it is an approximation.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MergeMethod {
+    /// Weighted average of the weights
+    Linear,
+    /// TIES-Merging: keep the `density` fraction of each adapter's weights
+    /// with the largest magnitude, elect a sign per weight from the
+    /// weighted sum, and average only the weights that agree with it
+    Ties {
+        /// Fraction of weights kept per adapter, in (0, 1]
+        density: f32,
+    },
+}
+
+/// Merge one factor of several adapters
+///
+/// `factors` pairs each adapter's weights with its merge weight.
+fn merge_factor(factors: &[(Vec<f32>, f32)], method: MergeMethod) -> Vec<f32> {
+    let first = &factors[0].0;
+    if factors.iter().all(|(f, _)| f == first) {
+        return first.clone();
+    }
+
+    let trimmed: Vec<(Vec<f32>, f32)> = match method {
+        MergeMethod::Linear => factors.to_vec(),
+        MergeMethod::Ties { density } => factors
+            .iter()
+            .map(|(f, w)| (trim(f, density), *w))
+            .collect(),
+    };
+
+    (0..first.len())
+        .map(|i| {
+            let elected = trimmed.iter().map(|(f, w)| f[i] * w).sum::<f32>();
+            let (mut sum, mut total) = (0.0f32, 0.0f32);
+            for (f, w) in &trimmed {
+                let agrees = match method {
+                    MergeMethod::Linear => true,
+                    MergeMethod::Ties { .. } => f[i] != 0.0 && (f[i] > 0.0) == (elected > 0.0),
+                };
+                if agrees {
+                    sum += f[i] * w;
+                    total += w;
+                }
+            }
+            if total > 0.0 { sum / total } else { 0.0 }
+        })
+        .collect()
+}
+
+/// Zero all but the `density` fraction of weights with largest magnitude
+fn trim(weights: &[f32], density: f32) -> Vec<f32> {
+    let keep = ((weights.len() as f32 * density).ceil() as usize).min(weights.len());
+    if keep == 0 {
+        return vec![0.0; weights.len()];
+    }
+    let mut magnitudes: Vec<f32> = weights.iter().map(|w| w.abs()).collect();
+    let (_, threshold, _) = magnitudes.select_nth_unstable_by(keep - 1, |a, b| b.total_cmp(a));
+    let threshold = *threshold;
+    weights
+        .iter()
+        .map(|&w| if w.abs() >= threshold { w } else { 0.0 })
+        .collect()
+}
+
+/// Check merge inputs: at least one adapter, positive total weight, and a
+/// valid TIES density
+fn check_merge<T>(adapters: &[(&T, f32)], method: MergeMethod) -> Result<(), String> {
+    if adapters.is_empty() {
+        return Err("No adapters to merge".to_string());
+    }
+    if adapters.iter().any(|(_, w)| !w.is_finite() || *w < 0.0)
+        || adapters.iter().map(|(_, w)| w).sum::<f32>() <= 0.0
+    {
+        return Err("Merge weights must be non-negative with a positive sum".to_string());
+    }
+    if let MergeMethod::Ties { density } = method {
+        if density.is_nan() || density <= 0.0 || density > 1.0 {
+            return Err(format!("TIES density must be in (0, 1], got {}", density));
+        }
+    }
+    Ok(())
+}
+
+impl MicroLoRA {
+    /// Merge adapters of the same shape into one
+    ///
+    /// `adapters` pairs each adapter with its merge weight. Each adapter's
+    /// scale is folded into its up projection, so adapters with different
+    /// scales merge correctly.
+    pub fn merge(adapters: &[(&MicroLoRA, f32)], method: MergeMethod) -> Result<MicroLoRA, String> {
+        check_merge(adapters, method)?;
+        let first = adapters[0].0;
+        if let Some((other, _)) = adapters
+            .iter()
+            .find(|(a, _)| a.hidden_dim != first.hidden_dim || a.rank != first.rank)
+        {
+            return Err(format!(
+                "Cannot merge MicroLoRA {}x{} with {}x{}",
+                first.hidden_dim, first.rank, other.hidden_dim, other.rank
+            ));
+        }
+
+        let downs: Vec<(Vec<f32>, f32)> = adapters
+            .iter()
+            .map(|(a, w)| (a.down_proj.clone(), *w))
+            .collect();
+        let ups: Vec<(Vec<f32>, f32)> = adapters
+            .iter()
+            .map(|(a, w)| {
+                let ratio = a.scale / first.scale;
+                (a.up_proj.iter().map(|x| x * ratio).collect(), *w)
+            })
+            .collect();
+
+        let mut merged = MicroLoRA::new(first.hidden_dim, first.rank);
+        merged.down_proj = merge_factor(&downs, method);
+        merged.up_proj = merge_factor(&ups, method);
+        merged.scale = first.scale;
+        Ok(merged)
+    }
+}
+
+impl BaseLoRA {
+    /// Merge adapters of the same shape into one, layer by layer
+    ///
+    /// `adapters` pairs each adapter with its merge weight. Each adapter's
+    /// alpha is folded into its up projections.
+    pub fn merge(adapters: &[(&BaseLoRA, f32)], method: MergeMethod) -> Result<BaseLoRA, String> {
+        check_merge(adapters, method)?;
+        let first = adapters[0].0;
+        if adapters.iter().any(|(a, _)| {
+            a.hidden_dim != first.hidden_dim
+                || a.rank != first.rank
+                || a.layers.len() != first.layers.len()
+        }) {
+            return Err("Cannot merge BaseLoRA adapters of different shapes".to_string());
+        }
+
+        let layers = (0..first.layers.len())
+            .map(|idx| {
+                let downs: Vec<(Vec<f32>, f32)> = adapters
+                    .iter()
+                    .map(|(a, w)| (a.layers[idx].down_proj.clone(), *w))
+                    .collect();
+                let ups: Vec<(Vec<f32>, f32)> = adapters
+                    .iter()
+                    .map(|(a, w)| {
+                        let ratio = a.alpha / first.alpha;
+                        (a.layers[idx].up_proj.iter().map(|x| x * ratio).collect(), *w)
+                    })
+                    .collect();
+                LoRALayer {
+                    down_proj: merge_factor(&downs, method),
+                    up_proj: merge_factor(&ups, method),
+                    layer_idx: idx,
+                }
+            })
+            .collect();
+
+        Ok(BaseLoRA {
+            layers,
+            rank: first.rank,
+            hidden_dim: first.hidden_dim,
+            alpha: first.alpha,
+        })
+    }
+}
+
+/// Adapters applied together at inference, each with its own scale
+///
+/// Lets separately trained skills run side by side without merging them;
+/// the output is the sum of every adapter's contribution times its scale.
+#[derive(Clone, Debug, Default)]
+pub struct LoRAComposition {
+    /// Named MicroLoRA adapters and their scales
+    micro: Vec<(String, MicroLoRA, f32)>,
+    /// Named BaseLoRA adapters and their scales
+    base: Vec<(String, BaseLoRA, f32)>,
+}
+
+impl LoRAComposition {
+    /// Create empty composition
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a MicroLoRA adapter, replacing any adapter with the same name
+    pub fn add_micro(&mut self, name: &str, adapter: MicroLoRA, scale: f32) {
+        self.remove(name);
+        self.micro.push((name.to_string(), adapter, scale));
+    }
+
+    /// Add a BaseLoRA adapter, replacing any adapter with the same name
+    pub fn add_base(&mut self, name: &str, adapter: BaseLoRA, scale: f32) {
+        self.remove(name);
+        self.base.push((name.to_string(), adapter, scale));
+    }
+
+    /// Remove an adapter; returns false if there was none with that name
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.len();
+        self.micro.retain(|(n, _, _)| n != name);
+        self.base.retain(|(n, _, _)| n != name);
+        self.len() != before
+    }
+
+    /// Change an adapter's scale; returns false if there was none with that name
+    pub fn set_scale(&mut self, name: &str, scale: f32) -> bool {
+        let micro = self.micro.iter_mut().map(|(n, _, s)| (n, s));
+        let base = self.base.iter_mut().map(|(n, _, s)| (n, s));
+        match micro.chain(base).find(|(n, _)| n.as_str() == name) {
+            Some((_, s)) => {
+                *s = scale;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Names of composed adapters, MicroLoRA first
+    pub fn names(&self) -> Vec<&str> {
+        let micro = self.micro.iter().map(|(n, _, _)| n.as_str());
+        micro.chain(self.base.iter().map(|(n, _, _)| n.as_str())).collect()
+    }
+
+    /// Get number of composed adapters
+    pub fn len(&self) -> usize {
+        self.micro.len() + self.base.len()
+    }
+
+    /// Check if no adapter is composed
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add every adapter's scaled contribution for `layer_idx` to `output`
+    pub fn forward(&self, layer_idx: usize, input: &[f32], output: &mut [f32]) {
+        let mut scratch = vec![0.0f32; output.len()];
+        for (_, adapter, scale) in &self.micro {
+            scratch.fill(0.0);
+            adapter.forward(input, &mut scratch);
+            output.iter_mut().zip(&scratch).for_each(|(o, x)| *o += scale * x);
+        }
+        for (_, adapter, scale) in &self.base {
+            scratch.fill(0.0);
+            adapter.forward_layer(layer_idx, input, &mut scratch);
+            output.iter_mut().zip(&scratch).for_each(|(o, x)| *o += scale * x);
+        }
+    }
+}
+
+/// Int8 weights with one scale per channel
+///
+/// Each channel of `channel_len` consecutive weights is scaled so its
+/// largest magnitude maps to 127 (symmetric quantization), which keeps
+/// the error of a channel proportional to its own range.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuantizedWeights {
+    /// Quantized values
+    pub values: Vec<i8>,
+    /// Dequantization scale per channel
+    pub scales: Vec<f32>,
+    /// Weights per channel
+    pub channel_len: usize,
+}
+
+impl QuantizedWeights {
+    /// Quantize `weights`, split into channels of `channel_len`
+    pub fn quantize(weights: &[f32], channel_len: usize) -> Self {
+        let channel_len = channel_len.max(1);
+        let mut values = Vec::with_capacity(weights.len());
+        let mut scales = Vec::with_capacity((weights.len() + channel_len - 1) / channel_len);
+        for channel in weights.chunks(channel_len) {
+            let max = channel.iter().fold(0.0f32, |m, w| m.max(w.abs()));
+            let scale = if max > 0.0 { max / 127.0 } else { 1.0 };
+            values.extend(channel.iter().map(|w| (w / scale).round().clamp(-127.0, 127.0) as i8));
+            scales.push(scale);
+        }
+        Self { values, scales, channel_len }
+    }
+
+    /// Restore f32 weights
+    pub fn dequantize(&self) -> Vec<f32> {
+        self.values
+            .chunks(self.channel_len)
+            .zip(&self.scales)
+            .flat_map(|(channel, &scale)| channel.iter().map(move |&q| q as f32 * scale))
+            .collect()
+    }
+
+    /// Dot product of channel `c` with `input`, without dequantizing
+    #[inline]
+    fn channel_dot(&self, c: usize, input: &[f32]) -> f32 {
+        let offset = c * self.channel_len;
+        let sum: f32 = self.values[offset..offset + self.channel_len]
+            .iter()
+            .zip(input)
+            .map(|(&q, x)| q as f32 * x)
+            .sum();
+        sum * self.scales[c]
+    }
+
+    /// Add `factor` times channel `c` to `output`, without dequantizing
+    #[inline]
+    fn add_channel(&self, c: usize, factor: f32, output: &mut [f32]) {
+        let offset = c * self.channel_len;
+        let factor = factor * self.scales[c];
+        for (o, &q) in output.iter_mut().zip(&self.values[offset..offset + self.channel_len]) {
+            *o += factor * q as f32;
+        }
+    }
+
+    /// Stored size in bytes
+    pub fn size_bytes(&self) -> usize {
+        self.values.len() + self.scales.len() * 4
+    }
+
+    /// Append the little-endian encoding to `out`
+    fn encode(&self, out: &mut Vec<u8>) {
+        put_u32(out, self.channel_len as u32);
+        put_u32(out, self.values.len() as u32);
+        out.extend(self.values.iter().map(|&q| q as u8));
+        for scale in &self.scales {
+            out.extend_from_slice(&scale.to_le_bytes());
+        }
+    }
+
+    /// Read an encoding written by [`encode`](Self::encode)
+    fn decode(input: &mut &[u8]) -> Result<Self, String> {
+        let channel_len = take_u32(input)? as usize;
+        let len = take_u32(input)? as usize;
+        if channel_len == 0 || len % channel_len != 0 {
+            return Err(format!("Invalid quantized tensor: {} values in channels of {}", len, channel_len));
+        }
+        let values = take(input, len)?.iter().map(|&b| b as i8).collect();
+        let scales = (0..len / channel_len)
+            .map(|_| take_f32(input))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { values, scales, channel_len })
+    }
+}
+
+/// Magic bytes of encoded quantized adapters
+const QUANTIZED_MAGIC: &[u8; 4] = b"SQ8\x01";
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], String> {
+    if input.len() < len {
+        return Err("Truncated quantized adapter".to_string());
+    }
+    let (head, rest) = input.split_at(len);
+    *input = rest;
+    Ok(head)
+}
+
+fn take_u32(input: &mut &[u8]) -> Result<u32, String> {
+    let b = take(input, 4)?;
+    Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn take_f32(input: &mut &[u8]) -> Result<f32, String> {
+    take_u32(input).map(f32::from_bits)
+}
+
+/// Check the magic bytes of an encoded adapter
+fn take_magic(input: &mut &[u8]) -> Result<(), String> {
+    if take(input, 4)? != QUANTIZED_MAGIC {
+        return Err("Not a quantized SONA adapter".to_string());
+    }
+    Ok(())
+}
+
+/// MicroLoRA with int8 weights, for compact storage on edge devices
+///
+/// Channels are the adapter's rank rows, so each factor carries `rank`
+/// scales. [`forward`](Self::forward) computes on the int8 weights
+/// directly; [`dequantize`](Self::dequantize) restores a trainable adapter.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuantizedMicroLoRA {
+    /// Down projection
+    pub down_proj: QuantizedWeights,
+    /// Up projection
+    pub up_proj: QuantizedWeights,
+    /// Rank
+    pub rank: usize,
+    /// Hidden dimension
+    pub hidden_dim: usize,
+    /// Scaling factor
+    pub scale: f32,
+}
+
+impl QuantizedMicroLoRA {
+    /// Forward pass: output += scale * (input @ down) @ up
+    pub fn forward(&self, input: &[f32], output: &mut [f32]) {
+        assert_eq!(input.len(), self.hidden_dim);
+        assert_eq!(output.len(), self.hidden_dim);
+
+        for r in 0..self.rank {
+            let intermediate = self.down_proj.channel_dot(r, input);
+            self.up_proj.add_channel(r, intermediate * self.scale, output);
+        }
+    }
+
+    /// Restore an f32 adapter
+    pub fn dequantize(&self) -> MicroLoRA {
+        let mut lora = MicroLoRA::new(self.hidden_dim, self.rank);
+        lora.down_proj = self.down_proj.dequantize();
+        lora.up_proj = self.up_proj.dequantize();
+        lora.scale = self.scale;
+        lora
+    }
+
+    /// Stored size of the weights in bytes
+    pub fn size_bytes(&self) -> usize {
+        self.down_proj.size_bytes() + self.up_proj.size_bytes()
+    }
+
+    /// Encode as compact little-endian bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.size_bytes() + 40);
+        self.encode(&mut out);
+        out
+    }
+
+    /// Decode bytes written by [`to_bytes`](Self::to_bytes)
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, String> {
+        Self::read_from(&mut bytes)
+    }
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(QUANTIZED_MAGIC);
+        put_u32(out, self.rank as u32);
+        put_u32(out, self.hidden_dim as u32);
+        out.extend_from_slice(&self.scale.to_le_bytes());
+        self.down_proj.encode(out);
+        self.up_proj.encode(out);
+    }
+
+    /// Decode an adapter from the front of `input`, advancing it past the
+    /// adapter, so several encoded adapters can be read back to back
+    pub fn read_from(input: &mut &[u8]) -> Result<Self, String> {
+        take_magic(input)?;
+        let rank = take_u32(input)? as usize;
+        let hidden_dim = take_u32(input)? as usize;
+        let scale = take_f32(input)?;
+        if !(1..=2).contains(&rank) {
+            return Err(format!("MicroLoRA rank must be 1-2, got {}", rank));
+        }
+        let down_proj = QuantizedWeights::decode(input)?;
+        let up_proj = QuantizedWeights::decode(input)?;
+        for w in [&down_proj, &up_proj] {
+            if w.channel_len != hidden_dim || w.values.len() != rank * hidden_dim {
+                return Err("Quantized MicroLoRA weights do not match its shape".to_string());
+            }
+        }
+        Ok(Self { down_proj, up_proj, rank, hidden_dim, scale })
+    }
+}
+
+impl MicroLoRA {
+    /// Quantize weights to int8 with per-channel scales
+    pub fn quantize(&self) -> QuantizedMicroLoRA {
+        QuantizedMicroLoRA {
+            down_proj: QuantizedWeights::quantize(&self.down_proj, self.hidden_dim),
+            up_proj: QuantizedWeights::quantize(&self.up_proj, self.hidden_dim),
+            rank: self.rank,
+            hidden_dim: self.hidden_dim,
+            scale: self.scale,
+        }
+    }
+}
+
+/// Single BaseLoRA layer with int8 weights
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuantizedLoRALayer {
+    /// Down projection
+    pub down_proj: QuantizedWeights,
+    /// Up projection
+    pub up_proj: QuantizedWeights,
+    /// Layer index
+    pub layer_idx: usize,
+}
+
+/// BaseLoRA with int8 weights; see [`QuantizedMicroLoRA`]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuantizedBaseLoRA {
+    /// LoRA layers
+    pub layers: Vec<QuantizedLoRALayer>,
+    /// Rank
+    pub rank: usize,
+    /// Hidden dimension
+    pub hidden_dim: usize,
+    /// Alpha scaling factor
+    pub alpha: f32,
+}
+
+impl QuantizedBaseLoRA {
+    /// Forward pass for single layer
+    pub fn forward_layer(&self, layer_idx: usize, input: &[f32], output: &mut [f32]) {
+        let Some(layer) = self.layers.get(layer_idx) else {
+            return;
+        };
+        let scale = self.alpha / self.rank as f32;
+
+        for r in 0..self.rank {
+            let intermediate = layer.down_proj.channel_dot(r, input);
+            layer.up_proj.add_channel(r, intermediate * scale, output);
+        }
+    }
+
+    /// Restore an f32 adapter
+    pub fn dequantize(&self) -> BaseLoRA {
+        BaseLoRA {
+            layers: self
+                .layers
+                .iter()
+                .map(|layer| LoRALayer {
+                    down_proj: layer.down_proj.dequantize(),
+                    up_proj: layer.up_proj.dequantize(),
+                    layer_idx: layer.layer_idx,
+                })
+                .collect(),
+            rank: self.rank,
+            hidden_dim: self.hidden_dim,
+            alpha: self.alpha,
+        }
+    }
+
+    /// Stored size of the weights in bytes
+    pub fn size_bytes(&self) -> usize {
+        self.layers
+            .iter()
+            .map(|layer| layer.down_proj.size_bytes() + layer.up_proj.size_bytes())
+            .sum()
+    }
+
+    /// Encode as compact little-endian bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.size_bytes() + 16 + 16 * self.layers.len());
+        self.encode(&mut out);
+        out
+    }
+
+    /// Decode bytes written by [`to_bytes`](Self::to_bytes)
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, String> {
+        Self::read_from(&mut bytes)
+    }
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(QUANTIZED_MAGIC);
+        put_u32(out, self.rank as u32);
+        put_u32(out, self.hidden_dim as u32);
+        out.extend_from_slice(&self.alpha.to_le_bytes());
+        put_u32(out, self.layers.len() as u32);
+        for layer in &self.layers {
+            put_u32(out, layer.layer_idx as u32);
+            layer.down_proj.encode(out);
+            layer.up_proj.encode(out);
+        }
+    }
+
+    /// Decode an adapter from the front of `input`, advancing it past the
+    /// adapter, so several encoded adapters can be read back to back
+    pub fn read_from(input: &mut &[u8]) -> Result<Self, String> {
+        take_magic(input)?;
+        let rank = take_u32(input)? as usize;
+        let hidden_dim = take_u32(input)? as usize;
+        let alpha = take_f32(input)?;
+        let num_layers = take_u32(input)? as usize;
+        let mut layers = Vec::new();
+        for _ in 0..num_layers {
+            let layer_idx = take_u32(input)? as usize;
+            let down_proj = QuantizedWeights::decode(input)?;
+            let up_proj = QuantizedWeights::decode(input)?;
+            for w in [&down_proj, &up_proj] {
+                if w.channel_len != hidden_dim || w.values.len() != rank * hidden_dim {
+                    return Err("Quantized BaseLoRA weights do not match its shape".to_string());
+                }
+            }
+            layers.push(QuantizedLoRALayer { down_proj, up_proj, layer_idx });
+        }
+        Ok(Self { layers, rank, hidden_dim, alpha })
+    }
+}
+
+impl BaseLoRA {
+    /// Quantize weights to int8 with per-channel scales
+    pub fn quantize(&self) -> QuantizedBaseLoRA {
+        QuantizedBaseLoRA {
+            layers: self
+                .layers
+                .iter()
+                .map(|layer| QuantizedLoRALayer {
+                    down_proj: QuantizedWeights::quantize(&layer.down_proj, self.hidden_dim),
+                    up_proj: QuantizedWeights::quantize(&layer.up_proj, self.hidden_dim),
+                    layer_idx: layer.layer_idx,
+                })
+                .collect(),
+            rank: self.rank,
+            hidden_dim: self.hidden_dim,
+            alpha: self.alpha,
+        }
+    }
+}
+
 /// Combined LoRA engine managing both tiers
 #[derive(Clone, Debug)]
 pub struct LoRAEngine {
@@ -507,9 +1127,156 @@ mod tests {
         engine.forward(0, &input, &mut output);
     }
 
+    fn trained_micro(hidden_dim: usize, gradient: f32) -> MicroLoRA {
+        let mut lora = MicroLoRA::new(hidden_dim, 2);
+        let signal = LearningSignal::with_gradient(vec![0.1; hidden_dim], vec![gradient; hidden_dim], 1.0);
+        lora.accumulate_gradient(&signal);
+        lora.apply_accumulated(1.0);
+        lora
+    }
+
+    #[test]
+    fn test_merge_linear_averages_outputs() {
+        let (a, b) = (trained_micro(16, 0.5), trained_micro(16, -0.1));
+        let merged = MicroLoRA::merge(&[(&a, 3.0), (&b, 1.0)], MergeMethod::Linear).unwrap();
+
+        let input: Vec<f32> = (0..16).map(|i| i as f32 / 16.0).collect();
+        let (mut out_a, mut out_b, mut out_m) = (vec![0.0; 16], vec![0.0; 16], vec![0.0; 16]);
+        a.forward(&input, &mut out_a);
+        b.forward(&input, &mut out_b);
+        merged.forward(&input, &mut out_m);
+        for i in 0..16 {
+            let expected = 0.75 * out_a[i] + 0.25 * out_b[i];
+            assert!((out_m[i] - expected).abs() < 1e-6);
+        }
+
+        assert!(MicroLoRA::merge(&[], MergeMethod::Linear).is_err());
+        let other = MicroLoRA::new(8, 2);
+        assert!(MicroLoRA::merge(&[(&a, 1.0), (&other, 1.0)], MergeMethod::Linear).is_err());
+    }
+
+    #[test]
+    fn test_ties_trims_and_elects_sign() {
+        let factors = vec![
+            (vec![0.9, -0.1, 0.5, 0.0], 1.0),
+            (vec![-0.2, -0.8, 0.4, 0.05], 1.0),
+            (vec![0.6, 0.7, -0.3, 0.0], 1.0),
+        ];
+        let merged = merge_factor(&factors, MergeMethod::Ties { density: 0.5 });
+        // Trimmed: [0.9, 0, 0.5, 0], [0, -0.8, 0.4, 0], [0.6, 0.7, 0, 0]
+        assert!((merged[0] - 0.75).abs() < 1e-6);
+        assert!((merged[1] + 0.8).abs() < 1e-6);
+        assert!((merged[2] - 0.45).abs() < 1e-6);
+        assert_eq!(merged[3], 0.0);
+    }
+
+    #[test]
+    fn test_composition() {
+        let (a, b) = (trained_micro(8, 0.5), trained_micro(8, 0.2));
+        let mut composition = LoRAComposition::new();
+        composition.add_micro("code", a.clone(), 1.0);
+        composition.add_micro("math", b.clone(), 0.5);
+        composition.add_base("style", BaseLoRA::new(8, 4, 2), 1.0);
+        assert_eq!(composition.names(), ["code", "math", "style"]);
+
+        let input = vec![1.0f32; 8];
+        let (mut out_a, mut out_b, mut out_c) = (vec![0.0; 8], vec![0.0; 8], vec![0.0; 8]);
+        a.forward(&input, &mut out_a);
+        b.forward(&input, &mut out_b);
+        composition.forward(0, &input, &mut out_c);
+        for i in 0..8 {
+            assert!((out_c[i] - (out_a[i] + 0.5 * out_b[i])).abs() < 1e-6);
+        }
+
+        assert!(composition.set_scale("math", 0.0));
+        assert!(composition.remove("style"));
+        assert!(!composition.set_scale("style", 1.0));
+        assert_eq!(composition.len(), 2);
+    }
+
     #[test]
     #[should_panic(expected = "MicroLoRA rank must be 1-2")]
     fn test_invalid_rank() {
         MicroLoRA::new(64, 5);
     }
+
+    #[test]
+    fn test_quantized_lora() {
+        let mut lora = MicroLoRA::new(64, 2);
+        let gradient: Vec<f32> = (0..64).map(|i| (i as f32 * 0.37).sin()).collect();
+        lora.accumulate_gradient(&LearningSignal::with_gradient(vec![0.0; 64], gradient, 1.0));
+        lora.apply_accumulated(0.1);
+
+        let quantized = lora.quantize();
+        assert!(quantized.size_bytes() * 3 < lora.param_count() * 4);
+
+        let input: Vec<f32> = (0..64).map(|i| (i as f32 * 0.11).cos()).collect();
+        let mut expected = vec![0.0; 64];
+        lora.forward(&input, &mut expected);
+        let max = expected.iter().fold(0.0f32, |m, x| m.max(x.abs()));
+        assert!(max > 0.0);
+
+        // Rounding moves each weight by at most half its channel's step, so
+        // intermediate r is off by at most sum|x| * step_r / 2; carry that
+        // and the up-projection step through to each output
+        let input_l1: f32 = input.iter().map(|x| x.abs()).sum();
+        let bounds: Vec<f32> = (0..64)
+            .map(|i| {
+                let per_rank: f32 = (0..2)
+                    .map(|r| {
+                        let exact: f32 =
+                            (0..64).map(|j| input[j] * lora.down_proj[r * 64 + j]).sum();
+                        let down_err = input_l1 * quantized.down_proj.scales[r] / 2.0;
+                        let up_step = quantized.up_proj.scales[r] / 2.0;
+                        let up = lora.up_proj[r * 64 + i].abs();
+                        down_err * (up + up_step) + exact.abs() * up_step
+                    })
+                    .sum();
+                per_rank * lora.scale
+            })
+            .collect();
+
+        // Int8 forward stays within the bound; dequantized weights match it
+        let mut direct = vec![0.0; 64];
+        quantized.forward(&input, &mut direct);
+        let mut restored = vec![0.0; 64];
+        quantized.dequantize().forward(&input, &mut restored);
+        for i in 0..64 {
+            assert!((direct[i] - expected[i]).abs() <= bounds[i] * 1.01 + max * 1e-5);
+            assert!((restored[i] - direct[i]).abs() < max * 1e-4);
+        }
+
+        let bytes = quantized.to_bytes();
+        assert_eq!(QuantizedMicroLoRA::from_bytes(&bytes).unwrap(), quantized);
+        assert!(QuantizedMicroLoRA::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_quantized_base_lora() {
+        let mut base = BaseLoRA::new(16, 4, 2);
+        for (l, layer) in base.layers.iter_mut().enumerate() {
+            for (i, w) in layer.down_proj.iter_mut().chain(layer.up_proj.iter_mut()).enumerate() {
+                *w = ((i + l * 7) as f32 * 0.29).sin() * (1 + i % 4) as f32;
+            }
+        }
+        let quantized = base.quantize();
+
+        let input: Vec<f32> = (0..16).map(|i| i as f32 / 16.0).collect();
+        let (mut expected, mut actual) = (vec![0.0; 16], vec![0.0; 16]);
+        base.forward_layer(1, &input, &mut expected);
+        quantized.forward_layer(1, &input, &mut actual);
+        let max = expected.iter().fold(0.0f32, |m, x| m.max(x.abs()));
+        for i in 0..16 {
+            assert!((actual[i] - expected[i]).abs() < max * 0.02);
+        }
+
+        // Adapters encoded back to back decode in turn
+        let mut bytes = quantized.to_bytes();
+        bytes.extend(MicroLoRA::new(16, 1).quantize().to_bytes());
+        let mut input = bytes.as_slice();
+        assert_eq!(QuantizedBaseLoRA::read_from(&mut input).unwrap(), quantized);
+        assert_eq!(QuantizedMicroLoRA::read_from(&mut input).unwrap().hidden_dim, 16);
+        assert!(input.is_empty());
+        assert_eq!(quantized.dequantize().layers[0].down_proj.len(), 64);
+    }
 }
@@ -9,15 +9,22 @@ pub mod ewc;
 pub mod reasoning_bank;
 pub mod loops;
 pub mod engine;
+pub mod adapter_registry;
 
 // Re-export main types
 pub use types::{
     LearningSignal, QueryTrajectory, TrajectoryStep,
     LearnedPattern, PatternType, SignalMetadata, SonaConfig,
 };
-pub use lora::{MicroLoRA, BaseLoRA, LoRAEngine, LoRALayer};
-pub use trajectory::{TrajectoryBuffer, TrajectoryBuilder, TrajectoryIdGen};
+pub use lora::{
+    MicroLoRA, BaseLoRA, LoRAEngine, LoRALayer, LoRAComposition, MergeMethod,
+    QuantizedWeights, QuantizedMicroLoRA, QuantizedBaseLoRA, QuantizedLoRALayer,
+};
+pub use trajectory::{
+    TrajectoryBuffer, TrajectoryBuilder, TrajectoryIdGen, TrajectoryMatch, TrajectorySimilarity,
+};
 pub use ewc::{EwcConfig, EwcPlusPlus, TaskFisher};
 pub use reasoning_bank::{ReasoningBank, PatternConfig};
 pub use loops::{InstantLoop, BackgroundLoop, LoopCoordinator};
 pub use engine::SonaEngine;
+pub use adapter_registry::{AdapterRegistry, TaskAdapter};
@@ -348,6 +348,27 @@ impl ReasoningBank {
             .collect()
     }
 
+    /// Add a pattern learned elsewhere, e.g. imported from a dataset
+    ///
+    /// The pattern gets a fresh ID, which is returned.
+    pub fn insert_pattern(&mut self, mut pattern: LearnedPattern) -> u64 {
+        let pattern_id = self.next_pattern_id;
+        self.next_pattern_id += 1;
+        pattern.id = pattern_id;
+        self.pattern_index.push((pattern.centroid.clone(), pattern_id));
+        self.patterns.insert(pattern_id, pattern);
+        pattern_id
+    }
+
+    /// Remove pattern by ID; returns false if there was none
+    pub fn remove_pattern(&mut self, id: u64) -> bool {
+        if self.patterns.remove(&id).is_none() {
+            return false;
+        }
+        self.pattern_index.retain(|(_, pid)| *pid != id);
+        true
+    }
+
     /// Get pattern by ID
     pub fn get_pattern(&self, id: u64) -> Option<&LearnedPattern> {
         self.patterns.get(&id)
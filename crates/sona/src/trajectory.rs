@@ -1,12 +1,62 @@
 //! Lock-free trajectory buffer for SONA
 //!
 //! Provides efficient, non-blocking trajectory recording during inference.
+//!
+//! A buffer can also keep a bounded history of recorded trajectories so
+//! agents can retrieve past reasoning sessions similar to the current one
+//! with [`TrajectoryBuffer::find_similar`]. A trajectory is compared as the
+//! sequence of its step activations (its query embedding when it has no
+//! steps), either pooled into one vector or aligned step by step; see
+//! [`TrajectorySimilarity`].
 
 use crate::types::{QueryTrajectory, TrajectoryStep};
 use crate::time_compat::Instant;
 use crossbeam::queue::ArrayQueue;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicU64, Ordering};
 
+/// Trajectories kept for similarity search by the instant loop
+pub const DEFAULT_HISTORY_CAPACITY: usize = 1000;
+
+/// How two trajectories are compared
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TrajectorySimilarity {
+    /// Cosine similarity of the step activations pooled with a temporal
+    /// kernel: a step's weight halves every `half_life` steps back from
+    /// the last one, so recent reasoning dominates
+    Pooled {
+        /// Steps over which a step's weight halves
+        half_life: f32,
+    },
+    /// Mean cosine similarity of steps aligned by dynamic time warping,
+    /// which matches sessions that took the same path at different speeds
+    Dtw {
+        /// Largest index gap between aligned steps (Sakoe-Chiba band);
+        /// `None` allows any alignment
+        window: Option<usize>,
+    },
+}
+
+impl Default for TrajectorySimilarity {
+    fn default() -> Self {
+        TrajectorySimilarity::Pooled { half_life: 4.0 }
+    }
+}
+
+/// A past trajectory similar to a query trajectory
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrajectoryMatch {
+    /// Trajectory ID
+    pub id: u64,
+    /// Similarity in [-1, 1], higher is more similar
+    pub similarity: f32,
+    /// Final quality of the past trajectory
+    pub final_quality: f32,
+    /// Model route it took
+    pub model_route: Option<String>,
+}
+
 /// Lock-free trajectory buffer using crossbeam ArrayQueue
 pub struct TrajectoryBuffer {
     /// Internal queue
@@ -17,25 +67,46 @@ pub struct TrajectoryBuffer {
     dropped: AtomicU64,
     /// Total trajectories seen
     total_seen: AtomicU64,
+    /// Most recent trajectories kept for similarity search, oldest first
+    history: Mutex<VecDeque<QueryTrajectory>>,
+    /// History capacity; 0 disables history
+    history_capacity: usize,
 }
 
 impl TrajectoryBuffer {
     /// Create new buffer with capacity
     pub fn new(capacity: usize) -> Self {
+        Self::with_history(capacity, 0)
+    }
+
+    /// Create buffer that also keeps the last `history_capacity` recorded
+    /// trajectories for [`TrajectoryBuffer::find_similar`]
+    pub fn with_history(capacity: usize, history_capacity: usize) -> Self {
         Self {
             buffer: ArrayQueue::new(capacity),
             capacity,
             dropped: AtomicU64::new(0),
             total_seen: AtomicU64::new(0),
+            history: Mutex::new(VecDeque::with_capacity(history_capacity)),
+            history_capacity,
         }
     }
 
     /// Record trajectory (non-blocking)
     ///
-    /// Returns true if recorded, false if buffer full
+    /// Returns true if recorded, false if buffer full. Trajectories enter
+    /// the history even when the buffer is full.
     pub fn record(&self, trajectory: QueryTrajectory) -> bool {
         self.total_seen.fetch_add(1, Ordering::Relaxed);
 
+        if self.history_capacity > 0 {
+            let mut history = self.history.lock();
+            if history.len() == self.history_capacity {
+                history.pop_front();
+            }
+            history.push_back(trajectory.clone());
+        }
+
         match self.buffer.push(trajectory) {
             Ok(()) => true,
             Err(_) => {
@@ -117,6 +188,128 @@ impl TrajectoryBuffer {
         self.dropped.store(0, Ordering::Relaxed);
         self.total_seen.store(0, Ordering::Relaxed);
     }
+
+    /// Get number of trajectories in the history
+    pub fn history_len(&self) -> usize {
+        self.history.lock().len()
+    }
+
+    /// Find the `k` past trajectories most similar to `query`
+    ///
+    /// Searches the history, most similar first. A trajectory with the same
+    /// ID as `query` is skipped, so an in-progress session can be compared
+    /// against its own recorded past.
+    pub fn find_similar(
+        &self,
+        query: &QueryTrajectory,
+        k: usize,
+        method: TrajectorySimilarity,
+    ) -> Vec<TrajectoryMatch> {
+        let history = self.history.lock();
+        let mut matches: Vec<TrajectoryMatch> = history
+            .iter()
+            .filter(|past| past.id != query.id)
+            .map(|past| TrajectoryMatch {
+                id: past.id,
+                similarity: trajectory_similarity(query, past, method),
+                final_quality: past.final_quality,
+                model_route: past.model_route.clone(),
+            })
+            .collect();
+        drop(history);
+
+        matches.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        matches.truncate(k);
+        matches
+    }
+}
+
+/// Similarity of two trajectories in [-1, 1]
+pub fn trajectory_similarity(
+    a: &QueryTrajectory,
+    b: &QueryTrajectory,
+    method: TrajectorySimilarity,
+) -> f32 {
+    let (a, b) = (step_sequence(a), step_sequence(b));
+    match method {
+        TrajectorySimilarity::Pooled { half_life } => {
+            cosine(&pooled(&a, half_life), &pooled(&b, half_life))
+        }
+        TrajectorySimilarity::Dtw { window } => dtw_similarity(&a, &b, window),
+    }
+}
+
+/// Step activations of a trajectory, or its query embedding without steps
+fn step_sequence(trajectory: &QueryTrajectory) -> Vec<&[f32]> {
+    if trajectory.steps.is_empty() {
+        return vec![&trajectory.query_embedding];
+    }
+    trajectory
+        .steps
+        .iter()
+        .map(|step| step.activations.as_slice())
+        .collect()
+}
+
+/// Weighted mean of `steps`, halving weights every `half_life` steps back
+fn pooled(steps: &[&[f32]], half_life: f32) -> Vec<f32> {
+    let dim = steps.iter().map(|s| s.len()).max().unwrap_or(0);
+    let mut sum = vec![0.0f32; dim];
+    let mut total = 0.0f32;
+    for (i, step) in steps.iter().enumerate() {
+        let age = (steps.len() - 1 - i) as f32;
+        let weight = 0.5f32.powf(age / half_life.max(f32::EPSILON));
+        for (s, &x) in sum.iter_mut().zip(step.iter()) {
+            *s += weight * x;
+        }
+        total += weight;
+    }
+    if total > 0.0 {
+        sum.iter_mut().for_each(|s| *s /= total);
+    }
+    sum
+}
+
+/// Mean cosine similarity along the best DTW alignment of `a` and `b`
+fn dtw_similarity(a: &[&[f32]], b: &[&[f32]], window: Option<usize>) -> f32 {
+    let (n, m) = (a.len(), b.len());
+    // The band must at least cover the length difference
+    let window = window.map_or(usize::MAX, |w| w.max(n.abs_diff(m)));
+
+    // cost[i][j] = (total distance, path length) of the best alignment of
+    // a[..i] with b[..j]
+    let mut cost = vec![vec![(f32::INFINITY, 0usize); m + 1]; n + 1];
+    cost[0][0] = (0.0, 0);
+    for i in 1..=n {
+        let lo = i.saturating_sub(window).max(1);
+        let hi = i.saturating_add(window).min(m);
+        for j in lo..=hi {
+            let d = 1.0 - cosine(a[i - 1], b[j - 1]);
+            let prev = [cost[i - 1][j - 1], cost[i - 1][j], cost[i][j - 1]]
+                .into_iter()
+                .min_by(|x, y| x.0.total_cmp(&y.0))
+                .unwrap_or((f32::INFINITY, 0));
+            cost[i][j] = (prev.0 + d, prev.1 + 1);
+        }
+    }
+    let (distance, steps) = cost[n][m];
+    if steps == 0 {
+        return 0.0;
+    }
+    1.0 - distance / steps as f32
+}
+
+/// Cosine similarity, comparing the shared prefix when dimensions differ
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a > 1e-8 && norm_b > 1e-8 {
+        dot / (norm_a * norm_b)
+    } else {
+        0.0
+    }
 }
 
 /// Builder for constructing trajectories during inference
@@ -188,6 +381,19 @@ impl TrajectoryBuilder {
         self.start_time.elapsed()
     }
 
+    /// Copy of the trajectory so far, e.g. to search for similar sessions
+    pub fn snapshot(&self) -> QueryTrajectory {
+        QueryTrajectory {
+            id: self.id,
+            query_embedding: self.query_embedding.clone(),
+            steps: self.steps.clone(),
+            final_quality: 0.0,
+            latency_us: self.start_time.elapsed().as_micros() as u64,
+            model_route: self.model_route.clone(),
+            context_ids: self.context_ids.clone(),
+        }
+    }
+
     /// Finalize and build trajectory
     pub fn build(self, final_quality: f32) -> QueryTrajectory {
         let latency_us = self.start_time.elapsed().as_micros() as u64;
@@ -314,6 +520,46 @@ mod tests {
         assert_eq!(buffer.len(), 2);
     }
 
+    fn session(id: u64, steps: &[[f32; 2]]) -> QueryTrajectory {
+        let mut trajectory = QueryTrajectory::new(id, vec![1.0, 0.0]);
+        for (i, step) in steps.iter().enumerate() {
+            trajectory.add_step(TrajectoryStep::new(step.to_vec(), vec![], 0.5, i));
+        }
+        trajectory
+    }
+
+    #[test]
+    fn test_find_similar() {
+        let buffer = TrajectoryBuffer::with_history(2, 3);
+        buffer.record(session(1, &[[1.0, 0.0], [0.0, 1.0]]));
+        buffer.record(session(2, &[[0.0, 1.0], [1.0, 0.0]]));
+        buffer.record(session(3, &[[1.0, 0.0], [1.0, 0.0], [1.0, 0.0], [0.0, 1.0]]));
+        buffer.record(session(4, &[[-1.0, 0.0]]));
+
+        // History keeps the newest three, even though the buffer is full
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.history_len(), 3);
+
+        // Same path as session 1, taken at a different speed
+        let query = session(9, &[[1.0, 0.0], [1.0, 0.0], [0.0, 1.0]]);
+        let dtw = buffer.find_similar(&query, 2, TrajectorySimilarity::Dtw { window: None });
+        assert_eq!(dtw[0].id, 3);
+        assert!((dtw[0].similarity - 1.0).abs() < 1e-6);
+        assert_eq!(dtw[1].id, 2);
+
+        // Pooling is cheaper but mostly order-blind: both sessions mixing
+        // the two steps score close to the query
+        let pooled = buffer.find_similar(&query, 3, TrajectorySimilarity::default());
+        assert_eq!(pooled.len(), 3);
+        assert!(pooled[1].similarity > 0.95);
+        assert_eq!(pooled[2].id, 4);
+
+        // Without history nothing is found
+        let plain = TrajectoryBuffer::new(2);
+        plain.record(session(1, &[[1.0, 0.0]]));
+        assert!(plain.find_similar(&query, 5, TrajectorySimilarity::default()).is_empty());
+    }
+
     #[test]
     fn test_builder() {
         let mut builder = TrajectoryBuilder::new(42, vec![0.1, 0.2, 0.3]);
@@ -246,6 +246,22 @@ impl std::fmt::Display for PatternType {
     }
 }
 
+impl std::str::FromStr for PatternType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "general" => Ok(PatternType::General),
+            "reasoning" => Ok(PatternType::Reasoning),
+            "factual" => Ok(PatternType::Factual),
+            "creative" => Ok(PatternType::Creative),
+            "codegen" => Ok(PatternType::CodeGen),
+            "conversational" => Ok(PatternType::Conversational),
+            other => Err(format!("unknown pattern type: {}", other)),
+        }
+    }
+}
+
 impl LearnedPattern {
     /// Create new pattern
     pub fn new(id: u64, centroid: Vec<f32>) -> Self {
@@ -219,6 +219,31 @@ impl WasmSonaEngine {
         output
     }
 
+    /// Export LoRA weights quantized to int8
+    ///
+    /// # Returns
+    /// Micro-LoRA followed by base LoRA as Uint8Array, about a quarter of
+    /// the f32 size
+    #[wasm_bindgen(js_name = exportQuantizedLora)]
+    pub fn export_quantized_lora(&self) -> Vec<u8> {
+        let (micro, base) = self.inner.read().quantize_lora();
+        let mut bytes = micro.to_bytes();
+        bytes.extend(base.to_bytes());
+        bytes
+    }
+
+    /// Load LoRA weights exported by `exportQuantizedLora`
+    ///
+    /// # Arguments
+    /// * `bytes` - Quantized weights as Uint8Array
+    #[wasm_bindgen(js_name = loadQuantizedLora)]
+    pub fn load_quantized_lora(&self, bytes: Vec<u8>) -> Result<(), JsValue> {
+        let mut input = bytes.as_slice();
+        let micro = crate::lora::QuantizedMicroLoRA::read_from(&mut input).map_err(|e| JsValue::from_str(&e))?;
+        let base = crate::lora::QuantizedBaseLoRA::read_from(&mut input).map_err(|e| JsValue::from_str(&e))?;
+        self.inner.read().load_quantized_lora(&micro, &base).map_err(|e| JsValue::from_str(&e))
+    }
+
     /// Run instant learning cycle
     ///
     /// Flushes accumulated micro-LoRA updates
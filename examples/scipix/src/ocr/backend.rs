@@ -0,0 +1,327 @@
+//! Pluggable OCR Backends
+//!
+//! An [`OcrBackend`] turns image bytes into an [`OcrResult`]. The local
+//! ONNX [`OcrEngine`] is one backend and [`RemoteApiBackend`] forwards to an
+//! HTTP OCR service; both can be registered in an [`OcrBackendRegistry`],
+//! which routes each request to the backend named in
+//! [`OcrOptions::backend`] (or the registry default).
+//!
+//! [`OcrBackendRegistry::benchmark`] runs every backend over the same
+//! labelled samples and scores them with a caller-supplied error metric,
+//! such as the CER/WER functions used by the accuracy tests.
+
+use super::{engine::OcrEngine, OcrError, OcrOptions, OcrResult, RegionType, Result, TextRegion};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::debug;
+
+/// An OCR implementation that can be selected per request
+#[async_trait]
+pub trait OcrBackend: Send + Sync {
+    /// Name the backend is registered and selected under
+    fn name(&self) -> &str;
+
+    /// Recognize text in an image
+    async fn recognize(&self, image_data: &[u8], options: &OcrOptions) -> Result<OcrResult>;
+}
+
+#[async_trait]
+impl OcrBackend for OcrEngine {
+    fn name(&self) -> &str {
+        "onnx"
+    }
+
+    async fn recognize(&self, image_data: &[u8], options: &OcrOptions) -> Result<OcrResult> {
+        self.recognize_with_options(image_data, options).await
+    }
+}
+
+/// Response body expected from a remote OCR API
+#[derive(Debug, Deserialize)]
+struct RemoteResponse {
+    /// Recognized text or LaTeX
+    text: String,
+    /// Confidence (0.0-1.0), if reported
+    #[serde(default)]
+    confidence: Option<f32>,
+    /// Whether the text is a math expression
+    #[serde(default)]
+    is_math: bool,
+}
+
+/// Backend that posts images to a remote OCR API
+///
+/// The image is sent as the multipart field `file` alongside the JSON
+/// encoded `options`; the service answers with
+/// `{"text": ..., "confidence": ..., "is_math": ...}`.
+pub struct RemoteApiBackend {
+    name: String,
+    endpoint: String,
+    api_key: Option<String>,
+    client: reqwest::Client,
+}
+
+impl RemoteApiBackend {
+    /// Create a backend posting to `endpoint`
+    pub fn new(name: impl Into<String>, endpoint: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            endpoint: endpoint.into(),
+            api_key: None,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Send `key` as a bearer token
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+}
+
+#[async_trait]
+impl OcrBackend for RemoteApiBackend {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn recognize(&self, image_data: &[u8], options: &OcrOptions) -> Result<OcrResult> {
+        let start = Instant::now();
+        let options_json = serde_json::to_string(options)
+            .map_err(|e| OcrError::InvalidConfig(format!("Cannot encode options: {}", e)))?;
+        let form = reqwest::multipart::Form::new()
+            .part(
+                "file",
+                reqwest::multipart::Part::bytes(image_data.to_vec()).file_name("image"),
+            )
+            .text("options", options_json);
+
+        let mut request = self.client.post(&self.endpoint).multipart(form);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response: RemoteResponse = request
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| OcrError::Inference(format!("{} request failed: {}", self.name, e)))?
+            .json()
+            .await
+            .map_err(|e| {
+                OcrError::Decoding(format!("{} returned invalid JSON: {}", self.name, e))
+            })?;
+
+        let confidence = response.confidence.unwrap_or(1.0);
+        let region_type = if response.is_math {
+            RegionType::Math
+        } else {
+            RegionType::Text
+        };
+
+        Ok(OcrResult {
+            text: response.text.clone(),
+            confidence,
+            regions: vec![TextRegion {
+                bbox: [0.0, 0.0, 0.0, 0.0],
+                text: response.text,
+                confidence,
+                region_type,
+                characters: vec![],
+            }],
+            has_math: response.is_math,
+            processing_time_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+}
+
+/// Named OCR backends with a default
+#[derive(Default)]
+pub struct OcrBackendRegistry {
+    backends: HashMap<String, Arc<dyn OcrBackend>>,
+    default: Option<String>,
+}
+
+/// Accuracy and latency of one backend over a benchmark set
+#[derive(Debug, Clone)]
+pub struct BackendBenchmark {
+    /// Backend name
+    pub backend: String,
+    /// Mean metric value over successful samples (lower is better for error rates)
+    pub mean_score: f64,
+    /// Mean processing time in milliseconds
+    pub mean_latency_ms: f64,
+    /// Samples the backend failed on
+    pub failures: usize,
+}
+
+impl OcrBackendRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a backend under its name; the first one becomes the default
+    pub fn register(&mut self, backend: Arc<dyn OcrBackend>) {
+        let name = backend.name().to_string();
+        if self.default.is_none() {
+            self.default = Some(name.clone());
+        }
+        self.backends.insert(name, backend);
+    }
+
+    /// Set the backend used when a request names none
+    pub fn set_default(&mut self, name: &str) -> Result<()> {
+        if !self.backends.contains_key(name) {
+            return Err(OcrError::InvalidConfig(format!(
+                "Unknown OCR backend: {}",
+                name
+            )));
+        }
+        self.default = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Get a backend by name
+    pub fn get(&self, name: &str) -> Option<Arc<dyn OcrBackend>> {
+        self.backends.get(name).cloned()
+    }
+
+    /// Registered backend names, sorted
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.backends.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Backend for a request: the one named in `options`, else the default
+    pub fn select(&self, options: &OcrOptions) -> Result<Arc<dyn OcrBackend>> {
+        let name = options
+            .backend
+            .as_deref()
+            .or(self.default.as_deref())
+            .ok_or_else(|| OcrError::InvalidConfig("No OCR backend registered".to_string()))?;
+        self.get(name)
+            .ok_or_else(|| OcrError::InvalidConfig(format!("Unknown OCR backend: {}", name)))
+    }
+
+    /// Recognize an image with the backend selected by `options`
+    pub async fn recognize(&self, image_data: &[u8], options: &OcrOptions) -> Result<OcrResult> {
+        let backend = self.select(options)?;
+        debug!("Recognizing with OCR backend '{}'", backend.name());
+        backend.recognize(image_data, options).await
+    }
+
+    /// Run every backend over labelled `(image, expected text)` samples
+    ///
+    /// `metric(expected, actual)` scores each output, e.g. CER or WER.
+    pub async fn benchmark<F>(
+        &self,
+        samples: &[(&[u8], &str)],
+        options: &OcrOptions,
+        metric: F,
+    ) -> Vec<BackendBenchmark>
+    where
+        F: Fn(&str, &str) -> f64,
+    {
+        let mut results = Vec::new();
+        for name in self.names() {
+            let backend = &self.backends[&name];
+            let (mut score, mut latency, mut succeeded, mut failures) = (0.0, 0.0, 0usize, 0);
+
+            for (image, expected) in samples {
+                let start = Instant::now();
+                match backend.recognize(image, options).await {
+                    Ok(result) => {
+                        score += metric(expected, &result.text);
+                        latency += start.elapsed().as_secs_f64() * 1000.0;
+                        succeeded += 1;
+                    }
+                    Err(_) => failures += 1,
+                }
+            }
+
+            let n = succeeded.max(1) as f64;
+            results.push(BackendBenchmark {
+                backend: name,
+                mean_score: score / n,
+                mean_latency_ms: latency / n,
+                failures,
+            });
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedBackend(&'static str, &'static str);
+
+    #[async_trait]
+    impl OcrBackend for FixedBackend {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        async fn recognize(&self, _image_data: &[u8], _options: &OcrOptions) -> Result<OcrResult> {
+            Ok(OcrResult {
+                text: self.1.to_string(),
+                confidence: 1.0,
+                regions: vec![],
+                has_math: false,
+                processing_time_ms: 0,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registry_routes_per_request() {
+        let mut registry = OcrBackendRegistry::new();
+        registry.register(Arc::new(FixedBackend("local", "x+1")));
+        registry.register(Arc::new(FixedBackend("remote", "x + 1")));
+
+        let options = OcrOptions::default();
+        assert_eq!(
+            registry.recognize(b"img", &options).await.unwrap().text,
+            "x+1"
+        );
+
+        let options = OcrOptions {
+            backend: Some("remote".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            registry.recognize(b"img", &options).await.unwrap().text,
+            "x + 1"
+        );
+
+        let options = OcrOptions {
+            backend: Some("missing".to_string()),
+            ..Default::default()
+        };
+        assert!(registry.recognize(b"img", &options).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_benchmark_scores_each_backend() {
+        let mut registry = OcrBackendRegistry::new();
+        registry.register(Arc::new(FixedBackend("a", "x+1")));
+        registry.register(Arc::new(FixedBackend("b", "y")));
+
+        let samples: [(&[u8], &str); 2] = [(b"1", "x+1"), (b"2", "x+1")];
+        let exact = |expected: &str, actual: &str| if expected == actual { 0.0 } else { 1.0 };
+        let results = registry
+            .benchmark(&samples, &OcrOptions::default(), exact)
+            .await;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].backend, "a");
+        assert_eq!(results[0].mean_score, 0.0);
+        assert_eq!(results[1].mean_score, 1.0);
+    }
+}
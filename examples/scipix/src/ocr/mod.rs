@@ -8,6 +8,7 @@
 //!
 //! The OCR module is organized into several submodules:
 //! - `engine`: Main OcrEngine for orchestrating OCR operations
+//! - `backend`: Pluggable OCR backends (local ONNX, remote API) and their registry
 //! - `models`: Model management, loading, and caching
 //! - `inference`: ONNX inference operations for detection and recognition
 //! - `decoder`: Output decoding strategies (beam search, greedy, CTC)
@@ -38,6 +39,7 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 // Submodules
+mod backend;
 mod confidence;
 mod decoder;
 mod engine;
@@ -45,6 +47,7 @@ mod inference;
 mod models;
 
 // Public exports
+pub use backend::{BackendBenchmark, OcrBackend, OcrBackendRegistry, RemoteApiBackend};
 pub use confidence::{aggregate_confidence, calculate_confidence, ConfidenceCalibrator};
 pub use decoder::{BeamSearchDecoder, CTCDecoder, Decoder, GreedyDecoder, Vocabulary};
 pub use engine::{OcrEngine, OcrProcessor};
@@ -77,6 +80,10 @@ pub struct OcrOptions {
 
     /// Language hints for recognition
     pub languages: Vec<String>,
+
+    /// Registered backend to use; the registry default if unset
+    #[serde(default)]
+    pub backend: Option<String>,
 }
 
 impl Default for OcrOptions {
@@ -90,6 +97,7 @@ impl Default for OcrOptions {
             batch_size: 1,
             use_gpu: false,
             languages: vec!["en".to_string()],
+            backend: None,
         }
     }
 }